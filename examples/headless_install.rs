@@ -0,0 +1,27 @@
+// Sets up the pack and starts it from another program, the way a guild launcher would embed the
+// installer instead of running its `install` subcommand:
+//
+//     cargo run --example headless_install -- "Guild Wynncraft" vanilla shaders
+//
+// Arguments are the installation's name, the launcher (as stored in the installer's config) and
+// any optional components to turn on.
+use wynncraft_overhaul_installer::embed::{self, InstallProgress, Installer};
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let name = args.first().map(String::as_str).unwrap_or("Embedded Wynncraft");
+    let launcher = args.get(1).map(String::as_str).unwrap_or("vanilla");
+    let extra = args.get(2..).unwrap_or_default();
+
+    let installer = Installer::connect(None).await?;
+    let features = installer.resolve_features(&installer.default_features(), extra)?;
+    let mut installation = installer.create_installation(name, launcher, features)?;
+    installer
+        .install(&mut installation, |progress: InstallProgress| {
+            println!("{}/{}", progress.done, progress.total);
+        })
+        .await?;
+    println!("Installed '{}' ({}), version {}", installation.name, installation.id, installation.universal_version);
+    embed::launch(&installation)
+}
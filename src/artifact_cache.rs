@@ -0,0 +1,288 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::hashing::{hash_file, HashAlgorithm};
+use crate::StorageSettings;
+
+// On-disk cache of downloaded mods, resource packs and shaders, so installing the same component
+// twice (a second installation, a reinstall) doesn't download it again. By default every OS user
+// has their own cache, on shared PCs a machine-wide location can be configured that all users
+// read from and write to. Config and installations always stay per user.
+
+const ARTIFACTS_DIR: &str = "artifacts";
+const META_FILE: &str = "artifact.json";
+const PROBE_FILE: &str = ".write-test";
+
+#[derive(Debug, Clone, Default)]
+struct CacheLocations {
+    // Where new downloads are stored
+    write: Option<PathBuf>,
+    // Looked up in order, the shared cache first
+    read: Vec<PathBuf>,
+}
+
+// Mirrors config.storage, set at startup and whenever preferences are saved
+static LOCATIONS: RwLock<Option<CacheLocations>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArtifactMeta {
+    filename: String,
+    size: u64,
+    sha256: String,
+}
+
+pub fn default_cache_dir() -> PathBuf {
+    crate::get_installer_dir().join("cache")
+}
+
+fn user_cache_dir(storage: &StorageSettings) -> PathBuf {
+    storage
+        .cache_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(default_cache_dir)
+}
+
+pub fn configure(storage: &StorageSettings) {
+    let user = user_cache_dir(storage).join(ARTIFACTS_DIR);
+    let shared = storage
+        .shared_cache_dir
+        .as_ref()
+        .map(|dir| PathBuf::from(dir).join(ARTIFACTS_DIR));
+
+    let mut locations = CacheLocations::default();
+    if let Some(shared) = shared {
+        // A shared cache we can only read from still saves downloads, ours just go to the user cache
+        match check_writable(&shared) {
+            Ok(_) => locations.write = Some(shared.clone()),
+            Err(e) => warn!("Shared cache {} is read-only for this user: {}", shared.display(), e),
+        }
+        locations.read.push(shared);
+    }
+    if locations.write.is_none() {
+        locations.write = Some(user.clone());
+    }
+    locations.read.push(user);
+    debug!("Artifact cache: {:?}", locations);
+
+    if let Ok(mut current) = LOCATIONS.write() {
+        *current = Some(locations);
+    }
+}
+
+fn locations() -> Option<CacheLocations> {
+    LOCATIONS.read().ok().and_then(|l| l.clone())
+}
+
+/// Identifies a downloadable item, anything that changes the downloaded file has to be part of it.
+pub fn key(source: &str, location: &str, version: &str, loader_type: &str, item_type: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}\n{}\n{}\n{}", source, location, version, loader_type, item_type));
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn entry_dir(root: &Path, key: &str) -> PathBuf {
+    root.join(&key[..2]).join(key)
+}
+
+/// Creates `dir` and checks we can write to it, used for the shared cache and in the preferences.
+pub fn check_writable(dir: &Path) -> Result<(), String> {
+    create_shared_dir(dir)?;
+    let probe = dir.join(PROBE_FILE);
+    fs::write(&probe, b"ok").map_err(|e| format!("Can't write to {}: {}", dir.display(), e))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+// Other users have to be able to add entries to directories we create, on Unix that means group
+// writable with setgid so new files keep the directory's group. Windows inherits the ACL of the
+// parent, pick a location like C:\ProgramData where users can create files.
+fn create_shared_dir(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // Only possible on directories we own, someone else's are left as they are
+        let _ = fs::set_permissions(dir, fs::Permissions::from_mode(0o2775));
+    }
+    Ok(())
+}
+
+fn make_shared_file(path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o664));
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+fn read_meta(entry: &Path) -> Option<ArtifactMeta> {
+    let content = fs::read_to_string(entry.join(META_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Whether `key` can be restored, without verifying the checksum like `restore` does.
+pub fn contains(key: &str) -> bool {
+    let Some(locations) = locations() else { return false };
+    locations.read.iter().any(|root| {
+        let entry = entry_dir(root, key);
+        read_meta(&entry).map_or(false, |meta| {
+            entry.join(&meta.filename).metadata().map_or(false, |m| m.len() == meta.size)
+        })
+    })
+}
+
+/// Copies a cached artifact into `dest_dir`, returns the path it was written to.
+pub fn restore(key: &str, dest_dir: &Path) -> Option<PathBuf> {
+    for root in locations()?.read {
+        let entry = entry_dir(&root, key);
+        let Some(meta) = read_meta(&entry) else { continue };
+        let cached = entry.join(&meta.filename);
+        // Somebody else's half-deleted or corrupted entry, just download again
+        match hash_file(&cached, HashAlgorithm::Sha256) {
+            Ok(hash) if hash == meta.sha256 => {}
+            Ok(_) => {
+                warn!("Cached {} doesn't match its checksum, ignoring it", cached.display());
+                continue;
+            }
+            Err(_) => continue,
+        }
+        if let Err(e) = fs::create_dir_all(dest_dir) {
+            warn!("Failed to create {}: {}", dest_dir.display(), e);
+            return None;
+        }
+        let dest = dest_dir.join(&meta.filename);
+        // Copying onto a file writes into it, which would change every installation it's
+        // hardlinked into through the content store
+        let _ = fs::remove_file(&dest);
+        match fs::copy(&cached, &dest) {
+            Ok(_) => {
+                debug!("Restored {} from the cache at {}", meta.filename, root.display());
+                return Some(dest);
+            }
+            Err(e) => warn!("Failed to copy {} from the cache: {}", cached.display(), e),
+        }
+    }
+    None
+}
+
+/// Adds a freshly downloaded file to the cache. Failures only cost a future download.
+pub fn store(key: &str, downloaded: &Path) {
+    let Some(root) = locations().and_then(|l| l.write) else { return };
+    if let Err(e) = try_store(&root, key, downloaded) {
+        warn!("Failed to cache {}: {}", downloaded.display(), e);
+    }
+}
+
+fn try_store(root: &Path, key: &str, downloaded: &Path) -> Result<(), String> {
+    let entry = entry_dir(root, key);
+    if read_meta(&entry).is_some() {
+        return Ok(());
+    }
+    let filename = downloaded
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("{} has no file name", downloaded.display()))?
+        .to_string();
+
+    if let Some(parent) = entry.parent() {
+        create_shared_dir(parent)?;
+    }
+    create_shared_dir(&entry)?;
+    let target = entry.join(&filename);
+    // Copy then rename so other users never see a partial file
+    let tmp = entry.join(format!("{}.tmp-{}", filename, std::process::id()));
+    fs::copy(downloaded, &tmp).map_err(|e| format!("Failed to copy into the cache: {}", e))?;
+    fs::rename(&tmp, &target).map_err(|e| format!("Failed to finalize cache entry: {}", e))?;
+    make_shared_file(&target);
+
+    let meta = ArtifactMeta {
+        size: target.metadata().map(|m| m.len()).unwrap_or(0),
+        sha256: hash_file(&target, HashAlgorithm::Sha256)?,
+        filename,
+    };
+    // The metadata is what marks an entry as complete, so it's written last
+    let meta_path = entry.join(META_FILE);
+    let json = serde_json::to_string(&meta).map_err(|e| format!("Failed to serialize cache entry: {}", e))?;
+    fs::write(&meta_path, json).map_err(|e| format!("Failed to write cache entry: {}", e))?;
+    make_shared_file(&meta_path);
+    Ok(())
+}
+
+/// Drops `key` from the per-user cache, returns the bytes freed. The shared cache is left alone.
+pub fn evict(key: &str) -> u64 {
+    // The per-user cache is always looked up last
+    let Some(root) = locations().and_then(|l| l.read.last().cloned()) else { return 0 };
+    let entry = entry_dir(&root, key);
+    let Some(meta) = read_meta(&entry) else { return 0 };
+    match fs::remove_dir_all(&entry) {
+        Ok(_) => {
+            debug!("Evicted {} from the download cache", meta.filename);
+            meta.size
+        }
+        Err(e) => {
+            warn!("Failed to remove cache entry {}: {}", entry.display(), e);
+            0
+        }
+    }
+}
+
+/// Removes the least recently added entries of the per-user cache until it fits the size limit.
+/// The shared cache is never pruned here, other users may rely on it.
+pub fn prune_user_cache(storage: &StorageSettings) -> Result<u64, String> {
+    let root = user_cache_dir(storage).join(ARTIFACTS_DIR);
+    let limit = storage.cache_limit_mb * 1024 * 1024;
+    if limit == 0 || !root.exists() {
+        return Ok(0);
+    }
+
+    let mut entries: Vec<(PathBuf, u64, std::time::SystemTime)> = Vec::new();
+    for meta_path in crate::hashing::walk_files(&root)?
+        .into_iter()
+        .filter(|p| p.file_name().map_or(false, |n| n == META_FILE))
+    {
+        let Some(entry) = meta_path.parent().map(Path::to_path_buf) else { continue };
+        let Some(meta) = read_meta(&entry) else { continue };
+        let added = meta_path.metadata().and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH);
+        entries.push((entry, meta.size, added));
+    }
+    let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    if total <= limit {
+        return Ok(0);
+    }
+
+    entries.sort_by_key(|(_, _, added)| *added);
+    let mut freed = 0;
+    for (entry, size, _) in entries {
+        if total <= limit {
+            break;
+        }
+        match fs::remove_dir_all(&entry) {
+            Ok(_) => {
+                total -= size;
+                freed += size;
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove cache entry {}: {}", entry.display(), e),
+        }
+    }
+    info!("Pruned {} bytes from the download cache", freed);
+    Ok(freed)
+}
+
+/// Applies the size limit on a thread of its own, at startup and when the storage settings change.
+pub fn prune_user_cache_in_background(storage: &StorageSettings) {
+    let storage = storage.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = prune_user_cache(&storage) {
+            warn!("Failed to prune the download cache: {}", e);
+        }
+    });
+}
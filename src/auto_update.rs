@@ -0,0 +1,83 @@
+use log::{debug, info, warn};
+
+use crate::batch_update::{self, BatchResult, Outcome};
+use crate::installation::{load_all_installations, Installation};
+use crate::CachedHttpClient;
+
+// "Keep up to date automatically" on an installation. When an update check finds a new pack
+// version for one that opted in, it's installed right away in the background, the same way
+// "Update all" does it, and a notification says how it went. Checks happen at startup and, in tray
+// mode, on the tray's schedule. The update sets the protected config (Wynntils) aside and puts it
+// back like any other, what it kept is shown the next time the installation is opened. Preset
+// updates still ask, they can change the feature selection.
+
+// Not while the game is running off those files, not when the user picked a time for it, and
+// not behind the PIN lock, which is there to stop installations changing unasked
+fn ready(installation: &Installation) -> bool {
+    if !installation.installed || !installation.auto_update || !installation.update_available {
+        return false;
+    }
+    if crate::lock::blocks_background_changes() {
+        info!("Skipping the automatic update of {}, installations are locked", installation.name);
+        return false;
+    }
+    if installation.scheduled_update.is_some() {
+        debug!("Skipping the automatic update of {}, it's scheduled", installation.id);
+        return false;
+    }
+    if crate::launcher::is_running(&installation.id) {
+        info!("Automatic update of {} waits until the game is closed", installation.name);
+        return false;
+    }
+    true
+}
+
+/// Updates the installations of `with_update` that opted in. Returns one result per update that ran.
+pub async fn run(with_update: &[Installation]) -> Vec<BatchResult> {
+    let due: Vec<Installation> = with_update.iter().filter(|i| ready(i)).cloned().collect();
+    if due.is_empty() {
+        return Vec::new();
+    }
+    info!("Updating {} installation(s) automatically", due.len());
+    let results = batch_update::update_all(&due, |_| {}).await;
+    for result in &results {
+        match &result.outcome {
+            Outcome::Updated { from, to } => crate::watch_mode::notify(
+                &format!("{} was updated", result.name),
+                &format!("Updated automatically from {} to {}", from, to),
+            ),
+            Outcome::Failed(e) => crate::watch_mode::notify(
+                &format!("Automatic update of {} failed", result.name),
+                &format!("{}. Open the installer to update it by hand.", e),
+            ),
+        }
+    }
+    results
+}
+
+/// Checks the installations that opted in for updates and runs them, used at startup.
+pub async fn check_and_run(http_client: &CachedHttpClient) -> Vec<BatchResult> {
+    let installations = match load_all_installations() {
+        Ok(installations) => installations,
+        Err(e) => {
+            warn!("Couldn't load the installations for automatic updates: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut with_update = Vec::new();
+    for mut installation in installations.into_iter().filter(|i| i.installed && i.auto_update) {
+        let presets = match installation.load_presets(http_client).await {
+            Ok(presets) => presets,
+            Err(e) => {
+                warn!("Update check of {} failed: {}", installation.name, e);
+                continue;
+            }
+        };
+        match installation.check_for_updates(http_client, &presets).await {
+            Ok(true) => with_update.push(installation),
+            Ok(false) => {}
+            Err(e) => warn!("Update check of {} failed: {}", installation.name, e),
+        }
+    }
+    run(&with_update).await
+}
@@ -6,6 +6,7 @@ use chrono::{DateTime, Utc};
 use log::{debug, error, info, warn};
 use zip::{ZipWriter, CompressionMethod};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 /// Enhanced backup item discovery with better file/folder scanning
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -42,6 +43,26 @@ pub struct BackupProgress {
     pub current_operation: String, // e.g., "Scanning files", "Creating archive"
 }
 
+// Progress of the automatic backup an update is making right now, the install screen polls it
+static AUTO_BACKUP_PROGRESS: Mutex<Option<BackupProgress>> = Mutex::new(None);
+
+pub fn report_auto_backup_progress(progress: BackupProgress) {
+    if let Ok(mut current) = AUTO_BACKUP_PROGRESS.lock() {
+        *current = Some(progress);
+    }
+}
+
+pub fn clear_auto_backup_progress() {
+    if let Ok(mut current) = AUTO_BACKUP_PROGRESS.lock() {
+        *current = None;
+    }
+}
+
+/// Progress of the running automatic backup, None when no update is backing up.
+pub fn auto_backup_progress() -> Option<BackupProgress> {
+    AUTO_BACKUP_PROGRESS.lock().ok()?.clone()
+}
+
 /// Configuration for what to include in backups
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BackupConfig {
@@ -50,6 +71,24 @@ pub struct BackupConfig {
     pub max_backups: usize,
     pub include_hidden_files: bool,
     pub exclude_patterns: Vec<String>, // Glob patterns to exclude
+    // Backs up auto_backup_items before every update, only the newest auto_backup_retention are kept
+    #[serde(default)]
+    pub auto_backup: bool,
+    #[serde(default = "default_auto_backup_items")]
+    pub auto_backup_items: Vec<String>,
+    #[serde(default = "default_auto_backup_retention")]
+    pub auto_backup_retention: usize,
+    // Set aside before every update and put back afterwards, see config_protection.rs
+    #[serde(default = "crate::config_protection::default_paths")]
+    pub protected_paths: Vec<String>,
+}
+
+fn default_auto_backup_items() -> Vec<String> {
+    vec!["config".to_string(), "wynntils".to_string(), "options.txt".to_string()]
+}
+
+fn default_auto_backup_retention() -> usize {
+    5
 }
 
 impl Default for BackupConfig {
@@ -71,6 +110,10 @@ impl Default for BackupConfig {
                 "temp".to_string(),
                 "cache".to_string(),
             ],
+            auto_backup: false,
+            auto_backup_items: default_auto_backup_items(),
+            auto_backup_retention: default_auto_backup_retention(),
+            protected_paths: crate::config_protection::default_paths(),
         }
     }
 }
@@ -82,8 +125,18 @@ pub enum BackupType {
     PreUpdate,
     PreInstall,
     Scheduled,
+    // Made right before a restore overwrites files. Has its own retention, pruned once the
+    // restore is done so it can never remove the backup being restored
+    Safety,
+    // A single world from the worlds section, kept per world so they don't push out full backups
+    World,
 }
 
+/// How many safety backups an installation keeps
+pub const SAFETY_BACKUPS_KEPT: usize = 15;
+/// How many backups of each world an installation keeps
+pub const WORLD_BACKUPS_KEPT: usize = 10;
+
 /// Metadata about a backup
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BackupMetadata {
@@ -97,6 +150,9 @@ pub struct BackupMetadata {
     pub size_bytes: u64,
     pub included_items: Vec<String>, // List of backed up items
     pub config: BackupConfig,
+    // Size of the new deduplicated chunks this backup added, None for zip/plain backups
+    #[serde(default)]
+    pub stored_bytes: Option<u64>,
 }
 
 impl FileSystemItem {
@@ -210,7 +266,7 @@ impl FileSystemItem {
         ];
         
         skip_patterns.iter().any(|pattern| {
-            name == *pattern || name.starts_with("tmp_") || name.ends_with(".tmp")
+            name == *pattern || name.starts_with("tmp_") || name.starts_with(".tmp_include_") || name.ends_with(".tmp")
         })
     }
     
@@ -286,7 +342,19 @@ impl BackupMetadata {
     }
     
     pub fn formatted_size(&self) -> String {
-        format_bytes(self.size_bytes)
+        match self.stored_bytes {
+            // Deduplicated backups share most of their data, show what this one actually added
+            Some(stored) => format!("{} ({} new)", format_bytes(self.size_bytes), format_bytes(stored)),
+            None => format_bytes(self.size_bytes),
+        }
+    }
+    
+    /// The world folder when this backup holds exactly one world
+    pub fn world(&self) -> Option<&str> {
+        match self.included_items.as_slice() {
+            [item] => item.strip_prefix("saves/").filter(|folder| !folder.is_empty() && !folder.contains('/')),
+            _ => None,
+        }
     }
 }
 
@@ -338,7 +406,7 @@ impl RollbackManager {
         let safety_description = format!("Safety backup before rollback to {}", backup_id);
         
         self.installation.create_backup(
-            BackupType::PreUpdate,
+            BackupType::Safety,
             &safety_config,
             safety_description,
             None::<fn(BackupProgress)>,
@@ -482,26 +550,11 @@ where
 }
 
 /// Extract a ZIP archive to a directory
-pub fn extract_zip_archive(zip_path: &std::path::Path, destination: &std::path::Path) -> Result<(), io::Error> {
-    let file = fs::File::open(zip_path)?;
-    let mut archive = zip::ZipArchive::new(file)?;
-    
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)?;
-        let outpath = destination.join(file.name());
-        
-        if file.name().ends_with('/') {
-            fs::create_dir_all(&outpath)?;
-        } else {
-            if let Some(parent) = outpath.parent() {
-                fs::create_dir_all(parent)?;
-            }
-            
-            let mut outfile = fs::File::create(&outpath)?;
-            io::copy(&mut file, &mut outfile)?;
-        }
-    }
-    
+pub fn extract_zip_archive(zip_path: &std::path::Path, destination: &std::path::Path) -> Result<(), String> {
+    let file = fs::File::open(zip_path).map_err(|e| format!("Failed to open {}: {}", zip_path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Failed to read {}: {}", zip_path.display(), e))?;
+    // Backups can be imported from another machine, the archive isn't trusted more than a download
+    crate::safe_extract::extract_all(&mut archive, destination, |relative| Some(relative.to_path_buf()), |_, _| {})?;
     Ok(())
 }
 
@@ -744,7 +797,7 @@ fn should_skip_item(name: &str) -> bool {
         ".DS_Store", "Thumbs.db", "desktop.ini",
     ];
     
-    skip_items.contains(&name) || name.starts_with("tmp_") || name.ends_with(".tmp")
+    skip_items.contains(&name) || name.starts_with("tmp_") || name.starts_with(".tmp_include_") || name.ends_with(".tmp")
 }
 
 fn is_allowed_hidden_item(name: &str) -> bool {
@@ -836,3 +889,231 @@ pub fn count_files_recursive(path: &Path) -> Result<usize, io::Error> {
     
     Ok(count)
 }
+
+/// A single file stored inside a backup, used by the backup browser
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupFileEntry {
+    pub path: String, // Relative to the installation root, forward slashes
+    pub size: u64,
+}
+
+impl BackupFileEntry {
+    /// True if this entry is `selection` itself or lives inside the `selection` folder
+    pub fn is_within(&self, selection: &str) -> bool {
+        let selection = selection.trim_end_matches('/');
+        self.path == selection || self.path.starts_with(&format!("{}/", selection))
+    }
+}
+
+// Files in a plain (uncompressed) backup folder that aren't part of the installation
+fn is_backup_bookkeeping(path: &str) -> bool {
+    path == "metadata.json" || path == "backup.zip" || path == crate::backup_store::SNAPSHOT_FILE
+}
+
+/// Lists every file in a backup regardless of how it was stored (chunked, zip or plain folder)
+pub fn list_backup_files(backup_dir: &Path) -> Result<Vec<BackupFileEntry>, String> {
+    let mut entries = if let Some(snapshot) = crate::backup_store::load_snapshot(backup_dir)? {
+        snapshot.files.into_iter()
+            .map(|file| BackupFileEntry { path: file.path, size: file.size })
+            .collect::<Vec<_>>()
+    } else if backup_dir.join("backup.zip").exists() {
+        let file = fs::File::open(backup_dir.join("backup.zip"))
+            .map_err(|e| format!("Failed to open backup archive: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Failed to read backup archive: {}", e))?;
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            let file = archive.by_index(i)
+                .map_err(|e| format!("Failed to read backup archive: {}", e))?;
+            if !file.is_dir() && !is_backup_bookkeeping(file.name()) {
+                entries.push(BackupFileEntry { path: file.name().to_string(), size: file.size() });
+            }
+        }
+        entries
+    } else {
+        crate::hashing::walk_files(backup_dir)?
+            .into_iter()
+            .filter_map(|path| {
+                let relative = path.strip_prefix(backup_dir).ok()?;
+                let relative = relative.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if is_backup_bookkeeping(&relative) {
+                    return None;
+                }
+                let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+                Some(BackupFileEntry { path: relative, size })
+            })
+            .collect()
+    };
+    
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Reads the start of a single file from a backup for previewing
+pub fn read_backup_file(backup_dir: &Path, chunks_dir: &Path, path: &str, limit: usize) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+    
+    if let Some(snapshot) = crate::backup_store::load_snapshot(backup_dir)? {
+        let file = snapshot.files.iter()
+            .find(|file| file.path == path)
+            .ok_or_else(|| format!("'{}' is not in this backup", path))?;
+        return crate::backup_store::read_file(chunks_dir, file, limit);
+    }
+    
+    let mut data = Vec::new();
+    if backup_dir.join("backup.zip").exists() {
+        let file = fs::File::open(backup_dir.join("backup.zip"))
+            .map_err(|e| format!("Failed to open backup archive: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Failed to read backup archive: {}", e))?;
+        let entry = archive.by_name(path)
+            .map_err(|e| format!("'{}' is not in this backup: {}", path, e))?;
+        entry.take(limit as u64).read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    } else {
+        let file = fs::File::open(backup_dir.join(path))
+            .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+        file.take(limit as u64).read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    }
+    Ok(data)
+}
+
+/// Restores only the selected files/folders of a backup into `destination`. Returns the number of files written.
+pub fn restore_backup_paths(
+    backup_dir: &Path,
+    chunks_dir: &Path,
+    selections: &[String],
+    destination: &Path,
+) -> Result<usize, String> {
+    let selected = |path: &str| {
+        let entry = BackupFileEntry { path: path.to_string(), size: 0 };
+        selections.iter().any(|selection| entry.is_within(selection))
+    };
+    let mut restored = 0;
+    
+    if let Some(snapshot) = crate::backup_store::load_snapshot(backup_dir)? {
+        for file in snapshot.files.iter().filter(|file| selected(&file.path)) {
+            crate::backup_store::restore_file(chunks_dir, file, destination)?;
+            restored += 1;
+        }
+    } else if backup_dir.join("backup.zip").exists() {
+        let file = fs::File::open(backup_dir.join("backup.zip"))
+            .map_err(|e| format!("Failed to open backup archive: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Failed to read backup archive: {}", e))?;
+        restored += crate::safe_extract::extract_all(
+            &mut archive,
+            destination,
+            |relative| selected(&relative.to_string_lossy().replace('\\', "/")).then(|| relative.to_path_buf()),
+            |_, _| {},
+        )?
+        .len();
+    } else {
+        for entry in list_backup_files(backup_dir)?.iter().filter(|entry| selected(&entry.path)) {
+            let target = destination.join(&entry.path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+            crate::content_store::detach(&target).map_err(|e| format!("Failed to replace {}: {}", entry.path, e))?;
+            fs::copy(backup_dir.join(&entry.path), &target)
+                .map_err(|e| format!("Failed to restore {}: {}", entry.path, e))?;
+            restored += 1;
+        }
+    }
+    
+    debug!("Restored {} files from backup {:?}", restored, backup_dir);
+    Ok(restored)
+}
+
+/// A singleplayer world in the installation's saves folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldInfo {
+    pub folder: String,
+    pub size_bytes: u64,
+    pub last_played: Option<DateTime<Utc>>,
+}
+
+impl WorldInfo {
+    /// The world's path relative to the installation root, what its backups select
+    pub fn backup_path(&self) -> String {
+        world_backup_path(&self.folder)
+    }
+}
+
+pub fn world_backup_path(folder: &str) -> String {
+    format!("saves/{}", folder)
+}
+
+/// Worlds in `installation_path`/saves, most recently played first
+pub fn list_worlds(installation_path: &Path) -> Result<Vec<WorldInfo>, String> {
+    let saves_dir = installation_path.join("saves");
+    let entries = match fs::read_dir(&saves_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read saves folder: {}", e)),
+    };
+    
+    let mut worlds = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Every world has a level.dat, anything else in saves/ isn't one
+        let level_dat = path.join("level.dat");
+        if !path.is_dir() || !level_dat.exists() {
+            continue;
+        }
+        // The game rewrites level.dat on every save, reading LastPlayed would need an NBT parser
+        let last_played = level_dat.metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        let size_bytes = calculate_directory_size(&path).unwrap_or_else(|e| {
+            warn!("Failed to size world {:?}: {}", path, e);
+            0
+        });
+        worlds.push(WorldInfo {
+            folder: entry.file_name().to_string_lossy().to_string(),
+            size_bytes,
+            last_played,
+        });
+    }
+    
+    worlds.sort_by(|a, b| b.last_played.cmp(&a.last_played).then_with(|| a.folder.cmp(&b.folder)));
+    debug!("Found {} worlds in {:?}", worlds.len(), saves_dir);
+    Ok(worlds)
+}
+
+/// Zips a world with its folder at the top, the way world downloads are shared
+pub fn export_world_zip(world_dir: &Path, zip_path: &Path) -> Result<u64, String> {
+    let folder = world_dir.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("{:?} is not a world folder", world_dir))?;
+    let file = fs::File::create(zip_path)
+        .map_err(|e| format!("Failed to create zip file: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    
+    let total_files = count_files_recursive(world_dir)
+        .map_err(|e| format!("Failed to count files: {}", e))?;
+    let mut files_processed = 0;
+    let mut bytes_processed = 0;
+    add_directory_to_zip(
+        &mut zip,
+        world_dir,
+        &folder,
+        &mut files_processed,
+        total_files,
+        &mut bytes_processed,
+        &None::<&fn(BackupProgress)>,
+    ).map_err(|e| format!("Failed to add world to zip: {}", e))?;
+    
+    let archive = zip.finish()
+        .map_err(|e| format!("Failed to finish zip archive: {}", e))?;
+    let size = archive.metadata()
+        .map_err(|e| format!("Failed to get archive metadata: {}", e))?
+        .len();
+    info!("Exported world {} to {:?} ({} files)", folder, zip_path, files_processed);
+    Ok(size)
+}
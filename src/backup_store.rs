@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufReader};
+use std::path::{Component, Path, PathBuf};
+
+use fastcdc::v2020::StreamCDC;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::backup::BackupProgress;
+
+// Deduplicated backup storage. Files are split with content-defined chunking so a mod update
+// or a changed config only stores the chunks that actually changed. Chunks are zstd-compressed
+// and shared by every snapshot of an installation, each snapshot only keeps a list of chunk ids.
+
+pub const SNAPSHOT_FILE: &str = "snapshot.json";
+const CHUNKS_DIR: &str = "chunks";
+
+const MIN_CHUNK_SIZE: u32 = 64 * 1024;
+const AVG_CHUNK_SIZE: u32 = 256 * 1024;
+const MAX_CHUNK_SIZE: u32 = 1024 * 1024;
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotFile {
+    // Relative to the installation root, always with forward slashes
+    pub path: String,
+    pub size: u64,
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Snapshot {
+    pub files: Vec<SnapshotFile>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotStats {
+    pub files: usize,
+    pub bytes: u64,
+    // Compressed size of the chunks this snapshot added to the store
+    pub stored_bytes: u64,
+}
+
+pub fn chunks_dir(backups_dir: &Path) -> PathBuf {
+    backups_dir.join(CHUNKS_DIR)
+}
+
+fn chunk_path(chunks_dir: &Path, id: &str) -> PathBuf {
+    chunks_dir.join(&id[..2]).join(format!("{}.zst", id))
+}
+
+fn to_snapshot_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Snapshot paths come from disk and could have been tampered with, never write outside the target
+fn safe_relative_path(path: &str) -> Result<PathBuf, String> {
+    let relative = PathBuf::from(path);
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("Refusing to restore unsafe path '{}'", path));
+    }
+    Ok(relative)
+}
+
+/// Stores a chunk unless it already exists, returns its id and the bytes written.
+fn store_chunk(chunks_dir: &Path, data: &[u8]) -> Result<(String, u64), String> {
+    let id: String = Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect();
+    let path = chunk_path(chunks_dir, &id);
+    if path.exists() {
+        return Ok((id, 0));
+    }
+
+    let compressed = zstd::encode_all(data, ZSTD_LEVEL)
+        .map_err(|e| format!("Failed to compress chunk: {}", e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create chunk directory: {}", e))?;
+    }
+    // Write then rename so an interrupted backup never leaves a truncated chunk behind
+    let tmp_path = path.with_extension("zst.tmp");
+    fs::write(&tmp_path, &compressed).map_err(|e| format!("Failed to write chunk: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to finalize chunk: {}", e))?;
+    Ok((id, compressed.len() as u64))
+}
+
+fn store_file(chunks_dir: &Path, source: &Path, stats: &mut SnapshotStats) -> Result<(u64, Vec<String>), String> {
+    let file = fs::File::open(source).map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+    let mut size = 0;
+    let mut chunks = Vec::new();
+    for chunk in StreamCDC::new(BufReader::new(file), MIN_CHUNK_SIZE, AVG_CHUNK_SIZE, MAX_CHUNK_SIZE) {
+        let chunk = chunk.map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        let (id, written) = store_chunk(chunks_dir, &chunk.data)?;
+        size += chunk.length as u64;
+        stats.stored_bytes += written;
+        chunks.push(id);
+    }
+    Ok((size, chunks))
+}
+
+/// Writes a snapshot of `items` (source path, path relative to the installation) into `backup_dir`.
+pub fn write_snapshot<F>(
+    items: &[(PathBuf, PathBuf)],
+    backup_dir: &Path,
+    chunks_dir: &Path,
+    total_files: usize,
+    total_bytes: u64,
+    progress_callback: Option<&F>,
+) -> Result<SnapshotStats, String>
+where
+    F: Fn(BackupProgress),
+{
+    let mut snapshot = Snapshot::default();
+    let mut stats = SnapshotStats::default();
+
+    for (source, relative) in items {
+        let files = if source.is_dir() {
+            crate::hashing::walk_files(source)?
+                .into_iter()
+                .map(|path| {
+                    let inner = path.strip_prefix(source).map(Path::to_path_buf).unwrap_or_default();
+                    (path, relative.join(inner))
+                })
+                .collect()
+        } else {
+            vec![(source.clone(), relative.clone())]
+        };
+
+        for (path, relative_path) in files {
+            let (size, chunks) = store_file(chunks_dir, &path, &mut stats)?;
+            stats.files += 1;
+            stats.bytes += size;
+            snapshot.files.push(SnapshotFile {
+                path: to_snapshot_path(&relative_path),
+                size,
+                chunks,
+            });
+
+            if let Some(callback) = progress_callback {
+                callback(BackupProgress {
+                    current_file: relative_path.to_string_lossy().to_string(),
+                    files_processed: stats.files,
+                    total_files,
+                    bytes_processed: stats.bytes,
+                    total_bytes,
+                    current_operation: "Compressing files".to_string(),
+                });
+            }
+        }
+    }
+
+    let json = serde_json::to_string(&snapshot).map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    fs::write(backup_dir.join(SNAPSHOT_FILE), json).map_err(|e| format!("Failed to write snapshot: {}", e))?;
+
+    info!(
+        "Snapshot stored {} files ({} bytes), {} bytes of new chunk data",
+        stats.files, stats.bytes, stats.stored_bytes
+    );
+    Ok(stats)
+}
+
+pub fn load_snapshot(backup_dir: &Path) -> Result<Option<Snapshot>, String> {
+    let path = backup_dir.join(SNAPSHOT_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read snapshot: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+/// Reassembles a single file from its chunks at `dest_root/<file.path>`.
+pub fn restore_file(chunks_dir: &Path, file: &SnapshotFile, dest_root: &Path) -> Result<(), String> {
+    let dest = dest_root.join(safe_relative_path(&file.path)?);
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    crate::content_store::detach(&dest).map_err(|e| format!("Failed to replace {}: {}", dest.display(), e))?;
+    let mut out = fs::File::create(&dest).map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    for id in &file.chunks {
+        let chunk = fs::File::open(chunk_path(chunks_dir, id))
+            .map_err(|e| format!("Backup chunk {} for '{}' is missing: {}", id, file.path, e))?;
+        zstd::stream::copy_decode(chunk, &mut out)
+            .map_err(|e| format!("Failed to decompress chunk {} for '{}': {}", id, file.path, e))?;
+    }
+    Ok(())
+}
+
+/// Reads up to `limit` bytes of a file without restoring it, used for previews.
+pub fn read_file(chunks_dir: &Path, file: &SnapshotFile, limit: usize) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    for id in &file.chunks {
+        if data.len() >= limit {
+            break;
+        }
+        let chunk = fs::File::open(chunk_path(chunks_dir, id))
+            .map_err(|e| format!("Backup chunk {} for '{}' is missing: {}", id, file.path, e))?;
+        let decoded = zstd::decode_all(chunk)
+            .map_err(|e| format!("Failed to decompress chunk {} for '{}': {}", id, file.path, e))?;
+        data.extend_from_slice(&decoded);
+    }
+    data.truncate(limit);
+    Ok(data)
+}
+
+pub fn restore_snapshot(snapshot: &Snapshot, chunks_dir: &Path, dest_root: &Path) -> Result<usize, String> {
+    for file in &snapshot.files {
+        restore_file(chunks_dir, file, dest_root)?;
+    }
+    debug!("Restored {} files from snapshot", snapshot.files.len());
+    Ok(snapshot.files.len())
+}
+
+/// Removes chunks that no snapshot in `backups_dir` references anymore. Returns the bytes freed.
+pub fn collect_garbage(backups_dir: &Path) -> Result<u64, String> {
+    let chunks_dir = chunks_dir(backups_dir);
+    if !chunks_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut referenced = HashSet::new();
+    let entries = fs::read_dir(backups_dir).map_err(|e| format!("Failed to read backups directory: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || path == chunks_dir {
+            continue;
+        }
+        match load_snapshot(&path) {
+            Ok(Some(snapshot)) => referenced.extend(snapshot.files.into_iter().flat_map(|f| f.chunks)),
+            Ok(None) => {}
+            // Keep everything if we can't tell what a snapshot needs
+            Err(e) => return Err(format!("Not collecting backup chunks, {}: {}", path.display(), e)),
+        }
+    }
+
+    let mut freed = 0;
+    for path in crate::hashing::walk_files(&chunks_dir)? {
+        let id = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".zst"))
+            .unwrap_or_default();
+        if referenced.contains(id) {
+            continue;
+        }
+        let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+        match fs::remove_file(&path) {
+            Ok(_) => freed += size,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove unused backup chunk {}: {}", path.display(), e),
+        }
+    }
+
+    if freed > 0 {
+        debug!("Freed {} bytes of unused backup chunks", freed);
+    }
+    Ok(freed)
+}
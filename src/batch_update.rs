@@ -0,0 +1,109 @@
+use log::{error, info};
+
+use crate::installation::{self, Installation};
+use crate::universal::UniversalManifest;
+use crate::CachedHttpClient;
+
+// "Update all" on the home page. A new pack version used to mean opening every installation and
+// pressing UPDATE in each; this runs them one after another, like the scheduled updates do, and
+// keeps going when one fails so the summary can list what happened to each.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchProgress {
+    // Position of the installation being updated, from 0
+    pub index: usize,
+    pub count: usize,
+    pub name: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Updated { from: String, to: String },
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    pub id: String,
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+/// The installations "Update all" covers.
+pub fn pending(installations: &[Installation]) -> Vec<Installation> {
+    installations.iter().filter(|i| i.installed && i.update_available).cloned().collect()
+}
+
+// Components the install reports progress for, the same count the installation page uses
+fn component_count(manifest: &UniversalManifest, features: &[String]) -> usize {
+    let wanted = |id: &str, optional: bool| id.is_empty() || id == "default" || !optional || features.iter().any(|f| f == id);
+    manifest.mods.iter().filter(|m| wanted(&m.id, m.optional)).count()
+        + manifest.shaderpacks.iter().filter(|s| wanted(&s.id, s.optional)).count()
+        + manifest.resourcepacks.iter().filter(|r| wanted(&r.id, r.optional)).count()
+        + manifest.include.iter().filter(|i| wanted(&i.id, i.optional)).count()
+        + manifest.remote_include.iter().filter(|r| wanted(&r.id, r.optional)).count()
+}
+
+async fn update_one<F: FnMut(usize, usize) + Clone>(
+    http_client: &CachedHttpClient,
+    installation_id: &str,
+    mut on_progress: F,
+) -> Result<(String, String), String> {
+    let mut installation = installation::load_installation(installation_id)?;
+    let from = installation.universal_version.clone();
+    let manifest = installation
+        .load_universal_manifest(http_client)
+        .await
+        .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
+    if let Some(features) = crate::feature_profiles::take_update_profile(&mut installation) {
+        installation.apply_feature_profile(features);
+    }
+
+    let total = component_count(&manifest, &installation.enabled_features);
+    let mut done = 0;
+    let progress_callback = {
+        let mut on_progress = on_progress.clone();
+        move || {
+            done += 1;
+            on_progress(done, total);
+        }
+    };
+    on_progress(0, total);
+    installation
+        .install_or_update_with_manifest(http_client, &manifest, &installation.pack_source().branch(), progress_callback)
+        .await?;
+    installation.universal_version = manifest.modpack_version.clone();
+    installation.preset_update_available = false;
+    installation.mark_installed()?;
+    Ok((from, manifest.modpack_version))
+}
+
+/// Updates `installations` in order, a failed one doesn't stop the rest. Returns one result per installation.
+pub async fn update_all<F: FnMut(BatchProgress) + Clone>(installations: &[Installation], on_progress: F) -> Vec<BatchResult> {
+    let http_client = CachedHttpClient::new();
+    let count = installations.len();
+    let mut results = Vec::new();
+    for (index, installation) in installations.iter().enumerate() {
+        info!("Update all: {} of {}, '{}'", index + 1, count, installation.name);
+        let progress = {
+            let mut on_progress = on_progress.clone();
+            let name = installation.name.clone();
+            move |done, total| on_progress(BatchProgress { index, count, name: name.clone(), done, total })
+        };
+        let outcome = match update_one(&http_client, &installation.id, progress).await {
+            Ok((from, to)) => Outcome::Updated { from, to },
+            Err(e) => {
+                error!("Update all: '{}' failed: {}", installation.name, e);
+                Outcome::Failed(e)
+            }
+        };
+        results.push(BatchResult {
+            id: installation.id.clone(),
+            name: installation.name.clone(),
+            outcome,
+        });
+    }
+    results
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use log::{debug, warn};
+
+use crate::CURRENT_MANIFEST_VERSION;
+
+// Capabilities this build of the installer knows how to handle. Manifests can
+// declare what they need instead of relying on lockstep manifest_version bumps.
+pub const SUPPORTED_CAPABILITIES: &[&str] = &[
+    "modrinth-source",
+    "ddl-source",
+    "mediafire-source",
+    "curseforge-source",
+    "fabric-loader",
+    "quilt-loader",
+    "forge-loader",
+    "neoforge-loader",
+    "remote-includes",
+    "optional-includes",
+    "include-reset",
+    "component-dependencies",
+    "component-conflicts",
+    "ignore-update",
+    "config-presets",
+    // Manifest sections added after capabilities, older installers skip them without a word
+    "wynncraft-version",
+    "profile-icons",
+    "shader-tiers",
+    "minecraft-versions",
+    "config-overrides",
+    "servers",
+    "config-patches",
+    "resourcepack-order",
+    "news-feed",
+    "page-theme",
+    "mirrors",
+];
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct ManifestCapabilities {
+    // The installer refuses to continue if any of these are unknown
+    #[serde(default)]
+    pub required: Vec<String>,
+    // Unknown optional capabilities only produce a warning
+    #[serde(default)]
+    pub optional: Vec<String>,
+}
+
+pub fn is_supported(capability: &str) -> bool {
+    SUPPORTED_CAPABILITIES.contains(&capability)
+}
+
+/// Checks whether a manifest can be handled by this installer.
+///
+/// Manifests that declare capabilities are accepted regardless of their
+/// manifest_version as long as every required capability is supported.
+/// Manifests without a capabilities block fall back to the old exact version check.
+/// Returns the list of unsupported optional capabilities on success.
+pub fn check_manifest_capabilities(
+    manifest_version: i32,
+    capabilities: Option<&ManifestCapabilities>,
+) -> Result<Vec<String>, String> {
+    let capabilities = match capabilities {
+        Some(caps) => caps,
+        None => {
+            if manifest_version != CURRENT_MANIFEST_VERSION {
+                return Err(format!(
+                    "Unsupported manifest version '{}'!",
+                    manifest_version
+                ));
+            }
+            return Ok(Vec::new());
+        }
+    };
+
+    let missing: Vec<String> = capabilities
+        .required
+        .iter()
+        .filter(|cap| !is_supported(cap))
+        .cloned()
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "This modpack requires features not supported by this installer version: {}. Please update the installer.",
+            missing.join(", ")
+        ));
+    }
+
+    let unknown_optional: Vec<String> = capabilities
+        .optional
+        .iter()
+        .filter(|cap| !is_supported(cap))
+        .cloned()
+        .collect();
+
+    for cap in &unknown_optional {
+        warn!("Manifest uses optional capability '{}' which this installer doesn't support, ignoring it", cap);
+    }
+
+    debug!(
+        "Manifest capabilities accepted (version {}, {} required, {} optional)",
+        manifest_version,
+        capabilities.required.len(),
+        capabilities.optional.len()
+    );
+
+    Ok(unknown_optional)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sections of universal.json that were there before capabilities, every installer reads them
+    const BASE_SECTIONS: &[&str] = &[
+        "manifest_version",
+        "modpack_version",
+        "minecraft_version",
+        "name",
+        "subtitle",
+        "description",
+        "icon",
+        "uuid",
+        "loader",
+        "mods",
+        "shaderpacks",
+        "resourcepacks",
+        "include",
+        "remote_include",
+        "category",
+        "short_description",
+        "version",
+        "max_mem",
+        "min_mem",
+        "java_args",
+        "capabilities",
+    ];
+
+    // Every other section and the capability a manifest declares to rely on it
+    const SECTION_CAPABILITIES: &[(&str, &str)] = &[
+        ("wynncraft_version", "wynncraft-version"),
+        ("icons", "profile-icons"),
+        ("config_presets", "config-presets"),
+        ("shader_tiers", "shader-tiers"),
+        ("minecraft_versions", "minecraft-versions"),
+        ("config_overrides", "config-overrides"),
+        ("servers", "servers"),
+        ("config_patches", "config-patches"),
+        ("resourcepack_order", "resourcepack-order"),
+        ("news_feed", "news-feed"),
+        ("tab_color", "page-theme"),
+        ("tab_background", "page-theme"),
+        // On components, not top level
+        ("mirrors", "mirrors"),
+    ];
+
+    fn manifest_keys() -> Vec<String> {
+        let minimal = serde_json::json!({
+            "manifest_version": CURRENT_MANIFEST_VERSION,
+            "modpack_version": "1.0.0",
+            "minecraft_version": "1.21",
+            "name": "Test",
+            "subtitle": "",
+            "description": "",
+            "icon": false,
+            "uuid": "test",
+            "loader": { "type": "fabric", "version": "0.16.0", "minecraft_version": "1.21" },
+            "mods": [],
+            "version": "1.0.0"
+        });
+        let manifest: crate::universal::UniversalManifest = serde_json::from_value(minimal).unwrap();
+        match serde_json::to_value(manifest).unwrap() {
+            serde_json::Value::Object(map) => map.keys().cloned().collect(),
+            other => panic!("manifest serialized to {}", other),
+        }
+    }
+
+    #[test]
+    fn every_section_has_a_supported_capability() {
+        for key in manifest_keys().iter().filter(|key| !BASE_SECTIONS.contains(&key.as_str())) {
+            let capability = SECTION_CAPABILITIES
+                .iter()
+                .find(|(section, _)| section == key)
+                .map(|(_, capability)| *capability);
+            match capability {
+                Some(capability) => assert!(is_supported(capability), "{} isn't in SUPPORTED_CAPABILITIES", capability),
+                None => panic!("manifest section '{}' has no capability, add one to SUPPORTED_CAPABILITIES", key),
+            }
+        }
+        for (_, capability) in SECTION_CAPABILITIES {
+            assert!(is_supported(capability), "{} isn't in SUPPORTED_CAPABILITIES", capability);
+        }
+    }
+
+    #[test]
+    fn required_section_capabilities_are_accepted() {
+        let capabilities = ManifestCapabilities {
+            required: vec!["config-patches".into(), "servers".into(), "resourcepack-order".into(), "mirrors".into()],
+            optional: Vec::new(),
+        };
+        assert_eq!(check_manifest_capabilities(CURRENT_MANIFEST_VERSION + 1, Some(&capabilities)), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn unknown_required_capability_is_refused() {
+        let capabilities = ManifestCapabilities { required: vec!["teleporters".into()], optional: Vec::new() };
+        assert!(check_manifest_capabilities(CURRENT_MANIFEST_VERSION, Some(&capabilities)).is_err());
+    }
+}
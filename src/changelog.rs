@@ -84,8 +84,14 @@ pub async fn fetch_changelog(
     http_client: &crate::CachedHttpClient
 ) -> Result<Changelog, String> {
     
+    // Developer mode, see dev_manifest.rs
+    if let Some(local) = crate::dev_manifest::read_local("changelog.json") {
+        return crate::manifest_validation::parse::<Changelog>(&local?, "changelog.json")
+            .map_err(|e| crate::manifest_validation::report(&e));
+    }
+
     let base_url = "https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/";
-    let changelog_url = format!("{}changelog.json", base_url);
+    let changelog_url = crate::dev_manifest::resolve_url(&format!("{}changelog.json", base_url));
     
     let mut changelog_resp = match http_client.get_async(changelog_url.clone()).await {
         Ok(val) => val,
@@ -108,14 +114,14 @@ pub async fn fetch_changelog(
         }
     };
     
-    match serde_json::from_str::<Changelog>(&changelog_text) {
+    match crate::manifest_validation::parse::<Changelog>(&changelog_text, "changelog.json") {
         Ok(changelog) => {
             debug!("Successfully parsed changelog with {} entries", changelog.entries.len());
             Ok(changelog)
         },
         Err(e) => {
             warn!("Failed to parse changelog: {}", e);
-            Err(format!("Failed to parse changelog: {}", e))
+            Err(crate::manifest_validation::report(&e))
         }
     }
 }
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::sources::PackSource;
+
+// Release channels. Beta and dev builds of a pack live on branches of its repo named after the
+// channel, stable is the source's own branch (master for the default repo). Only GitHub sources
+// have branches, a pack served from a plain manifest_url is always stable.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    Beta,
+    Dev,
+}
+
+impl ReleaseChannel {
+    pub const ALL: [ReleaseChannel; 3] = [ReleaseChannel::Stable, ReleaseChannel::Beta, ReleaseChannel::Dev];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            ReleaseChannel::Stable => "stable",
+            ReleaseChannel::Beta => "beta",
+            ReleaseChannel::Dev => "dev",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<ReleaseChannel> {
+        Self::ALL.into_iter().find(|channel| channel.id() == id)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReleaseChannel::Stable => "Stable",
+            ReleaseChannel::Beta => "Beta",
+            ReleaseChannel::Dev => "Dev",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ReleaseChannel::Stable => "Released versions, recommended for playing.",
+            ReleaseChannel::Beta => "Upcoming versions that are mostly done, may still have bugs.",
+            ReleaseChannel::Dev => "Whatever is being worked on right now, expect things to break.",
+        }
+    }
+
+    /// Branch of the channel, None for stable which keeps the source's own.
+    pub fn branch(&self) -> Option<&'static str> {
+        match self {
+            ReleaseChannel::Stable => None,
+            channel => Some(channel.id()),
+        }
+    }
+
+    /// `source` switched to the channel's branch.
+    pub fn apply(&self, mut source: PackSource) -> PackSource {
+        if let (Some(branch), Some(_)) = (self.branch(), &source.github) {
+            source.branch = Some(branch.to_string());
+        }
+        source
+    }
+}
+
+/// Channels of the default repo that have a branch, from the list fetched at startup. Other sources
+/// only get stable, we don't know their branches.
+pub fn available(source: &PackSource, branches: &[crate::GithubBranch]) -> Vec<ReleaseChannel> {
+    ReleaseChannel::ALL
+        .into_iter()
+        .filter(|channel| match channel.branch() {
+            None => true,
+            Some(branch) => source.is_default() && branches.iter().any(|b| b.name == branch),
+        })
+        .collect()
+}
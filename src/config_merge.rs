@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use isahc::AsyncReadResponseExt;
+use log::{debug, info};
+use serde_json::Value;
+
+use crate::universal::{ConfigPreset, ShaderTierSetting};
+use crate::CachedHttpClient;
+
+// Config presets (minimal HUD, streamer mode, ...) only ship the values they care about. Those are
+// merged over whatever the mods already wrote so the rest of the user's settings survive: objects
+// are merged key by key, anything else (numbers, strings, lists) is replaced. Every file that
+// already existed is copied aside before it's touched. Shader quality tiers only set a few
+// key=value lines, see set_properties.
+
+const BACKUPS_DIR: &str = "config-preset-backups";
+
+pub fn merge(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+async fn fetch_values(http_client: &CachedHttpClient, url: &str) -> Result<Value, String> {
+    let mut resp = http_client
+        .get_async(url)
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, resp.status()));
+    }
+    let text = resp
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{} is not valid json: {}", url, e))
+}
+
+/// Merges `preset` into the config files under `game_dir`. The originals are copied to a
+/// timestamped folder under `backup_root`, which is returned.
+pub async fn apply_preset(
+    http_client: &CachedHttpClient,
+    preset: &ConfigPreset,
+    game_dir: &Path,
+    backup_root: &Path,
+) -> Result<PathBuf, String> {
+    // Everything is downloaded and parsed first, a bad file shouldn't leave half a preset behind
+    let mut merged = Vec::new();
+    for file in &preset.files {
+        let target = crate::validate_safe_path(game_dir, &file.path)?;
+        let overlay = fetch_values(http_client, &file.url).await?;
+        let existed = target.exists();
+        let mut config = if existed {
+            let content = fs::read_to_string(&target)
+                .map_err(|e| format!("Failed to read {}: {}", target.display(), e))?;
+            serde_json::from_str(&content)
+                .map_err(|e| format!("{} is not valid json, not merging into it: {}", target.display(), e))?
+        } else {
+            Value::Object(Default::default())
+        };
+        merge(&mut config, &overlay);
+        merged.push((file.path.as_str(), target, existed, config));
+    }
+
+    let backup_dir = backup_root
+        .join(BACKUPS_DIR)
+        .join(format!("{}-{}", Utc::now().format("%Y%m%d-%H%M%S"), preset.id));
+    for (path, target, existed, _) in &merged {
+        if !existed {
+            continue;
+        }
+        let backup = backup_dir.join(path);
+        if let Some(parent) = backup.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create backup directory: {}", e))?;
+        }
+        fs::copy(target, &backup).map_err(|e| format!("Failed to back up {}: {}", target.display(), e))?;
+    }
+
+    for (_, target, _, config) in merged {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(&config)
+            .map_err(|e| format!("Failed to serialize {}: {}", target.display(), e))?;
+        fs::write(&target, json).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        debug!("Merged config preset '{}' into {}", preset.id, target.display());
+    }
+
+    info!("Applied config preset '{}', originals kept in {}", preset.id, backup_dir.display());
+    Ok(backup_dir)
+}
+
+/// Sets key=value lines in properties files (Iris' config, shader options), keeping every other line.
+pub fn set_properties(game_dir: &Path, settings: &[ShaderTierSetting]) -> Result<(), String> {
+    let mut paths: Vec<&str> = settings.iter().map(|s| s.path.as_str()).collect();
+    paths.sort();
+    paths.dedup();
+    for path in paths {
+        let target = crate::validate_safe_path(game_dir, path)?;
+        let content = if target.exists() {
+            fs::read_to_string(&target).map_err(|e| format!("Failed to read {}: {}", target.display(), e))?
+        } else {
+            String::new()
+        };
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        for setting in settings.iter().filter(|s| s.path == path) {
+            let line = format!("{}={}", setting.key, setting.value);
+            let existing = lines.iter_mut().find(|l| {
+                l.split_once('=').map_or(false, |(key, _)| key.trim() == setting.key)
+            });
+            match existing {
+                Some(existing) => *existing = line,
+                None => lines.push(line),
+            }
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&target, lines.join("\n") + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        debug!("Updated {} setting(s) in {}", settings.iter().filter(|s| s.path == path).count(), target.display());
+    }
+    Ok(())
+}
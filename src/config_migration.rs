@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+use log::{debug, error, info, warn};
+use serde_json::Value as JsonValue;
+
+use crate::Config;
+
+// Bump this and add a step to migrate_step whenever the config layout changes
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+
+// Applies a single migration step, turning a version `from` config into version `from + 1`
+fn migrate_step(from: u32, config: &mut serde_json::Map<String, JsonValue>) -> Result<(), String> {
+    match from {
+        // v0 -> v1: first_launch was added after the initial release
+        0 => {
+            config
+                .entry("first_launch")
+                .or_insert(JsonValue::Bool(false));
+        }
+        // v1 -> v2: global preference sections, everything else is filled in by serde defaults
+        1 => {
+            for section in ["general", "network", "privacy", "storage", "advanced"] {
+                config
+                    .entry(section)
+                    .or_insert_with(|| JsonValue::Object(serde_json::Map::new()));
+            }
+        }
+        _ => return Err(format!("No migration available from config version {}", from)),
+    }
+    Ok(())
+}
+
+/// Brings a raw config up to CURRENT_CONFIG_VERSION.
+/// Configs without a version are treated as either v0 or v1 depending on their contents.
+pub fn migrate_config(mut value: JsonValue) -> Result<JsonValue, String> {
+    let config = value
+        .as_object_mut()
+        .ok_or_else(|| "Config root is not an object".to_string())?;
+
+    let mut version = match config.get("config_version").and_then(|v| v.as_u64()) {
+        Some(v) => v as u32,
+        None if config.contains_key("first_launch") => 1,
+        None => 0,
+    };
+
+    if version > CURRENT_CONFIG_VERSION {
+        // Written by a newer installer, unknown fields are ignored by serde so just try to use it
+        warn!(
+            "Config version {} is newer than supported version {}, loading it anyway",
+            version, CURRENT_CONFIG_VERSION
+        );
+        return Ok(value);
+    }
+
+    while version < CURRENT_CONFIG_VERSION {
+        debug!("Migrating config from version {} to {}", version, version + 1);
+        migrate_step(version, config)?;
+        version += 1;
+    }
+    config.insert("config_version".to_string(), JsonValue::from(CURRENT_CONFIG_VERSION));
+
+    Ok(value)
+}
+
+// Also used by `doctor`, which must not touch the file
+pub fn parse_config(bytes: &[u8]) -> Result<(Config, bool), String> {
+    let raw: JsonValue = serde_json::from_slice(bytes)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+    let original_version = raw.get("config_version").and_then(|v| v.as_u64());
+    let migrated = migrate_config(raw)?;
+    let config: Config = serde_json::from_value(migrated)
+        .map_err(|e| format!("Invalid config: {}", e))?;
+    let changed = original_version != Some(config.config_version as u64);
+    Ok((config, changed))
+}
+
+fn write_config(config: &Config, config_path: &Path) {
+    match serde_json::to_vec(config) {
+        Ok(bytes) => {
+            if let Err(e) = fs::write(config_path, bytes) {
+                error!("Failed to write config: {}", e);
+            }
+        }
+        Err(e) => error!("Failed to serialize config: {}", e),
+    }
+}
+
+/// Loads the config, migrating older versions in place.
+/// If the file is unreadable or corrupt it is moved aside and defaults are used.
+/// The returned message (if any) should be shown to the user.
+pub fn load_config(config_path: &Path) -> (Config, Option<String>) {
+    if !config_path.exists() {
+        let config = Config::default();
+        write_config(&config, config_path);
+        return (config, None);
+    }
+
+    let result = fs::read(config_path)
+        .map_err(|e| format!("Failed to read config: {}", e))
+        .and_then(|bytes| parse_config(&bytes));
+
+    match result {
+        Ok((config, changed)) => {
+            if changed {
+                info!("Migrated config to version {}", CURRENT_CONFIG_VERSION);
+                write_config(&config, config_path);
+            }
+            (config, None)
+        }
+        Err(e) => {
+            error!("Failed to load config, resetting to defaults: {}", e);
+            let backup_path = config_path.with_file_name(format!(
+                "config.json.corrupt-{}",
+                chrono::Utc::now().format("%Y%m%d%H%M%S")
+            ));
+            let moved = match fs::rename(config_path, &backup_path) {
+                Ok(_) => {
+                    warn!("Moved corrupt config to {}", backup_path.display());
+                    true
+                }
+                Err(rename_err) => {
+                    error!("Failed to move corrupt config aside: {}", rename_err);
+                    false
+                }
+            };
+
+            let config = Config::default();
+            write_config(&config, config_path);
+
+            let message = if moved {
+                format!(
+                    "Your settings file could not be loaded and has been reset to defaults. The old file was saved as '{}'.\n\nReason: {}",
+                    backup_path.display(),
+                    e
+                )
+            } else {
+                format!(
+                    "Your settings file could not be loaded and has been reset to defaults.\n\nReason: {}",
+                    e
+                )
+            };
+            (config, Some(message))
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config_merge;
+
+// Targeted edits to mod configs listed in the manifest's `config_patches`, applied after the
+// includes so a pack can change one value without shipping (and overwriting) the whole file.
+// TOML and properties files are edited line by line, that keeps the comments mod authors put in
+// them and everything the player changed around the patched key.
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PatchFormat {
+    Json,
+    Toml,
+    Properties,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    // Always the pack's value
+    #[default]
+    Replace,
+    // Only when the key isn't there yet, the player's value wins
+    Keep,
+    // Json objects merged key by key, the pack's keys win, the player's others stay
+    Merge,
+    // Json arrays get the pack's items they don't contain yet
+    Append,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ConfigPatch {
+    // Relative to the game directory
+    pub path: String,
+    pub format: PatchFormat,
+    // Dotted path, e.g. "client.hud.scale"; for TOML everything before the last dot is the table
+    pub key: String,
+    pub value: Value,
+    #[serde(default)]
+    pub strategy: MergeStrategy,
+    // Only applied while this feature is enabled, always without one
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+impl ConfigPatch {
+    pub fn is_enabled(&self, enabled_features: &[String]) -> bool {
+        match &self.id {
+            None => true,
+            Some(id) => id == "default" || enabled_features.contains(id),
+        }
+    }
+}
+
+fn patch_json(content: &str, patch: &ConfigPatch) -> Result<String, String> {
+    let mut root: Value = if content.trim().is_empty() {
+        Value::Object(Default::default())
+    } else {
+        serde_json::from_str(content).map_err(|e| format!("{} is not valid json: {}", patch.path, e))?
+    };
+    let parts: Vec<&str> = patch.key.split('.').collect();
+    let (leaf, parents) = parts.split_last().ok_or_else(|| "Empty key".to_string())?;
+    let mut current = &mut root;
+    for part in parents {
+        let object = current
+            .as_object_mut()
+            .ok_or_else(|| format!("{} in {} isn't an object", part, patch.path))?;
+        current = object.entry(part.to_string()).or_insert_with(|| Value::Object(Default::default()));
+    }
+    let object = current
+        .as_object_mut()
+        .ok_or_else(|| format!("The parent of {} in {} isn't an object", patch.key, patch.path))?;
+    match (patch.strategy, object.get_mut(*leaf)) {
+        (MergeStrategy::Keep, Some(_)) => {}
+        (MergeStrategy::Merge, Some(existing)) => config_merge::merge(existing, &patch.value),
+        (MergeStrategy::Append, Some(Value::Array(items))) => {
+            let additions = patch.value.as_array().cloned().unwrap_or_else(|| vec![patch.value.clone()]);
+            for item in additions {
+                if !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+        }
+        (MergeStrategy::Append, Some(_)) => {
+            return Err(format!("{} in {} isn't a list to append to", patch.key, patch.path));
+        }
+        (_, Some(existing)) => *existing = patch.value.clone(),
+        (_, None) => {
+            object.insert(leaf.to_string(), patch.value.clone());
+        }
+    }
+    serde_json::to_string_pretty(&root).map_err(|e| format!("Failed to write {}: {}", patch.path, e))
+}
+
+fn toml_value(value: &Value) -> Result<String, String> {
+    Ok(match value {
+        // Json string escapes are valid in TOML basic strings
+        Value::String(_) | Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(items) => format!("[{}]", items.iter().map(toml_value).collect::<Result<Vec<_>, _>>()?.join(", ")),
+        Value::Object(entries) => format!(
+            "{{ {} }}",
+            entries
+                .iter()
+                .map(|(key, value)| toml_value(value).map(|value| format!("{} = {}", key, value)))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", ")
+        ),
+        Value::Null => return Err("TOML has no null".to_string()),
+    })
+}
+
+fn toml_header(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.starts_with("[[") {
+        // Arrays of tables, patches don't go into them
+        return Some(String::new());
+    }
+    let inner = line.strip_prefix('[')?.split(']').next()?;
+    Some(inner.split('.').map(|part| part.trim().trim_matches('"')).collect::<Vec<_>>().join("."))
+}
+
+fn toml_key(line: &str) -> Option<String> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    (!key.starts_with('#')).then(|| key.trim_matches('"').to_string())
+}
+
+fn patch_toml(content: &str, patch: &ConfigPatch) -> Result<String, String> {
+    let (table, leaf) = match patch.key.rsplit_once('.') {
+        Some((table, leaf)) => (table.to_string(), leaf),
+        None => (String::new(), patch.key.as_str()),
+    };
+    if !matches!(patch.strategy, MergeStrategy::Replace | MergeStrategy::Keep) {
+        return Err(format!("{:?} only works for json files", patch.strategy));
+    }
+    let line = format!("{} = {}", leaf, toml_value(&patch.value)?);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    // Lines of the table, the root one runs until the first header
+    let start = if table.is_empty() {
+        Some(0)
+    } else {
+        lines.iter().position(|l| toml_header(l).as_deref() == Some(table.as_str())).map(|i| i + 1)
+    };
+    let Some(start) = start else {
+        if lines.last().map_or(false, |l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(format!("[{}]", table));
+        lines.push(line);
+        return Ok(lines.join("\n") + "\n");
+    };
+    let end = lines[start..]
+        .iter()
+        .position(|l| toml_header(l).is_some())
+        .map_or(lines.len(), |i| start + i);
+
+    match (start..end).find(|i| toml_key(&lines[*i]).as_deref() == Some(leaf)) {
+        Some(_) if patch.strategy == MergeStrategy::Keep => {}
+        Some(i) => {
+            let indent: String = lines[i].chars().take_while(|c| c.is_whitespace()).collect();
+            lines[i] = format!("{}{}", indent, line);
+        }
+        None => {
+            // After the table's last value, not after the blank lines before the next header
+            let insert_at = (start..end).rev().find(|i| !lines[*i].trim().is_empty()).map_or(start, |i| i + 1);
+            lines.insert(insert_at, line);
+        }
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+fn patch_properties(content: &str, patch: &ConfigPatch) -> Result<String, String> {
+    if !matches!(patch.strategy, MergeStrategy::Replace | MergeStrategy::Keep) {
+        return Err(format!("{:?} only works for json files", patch.strategy));
+    }
+    let value = match &patch.value {
+        Value::String(value) => value.clone(),
+        other => other.to_string(),
+    };
+    let line = format!("{}={}", patch.key, value);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let existing = lines.iter_mut().find(|l| {
+        !l.trim_start().starts_with('#') && l.split_once('=').map_or(false, |(key, _)| key.trim() == patch.key)
+    });
+    match existing {
+        Some(_) if patch.strategy == MergeStrategy::Keep => {}
+        Some(existing) => *existing = line,
+        None => lines.push(line),
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Applies `patches` under `game_dir`, each file is read and written once. Returns the number of
+/// files changed.
+pub fn apply(game_dir: &Path, patches: &[&ConfigPatch]) -> Result<usize, String> {
+    let mut by_path: BTreeMap<&str, Vec<&ConfigPatch>> = BTreeMap::new();
+    for patch in patches {
+        by_path.entry(patch.path.as_str()).or_default().push(patch);
+    }
+    let mut changed = 0;
+    for (path, patches) in by_path {
+        let target = crate::validate_safe_path(game_dir, path)?;
+        let original = match fs::read_to_string(&target) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", target.display(), e)),
+        };
+        let mut content = original.clone();
+        for patch in patches {
+            content = match patch.format {
+                PatchFormat::Json => patch_json(&content, patch),
+                PatchFormat::Toml => patch_toml(&content, patch),
+                PatchFormat::Properties => patch_properties(&content, patch),
+            }
+            .map_err(|e| format!("Patch {} in {}: {}", patch.key, path, e))?;
+        }
+        if content == original {
+            debug!("{} already has the pack's values", path);
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&target, content).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        changed += 1;
+    }
+    info!("Config patches changed {} file(s)", changed);
+    Ok(changed)
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::hashing::{self, HashAlgorithm};
+
+// Updates overwrite the folders of mods whose settings users care most about (Wynntils). Before an
+// update the protected paths of the game folder are copied aside, afterwards every file the update
+// changed or deleted is put back, unless the manifest lists it in `config_overrides` because this
+// version deliberately replaces it. Files the update added are left alone. What was restored is
+// kept as a summary the GUI shows once.
+
+pub fn default_paths() -> Vec<String> {
+    vec!["wynntils".to_string()]
+}
+
+fn snapshots_dir() -> PathBuf {
+    crate::get_installer_dir().join("config-snapshots")
+}
+
+fn summary_path(uuid: &str) -> PathBuf {
+    snapshots_dir().join(format!("{}.json", uuid))
+}
+
+pub struct Snapshot {
+    uuid: String,
+    root: PathBuf,
+    dir: PathBuf,
+    // Path relative to the game folder (forward slashes) -> sha256 before the update
+    files: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RestoreSummary {
+    // Changed by the update and put back
+    pub restored: Vec<String>,
+    // Deleted by the update and put back
+    pub recreated: Vec<String>,
+    // Replaced on purpose, listed in the manifest's config_overrides
+    pub overridden: Vec<String>,
+}
+
+impl RestoreSummary {
+    pub fn is_empty(&self) -> bool {
+        self.restored.is_empty() && self.recreated.is_empty() && self.overridden.is_empty()
+    }
+}
+
+fn relative_name(root: &Path, path: &Path) -> Option<String> {
+    Some(path.strip_prefix(root).ok()?.to_string_lossy().replace('\\', "/"))
+}
+
+/// Copies the protected `paths` (files or folders, relative to `root`) aside.
+pub fn snapshot(uuid: &str, root: &Path, paths: &[String]) -> Result<Snapshot, String> {
+    let dir = snapshots_dir().join(uuid);
+    // Left over from an update that crashed before restoring, it's older than what's there now
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear {}: {}", dir.display(), e))?;
+    }
+    let mut files = HashMap::new();
+    for path in paths {
+        let path = path.trim().trim_matches('/');
+        if path.is_empty() || path.contains("..") {
+            continue;
+        }
+        let source = root.join(path);
+        let sources = if source.is_dir() {
+            hashing::walk_files(&source)?
+        } else if source.is_file() {
+            vec![source]
+        } else {
+            continue;
+        };
+        for file in sources {
+            let Some(relative) = relative_name(root, &file) else { continue };
+            let copy = dir.join(&relative);
+            if let Some(parent) = copy.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::copy(&file, &copy).map_err(|e| format!("Failed to copy {}: {}", file.display(), e))?;
+            files.insert(relative, hashing::hash_file(&file, HashAlgorithm::Sha256)?);
+        }
+    }
+    debug!("Snapshotted {} protected file(s) of {}", files.len(), uuid);
+    Ok(Snapshot { uuid: uuid.to_string(), root: root.to_path_buf(), dir, files })
+}
+
+fn is_overridden(relative: &str, overrides: &[String]) -> bool {
+    overrides.iter().any(|o| {
+        let o = o.trim().trim_matches('/');
+        !o.is_empty() && (relative == o || relative.starts_with(&format!("{}/", o)))
+    })
+}
+
+/// Puts back what the update changed, except `overrides`, and removes the snapshot.
+pub fn restore(snapshot: Snapshot, overrides: &[String]) -> Result<RestoreSummary, String> {
+    let mut summary = RestoreSummary::default();
+    let mut names: Vec<&String> = snapshot.files.keys().collect();
+    names.sort();
+    for relative in names {
+        let target = snapshot.root.join(relative);
+        let changed = !target.is_file()
+            || hashing::hash_file(&target, HashAlgorithm::Sha256).ok().as_ref() != snapshot.files.get(relative);
+        if !changed {
+            continue;
+        }
+        if is_overridden(relative, overrides) {
+            summary.overridden.push(relative.clone());
+            continue;
+        }
+        let existed = target.is_file();
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::copy(snapshot.dir.join(relative), &target)
+            .map_err(|e| format!("Failed to restore {}: {}", target.display(), e))?;
+        if existed {
+            summary.restored.push(relative.clone());
+        } else {
+            summary.recreated.push(relative.clone());
+        }
+    }
+    if let Err(e) = fs::remove_dir_all(&snapshot.dir) {
+        warn!("Failed to remove the config snapshot {}: {}", snapshot.dir.display(), e);
+    }
+    info!(
+        "Protected config: {} restored, {} recreated, {} replaced by the update",
+        summary.restored.len(),
+        summary.recreated.len(),
+        summary.overridden.len()
+    );
+    if !summary.is_empty() {
+        let json = serde_json::to_string_pretty(&summary).map_err(|e| format!("Failed to write the restore summary: {}", e))?;
+        fs::write(summary_path(&snapshot.uuid), json).map_err(|e| format!("Failed to write the restore summary: {}", e))?;
+    }
+    Ok(summary)
+}
+
+/// The summary of the last update of `uuid`, once.
+pub fn take_summary(uuid: &str) -> Option<RestoreSummary> {
+    let path = summary_path(uuid);
+    let summary = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok();
+    let _ = fs::remove_file(&path);
+    summary
+}
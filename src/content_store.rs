@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::{debug, info, warn};
+
+use crate::hashing::{hash_file, walk_files, HashAlgorithm};
+use crate::installation::load_all_installations;
+use crate::{get_launcher, get_modpack_root, Manifest, StorageSettings};
+
+// Content-addressed store for the mods, shaderpacks and resourcepacks of all installations. Every
+// file is kept once in .WC_OVHL/store/<sha256> and hardlinked into the installations that use it,
+// so five installations of the pack take the disk space of one. Where a hardlink isn't possible
+// (another drive, a file system without them) a copy-on-write clone is tried, which still shares
+// the data on Btrfs, XFS, APFS and ReFS. Anything else keeps its own copy.
+//
+// Nothing may write into these files in place, that would change the file of every installation
+// sharing it (and leave the blob under a wrong hash). Updates download to a new file and rename it
+// over the old one, restores and extraction call detach() first, either way only that
+// installation's link is dropped. Blobs nothing links to anymore are removed by the next full pass.
+
+const STORE_DIR: &str = "store";
+// Written after the first full pass over the installations that existed before the store
+const MIGRATED_MARKER: &str = ".migrated";
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn configure(storage: &StorageSettings) {
+    ENABLED.store(storage.deduplicate, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn store_dir() -> PathBuf {
+    crate::get_installer_dir().join(STORE_DIR)
+}
+
+fn blob_path(hash: &str) -> PathBuf {
+    store_dir().join(&hash[..2]).join(hash)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Linked {
+    // First copy, moved into the store
+    Added,
+    // Replaced by a link to the stored copy, saving its size
+    Shared(u64),
+    AlreadyShared,
+    // Neither a hardlink nor a clone works here
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DedupeReport {
+    pub files: usize,
+    pub shared: usize,
+    pub bytes_saved: u64,
+    pub blobs_removed: usize,
+    pub unsupported: usize,
+}
+
+impl DedupeReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Checked {} file(s), {} freed by sharing {} of them",
+            self.files,
+            crate::download_progress::format_bytes(self.bytes_saved),
+            self.shared
+        );
+        if self.blobs_removed > 0 {
+            summary.push_str(&format!(", removed {} unused stored file(s)", self.blobs_removed));
+        }
+        if self.unsupported > 0 {
+            summary.push_str(&format!(". {} file(s) are on a drive that can't share them", self.unsupported));
+        }
+        summary
+    }
+
+    fn add(&mut self, linked: Linked) {
+        self.files += 1;
+        match linked {
+            Linked::Shared(bytes) => {
+                self.shared += 1;
+                self.bytes_saved += bytes;
+            }
+            Linked::Unsupported => self.unsupported += 1,
+            Linked::Added | Linked::AlreadyShared => {}
+        }
+    }
+}
+
+// Hardlink first, it's free and works on every file system the installer dir is likely on
+fn share(from: &Path, to: &Path) -> io::Result<()> {
+    fs::hard_link(from, to).or_else(|e| {
+        debug!("Hardlinking {} failed ({}), trying a clone", from.display(), e);
+        reflink_copy::reflink(from, to)
+    })
+}
+
+fn link_file(path: &Path, hash: &str) -> Result<Linked, String> {
+    let blob = blob_path(hash);
+    if !blob.exists() {
+        if let Some(parent) = blob.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        return Ok(match share(path, &blob) {
+            Ok(_) => Linked::Added,
+            Err(e) => {
+                debug!("Can't add {} to the store: {}", path.display(), e);
+                Linked::Unsupported
+            }
+        });
+    }
+    if same_file::is_same_file(path, &blob).unwrap_or(false) {
+        return Ok(Linked::AlreadyShared);
+    }
+
+    let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+    // Linked next to the file and renamed over it, the file is never missing in between
+    let tmp = path.with_extension(format!("store-{}", std::process::id()));
+    let _ = fs::remove_file(&tmp);
+    if let Err(e) = share(&blob, &tmp) {
+        debug!("Can't share {} with the store: {}", path.display(), e);
+        return Ok(Linked::Unsupported);
+    }
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(format!("Failed to replace {}: {}", path.display(), e));
+    }
+    Ok(Linked::Shared(size))
+}
+
+/// Removes `path` so whatever is written there next gets a file of its own instead of writing
+/// through a link into the store. Directories and missing paths are left alone.
+pub fn detach(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.is_dir() => fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// Shares `paths` with the store, used after downloads. Failures only leave a file unshared.
+pub fn link_files(paths: &[PathBuf]) -> DedupeReport {
+    let mut report = DedupeReport::default();
+    if !enabled() {
+        return report;
+    }
+    for path in paths.iter().filter(|path| path.is_file()) {
+        let result = hash_file(path, HashAlgorithm::Sha256).and_then(|hash| link_file(path, &hash));
+        match result {
+            Ok(linked) => report.add(linked),
+            Err(e) => warn!("Failed to deduplicate {}: {}", path.display(), e),
+        }
+    }
+    if report.bytes_saved > 0 {
+        info!("Shared {} file(s) with other installations, {} bytes saved", report.shared, report.bytes_saved);
+    }
+    report
+}
+
+/// Files of the manifest.json an install left, the ones the store takes care of.
+fn manifest_files(manifest: &Manifest) -> Vec<PathBuf> {
+    manifest
+        .mods
+        .iter()
+        .filter_map(|m| m.path.clone())
+        .chain(manifest.shaderpacks.iter().filter_map(|s| s.path.clone()))
+        .chain(manifest.resourcepacks.iter().filter_map(|r| r.path.clone()))
+        .collect()
+}
+
+fn installed_files() -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for installation in load_all_installations()?.iter().filter(|i| i.installed) {
+        let launcher = match get_launcher(&installation.launcher_type) {
+            Ok(launcher) => launcher,
+            Err(e) => {
+                warn!("Skipping {} while deduplicating: {}", installation.id, e);
+                continue;
+            }
+        };
+        let manifest_path = get_modpack_root(&launcher, &installation.id).join("manifest.json");
+        let manifest: Manifest = match fs::read_to_string(&manifest_path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!("Skipping {} while deduplicating: {}", installation.id, e);
+                continue;
+            }
+        };
+        files.extend(manifest_files(&manifest).into_iter().filter(|path| path.is_file()));
+    }
+    Ok(files)
+}
+
+/// Shares the files of every installation with the store and drops blobs nothing uses anymore.
+/// Blocking, hashes every mod once.
+pub fn deduplicate_all() -> Result<DedupeReport, String> {
+    let files = installed_files()?;
+    info!("Deduplicating {} file(s)", files.len());
+    let mut report = DedupeReport::default();
+    let mut used = HashSet::new();
+    for path in &files {
+        let hash = match hash_file(path, HashAlgorithm::Sha256) {
+            Ok(hash) => hash,
+            Err(e) => {
+                warn!("Failed to hash {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        match link_file(path, &hash) {
+            Ok(linked) => report.add(linked),
+            Err(e) => warn!("Failed to deduplicate {}: {}", path.display(), e),
+        }
+        used.insert(hash);
+    }
+
+    let store = store_dir();
+    if store.is_dir() {
+        for blob in walk_files(&store)? {
+            let Some(name) = blob.file_name().and_then(|n| n.to_str()) else { continue };
+            if name.starts_with('.') || used.contains(name) {
+                continue;
+            }
+            match fs::remove_file(&blob) {
+                Ok(_) => report.blobs_removed += 1,
+                Err(e) => warn!("Failed to remove {}: {}", blob.display(), e),
+            }
+        }
+    }
+    info!("Deduplication done: {:?}", report);
+    Ok(report)
+}
+
+/// The first full pass for installations made before the store, once per installer dir.
+pub fn migrate_once() {
+    let marker = store_dir().join(MIGRATED_MARKER);
+    if !enabled() || marker.exists() {
+        return;
+    }
+    match deduplicate_all() {
+        Ok(_) => {
+            if let Err(e) = fs::create_dir_all(store_dir()).and_then(|_| fs::write(&marker, b"1")) {
+                warn!("Failed to mark the store as migrated: {}", e);
+            }
+        }
+        Err(e) => warn!("Failed to move installations to the shared store: {}", e),
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use isahc::AsyncReadResponseExt;
+use lazy_static::lazy_static;
+use log::{debug, error, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{get_installer_dir, CachedHttpClient};
+
+const KNOWN_ISSUES_URL: &str = "https://raw.githubusercontent.com/Olinus10/installer-test/master/known_issues.json";
+
+lazy_static! {
+    // Small key/value summary of what the app was doing, included in crash dumps
+    static ref CRASH_CONTEXT: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: String,
+    pub installer_version: String,
+    pub platform: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    #[serde(default)]
+    pub app_state: BTreeMap<String, String>,
+    // Set once the user has either submitted or dismissed the report
+    #[serde(default)]
+    pub handled: bool,
+    #[serde(default)]
+    pub submitted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KnownIssue {
+    // Regex matched against the panic message
+    pub pattern: String,
+    pub title: String,
+    pub link: String,
+}
+
+pub fn get_crashes_dir() -> PathBuf {
+    get_installer_dir().join("crashes")
+}
+
+pub fn set_crash_context(key: &str, value: impl Into<String>) {
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        context.insert(key.to_string(), value.into());
+    }
+}
+
+pub fn clear_crash_context(key: &str) {
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        context.remove(key);
+    }
+}
+
+/// Called from the panic hook, so this must never panic itself.
+pub fn write_crash_report(message: &str, location: Option<String>, backtrace: String) -> Option<PathBuf> {
+    // try_lock: the panic may have happened while the context was locked
+    let app_state = CRASH_CONTEXT
+        .try_lock()
+        .map(|context| context.clone())
+        .unwrap_or_default();
+
+    let now = chrono::Utc::now();
+    let report = CrashReport {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: now.to_rfc3339(),
+        installer_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        message: message.to_string(),
+        location,
+        thread: std::thread::current().name().map(|name| name.to_string()),
+        backtrace,
+        app_state,
+        handled: false,
+        submitted: false,
+    };
+
+    let crashes_dir = get_crashes_dir();
+    if let Err(e) = fs::create_dir_all(&crashes_dir) {
+        error!("Failed to create crash directory: {}", e);
+        return None;
+    }
+
+    let path = crashes_dir.join(format!("crash-{}.json", now.format("%Y%m%d-%H%M%S")));
+    let json = match serde_json::to_string_pretty(&report) {
+        Ok(json) => json,
+        Err(e) => {
+            error!("Failed to serialize crash report: {}", e);
+            return None;
+        }
+    };
+
+    match fs::write(&path, json) {
+        Ok(_) => Some(path),
+        Err(e) => {
+            error!("Failed to write crash report: {}", e);
+            None
+        }
+    }
+}
+
+/// Returns crash reports that the user hasn't seen yet, newest first.
+pub fn load_pending_reports() -> Vec<(PathBuf, CrashReport)> {
+    let entries = match fs::read_dir(get_crashes_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut reports: Vec<(PathBuf, CrashReport)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            match serde_json::from_str::<CrashReport>(&content) {
+                Ok(report) => Some((path, report)),
+                Err(e) => {
+                    warn!("Ignoring unreadable crash report {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .filter(|(_, report)| !report.handled)
+        .collect();
+
+    reports.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+    reports
+}
+
+pub fn mark_handled(path: &PathBuf, submitted: bool) -> Result<(), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read crash report: {}", e))?;
+    let mut report: CrashReport = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse crash report: {}", e))?;
+    report.handled = true;
+    report.submitted = submitted;
+    fs::write(path, serde_json::to_string_pretty(&report).unwrap_or(content))
+        .map_err(|e| format!("Failed to update crash report: {}", e))
+}
+
+pub fn submit_crash_report(report: &CrashReport) {
+    debug!("Submitting crash report {}", report.id);
+    crate::Analytics::record(crate::Event::CrashReport {
+        id: report.id.clone(),
+        report: serde_json::to_value(report).unwrap_or_default(),
+    });
+}
+
+pub async fn fetch_known_issues(http_client: &CachedHttpClient) -> Result<Vec<KnownIssue>, String> {
+    // Single attempt, this is only a nice to have and shouldn't stall the crash dialog
+    let mut response = http_client
+        .with_headers(KNOWN_ISSUES_URL, &[])
+        .await
+        .map_err(|e| format!("Failed to fetch known issues: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Known issues request returned {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read known issues: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("Failed to parse known issues: {}", e))
+}
+
+pub fn find_known_issue<'a>(report: &CrashReport, issues: &'a [KnownIssue]) -> Option<&'a KnownIssue> {
+    issues.iter().find(|issue| match Regex::new(&issue.pattern) {
+        Ok(re) => re.is_match(&report.message),
+        Err(e) => {
+            warn!("Invalid known issue pattern '{}': {}", issue.pattern, e);
+            false
+        }
+    })
+}
@@ -0,0 +1,61 @@
+use std::sync::RwLock;
+
+use log::debug;
+use serde::Deserialize;
+
+use crate::secret::Secret;
+
+// CurseForge components use the numeric project id as `location` and the numeric file id as
+// `version`. Their API needs a key, which users enter in the preferences (or CI sets through
+// CURSEFORGE_API_KEY). Authors can opt out of third party downloads, those files have no
+// download url and have to be downloaded by hand.
+
+pub const API_URL: &str = "https://api.curseforge.com/v1";
+pub const API_KEY_ENV: &str = "CURSEFORGE_API_KEY";
+
+// Mirrors config.network.curseforge_api_key, set at startup and whenever preferences are saved
+static API_KEY: RwLock<Option<String>> = RwLock::new(None);
+
+pub fn configure(key: Option<&Secret>) {
+    let key = std::env::var(API_KEY_ENV)
+        .ok()
+        .or_else(|| key.map(|k| k.0.clone()))
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty());
+    debug!("CurseForge API key configured: {}", key.is_some());
+    if let Ok(mut current) = API_KEY.write() {
+        *current = key;
+    }
+}
+
+pub fn api_key() -> Option<String> {
+    API_KEY.read().ok().and_then(|k| k.clone())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseFile {
+    pub id: u64,
+    pub mod_id: u64,
+    pub file_name: String,
+    // None when the author disabled third party downloads
+    pub download_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileResponse {
+    data: CurseFile,
+}
+
+pub fn file_url(project: &str, file: &str) -> String {
+    format!("{}/mods/{}/files/{}", API_URL, project, file)
+}
+
+pub fn parse_file(body: &str) -> Result<CurseFile, serde_json::Error> {
+    serde_json::from_str::<FileResponse>(body).map(|r| r.data)
+}
+
+/// Redirects to the project's page on the website, the API doesn't give us the slug.
+pub fn project_page(project: &str) -> String {
+    format!("https://www.curseforge.com/projects/{}", project)
+}
@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use std::time::SystemTime;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{GH_RAW, REPO};
+
+// Developer mode manifests: universal.json, presets.json and changelog.json read from a local
+// checkout of the pack, or from another branch of the default repo, instead of what's published.
+// A local folder only replaces the files it has, the rest still comes from the pack's source.
+// Set in the preferences (only used while developer mode is on) or for one run with
+// --dev-manifest <folder> / --dev-branch <branch>, which win over the config.
+
+pub const DIR_ARG: &str = "--dev-manifest";
+pub const BRANCH_ARG: &str = "--dev-branch";
+pub const FILES: [&str; 3] = ["universal.json", "presets.json", "changelog.json"];
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct DevManifestSettings {
+    pub local_dir: Option<String>,
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Override {
+    local_dir: Option<PathBuf>,
+    branch: Option<String>,
+}
+
+impl Override {
+    fn is_empty(&self) -> bool {
+        self.local_dir.is_none() && self.branch.is_none()
+    }
+}
+
+static FROM_ARGS: OnceLock<Override> = OnceLock::new();
+static CONFIGURED: RwLock<Override> = RwLock::new(Override { local_dir: None, branch: None });
+
+fn arg_value(args: &[String], name: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == name)?;
+    args.get(index + 1).map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// Reads --dev-manifest and --dev-branch, called once at startup.
+pub fn init(args: &[String]) {
+    let from_args = FROM_ARGS.get_or_init(|| Override {
+        local_dir: arg_value(args, DIR_ARG).map(PathBuf::from),
+        branch: arg_value(args, BRANCH_ARG),
+    });
+    if !from_args.is_empty() {
+        info!("Developer manifests from the command line: {:?}", from_args);
+    }
+}
+
+pub fn configure(developer_mode: bool, settings: &DevManifestSettings) {
+    let configured = if developer_mode {
+        Override {
+            local_dir: settings.local_dir.as_ref().filter(|dir| !dir.trim().is_empty()).map(PathBuf::from),
+            branch: settings.branch.as_ref().map(|b| b.trim().to_string()).filter(|b| !b.is_empty()),
+        }
+    } else {
+        Override::default()
+    };
+    if let Some(dir) = configured.local_dir.as_ref().filter(|dir| !dir.is_dir()) {
+        warn!("Developer manifest folder {} doesn't exist", dir.display());
+    }
+    *CONFIGURED.write().unwrap() = configured;
+}
+
+fn active() -> Override {
+    match FROM_ARGS.get().filter(|from_args| !from_args.is_empty()) {
+        Some(from_args) => from_args.clone(),
+        None => CONFIGURED.read().unwrap().clone(),
+    }
+}
+
+pub fn local_dir() -> Option<PathBuf> {
+    active().local_dir
+}
+
+/// `file` from the local folder, if there is one and it has the file.
+pub fn local_file(file: &str) -> Option<PathBuf> {
+    local_dir().map(|dir| dir.join(file)).filter(|path| path.is_file())
+}
+
+/// The content of the local `file`, None when it should be downloaded as usual.
+pub fn read_local(file: &str) -> Option<Result<String, String>> {
+    let path = local_file(file)?;
+    info!("Using the local {}", path.display());
+    Some(std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// `url` moved to the override branch when it points into the default repo, otherwise unchanged.
+pub fn resolve_url(url: &str) -> String {
+    let Some(branch) = active().branch else {
+        return url.to_string();
+    };
+    let repo_root = format!("{}{}", GH_RAW, REPO);
+    match url.strip_prefix(&repo_root).and_then(|rest| rest.split_once('/')) {
+        Some((_, file)) => format!("{}{}/{}", repo_root, branch, file),
+        None => url.to_string(),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Modification times of the local manifest files, changes whenever one of them is saved. None
+/// without a local folder, there's nothing to watch then.
+pub fn local_revision() -> Option<Vec<Option<SystemTime>>> {
+    let dir = local_dir()?;
+    Some(FILES.iter().map(|file| modified(&dir.join(file))).collect())
+}
+
+/// Short description for the UI, None when nothing is overridden.
+pub fn describe() -> Option<String> {
+    let active = active();
+    match (active.local_dir, active.branch) {
+        (Some(dir), _) => Some(format!("local folder {}", dir.display())),
+        (None, Some(branch)) => Some(format!("branch {}", branch)),
+        (None, None) => None,
+    }
+}
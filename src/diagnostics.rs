@@ -0,0 +1,258 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use isahc::AsyncReadResponseExt;
+use log::{debug, info, warn};
+use serde::Deserialize;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::installation::{get_installations_dir, Installation};
+use crate::{get_launcher, get_modpack_root, CachedHttpClient};
+
+// When the game doesn't start, support always asks for the same three things: the crash report,
+// latest.log and what's installed. This finds them in the game folder and either bundles them with
+// the installation's settings into a zip, turns them into a short summary to paste on Discord, or
+// uploads the log to mclo.gs, which also points out the usual suspects.
+
+const MCLOGS_URL: &str = "https://api.mclo.gs/1/log";
+// mclo.gs cuts logs off past this, the end is the interesting part
+const MAX_UPLOAD_LINES: usize = 25_000;
+// A crash report this much older than latest.log is from an earlier session
+const CRASH_REPORT_WINDOW: Duration = Duration::from_secs(10 * 60);
+const SUMMARY_LINES: usize = 15;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostics {
+    pub game_dir: PathBuf,
+    pub crash_report: Option<PathBuf>,
+    pub latest_log: Option<PathBuf>,
+    pub manifest: Option<PathBuf>,
+    pub installation_file: Option<PathBuf>,
+    pub install_log: Option<PathBuf>,
+}
+
+impl Diagnostics {
+    pub fn is_empty(&self) -> bool {
+        self.crash_report.is_none() && self.latest_log.is_none()
+    }
+
+    // What goes into the zip, with the name it gets there
+    fn files(&self) -> Vec<(&'static str, &PathBuf)> {
+        [
+            ("crash-report.txt", &self.crash_report),
+            ("latest.log", &self.latest_log),
+            ("manifest.json", &self.manifest),
+            ("installation.json", &self.installation_file),
+            ("install-log.jsonl", &self.install_log),
+        ]
+        .into_iter()
+        .filter_map(|(name, path)| path.as_ref().map(|path| (name, path)))
+        .collect()
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn existing(path: PathBuf) -> Option<PathBuf> {
+    path.is_file().then_some(path)
+}
+
+fn newest_crash_report(game_dir: &Path) -> Option<PathBuf> {
+    let entries = fs::read_dir(game_dir.join("crash-reports")).ok()?;
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "txt"))
+        .filter_map(|path| modified(&path).map(|time| (time, path)))
+        .max_by_key(|(time, _)| *time)
+        .map(|(_, path)| path)
+}
+
+/// Looks up the files of the last session. The crash report is only picked when it belongs to the
+/// same session as latest.log.
+pub fn collect(installation: &Installation) -> Diagnostics {
+    let game_dir = get_launcher(&installation.launcher_type)
+        .map(|launcher| get_modpack_root(&launcher, &installation.id))
+        .unwrap_or_else(|_| installation.installation_path.clone());
+    let latest_log = existing(game_dir.join("logs").join("latest.log"));
+    let crash_report = newest_crash_report(&game_dir).filter(|report| {
+        match (modified(report), latest_log.as_deref().and_then(modified)) {
+            (Some(report_time), Some(log_time)) => report_time + CRASH_REPORT_WINDOW >= log_time,
+            _ => true,
+        }
+    });
+    let installation_dir = get_installations_dir().join(&installation.id);
+    let diagnostics = Diagnostics {
+        crash_report,
+        latest_log,
+        manifest: existing(game_dir.join("manifest.json")),
+        installation_file: existing(installation_dir.join("installation.json")),
+        install_log: existing(installation_dir.join("install-log.jsonl")),
+        game_dir,
+    };
+    debug!("Collected diagnostics of {}: {:?}", installation.id, diagnostics);
+    diagnostics
+}
+
+// The part of a crash report people actually read: the description and the top of the trace
+fn crash_excerpt(report: &str) -> Vec<String> {
+    let lines: Vec<&str> = report.lines().collect();
+    let start = lines.iter().position(|line| line.starts_with("Description:")).unwrap_or(0);
+    lines[start..]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .take(SUMMARY_LINES)
+        .map(|line| line.to_string())
+        .collect()
+}
+
+// Last errors of the log, when there's no crash report the game usually logged why it gave up
+fn log_excerpt(log: &str) -> Vec<String> {
+    let errors: Vec<&str> = log
+        .lines()
+        .filter(|line| line.contains("/ERROR]") || line.contains("/FATAL]") || line.contains("Exception"))
+        .collect();
+    errors[errors.len().saturating_sub(SUMMARY_LINES)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Short plain text description of the setup and what went wrong, fits in a Discord message.
+pub fn summary(installation: &Installation, diagnostics: &Diagnostics) -> String {
+    let mut text = String::new();
+    text.push_str("```\n");
+    text.push_str(&format!(
+        "Installer {} on {} {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    ));
+    text.push_str(&format!(
+        "Pack {} | Minecraft {} | {} {}\n",
+        installation.universal_version, installation.minecraft_version, installation.loader_type, installation.loader_version
+    ));
+    text.push_str(&format!(
+        "Launcher {} | {} MB | {} features | preset {}\n",
+        installation.launcher_type,
+        installation.memory_allocation,
+        installation.enabled_features.len(),
+        installation.selected_preset_id.as_deref().unwrap_or("custom")
+    ));
+    if !installation.java_args.trim().is_empty() {
+        text.push_str(&format!("Java args: {}\n", installation.java_args.trim()));
+    }
+
+    let excerpt = match (&diagnostics.crash_report, &diagnostics.latest_log) {
+        (Some(report), _) => fs::read_to_string(report).ok().map(|content| {
+            let name = report.file_name().unwrap_or_default().to_string_lossy().to_string();
+            (name, crash_excerpt(&content))
+        }),
+        (None, Some(log)) => fs::read_to_string(log).ok().map(|content| ("latest.log".to_string(), log_excerpt(&content))),
+        (None, None) => None,
+    };
+    match excerpt {
+        Some((name, lines)) if !lines.is_empty() => {
+            text.push_str(&format!("\n{}:\n", name));
+            for line in lines {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        Some((name, _)) => text.push_str(&format!("\nNothing stands out in {}\n", name)),
+        None => text.push_str("\nNo crash report or latest.log found\n"),
+    }
+    text.push_str("```");
+    text
+}
+
+/// Zips the collected files and the summary to `dest`, returns how many files went in.
+pub fn bundle(installation: &Installation, diagnostics: &Diagnostics, dest: &Path) -> Result<usize, String> {
+    let options = zip::write::FileOptions::<()>::default().compression_method(CompressionMethod::Deflated);
+    let tmp = dest.with_extension("tmp");
+    let file = File::create(&tmp).map_err(|e| format!("Failed to create {}: {}", tmp.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let written = (|| {
+        zip.start_file("summary.txt", options)
+            .map_err(|e| format!("Failed to add summary.txt: {}", e))?;
+        zip.write_all(summary(installation, diagnostics).as_bytes())
+            .map_err(|e| format!("Failed to add summary.txt: {}", e))?;
+        let files = diagnostics.files();
+        for (name, path) in &files {
+            let mut source = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            zip.start_file(*name, options)
+                .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+            io::copy(&mut source, &mut zip).map_err(|e| format!("Failed to add {}: {}", name, e))?;
+        }
+        zip.finish().map_err(|e| format!("Failed to finish the zip: {}", e))?;
+        Ok::<usize, String>(files.len() + 1)
+    })();
+    let count = match written {
+        Ok(count) => count,
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            return Err(e);
+        }
+    };
+    fs::rename(&tmp, dest).map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    info!("Saved diagnostics of '{}' to {}", installation.name, dest.display());
+    Ok(count)
+}
+
+#[derive(Deserialize)]
+struct MclogsResponse {
+    success: bool,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Uploads the crash report, or latest.log without one, to mclo.gs and returns the link.
+pub async fn upload(http_client: &CachedHttpClient, diagnostics: &Diagnostics) -> Result<String, String> {
+    let path = diagnostics
+        .crash_report
+        .as_ref()
+        .or(diagnostics.latest_log.as_ref())
+        .ok_or_else(|| "There is no crash report or latest.log to upload".to_string())?;
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let content = lines[lines.len().saturating_sub(MAX_UPLOAD_LINES)..].join("\n");
+
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("content", &content)
+        .finish();
+    let request = isahc::Request::post(MCLOGS_URL)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body)
+        .map_err(|e| format!("Failed to create upload request: {}", e))?;
+    let mut response = http_client
+        .http_client
+        .send_async(request)
+        .await
+        .map_err(|e| format!("Failed to upload the log: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("mclo.gs returned {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read the mclo.gs response: {}", e))?;
+    let parsed: MclogsResponse =
+        serde_json::from_str(&text).map_err(|e| format!("Unexpected mclo.gs response: {}", e))?;
+    match (parsed.success, parsed.url) {
+        (true, Some(url)) => {
+            info!("Uploaded {} to {}", path.display(), url);
+            Ok(url)
+        }
+        _ => {
+            let reason = parsed.error.unwrap_or_else(|| "no reason given".to_string());
+            warn!("mclo.gs rejected the upload: {}", reason);
+            Err(format!("mclo.gs rejected the upload: {}", reason))
+        }
+    }
+}
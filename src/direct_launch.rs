@@ -0,0 +1,417 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+
+use isahc::ReadResponseExt;
+use log::{debug, info, warn};
+use rayon::prelude::*;
+use serde_json::Value as JsonValue;
+
+use crate::installation::Installation;
+use crate::ms_auth::{self, Account};
+use crate::{build_http_client, get_launcher, get_minecraft_folder, get_modpack_root, java, Loader};
+
+// Starts the game without the vanilla launcher. The loader's version json in .minecraft/versions
+// inherits from the vanilla one, together they list the libraries, the client jar, the assets and
+// the arguments; whatever the launcher hasn't downloaded yet is fetched here, in the same places
+// it would put them so both keep sharing one .minecraft. The account comes from ms_auth.rs.
+
+const VERSION_MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+const LIBRARIES_URL: &str = "https://libraries.minecraft.net/";
+const RESOURCES_URL: &str = "https://resources.download.minecraft.net/";
+
+fn os_name() -> &'static str {
+    match std::env::consts::OS {
+        "windows" => "windows",
+        "macos" => "osx",
+        _ => "linux",
+    }
+}
+
+fn download(url: &str, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut response = build_http_client()
+        .get(url)
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: status {}", url, response.status()));
+    }
+    // Renamed into place, a half downloaded library would otherwise look complete next time
+    let part = path.with_extension("part");
+    response
+        .copy_to_file(&part)
+        .map_err(|e| format!("Failed to save {}: {}", part.display(), e))?;
+    fs::rename(&part, path).map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+}
+
+// Present with the expected size, the launcher doesn't check more on startup either
+fn is_complete(path: &Path, size: Option<u64>) -> bool {
+    match (fs::metadata(path), size) {
+        (Ok(metadata), Some(size)) => metadata.len() == size,
+        (Ok(_), None) => true,
+        (Err(_), _) => false,
+    }
+}
+
+// Mojang's rules: the last matching rule decides, no rules means allowed. Every optional feature
+// (demo mode, custom resolution, quick play) counts as off
+fn rules_allow(rules: Option<&JsonValue>) -> bool {
+    let Some(rules) = rules.and_then(|r| r.as_array()) else { return true };
+    let mut allowed = false;
+    for rule in rules {
+        let os_matches = rule.get("os").map_or(true, |os| {
+            os.get("name").and_then(|n| n.as_str()).map_or(true, |name| name == os_name())
+                && os.get("arch").and_then(|a| a.as_str()).map_or(true, |arch| arch == "x86" && cfg!(target_arch = "x86"))
+        });
+        let features_match = rule
+            .get("features")
+            .and_then(|f| f.as_object())
+            .map_or(true, |features| features.values().all(|v| v == &JsonValue::Bool(false)));
+        if os_matches && features_match {
+            allowed = rule.get("action").and_then(|a| a.as_str()) == Some("allow");
+        }
+    }
+    allowed
+}
+
+fn read_version(minecraft_dir: &Path, id: &str) -> Result<Option<JsonValue>, String> {
+    let path = minecraft_dir.join("versions").join(id).join(format!("{}.json", id));
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+// The vanilla json isn't there until the launcher started that version once
+fn download_vanilla_version(minecraft_dir: &Path, id: &str) -> Result<JsonValue, String> {
+    info!("Downloading the version json of Minecraft {}", id);
+    let mut response = build_http_client()
+        .get(VERSION_MANIFEST_URL)
+        .map_err(|e| format!("Failed to load the version list: {}", e))?;
+    let manifest: JsonValue = serde_json::from_str(
+        &response.text().map_err(|e| format!("Failed to load the version list: {}", e))?,
+    )
+    .map_err(|e| format!("Unexpected version list: {}", e))?;
+    let url = manifest["versions"]
+        .as_array()
+        .and_then(|versions| versions.iter().find(|v| v["id"] == id))
+        .and_then(|v| v["url"].as_str())
+        .ok_or_else(|| format!("Minecraft {} isn't in the version list", id))?;
+    let path = minecraft_dir.join("versions").join(id).join(format!("{}.json", id));
+    download(url, &path)?;
+    read_version(minecraft_dir, id)?.ok_or_else(|| format!("{} disappeared right after downloading it", path.display()))
+}
+
+// The loader json on top of the vanilla one: its libraries and arguments come first, anything it
+// doesn't set is taken over
+fn resolve_version(minecraft_dir: &Path, id: &str) -> Result<JsonValue, String> {
+    let mut version = read_version(minecraft_dir, id)?
+        .ok_or_else(|| format!("Version {} isn't installed, reinstall the modpack", id))?;
+    let Some(parent_id) = version["inheritsFrom"].as_str().map(str::to_string) else {
+        return Ok(version);
+    };
+    let parent = match read_version(minecraft_dir, &parent_id)? {
+        Some(parent) => parent,
+        None => download_vanilla_version(minecraft_dir, &parent_id)?,
+    };
+    let mut merged = parent.clone();
+    let child = version.as_object_mut().ok_or_else(|| format!("Version {} isn't a json object", id))?;
+    for (key, value) in child.iter_mut() {
+        match key.as_str() {
+            "libraries" => {
+                let mut libraries = value.as_array().cloned().unwrap_or_default();
+                libraries.extend(parent["libraries"].as_array().cloned().unwrap_or_default());
+                merged["libraries"] = JsonValue::Array(libraries);
+            }
+            "arguments" => {
+                for kind in ["game", "jvm"] {
+                    let mut arguments = parent["arguments"][kind].as_array().cloned().unwrap_or_default();
+                    arguments.extend(value[kind].as_array().cloned().unwrap_or_default());
+                    merged["arguments"][kind] = JsonValue::Array(arguments);
+                }
+            }
+            _ => merged[key.as_str()] = value.take(),
+        }
+    }
+    // The client jar is the parent's, loader folders only hold an empty placeholder
+    merged["jar"] = JsonValue::String(parent_id);
+    Ok(merged)
+}
+
+// "group:artifact:version[:classifier]" to its path in a maven repository
+fn maven_path(name: &str) -> Option<String> {
+    let mut parts = name.split(':');
+    let (group, artifact, version) = (parts.next()?, parts.next()?, parts.next()?);
+    let classifier = parts.next().map(|c| format!("-{}", c)).unwrap_or_default();
+    Some(format!(
+        "{}/{}/{}/{}-{}{}.jar",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        artifact,
+        version,
+        classifier
+    ))
+}
+
+// Group, artifact and classifier, the loader's copy of a library replaces vanilla's
+fn library_key(name: &str) -> String {
+    let parts: Vec<&str> = name.split(':').collect();
+    match parts.as_slice() {
+        [group, artifact, _, classifier, ..] => format!("{}:{}:{}", group, artifact, classifier),
+        [group, artifact, ..] => format!("{}:{}", group, artifact),
+        _ => name.to_string(),
+    }
+}
+
+struct Library {
+    path: PathBuf,
+    url: Option<String>,
+    size: Option<u64>,
+    // Old versions ship natives in separate jars that are extracted before starting
+    natives: bool,
+}
+
+fn collect_libraries(version: &JsonValue, libraries_dir: &Path) -> Vec<Library> {
+    let mut seen = HashSet::new();
+    let mut libraries = Vec::new();
+    for library in version["libraries"].as_array().into_iter().flatten() {
+        if !rules_allow(library.get("rules")) {
+            continue;
+        }
+        let Some(name) = library["name"].as_str() else { continue };
+        if !seen.insert(library_key(name)) {
+            debug!("Skipping {}, the loader brings its own", name);
+            continue;
+        }
+        let artifact = &library["downloads"]["artifact"];
+        if let Some(path) = artifact["path"].as_str() {
+            libraries.push(Library {
+                path: libraries_dir.join(path),
+                url: artifact["url"].as_str().filter(|u| !u.is_empty()).map(str::to_string),
+                size: artifact["size"].as_u64(),
+                natives: false,
+            });
+        } else if library["downloads"].is_null() {
+            // Fabric and Quilt only give the maven coordinates and the repository
+            if let Some(path) = maven_path(name) {
+                let base = library["url"].as_str().unwrap_or(LIBRARIES_URL);
+                libraries.push(Library {
+                    url: Some(format!("{}/{}", base.trim_end_matches('/'), path)),
+                    path: libraries_dir.join(path),
+                    size: None,
+                    natives: false,
+                });
+            }
+        }
+        let classifier = library["natives"][os_name()]
+            .as_str()
+            .map(|c| c.replace("${arch}", if cfg!(target_pointer_width = "64") { "64" } else { "32" }));
+        if let Some(classifier) = classifier {
+            let native = &library["downloads"]["classifiers"][classifier.as_str()];
+            if let Some(path) = native["path"].as_str() {
+                libraries.push(Library {
+                    path: libraries_dir.join(path),
+                    url: native["url"].as_str().map(str::to_string),
+                    size: native["size"].as_u64(),
+                    natives: true,
+                });
+            }
+        }
+    }
+    libraries
+}
+
+fn extract_natives(jar: &Path, natives_dir: &Path) -> Result<(), String> {
+    let file = File::open(jar).map_err(|e| format!("Failed to open {}: {}", jar.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Failed to read {}: {}", jar.display(), e))?;
+    crate::safe_extract::extract_all(
+        &mut archive,
+        natives_dir,
+        |name| (!name.starts_with("META-INF")).then(|| name.to_path_buf()),
+        |_, _| {},
+    )?;
+    Ok(())
+}
+
+// Assets are shared with the launcher, only missing objects are downloaded
+fn ensure_assets(version: &JsonValue, assets_dir: &Path) -> Result<String, String> {
+    let index = &version["assetIndex"];
+    let id = index["id"].as_str().ok_or_else(|| "The version json has no asset index".to_string())?;
+    let index_path = assets_dir.join("indexes").join(format!("{}.json", id));
+    if !is_complete(&index_path, index["size"].as_u64()) {
+        let url = index["url"].as_str().ok_or_else(|| "The asset index has no url".to_string())?;
+        download(url, &index_path)?;
+    }
+    let objects: JsonValue = serde_json::from_str(
+        &fs::read_to_string(&index_path).map_err(|e| format!("Failed to read {}: {}", index_path.display(), e))?,
+    )
+    .map_err(|e| format!("Failed to parse {}: {}", index_path.display(), e))?;
+    let missing: Vec<(String, PathBuf)> = objects["objects"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(_, object)| {
+            let hash = object["hash"].as_str()?;
+            let path = assets_dir.join("objects").join(&hash[..2]).join(hash);
+            (!is_complete(&path, object["size"].as_u64())).then(|| (format!("{}{}/{}", RESOURCES_URL, &hash[..2], hash), path))
+        })
+        .collect();
+    if !missing.is_empty() {
+        info!("Downloading {} missing asset(s)", missing.len());
+        missing.par_iter().try_for_each(|(url, path)| download(url, path))?;
+    }
+    Ok(id.to_string())
+}
+
+// Argument entries are either plain strings or {rules, value} with one or more strings
+fn arguments(entries: &JsonValue) -> Vec<String> {
+    let mut out = Vec::new();
+    for entry in entries.as_array().into_iter().flatten() {
+        match entry {
+            JsonValue::String(arg) => out.push(arg.clone()),
+            JsonValue::Object(_) if rules_allow(entry.get("rules")) => match &entry["value"] {
+                JsonValue::String(arg) => out.push(arg.clone()),
+                JsonValue::Array(args) => out.extend(args.iter().filter_map(|a| a.as_str().map(str::to_string))),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    out
+}
+
+fn substitute(arg: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(arg.to_string(), |arg, (key, value)| arg.replace(&format!("${{{}}}", key), value))
+}
+
+/// Downloads whatever the installation's version still needs and starts the game signed in as
+/// the stored Microsoft account.
+pub fn launch(installation: &Installation) -> Result<Child, String> {
+    let account: Account = ms_auth::current_account()?;
+    let minecraft_dir = get_minecraft_folder();
+    let loader = Loader {
+        r#type: installation.loader_type.clone(),
+        version: installation.loader_version.clone(),
+        minecraft_version: installation.minecraft_version.clone(),
+    };
+    let version_id = loader
+        .version_id()
+        .ok_or_else(|| format!("Unsupported loader {}", installation.loader_type))?;
+    let version = resolve_version(&minecraft_dir, &version_id)?;
+
+    let libraries_dir = minecraft_dir.join("libraries");
+    let libraries = collect_libraries(&version, &libraries_dir);
+    let missing: Vec<&Library> = libraries.iter().filter(|l| !is_complete(&l.path, l.size)).collect();
+    if !missing.is_empty() {
+        info!("Downloading {} missing librar(y/ies)", missing.len());
+        missing.par_iter().try_for_each(|library| match &library.url {
+            Some(url) => download(url, &library.path),
+            None => Err(format!("{} is missing and has no download", library.path.display())),
+        })?;
+    }
+
+    let jar_id = version["jar"].as_str().unwrap_or(&version_id).to_string();
+    let client_jar = minecraft_dir.join("versions").join(&jar_id).join(format!("{}.jar", jar_id));
+    let client = &version["downloads"]["client"];
+    if !is_complete(&client_jar, client["size"].as_u64()) {
+        let url = client["url"].as_str().ok_or_else(|| format!("No download for the {} client", jar_id))?;
+        download(url, &client_jar)?;
+    }
+
+    // Fresh every launch, like the launcher does
+    let natives_dir = minecraft_dir.join("bin").join(&installation.id);
+    let _ = fs::remove_dir_all(&natives_dir);
+    fs::create_dir_all(&natives_dir).map_err(|e| format!("Failed to create {}: {}", natives_dir.display(), e))?;
+    for library in libraries.iter().filter(|l| l.natives) {
+        extract_natives(&library.path, &natives_dir)?;
+    }
+
+    let assets_dir = minecraft_dir.join("assets");
+    let assets_index = ensure_assets(&version, &assets_dir)?;
+
+    let java = match java::find_compatible(&minecraft_dir, &installation.minecraft_version) {
+        Some(install) => install.path,
+        None => {
+            let required = java::required_major(&installation.minecraft_version);
+            warn!("No Java {} found for the direct launch, downloading one", required);
+            java::download_runtime(required)?
+        }
+    };
+
+    let game_dir = get_launcher(&installation.launcher_type)
+        .map(|launcher| get_modpack_root(&launcher, &installation.id))
+        .unwrap_or_else(|_| installation.installation_path.clone());
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    let classpath = libraries
+        .iter()
+        .filter(|l| !l.natives)
+        .map(|l| l.path.to_string_lossy().to_string())
+        .chain(std::iter::once(client_jar.to_string_lossy().to_string()))
+        .collect::<Vec<_>>()
+        .join(separator);
+    let values = [
+        ("auth_player_name", account.username.clone()),
+        ("auth_uuid", account.uuid.clone()),
+        ("auth_access_token", account.access_token().to_string()),
+        ("auth_session", account.access_token().to_string()),
+        ("auth_xuid", account.xuid.clone()),
+        ("clientid", String::new()),
+        ("user_type", "msa".to_string()),
+        ("user_properties", "{}".to_string()),
+        ("version_name", version_id.clone()),
+        ("version_type", version["type"].as_str().unwrap_or("release").to_string()),
+        ("game_directory", game_dir.to_string_lossy().to_string()),
+        ("assets_root", assets_dir.to_string_lossy().to_string()),
+        ("game_assets", assets_dir.to_string_lossy().to_string()),
+        ("assets_index_name", assets_index),
+        ("natives_directory", natives_dir.to_string_lossy().to_string()),
+        ("library_directory", libraries_dir.to_string_lossy().to_string()),
+        ("classpath_separator", separator.to_string()),
+        ("classpath", classpath),
+        ("launcher_name", "wynncraft-overhaul-installer".to_string()),
+        ("launcher_version", env!("CARGO_PKG_VERSION").to_string()),
+    ];
+
+    let mut jvm_args = vec![format!("-Xmx{}M", installation.memory_allocation)];
+    jvm_args.extend(installation.java_args.split_whitespace().map(str::to_string));
+    let (jvm, game) = if version["arguments"].is_object() {
+        (arguments(&version["arguments"]["jvm"]), arguments(&version["arguments"]["game"]))
+    } else {
+        // Before 1.13 there was one string for the game and the launcher added the JVM part
+        let legacy = version["minecraftArguments"].as_str().unwrap_or_default();
+        (
+            vec![
+                "-Djava.library.path=${natives_directory}".to_string(),
+                "-cp".to_string(),
+                "${classpath}".to_string(),
+            ],
+            legacy.split_whitespace().map(str::to_string).collect(),
+        )
+    };
+    jvm_args.extend(jvm.iter().map(|arg| substitute(arg, &values)));
+    let main_class = version["mainClass"]
+        .as_str()
+        .ok_or_else(|| format!("Version {} has no main class", version_id))?;
+    let game_args: Vec<String> = game.iter().map(|arg| substitute(arg, &values)).collect();
+
+    info!("Starting Minecraft {} directly as {}", version_id, account.username);
+    debug!("Java {} with {} JVM and {} game argument(s)", java.display(), jvm_args.len(), game_args.len());
+    installation
+        .game_command(&java)
+        .current_dir(&game_dir)
+        .args(&jvm_args)
+        .arg(main_class)
+        .args(&game_args)
+        .spawn()
+        .map_err(|e| format!("Failed to start Java at {}: {}", java.display(), e))
+}
@@ -0,0 +1,425 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use isahc::config::Configurable;
+use isahc::Request;
+use log::debug;
+use serde::Serialize;
+
+use crate::installation::{self, Installation};
+use crate::verify_remote::{CheckResult, CheckStatus};
+use crate::{
+    build_http_client, config_migration, curseforge, get_installer_dir, get_launcher, get_minecraft_folder, java,
+    loader_repair, profile_reconcile, Config, Launcher, DEFAULT_UNIVERSAL_URL, GH_API, REPO,
+};
+
+// `doctor` runs every health check we know of without asking anything and prints a summary,
+// so a user can paste it on Discord and a support volunteer can tell at a glance what's wrong.
+// It only reads, nothing gets repaired or rewritten.
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+// Below this installs will fail halfway, a full pack with shaders is a few GB
+const MIN_FREE_SPACE: u64 = 1024 * 1024 * 1024;
+const LOW_FREE_SPACE: u64 = 5 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub installer_version: String,
+    pub os: String,
+    pub ok: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    pub checks: Vec<CheckResult>,
+}
+
+fn ok(kind: &str, id: &str, message: impl Into<String>) -> CheckResult {
+    CheckResult::new(kind, id, CheckStatus::Ok, message)
+}
+
+fn warning(kind: &str, id: &str, message: impl Into<String>) -> CheckResult {
+    CheckResult::new(kind, id, CheckStatus::Warning, message)
+}
+
+fn error(kind: &str, id: &str, message: impl Into<String>) -> CheckResult {
+    CheckResult::new(kind, id, CheckStatus::Error, message)
+}
+
+fn check_config(checks: &mut Vec<CheckResult>) -> Option<Config> {
+    let config_path = get_installer_dir().join("config.json");
+    if !config_path.exists() {
+        checks.push(warning("config", "config.json", "No config yet, the installer creates one on first start"));
+        return None;
+    }
+    match fs::read(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))
+        .and_then(|bytes| config_migration::parse_config(&bytes))
+    {
+        Ok((config, migrated)) => {
+            if migrated {
+                checks.push(ok("config", "config.json", "Valid, will be migrated on the next start"));
+            } else {
+                checks.push(ok("config", "config.json", "Valid"));
+            }
+            Some(config)
+        }
+        Err(e) => {
+            checks.push(error(
+                "config",
+                "config.json",
+                format!("{}, the installer will reset it to defaults on the next start", e),
+            ));
+            None
+        }
+    }
+}
+
+fn check_launcher(checks: &mut Vec<CheckResult>, config: Option<&Config>) {
+    let launcher_name = config.map_or("vanilla", |c| c.launcher.as_str());
+    match get_launcher(launcher_name) {
+        Ok(Launcher::Vanilla(_)) => {
+            let minecraft_dir = get_minecraft_folder();
+            if minecraft_dir.join("launcher_profiles.json").is_file() {
+                checks.push(ok("launcher", "vanilla", format!("Minecraft folder at {}", minecraft_dir.display())));
+            } else {
+                checks.push(warning(
+                    "launcher",
+                    "vanilla",
+                    format!("No launcher_profiles.json in {}, start the Minecraft launcher once", minecraft_dir.display()),
+                ));
+            }
+            match crate::launcher::find_minecraft_launcher() {
+                Some(path) => checks.push(ok("launcher", "executable", path.display().to_string())),
+                None => checks.push(warning(
+                    "launcher",
+                    "executable",
+                    "Minecraft launcher not found, installations have to be started from the launcher by hand",
+                )),
+            }
+        }
+        Ok(Launcher::MultiMC(root)) => {
+            checks.push(ok("launcher", launcher_name, format!("Data folder at {}", root.display())));
+        }
+        Err(e) => checks.push(error("launcher", launcher_name, e)),
+    }
+}
+
+fn check_java(checks: &mut Vec<CheckResult>) {
+    // Only the Forge and NeoForge installers need it, the launchers bring their own
+    let java = java::find_java(&get_minecraft_folder());
+    match Command::new(&java).arg("-version").output() {
+        Ok(output) if output.status.success() => {
+            // java -version prints to stderr
+            let version = String::from_utf8_lossy(&output.stderr);
+            let version = version.lines().next().unwrap_or("unknown version").trim().to_string();
+            checks.push(ok("java", &java.display().to_string(), version));
+        }
+        Ok(output) => checks.push(warning(
+            "java",
+            &java.display().to_string(),
+            format!("java -version failed ({}), Forge and NeoForge packs can't be installed", output.status),
+        )),
+        Err(e) => checks.push(warning(
+            "java",
+            &java.display().to_string(),
+            format!("No usable Java found ({}), Forge and NeoForge packs can't be installed", e),
+        )),
+    }
+}
+
+struct Endpoint {
+    id: &'static str,
+    url: String,
+    // Installs fail without it, the others only matter for some packs
+    required: bool,
+    headers: Vec<(&'static str, String)>,
+}
+
+fn endpoints() -> Vec<Endpoint> {
+    let endpoint = |id, url: String, required| Endpoint { id, url, required, headers: Vec::new() };
+    let github_api = format!("{}{}branches", GH_API, REPO);
+    let github_headers = crate::github::auth_header(&github_api).into_iter().collect();
+    let mut endpoints = vec![
+        Endpoint { id: "github-api", url: github_api, required: true, headers: github_headers },
+        endpoint("github-raw", DEFAULT_UNIVERSAL_URL.to_string(), true),
+        endpoint("modrinth", "https://api.modrinth.com/v2/".to_string(), true),
+        endpoint("fabric-meta", "https://meta.fabricmc.net/v2/versions/loader".to_string(), false),
+        endpoint("quilt-meta", "https://meta.quiltmc.org/v3/versions/loader".to_string(), false),
+        endpoint("forge-maven", "https://maven.minecraftforge.net/".to_string(), false),
+        endpoint("neoforge-maven", "https://maven.neoforged.net/releases/".to_string(), false),
+        endpoint("adoptium", "https://api.adoptium.net/v3/info/available_releases".to_string(), false),
+    ];
+    if let Some(key) = curseforge::api_key() {
+        endpoints.push(Endpoint {
+            id: "curseforge",
+            url: format!("{}/games", curseforge::API_URL),
+            required: false,
+            headers: vec![("x-api-key", key)],
+        });
+    }
+    endpoints
+}
+
+fn check_endpoint(endpoint: &Endpoint) -> CheckResult {
+    let fail = |message: String| {
+        if endpoint.required {
+            error("network", endpoint.id, message)
+        } else {
+            warning("network", endpoint.id, message)
+        }
+    };
+    let mut request = Request::get(endpoint.url.as_str()).timeout(REQUEST_TIMEOUT);
+    for (key, value) in &endpoint.headers {
+        request = request.header(*key, value.as_str());
+    }
+    let request = match request.body(()) {
+        Ok(request) => request,
+        Err(e) => return fail(format!("Invalid request for {}: {}", endpoint.url, e)),
+    };
+    let started = std::time::Instant::now();
+    match build_http_client().send(request) {
+        // Maven roots answer 404 on some mirrors, being reachable is what matters there
+        Ok(resp) if resp.status().is_success() || (!endpoint.required && resp.status().as_u16() == 404) => ok(
+            "network",
+            endpoint.id,
+            format!("{} ({} ms)", endpoint.url, started.elapsed().as_millis()),
+        ),
+        Ok(resp) if resp.status().as_u16() == 403 && endpoint.id == "curseforge" => {
+            fail("CurseForge rejected the API key".to_string())
+        }
+        Ok(resp) if endpoint.id == "github-api" && crate::github::is_rate_limited(resp.status().as_u16(), resp.headers()) => {
+            let hint = if crate::github::token().is_some() { "" } else { ", a GitHub token raises the limit" };
+            fail(format!("GitHub API rate limit reached{}", hint))
+        }
+        Ok(resp) => fail(format!("{} returned {}", endpoint.url, resp.status())),
+        Err(e) => fail(format!("{} failed: {}", endpoint.url, e)),
+    }
+}
+
+fn check_connectivity(checks: &mut Vec<CheckResult>) {
+    let endpoints = endpoints();
+    let results: Vec<CheckResult> = std::thread::scope(|scope| {
+        let handles: Vec<_> = endpoints
+            .iter()
+            .map(|endpoint| scope.spawn(move || check_endpoint(endpoint)))
+            .collect();
+        handles
+            .into_iter()
+            .zip(&endpoints)
+            .map(|(handle, endpoint)| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| error("network", endpoint.id, "Check panicked"))
+            })
+            .collect()
+    });
+    checks.extend(results);
+    if curseforge::api_key().is_none() {
+        checks.push(warning(
+            "network",
+            "curseforge",
+            "No CurseForge API key configured, packs with CurseForge components can't be installed",
+        ));
+    }
+}
+
+// Free bytes on the drive holding `path`, there's no portable std api for this
+fn available_space(path: &Path) -> Option<u64> {
+    if cfg!(windows) {
+        let drive = path.to_str()?.chars().next().filter(|c| c.is_ascii_alphabetic())?;
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-Command", &format!("(Get-PSDrive {}).Free", drive)])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    } else {
+        let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+        // Filesystem 1024-blocks Used Available Capacity Mounted on
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let available: u64 = stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+        Some(available * 1024)
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+fn check_disk_space(checks: &mut Vec<CheckResult>) {
+    for (id, path) in [("installer", get_installer_dir()), ("minecraft", get_minecraft_folder())] {
+        // The folder may not exist yet, the drive it will be on is what counts
+        let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+            continue;
+        };
+        match available_space(existing) {
+            Some(free) if free < MIN_FREE_SPACE => checks.push(error(
+                "disk",
+                id,
+                format!("Only {} free for {}", format_bytes(free), path.display()),
+            )),
+            Some(free) if free < LOW_FREE_SPACE => checks.push(warning(
+                "disk",
+                id,
+                format!("{} free for {}, large packs may not fit", format_bytes(free), path.display()),
+            )),
+            Some(free) => checks.push(ok("disk", id, format!("{} free for {}", format_bytes(free), path.display()))),
+            None => checks.push(warning("disk", id, format!("Couldn't determine free space for {}", path.display()))),
+        }
+    }
+}
+
+fn check_installation_loader(checks: &mut Vec<CheckResult>, installation: &Installation) {
+    if !installation.installed || !installation.launcher_type.starts_with("vanilla") {
+        return;
+    }
+    match loader_repair::check(installation) {
+        Ok(loader_repair::LoaderHealth::Ok) => {}
+        Ok(health) => checks.push(warning(
+            "installation",
+            &installation.id,
+            format!("Loader files of '{}' need repair ({:?}), they're restored on the next launch", installation.name, health),
+        )),
+        Err(e) => checks.push(warning("installation", &installation.id, e)),
+    }
+}
+
+fn check_installations(checks: &mut Vec<CheckResult>) {
+    let index = match installation::load_installations_index() {
+        Ok(index) => index,
+        Err(e) => {
+            checks.push(error("installation", "index.json", format!("Failed to read the installations index: {}", e)));
+            return;
+        }
+    };
+
+    let mut healthy = 0;
+    for id in &index.installations {
+        match installation::load_installation(id) {
+            Ok(installation) => {
+                healthy += 1;
+                check_installation_loader(checks, &installation);
+            }
+            Err(e) => checks.push(warning("installation", id, format!("Listed but can't be loaded: {}", e))),
+        }
+    }
+
+    // Folders nothing points at anymore, usually left over from a failed delete
+    let listed: HashSet<&String> = index.installations.iter().collect();
+    if let Ok(entries) = fs::read_dir(installation::get_installations_dir()) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() && !listed.contains(&name) {
+                checks.push(warning(
+                    "installation",
+                    &name,
+                    format!("Orphaned folder {} isn't listed in the index", entry.path().display()),
+                ));
+            }
+        }
+    }
+
+    match profile_reconcile::scan() {
+        Ok(issues) => {
+            for issue in issues {
+                checks.push(warning(
+                    "installation",
+                    &issue.profile_key,
+                    format!("Launcher profile '{}': {}", issue.profile_name, issue.description()),
+                ));
+            }
+        }
+        Err(e) => checks.push(warning("installation", "launcher_profiles.json", e)),
+    }
+
+    checks.push(ok("installation", "index.json", format!("{} installation(s) load fine", healthy)));
+}
+
+pub fn run_checks() -> DoctorReport {
+    let mut checks = Vec::new();
+    let config = check_config(&mut checks);
+    curseforge::configure(config.as_ref().and_then(|c| c.network.curseforge_api_key.as_ref()));
+    crate::ms_auth::configure(config.as_ref().and_then(|c| c.advanced.microsoft_client_id.as_ref()));
+    crate::github::configure(config.as_ref().and_then(|c| c.network.github_token.as_ref()));
+    check_launcher(&mut checks, config.as_ref());
+    check_java(&mut checks);
+    check_connectivity(&mut checks);
+    check_disk_space(&mut checks);
+    check_installations(&mut checks);
+
+    let errors = checks.iter().filter(|c| c.status == CheckStatus::Error).count();
+    let warnings = checks.iter().filter(|c| c.status == CheckStatus::Warning).count();
+    debug!("Doctor finished with {} errors and {} warnings", errors, warnings);
+    DoctorReport {
+        installer_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        ok: errors == 0,
+        errors,
+        warnings,
+        checks,
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    println!("Installer {} on {}\n", report.installer_version, report.os);
+    for check in &report.checks {
+        let status = match check.status {
+            CheckStatus::Ok => " OK ",
+            CheckStatus::Warning => "WARN",
+            CheckStatus::Error => "FAIL",
+        };
+        println!("[{}] {:<13} {:<24} {}", status, check.kind, check.id, check.message);
+    }
+    println!(
+        "\n{} checks, {} warning(s), {} error(s)",
+        report.checks.len(),
+        report.warnings,
+        report.errors
+    );
+}
+
+fn print_usage() {
+    eprintln!("Usage: wynncraft-overhaul-installer doctor [--json]");
+    eprintln!("Exit codes: 0 all good, 1 errors found, 2 invalid usage, 3 only warnings");
+}
+
+/// Entry point for `doctor`. Prints the report on stdout and returns the exit code.
+pub fn run(args: &[String]) -> i32 {
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                print_usage();
+                return 0;
+            }
+            other => {
+                eprintln!("Unknown argument '{}'", other);
+                print_usage();
+                return 2;
+            }
+        }
+    }
+
+    let report = run_checks();
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                return 2;
+            }
+        }
+    } else {
+        print_report(&report);
+    }
+
+    if report.errors > 0 {
+        1
+    } else if report.warnings > 0 {
+        3
+    } else {
+        0
+    }
+}
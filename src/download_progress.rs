@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use tokio::sync::broadcast;
+
+// Every download through download_resumable streams its bytes here. The install screen only knows
+// how many components are done, which jumps a lot when one of them is a 200 MB remote include, so
+// it adds up the bytes of the downloads in flight for a smooth percentage, the speed and an ETA.
+// Extracting remote include zips reports here too, counted in files instead of bytes.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadProgress {
+    pub name: String,
+    pub received: u64,
+    // From Content-Length, None when the server didn't send one
+    pub total: Option<u64>,
+    pub finished: bool,
+    // Extraction progress, received and total are files
+    pub extracting: bool,
+}
+
+lazy_static! {
+    // Events nobody listens to are dropped, a slow listener only misses some of them
+    static ref EVENTS: broadcast::Sender<DownloadProgress> = broadcast::channel(1024).0;
+}
+
+pub fn publish(event: DownloadProgress) {
+    let _ = EVENTS.send(event);
+}
+
+pub fn subscribe() -> broadcast::Receiver<DownloadProgress> {
+    EVENTS.subscribe()
+}
+
+pub fn publish_extraction(name: &str, extracted: u64, total: u64, finished: bool) {
+    publish(DownloadProgress {
+        name: name.to_string(),
+        received: extracted,
+        total: Some(total),
+        finished,
+        extracting: true,
+    });
+}
+
+/// Adds up the events of one install.
+pub struct TransferTracker {
+    active: HashMap<String, (u64, Option<u64>)>,
+    // Zips being extracted, files done and in total
+    extracting: HashMap<String, (u64, u64)>,
+    finished_bytes: u64,
+    started: Instant,
+    // Newest download, shown in the status line
+    current: Option<String>,
+}
+
+impl TransferTracker {
+    pub fn new() -> TransferTracker {
+        TransferTracker {
+            active: HashMap::new(),
+            extracting: HashMap::new(),
+            finished_bytes: 0,
+            started: Instant::now(),
+            current: None,
+        }
+    }
+
+    pub fn apply(&mut self, event: DownloadProgress) {
+        if event.extracting {
+            if event.finished {
+                self.extracting.remove(&event.name);
+            } else {
+                self.extracting.insert(event.name, (event.received, event.total.unwrap_or(0)));
+            }
+            return;
+        }
+        if event.finished {
+            let received = self.active.remove(&event.name).map_or(event.received, |(received, _)| received);
+            self.finished_bytes += received;
+            if self.current.as_deref() == Some(event.name.as_str()) {
+                self.current = self.active.keys().next().cloned();
+            }
+        } else {
+            if !self.active.contains_key(&event.name) {
+                self.current = Some(event.name.clone());
+            }
+            self.active.insert(event.name, (event.received, event.total));
+        }
+    }
+
+    /// Reads whatever arrived since the last call.
+    pub fn drain(&mut self, receiver: &mut broadcast::Receiver<DownloadProgress>) {
+        loop {
+            match receiver.try_recv() {
+                Ok(event) => self.apply(event),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+    }
+
+    pub fn received_bytes(&self) -> u64 {
+        self.finished_bytes + self.active.values().map(|(received, _)| received).sum::<u64>()
+    }
+
+    /// How much of the downloads in flight is done, in components: two halfway downloads are 1.0.
+    pub fn in_flight(&self) -> f64 {
+        self.active
+            .values()
+            .filter_map(|(received, total)| total.filter(|t| *t > 0).map(|t| (*received as f64 / t as f64).min(1.0)))
+            .chain(
+                self.extracting
+                    .values()
+                    .filter(|(_, total)| *total > 0)
+                    .map(|(done, total)| (*done as f64 / *total as f64).min(1.0)),
+            )
+            .sum()
+    }
+
+    pub fn bytes_per_sec(&self) -> f64 {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed < 1.0 {
+            return 0.0;
+        }
+        self.received_bytes() as f64 / elapsed
+    }
+
+    /// Time left at the pace so far, `fraction` is how much of the whole install is done.
+    pub fn eta(&self, fraction: f64) -> Option<Duration> {
+        // The first few percent are mostly setup and say nothing about the pace
+        if !(0.02..1.0).contains(&fraction) {
+            return None;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        Some(Duration::from_secs_f64(elapsed * (1.0 - fraction) / fraction))
+    }
+
+    /// "Downloading Sodium, 1.2 MB of 4.5 MB", None while nothing is downloading or extracting.
+    pub fn current_file(&self) -> Option<String> {
+        let Some(name) = self.current.as_ref() else {
+            let (name, (done, total)) = self.extracting.iter().next()?;
+            return Some(format!("Extracting {}, {} of {} files", name, done, total));
+        };
+        let (received, total) = self.active.get(name)?;
+        Some(match total {
+            Some(total) => format!("Downloading {}, {} of {}", name, format_bytes(*received), format_bytes(*total)),
+            None => format!("Downloading {}, {}", name, format_bytes(*received)),
+        })
+    }
+}
+
+impl Default for TransferTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs < 60 {
+        format!("{}s left", secs.max(1))
+    } else if secs < 3600 {
+        format!("{}m {}s left", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m left", secs / 3600, (secs % 3600) / 60)
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use futures::AsyncReadExt;
+use isahc::{AsyncBody, Response};
+use lazy_static::lazy_static;
+use log::debug;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::{NetworkSettings, CONCURRENCY};
+
+// Every download of an install (mods, includes, remote includes) takes a slot here first, so the
+// "concurrent downloads" setting holds however the downloads are started, and the bytes they
+// receive share one bandwidth budget. Changed settings apply to downloads started afterwards.
+
+pub const MAX_PARALLEL: usize = 32;
+// How much read_body takes before asking the bandwidth budget again
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub struct DownloadQueue {
+    // Replaced when the limit changes, downloads holding a permit of the old one just finish
+    slots: RwLock<Arc<Semaphore>>,
+    max_parallel: AtomicUsize,
+    // Bytes per second, 0 means unlimited
+    bytes_per_sec: AtomicU64,
+    // When the bandwidth budget has room again
+    next_free: Mutex<Instant>,
+}
+
+/// Held while a download runs, the slot is free again once it's dropped.
+pub struct DownloadSlot {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl DownloadQueue {
+    fn new(max_parallel: usize) -> DownloadQueue {
+        DownloadQueue {
+            slots: RwLock::new(Arc::new(Semaphore::new(max_parallel))),
+            max_parallel: AtomicUsize::new(max_parallel),
+            bytes_per_sec: AtomicU64::new(0),
+            next_free: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn set_limits(&self, max_parallel: usize, bandwidth_limit_kbps: Option<u64>) {
+        let max_parallel = max_parallel.clamp(1, MAX_PARALLEL);
+        if self.max_parallel.swap(max_parallel, Ordering::Relaxed) != max_parallel {
+            let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+            *slots = Arc::new(Semaphore::new(max_parallel));
+        }
+        let bytes_per_sec = bandwidth_limit_kbps.unwrap_or(0).saturating_mul(1024);
+        self.bytes_per_sec.store(bytes_per_sec, Ordering::Relaxed);
+    }
+
+    pub fn max_parallel(&self) -> usize {
+        self.max_parallel.load(Ordering::Relaxed)
+    }
+
+    /// Waits for a free download slot.
+    pub async fn acquire(&self) -> DownloadSlot {
+        let slots = self.slots.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let permit = slots
+            .acquire_owned()
+            .await
+            .expect("Download queue semaphore is never closed");
+        DownloadSlot { _permit: permit }
+    }
+
+    /// Waits until `bytes` fit in the bandwidth budget, returns right away without a limit.
+    pub async fn throttle(&self, bytes: usize) {
+        let rate = self.bytes_per_sec.load(Ordering::Relaxed);
+        if rate == 0 || bytes == 0 {
+            return;
+        }
+        let wait = {
+            let mut next_free = self.next_free.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            // Idle time isn't saved up, a limit of 500 KiB/s never allows a burst above it
+            let start = (*next_free).max(now);
+            *next_free = start + Duration::from_secs_f64(bytes as f64 / rate as f64);
+            start - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+lazy_static! {
+    // Shared by every install, there is one connection to share
+    pub static ref QUEUE: DownloadQueue = DownloadQueue::new(CONCURRENCY);
+}
+
+// Mirrors config.network, set at startup and whenever settings are saved
+pub fn configure(network: &NetworkSettings) {
+    QUEUE.set_limits(network.max_concurrent_downloads, network.bandwidth_limit_kbps);
+    debug!(
+        "Download queue: {} at once, bandwidth limit {:?} KiB/s",
+        QUEUE.max_parallel(),
+        network.bandwidth_limit_kbps
+    );
+}
+
+/// Reads the whole body of `response` within the bandwidth budget. The budget is asked chunk by
+/// chunk as the body arrives, waiting only once it was all received wouldn't limit anything.
+pub async fn read_body(response: &mut Response<AsyncBody>) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = response.body_mut().read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        QUEUE.throttle(read).await;
+        body.extend_from_slice(&buf[..read]);
+    }
+    Ok(body)
+}
@@ -0,0 +1,184 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Duration as ChronoDuration, Local, Timelike, Utc};
+use log::{debug, error, info, warn};
+
+use crate::{CachedHttpClient, NetworkSettings};
+
+// Big updates don't have to happen right away. "Update tonight" stores the start of the next
+// off-peak window on the installation and the scheduled update loop runs it then. Downloads also
+// wait while Windows reports a metered connection (phone hotspot, capped plans) if the user asked
+// for it: the .part file stays where it is and the download resumes from there once the
+// connection is unmetered again.
+
+const METERED_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const SCHEDULE_CHECK_INTERVAL_SECS: u64 = 60;
+
+// Mirror config.network, set at startup and whenever preferences are saved
+static PAUSE_ON_METERED: AtomicBool = AtomicBool::new(false);
+static OFF_PEAK_START: AtomicU8 = AtomicU8::new(2);
+static OFF_PEAK_END: AtomicU8 = AtomicU8::new(6);
+
+// Asking Windows takes a moment, the answer is reused for a while
+static METERED: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+// Set while some download waits for an unmetered connection, shown by the install screen
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub fn configure(network: &NetworkSettings) {
+    PAUSE_ON_METERED.store(network.pause_on_metered, Ordering::Relaxed);
+    OFF_PEAK_START.store(network.off_peak_start_hour.min(23), Ordering::Relaxed);
+    OFF_PEAK_END.store(network.off_peak_end_hour.min(23), Ordering::Relaxed);
+    debug!(
+        "Pause on metered connections: {}, off-peak hours {}-{}",
+        network.pause_on_metered, network.off_peak_start_hour, network.off_peak_end_hour
+    );
+}
+
+#[cfg(windows)]
+fn query_metered() -> bool {
+    use std::process::Command;
+
+    // NetworkCostType is Unrestricted, Fixed, Variable or Unknown. Fixed and Variable are metered
+    let script = "[void][Windows.Networking.Connectivity.NetworkInformation,Windows,ContentType=WindowsRuntime]; \
+        $profile = [Windows.Networking.Connectivity.NetworkInformation]::GetInternetConnectionProfile(); \
+        if ($profile) { $profile.GetConnectionCost().NetworkCostType }";
+    match Command::new("powershell").args(["-NoProfile", "-Command", script]).output() {
+        Ok(output) => {
+            let cost = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            debug!("Network cost type: {:?}", cost);
+            cost == "Fixed" || cost == "Variable"
+        }
+        Err(e) => {
+            warn!("Failed to check whether the connection is metered: {}", e);
+            false
+        }
+    }
+}
+
+// Other platforms don't tell us, downloads are never paused there
+#[cfg(not(windows))]
+fn query_metered() -> bool {
+    false
+}
+
+fn is_metered() -> bool {
+    if let Ok(cached) = METERED.lock() {
+        if let Some((checked, metered)) = *cached {
+            if checked.elapsed() < METERED_CHECK_INTERVAL {
+                return metered;
+            }
+        }
+    }
+    let metered = query_metered();
+    if let Ok(mut cached) = METERED.lock() {
+        *cached = Some((Instant::now(), metered));
+    }
+    metered
+}
+
+/// Whether downloads should stop for now, checked between chunks.
+pub async fn should_pause() -> bool {
+    if !PAUSE_ON_METERED.load(Ordering::Relaxed) {
+        return false;
+    }
+    tokio::task::spawn_blocking(is_metered).await.unwrap_or(false)
+}
+
+/// Whether a download is waiting for an unmetered connection right now.
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::Relaxed)
+}
+
+/// Returns once downloads may run, right away unless pausing on metered connections applies.
+pub async fn wait_until_allowed(name: &str) {
+    if !should_pause().await {
+        return;
+    }
+    info!("Connection is metered, pausing '{}' until it isn't", name);
+    PAUSED.store(true, Ordering::Relaxed);
+    while should_pause().await {
+        tokio::time::sleep(METERED_CHECK_INTERVAL).await;
+    }
+    PAUSED.store(false, Ordering::Relaxed);
+    info!("Connection is unmetered again, resuming '{}'", name);
+}
+
+/// Start of the next off-peak window, now if we're inside it.
+pub fn next_off_peak(now: DateTime<Local>) -> DateTime<Utc> {
+    let start = OFF_PEAK_START.load(Ordering::Relaxed) as u32;
+    let end = OFF_PEAK_END.load(Ordering::Relaxed) as u32;
+    let hour = now.hour();
+    // The window may wrap around midnight, 22 -> 6
+    let inside = if start <= end { hour >= start && hour < end } else { hour >= start || hour < end };
+    if inside {
+        return now.with_timezone(&Utc);
+    }
+    let today = now
+        .with_hour(start)
+        .and_then(|t| t.with_minute(0))
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(now);
+    let next = if today > now { today } else { today + ChronoDuration::days(1) };
+    next.with_timezone(&Utc)
+}
+
+async fn run_scheduled_update(http_client: &CachedHttpClient, installation_id: &str) -> Result<String, String> {
+    let mut installation = crate::installation::load_installation(installation_id)?;
+    // Cleared first, a crash halfway through shouldn't restart it in a loop
+    installation.scheduled_update = None;
+    installation.save()?;
+
+    let manifest = installation
+        .load_universal_manifest(http_client)
+        .await
+        .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
+    info!("Running the scheduled update of '{}' to {}", installation.name, manifest.modpack_version);
+    if let Some(features) = crate::feature_profiles::take_update_profile(&mut installation) {
+        installation.apply_feature_profile(features);
+    }
+    // The selection saved when the update was scheduled
+    let scheduled_selection = !installation.pending_features.is_empty();
+    if scheduled_selection {
+        installation.enabled_features = installation.pending_features.clone();
+    }
+    installation
+        .install_or_update_with_manifest(http_client, &manifest, &installation.pack_source().branch(), || {})
+        .await?;
+    if scheduled_selection {
+        installation.commit_installation();
+    }
+    installation.universal_version = manifest.modpack_version.clone();
+    installation.preset_update_available = false;
+    installation.mark_installed()?;
+    Ok(installation.name)
+}
+
+/// Runs forever, updating installations whose scheduled update time has come. Updates that came
+/// due while the installer was closed run on the first check.
+pub async fn run_scheduled_update_loop<F: FnMut(String)>(mut on_updated: F) {
+    let http_client = CachedHttpClient::new();
+    loop {
+        let now = Utc::now();
+        match crate::installation::load_all_installations() {
+            Ok(installations) => {
+                for installation in installations.iter().filter(|i| i.scheduled_update.map_or(false, |due| due <= now)) {
+                    match run_scheduled_update(&http_client, &installation.id).await {
+                        Ok(name) => {
+                            crate::watch_mode::notify("Update finished", &format!("{} was updated while you were away", name));
+                            on_updated(installation.id.clone());
+                        }
+                        Err(e) => {
+                            error!("Scheduled update of {} failed: {}", installation.id, e);
+                            crate::watch_mode::notify(&format!("Update of {} failed", installation.name), &e);
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to check for scheduled updates: {}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(SCHEDULE_CHECK_INTERVAL_SECS)).await;
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use log::{debug, info};
+
+use crate::installation::{self, Installation};
+use crate::universal::UniversalManifest;
+use crate::CachedHttpClient;
+
+// Everything a tool needs to set up and start the pack without the GUI: create an installation,
+// pick its features, install it with progress and launch it. The `install` subcommand is built on
+// it, and the library exports it for tools written in Rust, see examples/headless_install.rs. Other
+// tools drive the same thing by running the installer headless.
+
+/// Progress of an install. `total` counts every component and task that may run, components an
+/// update keeps aren't reported, so `done` can finish below it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InstallProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+pub struct Installer {
+    http_client: CachedHttpClient,
+    manifest: UniversalManifest,
+}
+
+impl Installer {
+    /// Applies the installer's settings and loads the universal manifest, `manifest_url` replaces
+    /// the default one.
+    pub async fn connect(manifest_url: Option<&str>) -> Result<Installer, String> {
+        // Embedding tools never went through run(), proxy, mirrors and offline mode come from the
+        // installer's config all the same
+        let (config, _) = crate::config_migration::load_config(&crate::get_installer_dir().join("config.json"));
+        crate::apply_config(&config);
+        let http_client = CachedHttpClient::new();
+        let manifest = crate::universal::load_universal_manifest(&http_client, manifest_url)
+            .await
+            .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
+        Ok(Installer { http_client, manifest })
+    }
+
+    /// What a new installation starts with, like the GUI's default selection.
+    pub fn default_features(&self) -> Vec<String> {
+        let manifest = &self.manifest;
+        let defaults = manifest
+            .mods
+            .iter()
+            .chain(&manifest.shaderpacks)
+            .chain(&manifest.resourcepacks)
+            .filter(|c| c.default_enabled)
+            .map(|c| c.id.clone())
+            .chain(manifest.include.iter().filter(|i| i.default_enabled && !i.id.is_empty()).map(|i| i.id.clone()))
+            .chain(manifest.remote_include.iter().filter(|r| r.default_enabled).map(|r| r.id.clone()));
+        std::iter::once("default".to_string()).chain(defaults).collect()
+    }
+
+    /// `base` plus the components in `extra` and everything they depend on.
+    pub fn resolve_features(&self, base: &[String], extra: &[String]) -> Result<Vec<String>, String> {
+        let manifest = &self.manifest;
+        let known: Vec<&String> = manifest
+            .mods
+            .iter()
+            .chain(&manifest.shaderpacks)
+            .chain(&manifest.resourcepacks)
+            .map(|c| &c.id)
+            .chain(manifest.include.iter().map(|i| &i.id))
+            .chain(manifest.remote_include.iter().map(|r| &r.id))
+            .collect();
+        if let Some(unknown) = extra.iter().find(|id| !known.contains(id)) {
+            return Err(format!("'{}' is not a component of {}", unknown, manifest.name));
+        }
+
+        let mut features: Vec<String> = base.iter().chain(extra).cloned().collect();
+        for feature in features.clone() {
+            crate::resolve_dependencies(&feature, &mut features, manifest);
+        }
+        let mut seen = std::collections::HashSet::new();
+        features.retain(|f| seen.insert(f.clone()));
+        debug!("Resolved features: {:?}", features);
+        Ok(features)
+    }
+
+    /// Creates and registers a new installation. `launcher` is a launcher as stored in the config,
+    /// "vanilla", "multimc-MultiMC", "multimc-PrismLauncher" or "custom-<path>".
+    pub fn create_installation(&self, name: &str, launcher: &str, features: Vec<String>) -> Result<Installation, String> {
+        crate::get_launcher(launcher)?;
+        let manifest = &self.manifest;
+        let mut installation = Installation::new_custom(
+            name.to_string(),
+            manifest.minecraft_version.clone(),
+            manifest.loader.r#type.clone(),
+            manifest.loader.version.clone(),
+            launcher.to_string(),
+            manifest.modpack_version.clone(),
+        );
+        installation.enabled_features = features.clone();
+        installation.pending_features = features.clone();
+        installation.pre_install_features = features;
+        installation.is_custom_configuration = true;
+        installation.mark_as_fresh();
+        installation::register_installation(&installation)?;
+        installation.save()?;
+        info!("Created installation '{}' ({})", installation.name, installation.id);
+        Ok(installation)
+    }
+
+    /// Installs the installation, or updates it when it's installed already.
+    pub async fn install<F: FnMut(InstallProgress) + Clone>(
+        &self,
+        installation: &mut Installation,
+        on_progress: F,
+    ) -> Result<(), String> {
+        let resolved = installation.resolve_manifest(&self.manifest)?;
+        let manifest = crate::universal::universal_to_manifest(&resolved, installation.enabled_features.clone());
+        let features = &installation.enabled_features;
+        let wanted = |id: &str, optional: bool| id.is_empty() || id == "default" || !optional || features.iter().any(|f| f == id);
+        let total = manifest.mods.iter().filter(|m| wanted(&m.id, true)).count()
+            + manifest.shaderpacks.iter().filter(|s| wanted(&s.id, true)).count()
+            + manifest.resourcepacks.iter().filter(|r| wanted(&r.id, true)).count()
+            + manifest.include.iter().filter(|i| wanted(&i.id, i.optional)).count()
+            + manifest.remote_include.iter().flatten().filter(|r| wanted(&r.id, r.optional)).count()
+            // Manifest, icon, launcher profile and loader
+            + 4;
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let progress_callback = {
+            let mut on_progress = on_progress.clone();
+            let done = done.clone();
+            move || {
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                on_progress(InstallProgress { done, total });
+            }
+        };
+        installation
+            .install_or_update_with_manifest(&self.http_client, &self.manifest, "master", progress_callback)
+            .await?;
+        installation.universal_version = self.manifest.modpack_version.clone();
+        installation.mark_installed()
+    }
+}
+
+/// Starts the installation in its launcher.
+pub fn launch(installation: &Installation) -> Result<(), String> {
+    crate::launch_modpack(&installation.id)
+}
+
+fn print_usage() {
+    eprintln!("Usage: wynncraft-overhaul-installer install (--id <installation id> | --name <name>) [--launcher <launcher>] [--feature <id>]... [--manifest <url>] [--launch]");
+    eprintln!("  --id        install or update an existing installation");
+    eprintln!("  --name      create a new installation with the default components");
+    eprintln!("  --launcher  launcher of a new installation, the configured one by default");
+    eprintln!("  --feature   turn on an optional component, can be repeated");
+    eprintln!("Exit codes: 0 installed, 1 install failed, 2 invalid usage");
+}
+
+/// Entry point for `install`. Progress goes to stdout one line per step.
+pub fn run(args: &[String]) -> i32 {
+    let mut id = None;
+    let mut name = None;
+    let mut launcher = None;
+    let mut extra_features = Vec::new();
+    let mut manifest_url = None;
+    let mut launch_after = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--id" => id = iter.next().cloned(),
+            "--name" => name = iter.next().cloned(),
+            "--launcher" => launcher = iter.next().cloned(),
+            "--feature" => extra_features.extend(iter.next().cloned()),
+            "--manifest" => manifest_url = iter.next().cloned(),
+            "--launch" => launch_after = true,
+            "-h" | "--help" => {
+                print_usage();
+                return 0;
+            }
+            other => {
+                eprintln!("Unknown argument '{}'", other);
+                print_usage();
+                return 2;
+            }
+        }
+    }
+    if id.is_some() == name.is_some() {
+        eprintln!("Pass either --id or --name");
+        print_usage();
+        return 2;
+    }
+
+    let (config, _) = crate::config_migration::load_config(&crate::get_installer_dir().join("config.json"));
+    crate::apply_config(&config);
+    let launcher = launcher.unwrap_or_else(|| config.launcher.clone());
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 2;
+        }
+    };
+    let result = runtime.block_on(async {
+        let installer = Installer::connect(manifest_url.as_deref()).await?;
+        let mut installation = match &id {
+            Some(id) => {
+                let mut installation = installation::load_installation(id)?;
+                installation.enabled_features = installer.resolve_features(&installation.enabled_features, &extra_features)?;
+                installation
+            }
+            None => {
+                let features = installer.resolve_features(&installer.default_features(), &extra_features)?;
+                installer.create_installation(name.as_deref().unwrap_or_default(), &launcher, features)?
+            }
+        };
+        installer
+            .install(&mut installation, |progress: InstallProgress| {
+                println!("{}/{}", progress.done, progress.total);
+            })
+            .await?;
+        println!("Installed '{}' ({}) version {}", installation.name, installation.id, installation.universal_version);
+        if launch_after {
+            launch(&installation)?;
+        }
+        Ok::<(), String>(())
+    });
+
+    match result {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
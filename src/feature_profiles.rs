@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::installation::{get_installations_dir, Installation};
+
+// Presets come from the pack, profiles are the user's own: named feature selections ("raids",
+// "low end laptop") kept next to installation.json so switching back and forth doesn't mean
+// toggling twenty cards. A profile can also be queued for the next update, which then installs
+// that selection instead of the current one.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureProfile {
+    pub name: String,
+    pub enabled_features: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn profiles_path(installation_id: &str) -> PathBuf {
+    get_installations_dir().join(installation_id).join("profiles.json")
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Profiles of an installation, sorted by name. Missing file means none.
+pub fn load(installation_id: &str) -> Result<Vec<FeatureProfile>, String> {
+    let path = profiles_path(installation_id);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let mut profiles: Vec<FeatureProfile> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    profiles.sort_by_key(|p| p.name.to_lowercase());
+    Ok(profiles)
+}
+
+/// Replaces all profiles of an installation, used when they're imported from another computer.
+pub fn write(installation_id: &str, profiles: &[FeatureProfile]) -> Result<(), String> {
+    let path = profiles_path(installation_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(profiles).map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn check_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name can't be empty".to_string());
+    }
+    if name.chars().count() > 40 {
+        return Err("Profile name is too long".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// Saves `features` under `name`, replacing a profile with the same name.
+pub fn save(installation_id: &str, name: &str, features: &[String]) -> Result<(), String> {
+    let name = check_name(name)?;
+    let mut profiles = load(installation_id)?;
+    profiles.retain(|p| !same_name(&p.name, &name));
+    profiles.push(FeatureProfile {
+        name: name.clone(),
+        enabled_features: features.to_vec(),
+        updated_at: Utc::now(),
+    });
+    write(installation_id, &profiles)?;
+    info!("Saved feature profile '{}' of {} ({} features)", name, installation_id, features.len());
+    Ok(())
+}
+
+pub fn rename(installation_id: &str, old: &str, new: &str) -> Result<(), String> {
+    let new = check_name(new)?;
+    let mut profiles = load(installation_id)?;
+    if !same_name(old, &new) && profiles.iter().any(|p| same_name(&p.name, &new)) {
+        return Err(format!("There already is a profile called '{}'", new));
+    }
+    let profile = profiles.iter_mut()
+        .find(|p| same_name(&p.name, old))
+        .ok_or_else(|| format!("Profile '{}' doesn't exist", old))?;
+    profile.name = new.clone();
+    write(installation_id, &profiles)?;
+    // A queued update follows the rename
+    let mut installation = crate::installation::load_installation(installation_id)?;
+    if installation.next_update_profile.as_deref().map_or(false, |queued| same_name(queued, old)) {
+        installation.next_update_profile = Some(new);
+        installation.save()?;
+    }
+    Ok(())
+}
+
+pub fn delete(installation_id: &str, name: &str) -> Result<(), String> {
+    let mut profiles = load(installation_id)?;
+    profiles.retain(|p| !same_name(&p.name, name));
+    write(installation_id, &profiles)?;
+    let mut installation = crate::installation::load_installation(installation_id)?;
+    if installation.next_update_profile.as_deref().map_or(false, |queued| same_name(queued, name)) {
+        installation.next_update_profile = None;
+        installation.save()?;
+    }
+    debug!("Deleted feature profile '{}' of {}", name, installation_id);
+    Ok(())
+}
+
+/// The profile with exactly these features, order doesn't matter.
+pub fn matching<'a>(profiles: &'a [FeatureProfile], features: &[String]) -> Option<&'a FeatureProfile> {
+    let features: HashSet<&String> = features.iter().collect();
+    profiles.iter().find(|p| p.enabled_features.iter().collect::<HashSet<_>>() == features)
+}
+
+/// Features of the profile queued for the next update, clearing the queue. Called right before
+/// an update installs, None when nothing is queued or the profile is gone.
+pub fn take_update_profile(installation: &mut Installation) -> Option<Vec<String>> {
+    let name = installation.next_update_profile.take()?;
+    if let Err(e) = installation.save() {
+        warn!("Failed to clear the queued profile of {}: {}", installation.id, e);
+    }
+    let profiles = match load(&installation.id) {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            warn!("Failed to load feature profiles of {}: {}", installation.id, e);
+            return None;
+        }
+    };
+    match profiles.into_iter().find(|p| same_name(&p.name, &name)) {
+        Some(profile) => {
+            info!("Applying feature profile '{}' to the update of {}", profile.name, installation.id);
+            Some(profile.enabled_features)
+        }
+        None => {
+            warn!("Feature profile '{}' queued for {} doesn't exist anymore", name, installation.id);
+            None
+        }
+    }
+}
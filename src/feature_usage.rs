@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use isahc::AsyncReadResponseExt;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::universal::UniversalManifest;
+use crate::{CachedHttpClient, DEFAULT_UNIVERSAL_URL};
+
+// Opt-in statistics on which optional components people actually use, so the pack team can tell
+// which ones deserve to be on by default and which nobody misses. Only the pack uuid, version and
+// the enabled optional component ids are sent, never the installation id or anything about the user.
+
+pub const FEATURE_USAGE_ACTION: &str = "feature_usage";
+const SUMMARY_URL: &str = "https://tracking.commander07.workers.dev/summary";
+const PROJECT_ID: &str = "55db8403a4f24f3aa5afd33fd1962888";
+
+// Components used by more than this share of installs are worth turning on by default
+const PROMOTE_THRESHOLD: f64 = 0.75;
+// ... and optional ones below this might not be worth maintaining
+const DROP_THRESHOLD: f64 = 0.05;
+
+// Mirrors config.privacy, set at startup and whenever preferences are saved
+static CONSENT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_consent(enabled: bool) {
+    debug!("Feature usage statistics {}", if enabled { "enabled" } else { "disabled" });
+    CONSENT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn has_consent() -> bool {
+    CONSENT.load(Ordering::Relaxed)
+}
+
+/// The enabled optional component ids, sorted so identical setups produce identical events.
+fn optional_feature_ids(manifest: &UniversalManifest, enabled_features: &[String]) -> Vec<String> {
+    let mut ids: Vec<String> = manifest
+        .get_all_optional_components()
+        .into_iter()
+        .map(|c| c.id)
+        .filter(|id| enabled_features.contains(id))
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+pub fn report_installed_features(manifest: &UniversalManifest, enabled_features: &[String]) {
+    if !has_consent() {
+        return;
+    }
+    crate::Analytics::record(crate::Event::FeatureUsage {
+        uuid: manifest.uuid.clone(),
+        modpack_version: manifest.modpack_version.clone(),
+        features: optional_feature_ids(manifest, enabled_features),
+    });
+}
+
+// What the tracking worker aggregates from the feature_usage events
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageSummary {
+    // Number of installs that reported
+    pub total: u64,
+    // Component id -> number of installs that had it enabled
+    pub features: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Suggestion {
+    Promote,
+    Keep,
+    Drop,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureUsage {
+    pub id: String,
+    pub name: String,
+    pub default_enabled: bool,
+    pub installs: u64,
+    pub share: f64,
+    pub suggestion: Suggestion,
+}
+
+/// Joins the raw counts with the manifest so every optional component shows up, even unused ones.
+pub fn summarize(manifest: &UniversalManifest, summary: &UsageSummary) -> Vec<FeatureUsage> {
+    let mut usage: Vec<FeatureUsage> = manifest
+        .get_all_optional_components()
+        .into_iter()
+        .map(|component| {
+            let installs = summary.features.get(&component.id).copied().unwrap_or(0);
+            let share = if summary.total == 0 {
+                0.0
+            } else {
+                installs as f64 / summary.total as f64
+            };
+            let suggestion = if !component.default_enabled && share >= PROMOTE_THRESHOLD {
+                Suggestion::Promote
+            } else if share <= DROP_THRESHOLD {
+                Suggestion::Drop
+            } else {
+                Suggestion::Keep
+            };
+            FeatureUsage {
+                id: component.id,
+                name: component.name,
+                default_enabled: component.default_enabled,
+                installs,
+                share,
+                suggestion,
+            }
+        })
+        .collect();
+    usage.sort_by(|a, b| b.installs.cmp(&a.installs).then_with(|| a.id.cmp(&b.id)));
+    usage
+}
+
+pub async fn fetch_summary(http_client: &CachedHttpClient, endpoint: &str, pack_uuid: &str) -> Result<UsageSummary, String> {
+    let url = format!(
+        "{}?projectId={}&action={}&dataSourceId={}",
+        endpoint, PROJECT_ID, FEATURE_USAGE_ACTION, pack_uuid
+    );
+    let mut response = http_client
+        .get_nocache(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch usage summary: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Usage summary request returned {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read usage summary: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("Failed to parse usage summary: {}", e))
+}
+
+fn print_usage() {
+    eprintln!("Usage: feature-usage [--manifest <url>] [--endpoint <url>] [--json]");
+    eprintln!("Prints how many installs enable each optional component of the manifest.");
+}
+
+pub fn run(args: &[String]) -> i32 {
+    let mut manifest_url = DEFAULT_UNIVERSAL_URL.to_string();
+    let mut endpoint = SUMMARY_URL.to_string();
+    let mut json = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--manifest" => manifest_url = iter.next().cloned().unwrap_or(manifest_url),
+            "--endpoint" => endpoint = iter.next().cloned().unwrap_or(endpoint),
+            "--json" => json = true,
+            "-h" | "--help" => {
+                print_usage();
+                return 0;
+            }
+            other => {
+                eprintln!("Unknown argument '{}'", other);
+                print_usage();
+                return 2;
+            }
+        }
+    }
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 2;
+        }
+    };
+    let result = runtime.block_on(async {
+        let http_client = CachedHttpClient::new();
+        let manifest = crate::universal::load_universal_manifest(&http_client, Some(&manifest_url))
+            .await
+            .map_err(|e| format!("Failed to load manifest: {}", e.message))?;
+        let summary = fetch_summary(&http_client, &endpoint, &manifest.uuid).await?;
+        info!("Usage summary covers {} installs", summary.total);
+        Ok::<_, String>((summary.total, summarize(&manifest, &summary)))
+    });
+    let (total, usage) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&serde_json::json!({ "total": total, "features": usage })) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize summary: {}", e);
+                return 2;
+            }
+        }
+        return 0;
+    }
+
+    println!("{} installs reported their optional components\n", total);
+    println!("{:<32} {:>8} {:>7}  {:<8} {}", "component", "installs", "share", "default", "suggestion");
+    for feature in &usage {
+        let suggestion = match feature.suggestion {
+            Suggestion::Promote => "make default?",
+            Suggestion::Drop => "rarely used",
+            Suggestion::Keep => "",
+        };
+        println!(
+            "{:<32} {:>8} {:>6.1}%  {:<8} {}",
+            feature.id,
+            feature.installs,
+            feature.share * 100.0,
+            if feature.default_enabled { "yes" } else { "no" },
+            suggestion
+        );
+    }
+    0
+}
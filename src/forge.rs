@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use isahc::ReadResponseExt;
+use log::{debug, info, warn};
+
+use crate::{build_http_client, get_installer_dir};
+
+// Forge and NeoForge can't be installed by dropping a version json into .minecraft/versions like
+// fabric and quilt, their installer patches the game jar. We download the official installer and
+// run it headless against the minecraft folder, which needs a Java runtime. Only the modern
+// installers (Minecraft 1.13+) support that.
+
+const INSTALLERS_DIR: &str = "loader-installers";
+
+pub fn is_forge_like(loader_type: &str) -> bool {
+    matches!(loader_type, "forge" | "neoforge")
+}
+
+/// Version the installer creates under .minecraft/versions.
+pub fn version_id(loader_type: &str, minecraft_version: &str, version: &str) -> Option<String> {
+    match loader_type {
+        "forge" => Some(format!("{}-forge-{}", minecraft_version, version)),
+        "neoforge" => Some(format!("neoforge-{}", version)),
+        _ => None,
+    }
+}
+
+pub fn installer_url(loader_type: &str, minecraft_version: &str, version: &str) -> Option<String> {
+    match loader_type {
+        "forge" => Some(format!(
+            "https://maven.minecraftforge.net/net/minecraftforge/forge/{0}-{1}/forge-{0}-{1}-installer.jar",
+            minecraft_version, version
+        )),
+        "neoforge" => Some(format!(
+            "https://maven.neoforged.net/releases/net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar",
+            version
+        )),
+        _ => None,
+    }
+}
+
+/// MultiMC/Prism component uid, they install the loader themselves.
+pub fn mmc_uid(loader_type: &str) -> Option<&'static str> {
+    match loader_type {
+        "forge" => Some("net.minecraftforge"),
+        "neoforge" => Some("net.neoforged"),
+        _ => None,
+    }
+}
+
+fn download_installer(url: &str, target: &Path) -> Result<(), String> {
+    if target.is_file() {
+        debug!("Using cached loader installer {}", target.display());
+        return Ok(());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut resp = build_http_client()
+        .get(url)
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if resp.status() != 200 {
+        return Err(format!("Failed to download {}: status {}", url, resp.status()));
+    }
+    // Write next to the target first so an interrupted download isn't picked up next time
+    let tmp = target.with_extension("part");
+    resp.copy_to_file(&tmp)
+        .map_err(|e| format!("Failed to save {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, target).map_err(|e| format!("Failed to save {}: {}", target.display(), e))
+}
+
+/// Runs the Forge/NeoForge installer for `root`, a no-op when the version is already installed.
+pub fn install(loader_type: &str, minecraft_version: &str, version: &str, root: &Path) -> Result<PathBuf, String> {
+    let (Some(version_id), Some(url)) = (
+        version_id(loader_type, minecraft_version, version),
+        installer_url(loader_type, minecraft_version, version),
+    ) else {
+        return Err(format!("Unsupported loader '{}'", loader_type));
+    };
+    let version_dir = root.join("versions").join(&version_id);
+    if crate::loader_repair::check_files(root, &version_id, false) == crate::loader_repair::LoaderHealth::Ok {
+        return Ok(version_dir);
+    }
+
+    let installer = get_installer_dir()
+        .join(INSTALLERS_DIR)
+        .join(url.rsplit('/').next().unwrap_or("installer.jar"));
+    download_installer(&url, &installer)?;
+
+    // The installer refuses to run without a launcher_profiles.json in the target
+    let profiles = root.join("launcher_profiles.json");
+    if !profiles.exists() {
+        fs::create_dir_all(root).map_err(|e| format!("Failed to create {}: {}", root.display(), e))?;
+        fs::write(&profiles, r#"{"profiles":{}}"#)
+            .map_err(|e| format!("Failed to create {}: {}", profiles.display(), e))?;
+    }
+
+    // Newer installers need the Java the version runs on, any Java does when none fits
+    let java = crate::java::find_compatible(root, minecraft_version)
+        .map(|install| install.path)
+        .unwrap_or_else(|| crate::java::find_java(root));
+    let install_flag = if loader_type == "forge" { "--installClient" } else { "--install-client" };
+    info!("Running {} installer {} with {}", loader_type, installer.display(), java.display());
+    let output = Command::new(&java)
+        .arg("-jar")
+        .arg(&installer)
+        .arg(install_flag)
+        .arg(root)
+        .current_dir(installer.parent().unwrap_or(root))
+        .output()
+        .map_err(|e| format!("Couldn't run Java ({}), is it installed? {}", java.display(), e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        warn!("{} installer output:\n{}\n{}", loader_type, stdout, stderr);
+        // A broken installer download would fail the same way every time
+        let _ = fs::remove_file(&installer);
+        return Err(format!("The {} installer failed ({})", loader_type, output.status));
+    }
+
+    match crate::loader_repair::check_files(root, &version_id, false) {
+        crate::loader_repair::LoaderHealth::Ok => {
+            info!("Installed {} {}", loader_type, version_id);
+            Ok(version_dir)
+        }
+        health => Err(format!("The {} installer didn't create {}: {:?}", loader_type, version_id, health)),
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::RwLock;
+
+use isahc::http::HeaderMap;
+use log::debug;
+
+use crate::secret::Secret;
+
+// Unauthenticated GitHub API requests are limited to 60 an hour per IP, which a remote include
+// directory listing on a shared network runs through quickly. A personal access token (entered in
+// the preferences or set through GITHUB_TOKEN) raises that to 5000. It's only ever sent to the
+// API host, downloads from raw.githubusercontent.com don't count against the limit.
+
+pub const API_HOST: &str = "api.github.com";
+pub const TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+// Mirrors config.network.github_token, set at startup and whenever preferences are saved
+static TOKEN: RwLock<Option<String>> = RwLock::new(None);
+
+pub fn configure(token: Option<&Secret>) {
+    let token = std::env::var(TOKEN_ENV)
+        .ok()
+        .or_else(|| token.map(|t| t.0.clone()))
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    debug!("GitHub token configured: {}", token.is_some());
+    if let Ok(mut current) = TOKEN.write() {
+        *current = token;
+    }
+}
+
+pub fn token() -> Option<String> {
+    TOKEN.read().ok().and_then(|t| t.clone())
+}
+
+/// The Authorization header for `url`, None for other hosts or without a token.
+pub fn auth_header(url: &str) -> Option<(&'static str, String)> {
+    if crate::retry::host_of(url) != API_HOST {
+        return None;
+    }
+    token().map(|token| ("Authorization", format!("Bearer {}", token)))
+}
+
+/// True when a 403/429 from the API is the rate limit rather than a missing permission.
+pub fn is_rate_limited(status: u16, headers: &HeaderMap) -> bool {
+    (status == 403 || status == 429)
+        && headers
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .map_or(status == 429, |v| v.trim() == "0")
+}
+
+/// When the rate limit resets, in seconds from now.
+pub fn rate_limit_reset_in(headers: &HeaderMap) -> Option<u64> {
+    let reset = headers.get("x-ratelimit-reset")?.to_str().ok()?.trim().parse::<i64>().ok()?;
+    Some((reset - chrono::Utc::now().timestamp()).max(0) as u64)
+}
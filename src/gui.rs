@@ -15,10 +15,9 @@ use modal::ModalContext;
 use modal::Modal; 
 use std::sync::mpsc;
 use log::{debug, error, info, warn};
-use isahc::ReadResponseExt;
 
-use crate::{GithubBranch, build_http_client, GH_API, REPO, Config};
-use crate::{get_app_data, get_installed_packs, get_launcher, uninstall, InstallerProfile, Launcher, PackName};
+use crate::{GithubBranch, build_http_client, GH_API, REPO};
+use crate::{get_app_data, get_installer_dir, get_installed_packs, get_launcher, uninstall, InstallerProfile, Launcher, PackName};
 use crate::Installation;
 use crate::installation;
 use crate::universal;
@@ -32,11 +31,19 @@ use crate::universal::ManifestErrorType;
 use crate::launcher::FeaturesTab;
 use crate::launcher::PerformanceTab;
 use crate::launcher::SettingsTab;
+use crate::launcher::ScreenshotsTab;
 use crate::installation::delete_installation;
 use crate::preset::find_preset_by_id;
 use crate::backup::BackupProgress;
+use crate::i18n::{t, t_with};
 
 mod modal;
+mod preferences;
+mod command_palette;
+mod discover;
+
+use preferences::Preferences;
+pub use preferences::save_config;
 
 
 // Font constants
@@ -171,7 +178,10 @@ fn ErrorNotification(
 ) -> Element {
     rsx! {
         div { class: "error-notification",
-            div { class: "error-message", "{message}" }
+            div { class: "error-message",
+                span { class: "status-icon", aria_hidden: "true", "⚠" }
+                "{message}"
+            }
             button { 
                 class: "error-close",
                 onclick: move |evt| on_close.call(evt),
@@ -186,17 +196,23 @@ fn ManifestErrorDisplay(
     error: String,
     error_type: String,
     file_name: String,
+    // Where the problem is when the file could be read, see manifest_validation.rs
+    location: Option<crate::manifest_validation::ErrorLocation>,
+    // Everything above as plain text for the copy button
+    report: String,
     onclose: EventHandler<MouseEvent>,
     onreport: EventHandler<MouseEvent>,
 ) -> Element {
     let formatted_error = format_error_message(&error);
+    let mut copied = use_signal(|| false);
+    let copy_label = if copied() { t("error.copied") } else { t("error.copy") };
     
     rsx! {
         // Use the correct class names that match your CSS
         div { class: "error-syntax-overlay",
             div { class: "error-syntax-container",
                 div { class: "error-syntax-header",
-                    h2 { "{error_type} Error" }
+                    h2 { {t_with("error.manifest_title", &[("type", &error_type)])} }
                     button { 
                         class: "close-button",
                         onclick: move |evt| onclose.call(evt),
@@ -206,21 +222,37 @@ fn ManifestErrorDisplay(
                 
                 div { class: "error-syntax-content",
                     div { class: "error-syntax-message",
-                        "There was a problem loading the {file_name} file. This could be due to:"
+                        {t_with("error.manifest_intro", &[("file", &file_name)])}
                     }
                     
                     ul { class: "error-reasons",
-                        li { "A network connection issue" }
-                        li { "A formatting problem in the file" }
-                        li { "Missing or invalid data in the file" }
+                        li { {t("error.reason_network")} }
+                        li { {t("error.reason_format")} }
+                        li { {t("error.reason_data")} }
                     }
                     
                     div { class: "error-syntax-details",
                         "{formatted_error}"
                     }
+
+                    if let Some(location) = location {
+                        div { class: "error-syntax-location",
+                            div { class: "error-location-pointer",
+                                {t_with("error.location", &[("location", &location.describe())])}
+                            }
+                            if let Some(expected) = &location.expected {
+                                div { class: "error-location-expected",
+                                    {t_with("error.expected", &[("expected", expected.as_str())])}
+                                }
+                            }
+                            if let Some(snippet) = &location.snippet {
+                                pre { class: "error-location-snippet", "{snippet}" }
+                            }
+                        }
+                    }
                     
                     p { class: "error-help",
-                        "Please copy these error details and report this issue so we can fix it."
+                        {t("error.help")}
                     }
                 }
                 
@@ -228,23 +260,24 @@ fn ManifestErrorDisplay(
                     button { 
                         class: "cancel-button",
                         onclick: move |evt| onclose.call(evt),
-                        "CLOSE"
+                        {t("error.close")}
                     }
                     
                     button { 
                         class: "retry-button",
                         onclick: move |evt| onreport.call(evt),
-                        "REPORT ISSUE"
+                        {t("error.report")}
                     }
                     
                     button { 
                         class: "secondary-button",
                         onclick: move |_| {
-                            // This would need to be implemented in your system
-                            // Typically using web_sys::clipboard in WASM
                             debug!("Copying error to clipboard");
+                            let text = serde_json::to_string(&report).unwrap_or_default();
+                            let _ = eval(&format!("navigator.clipboard.writeText({})", text));
+                            copied.set(true);
                         },
-                        "COPY ERROR DETAILS"
+                        {copy_label}
                     }
                 }
             }
@@ -334,7 +367,7 @@ fn open_url(url: &str) -> Result<(), String> {
 // Updated main function to initialize the app correctly
 fn main() {
     // Initialize logger
-    fs::create_dir_all(get_app_data().join(".WC_OVHL/")).expect("Failed to create config dir!");
+    fs::create_dir_all(get_installer_dir()).expect("Failed to create config dir!");
     CombinedLogger::init(vec![
         TermLogger::new(
             LevelFilter::Debug,
@@ -345,7 +378,7 @@ fn main() {
         WriteLogger::new(
             LevelFilter::Info,
             LogConfig::default(),
-            File::create(get_app_data().join(".WC_OVHL/installer.log")).unwrap(),
+            File::create(get_installer_dir().join("installer.log")).unwrap(),
         ),
     ])
     .unwrap();
@@ -388,31 +421,15 @@ fn main() {
     
     // Load branches
     let branches: Vec<GithubBranch> = serde_json::from_str(
-        build_http_client()
-            .get(GH_API.to_owned() + REPO + "branches")
+        crate::http_mock::get_text_blocking(&build_http_client(), &(GH_API.to_owned() + REPO + "branches"))
             .expect("Failed to retrieve branches!")
-            .text()
-            .unwrap()
             .as_str(),
     )
     .expect("Failed to parse branches!");
 
     // Load configuration
-    let config_path = get_app_data().join(".WC_OVHL/config.json");
-    let config: Config;
-
-    // Load or create config
-    if config_path.exists() {
-        config = serde_json::from_slice(&fs::read(&config_path).expect("Failed to read config!"))
-            .expect("Failed to load config!");
-    } else {
-        config = Config {
-            launcher: String::from("vanilla"),
-            first_launch: Some(true),
-        };
-        fs::write(&config_path, serde_json::to_vec(&config).unwrap())
-            .expect("Failed to write config!");
-    }
+    let config_path = get_installer_dir().join("config.json");
+    let (config, config_notice) = crate::config_migration::load_config(&config_path);
     
     info!("Running installer with config: {config:#?}");
     
@@ -430,17 +447,69 @@ fn main() {
         ).with_icon(
             Icon::from_rgba(icon.to_rgba8().to_vec(), icon.width(), icon.height()).unwrap(),
         ).with_data_directory(
-            env::temp_dir().join(".WC_OVHL")
+            match crate::portable::root() {
+                Some(root) => root.join(".WC_OVHL/webview"),
+                None => env::temp_dir().join(".WC_OVHL"),
+            }
         ).with_menu(None)
     ).with_context(AppProps {
         branches,
         modpack_source: String::from(REPO),
         config,
         config_path,
+        config_notice,
         installations,
     }).launch(app);
 }
 
+#[component]
+fn NewsPanel() -> Element {
+    let news = use_resource(|| async move { crate::news::load(&CachedHttpClient::new()).await });
+    let items = news.read().clone().unwrap_or_default();
+    if items.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "news-container",
+            div { class: "section-divider with-title",
+                span { class: "divider-title", "NEWS" }
+            }
+            div { class: "news-cards",
+                for item in items {
+                    {
+                        let link = item.link.clone();
+                        rsx! {
+                            div {
+                                class: if link.is_some() { "news-card clickable" } else { "news-card" },
+                                onclick: move |_| {
+                                    if let Some(link) = &link {
+                                        if let Err(e) = open::that(link) {
+                                            error!("Failed to open {}: {}", link, e);
+                                        }
+                                    }
+                                },
+                                if let Some(image) = &item.image {
+                                    img { class: "news-image", src: "{image}", alt: "" }
+                                }
+                                div { class: "news-body",
+                                    h3 { class: "news-title", "{item.title}" }
+                                    if let Some(date) = &item.date {
+                                        span { class: "news-date", "{date}" }
+                                    }
+                                    if let Some(summary) = &item.summary {
+                                        p { class: "news-summary", "{summary}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn ChangelogSection(changelog: Option<ChangelogData>) -> Element {
     let mut show_all = use_signal(|| false);
@@ -564,10 +633,14 @@ fn HomePage(
 ) -> Element {
     // State for the installation creation dialog
     let mut show_creation_dialog = use_signal(|| false);
+    let mut show_batch_update = use_signal(|| false);
+    let mut show_import = use_signal(|| false);
+    let locked = *use_context::<LockState>().locked.read();
     
     // Check if this is the first time (no installations)
     let has_installations = !installations().is_empty();
     let latest_installation = installations().first().cloned();
+    let outdated = crate::batch_update::pending(&installations());
     
     rsx! {
         div { class: "home-container home-page",
@@ -589,7 +662,25 @@ fn HomePage(
                 
                 // Section divider for installations
                 div { class: "section-divider with-title", 
-                    span { class: "divider-title", "YOUR INSTALLATIONS" }
+                    span { class: "divider-title", {t("home.your_installations")} }
+                }
+
+                if !outdated.is_empty() {
+                    div { class: "update-all-bar",
+                        span {
+                            if outdated.len() == 1 {
+                                {t("home.one_update")}
+                            } else {
+                                {t_with("home.updates", &[("count", &outdated.len().to_string())])}
+                            }
+                        }
+                        button {
+                            class: "update-all-button",
+                            disabled: locked,
+                            onclick: move |_| show_batch_update.set(true),
+                            {t("home.update_all")}
+                        }
+                    }
                 }
                 
                 // Grid of installation cards
@@ -617,16 +708,23 @@ fn HomePage(
                         
                         div { class: "installation-card-content", 
                             div { class: "installation-card-icon", "+" }
-                            h3 { "Create New Installation" }
-                            p { "Set up a new Wynncraft experience" }
+                            h3 { {t("home.create_title")} }
+                            p { {t("home.create_subtitle")} }
                         }
                     }
                 }
+
+                button {
+                    class: "import-instance-link",
+                    disabled: locked,
+                    onclick: move |_| show_import.set(true),
+                    {t("home.import_link")}
+                }
             } else {
                 // First-time user experience
                 div { class: "welcome-container first-time",
-                    h1 { "Welcome to the MAJESTIC OVERHAUL" }
-                    p { "Optimized performance and improved visuals!" }
+                    h1 { {t("home.welcome_title")} }
+                    p { {t("home.welcome_subtitle")} }
                     
                     // Statistics for first-time users too - pass changelog data
                     StatisticsDisplay { changelog: changelog() }
@@ -636,17 +734,50 @@ fn HomePage(
                         onclick: move |_| {
                             show_creation_dialog.set(true);
                         },
-                        "Get Started"
+                        {t("home.get_started")}
+                    }
+
+                    button {
+                        class: "import-instance-link",
+                        onclick: move |_| show_import.set(true),
+                        {t("home.welcome_import")}
                     }
                 }
             }
             
+            // Announcements from the pack team, not shown without a feed or connection
+            NewsPanel {}
+
             // Recent changes section
             ChangelogSection { changelog: changelog() }
             
             // Footer with dynamic data - pass changelog data
             Footer { changelog: changelog() }
             
+            if show_batch_update() {
+                BatchUpdateDialog {
+                    installations: outdated.clone(),
+                    onclose: move |_| show_batch_update.set(false),
+                    onfinished: move |_| {
+                        // The updates saved themselves, the cards only need the new state
+                        if let Ok(updated) = installation::load_all_installations() {
+                            installations.set(updated);
+                        }
+                    }
+                }
+            }
+
+            if show_import() {
+                ImportInstanceDialog {
+                    onclose: move |_| show_import.set(false),
+                    onimported: move |imported: Installation| {
+                        installations.with_mut(|list| list.insert(0, imported.clone()));
+                        show_import.set(false);
+                        current_installation_id.set(Some(imported.id));
+                    }
+                }
+            }
+
             // Installation creation dialog
             if *show_creation_dialog.read() {
                 SimplifiedInstallationWizard {
@@ -671,6 +802,227 @@ fn HomePage(
     }
 }
 
+// Lists modded instances the installer doesn't manage and adopts the picked one
+#[component]
+fn ImportInstanceDialog(onclose: EventHandler<()>, onimported: EventHandler<Installation>) -> Element {
+    let config = use_context::<ConfigState>().config;
+    let mut candidates = use_signal(|| Option::<Vec<crate::instance_import::Candidate>>::None);
+    let mut importing = use_signal(|| Option::<String>::None);
+    let mut import_error = use_signal(|| Option::<String>::None);
+    let mut imported = use_signal(|| Option::<(Installation, crate::instance_import::ImportSummary)>::None);
+
+    use_hook(move || {
+        let launcher = config.read().launcher.clone();
+        spawn(async move {
+            let found = tokio::task::spawn_blocking(move || crate::instance_import::scan(&launcher))
+                .await
+                .unwrap_or_default();
+            candidates.set(Some(found));
+        });
+    });
+
+    let body = match (imported.read().clone(), candidates.read().clone()) {
+        (Some((_, summary)), _) => {
+            let kept = summary.unmatched.join(", ");
+            rsx! {
+                p { "Found {summary.matched.len()} of the pack's components, their features are turned on. Press INSTALL on the next page to finish." }
+                if !summary.unmatched.is_empty() {
+                    p { class: "import-note", "{summary.unmatched.len()} mod(s) aren't part of the pack and were kept: {kept}" }
+                }
+            }
+        }
+        (None, None) => rsx! { p { "Looking for instances..." } },
+        (None, Some(found)) if found.is_empty() => rsx! {
+            p { "No modded Minecraft Launcher profiles or MultiMC/Prism instances found outside the installer." }
+        },
+        (None, Some(found)) => rsx! {
+            p {
+                "Pick the instance to bring over. Minecraft Launcher folders are copied, MultiMC and Prism instances "
+                "are taken over where they are. Your worlds, settings and other mods stay."
+            }
+            ul { class: "import-candidates",
+                for candidate in found {
+                    {
+                        let version = candidate.minecraft_version.clone().unwrap_or_else(|| "unknown version".to_string());
+                        let loader = candidate.loader.clone().map(|(kind, version)| format!(", {} {}", kind, version)).unwrap_or_default();
+                        let key = candidate.game_dir.to_string_lossy().to_string();
+                        let is_importing = importing.read().as_deref() == Some(key.as_str());
+                        let name = candidate.name.clone();
+                        let launcher = candidate.launcher_label();
+                        let mod_count = candidate.mod_count;
+                        rsx! {
+                            li { key: "{key}", class: "import-candidate",
+                                div { class: "import-candidate-info",
+                                    strong { "{name}" }
+                                    span { "{launcher} · Minecraft {version}{loader} · {mod_count} mods" }
+                                    span { class: "import-candidate-path", "{key}" }
+                                }
+                                button {
+                                    class: "update-proceed-button",
+                                    disabled: importing.read().is_some(),
+                                    onclick: move |_| {
+                                        let candidate = candidate.clone();
+                                        importing.set(Some(candidate.game_dir.to_string_lossy().to_string()));
+                                        import_error.set(None);
+                                        spawn(async move {
+                                            match crate::instance_import::import(&CachedHttpClient::new(), &candidate).await {
+                                                Ok(result) => imported.set(Some(result)),
+                                                Err(e) => {
+                                                    error!("Failed to import {}: {}", candidate.name, e);
+                                                    import_error.set(Some(e));
+                                                }
+                                            }
+                                            importing.set(None);
+                                        });
+                                    },
+                                    if is_importing { "Importing..." } else { "Import" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container import-instance-dialog",
+                div { class: "modal-header",
+                    h3 { "Import an Instance" }
+                    button {
+                        class: "modal-close",
+                        disabled: importing.read().is_some(),
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    if let Some(error) = &*import_error.read() {
+                        div { class: "error-message", "{error}" }
+                    }
+                    {body}
+                }
+
+                div { class: "modal-footer",
+                    if let Some((installation, _)) = imported.read().clone() {
+                        button {
+                            class: "update-proceed-button",
+                            onclick: move |_| onimported.call(installation.clone()),
+                            "Open Installation"
+                        }
+                    } else {
+                        button {
+                            class: "cancel-button",
+                            disabled: importing.read().is_some(),
+                            onclick: move |_| onclose.call(()),
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Runs "Update All" as soon as it opens and stays up with the results until closed
+#[component]
+fn BatchUpdateDialog(
+    installations: Vec<Installation>,
+    onclose: EventHandler<()>,
+    onfinished: EventHandler<()>,
+) -> Element {
+    let mut progress = use_signal(|| Option::<crate::batch_update::BatchProgress>::None);
+    let mut results = use_signal(|| Option::<Vec<crate::batch_update::BatchResult>>::None);
+
+    use_hook({
+        let installations = installations.clone();
+        move || {
+            spawn(async move {
+                let finished = crate::batch_update::update_all(&installations, move |update| progress.set(Some(update))).await;
+                results.set(Some(finished));
+                onfinished.call(());
+            });
+        }
+    });
+
+    let is_running = results.read().is_none();
+    let count = installations.len();
+    // Finished installations count whole, the current one by its components
+    let percentage = match &*progress.read() {
+        Some(p) if p.total > 0 => ((p.index as f64 + p.done as f64 / p.total as f64) / p.count as f64 * 100.0) as u32,
+        Some(p) => (p.index as f64 / p.count as f64 * 100.0) as u32,
+        None => 0,
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container batch-update-dialog",
+                div { class: "modal-header",
+                    h3 { "Update All" }
+                    button {
+                        class: "modal-close",
+                        disabled: is_running,
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    match results.read().clone() {
+                        None => rsx! {
+                            p {
+                                {match &*progress.read() {
+                                    Some(p) => format!("Updating {} ({} of {})... {}/{}", p.name, p.index + 1, p.count, p.done, p.total),
+                                    None => format!("Preparing {} updates...", count),
+                                }}
+                            }
+                            div { class: "progress-track",
+                                div { class: "progress-bar", style: "width: {percentage}%;" }
+                            }
+                            p { class: "batch-update-note", "Installations are updated one after another, keep the installer open until all are done." }
+                        },
+                        Some(finished) => rsx! {
+                            {
+                                let failed = finished.iter().filter(|r| matches!(r.outcome, crate::batch_update::Outcome::Failed(_))).count();
+                                rsx! {
+                                    p { "{finished.len() - failed} of {finished.len()} installations updated." }
+                                }
+                            }
+                            ul { class: "batch-update-results",
+                                for result in finished {
+                                    {
+                                        let (class, detail) = match &result.outcome {
+                                            crate::batch_update::Outcome::Updated { from, to } => ("batch-updated", format!("{} → {}", from, to)),
+                                            crate::batch_update::Outcome::Failed(e) => ("batch-failed", e.clone()),
+                                        };
+                                        rsx! {
+                                            li { key: "{result.id}", class: "{class}",
+                                                span { class: "batch-update-name", "{result.name}" }
+                                                span { class: "batch-update-detail", "{detail}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "update-proceed-button",
+                        disabled: is_running,
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
+
 // Special value for home page
 const HOME_PAGE: usize = usize::MAX;
 
@@ -688,6 +1040,27 @@ fn InstallationCard(
     let installation_id = installation.id.clone();
     let play_id = installation.id.clone();
     let mut error_signal = use_signal(|| Option::<String>::None);
+    // Stable is what everyone has, only the others get a badge
+    let channel = Some(installation.release_channel).filter(|c| *c != crate::channels::ReleaseChannel::Stable);
+    let channel_class = channel.map(|c| format!("channel-badge channel-{}", c.id())).unwrap_or_default();
+    let channel_label = channel.map(|c| c.label()).unwrap_or_default();
+
+    // Probed once per session in the background, see health.rs
+    let health = use_resource({
+        let installation = installation.clone();
+        move || {
+            let installation = installation.clone();
+            async move {
+                tokio::task::spawn_blocking(move || crate::health::cached(&installation))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+        }
+    });
+    let health_report = health.read().clone().flatten();
+    let palette_request = use_context::<command_palette::PaletteRequest>();
+    let repair_id = installation.id.clone();
     
     rsx! {
         div { 
@@ -696,10 +1069,49 @@ fn InstallationCard(
             
             div { class: "installation-card-header",
                 h3 { "{installation.name}" }
+
+                if !installation.notes.trim().is_empty() {
+                    span { class: "notes-indicator", title: "Has notes", "📝" }
+                }
+
+                if channel.is_some() {
+                    span { class: "{channel_class}", title: "Release channel", "{channel_label}" }
+                }
+
+                if let Some(report) = health_report {
+                    {
+                        let status = report.status;
+                        let class = format!("health-badge {}", status.class());
+                        let label = status.label();
+                        let title = if report.issues.is_empty() {
+                            "All installed files are in place".to_string()
+                        } else {
+                            format!("{}\nClick to repair", report.issues.join("\n"))
+                        };
+                        rsx! {
+                            button {
+                                class: "{class}",
+                                title: "{title}",
+                                disabled: status == crate::health::HealthStatus::Ok,
+                                onclick: move |evt| {
+                                    evt.stop_propagation();
+                                    let mut pending = palette_request.pending;
+                                    pending.set(Some(command_palette::PageCommand {
+                                        installation_id: repair_id.clone(),
+                                        action: command_palette::PageAction::Repair,
+                                    }));
+                                    onclick.call(repair_id.clone());
+                                },
+                                "{label}"
+                            }
+                        }
+                    }
+                }
                 
                 if installation.update_available {
                     span { 
                         class: "update-badge", 
+                        span { class: "status-icon", aria_hidden: "true", "▲" }
                         if installation.preset_update_available && !installation.update_available {
                             "Preset Update"
                         } else if !installation.preset_update_available && installation.update_available {
@@ -738,6 +1150,14 @@ fn InstallationCard(
                     span { class: "detail-value", "{installation.memory_allocation} MB" }
                 }
             }
+
+            // Shown over the details while hovering
+            if !installation.notes.trim().is_empty() {
+                div {
+                    class: "installation-card-notes",
+                    dangerous_inner_html: "{crate::markdown::render_markdown(&installation.notes)}"
+                }
+            }
             
             div { class: "installation-card-actions",
                 button { 
@@ -819,6 +1239,23 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
     let mut installation_error = use_signal(|| Option::<String>::None);
     let mut manifest_error = use_signal(|| Option::<ManifestError>::None);
     
+    // Share code entered by the user and the setup it resolved to
+    let mut share_input = use_signal(|| String::new());
+    let mut shared_setup = use_signal(|| Option::<crate::share::SharedInstallation>::None);
+    // Shared Java arguments run with the game, they're only used once the user ticked the box
+    let mut accept_shared_java_args = use_signal(|| false);
+    let mut is_fetching_share = use_signal(|| false);
+    
+    // Optional config preset from the manifest, merged into the game folder on install
+    let mut config_preset = use_signal(|| Option::<String>::None);
+    
+    // Modpack to install, the picker only shows up when config.json lists more sources
+    let mut pack_source = use_signal(crate::sources::PackSource::default);
+    let available_sources = crate::sources::all();
+    let mut release_channel = use_signal(crate::channels::ReleaseChannel::default);
+    // Game files somewhere else, checked when the installation is created
+    let mut custom_location = use_signal(|| Option::<std::path::PathBuf>::None);
+    
     // Character limit for installation names
     const MAX_NAME_LENGTH: usize = 15;
     
@@ -826,6 +1263,9 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
     let app_props = use_context::<AppProps>();
     let installations = app_props.installations;
     let selected_launcher = app_props.config.launcher.clone();
+    // Other launchers keep instances in their own folder
+    let can_pick_location = selected_launcher.starts_with("vanilla");
+    let available_channels = crate::channels::available(&pack_source.read(), &app_props.branches);
     let installation_count = installations.len() + 1;
     let suggested_names = vec![
         format!("Overhaul {}", installation_count),
@@ -847,11 +1287,21 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
     let manifest_error_clone = manifest_error.clone();
     let universal_manifest = use_resource(move || {
         let mut manifest_error = manifest_error_clone.clone();
+        let source = pack_source();
+        let channel = release_channel();
         async move {
-            debug!("Loading universal manifest...");
+            debug!("Loading universal manifest of {} ({})...", source.name, channel.id());
+            let source = channel.apply(source);
+            // The default pack is served from the CDN here, it's faster for the first look. Not with
+            // a developer override, that has to see the branch it points to
+            let manifest_url = if source.is_default() && crate::dev_manifest::describe().is_none() {
+                "https://cdn.jsdelivr.net/gh/Wynncraft-Overhaul/majestic-overhaul@latest/universal.json".to_string()
+            } else {
+                source.universal_url()
+            };
             match crate::universal::load_universal_manifest(
                 &crate::CachedHttpClient::new(), 
-                Some("https://cdn.jsdelivr.net/gh/Wynncraft-Overhaul/majestic-overhaul@latest/universal.json")
+                Some(&manifest_url)
             ).await {
                 Ok(manifest) => {
                     debug!("Successfully loaded universal manifest: {}", manifest.name);
@@ -868,6 +1318,56 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
         }
     });
     
+    // Look up a share code and prefill the wizard with it
+    let load_share_code = move |_| {
+        let input = share_input.read().clone();
+        is_fetching_share.set(true);
+        installation_error.set(None);
+        
+        spawn(async move {
+            let http_client = crate::CachedHttpClient::new();
+            match crate::share::fetch_shared(&http_client, &input).await {
+                Ok(shared) => {
+                    debug!("Loaded shared installation '{}' with {} features", shared.name, shared.enabled_features.len());
+                    let shared_name: String = shared.name.chars().take(MAX_NAME_LENGTH).collect();
+                    name.set(shared_name);
+                    accept_shared_java_args.set(false);
+                    shared_setup.set(Some(shared));
+                },
+                Err(e) => {
+                    error!("Failed to load share code: {}", e);
+                    installation_error.set(Some(e));
+                }
+            }
+            is_fetching_share.set(false);
+        });
+    };
+    
+    // Same as a share code, from a .wcpack file someone sent
+    let import_pack_file = move |_| {
+        let dialog = rfd::FileDialog::new()
+            .set_title("Import installation")
+            .add_filter("Installation file", &[crate::share::PACK_FILE_EXTENSION]);
+        let Some(path) = dialog.pick_file() else {
+            return;
+        };
+        match crate::share::read_pack_file(&path) {
+            Ok(shared) => {
+                debug!("Imported installation '{}' from {}", shared.name, path.display());
+                installation_error.set(None);
+                let shared_name: String = shared.name.chars().take(MAX_NAME_LENGTH).collect();
+                name.set(shared_name);
+                // Files get passed around just like codes, their Java arguments need the same yes
+                accept_shared_java_args.set(false);
+                shared_setup.set(Some(shared));
+            },
+            Err(e) => {
+                error!("Failed to import installation file: {}", e);
+                installation_error.set(Some(e));
+            }
+        }
+    };
+    
     // Function to create the installation
     let create_installation = move |_| {
         debug!("Creating installation with name: {}", name.read());
@@ -875,7 +1375,7 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
         // Validate name length
         let installation_name = name.read().trim().to_string();
         if installation_name.is_empty() {
-            installation_error.set(Some("Installation name cannot be empty.".to_string()));
+            installation_error.set(Some(t("wizard.error_empty_name")));
             return;
         }
         
@@ -903,6 +1403,18 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
             // CRITICAL FIX: Initialize with default-enabled features from universal manifest
             let http_client = crate::CachedHttpClient::new();
             let unwrapped_manifest_clone = unwrapped_manifest.clone();
+            let shared = shared_setup.read().clone();
+            let accept_java_args = accept_shared_java_args();
+            installation.config_preset = config_preset();
+            let source = pack_source();
+            installation.source = if source.is_default() { None } else { Some(source) };
+            installation.release_channel = release_channel();
+            if let Some(dir) = custom_location().filter(|_| can_pick_location) {
+                if let Err(e) = crate::install_location::apply(&mut installation, &dir) {
+                    installation_error.set(Some(e));
+                    return;
+                }
+            }
             
             spawn(async move {
                 // Build list of default features
@@ -946,12 +1458,20 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                     }
                 }
                 
-                // Initialize the installation with default features
-                installation.enabled_features = default_features.clone();
-                installation.pending_features = default_features.clone();
-                installation.pre_install_features = default_features.clone();
-                installation.is_custom_configuration = true;
-                installation.selected_preset_id = None;
+                if let Some(shared) = &shared {
+                    // Replicate the shared setup instead of the defaults
+                    let dropped = shared.apply_to(&mut installation, &unwrapped_manifest_clone, accept_java_args);
+                    if !dropped.is_empty() {
+                        warn!("Shared setup contained unknown features, skipped: {:?}", dropped);
+                    }
+                } else {
+                    // Initialize the installation with default features
+                    installation.enabled_features = default_features.clone();
+                    installation.pending_features = default_features.clone();
+                    installation.pre_install_features = default_features.clone();
+                    installation.is_custom_configuration = true;
+                    installation.selected_preset_id = None;
+                }
                 
                 debug!("Created custom installation with {} default features: {:?}", 
                        installation.enabled_features.len(), installation.enabled_features);
@@ -980,7 +1500,7 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
             });
         } else {
             error!("Universal manifest not available");
-            installation_error.set(Some("Failed to load modpack information. Please try again.".to_string()));
+            installation_error.set(Some(t("wizard.error_manifest")));
         }
     };
     
@@ -989,7 +1509,7 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
             div { class: "installation-wizard",
                 // Header with close button in corner
                 div { class: "wizard-header",
-                    h2 { "Create New Installation" }
+                    h2 { {t("wizard.title")} }
                     button { 
                         class: "close-button",
                         onclick: move |_| props.onclose.call(()),
@@ -1011,11 +1531,72 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                         }
                     }
                     
+                    if available_sources.len() > 1 {
+                        div { class: "wizard-section",
+                            h3 { {t("wizard.modpack")} }
+                            div { class: "suggestion-chips",
+                                for source in available_sources.clone() {
+                                    {
+                                        let is_selected = pack_source.read().id() == source.id();
+                                        let source_name = source.name.clone();
+                                        rsx! {
+                                            button {
+                                                key: "{source_name}",
+                                                class: if is_selected { "suggestion-chip selected" } else { "suggestion-chip" },
+                                                r#type: "button",
+                                                onclick: move |_| {
+                                                    // Presets and features belong to the old pack
+                                                    config_preset.set(None);
+                                                    shared_setup.set(None);
+                                                    manifest_error.set(None);
+                                                    release_channel.set(crate::channels::ReleaseChannel::Stable);
+                                                    pack_source.set(source.clone());
+                                                },
+                                                "{source_name}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    
+                    if available_channels.len() > 1 {
+                        div { class: "wizard-section",
+                            h3 { {t("wizard.channel")} }
+                            div { class: "suggestion-chips",
+                                for channel in available_channels.clone() {
+                                    {
+                                        let is_selected = release_channel() == channel;
+                                        let id = channel.id();
+                                        let label = channel.label();
+                                        let description = channel.description();
+                                        rsx! {
+                                            button {
+                                                key: "{id}",
+                                                class: if is_selected { "suggestion-chip selected" } else { "suggestion-chip" },
+                                                r#type: "button",
+                                                title: "{description}",
+                                                onclick: move |_| {
+                                                    // Presets and features can differ between channels
+                                                    config_preset.set(None);
+                                                    manifest_error.set(None);
+                                                    release_channel.set(channel);
+                                                },
+                                                "{label}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    
                     // Name section
                     div { class: "wizard-section",
-                        h3 { "Installation Name" }
+                        h3 { {t("wizard.name_heading")} }
                         div { class: "form-group",
-                            label { r#for: "installation-name", "Name your installation:" }
+                            label { r#for: "installation-name", {t("wizard.name_label")} }
                             input {
                                 id: "installation-name",
                                 r#type: "text",
@@ -1027,7 +1608,7 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                                         name.set(new_value);
                                     }
                                 },
-                                placeholder: "e.g. My Installation"
+                                placeholder: t("wizard.name_placeholder")
                             }
                             
                             // Character counter
@@ -1043,7 +1624,7 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                         
                         // Suggested names
                         div { class: "suggested-names",
-                            span { class: "suggestion-label", "Quick suggestions:" }
+                            span { class: "suggestion-label", {t("wizard.suggestions")} }
                             div { class: "suggestion-chips",
                                 for suggestion in suggested_names {
                                     button {
@@ -1056,33 +1637,159 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                             }
                         }
                     }
-                    
-                    // Minecraft info section - just to show what they're creating
-                    if let Some(unwrapped_manifest) = universal_manifest.read().as_ref().and_then(|opt| opt.as_ref()) {
-                        div { class: "wizard-section minecraft-info",
-                            div { class: "info-row",
-                                div { class: "info-item",
-                                    span { class: "info-label", "Minecraft:" }
-                                    span { class: "info-value", "{unwrapped_manifest.minecraft_version}" }
+
+                    if can_pick_location {
+                        div { class: "wizard-section",
+                            h3 { {t("wizard.location_heading")} }
+                            div { class: "form-group install-location",
+                                input {
+                                    r#type: "text",
+                                    readonly: true,
+                                    value: custom_location().map(|dir| dir.display().to_string()).unwrap_or_default(),
+                                    placeholder: t("wizard.location_default"),
                                 }
-                                
-                                div { class: "info-item",
-                                    span { class: "info-label", "Loader:" }
-                                    span { class: "info-value", "{unwrapped_manifest.loader.r#type} {unwrapped_manifest.loader.version}" }
+                                button {
+                                    class: "share-code-button",
+                                    r#type: "button",
+                                    onclick: move |_| {
+                                        if let Some(dir) = rfd::FileDialog::new().set_title("Pick an empty folder for the game files").pick_folder() {
+                                            installation_error.set(None);
+                                            custom_location.set(Some(dir));
+                                        }
+                                    },
+                                    {t("wizard.location_browse")}
                                 }
-                            }
-                            
+                                if custom_location().is_some() {
+                                    button {
+                                        class: "share-code-button",
+                                        r#type: "button",
+                                        onclick: move |_| custom_location.set(None),
+                                        {t("wizard.location_reset")}
+                                    }
+                                }
+                            }
+                            p { class: "info-description", {t("wizard.location_note")} }
+                        }
+                    }
+                    
+                    // Share code section
+                    div { class: "wizard-section share-code-section",
+                        h3 { {t("wizard.share_heading")} }
+                        if let Some(shared) = &*shared_setup.read() {
+                            div { class: "shared-setup-summary",
+                                span {
+                                    {t_with("wizard.shared_setup", &[
+                                        ("name", &shared.name),
+                                        ("count", &shared.enabled_features.len().to_string()),
+                                    ])}
+                                }
+                                button {
+                                    class: "shared-setup-clear",
+                                    r#type: "button",
+                                    onclick: move |_| shared_setup.set(None),
+                                    {t("wizard.dont_use")}
+                                }
+                            }
+                            if shared.has_java_args() {
+                                div { class: "shared-java-args",
+                                    p { {t("wizard.shared_java_args")} }
+                                    code { {shared.java_args.clone()} }
+                                    label {
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: accept_shared_java_args(),
+                                            onchange: move |evt| accept_shared_java_args.set(evt.checked()),
+                                        }
+                                        {t("wizard.shared_java_args_accept")}
+                                    }
+                                }
+                            }
+                        } else {
+                            div { class: "form-group share-code-input",
+                                input {
+                                    r#type: "text",
+                                    value: "{share_input}",
+                                    oninput: move |evt| share_input.set(evt.value().clone()),
+                                    placeholder: t("wizard.share_placeholder")
+                                }
+                                button {
+                                    class: "share-code-button",
+                                    r#type: "button",
+                                    disabled: share_input.read().trim().is_empty() || *is_fetching_share.read(),
+                                    onclick: load_share_code,
+                                    {if *is_fetching_share.read() { t("wizard.loading") } else { t("wizard.use_code") }}
+                                }
+                                button {
+                                    class: "share-code-button",
+                                    r#type: "button",
+                                    disabled: *is_fetching_share.read(),
+                                    onclick: import_pack_file,
+                                    {t("wizard.import_file")}
+                                }
+                            }
+                        }
+                    }
+                    
+                    // Minecraft info section - just to show what they're creating
+                    if let Some(unwrapped_manifest) = universal_manifest.read().as_ref().and_then(|opt| opt.as_ref()) {
+                        div { class: "wizard-section minecraft-info",
+                            div { class: "info-row",
+                                div { class: "info-item",
+                                    span { class: "info-label", {t("wizard.minecraft")} }
+                                    span { class: "info-value", "{unwrapped_manifest.minecraft_version}" }
+                                }
+                                
+                                div { class: "info-item",
+                                    span { class: "info-label", {t("wizard.loader")} }
+                                    span { class: "info-value", "{unwrapped_manifest.loader.r#type} {unwrapped_manifest.loader.version}" }
+                                }
+                            }
+                            
+                            if !unwrapped_manifest.config_presets.is_empty() {
+                                div { class: "config-preset-picker",
+                                    span { class: "suggestion-label", {t("wizard.config_preset")} }
+                                    div { class: "suggestion-chips",
+                                        button {
+                                            class: if config_preset().is_none() { "suggestion-chip selected" } else { "suggestion-chip" },
+                                            r#type: "button",
+                                            onclick: move |_| config_preset.set(None),
+                                            {t("wizard.config_preset_none")}
+                                        }
+                                        for preset in unwrapped_manifest.config_presets.clone() {
+                                            {
+                                                let is_selected = config_preset().as_ref() == Some(&preset.id);
+                                                let preset_id = preset.id.clone();
+                                                let description = preset.description.clone().unwrap_or_default();
+                                                rsx! {
+                                                    button {
+                                                        key: "{preset.id}",
+                                                        class: if is_selected { "suggestion-chip selected" } else { "suggestion-chip" },
+                                                        r#type: "button",
+                                                        title: "{description}",
+                                                        onclick: move |_| config_preset.set(Some(preset_id.clone())),
+                                                        "{preset.name}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    p { class: "info-description",
+                                        {t("wizard.config_preset_note")}
+                                    }
+                                }
+                            }
+                            
                             // Show what will be included by default
                             div { class: "default-features-info",
                                 p { class: "info-description", 
-                                    "You can customize further in the next step."
+                                    {t("wizard.customize_later")}
                                 }
                             }
                         }
                     } else {
                         div { class: "loading-section",
                             div { class: "loading-spinner" }
-                            div { class: "loading-text", "Loading modpack information..." }
+                            div { class: "loading-text", {t("wizard.loading_manifest")} }
                         }
                     }
                 }
@@ -1092,14 +1799,14 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                     button {
                         class: "cancel-button",
                         onclick: move |_| props.onclose.call(()),
-                        "Cancel"
+                        {t("wizard.cancel")}
                     }
                     
                     button {
                         class: "create-button",
                         disabled: universal_manifest.read().is_none(),
                         onclick: create_installation,
-                        "Create Installation"
+                        {t("wizard.create")}
                     }
                 }
             }
@@ -1110,6 +1817,8 @@ pub fn SimplifiedInstallationWizard(props: InstallationCreationProps) -> Element
                     error: error.message.clone(),
                     error_type: format!("{}", error.error_type),
                     file_name: error.file_name.clone(),
+                    location: error.location.clone(),
+                    report: crate::manifest_validation::report(&error),
                     onclose: move |_| manifest_error.set(None),
                     onreport: move |_| {
                         let _ = open_url("https://discord.com/channels/778965021656743966/1234506784626970684");
@@ -1135,6 +1844,9 @@ pub fn InstallationManagementPage(
     
     // State for the current tab
     let mut active_tab = use_signal(|| "features");
+    let lock_state = use_context::<LockState>();
+    let locked = *lock_state.locked.read();
+    let config = use_context::<ConfigState>().config;
 
     // Clone installation_id BEFORE moving it into use_memo
     let installation_id_for_delete = installation_id.clone();
@@ -1151,24 +1863,40 @@ pub fn InstallationManagementPage(
     // Installation status signals
     let mut is_installing = use_signal(|| false);
     let mut installation_error = use_signal(|| Option::<String>::None);
+    // The error is from a failed install, the error card offers to try again
+    let mut install_failed = use_signal(|| false);
+    // Another window or a background update holds the installation's lock, see instance_lock.rs
+    let mut install_busy = use_signal(|| Option::<String>::None);
     
     // Progress tracking signals
     let mut installation_progress = use_signal(|| 0i64);
     let mut installation_total = use_signal(|| 0i64);
     let mut installation_status = use_signal(|| String::new());
+    // Bytes of the downloads in flight, in components, and the file/speed/ETA line under the bar
+    let installation_partial = use_signal(|| 0f64);
+    let installation_detail = use_signal(|| Option::<String>::None);
+    // Protected config files the last update put back, shown until dismissed
+    // Also what an update in the background (automatic, scheduled, update all) left
+    let mut config_restore_summary = use_signal(|| crate::config_protection::take_summary(&installation_id));
+    // Files the last install found that aren't part of the pack, reviewed in OrphanFilesDialog
+    let mut unused_file_count = use_signal(|| 0usize);
+    let mut show_unused_files = use_signal(|| false);
+    // The error is from a failed launch, the error card offers to collect the game's logs
+    let mut launch_failed = use_signal(|| false);
+    let mut show_diagnostics = use_signal(|| false);
     
     // Handle installation not found
     if let Err(e) = &*installation_result.read() {
         return rsx! {
             div { class: "error-container",
-                h2 { "Installation Not Found" }
-                p { "The requested installation could not be found." }
-                p { "Error: {e}" }
+                h2 { {t("manage.not_found_title")} }
+                p { {t("manage.not_found_text")} }
+                p { {t_with("manage.error", &[("error", e)])} }
                 
                 button {
                     class: "back-button",
                     onclick: move |_| onback.call(()),
-                    "Back to Home"
+                    {t("manage.back_home")}
                 }
             }
         };
@@ -1190,6 +1918,8 @@ pub fn InstallationManagementPage(
     // State for modification tracking
     let mut has_changes = use_signal(|| false);
     let enabled_features = use_signal(|| installation.enabled_features.clone());
+    // What's on disk, on-demand content moves this without an update
+    let installed_features = use_signal(|| installation.enabled_features.clone());
     let memory_allocation = use_signal(|| installation.memory_allocation);
     let java_args = use_signal(|| installation.java_args.clone());
     let selected_preset = use_signal(|| Option::<String>::None);
@@ -1210,11 +1940,10 @@ pub fn InstallationManagementPage(
     // Effect to detect changes
     use_effect({
         let enabled_features_for_effect = enabled_features.clone();
-        let original_features = installation.enabled_features.clone();
         let mut features_modified_copy = features_modified.clone();
         
         move || {
-            let features_changed = enabled_features_for_effect.read().clone() != original_features;
+            let features_changed = *enabled_features_for_effect.read() != *installed_features.read();
             
             // Update specific modification flags
             features_modified_copy.set(features_changed);
@@ -1233,7 +1962,7 @@ pub fn InstallationManagementPage(
 
 let mut proceed_with_update = {
     let installation_for_update_clone = installation_for_update_clone.clone();
-    let enabled_features = enabled_features.clone();
+    let mut enabled_features = enabled_features.clone();
     let memory_allocation = memory_allocation.clone();
     let java_args = java_args.clone();
     let mut is_installing = is_installing.clone();
@@ -1246,18 +1975,31 @@ let mut proceed_with_update = {
     let performance_modified = performance_modified.clone();
     let installations = installations.clone();
     let installation_state = installation_state.clone();
-    let selected_preset = selected_preset.clone();
+    let mut selected_preset = selected_preset.clone();
     let installation_id_for_clear = installation_id.clone(); // Add this for session clearing
     
     move || {
+        // Checked before the progress window opens, the install itself takes the lock
+        if let Err(e) = crate::instance_lock::check_installation(&installation_for_update_clone) {
+            install_busy.set(Some(e));
+            return;
+        }
         is_installing.set(true);
         
         // Reset progress before starting
         installation_progress.set(0);
-        installation_status.set("Preparing installation...".to_string());
+        installation_status.set(t("progress.preparing"));
         
         let mut installation_clone = installation_for_update_clone.clone();
 
+        // A profile queued in the features tab replaces the current selection for this update
+        if let Ok(saved) = crate::installation::load_installation(&installation_clone.id) {
+            installation_clone.next_update_profile = saved.next_update_profile;
+        }
+        if let Some(features) = crate::feature_profiles::take_update_profile(&mut installation_clone) {
+            enabled_features.set(features);
+            selected_preset.set(None);
+        }
 
         // Save the user's current selections as pending
         let current_features = enabled_features.read().clone();
@@ -1274,11 +2016,17 @@ let mut proceed_with_update = {
         
         let http_client = crate::CachedHttpClient::new();
         let mut installation_error_clone = installation_error.clone();
+        let mut install_failed = install_failed;
+        let mut restore_summary = config_restore_summary;
+        let mut unused_files = unused_file_count;
         let mut progress = installation_progress.clone();
         let mut total = installation_total.clone();
         let mut status = installation_status.clone();
+        let mut partial = installation_partial;
+        let mut detail = installation_detail;
         let mut is_installing_clone = is_installing.clone();
         let mut has_changes_clone = has_changes.clone();
+        let mut installed_features = installed_features;
         let mut features_modified_clone = features_modified.clone();
         let mut performance_modified_clone = performance_modified.clone();
         let mut installations = installations.clone();
@@ -1288,7 +2036,7 @@ let mut proceed_with_update = {
 
         spawn(async move {
             // Calculate total items for accurate progress tracking
-            match crate::universal::load_universal_manifest(&http_client, None).await {
+            match installation_clone.load_universal_manifest(&http_client).await {
                 Ok(manifest) => {
                     let enabled_features = installation_clone.enabled_features.clone();
                     
@@ -1354,7 +2102,7 @@ let mut proceed_with_update = {
                     let overhead_tasks = 4;
                     total.set(total_items as i64); // Don't add overhead to total yet
                     progress.set(0);
-                    status.set("Starting installation...".to_string());
+                    status.set(t("progress.starting"));
                     
                     debug!("Total installation items: {}", total_items);
                     
@@ -1386,11 +2134,49 @@ let mut proceed_with_update = {
                         debug!("Progress update: {}/{} ({}%)", current, total_val, percent);
                     };
                     
+                    // Updates may back up the user's settings first and downloads may wait for an
+                    // unmetered connection, both show in the status line. The bytes of running
+                    // downloads fill the bar between components
+                    let mut download_events = crate::download_progress::subscribe();
+                    spawn(async move {
+                        let mut tracker = crate::download_progress::TransferTracker::new();
+                        while is_installing_clone() {
+                            if let Some(backup) = crate::backup::auto_backup_progress() {
+                                status.set(t_with("progress.backing_up", &[
+                                    ("done", &backup.files_processed.to_string()),
+                                    ("total", &backup.total_files.to_string()),
+                                ]));
+                            } else if crate::download_schedule::is_paused() {
+                                status.set(t("progress.paused_metered"));
+                            }
+
+                            tracker.drain(&mut download_events);
+                            let total_val = *total.peek();
+                            let done = *progress.peek();
+                            let in_flight = tracker.in_flight().min((total_val - done).max(0) as f64);
+                            partial.set(in_flight);
+                            let fraction = if total_val > 0 { (done as f64 + in_flight) / total_val as f64 } else { 0.0 };
+                            let mut parts: Vec<String> = tracker.current_file().into_iter().collect();
+                            let speed = tracker.bytes_per_sec();
+                            if speed > 0.0 {
+                                parts.push(format!("{}/s", crate::download_progress::format_bytes(speed as u64)));
+                            }
+                            if let Some(eta) = tracker.eta(fraction) {
+                                parts.push(crate::download_progress::format_eta(eta));
+                            }
+                            detail.set(if parts.is_empty() { None } else { Some(parts.join(" · ")) });
+
+                            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                        }
+                        partial.set(0.0);
+                        detail.set(None);
+                    });
+
                     // Run the installation
                     match installation_clone.install_or_update_with_progress(&http_client, progress_callback).await {
                         Ok(_) => {
                             // NOW handle overhead tasks with proper progress updates
-                            status.set("Finalizing installation...".to_string());
+                            status.set(t("progress.finalizing_installation"));
                             
                             // Update total to include overhead
                             let new_total = total_items as i64 + overhead_tasks;
@@ -1400,10 +2186,10 @@ let mut proceed_with_update = {
                             for i in 1..=overhead_tasks {
                                 progress.set(total_items as i64 + i);
                                 match i {
-                                    1 => status.set("Saving configuration...".to_string()),
-                                    2 => status.set("Creating launcher profile...".to_string()),
-                                    3 => status.set("Setting up game files...".to_string()),
-                                    4 => status.set("Completing installation...".to_string()),
+                                    1 => status.set(t("progress.saving_config")),
+                                    2 => status.set(t("progress.creating_profile")),
+                                    3 => status.set(t("progress.game_files")),
+                                    4 => status.set(t("progress.completing")),
                                     _ => {}
                                 }
                                 
@@ -1413,7 +2199,7 @@ let mut proceed_with_update = {
                             
                             // FINAL: Set to 100% and mark as complete
                             progress.set(new_total);
-                            status.set("Installation completed successfully!".to_string());
+                            status.set(t("progress.completed"));
                             
                             debug!("Installation completed successfully, progress: {}/{}", new_total, new_total);
                             
@@ -1431,13 +2217,15 @@ let mut proceed_with_update = {
                             installation_clone.modified = false;
                             
                             // Update the universal version
-                            if let Ok(manifest) = crate::universal::load_universal_manifest(&http_client, None).await {
+                            let manifest = installation_clone.load_universal_manifest(&http_client).await;
+                            if let Ok(manifest) = manifest {
                                 installation_clone.universal_version = manifest.modpack_version;
                             }
                             
                             // Update preset version if needed
+                            let presets = installation_clone.load_presets(&http_client).await;
                             if let Some(base_preset_id) = &installation_clone.base_preset_id {
-                                if let Ok(presets) = crate::preset::load_presets(&http_client, None).await {
+                                if let Ok(presets) = presets {
                                     if let Some(preset) = presets.iter().find(|p| p.id == *base_preset_id) {
                                         installation_clone.base_preset_version = preset.preset_version.clone();
                                     }
@@ -1450,8 +2238,11 @@ let mut proceed_with_update = {
                                 installation_error_clone.set(Some(format!("Failed to save installation: {}", e)));
                             } else {
                                 debug!("Successfully saved installation state");
+                                restore_summary.set(crate::config_protection::take_summary(&installation_id));
+                                unused_files.set(crate::orphan_files::find_for_installation(&installation_clone).map_or(0, |o| o.len()));
                                 
                                 // Update UI state only after successful save
+                                installed_features.set(installation_clone.enabled_features.clone());
                                 installation_state.set(installation_clone.clone());
                                 
                                 // Update the installations list
@@ -1473,11 +2264,17 @@ let mut proceed_with_update = {
                             tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                             debug!("Closing progress window after successful installation");
                             is_installing_clone.set(false);
+
+                            if config.peek().general.launch_after_install && crate::launcher::launches_instances() {
+                                info!("Launching {} after the install", installation_id);
+                                handle_play_click(installation_id.clone(), &installation_error_clone);
+                            }
                         },
                         Err(e) => {
                             error!("Installation failed: {}", e);
-                            installation_error_clone.set(Some(format!("Installation failed: {}", e)));
-                            status.set("Installation failed!".to_string());
+                            installation_error_clone.set(Some(t_with("manage.install_failed", &[("error", &e.to_string())])));
+                            install_failed.set(true);
+                            status.set(t("progress.failed"));
                             // Don't auto-close on failure
                             // Don't clear session state on failure - let user retry with same selections
                         }
@@ -1485,8 +2282,9 @@ let mut proceed_with_update = {
                 },
                 Err(e) => {
                     error!("Failed to load manifest: {}", e);
-                    installation_error_clone.set(Some(format!("Failed to load manifest: {}", e)));
-                    status.set("Failed to load manifest!".to_string());
+                    installation_error_clone.set(Some(t_with("manage.manifest_failed", &[("error", &e.to_string())])));
+                    install_failed.set(true);
+                    status.set(t("progress.manifest_failed"));
                     is_installing_clone.set(false);
                 }
             }
@@ -1523,21 +2321,25 @@ let (action_button_label, button_class, button_disabled) = {
     let is_installing = *is_installing.read();
     
     if is_installing {
-        ("INSTALLING...", "footer-action-button installing", true)
+        (t("manage.action_installing"), "footer-action-button installing", true)
+    } else if locked {
+        (t("manage.action_locked"), "footer-action-button locked", true)
     } else if !installed {
         // Not installed - always allow installation
-        ("INSTALL", "footer-action-button install", false)
+        (t("manage.action_install"), "footer-action-button install", false)
     } else if update_available || preset_update_available {
         // Update available - show update button
-        ("UPDATE", "footer-action-button update", false)
+        (t("manage.action_update"), "footer-action-button update", false)
     } else if has_changes {
         // User made changes - allow modification
-        ("MODIFY", "footer-action-button modify", false)
+        (t("manage.action_modify"), "footer-action-button modify", false)
     } else {
         // Installed and up-to-date with no changes
-        ("INSTALLED", "footer-action-button up-to-date", true)
+        (t("manage.action_installed"), "footer-action-button up-to-date", true)
     }
 };  
+    let mut retry_install = proceed_with_update.clone();
+
     // Handle launch
     let handle_launch = {
         let mut installation_error_clone = installation_error.clone();
@@ -1546,6 +2348,7 @@ let (action_button_label, button_class, button_disabled) = {
         move |_| {
             let mut installation_error_clone = installation_error_clone.clone();
             let installation_id = installation_id.clone();
+            launch_failed.set(false);
             
             // Create a channel to communicate back to the main thread
             let (error_tx, error_rx) = std::sync::mpsc::channel::<String>();
@@ -1567,42 +2370,167 @@ let (action_button_label, button_class, button_disabled) = {
             spawn(async move {
                 if let Ok(error_message) = error_rx.recv() {
                     installation_error_clone.set(Some(error_message));
+                    launch_failed.set(true);
                 }
             });
         }
     };
 
+    // The game closing with an error code shows up like a failed launch, with the diagnostics offer
+    let installation_id_for_exits = installation_id_for_launch.clone();
+    let mut installation_error_for_exits = installation_error.clone();
+    use_future(move || {
+        let installation_id = installation_id_for_exits.clone();
+        async move {
+            let mut exits = crate::launcher::subscribe_exits();
+            loop {
+                match exits.recv().await {
+                    Ok(exit) if exit.installation_id == installation_id => {
+                        if exit.is_abnormal() {
+                            installation_error_for_exits.set(Some(format!("{}. The crash report and logs can help find out why.", exit.describe())));
+                            launch_failed.set(true);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    });
+
     // Load universal manifest for features
-    let universal_manifest = use_resource(move || async {
-        match crate::universal::load_universal_manifest(&crate::CachedHttpClient::new(), None).await {
-            Ok(manifest) => {
-                debug!("Successfully loaded universal manifest for features");
-                Some(manifest)
-            },
-            Err(e) => {
-                error!("Failed to load universal manifest: {}", e);
-                None
+    let installation_for_resources = installation.clone();
+    let mut universal_manifest = use_resource({
+        let installation = installation_for_resources.clone();
+        move || {
+            let installation = installation.clone();
+            async move {
+                match installation.load_universal_manifest(&crate::CachedHttpClient::new()).await {
+                    Ok(manifest) => {
+                        debug!("Successfully loaded universal manifest for features");
+                        Some(manifest)
+                    },
+                    Err(e) => {
+                        error!("Failed to load universal manifest: {}", e);
+                        None
+                    }
+                }
             }
         }
     });
     
+    // "Preview changes" next to INSTALL/MODIFY/UPDATE, recomputed while it's open and features change
+    let mut show_file_preview = use_signal(|| false);
+    let file_preview = use_memo(move || {
+        if !show_file_preview() {
+            return None;
+        }
+        let manifest = universal_manifest.read().clone().flatten()?;
+        let features = enabled_features.read().clone();
+        Some(installation_state.read().preview_file_changes(&manifest, features))
+    });
+
     // Load presets
-    let presets = use_resource(move || async {
-        match crate::preset::load_presets(&crate::CachedHttpClient::new(), None).await {
-            Ok(presets) => {
-                debug!("Successfully loaded {} presets", presets.len());
-                presets
-            },
-            Err(e) => {
-                error!("Failed to load presets: {}", e);
-                Vec::new()
+    let mut presets = use_resource(move || {
+        let installation = installation_for_resources.clone();
+        async move {
+            match installation.load_presets(&crate::CachedHttpClient::new()).await {
+                Ok(presets) => {
+                    debug!("Successfully loaded {} presets", presets.len());
+                    presets
+                },
+                Err(e) => {
+                    error!("Failed to load presets: {}", e);
+                    Vec::new()
+                }
+            }
+        }
+    });
+
+    // Developer mode with a local manifest folder: reload when one of its files is saved, see dev_manifest.rs
+    use_future(move || async move {
+        let mut last = crate::dev_manifest::local_revision();
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            let revision = crate::dev_manifest::local_revision();
+            // Also covers the folder being set or cleared in the preferences
+            if revision != last {
+                debug!("Local manifest files changed, reloading");
+                universal_manifest.restart();
+                presets.restart();
+                last = revision;
+            }
+        }
+    });
+
+    // Carry out commands picked in the Ctrl+K palette for this installation
+    let mut palette_notice = use_signal(|| Option::<String>::None);
+    // Opens Verify Files once the settings tab is up, see PageAction::Repair
+    let mut repair_request = use_signal(|| false);
+    let palette_request = use_context::<command_palette::PaletteRequest>();
+    use_effect({
+        let mut pending = palette_request.pending;
+        let installation_id = installation.id.clone();
+        let mut proceed_with_update = proceed_with_update.clone();
+        move || {
+            let Some(command) = pending.read().clone() else { return };
+            if command.installation_id != installation_id {
+                return;
+            }
+            pending.set(None);
+            debug!("Running palette command {:?}", command.action);
+            match command.action {
+                command_palette::PageAction::Tab(tab) => active_tab.set(tab),
+                _ if *lock_state.locked.peek() => {
+                    palette_notice.set(Some("Installations are locked, unlock them from the home screen first".to_string()));
+                }
+                _ if *is_installing.peek() => {
+                    palette_notice.set(Some("Wait for the current installation to finish".to_string()));
+                }
+                command_palette::PageAction::Update => {
+                    if installation_state.peek().installed {
+                        show_update_warning.set(true);
+                    } else {
+                        proceed_with_update();
+                    }
+                }
+                command_palette::PageAction::ToggleFeature(feature_id) => {
+                    let manifest = universal_manifest.peek().clone().flatten();
+                    let Some(manifest) = manifest else {
+                        palette_notice.set(Some("The modpack information is still loading, try again in a moment".to_string()));
+                        return;
+                    };
+                    let presets = presets.peek().clone().unwrap_or_default();
+                    let summary = crate::launcher::toggle_component(
+                        &manifest,
+                        &installation_id,
+                        &presets,
+                        enabled_features,
+                        selected_preset.peek().clone(),
+                        &feature_id,
+                    );
+                    active_tab.set("features");
+                    palette_notice.set(Some(summary));
+                }
+                command_palette::PageAction::Repair => {
+                    active_tab.set("settings");
+                    repair_request.set(true);
+                }
             }
         }
     });
 
+    // Pack colors from the manifest, see theme.rs
+    let accent_style = match universal_manifest.read().as_ref() {
+        Some(Some(manifest)) => crate::theme::accent_variables(manifest.tab_color.as_deref(), manifest.tab_background.as_deref()),
+        _ => String::new(),
+    };
+
 rsx! {
     div { 
         class: "installation-management-container installation-page",
+        style: "{accent_style}",
             // Show progress view if installing
                 if *is_installing.read() {
                     div { class: "installation-page",
@@ -1610,7 +2538,9 @@ rsx! {
                             value: *installation_progress.read(),
                             max: *installation_total.read(),
                             status: installation_status.read().clone(),
-                            title: format!("Installing {}", installation.name),
+                            partial: Some(*installation_partial.read()),
+                            detail: installation_detail.read().clone(),
+                            title: t_with("manage.installing_title", &[("name", &installation.name)]),
                             on_complete: Some(EventHandler::new(move |_| {
                                 debug!("Progress view signaled completion");
                                 is_installing.set(false);
@@ -1636,7 +2566,7 @@ rsx! {
                         button { 
                             class: "nav-tab back-tab",
                             onclick: move |_| onback.call(()),
-                            "← Back"
+                            {t("manage.back")}
                         }
                         
                         button { 
@@ -1646,7 +2576,7 @@ rsx! {
                                 "nav-tab" 
                             },
                             onclick: move |_| active_tab.set("features"),
-                            "Features"
+                            {t("manage.tab_features")}
                             
                             if *features_modified.read() {
                                 span { class: "tab-modified-dot" }
@@ -1660,7 +2590,7 @@ rsx! {
                                 "nav-tab" 
                             },
                             onclick: move |_| active_tab.set("performance"),
-                            "Performance"
+                            {t("manage.tab_performance")}
                             
                             if *performance_modified.read() {
                                 span { class: "tab-modified-dot" }
@@ -1674,7 +2604,17 @@ rsx! {
                                 "nav-tab" 
                             },
                             onclick: move |_| active_tab.set("settings"),
-                            "Settings"
+                            {t("manage.tab_settings")}
+                        }
+
+                        button { 
+                            class: if *active_tab.read() == "screenshots" { 
+                                "nav-tab active" 
+                            } else { 
+                                "nav-tab" 
+                            },
+                            onclick: move |_| active_tab.set("screenshots"),
+                            {t("manage.tab_screenshots")}
                         }
                     }
                     
@@ -1684,9 +2624,9 @@ rsx! {
                             disabled: !installation_state.read().installed || *is_installing.read(),
                             onclick: handle_launch,
                             if installation_state.read().installed {
-                                "LAUNCH"
+                                {t("manage.launch")}
                             } else {
-                                "INSTALL FIRST"
+                                {t("manage.install_first")}
                             }
                         }
                     }
@@ -1698,14 +2638,156 @@ rsx! {
                     if let Some(error) = &*installation_error.read() {
                         div { class: "error-notification",
                             div { class: "error-message", "{error}" }
+                            if install_failed() {
+                                button {
+                                    class: "error-retry",
+                                    onclick: move |_| {
+                                        installation_error.set(None);
+                                        install_failed.set(false);
+                                        retry_install();
+                                    },
+                                    {t("manage.retry")}
+                                }
+                            }
+                            if launch_failed() {
+                                button {
+                                    class: "error-retry",
+                                    onclick: move |_| show_diagnostics.set(true),
+                                    {t("manage.collect_diagnostics")}
+                                }
+                            }
                             button { 
                                 class: "error-close",
-                                onclick: move |_| installation_error.set(None),
+                                onclick: move |_| {
+                                    installation_error.set(None);
+                                    install_failed.set(false);
+                                    launch_failed.set(false);
+                                },
+                                "×"
+                            }
+                        }
+                    }
+
+                    if let Some(source) = crate::dev_manifest::describe() {
+                        div { class: "dev-manifest-notice", "Developer manifest: {source}" }
+                    }
+
+                    if let Some(notice) = palette_notice() {
+                        div { class: "palette-notice",
+                            span { "{notice}" }
+                            button {
+                                class: "error-close",
+                                onclick: move |_| palette_notice.set(None),
+                                "×"
+                            }
+                        }
+                    }
+
+                    if let Some(restored) = config_restore_summary() {
+                        {
+                            let kept = restored.restored.len() + restored.recreated.len();
+                            let replaced = restored.overridden.len();
+                            rsx! {
+                                div { class: "palette-notice config-restore-notice",
+                                    details {
+                                        summary { "🛡 Kept {kept} of your config file(s) the update would have reset, {replaced} replaced on purpose by this version" }
+                                        ul {
+                                            for file in restored.restored.clone() {
+                                                li { key: "{file}", "Restored {file}" }
+                                            }
+                                            for file in restored.recreated.clone() {
+                                                li { key: "{file}", "Put back {file}" }
+                                            }
+                                            for file in restored.overridden.clone() {
+                                                li { key: "{file}", class: "change-updated", "Replaced {file}" }
+                                            }
+                                        }
+                                    }
+                                    button {
+                                        class: "error-close",
+                                        onclick: move |_| config_restore_summary.set(None),
+                                        "×"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if unused_file_count() > 0 {
+                        div { class: "palette-notice",
+                            span { "🧹 {unused_file_count} file(s) in mods, resourcepacks or shaderpacks aren't part of the modpack anymore." }
+                            button {
+                                class: "secondary-button",
+                                onclick: move |_| show_unused_files.set(true),
+                                "Review"
+                            }
+                            button {
+                                class: "error-close",
+                                onclick: move |_| unused_file_count.set(0),
                                 "×"
                             }
                         }
                     }
 
+                    if show_diagnostics() {
+                        crate::launcher::DiagnosticsDialog {
+                            installation: installation_state.read().clone(),
+                            onclose: move |_| show_diagnostics.set(false),
+                        }
+                    }
+
+                    if show_unused_files() {
+                        crate::launcher::OrphanFilesDialog {
+                            installation: installation_state.read().clone(),
+                            onclose: move |_| show_unused_files.set(false),
+                            oncleaned: move |message: String| {
+                                show_unused_files.set(false);
+                                unused_file_count.set(0);
+                                palette_notice.set(Some(message));
+                            }
+                        }
+                    }
+
+                    if let Some(due) = installation_state.read().scheduled_update {
+                        {
+                            let at = due.with_timezone(&chrono::Local).format("%a %H:%M").to_string();
+                            rsx! {
+                                div { class: "scheduled-update-banner",
+                                    span { "🌙 Update scheduled for {at}. The installer has to be running then, minimized is fine." }
+                                    button {
+                                        class: "secondary-button",
+                                        onclick: move |_| {
+                                            let mut cancelled = installation_state.read().clone();
+                                            cancelled.scheduled_update = None;
+                                            match cancelled.save() {
+                                                Ok(_) => installation_state.set(cancelled),
+                                                Err(e) => installation_error.set(Some(format!("Failed to cancel the scheduled update: {}", e))),
+                                            }
+                                        },
+                                        "Cancel"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(message) = install_busy() {
+                        div { class: "modal-overlay",
+                            div { class: "modal-container",
+                                div { class: "modal-header",
+                                    h3 { "Installation in progress" }
+                                    button { class: "modal-close", onclick: move |_| install_busy.set(None), "×" }
+                                }
+                                div { class: "modal-content",
+                                    p { "{message}" }
+                                }
+                                div { class: "modal-footer",
+                                    button { class: "cancel-button", onclick: move |_| install_busy.set(None), "OK" }
+                                }
+                            }
+                        }
+                    }
+
                     // Update warning dialog (if needed)
                     if *show_update_warning.read() {
                         UpdateWarningDialog {
@@ -1714,11 +2796,36 @@ rsx! {
                                 show_update_warning.set(false);
                                 proceed_with_update();
                             },
+                            onschedule: move |_| {
+                                show_update_warning.set(false);
+                                let mut scheduled = installation_state.read().clone();
+                                // Saved as pending, the scheduled update installs this selection
+                                scheduled.save_pre_install_selections(selected_preset.read().clone(), enabled_features.read().clone());
+                                scheduled.scheduled_update = Some(crate::download_schedule::next_off_peak(chrono::Local::now()));
+                                match scheduled.save() {
+                                    Ok(_) => installation_state.set(scheduled),
+                                    Err(e) => installation_error.set(Some(format!("Failed to schedule the update: {}", e))),
+                                }
+                            },
                             installation_path: installation.installation_path.clone(), // Add this line
+                            protected_paths: installation_state.read().backup_config.protected_paths.clone(),
+                            changes: {
+                                // Against what would be installed now, including unsaved feature toggles
+                                let mut pending = installation_state.read().clone();
+                                pending.enabled_features = enabled_features.read().clone();
+                                universal_manifest.read().clone().flatten().and_then(|manifest| pending.pending_changes(&manifest))
+                            },
+                        }
+                    }
+                    
+                    if locked {
+                        div { class: "locked-banner",
+                            "🔒 Installations are locked. Unlock from the home screen to make changes."
                         }
                     }
                     
-                    // Tab content
+                    // Tab content, read-only while locked
+                    div { class: if locked { "tab-content-wrapper locked-content" } else { "tab-content-wrapper" },
 match *active_tab.read() {
     "features" => {
         rsx! {
@@ -1726,6 +2833,7 @@ match *active_tab.read() {
                 universal_manifest: universal_manifest.read().clone().flatten(),
                 presets: presets.read().clone().unwrap_or_default(),
                 enabled_features: enabled_features,
+                installed_features: installed_features,
                 selected_preset: selected_preset,
                 filter_text: filter_text,
                 installation_id: installation.id.clone(),
@@ -1737,7 +2845,9 @@ match *active_tab.read() {
             PerformanceTab {
                 memory_allocation: memory_allocation,
                 java_args: java_args,
-                installation_id: installation.id.clone()
+                installation_id: installation.id.clone(),
+                universal_manifest: universal_manifest.read().clone().flatten(),
+                enabled_features: enabled_features,
             }
         }
     },
@@ -1747,6 +2857,8 @@ match *active_tab.read() {
         crate::launcher::SettingsTab {
             installation: installation.clone(),
             installation_id: installation_id_for_delete.clone(),
+            universal_manifest: universal_manifest.read().clone().flatten(),
+            repair_request,
             ondelete: move |_| {
                 let id_to_delete = installation_id_for_delete.clone();
                 installations.with_mut(|list| {
@@ -1763,23 +2875,84 @@ match *active_tab.read() {
             }
         }
     }
+},
+"screenshots" => {
+    rsx! {
+        ScreenshotsTab {
+            installation: installation.clone(),
+        }
+    }
 },
     _ => rsx! { div { "Unknown tab selected" } }
 }
+                    }
                 }
                 
+                if show_file_preview() && !button_disabled {
+                    div { class: "file-preview-panel",
+                        div { class: "file-preview-header",
+                            h4 { {t_with("manage.preview_title", &[("action", &action_button_label)])} }
+                            button {
+                                class: "modal-close",
+                                onclick: move |_| show_file_preview.set(false),
+                                "×"
+                            }
+                        }
+                        {match file_preview() {
+                            None => rsx! { p { class: "file-preview-summary", "Loading the modpack manifest..." } },
+                            Some(Err(e)) => rsx! { div { class: "error-message", "Can't preview the changes: {e}" } },
+                            Some(Ok(files)) if files.is_empty() => rsx! {
+                                p { class: "file-preview-summary", "No files change." }
+                            },
+                            Some(Ok(files)) => {
+                                use crate::manifest_diff::FileAction;
+                                let count = |action: FileAction| files.iter().filter(|f| f.action == action).count();
+                                let summary = format!(
+                                    "{} added, {} replaced, {} merged, {} deleted",
+                                    count(FileAction::Add),
+                                    count(FileAction::Replace),
+                                    count(FileAction::Merge),
+                                    count(FileAction::Delete)
+                                );
+                                rsx! {
+                                    p { class: "file-preview-summary", "{summary}" }
+                                    ul { class: "file-preview-list",
+                                        for file in files {
+                                            {
+                                                let (class, label) = match file.action {
+                                                    FileAction::Add => ("file-added", "add"),
+                                                    FileAction::Replace => ("file-replaced", "replace"),
+                                                    FileAction::Merge => ("file-merged", "merge"),
+                                                    FileAction::Delete => ("file-deleted", "delete"),
+                                                };
+                                                rsx! {
+                                                    li { class: "{class}",
+                                                        span { class: "file-action", "{label}" }
+                                                        span { class: "file-path", "{file.path}" }
+                                                        span { class: "file-component", "{file.component}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }}
+                    }
+                }
+
                 // Modern fixed footer
                 footer { class: "modern-footer",
                     div { class: "footer-info",
                         div { class: "footer-info-item",
-                            span { class: "footer-info-label", "MINECRAFT" }
+                            span { class: "footer-info-label", {t("manage.footer_minecraft")} }
                             span { class: "footer-info-value", "{installation.minecraft_version}" }
                         }
                         
                         div { class: "footer-divider" }
                         
                         div { class: "footer-info-item",
-                            span { class: "footer-info-label", "FEATURES" }
+                            span { class: "footer-info-label", {t("manage.footer_features")} }
                             span { class: "footer-info-value", 
                                 {
                                     let enabled_count = enabled_features.read().len();
@@ -1820,7 +2993,7 @@ match *active_tab.read() {
                                         
                                         format!("{}/{}", actual_count, total_components)
                                     } else {
-                                        format!("{} enabled", enabled_count)
+                                        t_with("manage.footer_enabled", &[("count", &enabled_count.to_string())])
                                     }
                                 }
                             }
@@ -1830,8 +3003,11 @@ match *active_tab.read() {
                             Fragment {
                                 div { class: "footer-divider" }
                                 div { class: "footer-info-item",
-                                    span { class: "footer-info-label", "STATUS" }
-                                    span { class: "footer-info-value", style: "color: #ffb900;", "Update Available" }
+                                    span { class: "footer-info-label", {t("manage.footer_status")} }
+                                    span { class: "footer-info-value status-update",
+                                        span { class: "status-icon", aria_hidden: "true", "▲" }
+                                        {t("manage.footer_update_available")}
+                                    }
                                 }
                             }
                         }
@@ -1839,6 +3015,13 @@ match *active_tab.read() {
                     
                     // Action buttons container
                     div { class: "footer-actions",
+                        if !button_disabled {
+                            button {
+                                class: "footer-preview-button",
+                                onclick: move |_| show_file_preview.toggle(),
+                                if show_file_preview() { {t("manage.preview_hide")} } else { {t("manage.preview_show")} }
+                            }
+                        }
                         button {
                             class: button_class,
                             disabled: button_disabled,
@@ -1856,8 +3039,20 @@ match *active_tab.read() {
 fn UpdateWarningDialog(
     onclose: EventHandler<()>,
     onproceed: EventHandler<()>,
+    onschedule: EventHandler<()>,
     installation_path: PathBuf, // Add this parameter
+    changes: Option<crate::manifest_diff::ChangeSet>,
+    protected_paths: Vec<String>,
 ) -> Element {
+    let protected = if protected_paths.is_empty() {
+        "no".to_string()
+    } else {
+        protected_paths.join(", ")
+    };
+    let off_peak = crate::download_schedule::next_off_peak(chrono::Local::now())
+        .with_timezone(&chrono::Local)
+        .format("%H:%M")
+        .to_string();
     // Function to open the installation folder
     let open_folder = move |_| {
         let path = installation_path.clone();
@@ -1887,60 +3082,514 @@ fn UpdateWarningDialog(
             error!("Failed to open installation folder: {}", e);
         }
     };
-    
+    
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container update-warning-dialog",
+                div { class: "modal-header",
+                    h3 { "UPDATE WARNING" }
+                    button { 
+                        class: "modal-close",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+                
+                div { class: "modal-content",
+                    if let Some(changes) = &changes {
+                        {
+                            use crate::manifest_diff::ChangeKind;
+                            let added = changes.count(|k| *k == ChangeKind::Added);
+                            let removed = changes.count(|k| *k == ChangeKind::Removed);
+                            let updated = changes.count(|k| matches!(k, ChangeKind::Updated { .. }));
+                            let from_version = changes.from_version.clone();
+                            let to_version = changes.to_version.clone();
+                            rsx! {
+                                div { class: "update-change-list",
+                                    h4 { "Changes ({from_version} → {to_version})" }
+                                    if changes.is_empty() {
+                                        p { class: "update-change-summary", "All components are up to date, only config files are refreshed." }
+                                    } else {
+                                        p { class: "update-change-summary", "{added} added, {updated} updated, {removed} removed" }
+                                        ul {
+                                            for change in changes.changes.clone() {
+                                                {
+                                                    let (class, detail) = match &change.kind {
+                                                        ChangeKind::Added => ("change-added", "new".to_string()),
+                                                        ChangeKind::Removed => ("change-removed", "removed".to_string()),
+                                                        ChangeKind::Updated { from, to } => ("change-updated", format!("{} → {}", from, to)),
+                                                    };
+                                                    rsx! {
+                                                        li { class: "{class}",
+                                                            span { class: "change-section", "{change.section}" }
+                                                            span { class: "change-name", "{change.name}" }
+                                                            span { class: "change-detail", "{detail}" }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    
+                    div { class: "warning-message",
+                        p { 
+                            "Updating may reset some settings, especially Wynntils settings."
+                        }
+                        
+                        p { 
+                            "Your {protected} settings are set aside before the update and put back afterwards, unless this version replaces them on purpose. You'll see what was restored when it's done."
+                        }
+                        
+                        div { class: "warning-note",
+                            p { 
+                                "💡 Tip: Change which folders are protected under Settings → Backups."
+                            }
+                        }
+                    }
+                }
+                
+                div { class: "modal-footer",
+                    button { 
+                        class: "cancel-button",
+                        onclick: move |_| onclose.call(()),
+                        "CANCEL"
+                    }
+                    
+                    button { 
+                        class: "secondary-button open-folder-button",
+                        onclick: open_folder,
+                        "OPEN FOLDER"
+                    }
+                    
+                    button {
+                        class: "secondary-button update-schedule-button",
+                        title: "Download and install at {off_peak}, even if the installer is minimized",
+                        onclick: move |_| onschedule.call(()),
+                        "UPDATE TONIGHT"
+                    }
+                    
+                    button { 
+                        class: "update-proceed-button",
+                        onclick: move |_| onproceed.call(()),
+                        "PROCEED"
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Shared lock state, installations are read-only in the UI while locked
+#[derive(Clone, Copy)]
+pub struct LockState {
+    pub locked: Signal<bool>,
+}
+
+// The app's config, for settings that can also be changed outside the preferences screen
+#[derive(Clone, Copy)]
+pub struct ConfigState {
+    pub config: Signal<super::Config>,
+}
+
+#[component]
+fn UnlockDialog(config: Signal<super::Config>, onclose: EventHandler<()>) -> Element {
+    let mut lock_state = use_context::<LockState>();
+    let mut pin = use_signal(String::new);
+    let mut pin_error = use_signal(|| Option::<String>::None);
+
+    let mut try_unlock = move || {
+        if crate::lock::verify_pin(&config.read().lock, &pin.read()) {
+            info!("Installer unlocked");
+            lock_state.locked.set(false);
+            onclose.call(());
+        } else {
+            warn!("Incorrect unlock PIN entered");
+            pin_error.set(Some("Incorrect PIN".to_string()));
+            pin.set(String::new());
+        }
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container unlock-dialog",
+                div { class: "modal-header",
+                    h3 { "UNLOCK" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    p { "Enter the PIN to allow changes to installations." }
+                    input {
+                        class: "setting-input",
+                        r#type: "password",
+                        inputmode: "numeric",
+                        autofocus: true,
+                        value: "{pin}",
+                        oninput: move |evt| pin.set(evt.value()),
+                        onkeydown: move |evt| {
+                            if evt.key() == Key::Enter {
+                                try_unlock();
+                            }
+                        }
+                    }
+                    if let Some(error) = pin_error() {
+                        p { class: "settings-error", "{error}" }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        onclick: move |_| onclose.call(()),
+                        "CANCEL"
+                    }
+                    button {
+                        class: "update-proceed-button",
+                        onclick: move |_| try_unlock(),
+                        "UNLOCK"
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Heads up when Wynncraft shipped a content update the pack wasn't tested with yet
+#[component]
+fn WynncraftCompatBanner(manifest: crate::universal::UniversalManifest) -> Element {
+    let mut dismissed = use_signal(|| false);
+    let warning = use_resource(move || {
+        let manifest = manifest.clone();
+        async move { crate::wynncraft_compat::check(&CachedHttpClient::new(), &manifest).await }
+    });
+
+    if dismissed() {
+        return rsx! {};
+    }
+    let Some(Some(warning)) = warning.read().clone() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { class: "compat-banner",
+            span { "⚠ {warning.message()}" }
+            button {
+                class: "compat-banner-close",
+                onclick: move |_| dismissed.set(true),
+                "×"
+            }
+        }
+    }
+}
+
+#[component]
+fn CrashReportDialog(
+    report: crate::crash_report::CrashReport,
+    telemetry_enabled: bool,
+    onsubmit: EventHandler<()>,
+    ondismiss: EventHandler<()>,
+) -> Element {
+    let message = report.message.clone();
+    let known_issue = use_resource(move || {
+        let report = report.clone();
+        async move {
+            match crate::crash_report::fetch_known_issues(&CachedHttpClient::new()).await {
+                Ok(issues) => crate::crash_report::find_known_issue(&report, &issues).cloned(),
+                Err(e) => {
+                    debug!("Couldn't check known issues: {}", e);
+                    None
+                }
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container crash-report-dialog",
+                div { class: "modal-header",
+                    h3 { "THE INSTALLER CRASHED" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| ondismiss.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    p { "The installer closed unexpectedly last time. A crash report was saved on your computer." }
+                    textarea { class: "error-area", readonly: true, "{message}" }
+
+                    if let Some(Some(issue)) = known_issue.read().as_ref() {
+                        div { class: "warning-note",
+                            p { "This looks like a known issue: " }
+                            a {
+                                href: "{issue.link}",
+                                target: "_blank",
+                                rel: "noopener noreferrer",
+                                "{issue.title}"
+                            }
+                        }
+                    }
+
+                    if !telemetry_enabled {
+                        p { class: "setting-description",
+                            "Sending reports is disabled because usage statistics are turned off or kept on this computer in Settings."
+                        }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        onclick: move |_| ondismiss.call(()),
+                        "DISMISS"
+                    }
+                    if telemetry_enabled {
+                        button {
+                            class: "update-proceed-button",
+                            onclick: move |_| onsubmit.call(()),
+                            "SEND REPORT"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Launcher profiles pointing at our folders that we don't manage, see profile_reconcile.rs
+#[component]
+fn ProfileReconcileDialog(
+    issues: Signal<Vec<crate::profile_reconcile::ProfileIssue>>,
+    installations: Signal<Vec<Installation>>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut issues = issues.clone();
+    let mut installations = installations.clone();
+    let mut action_error = use_signal(|| Option::<String>::None);
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container profile-reconcile-dialog",
+                div { class: "modal-header",
+                    h3 { "Launcher profiles need attention" }
+                    button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                }
+
+                div { class: "modal-content",
+                    p {
+                        "These Minecraft Launcher profiles use installer folders but aren't managed by the installer. "
+                        "Adopt them to manage them here, or remove them from the launcher. Game files are never deleted."
+                    }
+                    if let Some(error) = action_error() {
+                        div { class: "error-message", "{error}" }
+                    }
+                    div { class: "profile-issue-list",
+                        for issue in issues() {
+                            {
+                                let issue_for_adopt = issue.clone();
+                                let issue_for_remove = issue.clone();
+                                let game_dir = issue.game_dir.display().to_string();
+                                rsx! {
+                                    div {
+                                        key: "{issue.profile_key}",
+                                        class: "profile-issue",
+                                        div { class: "profile-issue-info",
+                                            strong { "{issue.profile_name}" }
+                                            span { class: "profile-issue-kind", "{issue.description()}" }
+                                            code { "{game_dir}" }
+                                        }
+                                        div { class: "profile-issue-actions",
+                                            if issue.can_adopt() {
+                                                button {
+                                                    class: "update-proceed-button",
+                                                    onclick: move |_| {
+                                                        match crate::profile_reconcile::adopt(&issue_for_adopt) {
+                                                            Ok(id) => {
+                                                                // Newly adopted installations have to show up on the home page
+                                                                if !installations.read().iter().any(|i| i.id == id) {
+                                                                    if let Ok(adopted) = installation::load_installation(&id) {
+                                                                        installations.write().insert(0, adopted);
+                                                                    }
+                                                                }
+                                                                action_error.set(None);
+                                                                issues.write().retain(|i| i.profile_key != issue_for_adopt.profile_key);
+                                                            }
+                                                            Err(e) => {
+                                                                error!("Failed to adopt profile: {}", e);
+                                                                action_error.set(Some(e));
+                                                            }
+                                                        }
+                                                    },
+                                                    "Adopt"
+                                                }
+                                            }
+                                            button {
+                                                class: "cancel-button",
+                                                onclick: move |_| {
+                                                    match crate::profile_reconcile::remove(&issue_for_remove) {
+                                                        Ok(_) => {
+                                                            action_error.set(None);
+                                                            issues.write().retain(|i| i.profile_key != issue_for_remove.profile_key);
+                                                        }
+                                                        Err(e) => {
+                                                            error!("Failed to remove profile: {}", e);
+                                                            action_error.set(Some(e));
+                                                        }
+                                                    }
+                                                },
+                                                "Remove"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        onclick: move |_| onclose.call(()),
+                        "LATER"
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Installs and updates the installer didn't get to finish last time, see install_journal.rs
+#[component]
+fn InterruptedInstallDialog(
+    journals: Signal<Vec<crate::install_journal::InstallJournal>>,
+    installations: Signal<Vec<Installation>>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut journals = journals.clone();
+    let mut installations = installations.clone();
+    let mut busy = use_signal(|| Option::<String>::None);
+    let mut action_error = use_signal(|| Option::<String>::None);
+
+    // Both actions end the same way, the installation is reloaded and the entry disappears
+    let mut run = move |journal: crate::install_journal::InstallJournal, roll_back: bool| {
+        busy.set(Some(journal.installation_id.clone()));
+        action_error.set(None);
+        spawn(async move {
+            let http_client = CachedHttpClient::new();
+            let result = if roll_back {
+                crate::install_journal::roll_back(&http_client, &journal).await
+            } else {
+                crate::install_journal::resume(&http_client, &journal).await
+            };
+            match result {
+                Ok(_) => {
+                    if let Ok(updated) = installation::load_installation(&journal.installation_id) {
+                        installations.with_mut(|list| {
+                            if let Some(existing) = list.iter_mut().find(|i| i.id == updated.id) {
+                                *existing = updated;
+                            }
+                        });
+                    }
+                    journals.write().retain(|j| j.installation_id != journal.installation_id);
+                }
+                Err(e) => {
+                    error!("Failed to recover {}: {}", journal.installation_id, e);
+                    action_error.set(Some(e));
+                }
+            }
+            busy.set(None);
+        });
+    };
+
     rsx! {
         div { class: "modal-overlay",
-            div { class: "modal-container update-warning-dialog",
+            div { class: "modal-container interrupted-install-dialog",
                 div { class: "modal-header",
-                    h3 { "UPDATE WARNING" }
-                    button { 
-                        class: "modal-close",
-                        onclick: move |_| onclose.call(()),
-                        "×"
-                    }
+                    h3 { "Unfinished updates" }
+                    button { class: "modal-close", disabled: busy().is_some(), onclick: move |_| onclose.call(()), "×" }
                 }
-                
+
                 div { class: "modal-content",
-                    div { class: "warning-message",
-                        p { 
-                            "Updating may reset some settings, especially Wynntils settings."
-                        }
-                        
-                        p { 
-                            "To protect your Wynntils configuration:"
-                        }
-                        
-                        ol { class: "protection-steps",
-                            li { "Click 'Open Folder' below" }
-                            li { "Make a backup copy of the 'wynntils' folder" }
-                            li { "After updating, restore your backed-up folder if needed" }
-                        }
-                        
-                        div { class: "warning-note",
-                            p { 
-                                "💡 Tip: Keep your Wynntils folder backed up regularly to avoid losing your custom settings."
+                    p {
+                        "The installer was closed while these installations were being installed or updated, so they may be missing files. "
+                        "Resume to finish the update, or roll back to the version they had before."
+                    }
+                    if let Some(error) = action_error() {
+                        div { class: "error-message", "{error}" }
+                    }
+                    div { class: "profile-issue-list",
+                        for journal in journals() {
+                            {
+                                let name = installations
+                                    .read()
+                                    .iter()
+                                    .find(|i| i.id == journal.installation_id)
+                                    .map(|i| i.name.clone())
+                                    .unwrap_or_else(|| journal.installation_id.clone());
+                                let versions = match &journal.from_version {
+                                    Some(from) => format!("{} → {}", from, journal.to_version),
+                                    None => format!("First install of {}", journal.to_version),
+                                };
+                                let started = journal.started_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string();
+                                let steps = journal.completed.len();
+                                let step_count = crate::install_journal::STEP_COUNT;
+                                let is_busy = busy().as_ref() == Some(&journal.installation_id);
+                                let any_busy = busy().is_some();
+                                let can_roll_back = journal.can_roll_back();
+                                let journal_for_resume = journal.clone();
+                                let journal_for_roll_back = journal.clone();
+                                rsx! {
+                                    div {
+                                        key: "{journal.installation_id}",
+                                        class: "profile-issue",
+                                        div { class: "profile-issue-info",
+                                            strong { "{name}" }
+                                            span { class: "profile-issue-kind", "{versions}" }
+                                            span { class: "interrupted-install-progress", "Started {started}, {steps} of {step_count} steps done" }
+                                        }
+                                        div { class: "profile-issue-actions",
+                                            if is_busy {
+                                                span { class: "interrupted-install-busy", "Working..." }
+                                            } else {
+                                                button {
+                                                    class: "update-proceed-button",
+                                                    disabled: any_busy,
+                                                    onclick: move |_| run(journal_for_resume.clone(), false),
+                                                    "Resume"
+                                                }
+                                                if can_roll_back {
+                                                    button {
+                                                        class: "cancel-button",
+                                                        disabled: any_busy,
+                                                        onclick: move |_| run(journal_for_roll_back.clone(), true),
+                                                        "Roll back"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
                 }
-                
+
                 div { class: "modal-footer",
-                    button { 
+                    button {
                         class: "cancel-button",
+                        disabled: busy().is_some(),
                         onclick: move |_| onclose.call(()),
-                        "CANCEL"
-                    }
-                    
-                    button { 
-                        class: "secondary-button open-folder-button",
-                        onclick: open_folder,
-                        "OPEN FOLDER"
-                    }
-                    
-                    button { 
-                        class: "update-proceed-button",
-                        onclick: move |_| onproceed.call(()),
-                        "PROCEED"
+                        "LATER"
                     }
                 }
             }
@@ -1955,16 +3604,23 @@ fn ProgressView(
     status: String,
     title: String,
     on_complete: Option<EventHandler<()>>, // Add callback for completion
+    // Components partly downloaded, fills the bar between whole ones
+    partial: Option<f64>,
+    // File, speed and time left
+    detail: Option<String>,
 ) -> Element {
     // Calculate percentage accurately
     let percentage = if max > 0 { 
-        ((value as f64 / max as f64) * 100.0) as i64 
+        (((value as f64 + partial.unwrap_or(0.0)).min(max as f64) / max as f64) * 100.0) as i64 
     } else { 
         0 
     };
     
     // FIXED: Proper completion detection - must be exact match and status indicates completion
+    // The management page sets the translated status, the English checks cover the other callers
+    let completed_status = t("progress.completed");
     let is_complete = value >= max && max > 0 && value > 0 && (
+        status == completed_status ||
         status.contains("completed") || 
         status.contains("Complete") || 
         status.contains("successfully") ||
@@ -1994,11 +3650,11 @@ fn ProgressView(
     
     // Display status with proper completion handling
     let display_status = if is_complete {
-        "Installation completed successfully!".to_string()
+        completed_status
     } else if status.is_empty() {
-        format!("Processing... {}%", percentage)
+        t_with("progress.processing", &[("percent", &percentage.to_string())])
     } else if percentage >= 99 && !is_complete {
-        format!("{} - Finalizing...", status)
+        t_with("progress.finalizing", &[("status", &status)])
     } else {
         status.clone()
     };
@@ -2019,12 +3675,12 @@ fn ProgressView(
     };
     
     let steps = vec![
-        ("prepare", "Prepare"),
-        ("download", "Download"),
-        ("extract", "Extract"),
-        ("configure", "Configure"),
-        ("finish", "Finish"),
-        ("complete", "Complete"),
+        ("prepare", t("progress.step_prepare")),
+        ("download", t("progress.step_download")),
+        ("extract", t("progress.step_extract")),
+        ("configure", t("progress.step_configure")),
+        ("finish", t("progress.step_finish")),
+        ("complete", t("progress.step_complete")),
     ];
     
     // Find current step index
@@ -2080,6 +3736,10 @@ fn ProgressView(
                 }
                 
                 p { class: "progress-status", "{display_status}" }
+
+                if let Some(detail) = detail.filter(|_| !is_complete) {
+                    p { class: "progress-detail", "{detail}" }
+                }
                 
                 // Download warning - NEW ADDITION
                 if show_download_warning {
@@ -2088,11 +3748,11 @@ fn ProgressView(
                         div { class: "warning-text",
                             p { 
                                 class: "warning-main",
-                                "Installation may take 5-15 minutes depending on your connection and selected features."
+                                {t("progress.warning_main")}
                             }
                             p { 
                                 class: "warning-sub",
-                                "Please don't close this window - the process is working even if it appears to pause briefly."
+                                {t("progress.warning_sub")}
                             }
                         }
                     }
@@ -2101,7 +3761,7 @@ fn ProgressView(
                 // Success indicator when complete
                 if is_complete {
                     div { class: "completion-indicator",
-                        "✓ Ready to play!"
+                        {t("progress.ready")}
                     }
                 }
                 
@@ -2259,8 +3919,10 @@ fn PackUninstallButton(launcher: Launcher, pack: PackName) -> Element {
             button {
                 class: "uninstall-list-item",
                 onclick: move |_| {
-                    uninstall(&launcher, &pack.uuid).unwrap();
-                    *hidden.write() = true;
+                    match uninstall(&launcher, &pack.uuid) {
+                        Ok(()) => *hidden.write() = true,
+                        Err(e) => error!("Failed to uninstall '{}': {}", pack.uuid, e),
+                    }
                 },
                 "{pack.name}"
             }
@@ -2268,103 +3930,6 @@ fn PackUninstallButton(launcher: Launcher, pack: PackName) -> Element {
     )
 }
 
-#[derive(PartialEq, Props, Clone)]
-struct SettingsProps {
-    config: Signal<super::Config>,
-    settings: Signal<bool>,
-    config_path: PathBuf,
-    error: Signal<Option<String>>,
-    b64_id: String,
-}
-
-#[component]
-fn Settings(mut props: SettingsProps) -> Element {
-    let mut vanilla = None;
-    let mut multimc = None;
-    let mut prism = None;
-    let mut custom = None;
-    let launcher = get_launcher(&props.config.read().launcher).unwrap();
-    
-    match &props.config.read().launcher[..] {
-        "vanilla" => vanilla = Some("true"),
-        "multimc-MultiMC" => multimc = Some("true"),
-        "multimc-PrismLauncher" => prism = Some("true"),
-        _ => {}
-    }
-    if props.config.read().launcher.starts_with("custom") {
-        custom = Some("true")
-    }
-
-    rsx! {
-        div { class: "settings-container",
-            h1 { class: "settings-title", "Launcher Settings" }  // Updated title
-            form {
-                id: "settings",
-                class: "settings-form",
-                onsubmit: move |event| {
-                    props
-                        .config
-                        .write()
-                        .launcher = event.data.values()["launcher-select"].as_value();
-                    if let Err(e) = std::fs::write(
-                        &props.config_path,
-                        serde_json::to_vec(&*props.config.read()).unwrap(),
-                    ) {
-                        props.error.set(Some(format!("{:#?}", e) + " (Failed to write config!)"));
-                    }
-                    props.settings.set(false);
-                },
-                
-                div { class: "setting-group",
-                    label { class: "setting-label", "Minecraft Launcher:" }
-                    select {
-                        name: "launcher-select",
-                        id: "launcher-select",
-                        form: "settings",
-                        class: "setting-select",
-                        if super::get_minecraft_folder().is_dir() {
-                            option { value: "vanilla", selected: vanilla, "Vanilla Launcher" }
-                        }
-                        if super::get_multimc_folder("MultiMC").is_ok() {
-                            option { value: "multimc-MultiMC", selected: multimc, "MultiMC" }
-                        }
-                        if super::get_multimc_folder("PrismLauncher").is_ok() {
-                            option {
-                                value: "multimc-PrismLauncher",
-                                selected: prism,
-                                "Prism Launcher"
-                            }
-                        }
-                        if custom.is_some() {
-                            option {
-                                value: "{props.config.read().launcher}",
-                                selected: custom,
-                                "Custom MultiMC"
-                            }
-                        }
-                    }
-                }
-                
-                CustomMultiMCButton {
-                    config: props.config,
-                    config_path: props.config_path.clone(),
-                    error: props.error,
-                    b64_id: props.b64_id.clone()
-                }
-                
-                div { class: "settings-buttons",
-                    input {
-                        r#type: "submit",
-                        value: "Save Changes",
-                        class: "primary-button",
-                        id: "save"
-                    }
-                }
-            }
-        }
-    }
-}
-
 #[derive(PartialEq, Props, Clone)]
 struct LauncherProps {
     config: Signal<super::Config>,
@@ -2850,23 +4415,11 @@ fn Version(mut props: VersionProps) -> Element {
                                 installed.set(true);
                                 debug!("SET INSTALLED: true");
                                 
-                                let _ = isahc::post(
-                                    "https://tracking.commander07.workers.dev/track",
-                                    format!(
-                                        "{{
-                                    \"projectId\": \"55db8403a4f24f3aa5afd33fd1962888\",
-                                    \"dataSourceId\": \"{}\",
-                                    \"userAction\": \"update\",
-                                    \"additionalData\": {{
-                                        \"old_version\": \"{}\",
-                                        \"new_version\": \"{}\"
-                                    }}
-                                }}",
-                                        installer_profile.manifest.uuid,
-                                        installer_profile.local_manifest.unwrap().modpack_version,
-                                        installer_profile.manifest.modpack_version
-                                    ),
-                                );
+                                crate::Analytics::record(crate::Event::Update {
+                                    uuid: installer_profile.manifest.uuid.clone(),
+                                    old_version: installer_profile.local_manifest.map(|m| m.modpack_version),
+                                    new_version: installer_profile.manifest.modpack_version.clone(),
+                                });
                             }
                             Err(e) => {
                                 props.error.set(Some(
@@ -2886,21 +4439,10 @@ fn Version(mut props: VersionProps) -> Element {
                         .await
                         {
                             Ok(_) => {
-                                let _ = isahc::post(
-                                    "https://tracking.commander07.workers.dev/track",
-                                    format!(
-                                        "{{
-                                    \"projectId\": \"55db8403a4f24f3aa5afd33fd1962888\",
-                                    \"dataSourceId\": \"{}\",
-                                    \"userAction\": \"modify\",
-                                    \"additionalData\": {{
-                                        \"features\": {:?}
-                                    }}
-                                }}",
-                                        installer_profile.manifest.uuid,
-                                        installer_profile.manifest.enabled_features
-                                    ),
-                                );
+                                crate::Analytics::record(crate::Event::Modify {
+                                    uuid: installer_profile.manifest.uuid.clone(),
+                                    features: installer_profile.manifest.enabled_features.clone(),
+                                });
                             }
                             Err(e) => {
                                 props.error.set(Some(
@@ -3131,7 +4673,12 @@ fn AppHeader(
     on_select_installation: EventHandler<String>,
     on_go_home: EventHandler<()>,
     on_open_settings: EventHandler<()>,
+    on_open_discover: EventHandler<()>,
+    discover_active: bool,
     show_installation_tabs: bool,
+    lock_enabled: bool,
+    locked: bool,
+    on_toggle_lock: EventHandler<()>,
 ) -> Element {
     let icon_base64 = {
         use base64::{Engine, engine::general_purpose::STANDARD};
@@ -3153,7 +4700,7 @@ fn AppHeader(
                 
                 div { class: "header-center",
                     button { 
-                        class: if current_installation_id.read().is_none() { 
+                        class: if current_installation_id.read().is_none() && !discover_active { 
                             "nav-tab active" 
                         } else { 
                             "nav-tab" 
@@ -3161,6 +4708,11 @@ fn AppHeader(
                         onclick: move |_| on_go_home.call(()),
                         "Home"
                     }
+                    button {
+                        class: if discover_active { "nav-tab active" } else { "nav-tab" },
+                        onclick: move |_| on_open_discover.call(()),
+                        "Discover"
+                    }
                     
                     // Direct installation tabs - CHANGED FROM take(3) TO take(2)
                     for installation in installations().iter().take(2) {
@@ -3213,10 +4765,18 @@ fn AppHeader(
                 }
                 
                 div { class: "header-right",
+                    if lock_enabled {
+                        button {
+                            class: if locked { "nav-tab lock-button locked" } else { "nav-tab lock-button" },
+                            title: if locked { "Unlock to make changes" } else { "Lock installations" },
+                            onclick: move |_| on_toggle_lock.call(()),
+                            if locked { "🔒 Locked" } else { "🔓 Unlocked" }
+                        }
+                    }
                     button { 
                         class: "nav-tab",
                         onclick: move |_| on_open_settings.call(()),
-                        "Launcher"
+                        "Settings"
                     }
                 }
             }
@@ -3233,6 +4793,8 @@ pub struct AppProps {
     pub modpack_source: String,
     pub config: super::Config,
     pub config_path: PathBuf,
+    // Shown once on startup, e.g. when a corrupt config had to be reset
+    pub config_notice: Option<String>,
     pub installations: Vec<Installation>,
 }
 
@@ -3243,13 +4805,213 @@ pub fn app() -> Element {
     
     // State management
     let config = use_signal(|| props.config);
+    use_context_provider(|| ConfigState { config });
     let mut settings = use_signal(|| false);
-    let mut error_signal = use_signal(|| Option::<String>::None);
+    let mut discover = use_signal(|| false);
+    let mut error_signal = use_signal(|| props.config_notice.clone());
     let mut manifest_error = use_signal(|| Option::<ManifestError>::None);
     
     // Installation handling
     let mut current_installation_id = use_signal(|| Option::<String>::None);
     let mut installations = use_signal(|| props.installations.clone());
+    let mut pending_crashes = use_signal(crate::crash_report::load_pending_reports);
+    let mut lock_state = use_context_provider(|| LockState {
+        locked: Signal::new(config.peek().lock.enabled),
+    });
+    let mut show_unlock = use_signal(|| false);
+    // Automatic updates run without the UI, they go by this
+    use_effect(move || {
+        crate::lock::configure(&config.read().lock);
+        crate::lock::set_engaged(*lock_state.locked.read());
+    });
+    // Commands from the Ctrl+K palette that the installation page has to carry out
+    let mut palette_request = use_context_provider(|| command_palette::PaletteRequest {
+        pending: Signal::new(None),
+    });
+
+    // Pack tester watch mode, the loop checks the settings itself so it's always started
+    use_hook(move || {
+        spawn(async move {
+            crate::watch_mode::run_watch_loop(move || {
+                let config = config.peek();
+                if config.advanced.developer_mode {
+                    Some(config.watch.clone())
+                } else {
+                    None
+                }
+            }).await;
+        })
+    });
+
+    // Hand-edited or restored launcher profiles pointing at our folders, checked once per start
+    let mut profile_issues = use_signal(|| {
+        if config.peek().launcher != "vanilla" || config.peek().first_launch.unwrap_or(true) {
+            return Vec::new();
+        }
+        crate::profile_reconcile::scan().unwrap_or_else(|e| {
+            warn!("Failed to check launcher profiles: {}", e);
+            Vec::new()
+        })
+    });
+
+    // Installs the installer crashed or was closed in the middle of, checked once per start
+    let mut interrupted_installs = use_signal(crate::install_journal::find_interrupted);
+
+    // Installation reminders, shown as a banner until dismissed
+    let mut due_reminders = use_signal(Vec::<crate::reminders::DueReminder>::new);
+    use_hook(move || {
+        spawn(async move {
+            crate::reminders::run_reminder_loop(move |due| {
+                due_reminders.write().extend(due);
+            }).await;
+        })
+    });
+
+    // "Update tonight", the loop runs scheduled updates once they're due
+    use_hook(move || {
+        spawn(async move {
+            crate::download_schedule::run_scheduled_update_loop(move |id| {
+                if let Ok(updated) = crate::installation::load_installation(&id) {
+                    installations.with_mut(|list| {
+                        if let Some(existing) = list.iter_mut().find(|i| i.id == id) {
+                            *existing = updated;
+                        }
+                    });
+                }
+            }).await;
+        })
+    });
+
+    // Tray mode, the icon lives as long as the app. Checks run on their own task so the menu stays
+    // responsive while one is going
+    let tray = use_hook(|| {
+        if !config.peek().general.tray_mode {
+            return None;
+        }
+        match crate::tray::Tray::create() {
+            Ok(tray) => Some(std::rc::Rc::new(tray)),
+            Err(e) => {
+                warn!("Tray mode is on, but {}", e);
+                None
+            }
+        }
+    });
+    // Without the tray, installations that keep themselves up to date are checked once per start
+    use_hook(move || {
+        if config.peek().general.tray_mode {
+            return;
+        }
+        spawn(async move {
+            let http_client = CachedHttpClient::new();
+            for result in crate::auto_update::check_and_run(&http_client).await {
+                if let Ok(updated) = crate::installation::load_installation(&result.id) {
+                    installations.with_mut(|list| {
+                        if let Some(existing) = list.iter_mut().find(|i| i.id == result.id) {
+                            *existing = updated;
+                        }
+                    });
+                }
+            }
+        });
+    });
+    let check_now = use_hook(|| std::rc::Rc::new(tokio::sync::Notify::new()));
+    use_hook({
+        let (tray, check_now) = (tray.clone(), check_now.clone());
+        move || {
+            let Some(tray) = tray else { return };
+            let interval = std::time::Duration::from_secs(config.peek().general.tray_check_hours.max(1) * 3600);
+            spawn(async move {
+                let http_client = CachedHttpClient::new();
+                loop {
+                    let mut with_update = crate::tray::check_for_updates(&http_client).await;
+                    // Ones that keep themselves up to date don't need the user
+                    for result in crate::auto_update::run(&with_update).await {
+                        if matches!(result.outcome, crate::batch_update::Outcome::Updated { .. }) {
+                            with_update.retain(|i| i.id != result.id);
+                            if let Ok(updated) = crate::installation::load_installation(&result.id) {
+                                with_update.push(updated);
+                            }
+                        }
+                    }
+                    installations.with_mut(|list| {
+                        for updated in &with_update {
+                            if let Some(existing) = list.iter_mut().find(|i| i.id == updated.id) {
+                                *existing = updated.clone();
+                            }
+                        }
+                    });
+                    with_update.retain(|i| i.update_available || i.preset_update_available);
+                    crate::tray::notify_updates(&tray.set_pending(&with_update));
+                    tokio::select! {
+                        _ = tokio::time::sleep(interval) => {}
+                        _ = check_now.notified() => {}
+                    }
+                }
+            });
+        }
+    });
+    // A second installer was started, with or without the tray this window is the one to show
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                if crate::instance_lock::take_show_request() {
+                    let window = dioxus::desktop::window();
+                    window.set_visible(true);
+                    window.set_focus();
+                }
+                tokio::time::sleep(crate::tray::POLL_INTERVAL).await;
+            }
+        });
+    });
+    use_hook(move || {
+        let Some(tray) = tray else { return };
+        spawn(async move {
+            loop {
+                while let Some(action) = tray.poll() {
+                    debug!("Tray: {:?}", action);
+                    let show_window = || {
+                        let window = dioxus::desktop::window();
+                        window.set_visible(true);
+                        window.set_focus();
+                    };
+                    match action {
+                        crate::tray::TrayAction::Open => show_window(),
+                        crate::tray::TrayAction::CheckNow => check_now.notify_one(),
+                        crate::tray::TrayAction::UpdateNow(id) => {
+                            show_window();
+                            if let Some(id) = id {
+                                settings.set(false);
+                                current_installation_id.set(Some(id.clone()));
+                                palette_request.pending.set(Some(command_palette::PageCommand {
+                                    installation_id: id,
+                                    action: command_palette::PageAction::Update,
+                                }));
+                            }
+                        }
+                        crate::tray::TrayAction::Quit => std::process::exit(0),
+                    }
+                }
+                tokio::time::sleep(crate::tray::POLL_INTERVAL).await;
+            }
+        });
+    });
+
+    // Keep the crash context up to date with what the user is looking at
+    use_effect(move || {
+        match current_installation_id.read().as_ref() {
+            Some(id) => {
+                crate::crash_report::set_crash_context("current_installation", id.clone());
+                match crate::installation::load_installation(id) {
+                    Ok(installation) => crate::installation::tag_experimental_context(&installation),
+                    Err(_) => crate::crash_report::clear_crash_context("experimental_features"),
+                }
+            }
+            None => {
+                crate::crash_report::clear_crash_context("current_installation");
+                crate::crash_report::clear_crash_context("experimental_features");
+            }
+        }
+    });
 
     // Get launcher configuration
         let has_launcher = match get_launcher(&config.read().launcher) {
@@ -3331,12 +5093,16 @@ use_effect(move || {
     use_effect(move || {
     let installations = installations.clone();
     let http_client = CachedHttpClient::new();
+    if !config.peek().general.check_updates_on_startup {
+        debug!("Skipping startup update check (disabled in settings)");
+        return;
+    }
     
     spawn(async move {
         // Check for updates on startup
         for mut installation in installations.read().clone() {
             if installation.installed {
-                if let Ok(presets) = crate::preset::load_presets(&http_client, None).await {
+                if let Ok(presets) = installation.load_presets(&http_client).await {
                     let _ = installation.check_for_updates(&http_client, &presets).await;
                 }
             }
@@ -3371,17 +5137,23 @@ let preset_styles = include_str!("assets/preset-styles.css");
 let search_styles = include_str!("assets/search-results-styles.css");
 let modal_styles = include_str!("assets/modal-styles.css");
 let installation_header_styles = include_str!("assets/installation-header-styles.css");
+let preferences_styles = include_str!("assets/preferences-styles.css");
+let accessibility_styles = include_str!("assets/accessibility-styles.css");
+let theme_styles = include_str!("assets/theme-styles.css");
 //let file_tree_styles = include_str!("assets/file-tree-styles.css");
 
 // Combine all CSS files
-let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}", 
+let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}", 
     css_content, 
     category_styles, 
     feature_styles, 
     preset_styles, 
     search_styles,
     modal_styles,
-    installation_header_styles
+    installation_header_styles,
+    preferences_styles,
+    accessibility_styles,
+    theme_styles
 );
 
     let mut modal_context = use_context_provider(ModalContext::default);
@@ -3411,6 +5183,7 @@ let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}",
                 current_installation_id: current_installation_id.clone(),
                 show_installation_tabs: false, // NEW: Never show installation tabs in main header
                 on_select_installation: move |id: String| {
+                    discover.set(false);
                     if id == "new" {
                         current_installation_id.set(Some(id));
                     } else {
@@ -3418,10 +5191,25 @@ let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}",
                     }
                 },
                 on_go_home: move |_| {
+                    discover.set(false);
                     current_installation_id.set(None);
                 },
                 on_open_settings: move |_| {
                     settings.set(true);
+                },
+                on_open_discover: move |_| {
+                    current_installation_id.set(None);
+                    discover.set(true);
+                },
+                discover_active: discover(),
+                lock_enabled: config.read().lock.enabled,
+                locked: *lock_state.locked.read(),
+                on_toggle_lock: move |_| {
+                    if *lock_state.locked.peek() {
+                        show_unlock.set(true);
+                    } else {
+                        lock_state.locked.set(true);
+                    }
                 }
             }
         })
@@ -3433,7 +5221,7 @@ let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}",
     let main_content = if settings() {
         // Settings screen
         rsx! {
-            Settings {
+            Preferences {
                 config,
                 settings,
                 config_path: props.config_path.clone(),
@@ -3460,6 +5248,18 @@ let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}",
                 p { class: "loading-info", "This may take a moment. Please wait..." }
             }
         }
+    } else if discover() && current_installation_id.read().is_none() {
+        rsx! {
+            discover::DiscoverPage {
+                branches: props.branches.clone(),
+                modpack_source: props.modpack_source.clone(),
+                default_branch: String::from("master"),
+                on_install: move |_| {
+                    discover.set(false);
+                    current_installation_id.set(Some("new".to_string()));
+                }
+            }
+        }
     } else {
         // Main content based on current state
         if current_installation_id.read().is_none() {
@@ -3507,8 +5307,12 @@ let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}",
     };
 
     // Combine components for final render
+    // Palette overrides are scoped to this class, see accessibility-styles.css
+    let palette_class = format!("palette-{}", config.read().general.color_palette);
+    let theme_class = crate::theme::mode_class(&config.read().general.theme);
+
     rsx! {
-        div {
+        div { class: "{palette_class} {theme_class}",
             style { {complete_css} }
             Modal {}
             BackgroundParticles {}
@@ -3526,21 +5330,117 @@ let complete_css = format!("{}\n{}\n{}\n{}\n{}\n{}\n{}",
                 } else {
                     "main-container"
                 },
+                if !settings() {
+                    if let Some(Some(manifest)) = universal_manifest.read().as_ref() {
+                        WynncraftCompatBanner { manifest: manifest.clone() }
+                    }
+                }
+                for due in due_reminders() {
+                    div {
+                        key: "{due.reminder.id}",
+                        class: "reminder-banner",
+                        span { class: "status-icon", aria_hidden: "true", "⏰" }
+                        div { class: "reminder-text",
+                            strong { "{due.installation_name}: " }
+                            "{due.reminder.message}"
+                        }
+                        button {
+                            class: "reminder-open",
+                            onclick: {
+                                let installation_id = due.installation_id.clone();
+                                move |_| {
+                                    settings.set(false);
+                                    discover.set(false);
+                                    current_installation_id.set(Some(installation_id.clone()));
+                                }
+                            },
+                            "Open"
+                        }
+                        button {
+                            class: "reminder-dismiss",
+                            onclick: {
+                                let reminder_id = due.reminder.id.clone();
+                                move |_| due_reminders.write().retain(|d| d.reminder.id != reminder_id)
+                            },
+                            "×"
+                        }
+                    }
+                }
                 {main_content}
             }
             
             // Only show footer on home page (not installation pages)
-if !settings() && current_installation_id.read().is_none() && 
+if !settings() && !discover() && current_installation_id.read().is_none() && 
    !config.read().first_launch.unwrap_or(true) && has_launcher {
     Footer { changelog: changelog_signal() }  
 }
             
+            if !config.read().first_launch.unwrap_or(true) && has_launcher {
+                command_palette::CommandPalette {
+                    installations,
+                    current_installation_id,
+                    settings,
+                    manifest: universal_manifest.read().as_ref().cloned().flatten(),
+                    error: error_signal.clone(),
+                }
+            }
+
+            if show_unlock() {
+                UnlockDialog {
+                    config,
+                    onclose: move |_| show_unlock.set(false)
+                }
+            }
+
+            if !profile_issues.read().is_empty() {
+                ProfileReconcileDialog {
+                    issues: profile_issues,
+                    installations,
+                    onclose: move |_| profile_issues.set(Vec::new())
+                }
+            }
+
+            if profile_issues.read().is_empty() && !interrupted_installs.read().is_empty() {
+                InterruptedInstallDialog {
+                    journals: interrupted_installs,
+                    installations,
+                    onclose: move |_| interrupted_installs.set(Vec::new())
+                }
+            }
+
+            // Offer to send the most recent unhandled crash report from a previous run
+            if let Some((path, report)) = pending_crashes.read().first().cloned() {
+                CrashReportDialog {
+                    report: report.clone(),
+                    telemetry_enabled: config.read().privacy.telemetry_mode() == crate::telemetry::Mode::Send,
+                    onsubmit: {
+                        let path = path.clone();
+                        move |_| {
+                            crate::crash_report::submit_crash_report(&report);
+                            if let Err(e) = crate::crash_report::mark_handled(&path, true) {
+                                error!("{}", e);
+                            }
+                            // Only bother the user about one crash per startup
+                            pending_crashes.set(Vec::new());
+                        }
+                    },
+                    ondismiss: move |_| {
+                        if let Err(e) = crate::crash_report::mark_handled(&path, false) {
+                            error!("{}", e);
+                        }
+                        pending_crashes.set(Vec::new());
+                    }
+                }
+            }
+
             // Add manifest error display outside of the main container
             if let Some(error) = manifest_error() {
                 ManifestErrorDisplay {
                     error: error.message.clone(),
                     error_type: format!("{}", error.error_type),
                     file_name: error.file_name.clone(),
+                    location: error.location.clone(),
+                    report: crate::manifest_validation::report(&error),
                     onclose: move |_| manifest_error.set(None),
                     onreport: move |_| {
                         let _ = open_url("https://discord.com/channels/778965021656743966/1234506784626970684");
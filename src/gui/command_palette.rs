@@ -0,0 +1,277 @@
+use dioxus::prelude::*;
+use log::{debug, error};
+
+use crate::universal::UniversalManifest;
+use crate::Installation;
+
+// Ctrl+K quick switcher. Global commands run right here, anything that needs an installation page
+// (updating, switching tabs, toggling a component) is handed to the page through PaletteRequest.
+
+const MAX_RESULTS: usize = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageAction {
+    Tab(&'static str),
+    Update,
+    ToggleFeature(String),
+    // Settings tab with Verify Files open, from the health badge on the home page
+    Repair,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCommand {
+    pub installation_id: String,
+    pub action: PageAction,
+}
+
+// Shared with InstallationManagementPage, which runs and clears the pending command
+#[derive(Clone, Copy)]
+pub struct PaletteRequest {
+    pub pending: Signal<Option<PageCommand>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Home,
+    NewInstallation,
+    OpenSettings,
+    OpenLogs,
+    Open(String),
+    Launch(String),
+    Page(PageCommand),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    label: String,
+    hint: String,
+    command: Command,
+}
+
+/// Subsequence match, higher is better. Consecutive characters and word starts score extra so
+/// "upov2" finds "Update Overhaul 2" before anything that merely contains the letters.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..text.len()).find(|&i| text[i] == q)?;
+        score += 1;
+        if previous_match.map_or(false, |p| p + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous_match = Some(found);
+        position = found + 1;
+    }
+    // Prefer shorter labels when the match is otherwise equal
+    Some(score * 100 - text.len() as i32)
+}
+
+fn build_entries(
+    installations: &[Installation],
+    current_installation: Option<&Installation>,
+    manifest: Option<&UniversalManifest>,
+) -> Vec<Entry> {
+    let mut entries = vec![
+        Entry { label: "Go home".to_string(), hint: "Navigation".to_string(), command: Command::Home },
+        Entry { label: "New installation".to_string(), hint: "Navigation".to_string(), command: Command::NewInstallation },
+        Entry { label: "Open settings".to_string(), hint: "Navigation".to_string(), command: Command::OpenSettings },
+        Entry { label: "Open logs".to_string(), hint: "Installer".to_string(), command: Command::OpenLogs },
+    ];
+
+    for installation in installations {
+        let name = &installation.name;
+        let page = |action: PageAction| {
+            Command::Page(PageCommand {
+                installation_id: installation.id.clone(),
+                action,
+            })
+        };
+        entries.push(Entry { label: format!("Open {}", name), hint: "Installation".to_string(), command: Command::Open(installation.id.clone()) });
+        if installation.installed {
+            entries.push(Entry { label: format!("Launch {}", name), hint: "Installation".to_string(), command: Command::Launch(installation.id.clone()) });
+            entries.push(Entry { label: format!("Update {}", name), hint: "Installation".to_string(), command: page(PageAction::Update) });
+        } else {
+            entries.push(Entry { label: format!("Install {}", name), hint: "Installation".to_string(), command: page(PageAction::Update) });
+        }
+        for (tab, title) in [("features", "features"), ("performance", "performance"), ("settings", "settings"), ("screenshots", "screenshots")] {
+            entries.push(Entry { label: format!("{} {}", name, title), hint: "Installation".to_string(), command: page(PageAction::Tab(tab)) });
+        }
+    }
+
+    // Components can only be toggled for the installation that's open
+    if let (Some(installation), Some(manifest)) = (current_installation, manifest) {
+        let components = manifest.get_all_optional_components()
+            .into_iter()
+            .filter(|c| !c.hidden || installation.show_experimental);
+        for component in components {
+            let state = if installation.enabled_features.contains(&component.id) { "on" } else { "off" };
+            entries.push(Entry {
+                label: format!("Toggle {}", component.name),
+                hint: format!("Currently {}", state),
+                command: Command::Page(PageCommand {
+                    installation_id: installation.id.clone(),
+                    action: PageAction::ToggleFeature(component.id.clone()),
+                }),
+            });
+        }
+    }
+    entries
+}
+
+#[component]
+pub fn CommandPalette(
+    installations: Signal<Vec<Installation>>,
+    current_installation_id: Signal<Option<String>>,
+    settings: Signal<bool>,
+    manifest: Option<UniversalManifest>,
+    error: Signal<Option<String>>,
+) -> Element {
+    let mut open = use_signal(|| false);
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+    let mut request = use_context::<PaletteRequest>();
+    let mut settings = settings.clone();
+    let mut current_installation_id = current_installation_id.clone();
+    let mut error = error.clone();
+
+    // The webview swallows shortcuts unless something listens on the document
+    use_future(move || async move {
+        let mut listener = eval(
+            r#"
+            document.addEventListener('keydown', (e) => {
+                if ((e.ctrlKey || e.metaKey) && e.key.toLowerCase() === 'k') {
+                    e.preventDefault();
+                    dioxus.send(true);
+                }
+            });
+            "#,
+        );
+        while listener.recv().await.is_ok() {
+            let is_open = open();
+            open.set(!is_open);
+            query.set(String::new());
+            selected.set(0);
+        }
+    });
+
+    if !open() {
+        return rsx! {};
+    }
+
+    // Toggle entries reflect what's saved, the open page keeps the installation file current
+    let current = current_installation_id
+        .read()
+        .as_ref()
+        .and_then(|id| crate::installation::load_installation(id).ok());
+    let entries = build_entries(&installations.read(), current.as_ref(), manifest.as_ref());
+    let mut matches: Vec<(i32, Entry)> = entries
+        .into_iter()
+        .filter_map(|entry| fuzzy_score(&query.read(), &entry.label).map(|score| (score, entry)))
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0));
+    let results: Vec<Entry> = matches.into_iter().take(MAX_RESULTS).map(|(_, entry)| entry).collect();
+    let highlighted = selected().min(results.len().saturating_sub(1));
+
+    let run = move |command: Command| {
+        debug!("Command palette: {:?}", command);
+        open.set(false);
+        match command {
+            Command::Home => {
+                settings.set(false);
+                current_installation_id.set(None);
+            }
+            Command::NewInstallation => {
+                settings.set(false);
+                current_installation_id.set(Some("new".to_string()));
+            }
+            Command::OpenSettings => settings.set(true),
+            Command::OpenLogs => {
+                let log_path = crate::get_installer_dir().join("installer.log");
+                if let Err(e) = open::that(&log_path) {
+                    error!("Failed to open {}: {}", log_path.display(), e);
+                    error.set(Some(format!("Failed to open the log file: {}", e)));
+                }
+            }
+            Command::Open(id) => {
+                settings.set(false);
+                current_installation_id.set(Some(id));
+            }
+            Command::Launch(id) => super::handle_play_click(id, &error),
+            Command::Page(page_command) => {
+                settings.set(false);
+                current_installation_id.set(Some(page_command.installation_id.clone()));
+                request.pending.set(Some(page_command));
+            }
+        }
+    };
+
+    let results_for_keys = results.clone();
+    let mut run_from_keys = run.clone();
+
+    rsx! {
+        div { class: "modal-overlay command-palette-overlay",
+            onclick: move |_| open.set(false),
+            div { class: "command-palette",
+                onclick: move |evt| evt.stop_propagation(),
+                input {
+                    class: "command-palette-input",
+                    r#type: "text",
+                    autofocus: true,
+                    placeholder: "Type a command or installation...",
+                    value: "{query}",
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        selected.set(0);
+                    },
+                    onkeydown: move |evt| {
+                        match evt.key() {
+                            Key::Escape => open.set(false),
+                            Key::ArrowDown => {
+                                if highlighted + 1 < results_for_keys.len() {
+                                    selected.set(highlighted + 1);
+                                }
+                            }
+                            Key::ArrowUp => selected.set(highlighted.saturating_sub(1)),
+                            Key::Enter => {
+                                if let Some(entry) = results_for_keys.get(highlighted) {
+                                    run_from_keys(entry.command.clone());
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                div { class: "command-palette-results",
+                    if results.is_empty() {
+                        div { class: "command-palette-empty", "No matching commands" }
+                    }
+                    for (i, entry) in results.into_iter().enumerate() {
+                        {
+                            let command = entry.command.clone();
+                            let mut run = run.clone();
+                            rsx! {
+                                div {
+                                    key: "{entry.label}",
+                                    class: if i == highlighted { "command-palette-item selected" } else { "command-palette-item" },
+                                    onmouseenter: move |_| selected.set(i),
+                                    onclick: move |_| run(command.clone()),
+                                    span { class: "command-palette-label", "{entry.label}" }
+                                    span { class: "command-palette-hint", "{entry.hint}" }
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "command-palette-footer", "↑↓ to move · Enter to run · Esc to close" }
+            }
+        }
+    }
+}
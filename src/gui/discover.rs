@@ -0,0 +1,242 @@
+use dioxus::prelude::*;
+use log::{debug, warn};
+use serde::Deserialize;
+
+use crate::{CachedHttpClient, GithubBranch, GH_RAW};
+
+// Browse view over the branches of the modpack repo. Every branch is a pack (or a channel of one)
+// and its manifest carries the listing metadata: category, short description, trending and new.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoverEntry {
+    pub branch: String,
+    pub name: String,
+    pub subtitle: String,
+    pub short_description: Option<String>,
+    pub category: Option<String>,
+    pub trend: bool,
+    pub is_new: bool,
+    pub modpack_version: String,
+    pub minecraft_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchLoader {
+    minecraft_version: String,
+}
+
+// Only what the listing needs, so both manifest.json and universal.json parse
+#[derive(Debug, Deserialize)]
+struct BranchMetadata {
+    name: String,
+    #[serde(default)]
+    subtitle: String,
+    #[serde(default)]
+    modpack_version: String,
+    #[serde(default)]
+    minecraft_version: Option<String>,
+    #[serde(default)]
+    loader: Option<BranchLoader>,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    short_description: Option<String>,
+    #[serde(default)]
+    trend: Option<bool>,
+    #[serde(default)]
+    is_new: Option<bool>,
+}
+
+async fn fetch_metadata(http_client: &CachedHttpClient, url: &str) -> Option<BranchMetadata> {
+    let mut resp = http_client.get_async(url.to_string()).await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let text = isahc::AsyncReadResponseExt::text(&mut resp).await.ok()?;
+    match serde_json::from_str(&text) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            warn!("Failed to parse {}: {}", url, e);
+            None
+        }
+    }
+}
+
+async fn fetch_entry(http_client: &CachedHttpClient, source: &str, branch: &str) -> Option<DiscoverEntry> {
+    // manifest.json has the listing flags, branches that only ship universal.json still show up
+    let mut metadata = None;
+    for file in ["manifest.json", "universal.json"] {
+        metadata = fetch_metadata(http_client, &format!("{}{}{}/{}", GH_RAW, source, branch, file)).await;
+        if metadata.is_some() {
+            break;
+        }
+    }
+    let Some(metadata) = metadata else {
+        debug!("Branch {} has no manifest, not listing it", branch);
+        return None;
+    };
+    Some(DiscoverEntry {
+        branch: branch.to_string(),
+        name: metadata.name,
+        subtitle: metadata.subtitle,
+        short_description: metadata.short_description.filter(|d| !d.trim().is_empty()),
+        category: metadata.category.filter(|c| !c.trim().is_empty()),
+        trend: metadata.trend.unwrap_or(false),
+        is_new: metadata.is_new.unwrap_or(false),
+        modpack_version: metadata.modpack_version,
+        minecraft_version: metadata.minecraft_version.or(metadata.loader.map(|l| l.minecraft_version)),
+    })
+}
+
+pub async fn load_entries(source: String, branches: Vec<GithubBranch>) -> Vec<DiscoverEntry> {
+    let http_client = CachedHttpClient::new();
+    let mut entries: Vec<DiscoverEntry> = futures::future::join_all(
+        branches.iter().map(|branch| fetch_entry(&http_client, &source, &branch.name)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect();
+    // Trending first, then new ones, then alphabetical
+    entries.sort_by(|a, b| {
+        b.trend
+            .cmp(&a.trend)
+            .then(b.is_new.cmp(&a.is_new))
+            .then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    entries
+}
+
+fn categories(entries: &[DiscoverEntry]) -> Vec<String> {
+    let mut categories: Vec<String> = entries.iter().filter_map(|e| e.category.clone()).collect();
+    categories.sort();
+    categories.dedup();
+    categories
+}
+
+#[component]
+pub fn DiscoverPage(
+    branches: Vec<GithubBranch>,
+    modpack_source: String,
+    // Branch the installer currently installs from, the only one that can be installed for now
+    default_branch: String,
+    on_install: EventHandler<()>,
+) -> Element {
+    let fetch_source = modpack_source.clone();
+    let entries = use_resource(move || {
+        let source = fetch_source.clone();
+        let branches = branches.clone();
+        async move { load_entries(source, branches).await }
+    });
+    let mut selected_category = use_signal(|| Option::<String>::None);
+
+    let Some(entries) = entries.read().clone() else {
+        return rsx! {
+            div { class: "loading-container",
+                div { class: "loading-spinner" }
+                div { class: "loading-text", "Looking for modpacks..." }
+            }
+        };
+    };
+
+    let all_categories = categories(&entries);
+    let visible: Vec<DiscoverEntry> = entries
+        .iter()
+        .filter(|e| selected_category().map_or(true, |c| e.category.as_ref() == Some(&c)))
+        .cloned()
+        .collect();
+
+    rsx! {
+        div { class: "home-container discover-container",
+            h1 { class: "discover-title", "Discover" }
+
+            if all_categories.len() > 1 {
+                div { class: "discover-categories",
+                    button {
+                        class: if selected_category().is_none() { "discover-category active" } else { "discover-category" },
+                        onclick: move |_| selected_category.set(None),
+                        "All"
+                    }
+                    for category in all_categories {
+                        {
+                            let is_active = selected_category().as_ref() == Some(&category);
+                            let value = category.clone();
+                            rsx! {
+                                button {
+                                    key: "{category}",
+                                    class: if is_active { "discover-category active" } else { "discover-category" },
+                                    onclick: move |_| selected_category.set(Some(value.clone())),
+                                    "{category}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if visible.is_empty() {
+                p { class: "discover-empty", "No modpacks found." }
+            }
+
+            div { class: "discover-grid",
+                for entry in visible {
+                    {
+                        let is_default = entry.branch == default_branch;
+                        let github_url = format!("https://github.com/{}tree/{}", modpack_source, entry.branch);
+                        rsx! {
+                            div {
+                                key: "{entry.branch}",
+                                class: "discover-card",
+                                div { class: "discover-card-badges",
+                                    if entry.trend {
+                                        span { class: "discover-badge trending", "🔥 Trending" }
+                                    }
+                                    if entry.is_new {
+                                        span { class: "discover-badge new", "✨ New" }
+                                    }
+                                    if let Some(category) = &entry.category {
+                                        span { class: "discover-badge category", "{category}" }
+                                    }
+                                }
+                                h3 { class: "discover-card-title", "{entry.name}" }
+                                if !entry.subtitle.is_empty() {
+                                    div { class: "discover-card-subtitle", "{entry.subtitle}" }
+                                }
+                                if let Some(description) = &entry.short_description {
+                                    p { class: "discover-card-description", "{description}" }
+                                }
+                                div { class: "discover-card-meta",
+                                    if !entry.modpack_version.is_empty() {
+                                        span { "v{entry.modpack_version}" }
+                                    }
+                                    if let Some(mc) = &entry.minecraft_version {
+                                        span { "Minecraft {mc}" }
+                                    }
+                                    span { class: "discover-card-branch", "{entry.branch}" }
+                                }
+                                div { class: "discover-card-actions",
+                                    if is_default {
+                                        button {
+                                            class: "save-button",
+                                            onclick: move |_| on_install.call(()),
+                                            "Create installation"
+                                        }
+                                    }
+                                    button {
+                                        class: "cancel-button",
+                                        onclick: move |_| {
+                                            if let Err(e) = super::open_url(&github_url) {
+                                                warn!("Failed to open {}: {}", github_url, e);
+                                            }
+                                        },
+                                        "View on GitHub"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
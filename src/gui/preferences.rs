@@ -0,0 +1,1237 @@
+use std::path::PathBuf;
+
+use dioxus::prelude::*;
+use log::{debug, info};
+
+use crate::Config;
+use super::{CustomMultiMCButton, LockState};
+
+const SECTIONS: &[(&str, &str)] = &[
+    ("launcher", "Launcher"),
+    ("general", "General"),
+    ("network", "Network"),
+    ("privacy", "Privacy"),
+    ("storage", "Storage"),
+    ("lock", "Lock"),
+    ("account", "Account"),
+    ("transfer", "Transfer"),
+    ("advanced", "Advanced"),
+];
+
+#[derive(PartialEq, Props, Clone)]
+pub struct PreferencesProps {
+    config: Signal<Config>,
+    settings: Signal<bool>,
+    config_path: PathBuf,
+    error: Signal<Option<String>>,
+    b64_id: String,
+}
+
+// Writes the config to disk, returning a user facing error on failure
+pub fn save_config(config: &Config, config_path: &PathBuf) -> Result<(), String> {
+    let bytes = serde_json::to_vec(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(config_path, bytes)
+        .map_err(|e| format!("{:#?} (Failed to write config!)", e))
+}
+
+#[component]
+pub fn Preferences(mut props: PreferencesProps) -> Element {
+    let mut active_section = use_signal(|| "launcher");
+    // Edits go into a draft and only hit disk when the user saves
+    let mut draft = use_signal(|| props.config.read().clone());
+
+    // The custom MultiMC button and the lock section write straight to the config, keep the draft in sync
+    let config = props.config;
+    use_effect(move || {
+        let launcher = config.read().launcher.clone();
+        let lock = config.read().lock.clone();
+        draft.with_mut(|d| {
+            d.launcher = launcher;
+            d.lock = lock;
+        });
+    });
+
+    let lock_state = use_context::<LockState>();
+    if *lock_state.locked.read() {
+        return rsx! {
+            div { class: "settings-container preferences-container",
+                h1 { class: "settings-title", "Settings" }
+                p { class: "locked-banner",
+                    "🔒 Settings are locked. Unlock from the home screen to change them."
+                }
+                div { class: "settings-buttons",
+                    button {
+                        class: "secondary-button",
+                        r#type: "button",
+                        onclick: move |_| props.settings.set(false),
+                        "Back"
+                    }
+                }
+            }
+        };
+    }
+
+    let on_save = move |_| {
+        let new_config = draft.read().clone();
+        debug!("Saving preferences: {:#?}", new_config);
+        if let Err(e) = save_config(&new_config, &props.config_path) {
+            props.error.set(Some(e));
+            return;
+        }
+        log::set_max_level(new_config.advanced.level_filter());
+        crate::apply_config(&new_config);
+        // A lower limit or another cache folder applies right away, not only at the next start
+        if new_config.storage != props.config.peek().storage {
+            crate::artifact_cache::prune_user_cache_in_background(&new_config.storage);
+        }
+        info!("Preferences saved");
+        props.config.set(new_config);
+        props.settings.set(false);
+    };
+
+    let section_content = match active_section() {
+        "general" => rsx! { GeneralSection { draft } },
+        "network" => rsx! { NetworkSection { draft } },
+        "privacy" => rsx! { PrivacySection { draft } },
+        "storage" => rsx! { StorageSection { draft } },
+        "lock" => rsx! {
+            LockSection {
+                config: props.config,
+                config_path: props.config_path.clone(),
+            }
+        },
+        "account" => rsx! { AccountSection { draft } },
+        "transfer" => rsx! {
+            TransferSection {
+                draft,
+                config: props.config,
+                config_path: props.config_path.clone(),
+            }
+        },
+        "advanced" => rsx! { AdvancedSection { draft } },
+        _ => rsx! {
+            LauncherSection {
+                draft,
+                config: props.config,
+                config_path: props.config_path.clone(),
+                error: props.error,
+                b64_id: props.b64_id.clone()
+            }
+        },
+    };
+
+    rsx! {
+        div { class: "settings-container preferences-container",
+            h1 { class: "settings-title", "Settings" }
+
+            div { class: "preferences-layout",
+                nav { class: "preferences-nav",
+                    for (id, label) in SECTIONS.iter() {
+                        button {
+                            class: if active_section() == *id { "preferences-nav-item active" } else { "preferences-nav-item" },
+                            r#type: "button",
+                            onclick: move |_| active_section.set(*id),
+                            "{label}"
+                        }
+                    }
+                }
+
+                div { class: "preferences-section settings-form",
+                    {section_content}
+                }
+            }
+
+            div { class: "settings-buttons",
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| props.settings.set(false),
+                    "Cancel"
+                }
+                button {
+                    class: "primary-button",
+                    r#type: "button",
+                    onclick: on_save,
+                    "Save Changes"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn LauncherSection(
+    draft: Signal<Config>,
+    config: Signal<Config>,
+    config_path: PathBuf,
+    error: Signal<Option<String>>,
+    b64_id: String,
+) -> Element {
+    let current = draft.read().launcher.clone();
+    let is_custom = current.starts_with("custom");
+
+    rsx! {
+        div { class: "setting-group",
+            label { class: "setting-label", "Minecraft Launcher:" }
+            select {
+                class: "setting-select",
+                value: "{current}",
+                onchange: move |evt| draft.with_mut(|d| d.launcher = evt.value()),
+                if crate::get_minecraft_folder().is_dir() {
+                    option { value: "vanilla", selected: current == "vanilla", "Vanilla Launcher" }
+                }
+                if crate::get_multimc_folder("MultiMC").is_ok() {
+                    option { value: "multimc-MultiMC", selected: current == "multimc-MultiMC", "MultiMC" }
+                }
+                if crate::get_multimc_folder("PrismLauncher").is_ok() {
+                    option { value: "multimc-PrismLauncher", selected: current == "multimc-PrismLauncher", "Prism Launcher" }
+                }
+                if is_custom {
+                    option { value: "{current}", selected: true, "Custom MultiMC" }
+                }
+            }
+        }
+
+        CustomMultiMCButton {
+            config,
+            config_path,
+            error,
+            b64_id
+        }
+    }
+}
+
+#[component]
+fn GeneralSection(draft: Signal<Config>) -> Element {
+    let general = draft.read().general.clone();
+
+    rsx! {
+        div { class: "setting-group",
+            label { class: "setting-label", {crate::i18n::t("settings.language")} }
+            select {
+                class: "setting-select",
+                value: "{general.language}",
+                onchange: move |evt| draft.with_mut(|d| d.general.language = evt.value()),
+                for (code, name) in crate::i18n::LANGUAGES.iter() {
+                    option { value: *code, selected: general.language == *code, "{name}" }
+                }
+            }
+        }
+        p { class: "setting-description", {crate::i18n::t("settings.language_note")} }
+        div { class: "setting-group",
+            label { class: "setting-label", "Theme:" }
+            select {
+                class: "setting-select",
+                value: "{general.theme}",
+                onchange: move |evt| draft.with_mut(|d| d.general.theme = evt.value()),
+                for (value, name) in crate::theme::MODES.iter() {
+                    option { value: *value, selected: general.theme == *value, "{name}" }
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Status colors:" }
+            select {
+                class: "setting-select",
+                value: "{general.color_palette}",
+                onchange: move |evt| draft.with_mut(|d| d.general.color_palette = evt.value()),
+                option { value: "default", selected: general.color_palette == "default", "Default" }
+                option { value: "colorblind", selected: general.color_palette == "colorblind", "Color-blind friendly" }
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: general.check_updates_on_startup,
+                onchange: move |evt| draft.with_mut(|d| d.general.check_updates_on_startup = evt.checked()),
+            }
+            "Check installations for updates on startup"
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: general.launch_after_install,
+                onchange: move |evt| draft.with_mut(|d| d.general.launch_after_install = evt.checked()),
+            }
+            "Start the game after an install or update (MultiMC and Prism Launcher)"
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: general.tray_mode,
+                onchange: move |evt| draft.with_mut(|d| d.general.tray_mode = evt.checked()),
+            }
+            "Keep running in the tray when the window is closed"
+        }
+        p { class: "setting-description",
+            "Checks installations for updates in the background and shows a notification when there's one. Takes effect after a restart."
+        }
+        if general.tray_mode {
+            div { class: "setting-group",
+                label { class: "setting-label", "Check for updates every (hours):" }
+                input {
+                    class: "setting-input",
+                    r#type: "number",
+                    min: "1",
+                    value: "{general.tray_check_hours}",
+                    oninput: move |evt| {
+                        if let Ok(hours) = evt.value().trim().parse::<u64>() {
+                            draft.with_mut(|d| d.general.tray_check_hours = hours.max(1));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NetworkSection(draft: Signal<Config>) -> Element {
+    let network = draft.read().network.clone();
+    let proxy = network.proxy.clone().unwrap_or_default();
+    let proxy_username = network.proxy_username.clone().unwrap_or_default();
+    let proxy_password = network.proxy_password.as_ref().map(|p| p.0.clone()).unwrap_or_default();
+    let system_proxy = use_hook(crate::proxy::detect_system_proxy);
+    let system_proxy_text = system_proxy.clone().unwrap_or_else(|| "none found".to_string());
+    let ca_bundle = network.ca_bundle.clone().unwrap_or_default();
+    let ca_bundle_status = (!ca_bundle.is_empty()).then(|| match crate::proxy::check_ca_bundle(std::path::Path::new(&ca_bundle)) {
+        Ok(count) => format!("{} certificate(s)", count),
+        Err(e) => e,
+    });
+    let bandwidth = network.bandwidth_limit_kbps.map(|v| v.to_string()).unwrap_or_default();
+    let curseforge_key = network.curseforge_api_key.as_ref().map(|k| k.0.clone()).unwrap_or_default();
+    let key_env = crate::curseforge::API_KEY_ENV;
+    let key_from_env = std::env::var(key_env).is_ok();
+    let github_token = network.github_token.as_ref().map(|t| t.0.clone()).unwrap_or_default();
+    let token_env = crate::github::TOKEN_ENV;
+    let token_from_env = std::env::var(token_env).is_ok();
+
+    rsx! {
+        div { class: "setting-group",
+            label { class: "setting-label", "Proxy:" }
+            input {
+                class: "setting-input",
+                r#type: "text",
+                placeholder: "http://host:port (leave empty for none)",
+                value: "{proxy}",
+                oninput: move |evt| {
+                    let value = evt.value().trim().to_string();
+                    draft.with_mut(|d| d.network.proxy = if value.is_empty() { None } else { Some(value) });
+                }
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: network.use_system_proxy,
+                onchange: move |evt| draft.with_mut(|d| d.network.use_system_proxy = evt.checked()),
+            }
+            "Use the system proxy when none is set above (detected: {system_proxy_text})"
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Proxy login:" }
+            div { class: "setting-row",
+                input {
+                    class: "setting-input",
+                    r#type: "text",
+                    placeholder: "Username (if the proxy asks for one)",
+                    value: "{proxy_username}",
+                    oninput: move |evt| {
+                        let value = evt.value().trim().to_string();
+                        draft.with_mut(|d| d.network.proxy_username = if value.is_empty() { None } else { Some(value) });
+                    }
+                }
+                input {
+                    class: "setting-input",
+                    r#type: "password",
+                    placeholder: "Password",
+                    value: "{proxy_password}",
+                    oninput: move |evt| {
+                        let value = evt.value();
+                        draft.with_mut(|d| d.network.proxy_password = if value.is_empty() { None } else { Some(crate::secret::Secret(value)) });
+                    }
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Extra CA certificates:" }
+            div { class: "setting-row",
+                input {
+                    class: "setting-input",
+                    r#type: "text",
+                    readonly: true,
+                    placeholder: "None, only the system certificates are trusted",
+                    value: "{ca_bundle}",
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| {
+                        let picked = rfd::FileDialog::new()
+                            .set_title("CA certificates")
+                            .add_filter("PEM certificates", &["pem", "crt", "cer"])
+                            .pick_file();
+                        if let Some(path) = picked {
+                            draft.with_mut(|d| d.network.ca_bundle = Some(path.to_string_lossy().to_string()));
+                        }
+                    },
+                    "Browse"
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| draft.with_mut(|d| d.network.ca_bundle = None),
+                    "Clear"
+                }
+            }
+            if let Some(status) = ca_bundle_status {
+                p { class: "setting-description", "{status}" }
+            }
+            p { class: "setting-description",
+                "For networks whose firewall inspects HTTPS traffic. Ask your IT department for the certificate in PEM format."
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Bandwidth limit (KiB/s):" }
+            input {
+                class: "setting-input",
+                r#type: "number",
+                min: "0",
+                placeholder: "Unlimited",
+                value: "{bandwidth}",
+                oninput: move |evt| {
+                    let limit = evt.value().trim().parse::<u64>().ok().filter(|v| *v > 0);
+                    draft.with_mut(|d| d.network.bandwidth_limit_kbps = limit);
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Concurrent downloads: {network.max_concurrent_downloads}" }
+            input {
+                class: "setting-range",
+                r#type: "range",
+                min: "1",
+                max: "32",
+                value: "{network.max_concurrent_downloads}",
+                oninput: move |evt| {
+                    if let Ok(value) = evt.value().parse::<usize>() {
+                        draft.with_mut(|d| d.network.max_concurrent_downloads = value.clamp(1, crate::download_queue::MAX_PARALLEL));
+                    }
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "CurseForge API key:" }
+            input {
+                class: "setting-input",
+                r#type: "password",
+                placeholder: "Only needed for modpacks with CurseForge downloads",
+                value: "{curseforge_key}",
+                oninput: move |evt| {
+                    let value = evt.value().trim().to_string();
+                    draft.with_mut(|d| {
+                        d.network.curseforge_api_key = if value.is_empty() { None } else { Some(crate::secret::Secret(value)) }
+                    });
+                }
+            }
+            if key_from_env {
+                p { class: "setting-description", "{key_env} is set and takes precedence." }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "GitHub token:" }
+            input {
+                class: "setting-input",
+                r#type: "password",
+                placeholder: "Optional, raises the GitHub API rate limit",
+                value: "{github_token}",
+                oninput: move |evt| {
+                    let value = evt.value().trim().to_string();
+                    draft.with_mut(|d| {
+                        d.network.github_token = if value.is_empty() { None } else { Some(crate::secret::Secret(value)) }
+                    });
+                }
+            }
+            p { class: "setting-description",
+                "Without one GitHub allows 60 requests an hour, which big remote includes can run out of. A token without any scopes is enough."
+            }
+            if token_from_env {
+                p { class: "setting-description", "{token_env} is set and takes precedence." }
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: network.work_offline,
+                onchange: move |evt| draft.with_mut(|d| d.network.work_offline = evt.checked()),
+            }
+            "Work offline"
+        }
+        p { class: "setting-description",
+            "Installs only from the download cache, nothing is downloaded. Installing tells you which components are missing from the cache. Modpack information is what was last seen online."
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: network.pause_on_metered,
+                onchange: move |evt| draft.with_mut(|d| d.network.pause_on_metered = evt.checked()),
+            }
+            "Pause downloads on metered connections"
+        }
+        p { class: "setting-description",
+            "Downloads wait while Windows reports a metered connection, like a phone hotspot, and continue where they stopped once it's unmetered."
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Off-peak hours:" }
+            div { class: "off-peak-hours",
+                input {
+                    class: "setting-input",
+                    r#type: "number",
+                    min: "0",
+                    max: "23",
+                    value: "{network.off_peak_start_hour}",
+                    oninput: move |evt| {
+                        if let Ok(hour) = evt.value().trim().parse::<u8>() {
+                            draft.with_mut(|d| d.network.off_peak_start_hour = hour.min(23));
+                        }
+                    }
+                }
+                span { ":00 to" }
+                input {
+                    class: "setting-input",
+                    r#type: "number",
+                    min: "0",
+                    max: "23",
+                    value: "{network.off_peak_end_hour}",
+                    oninput: move |evt| {
+                        if let Ok(hour) = evt.value().trim().parse::<u8>() {
+                            draft.with_mut(|d| d.network.off_peak_end_hour = hour.min(23));
+                        }
+                    }
+                }
+                span { ":00" }
+            }
+            p { class: "setting-description", "\"Update tonight\" starts the update at the beginning of this window." }
+        }
+    }
+}
+
+#[component]
+fn PrivacySection(draft: Signal<Config>) -> Element {
+    let privacy = draft.read().privacy.clone();
+    let mode = match privacy.telemetry_mode() {
+        crate::telemetry::Mode::Send => "send",
+        crate::telemetry::Mode::LocalOnly => "local",
+        crate::telemetry::Mode::Off => "off",
+    };
+    let mut local_statistics = use_signal(crate::telemetry::load_local);
+    let mut clear_error = use_signal(|| Option::<String>::None);
+
+    rsx! {
+        div { class: "setting-group",
+            label { class: "setting-label", "Usage statistics:" }
+            select {
+                class: "setting-select",
+                value: "{mode}",
+                onchange: move |evt| draft.with_mut(|d| {
+                    let value = evt.value();
+                    d.privacy.telemetry_enabled = value != "off";
+                    d.privacy.local_only = value == "local";
+                }),
+                option { value: "send", selected: mode == "send", "Send anonymous statistics" }
+                option { value: "local", selected: mode == "local", "Keep them on this computer only" }
+                option { value: "off", selected: mode == "off", "Off" }
+            }
+        }
+        p { class: "setting-description",
+            "Statistics only include installer events like installs, updates and uninstalls. They never include personal information. When they're kept on this computer nothing is sent, the counts are listed below."
+        }
+        if !local_statistics.read().counts.is_empty() {
+            div { class: "setting-group",
+                label { class: "setting-label", "Statistics on this computer:" }
+                ul { class: "local-statistics",
+                    for (action, count) in local_statistics.read().counts.clone() {
+                        li { key: "{action}", "{action}: {count}" }
+                    }
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| match crate::telemetry::clear_local() {
+                        Ok(_) => {
+                            clear_error.set(None);
+                            local_statistics.set(crate::telemetry::LocalStatistics::default());
+                        }
+                        Err(e) => clear_error.set(Some(e)),
+                    },
+                    "Clear"
+                }
+                if let Some(error) = &*clear_error.read() {
+                    p { class: "setting-description", "{error}" }
+                }
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                disabled: !privacy.telemetry_enabled,
+                checked: privacy.share_feature_usage,
+                onchange: move |evt| draft.with_mut(|d| d.privacy.share_feature_usage = evt.checked()),
+            }
+            "Share which optional features I use"
+        }
+        p { class: "setting-description",
+            "Helps the pack team decide which components to turn on by default or retire. Only the ids of enabled optional features are sent, never your installation id."
+        }
+    }
+}
+
+#[component]
+fn StorageSection(draft: Signal<Config>) -> Element {
+    let storage = draft.read().storage.clone();
+    let cache_dir = storage.cache_dir.clone().unwrap_or_default();
+
+    let shared_cache_dir = storage.shared_cache_dir.clone().unwrap_or_default();
+    let mut shared_cache_status = use_signal(|| Option::<String>::None);
+    let mut dedupe_running = use_signal(|| false);
+    let mut dedupe_status = use_signal(|| Option::<String>::None);
+
+    let deduplicate_now = move |_| {
+        dedupe_running.set(true);
+        dedupe_status.set(None);
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(crate::content_store::deduplicate_all)
+                .await
+                .unwrap_or_else(|e| Err(format!("Deduplication task failed: {}", e)));
+            dedupe_status.set(Some(match result {
+                Ok(report) => report.summary(),
+                Err(e) => e,
+            }));
+            dedupe_running.set(false);
+        });
+    };
+
+    let pick_shared_cache_dir = move |_| {
+        let dialog = rfd::FileDialog::new()
+            .set_title("Pick a folder all users of this PC can write to");
+        if let Some(path) = dialog.pick_folder() {
+            // Other users being able to write here can only be checked from their account
+            let status = match crate::artifact_cache::check_writable(&path) {
+                Ok(_) => "Folder is writable. Make sure the other accounts can write to it too.".to_string(),
+                Err(e) => format!("{}. The cache will only be read from this account.", e),
+            };
+            shared_cache_status.set(Some(status));
+            draft.with_mut(|d| d.storage.shared_cache_dir = Some(path.to_string_lossy().to_string()));
+        }
+    };
+
+    let pick_cache_dir = move |_| {
+        let dialog = rfd::FileDialog::new()
+            .set_title("Pick a directory for the download cache")
+            .set_directory(crate::get_installer_dir());
+        if let Some(path) = dialog.pick_folder() {
+            draft.with_mut(|d| d.storage.cache_dir = Some(path.to_string_lossy().to_string()));
+        }
+    };
+
+    rsx! {
+        if let Some(root) = crate::portable::root() {
+            p { class: "setting-description",
+                "Portable mode: settings, logs and installations are stored in {root.display()}"
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Cache location:" }
+            div { class: "setting-row",
+                input {
+                    class: "setting-input",
+                    r#type: "text",
+                    readonly: true,
+                    placeholder: "Default",
+                    value: "{cache_dir}",
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: pick_cache_dir,
+                    "Browse"
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| draft.with_mut(|d| d.storage.cache_dir = None),
+                    "Reset"
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Cache size limit (MB):" }
+            input {
+                class: "setting-input",
+                r#type: "number",
+                min: "0",
+                value: "{storage.cache_limit_mb}",
+                oninput: move |evt| {
+                    if let Ok(value) = evt.value().trim().parse::<u64>() {
+                        draft.with_mut(|d| d.storage.cache_limit_mb = value);
+                    }
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Shared download cache:" }
+            div { class: "setting-row",
+                input {
+                    class: "setting-input",
+                    r#type: "text",
+                    readonly: true,
+                    placeholder: "Off, downloads are cached per user",
+                    value: "{shared_cache_dir}",
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: pick_shared_cache_dir,
+                    "Browse"
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| {
+                        shared_cache_status.set(None);
+                        draft.with_mut(|d| d.storage.shared_cache_dir = None);
+                    },
+                    "Turn off"
+                }
+            }
+            if let Some(status) = shared_cache_status() {
+                p { class: "setting-description", "{status}" }
+            }
+            p { class: "setting-description",
+                "On a PC with several accounts, point every user to the same folder so mods are only downloaded once. Settings and installations stay separate for each user."
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-checkbox",
+                input {
+                    r#type: "checkbox",
+                    checked: storage.deduplicate,
+                    onchange: move |evt| draft.with_mut(|d| d.storage.deduplicate = evt.checked()),
+                }
+                "Share identical files between installations"
+            }
+            p { class: "setting-description",
+                "Mods, shaders and resource packs that several installations use are stored once and linked into each of them."
+            }
+            div { class: "setting-row",
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    disabled: dedupe_running() || !storage.deduplicate,
+                    onclick: deduplicate_now,
+                    if dedupe_running() { "Deduplicating..." } else { "Deduplicate now" }
+                }
+            }
+            if let Some(status) = dedupe_status() {
+                p { class: "setting-description", "{status}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn AdvancedSection(draft: Signal<Config>) -> Element {
+    let advanced = draft.read().advanced.clone();
+
+    rsx! {
+        div { class: "setting-group",
+            label { class: "setting-label", "Log level:" }
+            select {
+                class: "setting-select",
+                value: "{advanced.log_level}",
+                onchange: move |evt| draft.with_mut(|d| d.advanced.log_level = evt.value()),
+                for level in ["error", "warn", "info", "debug", "trace"] {
+                    option { value: level, selected: advanced.log_level == level, "{level}" }
+                }
+            }
+        }
+        p { class: "setting-description",
+            "A quieter level applies right away, a more detailed one than the installer was started with after a restart."
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: advanced.developer_mode,
+                onchange: move |evt| draft.with_mut(|d| d.advanced.developer_mode = evt.checked()),
+            }
+            "Developer mode"
+        }
+        p { class: "setting-description",
+            "Enables extra diagnostics intended for modpack developers and testers."
+        }
+
+        if advanced.developer_mode {
+            WatchModeSettings { draft }
+            DevManifestSection { draft }
+        }
+    }
+}
+
+#[component]
+fn DevManifestSection(draft: Signal<Config>) -> Element {
+    let settings = draft.read().dev_manifest.clone();
+    let local_dir = settings.local_dir.clone().unwrap_or_default();
+    let branch = settings.branch.clone().unwrap_or_default();
+
+    let pick_local_dir = move |_| {
+        if let Some(dir) = rfd::FileDialog::new().set_title("Pack checkout").pick_folder() {
+            draft.with_mut(|d| d.dev_manifest.local_dir = Some(dir.to_string_lossy().to_string()));
+        }
+    };
+
+    rsx! {
+        h3 { class: "setting-label", "Manifest override" }
+        p { class: "setting-description",
+            "Load universal.json, presets.json and changelog.json from a local folder or another branch instead of the published ones. Applies to every installation until it's cleared."
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Local folder:" }
+            div { class: "setting-row",
+                input {
+                    class: "setting-input",
+                    r#type: "text",
+                    placeholder: "Off",
+                    value: "{local_dir}",
+                    oninput: move |evt| {
+                        let value = evt.value().trim().to_string();
+                        draft.with_mut(|d| d.dev_manifest.local_dir = if value.is_empty() { None } else { Some(value) });
+                    },
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: pick_local_dir,
+                    "Browse"
+                }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: move |_| draft.with_mut(|d| d.dev_manifest.local_dir = None),
+                    "Clear"
+                }
+            }
+            p { class: "setting-description",
+                "Files missing from the folder are downloaded as usual. Saving a file reloads the open installation."
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Branch:" }
+            input {
+                class: "setting-input",
+                r#type: "text",
+                placeholder: "Published branch",
+                value: "{branch}",
+                oninput: move |evt| {
+                    let value = evt.value().trim().to_string();
+                    draft.with_mut(|d| d.dev_manifest.branch = if value.is_empty() { None } else { Some(value) });
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn WatchModeSettings(draft: Signal<Config>) -> Element {
+    let watch = draft.read().watch.clone();
+    let installations = use_hook(|| crate::installation::load_all_installations().unwrap_or_default());
+    let selected = watch.installation_id.clone().unwrap_or_default();
+
+    rsx! {
+        h3 { class: "setting-label", "Watch mode" }
+        p { class: "setting-description",
+            "Automatically updates a test installation whenever the manifest on the chosen branch changes."
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: watch.enabled,
+                onchange: move |evt| draft.with_mut(|d| d.watch.enabled = evt.checked()),
+            }
+            "Enable watch mode"
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Branch:" }
+            input {
+                class: "setting-input",
+                r#type: "text",
+                value: "{watch.branch}",
+                oninput: move |evt| draft.with_mut(|d| d.watch.branch = evt.value().trim().to_string()),
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Test installation:" }
+            select {
+                class: "setting-select",
+                value: "{selected}",
+                onchange: move |evt| {
+                    let value = evt.value();
+                    draft.with_mut(|d| d.watch.installation_id = if value.is_empty() { None } else { Some(value) });
+                },
+                option { value: "", selected: selected.is_empty(), "None" }
+                for installation in installations.iter() {
+                    option {
+                        value: "{installation.id}",
+                        selected: installation.id == selected,
+                        "{installation.name}"
+                    }
+                }
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Poll interval (seconds):" }
+            input {
+                class: "setting-input",
+                r#type: "number",
+                min: "{crate::watch_mode::MIN_INTERVAL_SECS}",
+                value: "{watch.interval_secs}",
+                oninput: move |evt| {
+                    if let Ok(value) = evt.value().trim().parse::<u64>() {
+                        draft.with_mut(|d| d.watch.interval_secs = value.max(crate::watch_mode::MIN_INTERVAL_SECS));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Lock changes are saved immediately, a PIN stored in the keychain can't wait for "Save Changes"
+#[component]
+fn LockSection(config: Signal<Config>, config_path: PathBuf) -> Element {
+    let lock = config.read().lock.clone();
+    let mut pin = use_signal(String::new);
+    let mut confirm_pin = use_signal(String::new);
+    let mut use_keychain = use_signal(|| lock.use_keychain);
+    let mut message = use_signal(|| Option::<String>::None);
+
+    let config_path_for_set = config_path.clone();
+    let set_lock_pin = move |_| {
+        if *pin.read() != *confirm_pin.read() {
+            message.set(Some("The PINs don't match".to_string()));
+            return;
+        }
+        let mut new_config = config.read().clone();
+        // Switching storage, drop the old PIN first so nothing stale is left behind
+        if new_config.lock.use_keychain != use_keychain() {
+            crate::lock::clear_pin(&mut new_config.lock);
+            new_config.lock.use_keychain = use_keychain();
+        }
+        if let Err(e) = crate::lock::set_pin(&mut new_config.lock, &pin.read()) {
+            message.set(Some(e));
+            return;
+        }
+        match save_config(&new_config, &config_path_for_set) {
+            Ok(_) => {
+                info!("Lock enabled");
+                config.set(new_config);
+                pin.set(String::new());
+                confirm_pin.set(String::new());
+                message.set(Some("Lock enabled. Use the lock button in the header to lock installations.".to_string()));
+            }
+            Err(e) => message.set(Some(e)),
+        }
+    };
+
+    let disable_lock = move |_| {
+        let mut new_config = config.read().clone();
+        crate::lock::clear_pin(&mut new_config.lock);
+        match save_config(&new_config, &config_path) {
+            Ok(_) => {
+                info!("Lock disabled");
+                config.set(new_config);
+                message.set(Some("Lock disabled.".to_string()));
+            }
+            Err(e) => message.set(Some(e)),
+        }
+    };
+
+    rsx! {
+        p { class: "setting-description",
+            "When locked, installations can still be launched but features, updates and deletion are blocked until the PIN is entered."
+        }
+        div { class: "setting-group",
+            label { class: "setting-label",
+                if lock.enabled { "New PIN:" } else { "PIN:" }
+            }
+            input {
+                class: "setting-input",
+                r#type: "password",
+                inputmode: "numeric",
+                value: "{pin}",
+                oninput: move |evt| pin.set(evt.value()),
+            }
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Confirm PIN:" }
+            input {
+                class: "setting-input",
+                r#type: "password",
+                inputmode: "numeric",
+                value: "{confirm_pin}",
+                oninput: move |evt| confirm_pin.set(evt.value()),
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: use_keychain(),
+                onchange: move |evt| use_keychain.set(evt.checked()),
+            }
+            "Store the PIN in the system keychain"
+        }
+        if let Some(message) = message() {
+            p { class: "setting-description", "{message}" }
+        }
+        div { class: "setting-row",
+            button {
+                class: "primary-button",
+                r#type: "button",
+                onclick: set_lock_pin,
+                if lock.enabled { "Change PIN" } else { "Enable Lock" }
+            }
+            if lock.enabled {
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    onclick: disable_lock,
+                    "Disable Lock"
+                }
+            }
+        }
+    }
+}
+
+// Sign in for the direct launch. The account lives in the keychain right away, only the client id
+// and the checkbox wait for "Save Changes"
+#[component]
+fn AccountSection(draft: Signal<Config>) -> Element {
+    let advanced = draft.read().advanced.clone();
+    let client_id = advanced.microsoft_client_id.clone().unwrap_or_default();
+    let client_id_env = crate::ms_auth::CLIENT_ID_ENV;
+    let client_id_from_env = std::env::var(client_id_env).is_ok();
+    let mut username = use_signal(|| crate::ms_auth::stored_account().map(|account| account.username));
+    let mut device_code = use_signal(|| Option::<crate::ms_auth::DeviceCode>::None);
+    let mut is_busy = use_signal(|| false);
+    let mut message = use_signal(|| Option::<String>::None);
+
+    let sign_in = move |_| {
+        // The id may not be saved yet, the sign in should use what's in the field
+        crate::ms_auth::configure(draft.read().advanced.microsoft_client_id.as_ref());
+        is_busy.set(true);
+        message.set(None);
+        spawn(async move {
+            let started = tokio::task::spawn_blocking(crate::ms_auth::start_device_flow)
+                .await
+                .unwrap_or_else(|e| Err(format!("Sign in task failed: {}", e)));
+            let code = match started {
+                Ok(code) => code,
+                Err(e) => {
+                    message.set(Some(e));
+                    is_busy.set(false);
+                    return;
+                }
+            };
+            if let Err(e) = open::that(&code.verification_uri) {
+                debug!("Failed to open {}: {}", code.verification_uri, e);
+            }
+            device_code.set(Some(code.clone()));
+            let finished = tokio::task::spawn_blocking(move || crate::ms_auth::finish_device_flow(&code))
+                .await
+                .unwrap_or_else(|e| Err(format!("Sign in task failed: {}", e)));
+            match finished {
+                Ok(account) => {
+                    message.set(Some(format!("Signed in as {}", account.username)));
+                    username.set(Some(account.username));
+                }
+                Err(e) => message.set(Some(e)),
+            }
+            device_code.set(None);
+            is_busy.set(false);
+        });
+    };
+
+    let sign_out = move |_| match crate::ms_auth::sign_out() {
+        Ok(_) => {
+            username.set(None);
+            message.set(Some("Signed out.".to_string()));
+        }
+        Err(e) => message.set(Some(e)),
+    };
+
+    rsx! {
+        p { class: "setting-description",
+            "Signed in, vanilla installations can start the game directly instead of opening the Minecraft Launcher. The login is stored in the system keychain."
+        }
+        div { class: "setting-group",
+            label { class: "setting-label", "Microsoft client id:" }
+            input {
+                class: "setting-input",
+                r#type: "text",
+                placeholder: "Azure application id allowed to sign in to Minecraft",
+                value: "{client_id}",
+                oninput: move |evt| {
+                    let value = evt.value().trim().to_string();
+                    draft.with_mut(|d| d.advanced.microsoft_client_id = if value.is_empty() { None } else { Some(value) });
+                }
+            }
+            if client_id_from_env {
+                p { class: "setting-description", "{client_id_env} is set and takes precedence." }
+            }
+        }
+        if let Some(code) = device_code() {
+            div { class: "account-device-code",
+                p { "Open {code.verification_uri} and enter this code:" }
+                p { class: "account-user-code", "{code.user_code}" }
+            }
+        }
+        if let Some(message) = message() {
+            p { class: "setting-description", "{message}" }
+        }
+        div { class: "setting-row",
+            if let Some(name) = username() {
+                span { class: "account-name", "Signed in as {name}" }
+                button {
+                    class: "secondary-button",
+                    r#type: "button",
+                    disabled: is_busy(),
+                    onclick: sign_out,
+                    "Sign Out"
+                }
+            } else {
+                button {
+                    class: "primary-button",
+                    r#type: "button",
+                    disabled: is_busy() || (client_id.is_empty() && !client_id_from_env),
+                    onclick: sign_in,
+                    if is_busy() { "Waiting for sign in..." } else { "Sign In with Microsoft" }
+                }
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: advanced.direct_launch,
+                onchange: move |evt| draft.with_mut(|d| d.advanced.direct_launch = evt.checked()),
+            }
+            "Launch vanilla installations directly"
+        }
+        p { class: "setting-description",
+            "Downloads missing game files and starts Java itself. Without a signed in account the Minecraft Launcher is used as before."
+        }
+    }
+}
+
+#[component]
+fn TransferSection(draft: Signal<Config>, config: Signal<Config>, config_path: PathBuf) -> Element {
+    let mut include_backups = use_signal(|| false);
+    let mut import_settings = use_signal(|| true);
+    let mut message = use_signal(|| Option::<String>::None);
+
+    let export_data = move |_| {
+        let dialog = rfd::FileDialog::new()
+            .set_title("Export installer data")
+            .add_filter("Zip archive", &["zip"])
+            .set_file_name(crate::transfer::default_export_name().to_string_lossy());
+        let Some(path) = dialog.save_file() else {
+            return;
+        };
+        match crate::transfer::export_app_state(&config.read(), &path, include_backups()) {
+            Ok(manifest) => message.set(Some(format!(
+                "Exported settings and {} installation(s) to {}",
+                manifest.installations.len(),
+                path.display()
+            ))),
+            Err(e) => message.set(Some(format!("Export failed: {}", e))),
+        }
+    };
+
+    let import_data = move |_| {
+        let dialog = rfd::FileDialog::new()
+            .set_title("Import installer data")
+            .add_filter("Zip archive", &["zip"]);
+        let Some(path) = dialog.pick_file() else {
+            return;
+        };
+        let current = config.read().clone();
+        let (summary, new_config) = match crate::transfer::import_app_state(&path, &current, import_settings()) {
+            Ok(result) => result,
+            Err(e) => {
+                message.set(Some(format!("Import failed: {}", e)));
+                return;
+            }
+        };
+        if let Some(new_config) = new_config {
+            if let Err(e) = save_config(&new_config, &config_path) {
+                message.set(Some(format!("Installations were imported but saving the settings failed: {}", e)));
+                return;
+            }
+            log::set_max_level(new_config.advanced.level_filter());
+            crate::apply_config(&new_config);
+            crate::artifact_cache::prune_user_cache_in_background(&new_config.storage);
+            // Otherwise saving the preferences afterwards would write the old values back
+            draft.set(new_config.clone());
+            config.set(new_config);
+        }
+
+        let mut text = format!("Imported {} installation(s).", summary.imported.len());
+        if !summary.skipped.is_empty() {
+            text.push_str(&format!(" {} already existed and were left alone.", summary.skipped.len()));
+        }
+        for warning in &summary.warnings {
+            text.push_str(&format!(" {}.", warning.trim_end_matches('.')));
+        }
+        text.push_str(" Restart the installer to see them, then install each one to download its files and create the launcher profile.");
+        message.set(Some(text));
+    };
+
+    rsx! {
+        p { class: "setting-description",
+            "Move to a new PC by exporting your settings and installations into a single file. Game files are not included, they are downloaded again when you install on the new machine."
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: include_backups(),
+                onchange: move |evt| include_backups.set(evt.checked()),
+            }
+            "Include backups (can be large)"
+        }
+        div { class: "setting-row",
+            button {
+                class: "primary-button",
+                r#type: "button",
+                onclick: export_data,
+                "Export installer data"
+            }
+        }
+        label { class: "setting-checkbox",
+            input {
+                r#type: "checkbox",
+                checked: import_settings(),
+                onchange: move |evt| import_settings.set(evt.checked()),
+            }
+            "Replace my settings with the imported ones"
+        }
+        div { class: "setting-row",
+            button {
+                class: "secondary-button",
+                r#type: "button",
+                onclick: import_data,
+                "Import installer data"
+            }
+        }
+        if let Some(message) = message() {
+            p { class: "setting-description", "{message}" }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use log::{debug, warn};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256, Sha512};
+
+// Files smaller than this are streamed, mapping them costs more than it saves
+const MMAP_THRESHOLD: u64 = 4 * 1024 * 1024;
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashAlgorithm {
+    Sha256,
+    // Modrinth publishes sha512 for every file
+    Sha512,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn digest_file<D: Digest>(path: &Path) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read metadata of {}: {}", path.display(), e))?
+        .len();
+    let mut hasher = D::new();
+
+    if len >= MMAP_THRESHOLD {
+        // SAFETY: the map is read-only and dropped before returning. If another process truncates
+        // the file while we hash it we can get a SIGBUS, the usual mmap caveat. Don't verify while
+        // an install or the game is writing to the same folder.
+        match unsafe { Mmap::map(&file) } {
+            Ok(map) => {
+                hasher.update(&map[..]);
+                return Ok(to_hex(&hasher.finalize()));
+            }
+            Err(e) => warn!("Failed to mmap {}, streaming instead: {}", path.display(), e),
+        }
+    }
+
+    let mut reader = BufReader::with_capacity(CHUNK_SIZE, file);
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+pub fn hash_file(path: &Path, algorithm: HashAlgorithm) -> Result<String, String> {
+    match algorithm {
+        HashAlgorithm::Sha256 => digest_file::<Sha256>(path),
+        HashAlgorithm::Sha512 => digest_file::<Sha512>(path),
+    }
+}
+
+/// Lists every regular file under `root`. Symlinks are not followed.
+pub fn walk_files(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        for entry in entries.flatten() {
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(e) => {
+                    warn!("Skipping {}: {}", entry.path().display(), e);
+                    continue;
+                }
+            };
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Hashes the given files in parallel. `progress` is called with (done, total) after every file.
+pub fn hash_files<F>(files: &[PathBuf], algorithm: HashAlgorithm, progress: F) -> HashMap<PathBuf, Result<String, String>>
+where
+    F: Fn(usize, usize) + Sync,
+{
+    let total = files.len();
+    let done = AtomicUsize::new(0);
+    files
+        .par_iter()
+        .map(|path| {
+            let result = hash_file(path, algorithm);
+            progress(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+            (path.clone(), result)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerifyResult {
+    pub ok: Vec<PathBuf>,
+    pub missing: Vec<PathBuf>,
+    pub mismatched: Vec<PathBuf>,
+    // Files that exist but couldn't be read, with the reason
+    pub unreadable: Vec<(PathBuf, String)>,
+}
+
+impl VerifyResult {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty() && self.unreadable.is_empty()
+    }
+}
+
+/// Checks files under `root` against expected hashes (relative path -> hex digest).
+pub fn verify_files<F>(root: &Path, expected: &HashMap<PathBuf, String>, algorithm: HashAlgorithm, progress: F) -> VerifyResult
+where
+    F: Fn(usize, usize) + Sync,
+{
+    let mut result = VerifyResult::default();
+    let mut present = Vec::new();
+    for relative in expected.keys() {
+        if root.join(relative).is_file() {
+            present.push(root.join(relative));
+        } else {
+            result.missing.push(relative.clone());
+        }
+    }
+
+    for (path, hash) in hash_files(&present, algorithm, progress) {
+        let relative = path.strip_prefix(root).map(Path::to_path_buf).unwrap_or_else(|_| path.clone());
+        match hash {
+            Ok(hash) if expected.get(&relative).map_or(false, |e| e.eq_ignore_ascii_case(&hash)) => result.ok.push(relative),
+            Ok(_) => result.mismatched.push(relative),
+            Err(e) => result.unreadable.push((relative, e)),
+        }
+    }
+
+    debug!(
+        "Verified {}: {} ok, {} missing, {} mismatched, {} unreadable",
+        root.display(),
+        result.ok.len(),
+        result.missing.len(),
+        result.mismatched.len(),
+        result.unreadable.len()
+    );
+    result
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::RwLock;
+
+use log::{debug, warn};
+
+use crate::installation::Installation;
+use crate::{get_launcher, get_modpack_root, loader_repair, Manifest};
+
+// Quick health check of installed installations for the badge on the home page. Unlike verify.rs
+// nothing is hashed, it only looks at whether the pieces are there: the manifest.json the install
+// left, the loader's version json and a file for every mod. Results are kept for the session and
+// dropped when the installation is installed or repaired again.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthStatus {
+    Ok,
+    // Something is missing that Verify Files or the next launch brings back
+    NeedsRepair,
+    // Not enough left to repair from, needs an update or reinstall
+    Broken,
+}
+
+impl HealthStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HealthStatus::Ok => "OK",
+            HealthStatus::NeedsRepair => "Needs repair",
+            HealthStatus::Broken => "Broken",
+        }
+    }
+
+    pub fn class(&self) -> &'static str {
+        match self {
+            HealthStatus::Ok => "health-ok",
+            HealthStatus::NeedsRepair => "health-repair",
+            HealthStatus::Broken => "health-broken",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub issues: Vec<String>,
+}
+
+impl HealthReport {
+    fn broken(issue: String) -> Self {
+        HealthReport { status: HealthStatus::Broken, issues: vec![issue] }
+    }
+}
+
+static REPORTS: RwLock<Option<HashMap<String, HealthReport>>> = RwLock::new(None);
+
+/// Checks `installation`, blocking but only reads a few files. None when it isn't installed.
+pub fn probe(installation: &Installation) -> Option<HealthReport> {
+    if !installation.installed {
+        return None;
+    }
+    let launcher = match get_launcher(&installation.launcher_type) {
+        Ok(launcher) => launcher,
+        Err(e) => return Some(HealthReport::broken(e)),
+    };
+    let root = get_modpack_root(&launcher, &installation.id);
+    if !root.is_dir() {
+        return Some(HealthReport::broken(format!("The game folder {} is gone", root.display())));
+    }
+
+    let manifest_path = root.join("manifest.json");
+    let manifest: Manifest = match fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+    {
+        Ok(manifest) => manifest,
+        Err(e) => return Some(HealthReport::broken(format!("manifest.json can't be read: {}", e))),
+    };
+
+    let mut issues = Vec::new();
+    // Other launchers keep the loader in the instance, only the vanilla one shares versions/
+    if installation.launcher_type.starts_with("vanilla") {
+        match loader_repair::check(installation) {
+            Ok(loader_repair::LoaderHealth::Ok) => {}
+            Ok(loader_repair::LoaderHealth::Missing) => issues.push("The loader's version files are missing, they're restored on the next launch".to_string()),
+            Ok(loader_repair::LoaderHealth::Invalid(reason)) => issues.push(reason),
+            Err(e) => issues.push(e),
+        }
+    }
+
+    let expected: Vec<_> = manifest.mods.iter().filter_map(|m| m.path.as_ref()).collect();
+    let missing = expected.iter().filter(|path| !path.is_file()).count();
+    if missing > 0 {
+        issues.push(format!("{} of {} mods are missing from the mods folder", missing, expected.len()));
+    }
+
+    let status = if issues.is_empty() { HealthStatus::Ok } else { HealthStatus::NeedsRepair };
+    Some(HealthReport { status, issues })
+}
+
+/// probe() once per session, later calls get the stored result.
+pub fn cached(installation: &Installation) -> Option<HealthReport> {
+    if let Some(report) = REPORTS.read().unwrap().as_ref().and_then(|reports| reports.get(&installation.id)) {
+        return Some(report.clone());
+    }
+    let report = probe(installation)?;
+    match report.status {
+        HealthStatus::Ok => debug!("{} looks healthy", installation.id),
+        _ => warn!("{} is {}: {:?}", installation.id, report.status.label(), report.issues),
+    }
+    REPORTS
+        .write()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(installation.id.clone(), report.clone());
+    Some(report)
+}
+
+/// Drops the stored result, the next look probes again.
+pub fn forget(installation_id: &str) {
+    if let Some(reports) = REPORTS.write().unwrap().as_mut() {
+        reports.remove(installation_id);
+    }
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::PathBuf;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use isahc::{AsyncBody, AsyncReadResponseExt, HttpClient, ReadResponseExt, Response};
+use lazy_static::lazy_static;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// Offline development support. Set WC_OVHL_HTTP_MODE to "record" to save every response the
+// installer gets into the fixtures dir, or to "replay" to answer requests from those fixtures
+// without touching the network. WC_OVHL_HTTP_FIXTURES overrides the fixtures dir.
+const MODE_VAR: &str = "WC_OVHL_HTTP_MODE";
+const FIXTURES_VAR: &str = "WC_OVHL_HTTP_FIXTURES";
+const DEFAULT_FIXTURES_DIR: &str = "http-fixtures";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpMode {
+    Live,
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+lazy_static! {
+    static ref MODE: HttpMode = mode_from_env();
+}
+
+fn mode_from_env() -> HttpMode {
+    let dir = std::env::var(FIXTURES_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_FIXTURES_DIR));
+    let mode = match std::env::var(MODE_VAR).unwrap_or_default().to_lowercase().as_str() {
+        "" | "live" => HttpMode::Live,
+        "record" => HttpMode::Record(dir),
+        "replay" => HttpMode::Replay(dir),
+        other => {
+            warn!("Unknown {} '{}', using live requests", MODE_VAR, other);
+            HttpMode::Live
+        }
+    };
+    if mode != HttpMode::Live {
+        info!("HTTP mode: {:?}", mode);
+    }
+    mode
+}
+
+pub fn mode() -> &'static HttpMode {
+    &MODE
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    // base64, responses are often zips or jars
+    body: String,
+}
+
+// Fixture names are stable across runs so they can be committed for the test harness
+fn fixture_path(dir: &PathBuf, method: &str, url: &str) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(url.as_bytes());
+    let hash: String = hasher.finalize().iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    let host: String = url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.replace('.', "_")))
+        .unwrap_or_else(|| "unknown".to_string());
+    dir.join(format!("{}-{}-{}.json", method.to_lowercase(), host, hash))
+}
+
+fn build_response(fixture: &Fixture, bytes: Vec<u8>) -> Result<Response<AsyncBody>, String> {
+    let mut builder = Response::builder().status(fixture.status);
+    for (name, value) in &fixture.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    builder
+        .body(AsyncBody::from(bytes))
+        .map_err(|e| format!("Invalid fixture response: {}", e))
+}
+
+fn load_fixture(dir: &PathBuf, method: &str, url: &str) -> Result<(Fixture, Vec<u8>), String> {
+    let path = fixture_path(dir, method, url);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("No fixture for {} {} ({}): {}", method, url, path.display(), e))?;
+    let fixture: Fixture = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid fixture {}: {}", path.display(), e))?;
+    let bytes = STANDARD
+        .decode(&fixture.body)
+        .map_err(|e| format!("Invalid fixture body {}: {}", path.display(), e))?;
+    Ok((fixture, bytes))
+}
+
+fn save_fixture(dir: &PathBuf, method: &str, url: &str, status: u16, headers: Vec<(String, String)>, bytes: &[u8]) {
+    let fixture = Fixture {
+        method: method.to_string(),
+        url: url.to_string(),
+        status,
+        headers,
+        body: STANDARD.encode(bytes),
+    };
+    let path = fixture_path(dir, method, url);
+    let result = fs::create_dir_all(dir)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&fixture).map_err(|e| e.to_string()))
+        .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+    match result {
+        Ok(_) => debug!("Recorded {} {} to {}", method, url, path.display()),
+        Err(e) => error!("Failed to record fixture for {}: {}", url, e),
+    }
+}
+
+fn header_pairs<T>(resp: &Response<T>) -> Vec<(String, String)> {
+    resp.headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// In replay mode returns the recorded response, `None` means the request should go out as usual.
+pub fn replay(method: &str, url: &str) -> Option<Result<Response<AsyncBody>, isahc::Error>> {
+    let HttpMode::Replay(dir) = mode() else {
+        return None;
+    };
+    let result = load_fixture(dir, method, url).and_then(|(fixture, bytes)| build_response(&fixture, bytes));
+    Some(result.map_err(|e| {
+        warn!("{}", e);
+        isahc::Error::from(std::io::Error::new(std::io::ErrorKind::NotFound, e))
+    }))
+}
+
+/// In record mode saves the response and hands back an identical one, otherwise a no-op.
+pub async fn record(method: &str, url: &str, mut resp: Response<AsyncBody>) -> Response<AsyncBody> {
+    let HttpMode::Record(dir) = mode() else {
+        return resp;
+    };
+    let bytes = match resp.bytes().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to read response body for recording {}: {}", url, e);
+            Vec::new()
+        }
+    };
+    let headers = header_pairs(&resp);
+    save_fixture(dir, method, url, resp.status().as_u16(), headers, &bytes);
+    crate::resp_rebuilder(&resp, &bytes)
+}
+
+/// Blocking GET returning the body as text, used for the startup requests made before the async runtime exists.
+pub fn get_text_blocking(http_client: &HttpClient, url: &str) -> Result<String, String> {
+    match mode() {
+        HttpMode::Replay(dir) => {
+            let (_, bytes) = load_fixture(dir, "GET", url)?;
+            String::from_utf8(bytes).map_err(|e| format!("Fixture for {} is not text: {}", url, e))
+        }
+        mode => {
+            let mut resp = http_client
+                .get(url)
+                .map_err(|e| format!("Failed to get {}: {}", url, e))?;
+            let text = resp.text().map_err(|e| format!("Failed to read {}: {}", url, e))?;
+            if let HttpMode::Record(dir) = mode {
+                save_fixture(dir, "GET", url, resp.status().as_u16(), header_pairs(&resp), text.as_bytes());
+            }
+            Ok(text)
+        }
+    }
+}
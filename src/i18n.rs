@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use log::{info, warn};
+
+// GUI strings, looked up by key in the locale of config.general.language. The locales are json
+// maps compiled into the binary from assets/locales; a key missing from a locale falls back to
+// English, and one missing from English shows the key itself so it's easy to spot.
+// `{name}` placeholders are filled in by `t_with`.
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+// Code and name of every locale, in the order the language selector lists them
+pub const LANGUAGES: &[(&str, &str)] = &[("en", "English"), ("de", "Deutsch")];
+
+fn locale_source(language: &str) -> Option<&'static str> {
+    match language {
+        "en" => Some(include_str!("assets/locales/en.json")),
+        "de" => Some(include_str!("assets/locales/de.json")),
+        _ => None,
+    }
+}
+
+lazy_static! {
+    static ref LOCALES: HashMap<&'static str, HashMap<String, String>> = LANGUAGES
+        .iter()
+        .filter_map(|(code, _)| {
+            let strings = serde_json::from_str(locale_source(code)?)
+                .map_err(|e| warn!("Locale '{}' is invalid: {}", code, e))
+                .ok()?;
+            Some((*code, strings))
+        })
+        .collect();
+    // Mirrors config.general.language, set at startup and whenever preferences are saved
+    static ref LANGUAGE: RwLock<String> = RwLock::new(DEFAULT_LANGUAGE.to_string());
+}
+
+pub fn configure(language: &str) {
+    let language = if LOCALES.contains_key(language) {
+        language
+    } else {
+        warn!("Unknown language '{}', using English", language);
+        DEFAULT_LANGUAGE
+    };
+    let mut current = LANGUAGE.write().unwrap_or_else(|e| e.into_inner());
+    if *current != language {
+        info!("Language: {}", language);
+        *current = language.to_string();
+    }
+}
+
+/// The string for `key` in the current language.
+pub fn t(key: &str) -> String {
+    let language = LANGUAGE.read().unwrap_or_else(|e| e.into_inner());
+    [language.as_str(), DEFAULT_LANGUAGE]
+        .iter()
+        .find_map(|code| LOCALES.get(code)?.get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Like `t`, with every `{name}` replaced by its value.
+pub fn t_with(key: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(t(key), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
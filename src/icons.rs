@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use base64::{engine, Engine};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader};
+use isahc::AsyncReadResponseExt;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::installation::Installation;
+use crate::{CachedHttpClient, Launcher};
+
+// Icons are decoded, resized and encoded on the blocking pool, installs run on the UI's runtime and
+// decoding a large PNG there freezes the window. The launcher never shows profile icons bigger than
+// 128px, so bigger ones are scaled down before they're embedded into launcher_profiles.json as a
+// data url. Full size PNGs made that file grow by megabytes per installation.
+
+pub const PACK_ICON_URL: &str = "https://raw.githubusercontent.com/Wynncraft-Overhaul/installer/master/src/assets/icon.png";
+const MAX_ICON_SIZE: u32 = 128;
+
+// In the installation folder: the image the user picked (already scaled down), and whatever icon
+// the launcher profile got last, which is what desktop shortcuts should show too
+const CUSTOM_ICON_FILE: &str = "custom-icon.png";
+const PROFILE_ICON_FILE: &str = "icon.png";
+
+// Decoded and already scaled down, keyed by url
+static DECODED: Lazy<Mutex<HashMap<String, Arc<DynamicImage>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Fallback when the pack icon can't be downloaded, only decoded the first time it's needed
+static EMBEDDED: Lazy<Arc<DynamicImage>> = Lazy::new(|| {
+    let icon = image::load_from_memory(include_bytes!("assets/icon.png")).expect("Embedded icon is a valid PNG");
+    Arc::new(fit(icon))
+});
+
+/// Icon as it ends up in the launcher: the image for MultiMC's icon.png and the data url for the vanilla launcher.
+pub struct ProfileIcon {
+    pub image: Arc<DynamicImage>,
+    pub data_url: String,
+}
+
+/// What an installation shows in the launcher instead of the pack icon.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InstallationIcon {
+    // One of the manifest's `icons`, the url is kept so it still works once the manifest drops it
+    Pack { id: String, url: String },
+    // Picked from disk, see import_local
+    Local,
+}
+
+fn fit(img: DynamicImage) -> DynamicImage {
+    if img.width() <= MAX_ICON_SIZE && img.height() <= MAX_ICON_SIZE {
+        return img;
+    }
+    debug!("Scaling icon down from {}x{}", img.width(), img.height());
+    img.resize(MAX_ICON_SIZE, MAX_ICON_SIZE, FilterType::Lanczos3)
+}
+
+fn decode(bytes: &[u8]) -> Result<DynamicImage, String> {
+    ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| format!("Failed to guess icon format: {}", e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode icon: {}", e))
+}
+
+fn to_data_url(img: &DynamicImage) -> Result<String, String> {
+    let mut data = Vec::new();
+    img.write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode icon: {}", e))?;
+    Ok(format!("data:image/png;base64,{}", engine::general_purpose::STANDARD.encode(data)))
+}
+
+/// Downloads and decodes an icon, the same url is only decoded once per run.
+pub async fn fetch(http_client: &CachedHttpClient, url: &str) -> Option<Arc<DynamicImage>> {
+    if let Some(icon) = DECODED.lock().ok().and_then(|decoded| decoded.get(url).cloned()) {
+        return Some(icon);
+    }
+    let bytes = match http_client.get_async(url).await {
+        Ok(mut resp) if resp.status().is_success() => resp.bytes().await.map_err(|e| e.to_string()),
+        Ok(resp) => Err(format!("HTTP {}", resp.status())),
+        Err(e) => Err(e.to_string()),
+    };
+    let decoded = match bytes {
+        Ok(bytes) => tokio::task::spawn_blocking(move || decode(&bytes).map(fit))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result),
+        Err(e) => Err(e),
+    };
+    match decoded {
+        Ok(icon) => {
+            let icon = Arc::new(icon);
+            if let Ok(mut cache) = DECODED.lock() {
+                cache.insert(url.to_string(), icon.clone());
+            }
+            Some(icon)
+        }
+        Err(e) => {
+            warn!("Failed to load icon {}: {}", url, e);
+            None
+        }
+    }
+}
+
+/// Encodes `icon` (or the embedded one) for the launcher profile.
+pub async fn prepare(icon: Option<Arc<DynamicImage>>) -> Option<ProfileIcon> {
+    let encoded = tokio::task::spawn_blocking(move || {
+        let image = icon.unwrap_or_else(|| EMBEDDED.clone());
+        to_data_url(&image).map(|data_url| ProfileIcon { image, data_url })
+    })
+    .await;
+    match encoded {
+        Ok(Ok(icon)) => Some(icon),
+        Ok(Err(e)) => {
+            warn!("{}", e);
+            None
+        }
+        Err(e) => {
+            warn!("Icon encoding task failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Scales a picked image down and stores it with the installation, it's decoded on the blocking pool.
+pub async fn import_local(installation_dir: PathBuf, source: PathBuf) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let bytes = fs::read(&source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        let icon = fit(decode(&bytes)?);
+        fs::create_dir_all(&installation_dir)
+            .map_err(|e| format!("Failed to create installation directory: {}", e))?;
+        icon.save_with_format(installation_dir.join(CUSTOM_ICON_FILE), ImageFormat::Png)
+            .map_err(|e| format!("Failed to save icon: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Icon import task failed: {}", e))?
+}
+
+async fn load_local(path: PathBuf) -> Option<Arc<DynamicImage>> {
+    let decoded = tokio::task::spawn_blocking(move || {
+        fs::read(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+            .and_then(|bytes| decode(&bytes))
+            .map(fit)
+    })
+    .await;
+    match decoded {
+        Ok(Ok(icon)) => Some(Arc::new(icon)),
+        Ok(Err(e)) => {
+            warn!("{}", e);
+            None
+        }
+        Err(e) => {
+            warn!("Icon decoding task failed: {}", e);
+            None
+        }
+    }
+}
+
+/// The icon the installation picked, None when it keeps the pack icon (or the picked one is gone).
+pub async fn resolve(http_client: &CachedHttpClient, installation: &Installation) -> Option<Arc<DynamicImage>> {
+    match &installation.icon {
+        None => None,
+        Some(InstallationIcon::Pack { url, .. }) => fetch(http_client, url).await,
+        Some(InstallationIcon::Local) => load_local(installation.installation_path.join(CUSTOM_ICON_FILE)).await,
+    }
+}
+
+/// Copies the profile icon into the installation folder for desktop shortcuts.
+pub fn keep_for_shortcuts(installation_dir: &Path, icon: &ProfileIcon) {
+    let result = fs::create_dir_all(installation_dir)
+        .map_err(|e| e.to_string())
+        .and_then(|_| {
+            icon.image
+                .save_with_format(installation_dir.join(PROFILE_ICON_FILE), ImageFormat::Png)
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Failed to keep the profile icon in {}: {}", installation_dir.display(), e);
+    }
+}
+
+/// Puts the installation's icon on its existing launcher profile, after the user picked another one.
+pub async fn apply(http_client: &CachedHttpClient, installation: &Installation) -> Result<(), String> {
+    let image = match resolve(http_client, installation).await {
+        Some(image) => Some(image),
+        None => fetch(http_client, PACK_ICON_URL).await,
+    };
+    let icon = prepare(image).await.ok_or("Failed to prepare the icon")?;
+    keep_for_shortcuts(&installation.installation_path, &icon);
+
+    // Not installed yet, the profile is created with this icon on install
+    if !installation.installed {
+        return Ok(());
+    }
+    match crate::get_launcher(&installation.launcher_type)? {
+        Launcher::Vanilla(_) => crate::launcher::config::update_launcher_profile_icon(&installation.id, &icon.data_url),
+        Launcher::MultiMC(root) => {
+            let instance_path = root.join("instances").join(&installation.id);
+            if !instance_path.is_dir() {
+                return Err(format!("MultiMC instance {} not found", installation.id));
+            }
+            let image = icon.image.clone();
+            tokio::task::spawn_blocking(move || image.save_with_format(instance_path.join("icon.png"), ImageFormat::Png))
+                .await
+                .map_err(|e| format!("Icon saving task failed: {}", e))?
+                .map_err(|e| format!("Failed to save instance icon: {}", e))
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{CachedHttpClient, Downloadable, Manifest};
+
+// install() and update() note every step they finish in install-journal.json next to the
+// installation's config, and delete it once everything went through. A journal that's still there
+// on startup means the installer crashed or was killed halfway, and the game folder may hold a mix
+// of old and new files even though the installation says it's installed. The user can resume,
+// which runs the update again and skips what's already there, or roll back to the manifest.json
+// from before the update, whose components mostly come straight out of the download cache.
+
+const JOURNAL_FILE: &str = "install-journal.json";
+// mods, shaderpacks, resourcepacks, includes, remote_includes, manifest, launcher_profile, loader
+pub const STEP_COUNT: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InstallJournal {
+    pub installation_id: String,
+    // When the first attempt started, a resumed attempt keeps it
+    pub started_at: DateTime<Utc>,
+    pub from_version: Option<String>,
+    pub to_version: String,
+    // Finished steps in order, see STEP_COUNT
+    pub completed: Vec<String>,
+    // manifest.json from before the update, None for first installs which have nothing to go back to
+    pub previous_manifest: Option<Manifest>,
+}
+
+impl InstallJournal {
+    pub fn can_roll_back(&self) -> bool {
+        self.previous_manifest.is_some()
+    }
+}
+
+fn journal_path(installation_id: &str) -> PathBuf {
+    crate::installation::get_installations_dir()
+        .join(installation_id)
+        .join(JOURNAL_FILE)
+}
+
+fn write(journal: &InstallJournal) -> Result<(), String> {
+    let path = journal_path(&journal.installation_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string(journal).map_err(|e| format!("Failed to serialize install journal: {}", e))?;
+    // Renamed into place, a crash while writing must not leave half a journal behind
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn load(installation_id: &str) -> Option<InstallJournal> {
+    let path = journal_path(installation_id);
+    let content = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(journal) => Some(journal),
+        Err(e) => {
+            warn!("Ignoring unreadable install journal {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Starts the journal of an install or update. When an earlier attempt was interrupted its
+/// manifest is kept, that's still what a rollback has to go back to.
+pub fn begin(installation_id: &str, to_version: &str, previous_manifest: Option<Manifest>) -> Result<(), String> {
+    let (started_at, previous_manifest) = match load(installation_id) {
+        Some(interrupted) => {
+            info!(
+                "Continuing the interrupted install of {} ({} step(s) were done)",
+                installation_id,
+                interrupted.completed.len()
+            );
+            (interrupted.started_at, interrupted.previous_manifest)
+        }
+        None => (Utc::now(), previous_manifest),
+    };
+    write(&InstallJournal {
+        installation_id: installation_id.to_string(),
+        started_at,
+        from_version: previous_manifest.as_ref().map(|m| m.modpack_version.clone()),
+        to_version: to_version.to_string(),
+        completed: Vec::new(),
+        previous_manifest,
+    })
+}
+
+pub fn step(installation_id: &str, step: &str) {
+    let Some(mut journal) = load(installation_id) else { return };
+    journal.completed.push(step.to_string());
+    match write(&journal) {
+        Ok(_) => debug!("Install step '{}' of {} done", step, installation_id),
+        Err(e) => warn!("Failed to record install step '{}': {}", step, e),
+    }
+}
+
+pub fn finish(installation_id: &str) {
+    match fs::remove_file(journal_path(installation_id)) {
+        Ok(_) => debug!("Install of {} finished, journal removed", installation_id),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!("Failed to remove the install journal of {}: {}", installation_id, e),
+    }
+}
+
+/// Journals left behind by installs that never finished.
+pub fn find_interrupted() -> Vec<InstallJournal> {
+    let Ok(entries) = fs::read_dir(crate::installation::get_installations_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| load(&entry.file_name().to_string_lossy()))
+        .collect()
+}
+
+/// Runs the interrupted install or update again, whatever is already there is kept.
+pub async fn resume(http_client: &CachedHttpClient, journal: &InstallJournal) -> Result<(), String> {
+    let mut installation = crate::installation::load_installation(&journal.installation_id)?;
+    let manifest = installation
+        .load_universal_manifest(http_client)
+        .await
+        .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
+    info!("Resuming the interrupted install of '{}'", installation.name);
+    installation
+        .install_or_update_with_manifest(http_client, &manifest, &installation.pack_source().branch(), || {})
+        .await?;
+    installation.universal_version = manifest.modpack_version.clone();
+    installation.mark_installed()
+}
+
+// Files the interrupted update put in `dir` that the old version doesn't have, including the
+// .part files of downloads that were in progress. Anything older than the update, like mods the
+// user added by hand, stays.
+fn remove_new_files<T: Downloadable>(dir: &Path, previous: &[T], started_at: SystemTime) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    let keep: Vec<&PathBuf> = previous.iter().filter_map(|item| item.get_path().as_ref()).collect();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() || keep.iter().any(|k| **k == path) {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+        if modified >= started_at {
+            debug!("Removing {} left by the interrupted update", path.display());
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+// Items whose file the update already deleted are downloaded again
+fn clear_missing<T: Downloadable>(items: &[T]) -> Vec<T> {
+    items
+        .iter()
+        .map(|item| {
+            let path = item.get_path().clone().filter(|p| p.exists());
+            T::new(
+                item.get_name().to_owned(),
+                item.get_source().to_owned(),
+                item.get_location().to_owned(),
+                item.get_version().to_owned(),
+                path,
+                item.get_id().to_owned(),
+                item.get_authors().to_owned(),
+            )
+            .with_mirrors(item.get_mirrors().clone())
+        })
+        .collect()
+}
+
+/// Puts the installation back to the version it had before the interrupted update.
+pub async fn roll_back(http_client: &CachedHttpClient, journal: &InstallJournal) -> Result<(), String> {
+    let Some(previous) = journal.previous_manifest.clone() else {
+        return Err("This was the first install, there is no earlier version to go back to".to_string());
+    };
+    let installation = crate::installation::load_installation(&journal.installation_id)?;
+    let launcher = crate::get_launcher(&installation.launcher_type)?;
+    let modpack_root = crate::get_modpack_root(&launcher, &installation.id);
+    info!(
+        "Rolling '{}' back to {} after an interrupted update",
+        installation.name, previous.modpack_version
+    );
+
+    let started_at: SystemTime = journal.started_at.into();
+    remove_new_files(&modpack_root.join("mods"), &previous.mods, started_at);
+    remove_new_files(&modpack_root.join("shaderpacks"), &previous.shaderpacks, started_at);
+    remove_new_files(&modpack_root.join("resourcepacks"), &previous.resourcepacks, started_at);
+
+    let mut manifest = previous.clone();
+    manifest.mods = clear_missing(&previous.mods);
+    manifest.shaderpacks = clear_missing(&previous.shaderpacks);
+    manifest.resourcepacks = clear_missing(&previous.resourcepacks);
+    let profile = crate::InstallerProfile {
+        manifest,
+        http_client: http_client.clone(),
+        installed: true,
+        update_available: true,
+        modpack_source: "Wynncraft-Overhaul/majestic-overhaul/".to_string(),
+        modpack_branch: "master".to_string(),
+        enabled_features: previous.enabled_features.clone(),
+        launcher: Some(launcher),
+        // Without a local manifest remote includes are extracted again, the update may already
+        // have overwritten them with the new version. The download cache usually has them
+        local_manifest: None,
+        changelog: None,
+    };
+    crate::install(&profile, || {}).await?;
+
+    // install() marks it up to date, the update is still there to be installed
+    let mut installation = crate::installation::load_installation(&journal.installation_id)?;
+    installation.update_available = true;
+    installation.save()?;
+    finish(&journal.installation_id);
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use log::{debug, info, warn};
+use serde::Deserialize;
+
+use crate::installation::{get_installations_dir, Installation};
+
+// Installations can keep their game files somewhere else than .WC_OVHL/installations/<id>, a
+// bigger drive for example. installation.json always stays in the installations folder, it's how
+// installations are found; only the game dir moves. get_modpack_root is called with just the id
+// from all over, so the custom dirs are looked up here and read from installation.json the first
+// time an id comes up. Only the vanilla launcher, other launchers keep instances in their own
+// folders.
+
+// Written into every folder validate() accepted. Deleting an installation only deletes its custom
+// dir when this is there, so a path that came from somewhere else (another machine's bundle, a
+// hand-edited installation.json) never takes an unrelated folder with it.
+const OWNER_MARKER: &str = ".wc-overhaul-game-dir";
+const OWNER_MARKER_TEXT: &[u8] = b"Game files of a Wynncraft Overhaul installation, deleted with it\n";
+
+static CUSTOM_DIRS: RwLock<Option<HashMap<String, Option<PathBuf>>>> = RwLock::new(None);
+
+// Just the one field, everything else in installation.json is ignored
+#[derive(Deserialize)]
+struct StoredLocation {
+    #[serde(default)]
+    custom_game_dir: Option<PathBuf>,
+}
+
+fn read_stored(id: &str) -> Option<PathBuf> {
+    let path = get_installations_dir().join(id).join("installation.json");
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<StoredLocation>(&content).ok()?.custom_game_dir
+}
+
+/// Keeps the lookup in step with `installation`, called whenever it's saved.
+pub fn remember(installation: &Installation) {
+    CUSTOM_DIRS
+        .write()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(installation.id.clone(), installation.custom_game_dir.clone());
+}
+
+/// The custom game dir of installation `id`, None when it uses the default one.
+pub fn custom_dir(id: &str) -> Option<PathBuf> {
+    if let Some(known) = CUSTOM_DIRS.read().unwrap().as_ref().and_then(|dirs| dirs.get(id)) {
+        return known.clone();
+    }
+    let stored = read_stored(id);
+    CUSTOM_DIRS
+        .write()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(id.to_string(), stored.clone());
+    stored
+}
+
+/// Where the vanilla launcher's game dir of installation `id` is.
+pub fn vanilla_game_dir(id: &str) -> PathBuf {
+    custom_dir(id).unwrap_or_else(|| get_installations_dir().join(id))
+}
+
+/// Whether the installer may empty the vanilla game dir of installation `id`: the default one
+/// always, a custom one only when it has the marker validate() leaves.
+pub fn owns_game_dir(id: &str) -> bool {
+    custom_dir(id).map_or(true, |dir| dir.join(OWNER_MARKER).is_file())
+}
+
+/// Puts the marker back into a custom game dir that was just emptied, nothing for the default one.
+pub fn mark_game_dir(id: &str) -> std::io::Result<()> {
+    match custom_dir(id) {
+        Some(dir) => fs::write(dir.join(OWNER_MARKER), OWNER_MARKER_TEXT),
+        None => Ok(()),
+    }
+}
+
+/// Checks a folder picked for a new installation. It has to be empty (or not exist yet), deleting
+/// the installation deletes it, and writable.
+pub fn validate(dir: &Path) -> Result<PathBuf, String> {
+    if !dir.is_absolute() {
+        return Err(format!("{} isn't a full path", dir.display()));
+    }
+    if dir.starts_with(get_installations_dir()) {
+        return Err("That's inside the installer's own folder, leave the location empty to use it".to_string());
+    }
+    if dir.exists() {
+        let mut entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        if entries.next().is_some() {
+            return Err(format!("{} isn't empty, pick an empty or new folder", dir.display()));
+        }
+    }
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    // Doubles as the check that the folder is writable
+    fs::write(dir.join(OWNER_MARKER), OWNER_MARKER_TEXT)
+        .map_err(|e| format!("Can't write to {}: {}", dir.display(), e))?;
+    debug!("{} is usable as a game dir", dir.display());
+    Ok(dir.to_path_buf())
+}
+
+/// Moves a new installation's game dir to `dir`, before it's installed.
+pub fn apply(installation: &mut Installation, dir: &Path) -> Result<(), String> {
+    let dir = validate(dir)?;
+    info!("Installation {} keeps its game files in {}", installation.id, dir.display());
+    installation.installation_path = dir.clone();
+    installation.custom_game_dir = Some(dir);
+    remember(installation);
+    Ok(())
+}
+
+/// Deletes the custom game dir of a deleted installation, nothing when it had none. Folders
+/// without the marker validate() leaves are kept.
+pub fn remove(installation: &Installation) -> Result<(), String> {
+    let Some(dir) = &installation.custom_game_dir else { return Ok(()) };
+    if dir.join(OWNER_MARKER).is_file() {
+        fs::remove_dir_all(dir).map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))?;
+    } else if dir.exists() {
+        warn!("Not deleting {}, it wasn't set up as the game dir of {}", dir.display(), installation.id);
+    }
+    if let Some(dirs) = CUSTOM_DIRS.write().unwrap().as_mut() {
+        dirs.remove(&installation.id);
+    }
+    Ok(())
+}
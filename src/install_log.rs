@@ -0,0 +1,134 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+// The installer log covers every installation and is full of debug noise. This one is per
+// installation and only has what happened to it: one JSON object per line with the phase
+// (mods, includes, launcher_profile...), the component if there is one, and how it went.
+// install() and update() append to it, the settings tab shows it. Runs are appended, the file is
+// cut down to the newest runs when it gets too long.
+
+const LOG_FILE: &str = "install-log.jsonl";
+// Older runs are dropped from the front once a new run would go over this
+const MAX_LINES: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    pub outcome: String,
+}
+
+fn log_path(installation_id: &str) -> PathBuf {
+    crate::installation::get_installations_dir()
+        .join(installation_id)
+        .join(LOG_FILE)
+}
+
+fn append(installation_id: &str, entry: &InstallLogEntry) -> Result<(), String> {
+    let path = log_path(installation_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let line = serde_json::to_string(entry).map_err(|e| format!("Failed to serialize install log entry: {}", e))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// Keeps the newest lines, a run that starts cut off is still readable
+fn trim(installation_id: &str) {
+    let path = log_path(installation_id);
+    let Ok(content) = fs::read_to_string(&path) else { return };
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() < MAX_LINES {
+        return;
+    }
+    let kept = lines[lines.len() - MAX_LINES / 2..].join("\n") + "\n";
+    if let Err(e) = fs::write(&path, kept) {
+        warn!("Failed to trim {}: {}", path.display(), e);
+    }
+}
+
+/// Marks the start of an install or update run.
+pub fn begin(installation_id: &str, kind: &str, version: &str) {
+    trim(installation_id);
+    record(installation_id, Level::Info, "start", None, &format!("{} of version {} started", kind, version));
+}
+
+/// Appends one entry. Failing to log never fails the install, it's only warned about.
+pub fn record(installation_id: &str, level: Level, phase: &str, component: Option<&str>, outcome: &str) {
+    let entry = InstallLogEntry {
+        timestamp: Utc::now(),
+        level,
+        phase: phase.to_string(),
+        component: component.map(str::to_string),
+        outcome: outcome.to_string(),
+    };
+    if let Err(e) = append(installation_id, &entry) {
+        warn!("Failed to write the install log of {}: {}", installation_id, e);
+    }
+}
+
+/// All entries, oldest first. Lines that don't parse are skipped.
+pub fn read(installation_id: &str) -> Result<Vec<InstallLogEntry>, String> {
+    let path = log_path(installation_id);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let mut skipped = 0;
+    let entries: Vec<InstallLogEntry> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(_) => {
+                skipped += 1;
+                None
+            }
+        })
+        .collect();
+    if skipped > 0 {
+        debug!("Skipped {} unreadable line(s) in {}", skipped, path.display());
+    }
+    Ok(entries)
+}
+
+pub fn clear(installation_id: &str) -> Result<(), String> {
+    let path = log_path(installation_id);
+    match fs::remove_file(&path) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to delete {}: {}", path.display(), e)),
+    }
+}
@@ -4,7 +4,8 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use log::{debug, error, info, warn};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::process::Command;
 use uuid::Uuid;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -15,8 +16,8 @@ use crate::Launcher;
 // FIXED: Combine all backup imports into one line
 use crate::backup::{
     BackupProgress, BackupConfig, BackupType, BackupMetadata, BackupItem,
-    FileSystemItem, count_files_recursive, calculate_directory_size, 
-    create_zip_archive, format_bytes
+    BackupFileEntry, FileSystemItem, WorldInfo, count_files_recursive, calculate_directory_size, 
+    format_bytes
 };
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
@@ -27,8 +28,7 @@ pub struct InstallationsIndex {
 }
 
 pub fn get_installations_dir() -> PathBuf {
-    let app_data = crate::get_app_data();
-    app_data.join(".WC_OVHL/installations")
+    crate::get_installer_dir().join("installations")
 }
 
 pub struct ProgressTracker {
@@ -123,7 +123,23 @@ pub struct Installation {
     
     // Performance settings
     pub memory_allocation: i32,   // in MB
+    // Memory follows the recommendation for the computer and the enabled features
+    #[serde(default)]
+    pub auto_memory: bool,
     pub java_args: String,
+    // JVM preset java_args was built from, None when the arguments are edited by hand
+    #[serde(default)]
+    pub jvm_preset: Option<String>,
+    // The user's own arguments on top of the preset
+    #[serde(default)]
+    pub custom_java_args: String,
+
+    // Set for the game process (MESA_GL_VERSION_OVERRIDE, __GL_THREADED_OPTIMIZATIONS...)
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+    // Program the game is started through ("mangohud", "gamemoderun"), split at spaces
+    #[serde(default)]
+    pub wrapper_command: Option<String>,
     
     // Installation status tracking
     pub installed: bool,
@@ -138,6 +154,98 @@ pub struct Installation {
     // Last launch info for statistics
     pub last_launch: Option<DateTime<Utc>>,
     pub total_launches: u32,
+    // Filled in by the process supervisor once the launched process exits
+    #[serde(default)]
+    pub total_play_seconds: u64,
+    #[serde(default)]
+    pub last_session_seconds: Option<u64>,
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
+
+    // Component id -> acknowledgement note the user confirmed before enabling it
+    #[serde(default)]
+    pub acknowledged_notes: HashMap<String, String>,
+
+    // Free-form markdown the user keeps about this installation ("guild wars build, don't update")
+    #[serde(default)]
+    pub notes: String,
+
+    #[serde(default)]
+    pub reminders: Vec<crate::reminders::Reminder>,
+
+    // Lists the manifest's hidden (experimental) components in the features tab
+    #[serde(default)]
+    pub show_experimental: bool,
+
+    // Components starred in the features tab, listed first
+    #[serde(default)]
+    pub favorite_components: Vec<String>,
+
+    // Component id -> installed version the user last looked at, anything installed since then
+    // gets a "changed" badge in the features tab
+    #[serde(default)]
+    pub reviewed_component_versions: HashMap<String, String>,
+
+    // Icon for the launcher profile, None keeps the pack's own icon
+    #[serde(default)]
+    pub icon: Option<crate::icons::InstallationIcon>,
+
+    // Config preset picked in the wizard and the one last merged into the game folder, it's
+    // only applied again when the pick changes so later tweaks in game aren't overwritten
+    #[serde(default)]
+    pub config_preset: Option<String>,
+    #[serde(default)]
+    pub applied_config_preset: Option<String>,
+
+    // Shader quality tier whose settings were written last, same reasoning as above
+    #[serde(default)]
+    pub applied_shader_tier: Option<String>,
+
+    // Servers (normalized addresses) already put into servers.dat, ones the user removed again
+    // aren't added back on the next update
+    #[serde(default)]
+    pub added_servers: Vec<String>,
+
+    // The user's resource pack order (component ids, highest priority first), empty follows the
+    // pack's resourcepack_order
+    #[serde(default)]
+    pub resourcepack_order: Vec<String>,
+
+    // What backups of this installation include, and whether one is made before every update
+    #[serde(default)]
+    pub backup_config: BackupConfig,
+
+    // When "update tonight" was picked, the scheduled update loop runs the update once this passes
+    #[serde(default)]
+    pub scheduled_update: Option<DateTime<Utc>>,
+
+    // Minecraft version picked instead of the manifest's own, one of its `minecraft_versions`
+    #[serde(default)]
+    pub minecraft_version_override: Option<String>,
+
+    // Where the modpack comes from, None is the default repo (and every installation made before sources)
+    #[serde(default)]
+    pub source: Option<crate::sources::PackSource>,
+
+    // Feature profile the next update switches to, see feature_profiles.rs
+    #[serde(default)]
+    pub next_update_profile: Option<String>,
+
+    // Stable, beta or dev builds of the pack, see channels.rs
+    #[serde(default)]
+    pub release_channel: crate::channels::ReleaseChannel,
+
+    // Channel of the last install, a switch is an update even when that version isn't newer
+    #[serde(default)]
+    pub installed_channel: crate::channels::ReleaseChannel,
+
+    // Game files somewhere else than the installations folder, see install_location.rs
+    #[serde(default)]
+    pub custom_game_dir: Option<PathBuf>,
+
+    // Install new pack versions without asking, see auto_update.rs
+    #[serde(default)]
+    pub auto_update: bool,
 }
 
 impl Installation {
@@ -176,7 +284,12 @@ impl Installation {
             installation_path,
             enabled_features: preset.enabled_features.clone(),
             memory_allocation,
+            auto_memory: false,
             java_args,
+            jvm_preset: None,
+            custom_java_args: String::new(),
+            env_vars: BTreeMap::new(),
+            wrapper_command: None,
             installed: false,
             modified: false,
             update_available: false,
@@ -184,6 +297,9 @@ impl Installation {
             universal_version,
             last_launch: None,
             total_launches: 0,
+            total_play_seconds: 0,
+            last_session_seconds: None,
+            last_exit_code: None,
             preset_update_available: false,
             base_preset_id: Some(preset.id.clone()),
             base_preset_version: preset.preset_version.clone(),
@@ -195,6 +311,27 @@ impl Installation {
             installed_features: Vec::new(),
             pending_features: preset.enabled_features.clone(),
             is_custom_configuration: false,
+            acknowledged_notes: HashMap::new(),
+            notes: String::new(),
+            reminders: Vec::new(),
+            show_experimental: false,
+            favorite_components: Vec::new(),
+            reviewed_component_versions: HashMap::new(),
+            icon: None,
+            config_preset: None,
+            applied_config_preset: None,
+            applied_shader_tier: None,
+            added_servers: Vec::new(),
+            resourcepack_order: Vec::new(),
+            backup_config: BackupConfig::default(),
+            scheduled_update: None,
+            minecraft_version_override: None,
+            source: None,
+            next_update_profile: None,
+            release_channel: crate::channels::ReleaseChannel::default(),
+            installed_channel: crate::channels::ReleaseChannel::default(),
+            custom_game_dir: None,
+            auto_update: false,
         }
     }
 
@@ -227,6 +364,11 @@ impl Installation {
             installation_path,
             enabled_features: vec!["default".to_string()],
             memory_allocation: 3072, // 3GB default
+            auto_memory: false,
+            jvm_preset: None,
+            custom_java_args: String::new(),
+            env_vars: BTreeMap::new(),
+            wrapper_command: None,
             java_args: "-XX:+UseG1GC -XX:+UnlockExperimentalVMOptions -XX:G1NewSizePercent=20 -XX:G1ReservePercent=20 -XX:MaxGCPauseMillis=50 -XX:G1HeapRegionSize=32M".to_string(),
             installed: false,
             modified: false,
@@ -235,6 +377,9 @@ impl Installation {
             universal_version,
             last_launch: None,
             total_launches: 0,
+            total_play_seconds: 0,
+            last_session_seconds: None,
+            last_exit_code: None,
             preset_update_available: false,
             base_preset_id: None,
             base_preset_version: None,
@@ -246,6 +391,27 @@ impl Installation {
             installed_features: Vec::new(),
             pending_features: vec!["default".to_string()],
             is_custom_configuration: true,
+            acknowledged_notes: HashMap::new(),
+            notes: String::new(),
+            reminders: Vec::new(),
+            show_experimental: false,
+            favorite_components: Vec::new(),
+            reviewed_component_versions: HashMap::new(),
+            icon: None,
+            config_preset: None,
+            applied_config_preset: None,
+            applied_shader_tier: None,
+            added_servers: Vec::new(),
+            resourcepack_order: Vec::new(),
+            backup_config: BackupConfig::default(),
+            scheduled_update: None,
+            minecraft_version_override: None,
+            source: None,
+            next_update_profile: None,
+            release_channel: crate::channels::ReleaseChannel::default(),
+            installed_channel: crate::channels::ReleaseChannel::default(),
+            custom_game_dir: None,
+            auto_update: false,
         }
     }
 
@@ -418,8 +584,27 @@ impl Installation {
             });
         }
         
-        // Process each selected path
-        for path in &selected_paths {
+        let mut stored_bytes = None;
+        if compress {
+            let items: Vec<(PathBuf, PathBuf)> = selected_paths.iter()
+                .map(|path| (self.installation_path.join(path), path.clone()))
+                .filter(|(source, _)| source.exists())
+                .collect();
+            let stats = crate::backup_store::write_snapshot(
+                &items,
+                &backup_dir,
+                &crate::backup_store::chunks_dir(&backups_dir),
+                total_files,
+                total_bytes,
+                progress_callback.as_ref(),
+            )?;
+            files_processed = stats.files;
+            bytes_processed = stats.bytes;
+            stored_bytes = Some(stats.stored_bytes);
+        }
+        
+        // Process each selected path (uncompressed backups are plain copies)
+        for path in selected_paths.iter().filter(|_| !compress) {
             let source_path = self.installation_path.join(path);
             let dest_path = backup_dir.join(path);
             
@@ -480,6 +665,7 @@ let config = BackupConfig {
     max_backups: 10,
     include_hidden_files: false,
     exclude_patterns: Vec::new(),
+    ..BackupConfig::default()
 };
 
 let metadata = BackupMetadata {
@@ -493,6 +679,7 @@ let metadata = BackupMetadata {
     size_bytes: bytes_processed,
     included_items,
     config: config.clone(), // <- Clone here instead of moving
+    stored_bytes,
 };
         
         // Save metadata
@@ -502,37 +689,6 @@ let metadata = BackupMetadata {
         fs::write(&metadata_path, metadata_json)
             .map_err(|e| format!("Failed to write metadata: {}", e))?;
         
-        // Optional compression
-        if compress {
-            if let Some(ref callback) = progress_callback {
-                callback(BackupProgress {
-                    current_file: "Compressing backup...".to_string(),
-                    files_processed,
-                    total_files,
-                    bytes_processed,
-                    total_bytes,
-                    current_operation: "Creating compressed archive".to_string(),
-                });
-            }
-            
-            let archive_path = backup_dir.join("backup.zip");
-            let final_size = create_zip_archive(&backup_dir, &archive_path, progress_callback.as_ref())?;
-            
-            // Remove uncompressed files after successful compression
-            for path in &selected_paths {
-                let uncompressed_path = backup_dir.join(path);
-                if uncompressed_path.exists() && uncompressed_path != archive_path {
-                    if uncompressed_path.is_file() {
-                        let _ = fs::remove_file(&uncompressed_path);
-                    } else if uncompressed_path.is_dir() {
-                        let _ = fs::remove_dir_all(&uncompressed_path);
-                    }
-                }
-            }
-            
-            debug!("Compressed backup to {} bytes", final_size);
-        }
-        
         // Clean up old backups
         self.cleanup_old_backups(config.max_backups)?;
         
@@ -640,6 +796,13 @@ let metadata = BackupMetadata {
             .map_err(|e| format!("Failed to create backup directory: {}", e))?;
         
         debug!("Creating backup {} for installation {}", backup_id, self.name);
+        // Safety backups are pruned after their restore (see prune_safety_backups), world backups
+        // per world
+        let world_folder = match backup_type {
+            BackupType::World => config.selected_items.first().and_then(|item| item.strip_prefix("saves/")).map(str::to_string),
+            _ => None,
+        };
+        let prune = !matches!(backup_type, BackupType::Safety | BackupType::World);
         
         // Check if this is a full backup (indicated by "*" in selected_items)
         let is_full_backup = config.selected_items.contains(&"*".to_string());
@@ -674,8 +837,24 @@ let metadata = BackupMetadata {
             });
         }
         
+        // Compressed backups go into the shared chunk store, only new chunks take up space
+        let mut stored_bytes = None;
+        if config.compress_backups {
+            let stats = crate::backup_store::write_snapshot(
+                &items_to_backup,
+                &backup_dir,
+                &crate::backup_store::chunks_dir(&backups_dir),
+                total_files,
+                total_bytes,
+                progress_callback.as_ref(),
+            )?;
+            files_processed = stats.files;
+            bytes_processed = stats.bytes;
+            stored_bytes = Some(stats.stored_bytes);
+        }
+        
         // Copy each item to backup directory
-        for (source_path, relative_path) in &items_to_backup {
+        for (source_path, relative_path) in items_to_backup.iter().filter(|_| !config.compress_backups) {
             let dest_path = backup_dir.join(relative_path);
             
             if source_path.is_file() {
@@ -737,37 +916,9 @@ let metadata = BackupMetadata {
             size_bytes: bytes_processed,
             included_items,
             config: config.clone(),
+            stored_bytes,
         };
         
-        // Compress if requested
-        if config.compress_backups {
-            if let Some(ref callback) = progress_callback {
-                callback(BackupProgress {
-                    current_file: "Compressing backup...".to_string(),
-                    files_processed,
-                    total_files,
-                    bytes_processed,
-                    total_bytes,
-                    current_operation: "Creating compressed archive".to_string(),
-                });
-            }
-            
-            let archive_path = backup_dir.join("backup.zip");
-            let _final_size = create_zip_archive(&backup_dir, &archive_path, progress_callback.as_ref())?;
-            
-            // Remove uncompressed files after successful compression
-            for (_, relative_path) in &items_to_backup {
-                let uncompressed_path = backup_dir.join(relative_path);
-                if uncompressed_path.exists() && uncompressed_path != archive_path {
-                    if uncompressed_path.is_file() {
-                        let _ = fs::remove_file(&uncompressed_path);
-                    } else if uncompressed_path.is_dir() {
-                        let _ = fs::remove_dir_all(&uncompressed_path);
-                    }
-                }
-            }
-        }
-        
         // Save metadata
         let metadata_path = backup_dir.join("metadata.json");
         let metadata_json = serde_json::to_string_pretty(&metadata)
@@ -776,7 +927,11 @@ let metadata = BackupMetadata {
             .map_err(|e| format!("Failed to write metadata: {}", e))?;
         
         // Clean up old backups
-        self.cleanup_old_backups(config.max_backups)?;
+        if prune {
+            self.cleanup_old_backups(config.max_backups)?;
+        } else if let Some(folder) = &world_folder {
+            self.cleanup_world_backups(folder, crate::backup::WORLD_BACKUPS_KEPT)?;
+        }
         
         // Final progress update
         if let Some(ref callback) = progress_callback {
@@ -1038,14 +1193,14 @@ let metadata = BackupMetadata {
         let safety_config = BackupConfig {
             selected_items: vec!["*".to_string()], // Full backup for safety
             compress_backups: true,
-            max_backups: 15, // Keep more safety backups
             include_hidden_files: true,
             exclude_patterns: vec!["backups".to_string()],
+            ..BackupConfig::default()
         };
         let safety_description = format!("Safety backup before restoring {}", backup_id);
         
         let _safety_backup = self.create_backup(
-            BackupType::PreUpdate,
+            BackupType::Safety,
             &safety_config,
             safety_description,
             None::<fn(BackupProgress)>,
@@ -1076,7 +1231,14 @@ let metadata = BackupMetadata {
         // Restore from backup
         let backup_archive = backup_dir.join("backup.zip");
         
-        if backup_archive.exists() {
+        if let Some(snapshot) = crate::backup_store::load_snapshot(&backup_dir)? {
+            // Deduplicated backup, rebuild the files from the chunk store
+            crate::backup_store::restore_snapshot(
+                &snapshot,
+                &crate::backup_store::chunks_dir(&self.get_backups_dir()),
+                &self.installation_path,
+            )?;
+        } else if backup_archive.exists() {
             // Restore from ZIP archive
             crate::backup::extract_zip_archive(&backup_archive, &self.installation_path)
                 .map_err(|e| format!("Failed to extract backup archive: {}", e))?;
@@ -1098,6 +1260,8 @@ let metadata = BackupMetadata {
                         }
                         
                         if source_path.is_file() {
+                            crate::content_store::detach(&target_path)
+                                .map_err(|e| format!("Failed to replace {}: {}", target_path.display(), e))?;
                             std::fs::copy(&source_path, &target_path)
                                 .map_err(|e| format!("Failed to restore file: {}", e))?;
                         } else if source_path.is_dir() {
@@ -1109,6 +1273,8 @@ let metadata = BackupMetadata {
             }
         }
         
+        self.prune_safety_backups();
+        
         // Update installation state
         self.enabled_features = metadata.enabled_features.clone();
         self.universal_version = metadata.modpack_version.clone();
@@ -1119,6 +1285,140 @@ let metadata = BackupMetadata {
         Ok(())
     }
     
+    /// List the files stored in a backup, for the backup browser
+    pub fn list_backup_files(&self, backup_id: &str) -> Result<Vec<BackupFileEntry>, String> {
+        crate::backup::list_backup_files(&self.get_backups_dir().join(backup_id))
+    }
+    
+    /// Read the first `limit` bytes of a file inside a backup
+    pub fn read_backup_file(&self, backup_id: &str, path: &str, limit: usize) -> Result<Vec<u8>, String> {
+        let backups_dir = self.get_backups_dir();
+        crate::backup::read_backup_file(
+            &backups_dir.join(backup_id),
+            &crate::backup_store::chunks_dir(&backups_dir),
+            path,
+            limit,
+        )
+    }
+    
+    /// Restore only the selected files/folders from a backup, returns how many files were written
+    pub async fn restore_backup_files(&self, backup_id: &str, selections: &[String]) -> Result<usize, String> {
+        let backups_dir = self.get_backups_dir();
+        let backup_dir = backups_dir.join(backup_id);
+        if !backup_dir.exists() {
+            return Err(format!("Backup {} not found", backup_id));
+        }
+        
+        // Safety backup of whatever is about to be overwritten
+        let existing: Vec<String> = selections.iter()
+            .filter(|selection| self.installation_path.join(selection.as_str()).exists())
+            .cloned()
+            .collect();
+        if !existing.is_empty() {
+            let safety_config = BackupConfig {
+                selected_items: existing,
+                compress_backups: true,
+                include_hidden_files: true,
+                exclude_patterns: Vec::new(),
+                ..BackupConfig::default()
+            };
+            self.create_backup(
+                BackupType::Safety,
+                &safety_config,
+                format!("Safety backup before restoring files from {}", backup_id),
+                None::<fn(BackupProgress)>,
+            ).await?;
+        }
+        
+        let restored = crate::backup::restore_backup_paths(
+            &backup_dir,
+            &crate::backup_store::chunks_dir(&backups_dir),
+            selections,
+            &self.installation_path,
+        )?;
+        self.prune_safety_backups();
+        Ok(restored)
+    }
+    
+    /// Singleplayer worlds of this installation, for the worlds section
+    pub fn list_worlds(&self) -> Result<Vec<WorldInfo>, String> {
+        crate::backup::list_worlds(&self.installation_path)
+    }
+    
+    // A backup with just the world's folder in it, BackupMetadata::world finds these again
+    fn world_backup_config(&self, folder: &str) -> BackupConfig {
+        BackupConfig {
+            selected_items: vec![crate::backup::world_backup_path(folder)],
+            compress_backups: true,
+            include_hidden_files: true,
+            exclude_patterns: Vec::new(),
+            ..self.backup_config.clone()
+        }
+    }
+    
+    /// Backs up one world
+    pub async fn backup_world(&self, folder: &str, description: String) -> Result<BackupMetadata, String> {
+        if !self.installation_path.join("saves").join(folder).join("level.dat").exists() {
+            return Err(format!("World '{}' not found", folder));
+        }
+        info!("Backing up world {} of {}", folder, self.name);
+        self.create_backup(
+            BackupType::World,
+            &self.world_backup_config(folder),
+            description,
+            None::<fn(BackupProgress)>,
+        ).await
+    }
+    
+    /// Backups holding exactly this world, newest first
+    pub fn world_backups(&self, folder: &str) -> Result<Vec<BackupMetadata>, String> {
+        Ok(self.list_available_backups()?
+            .into_iter()
+            .filter(|backup| backup.world() == Some(folder))
+            .collect())
+    }
+    
+    /// Puts a world back the way `backup_id` has it. Unlike restore_backup_files the world folder
+    /// is emptied first, region files the game wrote after the backup would otherwise stay mixed in.
+    pub async fn restore_world(&self, backup_id: &str, folder: &str) -> Result<usize, String> {
+        let backups_dir = self.get_backups_dir();
+        let backup_dir = backups_dir.join(backup_id);
+        if !backup_dir.exists() {
+            return Err(format!("Backup {} not found", backup_id));
+        }
+        
+        let world_dir = self.installation_path.join("saves").join(folder);
+        if world_dir.exists() {
+            self.create_backup(
+                BackupType::Safety,
+                &self.world_backup_config(folder),
+                format!("Safety backup before restoring world {}", folder),
+                None::<fn(BackupProgress)>,
+            ).await?;
+            // The world is only cleared while the backup to restore is certainly still there
+            if !backup_dir.join("metadata.json").exists() {
+                return Err(format!("Backup {} is gone, world {} was left as it was", backup_id, folder));
+            }
+            fs::remove_dir_all(&world_dir)
+                .map_err(|e| format!("Failed to clear world {}: {}", folder, e))?;
+        }
+        
+        let restored = crate::backup::restore_backup_paths(
+            &backup_dir,
+            &crate::backup_store::chunks_dir(&backups_dir),
+            &[crate::backup::world_backup_path(folder)],
+            &self.installation_path,
+        )?;
+        self.prune_safety_backups();
+        info!("Restored world {} of {} from backup {} ({} files)", folder, self.name, backup_id, restored);
+        Ok(restored)
+    }
+    
+    /// Writes a world to a zip that can be shared or unpacked into another saves folder
+    pub fn export_world(&self, folder: &str, zip_path: &Path) -> Result<u64, String> {
+        crate::backup::export_world_zip(&self.installation_path.join("saves").join(folder), zip_path)
+    }
+    
     /// Clear installation directory except for backups
     fn clear_installation_except_backups(&self) -> Result<(), String> {
         let entries = fs::read_dir(&self.installation_path)
@@ -1168,6 +1468,8 @@ let metadata = BackupMetadata {
                     fs::create_dir_all(parent)
                         .map_err(|e| format!("Failed to create directory: {}", e))?;
                 }
+                crate::content_store::detach(&dest_path)
+                    .map_err(|e| format!("Failed to replace {}: {}", dest_path.display(), e))?;
                 fs::copy(&source_path, &dest_path)
                     .map_err(|e| format!("Failed to copy file: {}", e))?;
             } else if source_path.is_dir() {
@@ -1179,9 +1481,12 @@ let metadata = BackupMetadata {
         Ok(())
     }
 
-    /// Cleanup old backups
+    /// Cleanup old backups. Safety and world backups don't count, they're pruned on their own
     pub fn cleanup_old_backups(&self, max_backups: usize) -> Result<(), String> {
-        let mut backups = self.list_available_backups()?;
+        let mut backups: Vec<BackupMetadata> = self.list_available_backups()?
+            .into_iter()
+            .filter(|b| !matches!(b.backup_type, BackupType::Safety | BackupType::World))
+            .collect();
         
         if backups.len() <= max_backups {
             return Ok(());
@@ -1201,9 +1506,110 @@ let metadata = BackupMetadata {
             }
         }
         
+        if let Err(e) = crate::backup_store::collect_garbage(&self.get_backups_dir()) {
+            warn!("Failed to clean up backup chunks: {}", e);
+        }
+        
+        Ok(())
+    }
+
+    // Called once a restore has read its backup, the safety backup made for it may push out the
+    // oldest ones now
+    fn prune_safety_backups(&self) {
+        if let Err(e) = self.cleanup_backups_of_type(BackupType::Safety, crate::backup::SAFETY_BACKUPS_KEPT) {
+            warn!("Failed to clean up old safety backups: {}", e);
+        }
+    }
+
+    // Keeps the newest `keep` world backups of one world
+    fn cleanup_world_backups(&self, folder: &str, keep: usize) -> Result<(), String> {
+        let mut backups: Vec<BackupMetadata> = self.world_backups(folder)?
+            .into_iter()
+            .filter(|b| b.backup_type == BackupType::World)
+            .collect();
+        if backups.len() <= keep {
+            return Ok(());
+        }
+        backups.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        let to_remove = backups.len() - keep;
+        for backup in backups.iter().take(to_remove) {
+            let backup_dir = self.get_backups_dir().join(&backup.id);
+            if backup_dir.exists() {
+                std::fs::remove_dir_all(&backup_dir)
+                    .map_err(|e| format!("Failed to cleanup old backup: {}", e))?;
+                debug!("Cleaned up old backup of world {}: {}", folder, backup.id);
+            }
+        }
+        if let Err(e) = crate::backup_store::collect_garbage(&self.get_backups_dir()) {
+            warn!("Failed to clean up backup chunks: {}", e);
+        }
+        Ok(())
+    }
+
+    // Like cleanup_old_backups but only counts (and removes) backups of one type
+    fn cleanup_backups_of_type(&self, backup_type: BackupType, keep: usize) -> Result<(), String> {
+        let mut backups: Vec<BackupMetadata> = self.list_available_backups()?
+            .into_iter()
+            .filter(|b| b.backup_type == backup_type)
+            .collect();
+        if backups.len() <= keep {
+            return Ok(());
+        }
+        backups.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        let to_remove = backups.len() - keep;
+        for backup in backups.iter().take(to_remove) {
+            let backup_dir = self.get_backups_dir().join(&backup.id);
+            if backup_dir.exists() {
+                std::fs::remove_dir_all(&backup_dir)
+                    .map_err(|e| format!("Failed to cleanup old backup: {}", e))?;
+                debug!("Cleaned up old {:?} backup: {}", backup_type, backup.id);
+            }
+        }
+        if let Err(e) = crate::backup_store::collect_garbage(&self.get_backups_dir()) {
+            warn!("Failed to clean up backup chunks: {}", e);
+        }
         Ok(())
     }
 
+    /// Backs up the configured auto backup items before an update touches them, if enabled.
+    pub async fn auto_backup_before_update(&self) -> Result<Option<BackupMetadata>, String> {
+        let config = &self.backup_config;
+        if !config.auto_backup {
+            return Ok(None);
+        }
+        let items: Vec<String> = config.auto_backup_items
+            .iter()
+            .filter(|item| self.installation_path.join(item).exists())
+            .cloned()
+            .collect();
+        if items.is_empty() {
+            debug!("Nothing to back up before updating {}", self.name);
+            return Ok(None);
+        }
+
+        info!("Backing up {:?} of {} before updating", items, self.name);
+        let backup_config = BackupConfig {
+            selected_items: items,
+            ..config.clone()
+        };
+        let result = self.create_backup(
+            BackupType::Scheduled,
+            &backup_config,
+            format!("Automatic backup before updating from {}", self.universal_version),
+            Some(crate::backup::report_auto_backup_progress),
+        ).await;
+        crate::backup::clear_auto_backup_progress();
+        let metadata = result?;
+        self.cleanup_backups_of_type(BackupType::Scheduled, config.auto_backup_retention.max(1))?;
+        Ok(Some(metadata))
+    }
+
+    pub fn set_backup_config(&mut self, config: BackupConfig) -> Result<(), String> {
+        debug!("Updating backup settings of installation {}", self.id);
+        self.backup_config = config;
+        self.save()
+    }
+
     pub fn save(&self) -> Result<(), String> {
         let installation_dir = get_installations_dir().join(&self.id);
         
@@ -1218,7 +1624,9 @@ let metadata = BackupMetadata {
             .map_err(|e| format!("Failed to serialize installation: {}", e))?;
         
         std::fs::write(config_path, config_json)
-            .map_err(|e| format!("Failed to write installation config: {}", e))
+            .map_err(|e| format!("Failed to write installation config: {}", e))?;
+        crate::install_location::remember(self);
+        Ok(())
     }
 
     pub fn mark_as_fresh(&mut self) {
@@ -1256,6 +1664,8 @@ let metadata = BackupMetadata {
         self.enabled_features = self.pending_features.clone();
         self.pending_features.clear();
         self.installed = true;
+        self.installed_channel = self.release_channel;
+        crate::health::forget(&self.id);
         self.modified = false;
         
         self.save().unwrap_or_else(|e| {
@@ -1289,18 +1699,120 @@ let metadata = BackupMetadata {
         }
     }
 
+    // Where the last install wrote its local manifest.json
+    fn local_manifest_path(&self) -> Option<PathBuf> {
+        match crate::get_launcher(&self.launcher_type).ok()? {
+            Launcher::Vanilla(_) => Some(self.installation_path.join("manifest.json")),
+            Launcher::MultiMC(root) => Some(root.join("instances").join(&self.id).join(".minecraft/manifest.json")),
+        }
+    }
+
+    /// What updating to `universal_manifest` would add, update and remove, None if it was never installed.
+    pub fn pending_changes(&self, universal_manifest: &crate::universal::UniversalManifest) -> Option<crate::manifest_diff::ChangeSet> {
+        if !self.installed {
+            return None;
+        }
+        let path = self.local_manifest_path()?;
+        let local: crate::Manifest = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(local) => local,
+            Err(e) => {
+                warn!("Can't compare {} with the remote manifest: {}", path.display(), e);
+                return None;
+            }
+        };
+        let universal_manifest = self.resolve_manifest(universal_manifest).ok()?;
+        let remote = crate::universal::universal_to_manifest(&universal_manifest, self.enabled_features.clone());
+        Some(crate::manifest_diff::diff(&local, &remote))
+    }
+
+    /// The files installing `universal_manifest` with `enabled_features` would add, replace and delete.
+    pub fn preview_file_changes(
+        &self,
+        universal_manifest: &crate::universal::UniversalManifest,
+        enabled_features: Vec<String>,
+    ) -> Result<Vec<crate::manifest_diff::FileChange>, String> {
+        let root = crate::get_modpack_root(&crate::get_launcher(&self.launcher_type)?, &self.id);
+        let local: Option<crate::Manifest> = if self.installed {
+            let path = root.join("manifest.json");
+            let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            Some(serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?)
+        } else {
+            None
+        };
+        let universal_manifest = self.resolve_manifest(universal_manifest)?;
+        let remote = crate::universal::universal_to_manifest(&universal_manifest, enabled_features);
+        Ok(crate::manifest_diff::file_changes(local.as_ref(), &remote, &root))
+    }
+
+    /// Component id -> version that is actually installed, read from the local manifest.
+    pub fn installed_component_versions(&self) -> HashMap<String, String> {
+        let mut versions = HashMap::new();
+        if !self.installed {
+            return versions;
+        }
+        let Some(path) = self.local_manifest_path() else {
+            return versions;
+        };
+        let manifest: serde_json::Value = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                debug!("No installed versions for {}, can't read {}: {}", self.name, path.display(), e);
+                return versions;
+            }
+        };
+
+        for section in ["mods", "shaderpacks", "resourcepacks", "remote_include"] {
+            let Some(items) = manifest.get(section).and_then(|v| v.as_array()) else {
+                continue;
+            };
+            for item in items {
+                let id = item.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+                let version = item.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+                // "default" is shared by every always-installed component, it doesn't identify one
+                if !id.is_empty() && id != "default" && !version.is_empty() {
+                    versions.insert(id.to_string(), version.to_string());
+                }
+            }
+        }
+        versions
+    }
+
     pub async fn install_or_update_with_progress<F: FnMut() + Clone>(
         &self, 
         http_client: &CachedHttpClient,
         progress_callback: F
     ) -> Result<(), String> {
         // Get the universal manifest
-        let universal_manifest = crate::universal::load_universal_manifest(http_client, None).await
+        let universal_manifest = self.load_universal_manifest(http_client).await
             .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
         
-        // Convert universal manifest to regular manifest with our enabled features
+        let branch = self.pack_source().branch();
+        self.install_or_update_with_manifest(http_client, &universal_manifest, &branch, progress_callback).await
+    }
+
+    // Same as install_or_update_with_progress but with an already loaded manifest,
+    // used by watch mode to install from a branch other than the source's own
+    pub async fn install_or_update_with_manifest<F: FnMut() + Clone>(
+        &self,
+        http_client: &CachedHttpClient,
+        universal_manifest: &crate::universal::UniversalManifest,
+        branch: &str,
+        progress_callback: F
+    ) -> Result<(), String> {
+        // Held until the install is done, a second one of the same installation fails here
+        let _lock = crate::instance_lock::lock_installation(self)?;
+
+        // Convert universal manifest to regular manifest with our enabled features, for the
+        // Minecraft version this installation picked
+        let resolved_manifest = self.resolve_manifest(universal_manifest)?;
         let mut manifest = crate::universal::universal_to_manifest(
-            &universal_manifest, 
+            &resolved_manifest, 
             self.enabled_features.clone()
         );
         
@@ -1310,14 +1822,15 @@ let metadata = BackupMetadata {
         
         // Create launcher
         let launcher = crate::get_launcher(&self.launcher_type)?;
+        let (modpack_source, _) = self.pack_source().profile_location();
         
         let installer_profile = crate::InstallerProfile {
             manifest,
             http_client: http_client.clone(),
             installed: self.installed,
             update_available: self.update_available,
-            modpack_source: "Wynncraft-Overhaul/majestic-overhaul/".to_string(),
-            modpack_branch: "master".to_string(),
+            modpack_source,
+            modpack_branch: branch.to_string(),
             enabled_features: self.enabled_features.clone(),
             launcher: Some(launcher),
             local_manifest: None,
@@ -1330,13 +1843,15 @@ let metadata = BackupMetadata {
         } else {
             crate::update(&installer_profile, progress_callback).await?;
         }
+
+        crate::feature_usage::report_installed_features(universal_manifest, &self.enabled_features);
         
         Ok(())
     }
 
     pub async fn check_for_updates(&mut self, http_client: &CachedHttpClient, presets: &[Preset]) -> Result<bool, String> {
         // Check modpack updates using semantic version comparison
-        let universal_manifest = crate::universal::load_universal_manifest(http_client, None).await
+        let universal_manifest = self.load_universal_manifest(http_client).await
             .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
         
         // Use the compare_versions function for modpack version
@@ -1345,6 +1860,11 @@ let metadata = BackupMetadata {
                 debug!("Modpack update available: {} -> {}", self.universal_version, universal_manifest.modpack_version);
                 true
             },
+            // Going back from beta to stable can mean an older version, it's still what was asked for
+            _ if self.installed && self.installed_channel != self.release_channel => {
+                debug!("Switched to the {} channel, {} gets installed", self.release_channel.id(), universal_manifest.modpack_version);
+                true
+            },
             _ => false
         };
         
@@ -1416,8 +1936,194 @@ let metadata = BackupMetadata {
         self.base_preset_version = preset.preset_version.clone();
     }
 
+    // True if the note changed or was never confirmed for this installation
+    pub fn needs_acknowledgement(&self, component_id: &str, note: &str) -> bool {
+        self.acknowledged_notes.get(component_id).map_or(true, |acked| acked != note)
+    }
+
+    pub fn acknowledge_note(&mut self, component_id: &str, note: &str) -> Result<(), String> {
+        debug!("Recording acknowledgement for component {}", component_id);
+        self.acknowledged_notes.insert(component_id.to_string(), note.to_string());
+        self.save()
+    }
+
+    /// `program` with this installation's environment variables, started through its wrapper
+    /// command when it has one.
+    pub fn game_command(&self, program: &Path) -> Command {
+        let mut wrapper = self.wrapper_command.as_deref().unwrap_or_default().split_whitespace();
+        let mut command = match wrapper.next() {
+            Some(wrapper_program) => {
+                let mut command = Command::new(wrapper_program);
+                command.args(wrapper).arg(program);
+                command
+            }
+            None => Command::new(program),
+        };
+        command.envs(&self.env_vars);
+        command
+    }
+
+    // MultiMC and Prism start the game themselves. The wrapper goes into the instance's
+    // WrapperCommand, with the variables in front through `env` so they also apply when the
+    // instance is started from the launcher. Windows has no `env`, there the variables only
+    // reach the game when it's launched from here
+    pub fn write_instance_wrapper(&self) -> Result<(), String> {
+        let Ok(Launcher::MultiMC(root)) = crate::get_launcher(&self.launcher_type) else {
+            return Ok(());
+        };
+        let cfg_path = root.join("instances").join(&self.id).join("instance.cfg");
+        let content = fs::read_to_string(&cfg_path)
+            .map_err(|e| format!("Failed to read {}: {}", cfg_path.display(), e))?;
+
+        let mut wrapper: Vec<String> = Vec::new();
+        if !self.env_vars.is_empty() && !cfg!(windows) {
+            wrapper.push("env".to_string());
+            for (name, value) in &self.env_vars {
+                if value.contains(char::is_whitespace) {
+                    wrapper.push(format!("\"{}={}\"", name, value));
+                } else {
+                    wrapper.push(format!("{}={}", name, value));
+                }
+            }
+        }
+        wrapper.extend(self.wrapper_command.iter().flat_map(|w| w.split_whitespace().map(str::to_string)));
+
+        let mut lines: Vec<String> = content
+            .lines()
+            .filter(|line| !line.starts_with("OverrideCommands=") && !line.starts_with("WrapperCommand="))
+            .map(str::to_string)
+            .collect();
+        if !wrapper.is_empty() {
+            lines.push("OverrideCommands=true".to_string());
+            lines.push(format!("WrapperCommand={}", wrapper.join(" ")));
+        }
+        fs::write(&cfg_path, lines.join("\n") + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", cfg_path.display(), e))?;
+        debug!("Instance wrapper for {}: {:?}", self.id, wrapper);
+        Ok(())
+    }
+
+    pub fn toggle_favorite(&mut self, component_id: &str) -> Result<(), String> {
+        if let Some(position) = self.favorite_components.iter().position(|id| id == component_id) {
+            self.favorite_components.remove(position);
+        } else {
+            self.favorite_components.push(component_id.to_string());
+        }
+        self.save()
+    }
+
+    /// Components whose installed version isn't the one the user last reviewed.
+    pub fn changed_components(&self, installed: &HashMap<String, String>) -> HashSet<String> {
+        installed
+            .iter()
+            .filter(|(id, version)| self.reviewed_component_versions.get(*id).map_or(false, |reviewed| reviewed != *version))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    // Components seen for the first time count as reviewed, only versions that change after
+    // that get flagged
+    pub fn remember_component_versions(&mut self, installed: &HashMap<String, String>) -> Result<(), String> {
+        let mut added = false;
+        for (id, version) in installed {
+            if !self.reviewed_component_versions.contains_key(id) {
+                self.reviewed_component_versions.insert(id.clone(), version.clone());
+                added = true;
+            }
+        }
+        if added { self.save() } else { Ok(()) }
+    }
+
+    pub fn mark_components_reviewed(&mut self, installed: &HashMap<String, String>, ids: &[String]) -> Result<(), String> {
+        for id in ids {
+            if let Some(version) = installed.get(id) {
+                self.reviewed_component_versions.insert(id.clone(), version.clone());
+            }
+        }
+        self.save()
+    }
+
+    pub fn set_show_experimental(&mut self, show: bool) -> Result<(), String> {
+        info!("Experimental features {} for installation {}", if show { "enabled" } else { "disabled" }, self.id);
+        self.show_experimental = show;
+        tag_experimental_context(self);
+        self.save()
+    }
+
+    pub fn set_icon(&mut self, icon: Option<crate::icons::InstallationIcon>) -> Result<(), String> {
+        debug!("Setting icon of installation {} to {:?}", self.id, icon);
+        self.icon = icon;
+        self.save()
+    }
+
+    /// The universal manifest as this installation installs it, for its Minecraft version.
+    pub fn pack_source(&self) -> crate::sources::PackSource {
+        self.release_channel.apply(self.source.clone().unwrap_or_default())
+    }
+
+    /// Follows another release channel from now on, the next update installs its version.
+    pub fn set_release_channel(&mut self, channel: crate::channels::ReleaseChannel) -> Result<(), String> {
+        if channel == self.release_channel {
+            return Ok(());
+        }
+        info!("Switching installation {} to the {} channel", self.id, channel.id());
+        self.release_channel = channel;
+        if self.installed {
+            self.update_available = channel != self.installed_channel;
+        }
+        self.save()
+    }
+
+    /// The universal manifest of the source this installation was created from.
+    pub async fn load_universal_manifest(
+        &self,
+        http_client: &CachedHttpClient,
+    ) -> Result<crate::universal::UniversalManifest, crate::universal::ManifestError> {
+        crate::universal::load_universal_manifest(http_client, Some(&self.pack_source().universal_url())).await
+    }
+
+    pub async fn load_presets(&self, http_client: &CachedHttpClient) -> Result<Vec<Preset>, crate::universal::ManifestError> {
+        crate::preset::load_presets(http_client, Some(&self.pack_source().presets_url())).await
+    }
+
+    pub fn resolve_manifest(&self, universal_manifest: &crate::universal::UniversalManifest) -> Result<crate::universal::UniversalManifest, String> {
+        match &self.minecraft_version_override {
+            Some(version) => universal_manifest.for_minecraft_version(version),
+            None => Ok(universal_manifest.clone()),
+        }
+    }
+
+    /// Switches to another Minecraft version of the pack, the next update installs it. Returns the
+    /// enabled components that have no build for it and won't be installed.
+    pub fn set_minecraft_version(
+        &mut self,
+        universal_manifest: &crate::universal::UniversalManifest,
+        version: &str,
+    ) -> Result<Vec<String>, String> {
+        let resolved = universal_manifest.for_minecraft_version(version)?;
+        debug!("Switching installation {} to Minecraft {}", self.id, version);
+        self.minecraft_version_override = if version == universal_manifest.minecraft_version {
+            None
+        } else {
+            Some(version.to_string())
+        };
+        self.minecraft_version = resolved.minecraft_version.clone();
+        self.loader_version = resolved.loader.version.clone();
+        if self.installed {
+            self.update_available = true;
+        }
+        self.save()?;
+        Ok(universal_manifest.incompatible_components(version, &self.enabled_features))
+    }
+
+    /// Writes the setup (features, preset, memory and java args) to a .wcpack file others can import.
+    pub fn export(&self, path: &Path) -> Result<(), String> {
+        crate::share::write_pack_file(&crate::share::SharedInstallation::from_installation(self), path)
+    }
+
     pub fn mark_installed(&mut self) -> Result<(), String> {
         self.installed = true;
+        self.installed_channel = self.release_channel;
         self.update_available = false;
         self.modified = false;
         self.last_used = chrono::Utc::now();
@@ -1437,15 +2143,24 @@ let metadata = BackupMetadata {
         // Save the updated installation data
         self.save()
     }
+
+    // Called when the launched process exits, None for the code means it was killed
+    pub fn record_session(&mut self, exit_code: Option<i32>, seconds: u64) -> Result<(), String> {
+        self.total_play_seconds += seconds;
+        self.last_session_seconds = Some(seconds);
+        self.last_exit_code = exit_code;
+        self.save()
+    }
     
     // Update the installation after successful install/update
     pub async fn complete_installation(&mut self, http_client: &CachedHttpClient) -> Result<(), String> {
         // Load latest manifest to get current version
-        let universal_manifest = crate::universal::load_universal_manifest(http_client, None).await
+        let universal_manifest = self.load_universal_manifest(http_client).await
             .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
         
         // Update installation state
         self.installed = true;
+        self.installed_channel = self.release_channel;
         self.update_available = false;
         self.modified = false;
         self.universal_version = universal_manifest.modpack_version;
@@ -1504,6 +2219,7 @@ let metadata = BackupMetadata {
         
         if let Some(java_args) = &preset.recommended_java_args {
             self.java_args = java_args.clone();
+            self.jvm_preset = None;
         }
         
         debug!("Applied preset - features changed from {:?} to {:?}", previous_features, self.enabled_features);
@@ -1538,6 +2254,14 @@ let metadata = BackupMetadata {
         debug!("Preset tracking cleared: base_preset_id = None");
     }
 
+    // Replaces the selection with a saved feature profile, profiles aren't tied to a preset
+    pub fn apply_feature_profile(&mut self, features: Vec<String>) {
+        debug!("Applying feature profile to installation '{}': {:?}", self.name, features);
+        self.switch_to_custom_with_tracking();
+        self.enabled_features = features.clone();
+        self.pending_features = features;
+    }
+
     // Method to track individual feature changes
     pub fn toggle_feature_with_tracking(&mut self, feature_id: &str, enable: bool, presets: &[crate::preset::Preset]) {
         debug!("Toggling feature '{}' to {} for installation '{}'", feature_id, enable, self.name);
@@ -1592,7 +2316,7 @@ let metadata = BackupMetadata {
     pub async fn initialize_with_universal_defaults(&mut self, http_client: &CachedHttpClient) -> Result<(), String> {
         debug!("Initializing installation '{}' with universal defaults", self.name);
         
-        let universal_manifest = crate::universal::load_universal_manifest(http_client, None).await
+        let universal_manifest = self.load_universal_manifest(http_client).await
             .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
         
         let mut features = vec!["default".to_string()];
@@ -1691,7 +2415,7 @@ let metadata = BackupMetadata {
         debug!("Completing installation for '{}' while preserving user choices", self.name);
         
         // Load latest manifest to get current version
-        let universal_manifest = crate::universal::load_universal_manifest(http_client, None).await
+        let universal_manifest = self.load_universal_manifest(http_client).await
             .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
         
         // Preserve user's enabled features - this is critical
@@ -1703,6 +2427,7 @@ let metadata = BackupMetadata {
         
         // Update installation state
         self.installed = true;
+        self.installed_channel = self.release_channel;
         self.update_available = false;
         self.modified = false;
         self.universal_version = universal_manifest.modpack_version;
@@ -1728,7 +2453,7 @@ let metadata = BackupMetadata {
 
     // Method to initialize enabled features based on universal manifest
     pub async fn initialize_default_features(&mut self, http_client: &CachedHttpClient) -> Result<(), String> {
-        let universal_manifest = crate::universal::load_universal_manifest(http_client, None).await
+        let universal_manifest = self.load_universal_manifest(http_client).await
             .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
         
         let mut features = vec!["default".to_string()];
@@ -1943,6 +2668,10 @@ let metadata = BackupMetadata {
         std::fs::remove_dir_all(&backup_dir)
             .map_err(|e| format!("Failed to delete backup directory: {}", e))?;
         
+        if let Err(e) = crate::backup_store::collect_garbage(&self.get_backups_dir()) {
+            warn!("Failed to clean up backup chunks: {}", e);
+        }
+        
         if let Some(info) = backup_info {
             debug!("Successfully deleted backup: {} - {}", backup_id, info);
         } else {
@@ -2056,6 +2785,7 @@ let metadata = BackupMetadata {
                 size_bytes,
                 included_items,
                 config,
+                stored_bytes: None,
             })
         } else {
             Err("Failed to parse old backup metadata format".to_string())
@@ -2141,7 +2871,10 @@ fn copy_dir_all(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result
         if ty.is_dir() {
             copy_dir_all(&entry.path(), &dst.join(entry.file_name()))?;
         } else {
-            std::fs::copy(entry.path(), dst.join(entry.file_name()))?;
+            // Restores land here, never write through a link into the content store
+            let target = dst.join(entry.file_name());
+            crate::content_store::detach(&target)?;
+            std::fs::copy(entry.path(), target)?;
         }
     }
     Ok(())
@@ -2197,6 +2930,16 @@ pub fn save_installations_index(index: &InstallationsIndex) -> Result<(), std::i
     std::fs::write(index_path, index_json)
 }
 
+// Crash reports say whether experimental components were shown, so maintainers know when a
+// report may come from something that isn't supported yet
+pub fn tag_experimental_context(installation: &Installation) {
+    if installation.show_experimental {
+        crate::crash_report::set_crash_context("experimental_features", "enabled");
+    } else {
+        crate::crash_report::clear_crash_context("experimental_features");
+    }
+}
+
 // Load an installation by ID
 pub fn load_installation(id: &str) -> Result<Installation, String> {
     let installation_dir = get_installations_dir().join(id);
@@ -2226,6 +2969,11 @@ pub fn load_installation(id: &str) -> Result<Installation, String> {
         installation.installed_features = installation.enabled_features.clone();
     }
 
+    // The game dir is wherever the user put it, whatever path was saved before
+    if let Some(dir) = &installation.custom_game_dir {
+        installation.installation_path = dir.clone();
+    }
+
 
     Ok(installation)
 }
@@ -2258,6 +3006,9 @@ pub fn delete_installation(id: &str) -> Result<(), String> {
     save_installations_index(&index)
         .map_err(|e| format!("Failed to save installations index: {}", e))?;
     
+    // Game files outside the installations folder go first, installation.json is what finds them
+    crate::install_location::remove(&installation)?;
+
     // Delete installation directory
     let installation_dir = get_installations_dir().join(id);
     if installation_dir.exists() {
@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use isahc::http::StatusCode;
+use isahc::AsyncReadResponseExt;
+use log::{debug, info, warn};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use crate::hashing::{self, HashAlgorithm};
+use crate::installation::{self, Installation};
+use crate::universal::{ModComponent, UniversalManifest};
+use crate::{get_launcher, get_minecraft_folder, CachedHttpClient, Launcher};
+
+// Modded setups made by hand or by other tools, found through launcher_profiles.json gameDir
+// entries and MultiMC/Prism instances, can be adopted as installations. Their mods are matched
+// against the pack (Modrinth hashes first, file names for the rest) and the matching features are
+// turned on, so the first install keeps what the player already had.
+// MultiMC instances are adopted in place, the instance folder name becomes the installation id.
+// Their matched jars are moved aside, the pack brings its own versions.
+// Vanilla launcher folders are copied into a new installation, the original profile stays as it is.
+// Profiles already pointing into our installations folder are profile_reconcile's business.
+
+const MODRINTH_VERSION_FILES: &str = "https://api.modrinth.com/v2/version_files";
+const MODRINTH_PROJECTS: &str = "https://api.modrinth.com/v2/projects";
+// Matched jars of adopted MultiMC instances are moved here instead of deleted
+const REPLACED_DIR: &str = "replaced-by-import";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandidateSource {
+    // Key in launcher_profiles.json
+    VanillaProfile(String),
+    // Launcher as stored in the config, instance folder name
+    Instance { launcher: String, folder: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub source: CandidateSource,
+    pub name: String,
+    pub game_dir: PathBuf,
+    pub minecraft_version: Option<String>,
+    // Loader type and version
+    pub loader: Option<(String, String)>,
+    pub mod_count: usize,
+}
+
+impl Candidate {
+    pub fn launcher_label(&self) -> &'static str {
+        match &self.source {
+            CandidateSource::VanillaProfile(_) => "Minecraft Launcher",
+            CandidateSource::Instance { launcher, .. } if launcher.ends_with("PrismLauncher") => "Prism Launcher",
+            CandidateSource::Instance { .. } => "MultiMC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportSummary {
+    // Names of the pack components found in the instance
+    pub matched: Vec<String>,
+    // Jars the pack doesn't have, kept as they are
+    pub unmatched: Vec<String>,
+}
+
+fn jars_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    let mut jars: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().map_or(false, |ext| ext == "jar"))
+        .collect();
+    jars.sort();
+    jars
+}
+
+// "fabric-loader-0.15.11-1.20.4", "1.20.1-forge-47.2.0", "neoforge-20.4.237", "1.20.4"
+fn parse_version_id(id: &str) -> (Option<String>, Option<(String, String)>) {
+    if id.starts_with("latest-") {
+        return (None, None);
+    }
+    for loader in ["fabric", "quilt"] {
+        if let Some(rest) = id.strip_prefix(&format!("{}-loader-", loader)) {
+            return match rest.rsplit_once('-') {
+                Some((loader_version, minecraft)) => (Some(minecraft.to_string()), Some((loader.to_string(), loader_version.to_string()))),
+                None => (None, Some((loader.to_string(), rest.to_string()))),
+            };
+        }
+    }
+    if let Some(version) = id.strip_prefix("neoforge-") {
+        // NeoForge numbers follow the game, 20.4.x is for 1.20.4 and 21.0.x for 1.21
+        let minecraft = match version.split('.').collect::<Vec<_>>()[..] {
+            [major, "0", ..] => Some(format!("1.{}", major)),
+            [major, minor, ..] => Some(format!("1.{}.{}", major, minor)),
+            _ => None,
+        };
+        return (minecraft, Some(("neoforge".to_string(), version.to_string())));
+    }
+    if let Some((minecraft, forge)) = id.split_once("-forge") {
+        let version = forge.rsplit('-').next().unwrap_or(forge).to_string();
+        return (Some(minecraft.to_string()), Some(("forge".to_string(), version)));
+    }
+    (Some(id.to_string()), None)
+}
+
+fn scan_profiles(managed_dirs: &[PathBuf]) -> Vec<Candidate> {
+    let path = get_minecraft_folder().join("launcher_profiles.json");
+    let Some(profiles) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<JsonValue>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let installer_dir = crate::get_installer_dir();
+    let mut candidates = Vec::new();
+    for (key, profile) in profiles["profiles"].as_object().into_iter().flatten() {
+        // Without a gameDir it's .minecraft itself, shared with every other profile
+        let Some(game_dir) = profile["gameDir"].as_str().map(PathBuf::from) else { continue };
+        if game_dir.starts_with(&installer_dir) || managed_dirs.contains(&game_dir) || !game_dir.is_dir() {
+            continue;
+        }
+        let mod_count = jars_in(&game_dir.join("mods")).len();
+        if mod_count == 0 {
+            continue;
+        }
+        let (minecraft_version, loader) = profile["lastVersionId"].as_str().map(parse_version_id).unwrap_or((None, None));
+        candidates.push(Candidate {
+            source: CandidateSource::VanillaProfile(key.clone()),
+            name: profile["name"].as_str().filter(|n| !n.is_empty()).unwrap_or(key).to_string(),
+            game_dir,
+            minecraft_version,
+            loader,
+            mod_count,
+        });
+    }
+    candidates
+}
+
+#[derive(Deserialize)]
+struct PackComponent {
+    uid: String,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MmcPack {
+    components: Vec<PackComponent>,
+}
+
+fn scan_instances(launcher: &str) -> Vec<Candidate> {
+    let Ok(Launcher::MultiMC(root)) = get_launcher(launcher) else { return Vec::new() };
+    let Ok(entries) = fs::read_dir(root.join("instances")) else { return Vec::new() };
+    let mut candidates = Vec::new();
+    for instance in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        let Some(folder) = instance.file_name().map(|n| n.to_string_lossy().to_string()) else { continue };
+        // Ours are named after the installation id
+        if installation::load_installation(&folder).is_ok() {
+            continue;
+        }
+        let game_dir = [".minecraft", "minecraft"].iter().map(|d| instance.join(d)).find(|d| d.is_dir());
+        let Some(game_dir) = game_dir else { continue };
+        let mod_count = jars_in(&game_dir.join("mods")).len();
+        if mod_count == 0 {
+            continue;
+        }
+        let components = fs::read_to_string(instance.join("mmc-pack.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<MmcPack>(&content).ok())
+            .map(|pack| pack.components)
+            .unwrap_or_default();
+        let version_of = |uid: &str| components.iter().find(|c| c.uid == uid).and_then(|c| c.version.clone());
+        let loader = [
+            ("net.fabricmc.fabric-loader", "fabric"),
+            ("org.quiltmc.quilt-loader", "quilt"),
+            ("net.minecraftforge", "forge"),
+            ("net.neoforged", "neoforge"),
+        ]
+        .iter()
+        .find_map(|(uid, loader)| version_of(uid).map(|version| (loader.to_string(), version)));
+        let name = fs::read_to_string(instance.join("instance.cfg"))
+            .ok()
+            .and_then(|cfg| cfg.lines().find_map(|l| l.strip_prefix("name=").map(str::to_string)))
+            .unwrap_or_else(|| folder.clone());
+        candidates.push(Candidate {
+            source: CandidateSource::Instance { launcher: launcher.to_string(), folder },
+            name,
+            game_dir,
+            minecraft_version: version_of("net.minecraft"),
+            loader,
+            mod_count,
+        });
+    }
+    candidates
+}
+
+/// Modded instances the installer doesn't manage. `configured_launcher` is the launcher from the
+/// settings, a custom MultiMC folder is only known through it.
+pub fn scan(configured_launcher: &str) -> Vec<Candidate> {
+    let managed_dirs: Vec<PathBuf> = installation::load_all_installations()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|i| i.installation_path)
+        .collect();
+    let mut launchers = vec!["multimc-MultiMC".to_string(), "multimc-PrismLauncher".to_string()];
+    if configured_launcher.starts_with("custom-") {
+        launchers.push(configured_launcher.to_string());
+    }
+    let mut candidates = scan_profiles(&managed_dirs);
+    for launcher in &launchers {
+        candidates.extend(scan_instances(launcher));
+    }
+    debug!("Found {} instance(s) to import", candidates.len());
+    candidates
+}
+
+// Lowercase letters and digits only, "Sodium Extra" and "sodium-extra-0.5.jar" meet at "sodiumextra"
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_ascii_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn matches_file_name(component: &ModComponent, file_name: &str) -> bool {
+    let file = normalize(file_name);
+    [&component.location, &component.name]
+        .iter()
+        .map(|n| normalize(n))
+        .any(|n| n.len() >= 3 && file.starts_with(&n))
+}
+
+#[derive(Deserialize)]
+struct ModrinthVersion {
+    project_id: String,
+}
+
+#[derive(Deserialize)]
+struct ModrinthProject {
+    id: String,
+    slug: String,
+}
+
+// sha512 -> project id and slug, empty when Modrinth can't be reached
+async fn modrinth_projects(http_client: &CachedHttpClient, hashes: &[String]) -> Result<HashMap<String, (String, String)>, String> {
+    let body = serde_json::json!({ "hashes": hashes, "algorithm": "sha512" }).to_string();
+    let request = isahc::Request::post(MODRINTH_VERSION_FILES)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| format!("Failed to create Modrinth request: {}", e))?;
+    let mut response = http_client
+        .http_client
+        .send_async(request)
+        .await
+        .map_err(|e| format!("Failed to ask Modrinth: {}", e))?;
+    if response.status() != StatusCode::OK {
+        return Err(format!("Modrinth returned {}", response.status()));
+    }
+    let text = response.text().await.map_err(|e| format!("Failed to read Modrinth's answer: {}", e))?;
+    let versions: HashMap<String, ModrinthVersion> =
+        serde_json::from_str(&text).map_err(|e| format!("Failed to read Modrinth's answer: {}", e))?;
+    if versions.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    // Manifests name Modrinth projects by slug or id, the version only has the id
+    let ids: HashSet<&str> = versions.values().map(|v| v.project_id.as_str()).collect();
+    let ids = serde_json::to_string(&ids).map_err(|e| e.to_string())?;
+    let url = format!("{}?ids={}", MODRINTH_PROJECTS, url::form_urlencoded::byte_serialize(ids.as_bytes()).collect::<String>());
+    let mut response = http_client.get_async(url).await.map_err(|e| format!("Failed to ask Modrinth: {}", e))?;
+    if response.status() != StatusCode::OK {
+        return Err(format!("Modrinth returned {}", response.status()));
+    }
+    let text = response.text().await.map_err(|e| format!("Failed to read Modrinth's answer: {}", e))?;
+    let projects: Vec<ModrinthProject> =
+        serde_json::from_str(&text).map_err(|e| format!("Failed to read Modrinth's answer: {}", e))?;
+    let slugs: HashMap<String, String> = projects.into_iter().map(|p| (p.id, p.slug)).collect();
+    Ok(versions
+        .into_iter()
+        .map(|(hash, version)| {
+            let slug = slugs.get(&version.project_id).cloned().unwrap_or_default();
+            (hash, (version.project_id, slug))
+        })
+        .collect())
+}
+
+// Pack components present in the folders of `game_dir`, with the files that matched them
+async fn fingerprint(http_client: &CachedHttpClient, game_dir: &Path, manifest: &UniversalManifest) -> (Vec<(ModComponent, PathBuf)>, Vec<PathBuf>) {
+    let mods = jars_in(&game_dir.join("mods"));
+    let hashes = hashing::hash_files(&mods, HashAlgorithm::Sha512, |_, _| {});
+    let hash_of: HashMap<&PathBuf, String> = hashes.iter().filter_map(|(path, hash)| Some((path, hash.clone().ok()?))).collect();
+    let hash_list: Vec<String> = hash_of.values().cloned().collect();
+    let projects = if hash_list.is_empty() {
+        HashMap::new()
+    } else {
+        modrinth_projects(http_client, &hash_list).await.unwrap_or_else(|e| {
+            warn!("Matching by file name only: {}", e);
+            HashMap::new()
+        })
+    };
+
+    let mut matched: Vec<(ModComponent, PathBuf)> = Vec::new();
+    let mut unmatched = Vec::new();
+    for jar in mods {
+        let file_name = jar.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let project = hash_of.get(&jar).and_then(|hash| projects.get(hash));
+        let component = manifest.mods.iter().find(|m| match project {
+            Some((id, slug)) if m.source == "modrinth" => m.location == *id || m.location == *slug,
+            _ => matches_file_name(m, &file_name),
+        });
+        match component {
+            Some(component) => matched.push((component.clone(), jar)),
+            None => unmatched.push(jar),
+        }
+    }
+    // Packs are matched by name, they're rarely on Modrinth under the same file
+    for (folder, components) in [("shaderpacks", &manifest.shaderpacks), ("resourcepacks", &manifest.resourcepacks)] {
+        let Ok(entries) = fs::read_dir(game_dir.join(folder)) else { continue };
+        for path in entries.flatten().map(|e| e.path()) {
+            let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            if let Some(component) = components.iter().find(|c| matches_file_name(c, &file_name)) {
+                matched.push((component.clone(), path));
+            }
+        }
+    }
+    (matched, unmatched)
+}
+
+// Copies `from` into `to`, leaving out `skip` (the pack reinstalls those)
+fn copy_filtered(from: &Path, to: &Path, skip: &HashSet<PathBuf>) -> Result<usize, String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let mut copied = 0;
+    let entries = fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if skip.contains(&path) {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if file_type.is_dir() {
+            copied += copy_filtered(&path, &target, skip)?;
+        } else if file_type.is_file() {
+            fs::copy(&path, &target).map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Adopts `candidate` as a new installation with the features its mods match. The installation
+/// isn't installed yet, its first install adds whatever the pack has that the instance lacked.
+pub async fn import(http_client: &CachedHttpClient, candidate: &Candidate) -> Result<(Installation, ImportSummary), String> {
+    let manifest = crate::universal::load_universal_manifest(http_client, None)
+        .await
+        .map_err(|e| format!("Failed to load universal manifest: {}", e))?;
+    let (matched, unmatched) = fingerprint(http_client, &candidate.game_dir, &manifest).await;
+
+    let mut features = vec!["default".to_string()];
+    for (component, _) in &matched {
+        if component.optional && !features.contains(&component.id) {
+            features.push(component.id.clone());
+        }
+    }
+    for feature in features.clone() {
+        crate::resolve_dependencies(&feature, &mut features, &manifest);
+    }
+
+    let launcher = match &candidate.source {
+        CandidateSource::VanillaProfile(_) => "vanilla".to_string(),
+        CandidateSource::Instance { launcher, .. } => launcher.clone(),
+    };
+    let mut adopted = Installation::new_custom(
+        candidate.name.clone(),
+        manifest.minecraft_version.clone(),
+        manifest.loader.r#type.clone(),
+        manifest.loader.version.clone(),
+        launcher.clone(),
+        manifest.modpack_version.clone(),
+    );
+    // Stay on the instance's Minecraft version when the pack has it
+    if let Some(version) = &candidate.minecraft_version {
+        if *version != manifest.minecraft_version && manifest.for_minecraft_version(version).is_ok() {
+            adopted.minecraft_version_override = Some(version.clone());
+        }
+    }
+    adopted.enabled_features = features.clone();
+    adopted.pre_install_features = features.clone();
+    adopted.pending_features = features;
+    adopted.is_custom_configuration = true;
+
+    match &candidate.source {
+        CandidateSource::Instance { folder, .. } => {
+            adopted.id = folder.clone();
+            let root = crate::get_modpack_root(&get_launcher(&launcher)?, &adopted.id);
+            // Prism also reads "minecraft", ours always use ".minecraft"
+            if candidate.game_dir != root {
+                fs::remove_dir(&root).map_err(|e| format!("Failed to prepare {}: {}", root.display(), e))?;
+                fs::rename(&candidate.game_dir, &root)
+                    .map_err(|e| format!("Failed to move {} to {}: {}", candidate.game_dir.display(), root.display(), e))?;
+            }
+            // The pack installs its own versions, two copies of a mod keep the game from starting
+            let replaced = root.join(REPLACED_DIR);
+            for (_, path) in &matched {
+                let (Ok(relative), Some(file_name)) = (path.strip_prefix(&candidate.game_dir), path.file_name()) else { continue };
+                let Some(folder) = relative.parent() else { continue };
+                let target = replaced.join(folder).join(file_name);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                fs::rename(root.join(relative), &target)
+                    .map_err(|e| format!("Failed to move {} aside: {}", relative.display(), e))?;
+            }
+        }
+        CandidateSource::VanillaProfile(_) => {
+            let skip: HashSet<PathBuf> = matched.iter().map(|(_, path)| path.clone()).collect();
+            let (from, to) = (candidate.game_dir.clone(), adopted.installation_path.clone());
+            let copied = tokio::task::spawn_blocking(move || copy_filtered(&from, &to, &skip))
+                .await
+                .map_err(|e| format!("Copying stopped unexpectedly: {}", e))??;
+            debug!("Copied {} file(s) from {}", copied, candidate.game_dir.display());
+        }
+    }
+    installation::register_installation(&adopted)?;
+    adopted.save()?;
+
+    let file_name = |path: &PathBuf| path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut summary = ImportSummary {
+        matched: matched.iter().map(|(component, _)| component.name.clone()).collect(),
+        unmatched: unmatched.iter().map(file_name).collect(),
+    };
+    summary.matched.sort();
+    summary.matched.dedup();
+    info!(
+        "Imported '{}' as {}: {} component(s) matched, {} unknown jar(s)",
+        candidate.name,
+        adopted.id,
+        summary.matched.len(),
+        summary.unmatched.len()
+    );
+    Ok((adopted, summary))
+}
@@ -0,0 +1,134 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use fs2::FileExt;
+use log::{debug, info, warn};
+
+use crate::installation::Installation;
+
+// Keeps two installers from working on the same files. While the GUI runs it holds installer.lock
+// in the installer dir, a second window finds it taken and says so instead of starting. Installs
+// and updates hold install.lock in the installation's folder, which also covers a scheduled update
+// or watch mode running into a click on Install. These are OS advisory locks that go away with the
+// process, a crash never leaves a stale lock behind.
+// In tray mode the open window may well be hidden, so the second installer doesn't just say so: the
+// first one listens on a local port (written to installer.port) and the second asks it through
+// there to show its window, then quits.
+
+const APP_LOCK: &str = "installer.lock";
+const INSTALL_LOCK: &str = "install.lock";
+const SHOW_PORT: &str = "installer.port";
+const SHOW_REQUEST: &[u8] = b"show";
+
+static APP_GUARD: OnceLock<File> = OnceLock::new();
+// Set when another installer asked for the window, the GUI takes it
+static SHOW_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+fn open_lock_file(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// Takes the single-instance lock for the rest of the run. Err when another installer has it.
+pub fn acquire_app_lock(installer_dir: &Path) -> Result<(), String> {
+    let path = installer_dir.join(APP_LOCK);
+    let mut file = open_lock_file(&path)?;
+    if file.try_lock_exclusive().is_err() {
+        return Err("The installer is already running. Switch to the open window, or close it and try again.".to_string());
+    }
+    // Only informative, nothing reads it back
+    if file.set_len(0).is_ok() {
+        let _ = write!(file, "{}", std::process::id());
+    }
+    let _ = APP_GUARD.set(file);
+    Ok(())
+}
+
+/// Answers other installers asking for the window, for the rest of the run. Only call it while
+/// holding the app lock, the port file belongs to whoever holds it.
+pub fn listen_for_show_requests(installer_dir: &Path) {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Failed to listen for other installers: {}", e);
+            return;
+        }
+    };
+    let port = match listener.local_addr() {
+        Ok(addr) => addr.port(),
+        Err(e) => {
+            warn!("Failed to listen for other installers: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = fs::write(installer_dir.join(SHOW_PORT), port.to_string()) {
+        warn!("Failed to write {}: {}", SHOW_PORT, e);
+        return;
+    }
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut request = [0; SHOW_REQUEST.len()];
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+            if (&stream).read_exact(&mut request).is_ok() && request == SHOW_REQUEST {
+                info!("Another installer was started, showing the window");
+                SHOW_REQUESTED.store(true, Ordering::Relaxed);
+            }
+        }
+    });
+}
+
+/// Asks the installer holding the app lock to show its window. False when it couldn't be reached.
+pub fn request_show(installer_dir: &Path) -> bool {
+    let Some(port) = fs::read_to_string(installer_dir.join(SHOW_PORT)).ok().and_then(|p| p.trim().parse::<u16>().ok()) else {
+        return false;
+    };
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    match TcpStream::connect_timeout(&addr, Duration::from_secs(1)) {
+        Ok(mut stream) => stream.write_all(SHOW_REQUEST).is_ok(),
+        Err(e) => {
+            debug!("The running installer didn't answer on port {}: {}", port, e);
+            false
+        }
+    }
+}
+
+/// Whether another installer asked for the window since the last call.
+pub fn take_show_request() -> bool {
+    SHOW_REQUESTED.swap(false, Ordering::Relaxed)
+}
+
+/// Held while an installation is installed or updated, unlocks when dropped.
+pub struct InstallationLock {
+    _file: File,
+}
+
+pub fn lock_installation(installation: &Installation) -> Result<InstallationLock, String> {
+    fs::create_dir_all(&installation.installation_path)
+        .map_err(|e| format!("Failed to create {}: {}", installation.installation_path.display(), e))?;
+    let path = installation.installation_path.join(INSTALL_LOCK);
+    let file = open_lock_file(&path)?;
+    if file.try_lock_exclusive().is_err() {
+        warn!("{} is locked, another install or update is running", path.display());
+        return Err(format!(
+            "{} is already being installed or updated, in this window or another one. Wait for that to finish, then try again.",
+            installation.name
+        ));
+    }
+    debug!("Locked {}", path.display());
+    Ok(InstallationLock { _file: file })
+}
+
+/// Whether an install of `installation` could start right now, without keeping the lock.
+pub fn check_installation(installation: &Installation) -> Result<(), String> {
+    lock_installation(installation).map(drop)
+}
@@ -0,0 +1,415 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use isahc::ReadResponseExt;
+use log::{debug, info, warn};
+use serde::Deserialize;
+
+use crate::hashing::{self, HashAlgorithm};
+use crate::{build_http_client, get_installer_dir};
+
+// Finding a Java that can run the pack. The vanilla launcher brings its own runtimes, but only
+// once it has started the version, and a javaDir the user set by hand or a stray JAVA_HOME easily
+// points at the wrong one. We look at the launcher's runtimes, JAVA_HOME, the registry on Windows
+// and the usual install folders, check each with `java -version`, and when nothing matches the
+// pack's Minecraft version we download a Temurin JRE into .WC_OVHL/runtime.
+
+const RUNTIME_DIR: &str = "runtime";
+const ADOPTIUM_API: &str = "https://api.adoptium.net/v3/assets/latest";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaInstall {
+    pub path: PathBuf,
+    pub version: String,
+    pub major: u32,
+    // GraalVM builds name themselves in the version output, some JVM flags only work on them
+    pub graalvm: bool,
+}
+
+fn java_binary() -> &'static str {
+    if cfg!(windows) { "java.exe" } else { "java" }
+}
+
+fn runtime_dir() -> PathBuf {
+    get_installer_dir().join(RUNTIME_DIR)
+}
+
+/// Java major version `minecraft_version` needs, see the launcher's version manifests.
+pub fn required_major(minecraft_version: &str) -> u32 {
+    let parts: Vec<u32> = minecraft_version
+        .split(|c: char| !c.is_ascii_digit())
+        .map_while(|p| p.parse().ok())
+        .collect();
+    match parts.as_slice() {
+        [1, minor, ..] if *minor >= 21 => 21,
+        [1, 20, patch, ..] if *patch >= 5 => 21,
+        [1, minor, ..] if *minor >= 18 => 17,
+        [1, 17, ..] => 16,
+        [1, ..] => 8,
+        // Versions after 1.21 dropped the "1." and need at least what 1.21 does
+        _ => 21,
+    }
+}
+
+pub fn is_compatible(major: u32, required: u32) -> bool {
+    // Old versions (and the Forge builds for them) break on anything newer than 8
+    if required == 8 {
+        major == 8
+    } else {
+        major >= required
+    }
+}
+
+// "1.8.0_382" is 8, "17.0.8" is 17, "21" is 21
+fn parse_major(version: &str) -> Option<u32> {
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    match parts.next()?.parse::<u32>().ok()? {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+/// Runs `java -version`, None when it isn't a working Java.
+pub fn probe(java: &Path) -> Option<JavaInstall> {
+    let output = Command::new(java).arg("-version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // java -version prints to stderr, `openjdk version "17.0.8" 2023-07-18`
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let first_line = stderr.lines().next()?;
+    let version = first_line.split('"').nth(1)?.to_string();
+    let major = parse_major(&version)?;
+    let graalvm = stderr.contains("GraalVM");
+    Some(JavaInstall { path: java.to_path_buf(), version, major, graalvm })
+}
+
+// Every <dir>/*/<suffix>/bin/java, for folders that hold one JVM per subfolder
+fn java_homes_in(dir: &Path, suffix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|e| e.path().join(suffix).join("bin").join(java_binary()))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+// The launcher keeps its own runtimes in .minecraft/runtime/<component>/<platform>/<component>/bin
+fn launcher_runtimes(minecraft_dir: &Path) -> Vec<PathBuf> {
+    let mut runtimes: Vec<PathBuf> = hashing::walk_files(&minecraft_dir.join("runtime"))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| {
+            p.file_name().map_or(false, |n| n == java_binary())
+                && p.parent().and_then(|b| b.file_name()).map_or(false, |b| b == "bin")
+        })
+        .collect();
+    // Newest component names sort last (java-runtime-alpha, -beta, -gamma, -delta...)
+    runtimes.sort();
+    runtimes.reverse();
+    runtimes
+}
+
+// The ones we downloaded, runtime/temurin-<major>/<archive folder>/...
+fn downloaded_runtimes() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(runtime_dir()) else { return Vec::new() };
+    entries
+        .flatten()
+        .filter(|e| !e.file_name().to_string_lossy().ends_with(".part"))
+        .filter_map(|e| find_binary(&e.path()))
+        .collect()
+}
+
+fn find_binary(dir: &Path) -> Option<PathBuf> {
+    hashing::walk_files(dir).ok()?.into_iter().find(|p| {
+        p.file_name().map_or(false, |n| n == java_binary())
+            && p.parent().and_then(|b| b.file_name()).map_or(false, |b| b == "bin")
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn registry_javas() -> Vec<PathBuf> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let mut found = Vec::new();
+    // Oracle and most OpenJDK builds, one subkey per version with a JavaHome value
+    for key_path in [
+        "SOFTWARE\\JavaSoft\\JDK",
+        "SOFTWARE\\JavaSoft\\JRE",
+        "SOFTWARE\\JavaSoft\\Java Development Kit",
+        "SOFTWARE\\JavaSoft\\Java Runtime Environment",
+        "SOFTWARE\\Microsoft\\JDK",
+    ] {
+        let Ok(key) = hklm.open_subkey(key_path) else { continue };
+        for version in key.enum_keys().flatten() {
+            if let Ok(home) = key.open_subkey(&version).and_then(|k| k.get_value::<String, _>("JavaHome")) {
+                found.push(PathBuf::from(home).join("bin").join(java_binary()));
+            }
+        }
+    }
+    // Adoptium/Temurin keep the path under <version>\hotspot\MSI
+    for key_path in [
+        "SOFTWARE\\Eclipse Adoptium\\JRE",
+        "SOFTWARE\\Eclipse Adoptium\\JDK",
+        "SOFTWARE\\Eclipse Foundation\\JDK",
+        "SOFTWARE\\AdoptOpenJDK\\JRE",
+        "SOFTWARE\\AdoptOpenJDK\\JDK",
+    ] {
+        let Ok(key) = hklm.open_subkey(key_path) else { continue };
+        for version in key.enum_keys().flatten() {
+            let msi = format!("{}\\hotspot\\MSI", version);
+            if let Ok(home) = key.open_subkey(&msi).and_then(|k| k.get_value::<String, _>("Path")) {
+                found.push(PathBuf::from(home).join("bin").join(java_binary()));
+            }
+        }
+    }
+    found
+}
+
+#[cfg(not(target_os = "windows"))]
+fn registry_javas() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+fn common_paths() -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let home = dirs::home_dir();
+    match env::consts::OS {
+        "windows" => {
+            for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+                let Some(program_files) = env::var_os(var).map(PathBuf::from) else { continue };
+                for vendor in ["Java", "Eclipse Adoptium", "AdoptOpenJDK", "Microsoft", "Zulu", "BellSoft"] {
+                    found.extend(java_homes_in(&program_files.join(vendor), ""));
+                }
+            }
+        }
+        "macos" => {
+            found.extend(java_homes_in(Path::new("/Library/Java/JavaVirtualMachines"), "Contents/Home"));
+            if let Some(home) = &home {
+                found.extend(java_homes_in(&home.join("Library/Java/JavaVirtualMachines"), "Contents/Home"));
+            }
+        }
+        _ => {
+            for dir in ["/usr/lib/jvm", "/usr/lib64/jvm", "/usr/java", "/opt/java"] {
+                found.extend(java_homes_in(Path::new(dir), ""));
+            }
+        }
+    }
+    if let Some(home) = &home {
+        found.extend(java_homes_in(&home.join(".sdkman/candidates/java"), ""));
+        found.extend(java_homes_in(&home.join(".jdks"), ""));
+    }
+    found
+}
+
+/// Every working Java we can find, best candidates first.
+pub fn detect(minecraft_dir: &Path) -> Vec<JavaInstall> {
+    let mut candidates = downloaded_runtimes();
+    candidates.extend(launcher_runtimes(minecraft_dir));
+    if let Some(home) = env::var_os("JAVA_HOME") {
+        candidates.push(PathBuf::from(home).join("bin").join(java_binary()));
+    }
+    candidates.extend(registry_javas());
+    candidates.extend(common_paths());
+
+    let mut installs: Vec<JavaInstall> = Vec::new();
+    for candidate in candidates {
+        let candidate = fs::canonicalize(&candidate).unwrap_or(candidate);
+        if installs.iter().any(|i| i.path == candidate) {
+            continue;
+        }
+        match probe(&candidate) {
+            Some(install) => installs.push(install),
+            None => debug!("{} isn't a working Java", candidate.display()),
+        }
+    }
+    debug!("Detected Java installs: {:?}", installs);
+    installs
+}
+
+/// The best detected Java for `minecraft_version`, the exact major version if there is one.
+pub fn find_compatible(minecraft_dir: &Path, minecraft_version: &str) -> Option<JavaInstall> {
+    let required = required_major(minecraft_version);
+    let mut compatible: Vec<JavaInstall> = detect(minecraft_dir)
+        .into_iter()
+        .filter(|i| is_compatible(i.major, required))
+        .collect();
+    // Stable sort, the detection order breaks ties
+    compatible.sort_by_key(|i| (i.major != required, i.major));
+    compatible.into_iter().next()
+}
+
+/// Some Java, compatible or not, for tools like the Forge installer that run on most versions.
+pub fn find_java(minecraft_dir: &Path) -> PathBuf {
+    if let Some(home) = env::var_os("JAVA_HOME") {
+        let java = PathBuf::from(home).join("bin").join(java_binary());
+        if java.is_file() {
+            return java;
+        }
+    }
+    if let Some(java) = launcher_runtimes(minecraft_dir).into_iter().next() {
+        return java;
+    }
+    if let Some(java) = downloaded_runtimes().into_iter().next() {
+        return java;
+    }
+    // Whatever is on the PATH, running it tells the user if there's nothing
+    PathBuf::from(java_binary())
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumRelease {
+    binary: AdoptiumBinary,
+    release_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumBinary {
+    package: AdoptiumPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumPackage {
+    name: String,
+    link: String,
+    checksum: Option<String>,
+}
+
+fn adoptium_platform() -> Result<(&'static str, &'static str), String> {
+    let os = match env::consts::OS {
+        "windows" => "windows",
+        "macos" => "mac",
+        "linux" => "linux",
+        other => return Err(format!("No Java downloads for {}", other)),
+    };
+    let arch = match env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "aarch64",
+        "x86" => "x32",
+        other => return Err(format!("No Java downloads for {}", other)),
+    };
+    Ok((os, arch))
+}
+
+fn extract(archive: &Path, target: &Path) -> Result<(), String> {
+    fs::create_dir_all(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    if archive.extension().map_or(false, |e| e == "zip") {
+        let file = fs::File::open(archive).map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Invalid Java archive: {}", e))?;
+        crate::safe_extract::extract_all(&mut zip, target, |relative| Some(relative.to_path_buf()), |_, _| {})
+            .map(drop)
+            .map_err(|e| format!("Failed to extract the Java runtime: {}", e))
+    } else {
+        // .tar.gz on Linux and macOS, where tar is always there and keeps the file modes
+        let status = Command::new("tar")
+            .arg("-xzf")
+            .arg(archive)
+            .arg("-C")
+            .arg(target)
+            .status()
+            .map_err(|e| format!("Couldn't run tar: {}", e))?;
+        if !status.success() {
+            return Err(format!("tar failed to extract the Java runtime ({})", status));
+        }
+        Ok(())
+    }
+}
+
+/// Downloads the latest Temurin JRE of `major` into .WC_OVHL/runtime, returns its java binary.
+pub fn download_runtime(major: u32) -> Result<PathBuf, String> {
+    let (os, arch) = adoptium_platform()?;
+    let url = format!(
+        "{}/{}/hotspot?architecture={}&image_type=jre&os={}&vendor=eclipse",
+        ADOPTIUM_API, major, arch, os
+    );
+    let client = build_http_client();
+    let mut resp = client
+        .get(&url)
+        .map_err(|e| format!("Failed to look up Java {}: {}", major, e))?;
+    if resp.status() != 200 {
+        return Err(format!("Failed to look up Java {}: status {}", major, resp.status()));
+    }
+    let body = resp
+        .text()
+        .map_err(|e| format!("Failed to look up Java {}: {}", major, e))?;
+    let releases: Vec<AdoptiumRelease> =
+        serde_json::from_str(&body).map_err(|e| format!("Unexpected Adoptium response: {}", e))?;
+    let release = releases
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Adoptium has no Java {} JRE for {} {}", major, os, arch))?;
+    let package = release.binary.package;
+    info!("Downloading {} from {}", release.release_name, package.link);
+
+    let runtime_dir = runtime_dir();
+    fs::create_dir_all(&runtime_dir).map_err(|e| format!("Failed to create {}: {}", runtime_dir.display(), e))?;
+    let archive = runtime_dir.join(&package.name);
+    let mut resp = client
+        .get(&package.link)
+        .map_err(|e| format!("Failed to download Java {}: {}", major, e))?;
+    if resp.status() != 200 {
+        return Err(format!("Failed to download Java {}: status {}", major, resp.status()));
+    }
+    resp.copy_to_file(&archive)
+        .map_err(|e| format!("Failed to save {}: {}", archive.display(), e))?;
+
+    if let Some(expected) = &package.checksum {
+        let actual = hashing::hash_file(&archive, HashAlgorithm::Sha256)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = fs::remove_file(&archive);
+            return Err(format!("The Java {} download is corrupted (checksum mismatch), try again", major));
+        }
+    }
+
+    // Extracted next to the final folder first, an interrupted extraction isn't picked up later
+    let target = runtime_dir.join(format!("temurin-{}", major));
+    let part = runtime_dir.join(format!("temurin-{}.part", major));
+    let _ = fs::remove_dir_all(&part);
+    let extracted = extract(&archive, &part);
+    let _ = fs::remove_file(&archive);
+    extracted?;
+    let _ = fs::remove_dir_all(&target);
+    fs::rename(&part, &target).map_err(|e| format!("Failed to move {} into place: {}", target.display(), e))?;
+
+    let java = find_binary(&target).ok_or_else(|| format!("The Java {} download has no {}", major, java_binary()))?;
+    match probe(&java) {
+        Some(install) => {
+            info!("Installed Java {} at {}", install.version, java.display());
+            Ok(java)
+        }
+        None => Err(format!("The downloaded Java at {} doesn't run", java.display())),
+    }
+}
+
+/// Java for the launcher profile's javaDir. None when the launcher's own runtime already fits,
+/// otherwise a compatible installed Java, or a freshly downloaded one.
+pub fn runtime_for_profile(minecraft_dir: &Path, minecraft_version: &str) -> Result<Option<PathBuf>, String> {
+    let required = required_major(minecraft_version);
+    let launcher_fits = launcher_runtimes(minecraft_dir)
+        .iter()
+        .filter_map(|java| probe(java))
+        .any(|install| install.major == required);
+    if launcher_fits {
+        debug!("The launcher has a Java {} runtime, leaving javaDir unset", required);
+        return Ok(None);
+    }
+
+    let java = match find_compatible(minecraft_dir, minecraft_version) {
+        Some(install) => {
+            info!("Using Java {} at {} for Minecraft {}", install.version, install.path.display(), minecraft_version);
+            install.path
+        }
+        None => {
+            warn!("No Java {} found for Minecraft {}, downloading one", required, minecraft_version);
+            // Temurin never shipped a Java 16 JRE, 1.17 runs fine on 17
+            download_runtime(if required == 16 { 17 } else { required })?
+        }
+    };
+    // javaw doesn't open a console window next to the game
+    let javaw = java.with_file_name("javaw.exe");
+    Ok(Some(if cfg!(windows) && javaw.is_file() { javaw } else { java }))
+}
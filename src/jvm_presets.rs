@@ -0,0 +1,95 @@
+use crate::java::JavaInstall;
+
+// Curated JVM arguments the Performance tab offers instead of hand-editing java_args. The
+// installation keeps the picked preset and the user's own extra arguments apart so the preset can
+// be swapped without losing them; java_args is always rebuilt as preset + custom + memory.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JvmPreset {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub args: &'static str,
+    // Oldest Java major version that knows every flag
+    pub min_java: u32,
+    // The JVMCI flags only exist in GraalVM builds, other JVMs refuse to start with them
+    pub requires_graalvm: bool,
+}
+
+pub const PRESETS: &[JvmPreset] = &[
+    JvmPreset {
+        id: "balanced-g1",
+        name: "Balanced G1",
+        description: "The G1 tuning most modpacks ship with. Short pauses and steady frame times on any computer, the safe choice.",
+        args: "-XX:+UseG1GC -XX:+ParallelRefProcEnabled -XX:MaxGCPauseMillis=200 -XX:+UnlockExperimentalVMOptions \
+               -XX:+DisableExplicitGC -XX:+AlwaysPreTouch -XX:G1NewSizePercent=30 -XX:G1MaxNewSizePercent=40 \
+               -XX:G1HeapRegionSize=8M -XX:G1ReservePercent=20 -XX:G1HeapWastePercent=5 -XX:G1MixedGCCountTarget=4 \
+               -XX:InitiatingHeapOccupancyPercent=15 -XX:G1MixedGCLiveThresholdPercent=90 \
+               -XX:G1RSetUpdatingPauseTimePercent=5 -XX:SurvivorRatio=32 -XX:+PerfDisableSharedMem \
+               -XX:MaxTenuringThreshold=1",
+        min_java: 8,
+        requires_graalvm: false,
+    },
+    JvmPreset {
+        id: "low-latency-zgc",
+        name: "Low-latency ZGC",
+        description: "Collects garbage alongside the game, so there are almost no GC stutters. Uses more CPU and memory, best with 8 GB or more allocated and a CPU with cores to spare.",
+        args: "-XX:+UseZGC -XX:+AlwaysPreTouch -XX:+DisableExplicitGC -XX:+PerfDisableSharedMem",
+        min_java: 17,
+        requires_graalvm: false,
+    },
+    JvmPreset {
+        id: "graalvm-ee",
+        name: "GraalVM EE",
+        description: "Turns on the Graal JIT compiler for noticeably better frame rates in heavy areas. Only works with a GraalVM Java (Enterprise or Oracle GraalVM), startup takes a bit longer.",
+        args: "-XX:+UnlockExperimentalVMOptions -XX:+UnlockDiagnosticVMOptions -XX:+AlwaysActAsServerClassMachine \
+               -XX:+AlwaysPreTouch -XX:+DisableExplicitGC -XX:+UseNUMA -XX:ReservedCodeCacheSize=400M \
+               -XX:NonNMethodCodeHeapSize=12M -XX:ProfiledCodeHeapSize=194M -XX:NonProfiledCodeHeapSize=194M \
+               -XX:-DontCompileHugeMethods -XX:+PerfDisableSharedMem -XX:+UseFastUnorderedTimeStamps \
+               -XX:+UseCriticalJavaThreadPriority -XX:+EnableJVMCIProduct -XX:+EnableJVMCI -XX:+UseJVMCICompiler \
+               -XX:+EagerJVMCI -XX:+UseG1GC -XX:MaxGCPauseMillis=37 -XX:G1HeapRegionSize=16M \
+               -XX:G1NewSizePercent=23 -XX:G1ReservePercent=20 -XX:SurvivorRatio=32 -XX:G1MixedGCCountTarget=3 \
+               -XX:G1HeapWastePercent=20 -XX:InitiatingHeapOccupancyPercent=10 -XX:G1RSetUpdatingPauseTimePercent=0 \
+               -XX:MaxTenuringThreshold=1 -XX:G1SATBBufferEnqueueingThresholdPercent=30 \
+               -XX:G1ConcMarkStepDurationMillis=5.0 -XX:GCTimeRatio=99 -Dgraal.CompilerConfiguration=enterprise",
+        min_java: 17,
+        requires_graalvm: true,
+    },
+];
+
+pub fn find(id: &str) -> Option<&'static JvmPreset> {
+    PRESETS.iter().find(|preset| preset.id == id)
+}
+
+impl JvmPreset {
+    /// Why the preset can't run on `java`, None when it can.
+    pub fn incompatibility(&self, java: &JavaInstall) -> Option<String> {
+        if java.major < self.min_java {
+            return Some(format!(
+                "{} needs Java {} or newer, this installation runs on Java {}",
+                self.name, self.min_java, java.version
+            ));
+        }
+        if self.requires_graalvm && !java.graalvm {
+            return Some(format!("{} needs a GraalVM Java, {} isn't one", self.name, java.path.display()));
+        }
+        None
+    }
+}
+
+fn is_memory_arg(arg: &str) -> bool {
+    arg.starts_with("-Xmx") || arg.starts_with("-Xms")
+}
+
+/// java_args for `preset` with the user's `custom` arguments on top, keeping the memory arguments
+/// of `current` since the memory slider owns those. Custom arguments come last so they win over
+/// the preset's.
+pub fn compose(preset: Option<&JvmPreset>, custom: &str, current: &str) -> String {
+    preset
+        .map_or("", |preset| preset.args)
+        .split_whitespace()
+        .chain(custom.split_whitespace().filter(|arg| !is_memory_arg(arg)))
+        .chain(current.split_whitespace().filter(|arg| is_memory_arg(arg)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
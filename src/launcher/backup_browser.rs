@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, HashSet};
+
+use dioxus::prelude::*;
+use log::{debug, error};
+
+use crate::backup::{format_bytes, BackupFileEntry, BackupMetadata};
+use crate::installation::Installation;
+
+// Only the start of a file is shown, config files are tiny but logs can be huge
+const PREVIEW_LIMIT: usize = 64 * 1024;
+// Rendering thousands of rows makes the webview crawl, search narrows it down
+const MAX_LISTED_FILES: usize = 500;
+
+fn preview_text(bytes: Vec<u8>) -> Result<String, String> {
+    if bytes.contains(&0) {
+        return Err("Binary file, no preview available".to_string());
+    }
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        // The limit can cut a multi-byte character in half, show what we have
+        Err(e) => Ok(String::from_utf8_lossy(e.as_bytes()).to_string()),
+    }
+}
+
+// Top level folders with how many files they contain, so a whole folder can be picked at once
+fn top_level_folders(files: &[BackupFileEntry]) -> BTreeMap<String, usize> {
+    let mut folders = BTreeMap::new();
+    for file in files {
+        if let Some((folder, _)) = file.path.split_once('/') {
+            *folders.entry(folder.to_string()).or_insert(0) += 1;
+        }
+    }
+    folders
+}
+
+#[component]
+pub fn BackupBrowser(
+    installation: Installation,
+    backup: BackupMetadata,
+    onclose: EventHandler<()>,
+    onrestored: EventHandler<String>,
+) -> Element {
+    let backup_id = backup.id.clone();
+    let files = use_hook({
+        let installation = installation.clone();
+        let backup_id = backup_id.clone();
+        move || installation.list_backup_files(&backup_id)
+    });
+
+    let mut search = use_signal(|| String::new());
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut preview = use_signal(|| Option::<(String, Result<String, String>)>::None);
+    let mut is_restoring = use_signal(|| false);
+    let mut restore_error = use_signal(|| Option::<String>::None);
+
+    let files = match files {
+        Ok(files) => files,
+        Err(e) => {
+            return rsx! {
+                div { class: "modal-overlay",
+                    div { class: "modal-container backup-browser",
+                        div { class: "modal-header",
+                            h3 { "Browse Backup" }
+                            button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                        }
+                        div { class: "modal-content",
+                            div { class: "error-message", "Failed to read backup: {e}" }
+                        }
+                    }
+                }
+            };
+        }
+    };
+
+    let query = search.read().trim().to_lowercase();
+    let matching: Vec<BackupFileEntry> = files
+        .iter()
+        .filter(|file| query.is_empty() || file.path.to_lowercase().contains(&query))
+        .cloned()
+        .collect();
+    let hidden_count = matching.len().saturating_sub(MAX_LISTED_FILES);
+    let folders = top_level_folders(&files);
+
+    let selection = selected.read().clone();
+    let selected_file_count = files
+        .iter()
+        .filter(|file| selection.iter().any(|s| file.is_within(s)))
+        .count();
+
+    let restore_selected = {
+        let installation = installation.clone();
+        let backup_id = backup_id.clone();
+        move |_| {
+            let installation = installation.clone();
+            let backup_id = backup_id.clone();
+            let selections: Vec<String> = selected.read().iter().cloned().collect();
+            is_restoring.set(true);
+            restore_error.set(None);
+
+            spawn(async move {
+                match installation.restore_backup_files(&backup_id, &selections).await {
+                    Ok(count) => {
+                        debug!("Restored {} files from backup {}", count, backup_id);
+                        onrestored.call(format!("Restored {} file(s) from the backup", count));
+                    }
+                    Err(e) => {
+                        error!("Selective restore failed: {}", e);
+                        restore_error.set(Some(e));
+                    }
+                }
+                is_restoring.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container backup-browser",
+                div { class: "modal-header",
+                    h3 { "Browse Backup: {backup.description}" }
+                    button {
+                        class: "modal-close",
+                        disabled: *is_restoring.read(),
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    if let Some(error) = &*restore_error.read() {
+                        div { class: "error-message", "{error}" }
+                    }
+
+                    div { class: "backup-browser-toolbar",
+                        input {
+                            r#type: "text",
+                            class: "backup-browser-search",
+                            value: "{search}",
+                            placeholder: "Search {files.len()} files...",
+                            oninput: move |evt| search.set(evt.value().clone()),
+                        }
+                        button {
+                            class: "backup-browser-select-all",
+                            disabled: matching.is_empty(),
+                            onclick: {
+                                let matching = matching.clone();
+                                move |_| {
+                                    let mut current = selected.write();
+                                    for file in &matching {
+                                        current.insert(file.path.clone());
+                                    }
+                                }
+                            },
+                            "Select all matching"
+                        }
+                        button {
+                            class: "backup-browser-clear",
+                            disabled: selection.is_empty(),
+                            onclick: move |_| selected.write().clear(),
+                            "Clear selection"
+                        }
+                    }
+
+                    // Whole folders
+                    if !folders.is_empty() {
+                        div { class: "backup-browser-folders",
+                            for (folder, count) in folders {
+                                {
+                                    let is_checked = selection.contains(&folder);
+                                    let folder_for_toggle = folder.clone();
+                                    rsx! {
+                                        label {
+                                            key: "{folder}",
+                                            class: "backup-browser-folder",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: is_checked,
+                                                onchange: move |_| {
+                                                    let mut current = selected.write();
+                                                    if !current.remove(&folder_for_toggle) {
+                                                        current.insert(folder_for_toggle.clone());
+                                                    }
+                                                }
+                                            }
+                                            "📁 {folder}/ ({count})"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "backup-browser-body",
+                        div { class: "backup-browser-files",
+                            for file in matching.into_iter().take(MAX_LISTED_FILES) {
+                                {
+                                    let is_checked = selection.iter().any(|s| file.is_within(s));
+                                    let path_for_toggle = file.path.clone();
+                                    let path_for_preview = file.path.clone();
+                                    let installation = installation.clone();
+                                    let backup_id = backup_id.clone();
+                                    let size = format_bytes(file.size);
+                                    rsx! {
+                                        div {
+                                            key: "{file.path}",
+                                            class: "backup-browser-file",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: is_checked,
+                                                onchange: move |_| {
+                                                    let mut current = selected.write();
+                                                    if !current.remove(&path_for_toggle) {
+                                                        current.insert(path_for_toggle.clone());
+                                                    }
+                                                }
+                                            }
+                                            span {
+                                                class: "backup-browser-path",
+                                                onclick: move |_| {
+                                                    let content = installation
+                                                        .read_backup_file(&backup_id, &path_for_preview, PREVIEW_LIMIT)
+                                                        .and_then(preview_text);
+                                                    preview.set(Some((path_for_preview.clone(), content)));
+                                                },
+                                                "{file.path}"
+                                            }
+                                            span { class: "backup-browser-size", "{size}" }
+                                        }
+                                    }
+                                }
+                            }
+                            if hidden_count > 0 {
+                                div { class: "backup-browser-more",
+                                    "{hidden_count} more files, refine the search to see them"
+                                }
+                            }
+                        }
+
+                        if let Some((path, content)) = &*preview.read() {
+                            div { class: "backup-browser-preview",
+                                div { class: "backup-browser-preview-header",
+                                    span { "{path}" }
+                                    button { onclick: move |_| preview.set(None), "×" }
+                                }
+                                match content {
+                                    Ok(text) => rsx! { pre { "{text}" } },
+                                    Err(e) => rsx! { div { class: "backup-browser-no-preview", "{e}" } },
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        disabled: *is_restoring.read(),
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "update-proceed-button",
+                        disabled: selection.is_empty() || *is_restoring.read(),
+                        onclick: restore_selected,
+                        {if *is_restoring.read() {
+                            "Restoring...".to_string()
+                        } else {
+                            format!("Restore {} file(s)", selected_file_count)
+                        }}
+                    }
+                }
+            }
+        }
+    }
+}
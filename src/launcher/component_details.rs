@@ -0,0 +1,145 @@
+use dioxus::prelude::*;
+
+use crate::markdown::render_markdown;
+use crate::universal::ModComponent;
+
+// Details of one component, opened by clicking its feature card: the full markdown description,
+// what changed in this version, credits, links, the installed vs available version and what it
+// needs or can't be used with. Dependency ids are resolved to names by the caller since only the
+// feature list has the whole manifest.
+#[component]
+pub fn ComponentDetails(
+    component: ModComponent,
+    installed_version: Option<String>,
+    dependency_names: Vec<String>,
+    conflict_names: Vec<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let text = component
+        .details
+        .clone()
+        .or_else(|| component.description.clone())
+        .unwrap_or_default();
+    let page_url = component.project_page_url();
+    let version_changed = installed_version
+        .as_ref()
+        .map_or(false, |installed| *installed != component.version);
+
+    rsx! {
+        div { class: "modal-overlay",
+            onclick: move |_| onclose.call(()),
+            div { class: "modal-container component-details-dialog",
+                // Clicks inside shouldn't reach the overlay
+                onclick: move |evt| evt.stop_propagation(),
+
+                div { class: "modal-header",
+                    h3 { "{component.name}" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content component-details-pane",
+                    if !text.is_empty() {
+                        div {
+                            class: "component-details-markdown",
+                            dangerous_inner_html: "{render_markdown(&text)}"
+                        }
+                    }
+
+                    div { class: "component-details-meta",
+                        div { class: "component-details-row",
+                            span { class: "component-details-label", "Available:" }
+                            span { "{component.version}" }
+                        }
+                        div { class: "component-details-row",
+                            span { class: "component-details-label", "Installed:" }
+                            match &installed_version {
+                                Some(version) => rsx! {
+                                    span { class: if version_changed { "component-version-outdated" } else { "" },
+                                        "{version}"
+                                        if version_changed { " (update available)" }
+                                    }
+                                },
+                                None => rsx! { span { "Not installed" } },
+                            }
+                        }
+                        if let Some(category) = &component.category {
+                            div { class: "component-details-row",
+                                span { class: "component-details-label", "Category:" }
+                                span { "{category}" }
+                            }
+                        }
+                        if !component.authors.is_empty() {
+                            div { class: "component-details-row",
+                                span { class: "component-details-label", "Credits:" }
+                                span {
+                                    for (i, author) in component.authors.iter().enumerate() {
+                                        a {
+                                            class: "author-link",
+                                            href: "{author.link}",
+                                            target: "_blank",
+                                            "{author.name}"
+                                        }
+                                        if i + 1 < component.authors.len() { ", " }
+                                    }
+                                }
+                            }
+                        }
+                        if !dependency_names.is_empty() {
+                            div { class: "component-details-row",
+                                span { class: "component-details-label", "Requires:" }
+                                span { class: "dependency-list", {dependency_names.join(", ")} }
+                            }
+                        }
+                        if !conflict_names.is_empty() {
+                            div { class: "component-details-row",
+                                span { class: "component-details-label", "Conflicts:" }
+                                span { class: "incompatibility-list", {conflict_names.join(", ")} }
+                            }
+                        }
+                    }
+
+                    if let Some(changelog) = &component.changelog {
+                        div { class: "component-details-changelog",
+                            h4 { "What's new in {component.version}" }
+                            div {
+                                class: "component-details-markdown",
+                                dangerous_inner_html: "{render_markdown(changelog)}"
+                            }
+                        }
+                    }
+
+                    if page_url.is_some() || !component.links.is_empty() {
+                        div { class: "component-details-links",
+                            if let Some(url) = page_url {
+                                a {
+                                    class: "component-details-link",
+                                    href: "{url}",
+                                    target: "_blank",
+                                    rel: "noopener noreferrer",
+                                    if component.source == "modrinth" && component.homepage.is_none() {
+                                        "Modrinth page ↗"
+                                    } else {
+                                        "Project page ↗"
+                                    }
+                                }
+                            }
+                            for link in component.links.iter() {
+                                a {
+                                    class: "component-details-link",
+                                    href: "{link.url}",
+                                    target: "_blank",
+                                    rel: "noopener noreferrer",
+                                    "{link.name} ↗"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
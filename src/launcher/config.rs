@@ -62,7 +62,7 @@ pub fn update_jvm_args(profile_id: &str, jvm_args: &str) -> Result<(), String> {
             "lastUsed": chrono::Utc::now().to_rfc3339(),
             "icon": "Furnace",
             "javaArgs": jvm_args,
-            "gameDir": format!("{}", get_minecraft_dir().join(format!("instances/{}", profile_id)).display())
+            "gameDir": format!("{}", crate::install_location::vanilla_game_dir(profile_id).display())
         });
         
         if let Some(profiles_obj) = profiles["profiles"].as_object_mut() {
@@ -84,8 +84,8 @@ pub fn update_jvm_args(profile_id: &str, jvm_args: &str) -> Result<(), String> {
 // Get current JVM args for a profile (returns default if not found)
 pub fn get_jvm_args(profile_id: &str) -> Result<String, String> {
     // First check if we have custom arguments in our own config
-    let app_data = crate::get_app_data();
-    let custom_args_path = app_data.join(format!(".WC_OVHL/{}/jvm_args.txt", profile_id));
+    let installer_dir = crate::get_installer_dir();
+    let custom_args_path = installer_dir.join(format!("{}/jvm_args.txt", profile_id));
     
     if custom_args_path.exists() {
         match fs::read_to_string(&custom_args_path) {
@@ -100,7 +100,7 @@ pub fn get_jvm_args(profile_id: &str) -> Result<String, String> {
     }
     
     // Check for JVM args in the manifest
-    let manifest_path = app_data.join(format!(".WC_OVHL/{}/manifest.json", profile_id));
+    let manifest_path = installer_dir.join(format!("{}/manifest.json", profile_id));
     if manifest_path.exists() {
         if let Ok(content) = fs::read_to_string(&manifest_path) {
             if let Ok(manifest) = serde_json::from_str::<Value>(&content) {
@@ -154,8 +154,7 @@ pub fn update_memory_allocation(installation_id: &str, memory_mb: i32) -> Result
     debug!("Updating memory allocation for {} to {}MB", installation_id, memory_mb);
     
     // Load the installation
-    let app_data = get_app_data_dir();
-    let installation_dir = app_data.join(format!(".WC_OVHL/installations/{}", installation_id));
+    let installation_dir = crate::installation::get_installations_dir().join(installation_id);
     let config_path = installation_dir.join("installation.json");
     
     if !config_path.exists() {
@@ -204,15 +203,14 @@ pub fn update_memory_allocation(installation_id: &str, memory_mb: i32) -> Result
         .map_err(|e| format!("Failed to write installation config: {}", e))?;
     
     // Also update the launcher profile
-    update_launcher_profile_memory(installation_id, memory_mb)?;
+    update_launcher_profile_memory(installation_id, memory_mb, None)?;
     
     Ok(())
 }
 
 // Get the JVM args for an installation
 pub fn get_installation_jvm_args(installation_id: &str) -> Result<String, String> {
-    let app_data = get_app_data_dir();
-    let installation_dir = app_data.join(format!(".WC_OVHL/installations/{}", installation_id));
+    let installation_dir = crate::installation::get_installations_dir().join(installation_id);
     
     // First try to read from the installation's config
     let config_path = installation_dir.join("installation.json");
@@ -238,8 +236,7 @@ pub fn get_installation_jvm_args(installation_id: &str) -> Result<String, String
 
 // Save JVM args for an installation
 fn save_jvm_args(installation_id: &str, args: &str) -> Result<(), String> {
-    let app_data = get_app_data_dir();
-    let installation_dir = app_data.join(format!(".WC_OVHL/installations/{}", installation_id));
+    let installation_dir = crate::installation::get_installations_dir().join(installation_id);
     
     // Make sure directory exists
     fs::create_dir_all(&installation_dir)
@@ -287,17 +284,6 @@ fn save_jvm_args(installation_id: &str, args: &str) -> Result<(), String> {
     }
 }
 
-// Helper to get app data directory
-fn get_app_data_dir() -> PathBuf {
-    if cfg!(target_os = "windows") {
-        dirs::config_dir().unwrap_or_else(|| PathBuf::from("."))
-    } else if cfg!(target_os = "macos") {
-        dirs::config_dir().unwrap_or_else(|| PathBuf::from("."))
-    } else {
-        // Linux
-        dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
-    }
-}
 
 // Extract current memory value from JVM args
 pub fn extract_memory_from_args(args: &str) -> Option<i32> {
@@ -330,7 +316,8 @@ pub fn extract_memory_from_args(args: &str) -> Option<i32> {
     Some(4 * 1024)
 }
 
-pub fn update_launcher_profile_memory(installation_id: &str, memory_mb: i32) -> Result<(), String> {
+// `initial_mb` adds an -Xms, without one the JVM picks the starting heap itself
+pub fn update_launcher_profile_memory(installation_id: &str, memory_mb: i32, initial_mb: Option<i32>) -> Result<(), String> {
     use std::fs;
     use serde_json::Value;
     
@@ -365,12 +352,15 @@ pub fn update_launcher_profile_memory(installation_id: &str, memory_mb: i32) ->
                     }
                 }
                 
-                // Add new memory setting (ONLY -Xmx, NO -Xms)
+                // Add new memory setting
                 if memory_mb >= 1024 && memory_mb % 1024 == 0 {
                     args_parts.push(format!("-Xmx{}G", memory_mb / 1024));
                 } else {
                     args_parts.push(format!("-Xmx{}M", memory_mb));
                 }
+                if let Some(initial_mb) = initial_mb {
+                    args_parts.push(format!("-Xms{}M", initial_mb));
+                }
                 
                 let final_args = args_parts.join(" ");
                 profile_obj.insert("javaArgs".to_string(), Value::String(final_args.clone()));
@@ -388,3 +378,33 @@ pub fn update_launcher_profile_memory(installation_id: &str, memory_mb: i32) ->
     
     Ok(())
 }
+
+// Swaps the icon of an existing profile, the rest stays as the install wrote it
+pub fn update_launcher_profile_icon(installation_id: &str, icon: &str) -> Result<(), String> {
+    let profiles_path = crate::get_minecraft_folder().join("launcher_profiles.json");
+
+    if !profiles_path.exists() {
+        return Err("launcher_profiles.json not found".to_string());
+    }
+
+    let content = fs::read_to_string(&profiles_path)
+        .map_err(|e| format!("Failed to read launcher profiles: {}", e))?;
+
+    let mut profiles: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse launcher profiles: {}", e))?;
+
+    let profile = profiles.get_mut("profiles")
+        .and_then(|p| p.get_mut(installation_id))
+        .and_then(|p| p.as_object_mut())
+        .ok_or_else(|| format!("No launcher profile for installation {}", installation_id))?;
+    profile.insert("icon".to_string(), Value::String(icon.to_string()));
+
+    let updated_json = serde_json::to_string_pretty(&profiles)
+        .map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+
+    fs::write(&profiles_path, updated_json)
+        .map_err(|e| format!("Failed to write launcher profiles: {}", e))?;
+
+    debug!("Updated launcher profile icon for {}", installation_id);
+    Ok(())
+}
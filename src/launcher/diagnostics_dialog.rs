@@ -0,0 +1,168 @@
+use dioxus::prelude::*;
+use log::error;
+
+use crate::diagnostics::{self, Diagnostics};
+use crate::installation::Installation;
+
+// Opened from the launch error, gathers what support needs to figure out why the game won't start
+#[component]
+pub fn DiagnosticsDialog(installation: Installation, onclose: EventHandler<()>) -> Element {
+    let collected: Diagnostics = use_hook({
+        let installation = installation.clone();
+        move || diagnostics::collect(&installation)
+    });
+    let summary = use_hook({
+        let installation = installation.clone();
+        let collected = collected.clone();
+        move || diagnostics::summary(&installation, &collected)
+    });
+    let mut is_busy = use_signal(|| false);
+    let mut status = use_signal(|| Option::<String>::None);
+    let mut upload_url = use_signal(|| Option::<String>::None);
+
+    let copy_summary = {
+        let summary = summary.clone();
+        move |_| {
+            let text = serde_json::to_string(&summary).unwrap_or_default();
+            let _ = eval(&format!("navigator.clipboard.writeText({})", text));
+            status.set(Some("Summary copied, paste it into your support request".to_string()));
+        }
+    };
+
+    let save_zip = {
+        let installation = installation.clone();
+        let collected = collected.clone();
+        move |_| {
+            let dialog = rfd::FileDialog::new()
+                .set_title("Save diagnostics")
+                .set_file_name(&format!("{}-diagnostics.zip", installation.name))
+                .add_filter("Zip archive", &["zip"]);
+            let Some(path) = dialog.save_file() else { return };
+            let installation = installation.clone();
+            let collected = collected.clone();
+            is_busy.set(true);
+            spawn(async move {
+                let result = tokio::task::spawn_blocking(move || {
+                    diagnostics::bundle(&installation, &collected, &path).map(|count| (count, path))
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Diagnostics task failed: {}", e)));
+                match result {
+                    Ok((count, path)) => status.set(Some(format!("Saved {} files to {}", count, path.display()))),
+                    Err(e) => {
+                        error!("Failed to save diagnostics: {}", e);
+                        status.set(Some(e));
+                    }
+                }
+                is_busy.set(false);
+            });
+        }
+    };
+
+    let upload = {
+        let collected = collected.clone();
+        move |_| {
+            let collected = collected.clone();
+            is_busy.set(true);
+            spawn(async move {
+                match diagnostics::upload(&crate::CachedHttpClient::new(), &collected).await {
+                    Ok(url) => {
+                        status.set(None);
+                        upload_url.set(Some(url));
+                    }
+                    Err(e) => {
+                        error!("Failed to upload the log: {}", e);
+                        status.set(Some(e));
+                    }
+                }
+                is_busy.set(false);
+            });
+        }
+    };
+
+    let found: Vec<(&'static str, bool)> = vec![
+        ("Crash report", collected.crash_report.is_some()),
+        ("latest.log", collected.latest_log.is_some()),
+        ("Installed manifest", collected.manifest.is_some()),
+        ("Installation settings", collected.installation_file.is_some()),
+    ];
+    let nothing_to_upload = collected.is_empty();
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container diagnostics-dialog",
+                div { class: "modal-header",
+                    h3 { "Diagnostics for {installation.name}" }
+                    button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                }
+
+                div { class: "modal-content",
+                    p { "These files from the last game session help figure out why it didn't start." }
+                    ul { class: "diagnostics-files",
+                        for (name, is_found) in found {
+                            li {
+                                key: "{name}",
+                                class: if is_found { "diagnostics-file found" } else { "diagnostics-file missing" },
+                                {if is_found { "✓ " } else { "✗ " }}
+                                "{name}"
+                            }
+                        }
+                    }
+
+                    textarea {
+                        class: "diagnostics-summary",
+                        readonly: true,
+                        value: "{summary}",
+                    }
+
+                    if let Some(url) = upload_url() {
+                        div { class: "diagnostics-upload",
+                            span { "Uploaded to " }
+                            a {
+                                href: "#",
+                                onclick: {
+                                    let url = url.clone();
+                                    move |evt: MouseEvent| {
+                                        evt.prevent_default();
+                                        if let Err(e) = open::that(&url) {
+                                            error!("Failed to open {}: {}", url, e);
+                                        }
+                                    }
+                                },
+                                "{url}"
+                            }
+                        }
+                    }
+
+                    if let Some(text) = status() {
+                        p { class: "diagnostics-status", "{text}" }
+                    }
+
+                    p { class: "diagnostics-note",
+                        "Uploading makes the log public on mclo.gs. Logs can contain your Minecraft username and folder names."
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        onclick: copy_summary,
+                        "Copy Summary"
+                    }
+                    button {
+                        class: "cancel-button",
+                        disabled: is_busy(),
+                        onclick: save_zip,
+                        "Save as Zip"
+                    }
+                    button {
+                        class: "update-proceed-button",
+                        disabled: is_busy() || nothing_to_upload,
+                        onclick: upload,
+                        {if is_busy() { "Working..." } else { "Upload to mclo.gs" }}
+                    }
+                }
+            }
+        }
+    }
+}
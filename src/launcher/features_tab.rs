@@ -1,8 +1,9 @@
 use dioxus::prelude::*;
 use crate::universal::{ModComponent, UniversalManifest};
 use crate::preset::{Preset, find_preset_by_id};
+use super::component_details::ComponentDetails;
 use log::debug;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 
@@ -59,6 +60,7 @@ pub fn FeaturesTab(
     universal_manifest: Option<UniversalManifest>,
     presets: Vec<Preset>,
     enabled_features: Signal<Vec<String>>,
+    installed_features: Signal<Vec<String>>,
     selected_preset: Signal<Option<String>>,
     filter_text: Signal<String>,
     installation_id: String,
@@ -78,7 +80,119 @@ pub fn FeaturesTab(
     
     // Track if we've initialized from session state (not mutable)
     let session_initialized = use_signal(|| false);
+
+    // What the last install actually put on disk, for the details panes
+    let installed_versions = use_hook({
+        let installation_id = installation_id.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .map(|installation| installation.installed_component_versions())
+                .unwrap_or_default()
+        }
+    });
     
+    // Hidden components are only listed once the user opted in for this installation
+    let mut show_experimental = use_signal({
+        let installation_id = installation_id.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .map(|installation| installation.show_experimental)
+                .unwrap_or(false)
+        }
+    });
+    let mut confirm_experimental = use_signal(|| false);
+
+    // Starred components, pinned to the top of the list
+    let mut favorites = use_signal({
+        let installation_id = installation_id.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .map(|installation| installation.favorite_components)
+                .unwrap_or_default()
+        }
+    });
+    // Components an update changed since the user last looked at them
+    let mut changed_components = use_signal({
+        let installation_id = installation_id.clone();
+        let installed_versions = installed_versions.clone();
+        move || match crate::installation::load_installation(&installation_id) {
+            Ok(mut installation) => {
+                if let Err(e) = installation.remember_component_versions(&installed_versions) {
+                    log::error!("Failed to save reviewed component versions: {}", e);
+                }
+                installation.changed_components(&installed_versions)
+            }
+            Err(_) => HashSet::new(),
+        }
+    });
+    let toggle_favorite = {
+        let installation_id = installation_id.clone();
+        move |component_id: String| {
+            match crate::installation::load_installation(&installation_id) {
+                Ok(mut installation) => match installation.toggle_favorite(&component_id) {
+                    Ok(()) => favorites.set(installation.favorite_components),
+                    Err(e) => log::error!("Failed to save favorite: {}", e),
+                },
+                Err(e) => log::error!("Failed to load installation for favorite: {}", e),
+            }
+        }
+    };
+    let mut review_components = {
+        let installation_id = installation_id.clone();
+        let installed_versions = installed_versions.clone();
+        move |ids: Vec<String>| {
+            let ids: Vec<String> = ids.into_iter().filter(|id| changed_components.read().contains(id)).collect();
+            if ids.is_empty() {
+                return;
+            }
+            match crate::installation::load_installation(&installation_id) {
+                Ok(mut installation) => {
+                    if let Err(e) = installation.mark_components_reviewed(&installed_versions, &ids) {
+                        log::error!("Failed to save reviewed components: {}", e);
+                    }
+                    changed_components.with_mut(|changed| changed.retain(|id| !ids.contains(id)));
+                }
+                Err(e) => log::error!("Failed to load installation for review: {}", e),
+            }
+        }
+    };
+    let review_component = {
+        let mut review_components = review_components.clone();
+        move |id: String| review_components(vec![id])
+    };
+
+    // Facets next to the search box
+    let mut category_facets = use_signal(Vec::<String>::new);
+    let mut optional_only = use_signal(|| false);
+    let mut installed_only = use_signal(|| false);
+    let feature_filter = FeatureFilter {
+        text: filter_text.read().clone(),
+        categories: category_facets.read().clone(),
+        optional_only: optional_only(),
+        installed_only: installed_only(),
+        installed: installed_versions.keys().cloned().collect(),
+    };
+    // Counts per category ignore the category facet itself, so the other chips say what picking
+    // them would add
+    let (category_counts, matching_count, total_count) = universal_manifest.as_ref()
+        .map(|manifest| {
+            let components = listed_components(manifest, show_experimental());
+            let without_categories = FeatureFilter { categories: Vec::new(), ..feature_filter.clone() };
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for component in &components {
+                let count = counts.entry(feature_category(component)).or_insert(0);
+                if without_categories.score(component).is_some() {
+                    *count += 1;
+                }
+            }
+            let matching = components.iter().filter(|c| feature_filter.score(c).is_some()).count();
+            (counts, matching, components.len())
+        })
+        .unwrap_or_default();
+    let has_experimental = universal_manifest.as_ref().map_or(false, |manifest| {
+        manifest.get_all_optional_components().iter().any(|c| c.hidden)
+    });
+
     // Initialize preset state based on installation OR session state
     use_effect({
         let installation_id = installation_id_for_effect.clone();
@@ -221,14 +335,61 @@ pub fn FeaturesTab(
         }
     };
     
+    // Switching to a saved profile replaces the whole selection, like picking a preset
+    let apply_profile = {
+        let installation_id = installation_id.clone();
+        let mut enabled_features = enabled_features.clone();
+        let mut selected_preset = selected_preset.clone();
+        move |features: Vec<String>| {
+            enabled_features.set(features.clone());
+            selected_preset.set(None);
+            set_session_state(&installation_id, None, features.clone());
+            match crate::installation::load_installation(&installation_id) {
+                Ok(mut installation) => {
+                    installation.apply_feature_profile(features);
+                    if let Err(e) = installation.save() {
+                        log::error!("Failed to save the applied profile: {}", e);
+                    }
+                }
+                Err(e) => log::error!("Failed to load installation for the profile: {}", e),
+            }
+        }
+    };
+
+    // A newer version of the installation's preset, reviewed change by change before adopting it
+    let preset_update: Option<Preset> = crate::installation::load_installation(&installation_id)
+        .ok()
+        .and_then(|installation| presets.iter().find(|p| crate::preset_diff::has_update(&installation, p)).cloned());
+    let mut show_preset_update = use_signal(|| false);
+    let preset_update_adopted = {
+        let installation_id = installation_id.clone();
+        let mut enabled_features = enabled_features.clone();
+        move |features: Vec<String>| {
+            enabled_features.set(features.clone());
+            set_session_state(&installation_id, selected_preset.read().clone(), features);
+            show_preset_update.set(false);
+        }
+    };
+
+    // Estimate from the manifest's size_bytes, only shown once the pack declares some
+    let selection_size: Option<(String, usize)> = universal_manifest.as_ref()
+        .map(|manifest| manifest.selection_size(&enabled_features.read()))
+        .filter(|(bytes, _)| *bytes > 0)
+        .map(|(bytes, unknown)| (crate::backup::format_bytes(bytes), unknown));
+
+    // Undo snackbar for bulk actions and for toggles that turned off conflicting components
+    let mut batch_undo = use_signal(|| Option::<(Vec<String>, String)>::None);
+    let mut batch_generation = use_signal(|| 0u32);
+
     // Handle toggling a feature with dependency checking
     let toggle_feature = move |feature_id: String| {
         // Clone values at the start of the closure
         let manifest_for_deps = universal_manifest_for_toggle.clone();
         let installation_id_local = installation_id_for_toggle.clone();
         let presets_local = presets_for_toggle.clone();
+        let previous = enabled_features.read().clone();
         
-        enabled_features.with_mut(|features| {
+        let conflict_message = enabled_features.with_mut(|features| {
             let is_enabling = !features.contains(&feature_id);
             
             if is_enabling {
@@ -252,6 +413,27 @@ pub fn FeaturesTab(
                                 }
                             }
                         }
+                        // One of requires_any has to come along, the first that doesn't conflict
+                        if !component.requires_any_met(features) {
+                            if let Some(option) = component.requires_any.iter().find(|o| !manifest.conflict_between(o, &feature_id)) {
+                                debug!("Auto-enabling {} to satisfy requires_any of {}", option, feature_id);
+                                features.push(option.clone());
+                            }
+                        }
+                    }
+
+                    // Whatever conflicts with the new picks gets turned off, then what depended on it
+                    let added: Vec<String> = features.iter().filter(|id| !previous.contains(id)).cloned().collect();
+                    let dropped = drop_conflicts(manifest, features, &added);
+                    if !dropped.is_empty() {
+                        let name = |id: &String| manifest.component(id).map_or(id.clone(), |c| c.name.clone());
+                        let feature_name = name(&feature_id);
+                        return Some(format!(
+                            "Turned off {}, {} conflicts with {}",
+                            dropped.iter().map(name).collect::<Vec<_>>().join(", "),
+                            if dropped.len() == 1 { "it" } else { "they" },
+                            feature_name
+                        ));
                     }
                 }
             } else {
@@ -276,10 +458,25 @@ pub fn FeaturesTab(
                         debug!("Auto-disabling dependent feature: {} (depends on {})", dep_feat, feature_id);
                         features.retain(|id| id != &dep_feat);
                     }
+                    drop_broken(manifest, features, &mut vec![feature_id.clone()]);
                 }
             }
+            None
         });
 
+        if let Some(message) = conflict_message {
+            debug!("{}", message);
+            batch_undo.set(Some((previous, message)));
+            let generation = batch_generation() + 1;
+            batch_generation.set(generation);
+            spawn(async move {
+                tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                if batch_generation() == generation {
+                    batch_undo.set(None);
+                }
+            });
+        }
+
         // Save to session state
         let current_preset = selected_preset.read().clone();
         let current_features = enabled_features.read().clone();
@@ -296,6 +493,255 @@ pub fn FeaturesTab(
         }
     };
     
+    // Components that carry an acknowledgement note: id -> (name, note)
+    let ack_notes: HashMap<String, (String, String)> = universal_manifest.as_ref()
+        .map(collect_acknowledgement_notes)
+        .unwrap_or_default();
+    
+    // Notes this installation hasn't confirmed yet, "Enable All" skips these
+    let unacknowledged: HashSet<String> = {
+        let installation = crate::installation::load_installation(&installation_id).ok();
+        ack_notes.iter()
+            .filter(|(id, (_, note))| installation.as_ref().map_or(true, |i| i.needs_acknowledgement(id, note)))
+            .map(|(id, _)| id.clone())
+            .collect()
+    };
+    
+    // Component waiting for the user to confirm its note: (id, name, note)
+    let mut pending_ack = use_signal(|| Option::<(String, String, String)>::None);
+    
+    // Enabling a component with an unconfirmed note opens the acknowledgement dialog instead
+    let request_toggle = {
+        let mut toggle_feature = toggle_feature.clone();
+        let ack_notes = ack_notes.clone();
+        let unacknowledged = unacknowledged.clone();
+        move |feature_id: String| {
+            let is_enabling = !enabled_features.read().contains(&feature_id);
+            if is_enabling && unacknowledged.contains(&feature_id) {
+                if let Some((name, note)) = ack_notes.get(&feature_id) {
+                    debug!("Component {} requires acknowledgement before enabling", feature_id);
+                    pending_ack.set(Some((feature_id, name.clone(), note.clone())));
+                    return;
+                }
+            }
+            toggle_feature(feature_id);
+        }
+    };
+    
+    let mut toggle_after_ack = toggle_feature.clone();
+    let installation_id_for_ack = installation_id.clone();
+
+    // Bulk actions on the search results, with one undo for the whole batch
+    let persist_batch = {
+        let installation_id = installation_id.clone();
+        let presets = presets.clone();
+        let mut enabled_features = enabled_features.clone();
+        move |previous: Vec<String>, features: Vec<String>| {
+            enabled_features.set(features.clone());
+            persist_features(&installation_id, &presets, selected_preset.read().clone(), &previous, features);
+        }
+    };
+    let shown_ids: Vec<String> = universal_manifest.as_ref()
+        .map(|manifest| shown_optional_ids(manifest, &feature_filter, show_experimental()))
+        .unwrap_or_default();
+    let changed_count = changed_components.read().len();
+    let shown_enabled = shown_ids.iter().filter(|id| enabled_features.read().contains(id)).count();
+    let run_batch = {
+        let manifest = universal_manifest.clone();
+        let shown_ids = shown_ids.clone();
+        let unacknowledged = unacknowledged.clone();
+        let mut persist_batch = persist_batch.clone();
+        move |enable: bool| {
+            let Some(manifest) = &manifest else {
+                return;
+            };
+            let previous = enabled_features.read().clone();
+            let result = apply_batch(manifest, &previous, &shown_ids, enable, &unacknowledged);
+            let names: HashMap<String, String> = manifest.get_all_optional_components()
+                .into_iter()
+                .map(|c| (c.id, c.name))
+                .collect();
+            let message = batch_summary(&result, enable, &names);
+            debug!("Bulk {}: {}", if enable { "enable" } else { "disable" }, message);
+            if result.changed.is_empty() {
+                batch_undo.set(Some((previous, message)));
+                return;
+            }
+            persist_batch(previous.clone(), result.features);
+            batch_undo.set(Some((previous, message)));
+
+            // Hide the snackbar after a while unless another batch replaced it
+            let generation = batch_generation() + 1;
+            batch_generation.set(generation);
+            spawn(async move {
+                tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+                if batch_generation() == generation {
+                    batch_undo.set(None);
+                }
+            });
+        }
+    };
+    let mut enable_shown = run_batch.clone();
+    let mut disable_shown = run_batch;
+    let mut undo_batch = persist_batch.clone();
+
+    // Shader quality picks between the tiers' shaderpacks, they aren't listed as features
+    let shader_tiers = universal_manifest.as_ref().map(|m| m.shader_tiers.clone()).unwrap_or_default();
+    let active_tier = crate::universal::active_shader_tier(&shader_tiers, &enabled_features.read()).map(|t| t.id.clone());
+    let select_tier = {
+        let shader_tiers = shader_tiers.clone();
+        let mut persist_batch = persist_batch.clone();
+        move |tier_id: String| {
+            let Some(tier) = shader_tiers.iter().find(|t| t.id == tier_id) else {
+                return;
+            };
+            let tier_components = crate::universal::shader_tier_components(&shader_tiers);
+            let previous = enabled_features.read().clone();
+            let mut features: Vec<String> = previous.iter().filter(|id| !tier_components.contains(*id)).cloned().collect();
+            features.extend(tier.shaderpacks.iter().cloned());
+            debug!("Selecting shader quality {}", tier.id);
+            persist_batch(previous, features);
+        }
+    };
+
+    // On-demand content (the music pack) is downloaded or deleted right away in installed packs
+    let on_demand: Vec<ModComponent> = universal_manifest.as_ref()
+        .map(|m| m.on_demand_components().into_iter().cloned().collect())
+        .unwrap_or_default();
+    let mut on_demand_sizes = use_signal({
+        let installation_id = installation_id.clone();
+        let ids: Vec<String> = on_demand.iter().map(|c| c.id.clone()).collect();
+        move || {
+            let installation = crate::installation::load_installation(&installation_id).ok();
+            ids.into_iter()
+                .filter_map(|id| {
+                    let size = crate::on_demand::installed_size(installation.as_ref()?, &id)?;
+                    Some((id, size))
+                })
+                .collect::<HashMap<String, u64>>()
+        }
+    });
+    let mut on_demand_busy = use_signal(|| Option::<String>::None);
+    let mut on_demand_progress = use_signal(|| Option::<(u64, Option<u64>)>::None);
+    let mut on_demand_message = use_signal(|| Option::<String>::None);
+    let run_on_demand = {
+        let installation_id = installation_id.clone();
+        let manifest = universal_manifest.clone();
+        let mut persist_batch = persist_batch.clone();
+        move |component: ModComponent, enable: bool| {
+            if on_demand_busy.read().is_some() {
+                return;
+            }
+            let Some(manifest) = manifest.clone() else {
+                return;
+            };
+            let mut installation = match crate::installation::load_installation(&installation_id) {
+                Ok(installation) => installation,
+                Err(e) => {
+                    log::error!("Failed to load installation for {}: {}", component.id, e);
+                    return;
+                }
+            };
+
+            // Nothing on disk yet, it's downloaded with the rest of the pack
+            if !installation.installed {
+                let previous = enabled_features.read().clone();
+                let mut features: Vec<String> = previous.iter().filter(|id| **id != component.id).cloned().collect();
+                if enable {
+                    features.push(component.id.clone());
+                }
+                persist_batch(previous, features);
+                return;
+            }
+
+            on_demand_busy.set(Some(component.id.clone()));
+            on_demand_message.set(None);
+            if enable {
+                // Polls the download while it runs, big packs take a while
+                let name = component.name.clone();
+                spawn(async move {
+                    while on_demand_busy.read().is_some() {
+                        on_demand_progress.set(crate::partial_download::byte_progress(&name));
+                        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+                    }
+                    on_demand_progress.set(None);
+                });
+            }
+            let installation_id = installation_id.clone();
+            spawn(async move {
+                let result = if enable {
+                    let http_client = crate::CachedHttpClient::new();
+                    crate::on_demand::install(&http_client, &mut installation, &manifest, &component.id).await
+                } else {
+                    crate::on_demand::remove(&mut installation, &component.id)
+                };
+                match result {
+                    Ok(bytes) => {
+                        let update = |features: &mut Vec<String>| {
+                            features.retain(|id| *id != component.id);
+                            if enable {
+                                features.push(component.id.clone());
+                            }
+                        };
+                        enabled_features.with_mut(update);
+                        installed_features.with_mut(update);
+                        set_session_state(&installation_id, selected_preset.read().clone(), enabled_features.read().clone());
+                        on_demand_sizes.with_mut(|sizes| {
+                            if enable {
+                                sizes.insert(component.id.clone(), bytes);
+                            } else {
+                                sizes.remove(&component.id);
+                            }
+                        });
+                        let size = crate::backup::format_bytes(bytes);
+                        on_demand_message.set(Some(if enable {
+                            format!("Installed {} ({})", component.name, size)
+                        } else {
+                            format!("Removed {}, freed {}", component.name, size)
+                        }));
+                    }
+                    Err(e) => {
+                        log::error!("On-demand {} of {} failed: {}", if enable { "install" } else { "removal" }, component.id, e);
+                        on_demand_message.set(Some(format!("{} failed: {}", component.name, e)));
+                    }
+                }
+                on_demand_busy.set(None);
+            });
+        }
+    };
+
+    let set_experimental = {
+        let installation_id = installation_id.clone();
+        let manifest = universal_manifest.clone();
+        let mut persist_batch = persist_batch;
+        move |show: bool| {
+            match crate::installation::load_installation(&installation_id) {
+                Ok(mut installation) => {
+                    if let Err(e) = installation.set_show_experimental(show) {
+                        log::error!("Failed to save experimental features setting: {}", e);
+                    }
+                }
+                Err(e) => log::error!("Failed to load installation for experimental features: {}", e),
+            }
+            show_experimental.set(show);
+            confirm_experimental.set(false);
+
+            // Components the user can no longer see shouldn't stay enabled
+            if !show {
+                if let Some(manifest) = &manifest {
+                    let hidden = experimental_ids(manifest);
+                    let previous = enabled_features.read().clone();
+                    if previous.iter().any(|id| hidden.contains(id)) {
+                        let features = previous.iter().filter(|id| !hidden.contains(id)).cloned().collect();
+                        persist_batch(previous, features);
+                    }
+                }
+            }
+        }
+    };
+    let mut enable_experimental = set_experimental.clone();
+    let mut disable_experimental = set_experimental;
+    
     // Button hover states
     let mut custom_button_hover = use_signal(|| false);
     let mut trending_button_hover = use_signal(Vec::<String>::new);
@@ -309,6 +755,109 @@ pub fn FeaturesTab(
     
     rsx! {
         div { class: "features-tab",
+            // Undo for the last bulk action
+            if let Some((previous, message)) = batch_undo() {
+                div { class: "undo-snackbar",
+                    span { class: "undo-snackbar-message", "{message}" }
+                    button {
+                        class: "undo-snackbar-button",
+                        onclick: move |_| {
+                            let current = enabled_features.read().clone();
+                            undo_batch(current, previous.clone());
+                            batch_undo.set(None);
+                        },
+                        "Undo"
+                    }
+                    button {
+                        class: "undo-snackbar-close",
+                        onclick: move |_| batch_undo.set(None),
+                        "×"
+                    }
+                }
+            }
+
+            // Acknowledgement dialog for components with a rollout note
+            if let Some((ack_id, ack_name, ack_note)) = pending_ack() {
+                div { class: "modal-overlay",
+                    div { class: "modal-container acknowledgement-dialog",
+                        div { class: "modal-header",
+                            h3 { "BEFORE ENABLING {ack_name}" }
+                            button { 
+                                class: "modal-close",
+                                onclick: move |_| pending_ack.set(None),
+                                "×"
+                            }
+                        }
+                        
+                        div { class: "modal-content",
+                            div { class: "warning-message",
+                                p { "{ack_note}" }
+                            }
+                        }
+                        
+                        div { class: "modal-footer",
+                            button { 
+                                class: "cancel-button",
+                                onclick: move |_| pending_ack.set(None),
+                                "CANCEL"
+                            }
+                            button { 
+                                class: "update-proceed-button",
+                                onclick: move |_| {
+                                    match crate::installation::load_installation(&installation_id_for_ack) {
+                                        Ok(mut installation) => {
+                                            if let Err(e) = installation.acknowledge_note(&ack_id, &ack_note) {
+                                                log::error!("Failed to save acknowledgement: {}", e);
+                                            }
+                                        },
+                                        Err(e) => log::error!("Failed to load installation for acknowledgement: {}", e),
+                                    }
+                                    pending_ack.set(None);
+                                    toggle_after_ack(ack_id.clone());
+                                },
+                                "I UNDERSTAND, ENABLE"
+                            }
+                        }
+                    }
+                }
+            }
+            
+            // Warning before listing experimental components
+            if confirm_experimental() {
+                div { class: "modal-overlay",
+                    div { class: "modal-container acknowledgement-dialog",
+                        div { class: "modal-header",
+                            h3 { "SHOW EXPERIMENTAL FEATURES" }
+                            button {
+                                class: "modal-close",
+                                onclick: move |_| confirm_experimental.set(false),
+                                "×"
+                            }
+                        }
+
+                        div { class: "modal-content",
+                            div { class: "warning-message",
+                                p { "Experimental features are still being worked on. They can break your game, change without notice or disappear in a later update." }
+                                p { "Please mention that they are enabled when reporting problems." }
+                            }
+                        }
+
+                        div { class: "modal-footer",
+                            button {
+                                class: "cancel-button",
+                                onclick: move |_| confirm_experimental.set(false),
+                                "CANCEL"
+                            }
+                            button {
+                                class: "update-proceed-button",
+                                onclick: move |_| enable_experimental(true),
+                                "I UNDERSTAND, SHOW THEM"
+                            }
+                        }
+                    }
+                }
+            }
+
             // PRESETS section header
             div { class: "section-divider with-title", 
                 span { class: "divider-title", "PRESETS" }
@@ -318,6 +867,28 @@ pub fn FeaturesTab(
                 "Choose a preset configuration or customize individual features below."
             }
             
+            if let Some(preset) = preset_update.clone() {
+                div { class: "preset-update-notice",
+                    span { "{preset.name} has a new version." }
+                    button {
+                        class: "preset-update-review",
+                        onclick: move |_| show_preset_update.set(true),
+                        "Review Changes"
+                    }
+                }
+
+                if show_preset_update() {
+                    crate::launcher::PresetUpdateDialog {
+                        installation_id: installation_id.clone(),
+                        preset: preset.clone(),
+                        manifest: universal_manifest.clone(),
+                        current: enabled_features.read().clone(),
+                        onclose: move |_| show_preset_update.set(false),
+                        onadopted: preset_update_adopted,
+                    }
+                }
+            }
+
             // Presets grid
             div { class: "presets-grid",
                 // Custom preset (no preset selected)
@@ -505,6 +1076,130 @@ pub fn FeaturesTab(
                 }
             }
 
+            crate::launcher::FeatureProfilesSection {
+                installation_id: installation_id.clone(),
+                enabled_features,
+                onapply: apply_profile,
+            }
+
+            // SHADER QUALITY section
+            if !shader_tiers.is_empty() {
+                div { class: "section-divider with-title",
+                    span { class: "divider-title", "SHADER QUALITY" }
+                }
+
+                div { class: "shader-tier-selector",
+                    for tier in shader_tiers.clone() {
+                        {
+                            let is_active = active_tier.as_ref() == Some(&tier.id);
+                            let tier_id = tier.id.clone();
+                            let description = tier.description.clone().unwrap_or_default();
+                            let mut select_tier = select_tier.clone();
+                            rsx! {
+                                button {
+                                    key: "{tier.id}",
+                                    class: if is_active { "shader-tier active" } else { "shader-tier" },
+                                    title: "{description}",
+                                    onclick: move |_| select_tier(tier_id.clone()),
+                                    "{tier.name}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if active_tier.is_none() {
+                    p { class: "section-description",
+                        "Your current shader selection doesn't match a quality level, pick one to reset it."
+                    }
+                }
+            }
+
+            // ON-DEMAND CONTENT section
+            if !on_demand.is_empty() {
+                div { class: "section-divider with-title",
+                    span { class: "divider-title", "ON-DEMAND CONTENT" }
+                }
+
+                p { class: "section-description",
+                    "Large extras like the music pack are downloaded as soon as you turn them on and deleted when you turn them off."
+                }
+
+                div { class: "on-demand-list",
+                    for component in on_demand.clone() {
+                        {
+                            let is_enabled = enabled_features.read().contains(&component.id);
+                            let size = on_demand_sizes.read().get(&component.id).copied();
+                            let is_busy = on_demand_busy.read().as_ref() == Some(&component.id);
+                            let size_label = size.map(crate::backup::format_bytes).unwrap_or_default();
+                            let description = component.description.clone().unwrap_or_default();
+                            let progress_label = match on_demand_progress() {
+                                Some((received, Some(total))) if total > 0 => format!(
+                                    "{} / {} ({}%)",
+                                    crate::backup::format_bytes(received),
+                                    crate::backup::format_bytes(total),
+                                    received * 100 / total
+                                ),
+                                Some((received, _)) => crate::backup::format_bytes(received),
+                                None => "Starting...".to_string(),
+                            };
+                            let progress_percent = match on_demand_progress() {
+                                Some((received, Some(total))) if total > 0 => received * 100 / total,
+                                _ => 0,
+                            };
+                            let component_for_click = component.clone();
+                            let mut run_on_demand = run_on_demand.clone();
+                            rsx! {
+                                div {
+                                    key: "{component.id}",
+                                    class: "on-demand-item",
+                                    div { class: "on-demand-info",
+                                        span { class: "on-demand-name", "{component.name}" }
+                                        if !description.is_empty() {
+                                            span { class: "on-demand-description", "{description}" }
+                                        }
+                                        if size.is_some() {
+                                            span { class: "on-demand-size", "Using {size_label} of disk space" }
+                                        }
+                                    }
+                                    if is_busy && !is_enabled {
+                                        div { class: "on-demand-progress",
+                                            div { class: "on-demand-progress-bar",
+                                                div {
+                                                    class: "on-demand-progress-fill",
+                                                    style: "width: {progress_percent}%",
+                                                }
+                                            }
+                                            span { class: "on-demand-progress-label", "{progress_label}" }
+                                        }
+                                    } else if is_busy {
+                                        span { class: "on-demand-progress-label", "Removing..." }
+                                    } else if is_enabled {
+                                        button {
+                                            class: "on-demand-button remove",
+                                            disabled: on_demand_busy.read().is_some(),
+                                            onclick: move |_| run_on_demand(component_for_click.clone(), false),
+                                            {if size.is_some() { "Remove to free space" } else { "Turn off" }}
+                                        }
+                                    } else {
+                                        button {
+                                            class: "on-demand-button",
+                                            disabled: on_demand_busy.read().is_some(),
+                                            onclick: move |_| run_on_demand(component_for_click.clone(), true),
+                                            "Install"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(message) = on_demand_message() {
+                    p { class: "on-demand-message", "{message}" }
+                }
+            }
+
             // FEATURES section
             div { class: "optional-features-wrapper",
                 div { class: "section-divider with-title", 
@@ -514,6 +1209,15 @@ pub fn FeaturesTab(
                 p { class: "section-description", 
                     "Customize individual features to create your perfect experience."
                 }
+
+                if let Some((size, unknown)) = selection_size.clone() {
+                    p { class: "selection-size",
+                        "This selection will use ~{size}"
+                        if unknown > 0 {
+                            " plus {unknown} component(s) without a known size"
+                        }
+                    }
+                }
                                 
                 // Centered expand/collapse button
                 button { 
@@ -558,6 +1262,120 @@ pub fn FeaturesTab(
                             }
                         }
                     }
+
+                    // Category chips, picking several shows all of them
+                    if category_counts.len() > 1 {
+                        div { class: "feature-facets",
+                            for (category, count) in category_counts.clone() {
+                                {
+                                    let selected = category_facets.read().contains(&category);
+                                    let chip_category = category.clone();
+                                    rsx! {
+                                        button {
+                                            key: "{category}",
+                                            class: if selected { "feature-facet-chip selected" } else { "feature-facet-chip" },
+                                            disabled: count == 0 && !selected,
+                                            onclick: move |_| {
+                                                let mut facets = category_facets.write();
+                                                if let Some(position) = facets.iter().position(|c| *c == chip_category) {
+                                                    facets.remove(position);
+                                                } else {
+                                                    facets.push(chip_category.clone());
+                                                }
+                                            },
+                                            "{category}"
+                                            span { class: "feature-facet-count", "{count}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "feature-facet-options",
+                        label { class: "option-item",
+                            input {
+                                r#type: "checkbox",
+                                checked: optional_only(),
+                                onchange: move |evt| optional_only.set(evt.value() == "true"),
+                            }
+                            span { "Optional only" }
+                        }
+                        label { class: "option-item",
+                            input {
+                                r#type: "checkbox",
+                                checked: installed_only(),
+                                onchange: move |evt| installed_only.set(evt.value() == "true"),
+                            }
+                            span { "Installed only" }
+                        }
+                        if feature_filter.is_active() {
+                            span { class: "feature-result-count",
+                                "{matching_count} of {total_count} components"
+                            }
+                            button {
+                                class: "feature-facet-reset",
+                                onclick: move |_| {
+                                    filter_text.set(String::new());
+                                    category_facets.set(Vec::new());
+                                    optional_only.set(false);
+                                    installed_only.set(false);
+                                },
+                                "Clear filters"
+                            }
+                        }
+                    }
+
+                    // Opt-in for the manifest's hidden components
+                    if has_experimental || show_experimental() {
+                        label { class: "option-item experimental-toggle",
+                            input {
+                                r#type: "checkbox",
+                                checked: show_experimental(),
+                                onchange: move |evt| {
+                                    if evt.value() == "true" {
+                                        confirm_experimental.set(true);
+                                    } else {
+                                        disable_experimental(false);
+                                    }
+                                }
+                            }
+                            span { "Show experimental features" }
+                        }
+                    }
+
+                    // Badged components from the last update, until they're looked at
+                    if changed_count > 0 {
+                        div { class: "feature-changed-notice",
+                            span { "{changed_count} components changed in the last update, open them to see what's new" }
+                            button {
+                                class: "feature-facet-reset",
+                                onclick: move |_| review_components(changed_components.read().iter().cloned().collect()),
+                                "Mark all as seen"
+                            }
+                        }
+                    }
+
+                    // Bulk actions for whatever the search currently shows
+                    if feature_filter.is_active() && !shown_ids.is_empty() {
+                        div { class: "feature-bulk-actions",
+                            span { class: "feature-bulk-count",
+                                "{shown_ids.len()} shown, {shown_enabled} enabled"
+                            }
+                            button {
+                                class: "feature-bulk-button enable",
+                                disabled: shown_enabled == shown_ids.len(),
+                                onclick: move |_| enable_shown(true),
+                                "Enable all shown"
+                            }
+                            button {
+                                class: "feature-bulk-button disable",
+                                disabled: shown_enabled == 0,
+                                onclick: move |_| disable_shown(false),
+                                "Disable all shown"
+                            }
+                        }
+                    }
                     
                     // Features content
                     {
@@ -565,8 +1383,15 @@ pub fn FeaturesTab(
                             render_all_features_sections(
                                 manifest.clone(),
                                 enabled_features.clone(),
-                                filter_text.clone(),
-                                toggle_feature
+                                feature_filter.clone(),
+                                unacknowledged.clone(),
+                                installed_versions.clone(),
+                                show_experimental(),
+                                favorites.read().clone(),
+                                changed_components.read().clone(),
+                                request_toggle,
+                                toggle_favorite.clone(),
+                                review_component.clone()
                             )
                         } else {
                             rsx! {
@@ -583,14 +1408,368 @@ pub fn FeaturesTab(
     }
 }
 
-fn render_all_features_sections(
-    manifest: UniversalManifest,
-    enabled_features: Signal<Vec<String>>,
-    filter_text: Signal<String>,
-    toggle_feature: impl FnMut(String) + Clone + 'static,
-) -> Element {
-    let filter = filter_text.read().to_lowercase();
-    
+// Saves a feature change made outside the per-card toggle, keeping the preset tracking in sync
+fn persist_features(
+    installation_id: &str,
+    presets: &[Preset],
+    selected_preset: Option<String>,
+    previous: &[String],
+    features: Vec<String>,
+) {
+    set_session_state(installation_id, selected_preset, features.clone());
+    if let Ok(mut installation) = crate::installation::load_installation(installation_id) {
+        for id in previous.iter().filter(|id| !features.contains(id)) {
+            installation.toggle_feature_with_tracking(id, false, presets);
+        }
+        for id in features.iter().filter(|id| !previous.contains(id)) {
+            installation.toggle_feature_with_tracking(id, true, presets);
+        }
+        installation.enabled_features = features.clone();
+        installation.pending_features = features;
+        installation.modified = true;
+        if let Err(e) = installation.save() {
+            log::error!("Failed to save feature change: {}", e);
+        }
+    }
+}
+
+/// Toggles a single component from outside the features tab (the command palette), with the same
+/// dependency and conflict handling as the bulk actions. Returns a short summary for the user.
+pub fn toggle_component(
+    manifest: &UniversalManifest,
+    installation_id: &str,
+    presets: &[Preset],
+    mut enabled_features: Signal<Vec<String>>,
+    selected_preset: Option<String>,
+    feature_id: &str,
+) -> String {
+    let previous = enabled_features.read().clone();
+    let enable = !previous.iter().any(|id| id == feature_id);
+    let unacknowledged: HashSet<String> = {
+        let installation = crate::installation::load_installation(installation_id).ok();
+        collect_acknowledgement_notes(manifest)
+            .into_iter()
+            .filter(|(id, (_, note))| installation.as_ref().map_or(true, |i| i.needs_acknowledgement(id, note)))
+            .map(|(id, _)| id)
+            .collect()
+    };
+    let result = apply_batch(manifest, &previous, &[feature_id.to_string()], enable, &unacknowledged);
+    let names: HashMap<String, String> = manifest.get_all_optional_components()
+        .into_iter()
+        .map(|c| (c.id, c.name))
+        .collect();
+    let summary = batch_summary(&result, enable, &names);
+    if !result.changed.is_empty() {
+        enabled_features.set(result.features.clone());
+        persist_features(installation_id, presets, selected_preset, &previous, result.features);
+    }
+    summary
+}
+
+// Search text plus the facets next to it. The list, the result counts and the bulk actions all
+// go through `score`, so "all shown" means exactly what's on screen
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FeatureFilter {
+    text: String,
+    // Empty means every category
+    categories: Vec<String>,
+    optional_only: bool,
+    installed_only: bool,
+    // Ids the last install put on disk
+    installed: HashSet<String>,
+}
+
+impl FeatureFilter {
+    fn is_active(&self) -> bool {
+        !self.text.trim().is_empty() || !self.categories.is_empty() || self.optional_only || self.installed_only
+    }
+
+    // None when the component is filtered out, otherwise how well it matches the search
+    fn score(&self, comp: &ModComponent) -> Option<i64> {
+        if self.optional_only && !comp.optional {
+            return None;
+        }
+        if self.installed_only && !self.installed.contains(&comp.id) {
+            return None;
+        }
+        if !self.categories.is_empty() && !self.categories.contains(&feature_category(comp)) {
+            return None;
+        }
+        // Every word has to match somewhere
+        let text = self.text.to_lowercase();
+        text.split_whitespace().map(|term| term_score(comp, term)).sum()
+    }
+}
+
+fn term_score(comp: &ModComponent, term: &str) -> Option<i64> {
+    let name = fuzzy_score(term, &comp.name.to_lowercase()).map(|score| score * 3);
+    let id = fuzzy_score(term, &comp.id.to_lowercase());
+    // Descriptions are long enough for almost any letters to appear in order, so no fuzzy there
+    let description = comp.description.as_ref()
+        .filter(|desc| desc.to_lowercase().contains(term))
+        .map(|_| 10);
+    let category = comp.category.as_ref()
+        .filter(|cat| cat.to_lowercase().contains(term))
+        .map(|_| 20);
+    [name, id, description, category].into_iter().flatten().max()
+}
+
+// Substring matches score best, earlier ones more. Otherwise the letters have to appear in order
+// ("sodum" finds "sodium"), and the more of them are next to each other the better
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    if let Some(position) = text.find(query) {
+        return Some(100 - position.min(50) as i64);
+    }
+    let mut score = 0;
+    let mut next_index = None;
+    let mut chars = text.char_indices();
+    for wanted in query.chars() {
+        let (index, found) = chars.find(|(_, c)| *c == wanted)?;
+        score += if next_index == Some(index) { 3 } else { 1 };
+        next_index = Some(index + found.len_utf8());
+    }
+    // Too scattered to be what was meant
+    (score * 2 >= query.chars().count() as i64 * 3).then_some(score)
+}
+
+// Optional components currently shown, the only ones bulk actions touch
+fn shown_optional_ids(manifest: &UniversalManifest, filter: &FeatureFilter, show_experimental: bool) -> Vec<String> {
+    let tier_components = crate::universal::shader_tier_components(&manifest.shader_tiers);
+    manifest.get_all_optional_components()
+        .into_iter()
+        .filter(|comp| comp.id != "default" && (show_experimental || !comp.hidden) && filter.score(comp).is_some())
+        .filter(|comp| !tier_components.contains(&comp.id) && !comp.on_demand)
+        .map(|comp| comp.id)
+        .collect()
+}
+
+fn experimental_ids(manifest: &UniversalManifest) -> HashSet<String> {
+    manifest.get_all_optional_components()
+        .into_iter()
+        .filter(|comp| comp.hidden)
+        .map(|comp| comp.id)
+        .collect()
+}
+
+// Turns off enabled components that conflict with `added`, then whatever needed them. Returns
+// everything that was turned off
+fn drop_conflicts(manifest: &UniversalManifest, features: &mut Vec<String>, added: &[String]) -> Vec<String> {
+    let mut dropped: Vec<String> = features.iter()
+        .filter(|id| !added.contains(id) && added.iter().any(|a| manifest.conflict_between(a, id)))
+        .cloned()
+        .collect();
+    if dropped.is_empty() {
+        return dropped;
+    }
+    features.retain(|id| !dropped.contains(id));
+    drop_broken(manifest, features, &mut dropped);
+    dropped
+}
+
+// Turns off what lost a dependency in `removed` or has no requires_any option left, adding them
+// to `removed`, until nothing changes
+fn drop_broken(manifest: &UniversalManifest, features: &mut Vec<String>, removed: &mut Vec<String>) {
+    loop {
+        let current = features.clone();
+        let broken: Vec<String> = current.iter()
+            .filter(|id| manifest.component(id).map_or(false, |c| {
+                !c.requires_any_met(&current)
+                    || c.dependencies.as_ref().map_or(false, |deps| deps.iter().any(|dep| removed.contains(dep)))
+            }))
+            .cloned()
+            .collect();
+        if broken.is_empty() {
+            break;
+        }
+        for id in broken {
+            debug!("Auto-disabling {}, it needs something that was turned off", id);
+            features.retain(|f| f != &id);
+            removed.push(id);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct BatchResult {
+    features: Vec<String>,
+    changed: Vec<String>,
+    // Pulled in or dropped because of dependencies
+    dependencies: Vec<String>,
+    // Skipped ids with the reason
+    skipped: Vec<(String, String)>,
+}
+
+// Applies a whole batch at once: dependencies are resolved and conflicts checked against the
+// final selection instead of toggle by toggle, so the order of the shown components doesn't matter
+fn apply_batch(
+    manifest: &UniversalManifest,
+    current: &[String],
+    ids: &[String],
+    enable: bool,
+    unacknowledged: &HashSet<String>,
+) -> BatchResult {
+    let components: HashMap<String, ModComponent> = manifest.get_all_optional_components()
+        .into_iter()
+        .map(|comp| (comp.id.clone(), comp))
+        .collect();
+    let mut features: Vec<String> = current.to_vec();
+    let mut result = BatchResult::default();
+
+    if enable {
+        // Everything the batch wants on, dependencies included
+        let mut wanted: Vec<String> = Vec::new();
+        let mut queue: Vec<String> = ids.iter().filter(|id| !features.contains(id)).cloned().collect();
+        while let Some(id) = queue.pop() {
+            if wanted.contains(&id) || features.contains(&id) {
+                continue;
+            }
+            if unacknowledged.contains(&id) {
+                result.skipped.push((id, "needs its note confirmed first".to_string()));
+                continue;
+            }
+            if let Some(deps) = components.get(&id).and_then(|c| c.dependencies.as_ref()) {
+                for dep in deps {
+                    if !ids.contains(dep) && !features.contains(dep) && !wanted.contains(dep) {
+                        result.dependencies.push(dep.clone());
+                    }
+                    queue.push(dep.clone());
+                }
+            }
+            // No requires_any option on or picked yet, bring the first one
+            if let Some(component) = components.get(&id) {
+                let picked: Vec<String> = features.iter().chain(ids.iter()).chain(wanted.iter()).cloned().collect();
+                if !component.requires_any_met(&picked) {
+                    if let Some(option) = component.requires_any.first() {
+                        result.dependencies.push(option.clone());
+                        queue.push(option.clone());
+                    }
+                }
+            }
+            wanted.push(id);
+        }
+
+        // Drop anything that conflicts with what's already on or with an earlier pick of the batch
+        let mut accepted: Vec<String> = Vec::new();
+        for id in wanted {
+            let conflict = features.iter().chain(accepted.iter()).find(|enabled| manifest.conflict_between(&id, enabled));
+            match conflict {
+                Some(other) => {
+                    let name = components.get(other).map_or(other.clone(), |c| c.name.clone());
+                    result.skipped.push((id.clone(), format!("conflicts with {}", name)));
+                }
+                None => accepted.push(id),
+            }
+        }
+
+        // A component whose dependency got skipped can't be enabled either
+        loop {
+            let picked: Vec<String> = features.iter().chain(accepted.iter()).cloned().collect();
+            let missing: Vec<String> = accepted.iter()
+                .filter(|id| components.get(*id).map_or(false, |c| {
+                    !c.requires_any_met(&picked)
+                        || c.dependencies.as_ref().map_or(false, |deps| deps.iter().any(|dep| {
+                            dep != "default" && !picked.contains(dep)
+                        }))
+                }))
+                .cloned()
+                .collect();
+            if missing.is_empty() {
+                break;
+            }
+            for id in missing {
+                accepted.retain(|a| a != &id);
+                result.skipped.push((id, "a dependency was skipped".to_string()));
+            }
+        }
+
+        features.extend(accepted.iter().cloned());
+        result.changed = accepted;
+    } else {
+        for id in ids {
+            if features.contains(id) {
+                features.retain(|f| f != id);
+                result.changed.push(id.clone());
+            }
+        }
+        // Anything left that depends on a removed component has to go as well
+        loop {
+            let broken: Vec<String> = features.iter()
+                .filter(|id| components.get(*id).map_or(false, |c| {
+                    !c.requires_any_met(&features)
+                        || c.dependencies.as_ref().map_or(false, |deps| deps.iter().any(|dep| dep != "default" && !features.contains(dep)))
+                }))
+                .cloned()
+                .collect();
+            if broken.is_empty() {
+                break;
+            }
+            for id in broken {
+                debug!("Auto-disabling {} with a disabled dependency", id);
+                features.retain(|f| f != &id);
+                result.changed.push(id.clone());
+                result.dependencies.push(id);
+            }
+        }
+    }
+
+    result.dependencies.sort();
+    result.dependencies.dedup();
+    result.dependencies.retain(|dep| result.changed.contains(dep));
+    result.features = features;
+    result
+}
+
+fn batch_summary(result: &BatchResult, enable: bool, names: &HashMap<String, String>) -> String {
+    let name = |id: &String| names.get(id).cloned().unwrap_or_else(|| id.clone());
+    let direct = result.changed.len() - result.dependencies.len().min(result.changed.len());
+    let mut text = format!("{} {} feature(s)", if enable { "Enabled" } else { "Disabled" }, direct);
+    if !result.dependencies.is_empty() {
+        text.push_str(&format!(
+            ", {} {} dependency",
+            if enable { "plus" } else { "and" },
+            result.dependencies.iter().map(name).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    if !result.skipped.is_empty() {
+        let skipped: Vec<String> = result.skipped.iter()
+            .map(|(id, reason)| format!("{} ({})", name(id), reason))
+            .collect();
+        text.push_str(&format!(". Skipped {}", skipped.join(", ")));
+    }
+    text
+}
+
+fn collect_acknowledgement_notes(manifest: &UniversalManifest) -> HashMap<String, (String, String)> {
+    let mut notes = HashMap::new();
+    for component in manifest.mods.iter()
+        .chain(manifest.shaderpacks.iter())
+        .chain(manifest.resourcepacks.iter())
+    {
+        if let Some(note) = &component.acknowledgement {
+            notes.insert(component.id.clone(), (component.name.clone(), note.clone()));
+        }
+    }
+    for include in &manifest.include {
+        if let Some(note) = &include.acknowledgement {
+            let name = include.name.clone().unwrap_or_else(|| include.id.clone());
+            notes.insert(include.id.clone(), (name, note.clone()));
+        }
+    }
+    for remote in &manifest.remote_include {
+        if let Some(note) = &remote.acknowledgement {
+            let name = remote.name.clone().unwrap_or_else(|| remote.id.clone());
+            notes.insert(remote.id.clone(), (name, note.clone()));
+        }
+    }
+    notes
+}
+
+// Every component the list can show: mods, packs and includes, without the ones picked elsewhere
+// (shader quality tiers, on-demand content) and the experimental ones unless they're turned on
+fn listed_components(manifest: &UniversalManifest, show_experimental: bool) -> Vec<ModComponent> {
     // Collect all components INCLUDING includes and remote includes
     let mut all_components = Vec::new();
     all_components.extend(manifest.mods.iter().cloned());
@@ -629,7 +1808,19 @@ fn render_all_features_sections(
                 category: include.category.clone(),
                 dependencies: include.dependencies.clone(),
                 incompatibilities: None,
+                conflicts_with: Vec::new(),
+                requires_any: Vec::new(),
                 ignore_update: include.ignore_update,
+                acknowledgement: include.acknowledgement.clone(),
+                details: None,
+                homepage: None,
+                changelog: None,
+                links: Vec::new(),
+                hidden: include.hidden,
+                on_demand: false,
+                versions: std::collections::HashMap::new(),
+                mirrors: Vec::new(),
+                size_bytes: None,
             });
         }
     }
@@ -659,29 +1850,95 @@ fn render_all_features_sections(
             category: remote.category.clone(),
             dependencies: remote.dependencies.clone(),
             incompatibilities: None,
+            conflicts_with: Vec::new(),
+            requires_any: Vec::new(),
             ignore_update: remote.ignore_update,
+            acknowledgement: remote.acknowledgement.clone(),
+            details: None,
+            homepage: None,
+            changelog: None,
+            links: Vec::new(),
+            hidden: remote.hidden,
+            on_demand: false,
+            versions: remote.versions.clone(),
+            mirrors: remote.mirrors.clone(),
+            size_bytes: remote.size_bytes,
         });
     }
     
     debug!("Total components after includes: {}", all_components.len());
     debug!("Component IDs: {:?}", all_components.iter().map(|c| &c.id).collect::<Vec<_>>());
     
-    // Filter components
-    let filtered_components = if filter.is_empty() {
-        all_components
-    } else {
-        all_components.into_iter()
-            .filter(|comp| {
-                let name_match = comp.name.to_lowercase().contains(&filter);
-                let desc_match = comp.description.as_ref()
-                    .map_or(false, |desc| desc.to_lowercase().contains(&filter));
-                let category_match = comp.category.as_ref()
-                    .map_or(false, |cat| cat.to_lowercase().contains(&filter));
-                let id_match = comp.id.to_lowercase().contains(&filter);
-                name_match || desc_match || category_match || id_match
-            })
-            .collect()
-    };
+    if !show_experimental {
+        all_components.retain(|comp| !comp.hidden);
+    }
+
+    // Picked with the shader quality selector and the on-demand section instead
+    let tier_components = crate::universal::shader_tier_components(&manifest.shader_tiers);
+    all_components.retain(|comp| !tier_components.contains(&comp.id) && !comp.on_demand);
+
+    all_components
+}
+
+// Category a component is listed under, guessed from its source when the manifest has none
+fn feature_category(component: &ModComponent) -> String {
+    component.category.clone().unwrap_or_else(|| {
+        // Only assign default categories if no category is specified
+        match component.source.as_str() {
+            "modrinth" => "Mods".to_string(),
+            "ddl" | "mediafire" => {
+                // Try to infer from component type or location
+                if component.location.contains("shader") {
+                    "Shaders".to_string()
+                } else if component.location.contains("resource") || component.location.contains("texture") {
+                    "Resource Packs".to_string()
+                } else {
+                    "Mods".to_string()
+                }
+            },
+            // For includes, try to infer from location
+            "include" => {
+                if component.location.contains("config") {
+                    "Configuration".to_string()
+                } else if component.location.contains("options") {
+                    "Settings".to_string()
+                } else if component.location.contains("shader") {
+                    "Shaders".to_string()
+                } else {
+                    "Configuration".to_string()
+                }
+            },
+            "remote_include" => "Remote Content".to_string(),
+            _ => "Other".to_string(),
+        }
+    })
+}
+
+// Name of the section with the starred components
+const FAVORITES_SECTION: &str = "★ Favorites";
+
+fn render_all_features_sections(
+    manifest: UniversalManifest,
+    enabled_features: Signal<Vec<String>>,
+    filter: FeatureFilter,
+    unacknowledged: HashSet<String>,
+    installed_versions: HashMap<String, String>,
+    show_experimental: bool,
+    favorites: Vec<String>,
+    changed: HashSet<String>,
+    toggle_feature: impl FnMut(String) + Clone + 'static,
+    toggle_favorite: impl FnMut(String) + Clone + 'static,
+    review_component: impl FnMut(String) + Clone + 'static,
+) -> Element {
+    // Best matches first while searching, manifest order otherwise
+    let mut scored: Vec<(i64, ModComponent)> = listed_components(&manifest, show_experimental)
+        .into_iter()
+        .filter_map(|comp| filter.score(&comp).map(|score| (score, comp)))
+        .collect();
+    scored.sort_by_key(|(score, _)| -score);
+    // Starred ones first, the sort keeps the order within both groups
+    scored.sort_by_key(|(_, comp)| !favorites.contains(&comp.id));
+    let filtered_components: Vec<ModComponent> = scored.into_iter().map(|(_, comp)| comp).collect();
     
     // Separate into included (default-enabled AND non-optional) and optional
     let (included_components, optional_components): (Vec<_>, Vec<_>) = filtered_components
@@ -704,36 +1961,7 @@ fn render_all_features_sections(
     // Group optional components by category
     let mut categories: std::collections::BTreeMap<String, Vec<ModComponent>> = std::collections::BTreeMap::new();
     for component in optional_components {
-        let category = component.category.clone().unwrap_or_else(|| {
-            // Only assign default categories if no category is specified
-            match component.source.as_str() {
-                "modrinth" => "Mods".to_string(),
-                "ddl" | "mediafire" => {
-                    // Try to infer from component type or location
-                    if component.location.contains("shader") {
-                        "Shaders".to_string()
-                    } else if component.location.contains("resource") || component.location.contains("texture") {
-                        "Resource Packs".to_string()
-                    } else {
-                        "Mods".to_string()
-                    }
-                },
-                // For includes, try to infer from location
-                "include" => {
-                    if component.location.contains("config") {
-                        "Configuration".to_string()
-                    } else if component.location.contains("options") {
-                        "Settings".to_string()
-                    } else if component.location.contains("shader") {
-                        "Shaders".to_string()
-                    } else {
-                        "Configuration".to_string()
-                    }
-                },
-                "remote_include" => "Remote Content".to_string(),
-                _ => "Other".to_string(),
-            }
-        });
+        let category = feature_category(&component);
         
         debug!("Adding component {} to category: {}", component.id, category);
         categories.entry(category).or_insert_with(Vec::new).push(component);
@@ -744,18 +1972,42 @@ fn render_all_features_sections(
     // Rest of the function remains the same...
     let mut included_expanded = use_signal(|| false);
     let mut expanded_categories = use_signal(|| Vec::<String>::new());
+    // Component whose details are open
+    let mut details_for = use_signal(|| None::<ModComponent>);
+    let details_dialog = details_for().map(|component| {
+        let component_name = |id: &String| manifest.component(id).map_or(id.clone(), |c| c.name.clone());
+        let dependency_names: Vec<String> = component.dependencies.iter().flatten().map(component_name).collect();
+        let conflict_names: Vec<String> = component.conflicts().map(component_name).collect();
+        let installed_version = installed_versions.get(&component.id).cloned();
+        (component, installed_version, dependency_names, conflict_names)
+    });
     
     // Check for no results
-    let no_results = categories.is_empty() && included_components.is_empty() && !filter.is_empty();
+    let no_results = categories.is_empty() && included_components.is_empty() && filter.is_active();
     
     if no_results {
+        let message = if filter.text.trim().is_empty() {
+            "No features match the selected filters.".to_string()
+        } else {
+            format!("No features found matching '{}'. Try a different search term.", filter.text.trim())
+        };
         return rsx! {
-            div { class: "no-search-results",
-                "No features found matching '{filter}'. Try a different search term."
-            }
+            div { class: "no-search-results", "{message}" }
         };
     }
     
+    // Starred components get a section on top, they stay in their own category too
+    let favorite_components: Vec<ModComponent> = categories.values()
+        .flatten()
+        .filter(|comp| favorites.contains(&comp.id))
+        .cloned()
+        .collect();
+    let mut sections: Vec<(String, Vec<ModComponent>)> = Vec::new();
+    if !favorite_components.is_empty() {
+        sections.push((FAVORITES_SECTION.to_string(), favorite_components));
+    }
+    sections.extend(categories);
+
     rsx! {
         div { class: "feature-categories",
             // Included Features Section
@@ -794,22 +2046,36 @@ fn render_all_features_sections(
                         div { class: "feature-cards-grid",
                             for component in included_components {
                                 {
+                                    let component_for_details = component.clone();
+                                    let is_changed = changed.contains(&component.id);
+                                    let mut review_func = review_component.clone();
                                     rsx! {
                                         div { 
                                             class: "feature-card feature-included",
+                                            onclick: move |_| {
+                                                review_func(component_for_details.id.clone());
+                                                details_for.set(Some(component_for_details.clone()));
+                                            },
                                             
                                             div { class: "feature-card-header",
                                                 h3 { class: "feature-card-title", "{component.name}" }
+
+                                                if is_changed {
+                                                    span { class: "feature-changed-badge", title: "Changed in the last update", "Changed" }
+                                                }
                                                 
                                                 span {
                                                     class: "feature-toggle-button included-component",
+                                                    span { class: "status-icon", aria_hidden: "true", "●" }
                                                     "Included"
                                                 }
                                             }
                                             
                                             if let Some(description) = &component.description {
-                                                div { class: "feature-card-description", "{description}" }
+                                                div { class: "feature-card-description", "{crate::markdown::plain_text(description)}" }
                                             }
+
+                                            span { class: "component-details-hint", "ⓘ Details" }
                                             
                                             if !component.authors.is_empty() {
                                                 div { class: "feature-authors",
@@ -822,6 +2088,7 @@ fn render_all_features_sections(
                                                                     class: "author-link",
                                                                     href: "{author.link}",
                                                                     target: "_blank",
+                                                                    onclick: move |evt| evt.stop_propagation(),
                                                                     "{author.name}"
                                                                 }
                                                                 if !is_last {
@@ -842,11 +2109,12 @@ fn render_all_features_sections(
             }
             
             // Optional Features by Category
-            for (category_name, components) in categories {
+            for (category_name, components) in sections {
                 {
                     let category_key = category_name.clone();
-                    let is_expanded = expanded_categories.read().contains(&category_key) 
-                                     || !filter.is_empty();
+                    // Favorites start expanded, so for them the list holds whether it was collapsed
+                    let is_expanded = (expanded_categories.read().contains(&category_key) != (category_key == FAVORITES_SECTION))
+                                     || filter.is_active();
                     
                     let enabled_count = enabled_features.read().iter()
                         .filter(|id| components.iter().any(|comp| &comp.id == *id))
@@ -880,6 +2148,7 @@ fn render_all_features_sections(
                                 {
                                     let components_clone = components.clone();
                                     let mut enabled_features = enabled_features.clone();
+                                    let unacknowledged = unacknowledged.clone();
                                     
                                     rsx! {
                                         button {
@@ -902,9 +2171,10 @@ fn render_all_features_sections(
                                                             }
                                                         }
                                                     } else {
-                                                        // Enable all optional components
+                                                        // Enable all optional components, notes still need to be confirmed one by one
                                                         for comp in &components_clone {
-                                                            if comp.id != "default" && comp.optional && !features.contains(&comp.id) {
+                                                            if comp.id != "default" && comp.optional && !features.contains(&comp.id)
+                                                                && !unacknowledged.contains(&comp.id) {
                                                                 features.push(comp.id.clone());
                                                             }
                                                         }
@@ -949,6 +2219,27 @@ fn render_all_features_sections(
                                             let component_id = component.id.clone();
                                             let is_enabled = enabled_features.read().contains(&component_id);
                                             let mut toggle_func = toggle_feature.clone();
+                                            let component_name = |id: &String| manifest.component(id).map_or(id.clone(), |c| c.name.clone());
+                                            let conflict_names: Vec<String> = component.conflicts().map(component_name).collect();
+                                            let requires_any_names: Vec<String> = component.requires_any.iter().map(component_name).collect();
+                                            // Enabled components this one would turn off
+                                            let blocked_by: Vec<String> = if is_enabled {
+                                                Vec::new()
+                                            } else {
+                                                manifest.conflicts_in(&component_id, &enabled_features.read()).iter().map(component_name).collect()
+                                            };
+                                            let conflict_hint = if blocked_by.is_empty() {
+                                                "Can't be enabled together with these".to_string()
+                                            } else {
+                                                format!("Turning this on turns off {}", blocked_by.join(", "))
+                                            };
+                                            let toggle_hint = if blocked_by.is_empty() { String::new() } else { conflict_hint.clone() };
+                                            let component_for_details = component.clone();
+                                            let is_changed = changed.contains(&component_id);
+                                            let is_favorite = favorites.contains(&component_id);
+                                            let mut review_func = review_component.clone();
+                                            let mut favorite_func = toggle_favorite.clone();
+                                            let favorite_id = component_id.clone();
                                             
                                             rsx! {
                                                 div { 
@@ -957,15 +2248,38 @@ fn render_all_features_sections(
                                                     } else {
                                                         "feature-card feature-disabled"
                                                     },
+                                                    onclick: move |_| {
+                                                        review_func(component_for_details.id.clone());
+                                                        details_for.set(Some(component_for_details.clone()));
+                                                    },
                                                     
                                                     div { class: "feature-card-header",
+                                                        button {
+                                                            class: if is_favorite { "feature-favorite-button starred" } else { "feature-favorite-button" },
+                                                            title: if is_favorite { "Remove from favorites" } else { "Add to favorites" },
+                                                            onclick: move |evt| {
+                                                                evt.stop_propagation();
+                                                                favorite_func(favorite_id.clone());
+                                                            },
+                                                            if is_favorite { "★" } else { "☆" }
+                                                        }
+
                                                         h3 { class: "feature-card-title", "{component.name}" }
+
+                                                        if is_changed {
+                                                            span { class: "feature-changed-badge", title: "Changed in the last update", "Changed" }
+                                                        }
+
+                                                        if component.hidden {
+                                                            span { class: "experimental-badge", "Experimental" }
+                                                        }
                                                         
                                                         // Special handling for default/included components
                                                         if component.id == "default" || !component.optional {
                                                             span {
                                                                 class: "feature-toggle-button enabled default-component",
                                                                 style: "cursor: default; opacity: 0.8;",
+                                                                span { class: "status-icon", aria_hidden: "true", "●" }
                                                                 "Included"
                                                             }
                                                         } else {
@@ -975,13 +2289,18 @@ fn render_all_features_sections(
                                                                 } else {
                                                                     "feature-toggle-button disabled"
                                                                 },
-                                                                onclick: move |_| {
+                                                                title: "{toggle_hint}",
+                                                                onclick: move |evt| {
+                                                                    // Toggling shouldn't open the details too
+                                                                    evt.stop_propagation();
                                                                     toggle_func(component_id.clone());
                                                                 },
                                                                 
                                                                 if is_enabled {
+                                                                    span { class: "status-icon", aria_hidden: "true", "✓" }
                                                                     "Enabled"
                                                                 } else {
+                                                                    span { class: "status-icon", aria_hidden: "true", "✕" }
                                                                     "Disabled"
                                                                 }
                                                             }
@@ -990,7 +2309,14 @@ fn render_all_features_sections(
                                                     
                                                     // Description display
                                                     if let Some(description) = &component.description {
-                                                        div { class: "feature-card-description", "{description}" }
+                                                        div { class: "feature-card-description", "{crate::markdown::plain_text(description)}" }
+                                                    }
+
+                                                    span { class: "component-details-hint", "ⓘ Details" }
+                                                    
+                                                    // Rollout note the user has to confirm before enabling
+                                                    if let Some(note) = &component.acknowledgement {
+                                                        div { class: "feature-acknowledgement", "⚠ {note}" }
                                                     }
                                                     
                                                     // Dependencies display
@@ -1004,6 +2330,20 @@ fn render_all_features_sections(
                                                             }
                                                         }
                                                     }
+
+                                                    if !requires_any_names.is_empty() {
+                                                        div { class: "feature-dependencies",
+                                                            "Requires one of: ",
+                                                            span { class: "dependency-list", {requires_any_names.join(", ")} }
+                                                        }
+                                                    }
+
+                                                    if !conflict_names.is_empty() {
+                                                        div { class: "feature-incompatibilities", title: "{conflict_hint}",
+                                                            "Conflicts with: ",
+                                                            span { class: "incompatibility-list", {conflict_names.join(", ")} }
+                                                        }
+                                                    }
                                                     
                                                     // Authors display
                                                     if !component.authors.is_empty() {
@@ -1017,6 +2357,7 @@ fn render_all_features_sections(
                                                                             class: "author-link",
                                                                             href: "{author.link}",
                                                                             target: "_blank",
+                                                                            onclick: move |evt| evt.stop_propagation(),
                                                                             "{author.name}"
                                                                         }
                                                                         if !is_last {
@@ -1037,6 +2378,16 @@ fn render_all_features_sections(
                     }
                 }
             }
+
+            if let Some((component, installed_version, dependency_names, conflict_names)) = details_dialog {
+                ComponentDetails {
+                    component,
+                    installed_version,
+                    dependency_names,
+                    conflict_names,
+                    onclose: move |_| details_for.set(None),
+                }
+            }
         }
     }
 }
@@ -0,0 +1,134 @@
+use dioxus::prelude::*;
+use log::error;
+
+use crate::install_log::{self, InstallLogEntry, Level};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LogFilter {
+    All,
+    Warnings,
+    Errors,
+}
+
+impl LogFilter {
+    fn shows(&self, level: Level) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::Warnings => level != Level::Info,
+            LogFilter::Errors => level == Level::Error,
+        }
+    }
+}
+
+// What install() and update() recorded for this installation, newest first
+#[component]
+pub fn InstallLogDialog(installation_id: String, onclose: EventHandler<()>) -> Element {
+    let mut entries = use_signal({
+        let installation_id = installation_id.clone();
+        move || install_log::read(&installation_id)
+    });
+    let mut filter = use_signal(|| LogFilter::All);
+
+    let loaded: Vec<InstallLogEntry> = match &*entries.read() {
+        Ok(entries) => entries.clone(),
+        Err(e) => {
+            let e = e.clone();
+            return rsx! {
+                div { class: "modal-overlay",
+                    div { class: "modal-container install-log-dialog",
+                        div { class: "modal-header",
+                            h3 { "Install Log" }
+                            button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                        }
+                        div { class: "modal-content",
+                            div { class: "error-message", "{e}" }
+                        }
+                    }
+                }
+            };
+        }
+    };
+
+    let warn_count = loaded.iter().filter(|e| e.level == Level::Warn).count();
+    let error_count = loaded.iter().filter(|e| e.level == Level::Error).count();
+    let current_filter = filter();
+    let shown: Vec<InstallLogEntry> = loaded.iter().rev().filter(|e| current_filter.shows(e.level)).cloned().collect();
+    let is_empty = loaded.is_empty();
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container install-log-dialog",
+                div { class: "modal-header",
+                    h3 { "Install Log" }
+                    button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                }
+
+                div { class: "modal-content",
+                    div { class: "install-log-filters",
+                        button {
+                            class: if current_filter == LogFilter::All { "install-log-filter active" } else { "install-log-filter" },
+                            onclick: move |_| filter.set(LogFilter::All),
+                            "All ({loaded.len()})"
+                        }
+                        button {
+                            class: if current_filter == LogFilter::Warnings { "install-log-filter active" } else { "install-log-filter" },
+                            onclick: move |_| filter.set(LogFilter::Warnings),
+                            "Warnings ({warn_count})"
+                        }
+                        button {
+                            class: if current_filter == LogFilter::Errors { "install-log-filter active" } else { "install-log-filter" },
+                            onclick: move |_| filter.set(LogFilter::Errors),
+                            "Errors ({error_count})"
+                        }
+                    }
+
+                    if is_empty {
+                        p { "Nothing logged yet, entries show up after the next install or update." }
+                    } else if shown.is_empty() {
+                        p { "No entries match this filter." }
+                    } else {
+                        div { class: "install-log-entries",
+                            for (index, entry) in shown.into_iter().enumerate() {
+                                {
+                                    let time = entry.timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string();
+                                    let level = entry.level.label();
+                                    let component = entry.component.clone().unwrap_or_default();
+                                    rsx! {
+                                        div {
+                                            key: "{index}",
+                                            class: "install-log-entry {level}",
+                                            span { class: "install-log-time", "{time}" }
+                                            span { class: "install-log-level", "{level}" }
+                                            span { class: "install-log-phase", "{entry.phase}" }
+                                            span { class: "install-log-component", "{component}" }
+                                            span { class: "install-log-outcome", "{entry.outcome}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        disabled: is_empty,
+                        onclick: move |_| {
+                            if let Err(e) = install_log::clear(&installation_id) {
+                                error!("{}", e);
+                            }
+                            entries.set(install_log::read(&installation_id));
+                        },
+                        "Clear Log"
+                    }
+                    button {
+                        class: "update-proceed-button",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
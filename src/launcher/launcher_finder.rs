@@ -261,7 +261,7 @@ pub fn get_launcher_path() -> Result<PathBuf, String> {
 
 /// Save the launcher path to cache
 fn save_launcher_path_cache(path: &Path) {
-    let cache_file = crate::get_app_data().join(".WC_OVHL/launcher_path.txt");
+    let cache_file = crate::get_installer_dir().join("launcher_path.txt");
     if let Err(e) = std::fs::write(&cache_file, path.to_string_lossy().as_bytes()) {
         warn!("Failed to cache launcher path: {}", e);
     }
@@ -269,7 +269,7 @@ fn save_launcher_path_cache(path: &Path) {
 
 /// Load the cached launcher path
 fn load_cached_launcher_path() -> Option<PathBuf> {
-    let cache_file = crate::get_app_data().join(".WC_OVHL/launcher_path.txt");
+    let cache_file = crate::get_installer_dir().join("launcher_path.txt");
     if let Ok(content) = std::fs::read_to_string(&cache_file) {
         let path = PathBuf::from(content.trim());
         Some(path)
@@ -277,3 +277,77 @@ fn load_cached_launcher_path() -> Option<PathBuf> {
         None
     }
 }
+
+// Executable names of MultiMC and Prism Launcher next to their data folder (portable installs,
+// and the usual Windows/Linux tarball layout)
+fn instance_launcher_names(kind: &str) -> &'static [&'static str] {
+    match (kind, cfg!(target_os = "windows")) {
+        ("PrismLauncher", true) => &["prismlauncher.exe", "PrismLauncher.exe"],
+        ("PrismLauncher", false) => &["prismlauncher", "PrismLauncher"],
+        (_, true) => &["MultiMC.exe"],
+        (_, false) => &["MultiMC"],
+    }
+}
+
+// Installed copies, kept apart from the data folder
+#[cfg(target_os = "windows")]
+fn installed_instance_launchers(kind: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if kind == "PrismLauncher" {
+        if let Some(local) = dirs::data_local_dir() {
+            paths.push(local.join("Programs\\PrismLauncher\\prismlauncher.exe"));
+        }
+        if let Some(home) = dirs::home_dir() {
+            paths.push(home.join("scoop\\apps\\prismlauncher\\current\\prismlauncher.exe"));
+        }
+        paths.push(PathBuf::from("C:\\Program Files\\PrismLauncher\\prismlauncher.exe"));
+    }
+    paths
+}
+
+#[cfg(target_os = "macos")]
+fn installed_instance_launchers(kind: &str) -> Vec<PathBuf> {
+    let app = match kind {
+        "PrismLauncher" => "Prism Launcher.app/Contents/MacOS/prismlauncher",
+        _ => "MultiMC.app/Contents/MacOS/MultiMC",
+    };
+    let mut paths = vec![PathBuf::from("/Applications").join(app)];
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join("Applications").join(app));
+    }
+    paths
+}
+
+#[cfg(target_os = "linux")]
+fn installed_instance_launchers(kind: &str) -> Vec<PathBuf> {
+    let binary = match kind {
+        "PrismLauncher" => "prismlauncher",
+        _ => "multimc",
+    };
+    let mut paths: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).map(|dir| dir.join(binary)).collect())
+        .unwrap_or_default();
+    // Flatpak exports a wrapper script that runs the app, arguments are passed through
+    if kind == "PrismLauncher" {
+        if let Some(home) = dirs::home_dir() {
+            paths.push(home.join(".local/share/flatpak/exports/bin/org.prismlauncher.PrismLauncher"));
+        }
+        paths.push(PathBuf::from("/var/lib/flatpak/exports/bin/org.prismlauncher.PrismLauncher"));
+    }
+    paths
+}
+
+/// MultiMC or Prism Launcher executable for `--launch`, `kind` is the name of its data folder
+/// ("MultiMC" or "PrismLauncher").
+pub fn find_instance_launcher(kind: &str, data_dir: &Path) -> Option<PathBuf> {
+    let found = instance_launcher_names(kind)
+        .iter()
+        .map(|name| data_dir.join(name))
+        .chain(installed_instance_launchers(kind))
+        .find(|path| path.is_file());
+    match &found {
+        Some(path) => debug!("Found {} at: {}", kind, path.display()),
+        None => warn!("Could not find the {} executable", kind),
+    }
+    found
+}
@@ -5,21 +5,38 @@ mod process;
 
 // Correct imports
 pub use config::{update_jvm_args, get_jvm_args, update_memory_allocation, extract_memory_from_args, update_launcher_profile_memory};
-pub use process::launch_modpack;
+pub use process::{launch_modpack, launches_instances, subscribe_exits, is_running, format_play_time};
 
 // Component modules - features_tab remains public
 mod integrated_features;
 pub mod features_tab;
 mod performance_tab;
 mod settings_tab;
+mod backup_browser;
+mod orphan_files_dialog;
+mod install_log_dialog;
+mod verify_dialog;
+mod diagnostics_dialog;
+mod profiles_section;
+mod preset_update_dialog;
+mod component_details;
+mod screenshots_tab;
+mod worlds_section;
 
 mod launcher_finder;
-pub use launcher_finder::get_launcher_path;
+pub use launcher_finder::{find_minecraft_launcher, get_launcher_path};
 
 // Updated exports
-pub use features_tab::{FeaturesTab, clear_session_state};
+pub use features_tab::{FeaturesTab, clear_session_state, toggle_component};
 pub use performance_tab::PerformanceTab;
 pub use settings_tab::SettingsTab;
+pub use orphan_files_dialog::OrphanFilesDialog;
+pub use install_log_dialog::InstallLogDialog;
+pub use verify_dialog::VerifyDialog;
+pub use diagnostics_dialog::DiagnosticsDialog;
+pub use profiles_section::FeatureProfilesSection;
+pub use preset_update_dialog::PresetUpdateDialog;
+pub use screenshots_tab::ScreenshotsTab;
 
 // Define public feature types needed by other modules
 pub struct FeatureCard;
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+use log::{debug, error};
+
+use crate::backup::format_bytes;
+use crate::installation::Installation;
+use crate::orphan_files::{self, OrphanFile};
+
+// Dry run of the cleanup: lists the unused files and deletes only what stays ticked
+#[component]
+pub fn OrphanFilesDialog(
+    installation: Installation,
+    onclose: EventHandler<()>,
+    oncleaned: EventHandler<String>,
+) -> Element {
+    let orphans = use_hook({
+        let installation = installation.clone();
+        move || orphan_files::find_for_installation(&installation)
+    });
+    // Everything starts ticked, unticking keeps a file
+    let mut kept = use_signal(HashSet::<String>::new);
+    let mut is_removing = use_signal(|| false);
+    let mut remove_error = use_signal(|| Option::<String>::None);
+
+    let orphans: Vec<OrphanFile> = match orphans {
+        Ok(orphans) => orphans,
+        Err(e) => {
+            return rsx! {
+                div { class: "modal-overlay",
+                    div { class: "modal-container orphan-files-dialog",
+                        div { class: "modal-header",
+                            h3 { "Clean Up Unused Files" }
+                            button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                        }
+                        div { class: "modal-content",
+                            div { class: "error-message", "Failed to look for unused files: {e}" }
+                        }
+                    }
+                }
+            };
+        }
+    };
+
+    let keep = kept.read().clone();
+    let picked: Vec<OrphanFile> = orphans.iter().filter(|o| !keep.contains(&o.relative)).cloned().collect();
+    let picked_count = picked.len();
+    let picked_size = format_bytes(picked.iter().map(|o| o.size).sum());
+
+    let remove_picked = move |_| {
+        let picked = picked.clone();
+        is_removing.set(true);
+        remove_error.set(None);
+        spawn(async move {
+            let result = tokio::task::spawn_blocking(move || orphan_files::remove(&picked))
+                .await
+                .map_err(|e| format!("Cleanup stopped unexpectedly: {}", e))
+                .and_then(|r| r);
+            match result {
+                Ok(count) => {
+                    debug!("Removed {} unused file(s)", count);
+                    oncleaned.call(format!("Removed {} unused file(s)", count));
+                }
+                Err(e) => {
+                    error!("Cleanup failed: {}", e);
+                    remove_error.set(Some(e));
+                }
+            }
+            is_removing.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container orphan-files-dialog",
+                div { class: "modal-header",
+                    h3 { "Clean Up Unused Files" }
+                    button {
+                        class: "modal-close",
+                        disabled: *is_removing.read(),
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    if let Some(error) = &*remove_error.read() {
+                        div { class: "error-message", "{error}" }
+                    }
+
+                    if orphans.is_empty() {
+                        p { "Nothing to clean up, every file in mods, resourcepacks and shaderpacks belongs to the modpack." }
+                    } else {
+                        p {
+                            "These files aren't part of {installation.name} anymore, they're left over from features you turned off or older versions. "
+                            "Untick anything you added yourself."
+                        }
+                        div { class: "orphan-files-list",
+                            for orphan in orphans.clone() {
+                                {
+                                    let is_checked = !keep.contains(&orphan.relative);
+                                    let relative = orphan.relative.clone();
+                                    let size = format_bytes(orphan.size);
+                                    rsx! {
+                                        label {
+                                            key: "{orphan.relative}",
+                                            class: "orphan-file",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: is_checked,
+                                                onchange: move |_| {
+                                                    let mut current = kept.write();
+                                                    if !current.remove(&relative) {
+                                                        current.insert(relative.clone());
+                                                    }
+                                                }
+                                            }
+                                            span { class: "orphan-file-path", "{orphan.relative}" }
+                                            span { class: "orphan-file-size", "{size}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        disabled: *is_removing.read(),
+                        onclick: move |_| onclose.call(()),
+                        "Keep All"
+                    }
+                    if !orphans.is_empty() {
+                        button {
+                            class: "update-proceed-button",
+                            disabled: *is_removing.read() || picked_count == 0,
+                            onclick: remove_picked,
+                            {if *is_removing.read() { "Removing...".to_string() } else { format!("Remove {} ({})", picked_count, picked_size) }}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,62 +3,55 @@ use tokio::time::sleep;
 use std::time::Duration;
 use log::{debug, error};
 
-// Helper function to get system memory
+use crate::universal::{ModComponent, UniversalManifest};
+
+// Total physical memory in MB
 fn get_system_memory() -> Option<i32> {
-    #[cfg(target_os = "windows")]
-    {
-        // Use wmic command on Windows
-        if let Ok(output) = std::process::Command::new("wmic")
-            .args(&["computersystem", "get", "TotalPhysicalMemory"])
-            .output() 
-        {
-            if let Ok(output_str) = String::from_utf8(output.stdout) {
-                // Parse the output to get total memory in bytes
-                if let Some(mem_str) = output_str.lines().nth(1) {
-                    if let Ok(mem_bytes) = mem_str.trim().parse::<u64>() {
-                        // Convert bytes to MB
-                        return Some((mem_bytes / (1024 * 1024)) as i32);
-                    }
-                }
-            }
-        }
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    let total_mb = system.total_memory() / (1024 * 1024);
+    (total_mb > 0).then_some(total_mb as i32)
+}
+
+const MIN_MEMORY: i32 = 1024;
+const STEP: i32 = 512;
+
+// Highest the slider goes: 70% of the computer's memory, 8 GB at most. More doesn't help
+// Minecraft and starves the OS
+fn max_memory(system_mb: Option<i32>) -> i32 {
+    match system_mb {
+        Some(mem) => (mem * 70 / 100).clamp(MIN_MEMORY, 8 * 1024) / STEP * STEP,
+        None => 8 * 1024,
     }
-    
-    #[cfg(target_os = "linux")]
-    {
-        // Use /proc/meminfo on Linux
-        if let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") {
-            for line in meminfo.lines() {
-                if line.starts_with("MemTotal:") {
-                    if let Some(mem_kb_str) = line.split_whitespace().nth(1) {
-                        if let Ok(mem_kb) = mem_kb_str.parse::<u64>() {
-                            // Convert KB to MB
-                            return Some((mem_kb / 1024) as i32);
-                        }
-                    }
-                }
-            }
+}
+
+// What Auto mode picks, as (Xmx, Xms). 3 GB for the pack, a GB more with a shaderpack, half a GB
+// per enabled resourcepack and per ten other optional components, never more than half of the
+// computer's memory
+fn recommend_memory(system_mb: Option<i32>, manifest: Option<&UniversalManifest>, enabled_features: &[String]) -> (i32, i32) {
+    let mut recommended = 3072;
+    if let Some(manifest) = manifest {
+        let is_enabled = |comp: &ModComponent| {
+            comp.id != "default" && (enabled_features.contains(&comp.id) || (comp.default_enabled && !comp.optional))
+        };
+        if manifest.shaderpacks.iter().any(|comp| is_enabled(comp)) {
+            recommended += 1024;
         }
+        recommended += manifest.resourcepacks.iter().filter(|comp| comp.optional && is_enabled(comp)).count() as i32 * 512;
+        let is_pack = |comp: &ModComponent| manifest.shaderpacks.iter().chain(&manifest.resourcepacks).any(|pack| pack.id == comp.id);
+        let other_optional = manifest.get_all_optional_components().iter()
+            .filter(|comp| is_enabled(comp) && !is_pack(comp))
+            .count() as i32;
+        recommended += other_optional / 10 * 512;
     }
-    
-    #[cfg(target_os = "macos")]
-    {
-        // Use sysctl on macOS
-        if let Ok(output) = std::process::Command::new("sysctl")
-            .args(&["-n", "hw.memsize"])
-            .output() 
-        {
-            if let Ok(output_str) = String::from_utf8(output.stdout) {
-                if let Ok(mem_bytes) = output_str.trim().parse::<u64>() {
-                    // Convert bytes to MB
-                    return Some((mem_bytes / (1024 * 1024)) as i32);
-                }
-            }
-        }
+    let mut upper = max_memory(system_mb);
+    if let Some(mem) = system_mb {
+        upper = upper.min(mem / 2).max(MIN_MEMORY);
     }
-    
-    // Default fallback
-    None
+    let xmx = recommended.clamp(MIN_MEMORY, upper) / STEP * STEP;
+    // Starting at half saves the JVM growing the heap in small steps during loading
+    let xms = (xmx / 2 / STEP * STEP).max(MIN_MEMORY).min(xmx);
+    (xmx, xms)
 }
 
 // Format memory value for display
@@ -75,51 +68,42 @@ pub fn PerformanceTab(
     memory_allocation: Signal<i32>,
     java_args: Signal<String>,
     installation_id: String,
+    universal_manifest: Option<UniversalManifest>,
+    enabled_features: Signal<Vec<String>>,
 ) -> Element {
-    // State for system memory
-    let mut detected_memory = use_signal(|| None::<i32>);
+    // Probed once, the memory of the computer doesn't change while the installer runs
+    let detected_memory = use_hook(get_system_memory);
     
     // State for showing success message
     let mut show_apply_success = use_signal(|| false);
     
-    // State for recommended memory
-    let mut recommended_memory = use_signal(|| 4096); // Default 4GB recommendation
-    
-    // Try to detect system memory on component load
-    use_effect(move || {
-        if detected_memory.read().is_none() {
-            if let Some(mem) = get_system_memory() {
-                detected_memory.set(Some(mem));
-                
-                // Calculate recommended memory (30% of system, max 4GB)
-                let thirty_percent = (mem * 30) / 100;
-                let recommended = std::cmp::min(4096, thirty_percent);
-                recommended_memory.set(recommended);
-            }
+    let memory_limit = max_memory(detected_memory);
+    let (recommended_memory, recommended_initial) =
+        recommend_memory(detected_memory, universal_manifest.as_ref(), &enabled_features.read());
+
+    // Auto mode keeps the allocation on the recommendation, also when features are toggled
+    let mut auto_memory = use_signal({
+        let installation_id = installation_id.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .map(|installation| installation.auto_memory)
+                .unwrap_or(false)
         }
     });
-    
-    // Default memory boundaries
-    let min_memory = 1024;  // Minimum 1GB
-    let max_memory = use_signal(|| 8 * 1024); // Default 8GB max
-    
-    // Update max memory when system memory is available
+    let mut original_auto = use_signal(|| *auto_memory.peek());
     use_effect({
-        let detected_memory = detected_memory.clone();
-        let mut max_memory = max_memory.clone();
-        
+        let universal_manifest = universal_manifest.clone();
         move || {
-            if let Some(mem) = *detected_memory.read() {
-                // Cap at 8GB or 70% of system memory, whichever is less
-                let max_allowed = 8 * 1024; // 8GB in MB
-                let seventy_percent = (mem * 70) / 100;
-                max_memory.set(std::cmp::min(max_allowed, seventy_percent));
+            if !auto_memory() {
+                return;
+            }
+            let (recommended, _) = recommend_memory(detected_memory, universal_manifest.as_ref(), &enabled_features.read());
+            if *memory_allocation.peek() != recommended {
+                memory_allocation.set(recommended);
             }
         }
     });
     
-    let step = 512; // 512MB steps
-    
     // Store original value for comparison to detect changes
     let mut original_memory = use_signal(|| *memory_allocation.read());
     
@@ -141,7 +125,8 @@ pub fn PerformanceTab(
         let current = *memory_allocation.read();
         let original = *original_memory.read();
         debug!("Memory comparison: current={}, original={}, changed={}", current, original, current != original);
-        current != original && original != 0  // Don't show as changed if original is uninitialized
+        (current != original && original != 0)  // Don't show as changed if original is uninitialized
+            || auto_memory() != original_auto()
     };
     
     // Apply memory function
@@ -150,22 +135,27 @@ let apply_memory = {
     let memory_allocation = memory_allocation.clone();
     let mut show_apply_success = show_apply_success.clone();
     let mut original_memory = original_memory.clone();
+    let mut java_args = java_args.clone();
     
     move |_| {
         let current_memory = *memory_allocation.read();
+        let auto = auto_memory();
+        // Auto also sets where the heap starts, a hand-picked size leaves that to the JVM
+        let initial_memory = auto.then_some(recommended_initial.min(current_memory));
         let installation_id_clone = installation_id.clone();
         
-        debug!("Applying memory change: {} MB", current_memory);
+        debug!("Applying memory change: {} MB (auto: {})", current_memory, auto);
         
         spawn(async move {
             // Update the launcher profile
-            match crate::launcher::config::update_launcher_profile_memory(&installation_id_clone, current_memory) {
+            match crate::launcher::config::update_launcher_profile_memory(&installation_id_clone, current_memory, initial_memory) {
                 Ok(_) => {
                     debug!("Successfully updated launcher profile memory");
                     
                     // Update the installation record
                     if let Ok(mut installation) = crate::installation::load_installation(&installation_id_clone) {
                         installation.memory_allocation = current_memory;
+                        installation.auto_memory = auto;
                         
                         // Update java args in installation
                         let mut args_parts: Vec<String> = Vec::new();
@@ -181,13 +171,19 @@ let apply_memory = {
                         } else {
                             args_parts.push(format!("-Xmx{}M", current_memory));
                         }
+                        if let Some(initial_memory) = initial_memory {
+                            args_parts.push(format!("-Xms{}M", initial_memory));
+                        }
                         
                         installation.java_args = args_parts.join(" ");
                         
                         if let Err(e) = installation.save() {
                             error!("Failed to save installation: {}", e);
                         } else {
+                            // The JVM section builds on these, it has to see the new memory arguments
+                            java_args.set(installation.java_args.clone());
                             original_memory.set(current_memory);
+                            original_auto.set(auto);
                             show_apply_success.set(true);
                             
                             tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
@@ -203,39 +199,135 @@ let apply_memory = {
     }
 };
     
+    // JVM arguments: a curated preset with the user's own on top, or everything by hand
+    let saved_jvm = use_hook({
+        let installation_id = installation_id.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .map(|installation| (installation.jvm_preset, installation.custom_java_args))
+                .unwrap_or_default()
+        }
+    });
+    let mut jvm_preset = use_signal(|| saved_jvm.0.clone());
+    let mut custom_args = use_signal(|| match &saved_jvm.0 {
+        Some(_) => saved_jvm.1.clone(),
+        // By hand the box holds the whole arguments, the slider still owns the memory ones
+        None => crate::jvm_presets::compose(None, &java_args.peek(), ""),
+    });
+    let mut jvm_message = use_signal(|| None::<String>);
+    // The Java the game will run on, probing every candidate with `java -version` takes a moment
+    let java_install = use_resource({
+        let installation_id = installation_id.clone();
+        move || {
+            let installation_id = installation_id.clone();
+            async move {
+                let installation = crate::installation::load_installation(&installation_id).ok()?;
+                let version = installation.minecraft_version_override.unwrap_or(installation.minecraft_version);
+                tokio::task::spawn_blocking(move || crate::java::find_compatible(&crate::get_minecraft_folder(), &version))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+        }
+    });
+    let selected_jvm_preset = jvm_preset().as_deref().and_then(crate::jvm_presets::find);
+    let composed_args = crate::jvm_presets::compose(selected_jvm_preset, &custom_args.read(), &java_args.read());
+    let (java_status, jvm_incompatibility) = match &*java_install.read() {
+        None => ("Looking for Java...".to_string(), None),
+        Some(None) => ("No Java for this Minecraft version was found, the launcher brings its own when the game starts".to_string(), None),
+        Some(Some(java)) => (
+            format!("Runs on Java {}{}", java.version, if java.graalvm { " (GraalVM)" } else { "" }),
+            selected_jvm_preset.and_then(|preset| preset.incompatibility(java)),
+        ),
+    };
+    let jvm_preset_value = jvm_preset().unwrap_or_default();
+    let jvm_changed = composed_args != *java_args.read() || jvm_preset() != saved_jvm.0;
+    let apply_jvm_args = {
+        let installation_id = installation_id.clone();
+        let composed_args = composed_args.clone();
+        let mut java_args = java_args.clone();
+        move |_| {
+            let result = crate::installation::load_installation(&installation_id).and_then(|mut installation| {
+                installation.jvm_preset = jvm_preset();
+                installation.custom_java_args = if jvm_preset().is_some() { custom_args() } else { String::new() };
+                installation.java_args = composed_args.clone();
+                installation.save()
+            });
+            match result {
+                Ok(_) => {
+                    java_args.set(composed_args.clone());
+                    // Not every launcher has a profile to update, the next install writes it anyway
+                    if let Err(e) = crate::launcher::update_launcher_profile_jvm_args(&installation_id, &composed_args) {
+                        debug!("Launcher profile not updated: {}", e);
+                    }
+                    jvm_message.set(Some("JVM arguments applied".to_string()));
+                }
+                Err(e) => {
+                    error!("Failed to save JVM arguments: {}", e);
+                    jvm_message.set(Some(e));
+                }
+            }
+        }
+    };
+
+    // Download settings are shared by every installation, they live in the app config
+    let mut config = use_context::<crate::gui::ConfigState>().config;
+    let config_path = use_context::<crate::gui::AppProps>().config_path;
+    let mut download_parallel = use_signal(|| config.peek().network.max_concurrent_downloads);
+    let mut download_bandwidth = use_signal(|| config.peek().network.bandwidth_limit_kbps);
+    let mut download_message = use_signal(|| None::<String>);
+    let downloads_changed = {
+        let network = &config.read().network;
+        *download_parallel.read() != network.max_concurrent_downloads
+            || *download_bandwidth.read() != network.bandwidth_limit_kbps
+    };
+    let apply_downloads = move |_| {
+        let mut new_config = config.read().clone();
+        new_config.network.max_concurrent_downloads = download_parallel();
+        new_config.network.bandwidth_limit_kbps = download_bandwidth();
+        match crate::gui::save_config(&new_config, &config_path) {
+            Ok(_) => {
+                crate::download_queue::configure(&new_config.network);
+                config.set(new_config);
+                download_message.set(Some("Download settings applied to all installations".to_string()));
+            }
+            Err(e) => {
+                error!("Failed to save download settings: {}", e);
+                download_message.set(Some(e));
+            }
+        }
+    };
+    let parallel = download_parallel();
+    let bandwidth = download_bandwidth().map(|v| v.to_string()).unwrap_or_default();
+    let max_parallel = crate::download_queue::MAX_PARALLEL;
+
     // Get system memory display
-    let system_memory_display = match *detected_memory.read() {
+    let system_memory_display = match detected_memory {
         Some(mem) => format_memory_display(mem),
         None => "Unknown".to_string(),
     };
     
     // Calculate percentages safely
-    let memory_percentage = match *detected_memory.read() {
+    let memory_percentage = match detected_memory {
         Some(sys_mem) if sys_mem > 0 => {
             Some((*memory_allocation.read() as f32 / sys_mem as f32) * 100.0)
         },
         _ => None
     };
     
-    let recommended_percentage = match *detected_memory.read() {
+    let recommended_percentage = match detected_memory {
         Some(sys_mem) if sys_mem > 0 => {
-            Some((*recommended_memory.read() as f32 / sys_mem as f32) * 100.0)
+            Some((recommended_memory as f32 / sys_mem as f32) * 100.0)
         },
         _ => None
     };
-    
-    // Create memory markers
-    let markers = vec![
-    ("1 GB", 1024),
-    ("8 GB", 8192),
-    ];
-
-    
+    let over_half = memory_percentage.map_or(false, |percentage| percentage > 50.0);
+    let memory_limit_display = format_memory_display(memory_limit);
     
     rsx! {
         div { class: "performance-tab",
             h2 { "Performance Settings" }
-            p { "Adjust memory allocation for Minecraft to optimize performance." }
+            p { "Adjust memory allocation for Minecraft and how the installer downloads." }
             
             div { class: "performance-section memory-section",
                 h3 { "Memory Allocation" }
@@ -257,23 +349,32 @@ let apply_memory = {
                     }
                 }
                 
+                label { class: "option-item memory-auto-toggle",
+                    input {
+                        r#type: "checkbox",
+                        checked: auto_memory(),
+                        onchange: move |evt| auto_memory.set(evt.value() == "true"),
+                    }
+                    span { "Auto: follow the recommendation for this computer and the enabled features" }
+                }
+                
                 // Memory slider with improved design
                 div { class: "memory-slider-container",
                     input {
                         r#type: "range",
-                        min: "{min_memory}",
-                        max: "{*max_memory.read()}",
-                        step: "{step}",
+                        min: "{MIN_MEMORY}",
+                        max: "{memory_limit}",
+                        step: "{STEP}",
                         value: "{*memory_allocation.read()}",
+                        disabled: auto_memory(),
 style: {
-    let current_mem = *memory_allocation.read();
-    let max_mem = *max_memory.read();
-    let progress = ((current_mem - min_memory) as f32 / (max_mem - min_memory) as f32 * 100.0) as i32;
+    let current_mem = (*memory_allocation.read()).min(memory_limit);
+    let progress = ((current_mem - MIN_MEMORY) as f32 / (memory_limit - MIN_MEMORY).max(1) as f32 * 100.0) as i32;
     format!("--progress: {}%", progress)
 },
                         oninput: move |evt| {
                             if let Ok(value) = evt.value().parse::<i32>() {
-                                memory_allocation.set(value);
+                                memory_allocation.set(value.clamp(MIN_MEMORY, memory_limit));
                             }
                         },
                         class: "memory-slider"
@@ -289,11 +390,17 @@ div { class: "memory-markers",
     div { 
         class: "memory-marker",
         style: "left: 100%;",
-        "8 GB"
+        "{memory_limit_display}"
     }
 }
                 }
 
+                if over_half {
+                    div { class: "memory-warning",
+                        "⚠️ This is more than half of your computer's memory. The game, the launcher and the OS can start competing for it, which causes stutters or crashes."
+                    }
+                }
+
                  div { class: "memory-info-box",
     style: "background-color: rgba(74, 144, 226, 0.2); border-left: 3px solid #4a90e2; padding: 10px; margin: 15px 0; border-radius: 4px;",
     
@@ -324,17 +431,128 @@ div { class: "memory-markers",
                 
                 p { class: "memory-recommendation",
                     {
-                        if detected_memory.read().is_some() {
-                            let rec_text = format!("Recommended: {}", format_memory_display(*recommended_memory.read()));
-                            
-                            if let Some(percentage) = recommended_percentage {
-                                format!("{} (~{}% of your system memory)", rec_text, percentage as i32)
-                            } else {
-                                format!("{} (max 4GB)", rec_text)
-                            }
+                        let rec_text = format!(
+                            "Recommended for the enabled features: {}, starting at {}",
+                            format_memory_display(recommended_memory),
+                            format_memory_display(recommended_initial)
+                        );
+                        if let Some(percentage) = recommended_percentage {
+                            format!("{} (~{}% of your system memory)", rec_text, percentage as i32)
                         } else {
-                            "Recommended: Up to 4GB for optimal performance".to_string()
+                            rec_text
+                        }
+                    }
+                }
+            }
+
+            div { class: "performance-section java-args-section",
+                h3 { "JVM Arguments" }
+                p { class: "java-args-description",
+                    "Pick a tuned set of arguments or write your own. {java_status}."
+                }
+
+                select {
+                    class: "setting-select jvm-preset-select",
+                    value: "{jvm_preset_value}",
+                    onchange: move |evt| {
+                        let picked = Some(evt.value()).filter(|id| !id.is_empty());
+                        if picked.is_none() && jvm_preset().is_some() {
+                            // Going back to editing by hand starts from what the preset produced
+                            let current = crate::jvm_presets::compose(selected_jvm_preset, &custom_args.read(), "");
+                            custom_args.set(current);
+                        } else if picked.is_some() && jvm_preset().is_none() {
+                            custom_args.set(String::new());
                         }
+                        jvm_preset.set(picked);
+                        jvm_message.set(None);
+                    },
+                    option { value: "", "Custom (edit by hand)" }
+                    for preset in crate::jvm_presets::PRESETS {
+                        option { value: "{preset.id}", "{preset.name}" }
+                    }
+                }
+
+                if let Some(preset) = selected_jvm_preset {
+                    p { class: "jvm-preset-description", "{preset.description}" }
+                }
+                if let Some(problem) = &jvm_incompatibility {
+                    div { class: "memory-warning", "⚠️ {problem}" }
+                }
+
+                label { class: "jvm-custom-label",
+                    if selected_jvm_preset.is_some() { "Additional arguments, added after the preset's" } else { "Arguments" }
+                }
+                textarea {
+                    class: "java-args-input",
+                    value: "{custom_args}",
+                    placeholder: "-Dfml.ignorePatchDiscrepancies=true",
+                    oninput: move |evt| {
+                        custom_args.set(evt.value());
+                        jvm_message.set(None);
+                    }
+                }
+                p { class: "jvm-args-preview", "{composed_args}" }
+
+                div { class: "memory-apply-container",
+                    button {
+                        class: if jvm_changed { "memory-apply-button changed" } else { "memory-apply-button" },
+                        disabled: !jvm_changed || jvm_incompatibility.is_some(),
+                        onclick: apply_jvm_args,
+                        "Apply JVM Arguments"
+                    }
+                    if let Some(message) = jvm_message() {
+                        div { class: "apply-success-message", "{message}" }
+                    }
+                }
+            }
+
+            div { class: "performance-section downloads-section",
+                h3 { "Downloads" }
+                p { class: "downloads-description",
+                    "Shared by mods, includes and remote includes of every installation. Applies to downloads started afterwards."
+                }
+
+                div { class: "download-setting",
+                    label { "Parallel downloads: {parallel}" }
+                    input {
+                        r#type: "range",
+                        class: "download-parallel-slider",
+                        min: "1",
+                        max: "{max_parallel}",
+                        value: "{parallel}",
+                        oninput: move |evt| {
+                            if let Ok(value) = evt.value().parse::<usize>() {
+                                download_parallel.set(value.clamp(1, max_parallel));
+                                download_message.set(None);
+                            }
+                        }
+                    }
+                }
+
+                div { class: "download-setting",
+                    label { "Bandwidth limit (KiB/s)" }
+                    input {
+                        r#type: "number",
+                        class: "download-bandwidth-input",
+                        min: "0",
+                        placeholder: "Unlimited",
+                        value: "{bandwidth}",
+                        oninput: move |evt| {
+                            download_bandwidth.set(evt.value().trim().parse::<u64>().ok().filter(|v| *v > 0));
+                            download_message.set(None);
+                        }
+                    }
+                }
+
+                div { class: "memory-apply-container",
+                    button {
+                        class: if downloads_changed { "memory-apply-button changed" } else { "memory-apply-button" },
+                        disabled: !downloads_changed,
+                        onclick: apply_downloads,
+                        "Apply Download Settings"
+                    }
+                    if let Some(message) = download_message() {
+                        div { class: "apply-success-message", "{message}" }
                     }
                 }
             }
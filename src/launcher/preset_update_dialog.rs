@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use dioxus::prelude::*;
+use log::error;
+
+use crate::preset::Preset;
+use crate::preset_diff::{self, PresetChange};
+use crate::universal::UniversalManifest;
+
+// Lists what the new version of the installation's preset turns on and off compared to the
+// current selection, the user picks which of those changes to take over
+#[component]
+pub fn PresetUpdateDialog(
+    installation_id: String,
+    preset: Preset,
+    manifest: Option<UniversalManifest>,
+    current: Vec<String>,
+    onclose: EventHandler<()>,
+    onadopted: EventHandler<Vec<String>>,
+) -> Element {
+    let changes: Vec<PresetChange> = use_hook({
+        let installation_id = installation_id.clone();
+        let preset = preset.clone();
+        let current = current.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .map(|installation| preset_diff::diff(&installation, &current, &preset))
+                .unwrap_or_default()
+        }
+    });
+    let mut picked = use_signal({
+        let changes = changes.clone();
+        move || changes.iter().filter(|c| c.adopt_by_default()).map(|c| c.id.clone()).collect::<HashSet<String>>()
+    });
+    let mut adopt_error = use_signal(|| Option::<String>::None);
+
+    let names: HashMap<String, String> = manifest.as_ref()
+        .map(|m| m.get_all_optional_components().into_iter().map(|c| (c.id, c.name)).collect())
+        .unwrap_or_default();
+    let version = preset.preset_version.clone().unwrap_or_default();
+
+    let adopt = {
+        let changes = changes.clone();
+        let preset = preset.clone();
+        let current = current.clone();
+        move |adopted: Vec<String>| {
+            let result = crate::installation::load_installation(&installation_id).and_then(|mut installation| {
+                let features = preset_diff::adopt(&mut installation, &current, &preset, &changes, &adopted);
+                installation.save()?;
+                Ok(features)
+            });
+            match result {
+                Ok(features) => onadopted.call(features),
+                Err(e) => {
+                    error!("Failed to adopt the preset update: {}", e);
+                    adopt_error.set(Some(e));
+                }
+            }
+        }
+    };
+    let all_ids: Vec<String> = changes.iter().map(|c| c.id.clone()).collect();
+    let mut adopt_all = adopt.clone();
+    let mut adopt_none = adopt.clone();
+    let mut adopt_picked = adopt;
+    let picked_count = picked.read().len();
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container preset-update-dialog",
+                div { class: "modal-header",
+                    h3 { "{preset.name} {version}" }
+                    button { class: "modal-close", onclick: move |_| onclose.call(()), "×" }
+                }
+
+                div { class: "modal-content",
+                    if let Some(error) = adopt_error() {
+                        div { class: "error-message", "{error}" }
+                    }
+
+                    if changes.is_empty() {
+                        p { "The new version of this preset doesn't change anything about your current selection." }
+                    } else {
+                        p { "Pick the changes you want to take over. Features you added yourself stay as they are." }
+                        div { class: "preset-update-changes",
+                            for change in changes.clone() {
+                                {
+                                    let name = names.get(&change.id).cloned().unwrap_or_else(|| change.id.clone());
+                                    let is_picked = picked.read().contains(&change.id);
+                                    let id = change.id.clone();
+                                    rsx! {
+                                        label {
+                                            key: "{change.id}",
+                                            class: if change.enable { "preset-update-change enable" } else { "preset-update-change disable" },
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: is_picked,
+                                                onchange: move |_| {
+                                                    let mut ids = picked.write();
+                                                    if !ids.remove(&id) {
+                                                        ids.insert(id.clone());
+                                                    }
+                                                }
+                                            }
+                                            span { class: "preset-update-action",
+                                                {if change.enable { "Turn on" } else { "Turn off" }}
+                                            }
+                                            span { class: "preset-update-name", "{name}" }
+                                            if change.user_override {
+                                                span { class: "preset-update-note", "you turned this off before" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        onclick: move |_| adopt_none(Vec::new()),
+                        "Keep Mine"
+                    }
+                    if !changes.is_empty() {
+                        button {
+                            class: "cancel-button",
+                            onclick: move |_| adopt_all(all_ids.clone()),
+                            "Adopt All"
+                        }
+                        button {
+                            class: "update-proceed-button",
+                            onclick: move |_| adopt_picked(picked.read().iter().cloned().collect()),
+                            "Apply {picked_count} Change(s)"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
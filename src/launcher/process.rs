@@ -1,9 +1,15 @@
-use std::process::Command;
+use std::process::{Child, Command};
 use log::{debug, error, warn, info};
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-use crate::launcher::launcher_finder::get_launcher_path;
+use lazy_static::lazy_static;
+use tokio::sync::broadcast;
+
+use crate::launcher::launcher_finder::{find_instance_launcher, get_launcher_path};
 
 #[derive(Debug)]
 enum LauncherType {
@@ -25,22 +31,140 @@ impl fmt::Display for LauncherType {
     }
 }
 
+// How a launched process ended. For the vanilla launcher that's the Minecraft Launcher itself,
+// which stays open while the game runs, MultiMC and Prism with --launch close with the game
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameExit {
+    pub installation_id: String,
+    // None when the process was killed or couldn't be waited on
+    pub exit_code: Option<i32>,
+    pub session: Duration,
+}
+
+impl GameExit {
+    pub fn is_abnormal(&self) -> bool {
+        self.exit_code != Some(0)
+    }
+
+    pub fn describe(&self) -> String {
+        let played = format_play_time(self.session.as_secs());
+        match self.exit_code {
+            Some(code) => format!("Minecraft exited with code {} after {}", code, played),
+            None => format!("Minecraft was stopped after {}", played),
+        }
+    }
+}
+
+lazy_static! {
+    static ref EXITS: broadcast::Sender<GameExit> = broadcast::channel(16).0;
+    // Installations whose launched process hasn't exited yet
+    static ref RUNNING: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+pub fn subscribe_exits() -> broadcast::Receiver<GameExit> {
+    EXITS.subscribe()
+}
+
+/// Whether the installation was launched from this installer and is still running.
+pub fn is_running(installation_id: &str) -> bool {
+    RUNNING.lock().unwrap().contains(installation_id)
+}
+
+/// "2h 15m", "12m" or "< 1m".
+pub fn format_play_time(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "< 1m".to_string(),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// True when the selected launcher starts the instance itself (MultiMC, Prism), the vanilla
+/// launcher only opens with the profile selected.
+pub fn launches_instances() -> bool {
+    match get_current_launcher_type() {
+        Ok(LauncherType::Vanilla) => uses_direct_launch(),
+        Ok(_) => true,
+        Err(_) => false,
+    }
+}
+
+// Direct launch is opt-in and needs a signed in account, otherwise the vanilla launcher is used
+fn uses_direct_launch() -> bool {
+    let enabled = std::fs::read_to_string(crate::get_installer_dir().join("config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config["advanced"]["direct_launch"].as_bool())
+        .unwrap_or(false);
+    enabled && crate::ms_auth::stored_account().is_some()
+}
+
 // Main function to launch Minecraft with a specific profile
 pub fn launch_modpack(profile_id: &str) -> Result<(), String> {
     // Determine which launcher we're using
-    match get_current_launcher_type() {
+    let child = match get_current_launcher_type() {
         Ok(LauncherType::Vanilla) => launch_vanilla(profile_id),
         Ok(LauncherType::MultiMC) => launch_multimc(profile_id),
         Ok(LauncherType::PrismLauncher) => launch_prism(profile_id),
         Ok(LauncherType::Custom(path)) => launch_custom_multimc(profile_id, path),
         Err(e) => Err(e),
+    }?;
+    supervise(profile_id, child);
+    Ok(())
+}
+
+// Counts the launch and waits for the process on its own thread, then stores how long it ran
+// and how it exited and tells the GUI
+fn supervise(profile_id: &str, mut child: Child) {
+    let installation_id = profile_id.to_string();
+    match crate::installation::load_installation(&installation_id) {
+        Ok(mut installation) => {
+            if let Err(e) = installation.record_launch() {
+                warn!("Failed to record the launch of {}: {}", installation_id, e);
+            }
+        }
+        Err(e) => warn!("Failed to load {} to record the launch: {}", installation_id, e),
     }
+    debug!("Supervising process {} for {}", child.id(), installation_id);
+    RUNNING.lock().unwrap().insert(installation_id.clone());
+
+    let started = Instant::now();
+    std::thread::spawn(move || {
+        let exit_code = match child.wait() {
+            Ok(status) => status.code(),
+            Err(e) => {
+                warn!("Failed to wait for the game process of {}: {}", installation_id, e);
+                None
+            }
+        };
+        RUNNING.lock().unwrap().remove(&installation_id);
+        let exit = GameExit {
+            installation_id: installation_id.clone(),
+            exit_code,
+            session: started.elapsed(),
+        };
+        if exit.is_abnormal() {
+            warn!("{} ({})", exit.describe(), installation_id);
+        } else {
+            info!("{} ({})", exit.describe(), installation_id);
+        }
+        match crate::installation::load_installation(&installation_id) {
+            Ok(mut installation) => {
+                if let Err(e) = installation.record_session(exit_code, exit.session.as_secs()) {
+                    warn!("Failed to record the play session of {}: {}", installation_id, e);
+                }
+            }
+            Err(e) => warn!("Failed to load {} to record the play session: {}", installation_id, e),
+        }
+        let _ = EXITS.send(exit);
+    });
 }
 
 // Determine which launcher we're using
 fn get_current_launcher_type() -> Result<LauncherType, String> {
     // Read config to determine current launcher
-    let config = match std::fs::read_to_string(crate::get_app_data().join(".WC_OVHL/config.json")) {
+    let config = match std::fs::read_to_string(crate::get_installer_dir().join("config.json")) {
         Ok(content) => content,
         Err(e) => return Err(format!("Failed to read config: {}", e)),
     };
@@ -68,9 +192,21 @@ fn get_current_launcher_type() -> Result<LauncherType, String> {
 }
 
 // Launch vanilla Minecraft with the specified profile
-fn launch_vanilla(profile_id: &str) -> Result<(), String> {
+fn launch_vanilla(profile_id: &str) -> Result<Child, String> {
     debug!("Launching vanilla Minecraft for profile {}", profile_id);
-    
+
+    // The launcher would just fail with "version not found" if the loader files were cleaned up
+    if let Ok(installation) = crate::installation::load_installation(profile_id) {
+        match crate::loader_repair::ensure(&installation) {
+            Ok(true) => info!("Repaired loader for {} before launch", profile_id),
+            Ok(false) => {}
+            Err(e) => return Err(format!("Loader files are missing and couldn't be restored: {}", e)),
+        }
+        if uses_direct_launch() {
+            return crate::direct_launch::launch(&installation);
+        }
+    }
+
     // Get Minecraft directory
     let minecraft_dir = crate::get_minecraft_folder();
     
@@ -136,11 +272,18 @@ If Minecraft is installed in a non-standard location, the launcher may not be ab
     };
     
     info!("Launching Minecraft from: {}", launcher_path.display());
+
+    // The launcher hands its environment down to the game, so variables and wrappers that work
+    // through it (mangohud, gamemoderun) reach the game too
+    let mut command = match crate::installation::load_installation(profile_id) {
+        Ok(installation) => installation.game_command(&launcher_path),
+        Err(_) => Command::new(&launcher_path),
+    };
     
-    match Command::new(&launcher_path).spawn() {
-        Ok(_) => {
+    match command.spawn() {
+        Ok(child) => {
             info!("Minecraft launcher started successfully");
-            Ok(())
+            Ok(child)
         },
         Err(e) => {
             error!("Failed to start Minecraft launcher: {}", e);
@@ -162,121 +305,55 @@ If problems persist, you may need to reinstall Minecraft.",
 }
 
 // Launch MultiMC with the specified instance
-fn launch_multimc(profile_id: &str) -> Result<(), String> {
+fn launch_multimc(profile_id: &str) -> Result<Child, String> {
     let multimc_path = crate::get_multimc_folder("MultiMC")
         .map_err(|e| format!("Failed to find MultiMC folder: {}", e))?;
-    
-    let executable = if cfg!(target_os = "windows") {
-        multimc_path.join("MultiMC.exe")
-    } else if cfg!(target_os = "macos") {
-        multimc_path.join("MultiMC.app/Contents/MacOS/MultiMC")
-    } else {
-        multimc_path.join("MultiMC")
-    };
-    
-    debug!("Launching MultiMC with instance {}", profile_id);
-    debug!("MultiMC executable path: {:?}", executable);
-    
-    // Launch MultiMC with the instance
-    let command = Command::new(&executable)
-        .arg("--launch").arg(profile_id)
-        .spawn();
-        
-    match command {
-        Ok(_) => {
-            debug!("MultiMC launched successfully with instance: {}", profile_id);
-            Ok(())
-        },
-        Err(e) => {
-            error!("Failed to start MultiMC: {}", e);
-            Err(format!("Failed to start MultiMC: {}", e))
-        }
-    }
+    let executable = find_instance_launcher("MultiMC", &multimc_path)
+        .ok_or_else(|| "Could not find MultiMC. Start it yourself and launch the instance from there.".to_string())?;
+    launch_instance(&LauncherType::MultiMC, &executable, profile_id)
 }
 
 // Launch Prism Launcher with the specified instance
-fn launch_prism(profile_id: &str) -> Result<(), String> {
+fn launch_prism(profile_id: &str) -> Result<Child, String> {
     let prism_path = crate::get_multimc_folder("PrismLauncher")
         .map_err(|e| format!("Failed to find Prism Launcher folder: {}", e))?;
-    
-    let executable = if cfg!(target_os = "windows") {
-        prism_path.join("prismlauncher.exe")
-    } else if cfg!(target_os = "macos") {
-        prism_path.join("prismlauncher.app/Contents/MacOS/prismlauncher")
-    } else {
-        prism_path.join("prismlauncher")
-    };
-    
-    debug!("Launching Prism Launcher with instance {}", profile_id);
-    
-    // Launch Prism with the instance
-    match Command::new(executable)
-        .arg("-l")
-        .arg(profile_id)
-        .spawn() {
-            Ok(_) => {
-                debug!("Prism Launcher launched successfully with instance: {}", profile_id);
-                Ok(())
-            },
-            Err(e) => {
-                error!("Failed to start Prism Launcher: {}", e);
-                Err(format!("Failed to start Prism Launcher: {}", e))
-            }
-        }
+    let executable = find_instance_launcher("PrismLauncher", &prism_path)
+        .ok_or_else(|| "Could not find Prism Launcher. Start it yourself and launch the instance from there.".to_string())?;
+    launch_instance(&LauncherType::PrismLauncher, &executable, profile_id)
 }
 
 // Launch custom MultiMC with the specified instance
-fn launch_custom_multimc(profile_id: &str, path: String) -> Result<(), String> {
-    // Convert path string to PathBuf
-    let custom_path = std::path::PathBuf::from(path);
-    
-    // Try to determine the executable name
-    let executable = if cfg!(target_os = "windows") {
-        // Look for common executables
-        if custom_path.join("MultiMC.exe").exists() {
-            custom_path.join("MultiMC.exe")
-        } else if custom_path.join("prismlauncher.exe").exists() {
-            custom_path.join("prismlauncher.exe")
-        } else {
-            // Default to MultiMC.exe and hope for the best
-            custom_path.join("MultiMC.exe")
-        }
-    } else if cfg!(target_os = "macos") {
-        // Look for common executables
-        if custom_path.join("MultiMC.app/Contents/MacOS/MultiMC").exists() {
-            custom_path.join("MultiMC.app/Contents/MacOS/MultiMC")
-        } else if custom_path.join("prismlauncher.app/Contents/MacOS/prismlauncher").exists() {
-            custom_path.join("prismlauncher.app/Contents/MacOS/prismlauncher")
-        } else {
-            // Default to MultiMC and hope for the best
-            custom_path.join("MultiMC.app/Contents/MacOS/MultiMC")
+fn launch_custom_multimc(profile_id: &str, path: String) -> Result<Child, String> {
+    let custom_path = PathBuf::from(&path);
+    // A custom folder can hold either one
+    let executable = find_instance_launcher("PrismLauncher", &custom_path)
+        .or_else(|| find_instance_launcher("MultiMC", &custom_path))
+        .ok_or_else(|| format!("Could not find MultiMC or Prism Launcher in {}", custom_path.display()))?;
+    launch_instance(&LauncherType::Custom(path), &executable, profile_id)
+}
+
+// Both take the instance folder name, which is the installation id
+fn launch_instance(launcher: &LauncherType, executable: &Path, profile_id: &str) -> Result<Child, String> {
+    debug!("Launching {} from {} with instance {}", launcher, executable.display(), profile_id);
+    let mut command = Command::new(executable);
+    match crate::installation::load_installation(profile_id) {
+        Ok(installation) => {
+            // Installs write a fresh instance.cfg, the wrapper has to be put back every time
+            if let Err(e) = installation.write_instance_wrapper() {
+                warn!("Failed to set the wrapper command of {}: {}", profile_id, e);
+            }
+            command.envs(&installation.env_vars);
         }
-    } else {
-        // Linux: look for common executables
-        if custom_path.join("MultiMC").exists() {
-            custom_path.join("MultiMC")
-        } else if custom_path.join("prismlauncher").exists() {
-            custom_path.join("prismlauncher")
-        } else {
-            // Default to MultiMC and hope for the best
-            custom_path.join("MultiMC")
+        Err(e) => warn!("Failed to load {} for its launch settings: {}", profile_id, e),
+    }
+    match command.arg("--launch").arg(profile_id).spawn() {
+        Ok(child) => {
+            debug!("{} launched successfully with instance: {}", launcher, profile_id);
+            Ok(child)
         }
-    };
-    
-    debug!("Launching custom MultiMC-like launcher with instance {}", profile_id);
-    
-    // Launch with the instance
-    match Command::new(executable)
-        .arg("-l")
-        .arg(profile_id)
-        .spawn() {
-            Ok(_) => {
-                debug!("Custom launcher launched successfully with instance: {}", profile_id);
-                Ok(())
-            },
-            Err(e) => {
-                error!("Failed to start custom launcher: {}", e);
-                Err(format!("Failed to start custom launcher: {}", e))
-            }
+        Err(e) => {
+            error!("Failed to start {}: {}", launcher, e);
+            Err(format!("Failed to start {}: {}", launcher, e))
         }
+    }
 }
@@ -0,0 +1,240 @@
+use dioxus::prelude::*;
+use log::error;
+
+use crate::feature_profiles::{self, FeatureProfile};
+
+// The user's own saved selections, below the pack's presets in the features tab
+#[component]
+pub fn FeatureProfilesSection(
+    installation_id: String,
+    enabled_features: Signal<Vec<String>>,
+    onapply: EventHandler<Vec<String>>,
+) -> Element {
+    let mut profiles = use_signal({
+        let installation_id = installation_id.clone();
+        move || feature_profiles::load(&installation_id).unwrap_or_else(|e| {
+            error!("{}", e);
+            Vec::new()
+        })
+    });
+    let mut queued = use_signal({
+        let installation_id = installation_id.clone();
+        move || {
+            crate::installation::load_installation(&installation_id)
+                .ok()
+                .and_then(|installation| installation.next_update_profile)
+        }
+    });
+    let mut new_name = use_signal(String::new);
+    // Profile being renamed and the name typed so far
+    let mut renaming = use_signal(|| Option::<(String, String)>::None);
+    let mut confirm_delete = use_signal(|| Option::<String>::None);
+    let mut message = use_signal(|| Option::<String>::None);
+
+    // Runs a change, then reloads the list
+    let run = {
+        let installation_id = installation_id.clone();
+        move |result: Result<(), String>, done: String| {
+            match result {
+                Ok(()) => message.set(Some(done)),
+                Err(e) => {
+                    error!("Feature profile change failed: {}", e);
+                    message.set(Some(e));
+                }
+            }
+            profiles.set(feature_profiles::load(&installation_id).unwrap_or_default());
+            queued.set(
+                crate::installation::load_installation(&installation_id)
+                    .ok()
+                    .and_then(|installation| installation.next_update_profile),
+            );
+        }
+    };
+
+    let save_new = {
+        let installation_id = installation_id.clone();
+        let mut run = run.clone();
+        move |_| {
+            let name = new_name.read().trim().to_string();
+            let result = feature_profiles::save(&installation_id, &name, &enabled_features.read());
+            if result.is_ok() {
+                new_name.set(String::new());
+            }
+            run(result, format!("Saved the current selection as '{}'", name));
+        }
+    };
+
+    let current: Vec<FeatureProfile> = profiles();
+    let active = feature_profiles::matching(&current, &enabled_features.read()).map(|p| p.name.clone());
+
+    rsx! {
+        div { class: "section-divider with-title",
+            span { class: "divider-title", "MY PROFILES" }
+        }
+
+        p { class: "section-description",
+            "Save your feature selection under a name to switch back to it later, or queue one for the next update."
+        }
+
+        div { class: "feature-profiles",
+            for profile in current.clone() {
+                {
+                    let is_active = active.as_deref() == Some(profile.name.as_str());
+                    let is_queued = queued().as_deref() == Some(profile.name.as_str());
+                    let is_renaming = renaming().map_or(false, |(old, _)| old == profile.name);
+                    let typed_name = renaming().map(|(_, typed)| typed).unwrap_or_default();
+                    let is_deleting = confirm_delete().as_deref() == Some(profile.name.as_str());
+                    let name = profile.name.clone();
+                    let features = profile.enabled_features.clone();
+                    let installation_id = installation_id.clone();
+                    let mut run = run.clone();
+                    rsx! {
+                        div {
+                            key: "{profile.name}",
+                            class: if is_active { "feature-profile active" } else { "feature-profile" },
+
+                            if is_renaming {
+                                input {
+                                    class: "feature-profile-name-input",
+                                    value: "{typed_name}",
+                                    oninput: {
+                                        let name = name.clone();
+                                        move |evt| renaming.set(Some((name.clone(), evt.value())))
+                                    },
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    onclick: {
+                                        let installation_id = installation_id.clone();
+                                        let name = name.clone();
+                                        let mut run = run.clone();
+                                        move |_| {
+                                            let typed = renaming().map(|(_, typed)| typed).unwrap_or_default();
+                                            renaming.set(None);
+                                            run(
+                                                feature_profiles::rename(&installation_id, &name, &typed),
+                                                format!("Renamed '{}' to '{}'", name, typed.trim()),
+                                            );
+                                        }
+                                    },
+                                    "Save"
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    onclick: move |_| renaming.set(None),
+                                    "Cancel"
+                                }
+                            } else if is_deleting {
+                                span { class: "feature-profile-name", "Delete '{profile.name}'?" }
+                                button {
+                                    class: "feature-profile-button remove",
+                                    onclick: {
+                                        let installation_id = installation_id.clone();
+                                        let name = name.clone();
+                                        let mut run = run.clone();
+                                        move |_| {
+                                            confirm_delete.set(None);
+                                            run(feature_profiles::delete(&installation_id, &name), format!("Deleted '{}'", name));
+                                        }
+                                    },
+                                    "Delete"
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    onclick: move |_| confirm_delete.set(None),
+                                    "Cancel"
+                                }
+                            } else {
+                                div { class: "feature-profile-info",
+                                    span { class: "feature-profile-name", "{profile.name}" }
+                                    span { class: "feature-profile-meta",
+                                        "{profile.enabled_features.len()} features"
+                                        if is_active { " · current selection" }
+                                        if is_queued { " · used by the next update" }
+                                    }
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    disabled: is_active,
+                                    onclick: {
+                                        let features = features.clone();
+                                        move |_| onapply.call(features.clone())
+                                    },
+                                    "Apply"
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    title: "Replace this profile with the current selection",
+                                    disabled: is_active,
+                                    onclick: {
+                                        let installation_id = installation_id.clone();
+                                        let name = name.clone();
+                                        let mut run = run.clone();
+                                        move |_| run(
+                                            feature_profiles::save(&installation_id, &name, &enabled_features.read()),
+                                            format!("Updated '{}'", name),
+                                        )
+                                    },
+                                    "Overwrite"
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    title: "The next update installs this profile's features",
+                                    onclick: {
+                                        let installation_id = installation_id.clone();
+                                        let name = name.clone();
+                                        move |_| {
+                                            let result = crate::installation::load_installation(&installation_id).and_then(|mut installation| {
+                                                installation.next_update_profile = if is_queued { None } else { Some(name.clone()) };
+                                                installation.save()
+                                            });
+                                            let done = if is_queued {
+                                                "The next update keeps the current selection".to_string()
+                                            } else {
+                                                format!("The next update switches to '{}'", name)
+                                            };
+                                            run(result, done);
+                                        }
+                                    },
+                                    {if is_queued { "Don't use on update" } else { "Use on next update" }}
+                                }
+                                button {
+                                    class: "feature-profile-button",
+                                    onclick: {
+                                        let name = name.clone();
+                                        move |_| renaming.set(Some((name.clone(), name.clone())))
+                                    },
+                                    "Rename"
+                                }
+                                button {
+                                    class: "feature-profile-button remove",
+                                    onclick: move |_| confirm_delete.set(Some(name.clone())),
+                                    "Delete"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "feature-profile new",
+                input {
+                    class: "feature-profile-name-input",
+                    placeholder: "Profile name",
+                    value: "{new_name}",
+                    oninput: move |evt| new_name.set(evt.value()),
+                }
+                button {
+                    class: "feature-profile-button",
+                    disabled: new_name.read().trim().is_empty(),
+                    onclick: save_new,
+                    "Save Current Selection"
+                }
+            }
+        }
+
+        if let Some(text) = message() {
+            p { class: "feature-profile-message", "{text}" }
+        }
+    }
+}
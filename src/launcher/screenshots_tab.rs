@@ -0,0 +1,185 @@
+use dioxus::prelude::*;
+use log::{debug, error};
+
+use crate::installation::Installation;
+use crate::screenshots::{self, Screenshot};
+
+// Screenshots the game took for this installation, with thumbnails that load one by one
+#[component]
+pub fn ScreenshotsTab(installation: Installation) -> Element {
+    let mut screenshots = use_resource({
+        let installation = installation.clone();
+        move || {
+            let installation = installation.clone();
+            async move {
+                tokio::task::spawn_blocking(move || screenshots::list(&installation))
+                    .await
+                    .map_err(|e| format!("Listing screenshots stopped: {}", e))
+                    .and_then(|r| r)
+            }
+        }
+    });
+    let mut status = use_signal(|| Option::<String>::None);
+    let mut pending_delete = use_signal(|| Option::<Screenshot>::None);
+
+    let open_folder = {
+        let installation = installation.clone();
+        move |_| match screenshots::screenshots_dir(&installation) {
+            Ok(dir) => {
+                if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| open::that(&dir)) {
+                    status.set(Some(format!("Failed to open {}: {}", dir.display(), e)));
+                }
+            }
+            Err(e) => status.set(Some(e)),
+        }
+    };
+
+    let listing = screenshots.read().clone();
+    rsx! {
+        div { class: "screenshots-tab",
+            div { class: "screenshots-header",
+                h2 { "Screenshots" }
+                button { class: "secondary-button", onclick: move |_| screenshots.restart(), "Refresh" }
+                button { class: "secondary-button", onclick: open_folder, "Open Folder" }
+            }
+
+            if let Some(message) = status() {
+                div { class: "screenshots-status", "{message}" }
+            }
+
+            match listing {
+                None => rsx! {
+                    div { class: "loading-container",
+                        div { class: "loading-spinner" }
+                        div { class: "loading-text", "Looking for screenshots..." }
+                    }
+                },
+                Some(Err(e)) => rsx! { div { class: "error-message", "{e}" } },
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    p { class: "screenshots-empty", "No screenshots yet. Press F2 in game to take one." }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "screenshots-grid",
+                        for screenshot in list {
+                            ScreenshotCard {
+                                key: "{screenshot.path.display()}",
+                                screenshot: screenshot.clone(),
+                                onstatus: move |message| status.set(Some(message)),
+                                ondelete: move |screenshot| pending_delete.set(Some(screenshot)),
+                            }
+                        }
+                    }
+                },
+            }
+
+            if let Some(screenshot) = pending_delete() {
+                div { class: "modal-overlay",
+                    div { class: "modal-container",
+                        div { class: "modal-header",
+                            h3 { "Delete screenshot" }
+                            button { class: "modal-close", onclick: move |_| pending_delete.set(None), "×" }
+                        }
+                        div { class: "modal-content",
+                            p { "Delete {screenshot.name}? This can't be undone." }
+                        }
+                        div { class: "modal-footer",
+                            button { class: "cancel-button", onclick: move |_| pending_delete.set(None), "Cancel" }
+                            button {
+                                class: "delete-button",
+                                onclick: move |_| {
+                                    match screenshots::delete(&screenshot) {
+                                        Ok(_) => status.set(None),
+                                        Err(e) => {
+                                            error!("{}", e);
+                                            status.set(Some(e));
+                                        }
+                                    }
+                                    pending_delete.set(None);
+                                    screenshots.restart();
+                                },
+                                "Delete"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ScreenshotCard(
+    screenshot: Screenshot,
+    onstatus: EventHandler<String>,
+    ondelete: EventHandler<Screenshot>,
+) -> Element {
+    let thumbnail = use_resource({
+        let path = screenshot.path.clone();
+        move || screenshots::thumbnail(path.clone())
+    });
+    let taken = screenshot
+        .taken
+        .map(|taken| taken.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let size = crate::backup::format_bytes(screenshot.size);
+
+    let open_file = {
+        let path = screenshot.path.clone();
+        move |_| {
+            if let Err(e) = open::that(&path) {
+                onstatus.call(format!("Failed to open {}: {}", path.display(), e));
+            }
+        }
+    };
+    let copy = {
+        let path = screenshot.path.clone();
+        let name = screenshot.name.clone();
+        move |_| {
+            let path = path.clone();
+            let name = name.clone();
+            spawn(async move {
+                match screenshots::png_data_url(path).await {
+                    Ok(data_url) => {
+                        // The webview's clipboard takes images as blobs
+                        let url = serde_json::to_string(&data_url).unwrap_or_default();
+                        let _ = eval(&format!(
+                            "fetch({}).then(r => r.blob()).then(b => navigator.clipboard.write([new ClipboardItem({{'image/png': b}})]))",
+                            url
+                        ));
+                        debug!("Copied screenshot {}", name);
+                        onstatus.call(format!("Copied {}", name));
+                    }
+                    Err(e) => onstatus.call(e),
+                }
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "screenshot-card",
+            div { class: "screenshot-thumbnail", onclick: open_file.clone(),
+                match &*thumbnail.read() {
+                    Some(Ok(data_url)) => rsx! { img { src: "{data_url}", alt: "{screenshot.name}" } },
+                    Some(Err(_)) => rsx! { span { class: "screenshot-placeholder", "Can't show this image" } },
+                    None => rsx! { div { class: "loading-spinner" } },
+                }
+            }
+            div { class: "screenshot-info",
+                span { class: "screenshot-name", title: "{screenshot.name}", "{taken}" }
+                span { class: "screenshot-size", "{size}" }
+            }
+            div { class: "screenshot-actions",
+                button { class: "secondary-button", onclick: open_file, "Open" }
+                button { class: "secondary-button", onclick: copy, "Copy" }
+                button {
+                    class: "secondary-button danger",
+                    onclick: {
+                        let screenshot = screenshot.clone();
+                        move |_| ondelete.call(screenshot.clone())
+                    },
+                    "Delete"
+                }
+            }
+        }
+    }
+}
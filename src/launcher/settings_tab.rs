@@ -1,25 +1,858 @@
 use dioxus::prelude::*;
 use crate::installation::{Installation, delete_installation};
 use crate::backup::{BackupConfig, BackupType, BackupMetadata, BackupProgress};
+use crate::reminders::Reminder;
+use crate::icons::InstallationIcon;
+use crate::universal::{PackIcon, UniversalManifest};
+use super::backup_browser::BackupBrowser;
+use super::worlds_section::WorldsSection;
+use super::orphan_files_dialog::OrphanFilesDialog;
+use super::install_log_dialog::InstallLogDialog;
+use super::verify_dialog::VerifyDialog;
 use log::{debug, error, warn}; // Only import from log, remove the duplicate
+use std::collections::BTreeMap;
 
 #[component]
 pub fn SettingsTab(
     installation: Installation,
     installation_id: String,
+    universal_manifest: Option<UniversalManifest>,
+    // Set from the home page's health badge, opens Verify Files
+    repair_request: Signal<bool>,
     ondelete: EventHandler<()>,
     onupdate: EventHandler<Installation>,
 ) -> Element {
+    let pack_icons = universal_manifest.as_ref().map(|m| m.icons.clone()).unwrap_or_default();
+    // Ordering only matters with more than one pack
+    let order_manifest = universal_manifest.clone().filter(|m| m.resourcepacks.len() > 1);
+    // Only offered when the pack lists versions besides its own
+    let version_manifest = universal_manifest.filter(|m| m.selectable_minecraft_versions().len() > 1);
+
     // Remove the tab navigation - everything goes in one page now
     rsx! {
-        div { class: "settings-tab",
-            h2 { "Installation Settings" }
-            
-            GeneralSettingsSection {
-                installation: installation.clone(),
-                installation_id: installation_id.clone(),
-                ondelete: ondelete.clone(),
-                onupdate: onupdate.clone()
+        div { class: "settings-tab",
+            h2 { "Installation Settings" }
+            
+            GeneralSettingsSection {
+                installation: installation.clone(),
+                installation_id: installation_id.clone(),
+                repair_request,
+                ondelete: ondelete.clone(),
+                onupdate: onupdate.clone()
+            }
+
+            ReleaseChannelSection {
+                installation: installation.clone(),
+                onupdate: onupdate.clone()
+            }
+
+            AutoUpdateSection {
+                installation: installation.clone(),
+                onupdate: onupdate.clone()
+            }
+
+            if let Some(manifest) = version_manifest {
+                MinecraftVersionSection {
+                    installation: installation.clone(),
+                    universal_manifest: manifest,
+                    onupdate: onupdate.clone()
+                }
+            }
+
+            IconSection {
+                installation: installation.clone(),
+                pack_icons: pack_icons,
+                onupdate: onupdate.clone()
+            }
+
+            if let Some(manifest) = order_manifest {
+                ResourcePackOrderSection {
+                    installation: installation.clone(),
+                    universal_manifest: manifest,
+                    onupdate: onupdate.clone()
+                }
+            }
+
+            LaunchEnvironmentSection {
+                installation: installation.clone(),
+                onupdate: onupdate.clone()
+            }
+
+            WorldsSection {
+                installation: installation.clone()
+            }
+
+            NotesSection {
+                installation: installation.clone(),
+                onupdate: onupdate.clone()
+            }
+
+            RemindersSection {
+                installation: installation.clone(),
+                onupdate: onupdate.clone()
+            }
+        }
+    }
+}
+
+// Saves the pick, then updates the launcher profile right away instead of on the next install
+async fn save_icon_choice(
+    installation_id: String,
+    choice: Option<InstallationIcon>,
+    picked_file: Option<std::path::PathBuf>,
+) -> Result<Installation, String> {
+    let mut updated = crate::installation::load_installation(&installation_id)?;
+    if let Some(source) = picked_file {
+        crate::icons::import_local(updated.installation_path.clone(), source).await?;
+    }
+    updated.set_icon(choice)?;
+    crate::icons::apply(&crate::CachedHttpClient::new(), &updated).await?;
+    Ok(updated)
+}
+
+#[component]
+fn IconSection(
+    installation: Installation,
+    pack_icons: Vec<PackIcon>,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let mut icon_error = use_signal(|| Option::<String>::None);
+    let mut is_applying = use_signal(|| false);
+
+    let choose = {
+        let installation_id = installation.id.clone();
+        move |choice: Option<InstallationIcon>, picked_file: Option<std::path::PathBuf>| {
+            if is_applying() {
+                return;
+            }
+            is_applying.set(true);
+            let installation_id = installation_id.clone();
+            spawn(async move {
+                match save_icon_choice(installation_id, choice, picked_file).await {
+                    Ok(updated) => {
+                        icon_error.set(None);
+                        onupdate.call(updated);
+                    }
+                    Err(e) => {
+                        error!("Failed to change the installation icon: {}", e);
+                        icon_error.set(Some(format!("Failed to change the icon: {}", e)));
+                    }
+                }
+                is_applying.set(false);
+            });
+        }
+    };
+
+    let pick_file = {
+        let mut choose = choose.clone();
+        move |_| {
+            let dialog = rfd::FileDialog::new()
+                .set_title("Pick an icon")
+                .add_filter("Images", &["png", "jpg", "jpeg", "gif", "bmp", "webp"]);
+            if let Some(path) = dialog.pick_file() {
+                choose(Some(InstallationIcon::Local), Some(path));
+            }
+        }
+    };
+
+    let mut choose_default = choose.clone();
+    let is_default = installation.icon.is_none();
+    let is_local = installation.icon == Some(InstallationIcon::Local);
+
+    rsx! {
+        div { class: "settings-section installation-icon",
+            h3 { "Launcher Icon" }
+            p { class: "notes-description",
+                "The icon of this installation's profile in the launcher."
+            }
+
+            if let Some(error) = icon_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            div { class: "icon-choices",
+                button {
+                    class: if is_default { "icon-choice selected" } else { "icon-choice" },
+                    disabled: is_applying(),
+                    onclick: move |_| choose_default(None, None),
+                    "Pack default"
+                }
+                for pack_icon in pack_icons {
+                    {
+                        let selected = matches!(&installation.icon, Some(InstallationIcon::Pack { id, .. }) if *id == pack_icon.id);
+                        let choice = InstallationIcon::Pack { id: pack_icon.id.clone(), url: pack_icon.url.clone() };
+                        let mut choose = choose.clone();
+                        rsx! {
+                            button {
+                                key: "{pack_icon.id}",
+                                class: if selected { "icon-choice selected" } else { "icon-choice" },
+                                title: "{pack_icon.name}",
+                                disabled: is_applying(),
+                                onclick: move |_| choose(Some(choice.clone()), None),
+                                img { src: "{pack_icon.url}", alt: "{pack_icon.name}" }
+                            }
+                        }
+                    }
+                }
+                button {
+                    class: if is_local { "icon-choice selected" } else { "icon-choice" },
+                    disabled: is_applying(),
+                    onclick: pick_file,
+                    {if is_local { "Custom image (change…)" } else { "Choose image…" }}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn MinecraftVersionSection(
+    installation: Installation,
+    universal_manifest: UniversalManifest,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let mut picked = use_signal(|| installation.minecraft_version.clone());
+    let mut version_error = use_signal(|| Option::<String>::None);
+
+    let versions = universal_manifest.selectable_minecraft_versions();
+    let current = installation.minecraft_version.clone();
+    let picked_version = picked();
+    let incompatible = universal_manifest.incompatible_components(&picked_version, &installation.enabled_features);
+    let incompatible_list = incompatible.join(", ");
+    let changed = picked_version != current;
+    let pack_version = universal_manifest.minecraft_version.clone();
+
+    let switch_version = {
+        let installation = installation.clone();
+        let universal_manifest = universal_manifest.clone();
+        move |_| {
+            let mut updated = installation.clone();
+            match updated.set_minecraft_version(&universal_manifest, &picked()) {
+                Ok(_) => {
+                    version_error.set(None);
+                    onupdate.call(updated);
+                }
+                Err(e) => {
+                    error!("Failed to switch the Minecraft version: {}", e);
+                    version_error.set(Some(e));
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "settings-section minecraft-version",
+            h3 { "Minecraft Version" }
+            p { class: "notes-description",
+                "The pack is made for Minecraft {pack_version}, it can also be installed for the other versions here. The next update switches the game over."
+            }
+
+            if let Some(error) = version_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            div { class: "minecraft-version-picker",
+                select {
+                    value: "{picked_version}",
+                    onchange: move |evt| picked.set(evt.value()),
+                    for version in versions {
+                        option {
+                            key: "{version}",
+                            value: "{version}",
+                            selected: version == picked_version,
+                            "{version}"
+                        }
+                    }
+                }
+                button {
+                    class: "settings-action-button",
+                    disabled: !changed,
+                    onclick: switch_version,
+                    "Switch to {picked_version}"
+                }
+            }
+
+            if !incompatible.is_empty() {
+                div { class: "minecraft-version-warning",
+                    "Not available for Minecraft {picked_version}, these won't be installed: {incompatible_list}"
+                }
+            }
+        }
+    }
+}
+
+// Which branch updates come from, see crate::channels. Hidden while the pack has nothing but stable
+#[component]
+fn ReleaseChannelSection(
+    installation: Installation,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let branches = use_context::<crate::gui::AppProps>().branches;
+    let mut channel_error = use_signal(|| Option::<String>::None);
+
+    let current = installation.release_channel;
+    let mut channels = crate::channels::available(&installation.source.clone().unwrap_or_default(), &branches);
+    if !channels.contains(&current) {
+        // Its branch is gone, still show it so there's a way back
+        channels.push(current);
+    }
+    if channels.len() < 2 {
+        return rsx! {};
+    }
+    let pending_switch = installation.installed && current != installation.installed_channel;
+    let installed_label = installation.installed_channel.label();
+    let current_label = current.label();
+
+    rsx! {
+        div { class: "settings-section release-channel",
+            h3 { "Release Channel" }
+            p { class: "notes-description",
+                "Which versions of the pack this installation gets. Switching takes effect with the next update."
+            }
+
+            if let Some(error) = channel_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            div { class: "release-channel-options",
+                for channel in channels {
+                    {
+                        let installation = installation.clone();
+                        let selected = channel == current;
+                        let id = channel.id();
+                        let label = channel.label();
+                        let description = channel.description();
+                        rsx! {
+                            button {
+                                key: "{id}",
+                                class: if selected { "release-channel-option selected" } else { "release-channel-option" },
+                                disabled: selected,
+                                onclick: move |_| {
+                                    let mut updated = installation.clone();
+                                    match updated.set_release_channel(channel) {
+                                        Ok(_) => {
+                                            channel_error.set(None);
+                                            onupdate.call(updated);
+                                        }
+                                        Err(e) => {
+                                            error!("Failed to switch the release channel: {}", e);
+                                            channel_error.set(Some(e));
+                                        }
+                                    }
+                                },
+                                span { class: "channel-badge channel-{id}", "{label}" }
+                                span { class: "release-channel-description", "{description}" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if pending_switch {
+                div { class: "minecraft-version-warning",
+                    "Update the installation to switch from {installed_label} to {current_label}."
+                }
+            }
+        }
+    }
+}
+
+// The updates themselves run from the GUI's startup and tray loops, see crate::auto_update
+#[component]
+fn AutoUpdateSection(
+    installation: Installation,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let mut auto_update_error = use_signal(|| Option::<String>::None);
+    let installation_id = installation.id.clone();
+
+    rsx! {
+        div { class: "settings-section auto-update",
+            h3 { "Automatic Updates" }
+            label { class: "auto-update-toggle",
+                input {
+                    r#type: "checkbox",
+                    checked: installation.auto_update,
+                    onchange: move |evt| {
+                        // Latest state, a check in the background may have saved it meanwhile
+                        let result = crate::installation::load_installation(&installation_id).and_then(|mut updated| {
+                            updated.auto_update = evt.checked();
+                            updated.save().map(|_| updated)
+                        });
+                        match result {
+                            Ok(updated) => {
+                                auto_update_error.set(None);
+                                onupdate.call(updated);
+                            }
+                            Err(e) => {
+                                error!("Failed to save the automatic update setting: {}", e);
+                                auto_update_error.set(Some(e));
+                            }
+                        }
+                    },
+                }
+                "Keep up to date automatically"
+            }
+            p { class: "notes-description",
+                "New pack versions are installed in the background when the installer starts, or on the tray's schedule in tray mode, and a notification says how it went. Your Wynntils settings are kept like with any update. Not while the game is running."
+            }
+
+            if let Some(error) = auto_update_error() {
+                div { class: "error-message", "{error}" }
+            }
+        }
+    }
+}
+
+// Reminders fire from the background loop in crate::reminders, here they're only added and removed
+#[component]
+fn RemindersSection(
+    installation: Installation,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let mut message = use_signal(|| String::new());
+    let mut due = use_signal(|| String::new());
+    let mut reminder_error = use_signal(|| Option::<String>::None);
+
+    // Loads the latest state, applies the change and saves, so other edits aren't overwritten
+    let save_reminders = {
+        let installation_id = installation.id.clone();
+        move |change: &dyn Fn(&mut Vec<Reminder>)| -> bool {
+            let mut updated = match crate::installation::load_installation(&installation_id) {
+                Ok(installation) => installation,
+                Err(e) => {
+                    reminder_error.set(Some(format!("Failed to load installation: {}", e)));
+                    return false;
+                }
+            };
+            change(&mut updated.reminders);
+            updated.reminders.sort_by_key(|r| r.due);
+            match updated.save() {
+                Ok(_) => {
+                    reminder_error.set(None);
+                    onupdate.call(updated);
+                    true
+                }
+                Err(e) => {
+                    error!("Failed to save reminders: {}", e);
+                    reminder_error.set(Some(format!("Failed to save reminders: {}", e)));
+                    false
+                }
+            }
+        }
+    };
+
+    let handle_add = {
+        let mut save_reminders = save_reminders.clone();
+        move |_| {
+            let text = message.read().trim().to_string();
+            if text.is_empty() {
+                reminder_error.set(Some("Enter what you want to be reminded of".to_string()));
+                return;
+            }
+            let due_at = match crate::reminders::parse_local_input(&due.read()) {
+                Ok(due_at) => due_at,
+                Err(_) => {
+                    reminder_error.set(Some("Pick a date and time for the reminder".to_string()));
+                    return;
+                }
+            };
+            if due_at <= chrono::Utc::now() {
+                reminder_error.set(Some("The reminder time is in the past".to_string()));
+                return;
+            }
+            let reminder = Reminder::new(text, due_at);
+            debug!("Adding reminder {} due {}", reminder.id, reminder.due);
+            if save_reminders(&move |reminders: &mut Vec<Reminder>| reminders.push(reminder.clone())) {
+                message.set(String::new());
+                due.set(String::new());
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "settings-section installation-reminders",
+            h3 { "Reminders" }
+            p { class: "notes-description",
+                "Get a notification at a set time, e.g. to re-enable shaders after an event."
+            }
+
+            if let Some(error) = reminder_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            if !installation.reminders.is_empty() {
+                div { class: "reminder-list",
+                    for reminder in installation.reminders.clone() {
+                        {
+                            let reminder_id = reminder.id.clone();
+                            let mut save_reminders = save_reminders.clone();
+                            rsx! {
+                                div {
+                                    key: "{reminder.id}",
+                                    class: if reminder.fired { "reminder-item fired" } else { "reminder-item" },
+                                    span { class: "reminder-due", "{reminder.due_display()}" }
+                                    span { class: "reminder-message", "{reminder.message}" }
+                                    button {
+                                        class: "delete-backup-button-mini",
+                                        title: "Remove reminder",
+                                        onclick: move |_| {
+                                            let reminder_id = reminder_id.clone();
+                                            save_reminders(&move |reminders: &mut Vec<Reminder>| reminders.retain(|r| r.id != reminder_id));
+                                        },
+                                        "×"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "reminder-form",
+                input {
+                    r#type: "text",
+                    class: "reminder-message-input",
+                    value: "{message}",
+                    placeholder: "Remind me to...",
+                    oninput: move |evt| message.set(evt.value().clone())
+                }
+                input {
+                    r#type: "datetime-local",
+                    class: "reminder-due-input",
+                    value: "{due}",
+                    oninput: move |evt| due.set(evt.value().clone())
+                }
+                button {
+                    class: "save-button",
+                    onclick: handle_add,
+                    "Add Reminder"
+                }
+            }
+        }
+    }
+}
+
+// One NAME=value per line, blank lines and # comments are skipped
+fn parse_env_vars(text: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut vars = BTreeMap::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let (name, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("'{}' isn't NAME=value", line))?;
+        let name = name.trim();
+        let valid = name.chars().next().map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!("'{}' isn't a valid variable name", name));
+        }
+        vars.insert(name.to_string(), value.trim().to_string());
+    }
+    Ok(vars)
+}
+
+fn format_env_vars(vars: &BTreeMap<String, String>) -> String {
+    vars.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<_>>().join("\n")
+}
+
+// Environment variables and a wrapper for the game, mostly for Linux (mangohud, gamemode,
+// Mesa overrides)
+#[component]
+fn LaunchEnvironmentSection(
+    installation: Installation,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let mut env_text = use_signal(|| format_env_vars(&installation.env_vars));
+    let mut wrapper = use_signal(|| installation.wrapper_command.clone().unwrap_or_default());
+    let mut launch_error = use_signal(|| Option::<String>::None);
+    let is_dirty = *env_text.read() != format_env_vars(&installation.env_vars)
+        || wrapper.read().trim() != installation.wrapper_command.as_deref().unwrap_or_default();
+
+    let handle_save = {
+        let installation_id = installation.id.clone();
+        move |_| {
+            let env_vars = match parse_env_vars(&env_text.read()) {
+                Ok(vars) => vars,
+                Err(e) => {
+                    launch_error.set(Some(e));
+                    return;
+                }
+            };
+            // Reload so a concurrent change (feature toggles, launches) isn't overwritten
+            let mut updated = match crate::installation::load_installation(&installation_id) {
+                Ok(installation) => installation,
+                Err(e) => {
+                    launch_error.set(Some(format!("Failed to load installation: {}", e)));
+                    return;
+                }
+            };
+            updated.env_vars = env_vars;
+            updated.wrapper_command = Some(wrapper.read().trim().to_string()).filter(|w| !w.is_empty());
+            match updated.save() {
+                Ok(_) => {
+                    debug!("Saved launch environment for installation {}", updated.id);
+                    if let Err(e) = updated.write_instance_wrapper() {
+                        warn!("Instance wrapper not updated: {}", e);
+                    }
+                    env_text.set(format_env_vars(&updated.env_vars));
+                    launch_error.set(None);
+                    onupdate.call(updated);
+                }
+                Err(e) => {
+                    error!("Failed to save launch environment: {}", e);
+                    launch_error.set(Some(format!("Failed to save: {}", e)));
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "settings-section launch-environment",
+            h3 { "Launch Environment" }
+            p { class: "notes-description",
+                "Environment variables and a wrapper command for the game. MultiMC and Prism instances get them in their wrapper command too."
+            }
+
+            if let Some(error) = launch_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            label { class: "launch-environment-label", "Environment variables, one NAME=value per line" }
+            textarea {
+                class: "launch-environment-input",
+                rows: "4",
+                value: "{env_text}",
+                placeholder: "MESA_GL_VERSION_OVERRIDE=4.6",
+                oninput: move |evt| env_text.set(evt.value())
+            }
+
+            label { class: "launch-environment-label", "Wrapper command" }
+            input {
+                class: "launch-environment-input",
+                r#type: "text",
+                value: "{wrapper}",
+                placeholder: "mangohud",
+                oninput: move |evt| wrapper.set(evt.value())
+            }
+
+            div { class: "notes-actions",
+                button {
+                    class: "save-button",
+                    disabled: !is_dirty,
+                    onclick: handle_save,
+                    "Save Launch Environment"
+                }
+            }
+        }
+    }
+}
+
+// Resource packs of the installation's features, topmost wins. Saved orders go straight into
+// options.txt when the packs are already installed, otherwise the next install writes them
+#[component]
+fn ResourcePackOrderSection(
+    installation: Installation,
+    universal_manifest: UniversalManifest,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let packs: Vec<(String, String)> = universal_manifest
+        .resourcepacks
+        .iter()
+        .filter(|pack| !pack.optional || installation.enabled_features.contains(&pack.id))
+        .map(|pack| (pack.id.clone(), pack.name.clone()))
+        .collect();
+    let ids: Vec<String> = packs.iter().map(|(id, _)| id.clone()).collect();
+    let pack_default = crate::pack_order::resolve(&ids, &universal_manifest.resourcepack_order, &[]);
+    let saved = crate::pack_order::resolve(&ids, &universal_manifest.resourcepack_order, &installation.resourcepack_order);
+
+    let mut order = use_signal(|| saved.clone());
+    let mut order_error = use_signal(|| Option::<String>::None);
+    // Features toggled since drop and add packs without losing the rearranged ones
+    let current = crate::pack_order::resolve(&ids, &saved, &order.read());
+    let is_dirty = current != saved;
+    let is_default = current == pack_default;
+
+    let handle_save = {
+        let installation_id = installation.id.clone();
+        let pack_default = pack_default.clone();
+        let default_order = universal_manifest.resourcepack_order.clone();
+        move |_| {
+            let mut updated = match crate::installation::load_installation(&installation_id) {
+                Ok(installation) => installation,
+                Err(e) => {
+                    order_error.set(Some(format!("Failed to load installation: {}", e)));
+                    return;
+                }
+            };
+            // The pack's order isn't copied, so changes to it keep coming through
+            let picked = order.read().clone();
+            updated.resourcepack_order = if picked == pack_default { Vec::new() } else { picked };
+            if let Err(e) = updated.save() {
+                error!("Failed to save the resource pack order: {}", e);
+                order_error.set(Some(format!("Failed to save: {}", e)));
+                return;
+            }
+            if updated.installed {
+                if let Err(e) = crate::pack_order::apply_to_installation(&updated, &default_order) {
+                    warn!("Resource pack order not written yet: {}", e);
+                }
+            }
+            debug!("Saved the resource pack order of installation {}", updated.id);
+            order_error.set(None);
+            onupdate.call(updated);
+        }
+    };
+
+    let names: std::collections::HashMap<String, String> = packs.into_iter().collect();
+    let count = current.len();
+
+    rsx! {
+        div { class: "settings-section resourcepack-order",
+            h3 { "Resource Pack Order" }
+            p { class: "notes-description",
+                "Packs higher up win where they change the same textures. Disabled packs aren't listed."
+            }
+
+            if let Some(error) = order_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            if current.is_empty() {
+                p { class: "notes-description", "No resource packs are enabled." }
+            }
+
+            div { class: "resourcepack-order-list",
+                for (index, id) in current.iter().cloned().enumerate() {
+                    {
+                        let moved = |to: usize| {
+                            let mut moved = current.clone();
+                            moved.swap(index, to);
+                            moved
+                        };
+                        let up = (index > 0).then(|| moved(index - 1));
+                        let down = (index + 1 < count).then(|| moved(index + 1));
+                        let (can_move_up, can_move_down) = (up.is_some(), down.is_some());
+                        let name = names.get(&id).cloned().unwrap_or_else(|| id.clone());
+                        rsx! {
+                            div { key: "{id}", class: "resourcepack-order-item",
+                                span { class: "resourcepack-order-position", "{index + 1}" }
+                                span { class: "resourcepack-order-name", "{name}" }
+                                button {
+                                    class: "resourcepack-order-move",
+                                    title: "Move up",
+                                    disabled: !can_move_up,
+                                    onclick: move |_| if let Some(up) = &up { order.set(up.clone()) },
+                                    "▲"
+                                }
+                                button {
+                                    class: "resourcepack-order-move",
+                                    title: "Move down",
+                                    disabled: !can_move_down,
+                                    onclick: move |_| if let Some(down) = &down { order.set(down.clone()) },
+                                    "▼"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "notes-actions",
+                button {
+                    class: "cancel-button",
+                    disabled: is_default,
+                    onclick: move |_| order.set(pack_default.clone()),
+                    "Pack Default"
+                }
+                button {
+                    class: "save-button",
+                    disabled: !is_dirty,
+                    onclick: handle_save,
+                    "Save Order"
+                }
+            }
+        }
+    }
+}
+
+// Notes are markdown, shown rendered when hovering the installation card on the home page
+#[component]
+fn NotesSection(
+    installation: Installation,
+    onupdate: EventHandler<Installation>,
+) -> Element {
+    let mut notes = use_signal(|| installation.notes.clone());
+    let mut show_preview = use_signal(|| false);
+    let mut notes_error = use_signal(|| Option::<String>::None);
+    let is_dirty = *notes.read() != installation.notes;
+
+    let handle_save = {
+        let installation_id = installation.id.clone();
+        move |_| {
+            // Reload so a concurrent change (feature toggles, launches) isn't overwritten
+            let mut updated = match crate::installation::load_installation(&installation_id) {
+                Ok(installation) => installation,
+                Err(e) => {
+                    notes_error.set(Some(format!("Failed to load installation: {}", e)));
+                    return;
+                }
+            };
+            updated.notes = notes.read().trim_end().to_string();
+            match updated.save() {
+                Ok(_) => {
+                    debug!("Saved notes for installation {}", updated.id);
+                    notes_error.set(None);
+                    onupdate.call(updated);
+                }
+                Err(e) => {
+                    error!("Failed to save notes: {}", e);
+                    notes_error.set(Some(format!("Failed to save notes: {}", e)));
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "settings-section installation-notes",
+            h3 { "Notes" }
+            p { class: "notes-description",
+                "What is this installation for? Shown when hovering its card. Markdown is supported."
+            }
+
+            if let Some(error) = notes_error() {
+                div { class: "error-message", "{error}" }
+            }
+
+            if show_preview() {
+                div {
+                    class: "notes-preview",
+                    dangerous_inner_html: "{crate::markdown::render_markdown(&notes.read())}"
+                }
+            } else {
+                textarea {
+                    class: "notes-input",
+                    rows: "5",
+                    value: "{notes}",
+                    placeholder: "e.g. Guild wars build, do not update before the event",
+                    oninput: move |evt| notes.set(evt.value().clone())
+                }
+            }
+
+            div { class: "notes-actions",
+                button {
+                    class: "cancel-button",
+                    onclick: move |_| {
+                        let showing = show_preview();
+                        show_preview.set(!showing);
+                    },
+                    if show_preview() { "Edit" } else { "Preview" }
+                }
+                button {
+                    class: "save-button",
+                    disabled: !is_dirty,
+                    onclick: handle_save,
+                    "Save Notes"
+                }
             }
         }
     }
@@ -29,6 +862,7 @@ pub fn SettingsTab(
 fn GeneralSettingsSection(
     installation: Installation,
     installation_id: String,
+    mut repair_request: Signal<bool>,
     ondelete: EventHandler<()>,
     onupdate: EventHandler<Installation>,
 ) -> Element {
@@ -46,8 +880,37 @@ fn GeneralSettingsSection(
     let launcher_type = installation.launcher_type.clone();
     let created_at = installation.created_at;
     let last_used = installation.last_used;
+
+    // Walking a big game folder takes a moment, it's done off the UI thread
+    let disk_usage = use_resource({
+        let path = installation.installation_path.clone();
+        move || {
+            let path = path.clone();
+            async move {
+                tokio::task::spawn_blocking(move || crate::backup::calculate_directory_size(&path))
+                    .await
+                    .map_err(|e| format!("Size calculation stopped: {}", e))
+                    .and_then(|r| r)
+            }
+        }
+    });
+    let disk_usage_display = match &*disk_usage.read() {
+        Some(Ok(bytes)) => crate::backup::format_bytes(*bytes),
+        Some(Err(e)) => {
+            debug!("Couldn't measure the installation: {}", e);
+            "Unknown".to_string()
+        }
+        None => "Calculating...".to_string(),
+    };
     let total_launches = installation.total_launches;
     let last_launch = installation.last_launch;
+    let time_played = super::format_play_time(installation.total_play_seconds);
+    let last_session = match (installation.last_session_seconds, installation.last_exit_code) {
+        (None, _) => "None yet".to_string(),
+        (Some(seconds), Some(0)) => super::format_play_time(seconds),
+        (Some(seconds), Some(code)) => format!("{} (exit code {})", super::format_play_time(seconds), code),
+        (Some(seconds), None) => format!("{} (stopped)", super::format_play_time(seconds)),
+    };
     
     // State for rename dialog
     let mut show_rename_dialog = use_signal(|| false);
@@ -63,7 +926,7 @@ fn GeneralSettingsSection(
     
     // Backup-related state
     let mut show_backup_section = use_signal(|| false);
-    let mut backup_config = use_signal(|| BackupConfig::default());
+    let mut backup_config = use_signal(|| installation.backup_config.clone());
     let mut backup_description = use_signal(|| String::new());
     let mut available_backups = use_signal(|| Vec::<BackupMetadata>::new());
     let mut selected_backup = use_signal(|| Option::<String>::None);
@@ -72,6 +935,20 @@ fn GeneralSettingsSection(
     let mut backup_success = use_signal(|| Option::<String>::None);
     let mut show_backup_config = use_signal(|| false);
     let mut show_restore_confirm = use_signal(|| false);
+    let mut browse_backup = use_signal(|| Option::<BackupMetadata>::None);
+    let mut show_orphan_files = use_signal(|| false);
+    let mut show_install_log = use_signal(|| false);
+    let mut show_verify = use_signal(|| false);
+    use_effect(move || {
+        if repair_request() {
+            repair_request.set(false);
+            show_verify.set(true);
+        }
+    });
+    
+    // Sharing state, holds the short code once published
+    let mut share_code = use_signal(|| Option::<String>::None);
+    let mut is_sharing = use_signal(|| false);
     
     // Load available backups when backup section is shown
     use_effect({
@@ -189,6 +1066,20 @@ fn GeneralSettingsSection(
         });
     };
     
+    // Backup settings are saved right away, updates read them from the installation
+    let id_for_backup_config = use_signal(|| installation_id.clone());
+    let mut save_backup_config = move |config: BackupConfig| {
+        match crate::installation::load_installation(&id_for_backup_config.read()) {
+            Ok(mut updated) => match updated.set_backup_config(config) {
+                Ok(_) => onupdate.call(updated),
+                Err(e) => operation_error.set(Some(format!("Failed to save backup settings: {}", e))),
+            },
+            Err(e) => operation_error.set(Some(format!("Failed to save backup settings: {}", e))),
+        }
+    };
+    let auto_backup_items = backup_config.read().auto_backup_items.join(", ");
+    let protected_paths = backup_config.read().protected_paths.join(", ");
+
     // Backup functions
     let create_backup = {
         let installation_clone = installation.clone();
@@ -253,6 +1144,100 @@ fn GeneralSettingsSection(
         }
     };
     
+    // Publish the installation's setup and show the share code
+    let installation_for_share = installation.clone();
+    let installation_for_browser = installation.clone();
+    let share_installation = move |_| {
+        let shared = crate::share::SharedInstallation::from_installation(&installation_for_share);
+        is_sharing.set(true);
+        operation_error.set(None);
+        
+        spawn(async move {
+            let http_client = crate::CachedHttpClient::new();
+            match crate::share::publish(&http_client, &shared).await {
+                Ok(code) => {
+                    debug!("Installation shared with code: {}", code);
+                    share_code.set(Some(code));
+                },
+                Err(e) => {
+                    error!("Failed to share installation: {}", e);
+                    operation_error.set(Some(format!("Failed to share installation: {}", e)));
+                }
+            }
+            is_sharing.set(false);
+        });
+    };
+    
+    // Same setup as a share code, as a file
+    let installation_for_export = installation.clone();
+    let export_installation = move |_| {
+        let file_name = format!("{}.{}", installation_for_export.name, crate::share::PACK_FILE_EXTENSION);
+        let dialog = rfd::FileDialog::new()
+            .set_title("Export installation")
+            .set_file_name(&file_name)
+            .add_filter("Installation file", &[crate::share::PACK_FILE_EXTENSION]);
+        if let Some(path) = dialog.save_file() {
+            match installation_for_export.export(&path) {
+                Ok(_) => {
+                    operation_error.set(None);
+                    backup_success.set(Some(format!("Installation exported to {}", path.display())));
+                }
+                Err(e) => {
+                    error!("Failed to export installation: {}", e);
+                    operation_error.set(Some(format!("Failed to export installation: {}", e)));
+                }
+            }
+        }
+    };
+
+    // The installed game folder, for launchers on machines without the installer
+    let installation_for_pack_export = installation.clone();
+    let export_pack = move |format: crate::pack_export::ExportFormat| {
+        let file_name = format!("{}.{}", installation_for_pack_export.name, format.extension());
+        let dialog = rfd::FileDialog::new()
+            .set_title(format!("Export as {}", format.label()))
+            .set_file_name(&file_name)
+            .add_filter(format.label(), &[format.extension()]);
+        let Some(path) = dialog.save_file() else { return };
+        is_operating.set(true);
+        let installation = installation_for_pack_export.clone();
+        spawn(async move {
+            // Zipping the whole game folder takes a while
+            let result = tokio::task::spawn_blocking(move || {
+                crate::pack_export::export(&installation, format, &path).map(|count| (count, path))
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Export task failed: {}", e)));
+            match result {
+                Ok((count, path)) => {
+                    operation_error.set(None);
+                    backup_success.set(Some(format!("Exported {} files to {}", count, path.display())));
+                }
+                Err(e) => {
+                    error!("Failed to export installation as {}: {}", format.label(), e);
+                    operation_error.set(Some(format!("Failed to export installation: {}", e)));
+                }
+            }
+            is_operating.set(false);
+        });
+    };
+    let mut export_mrpack = export_pack.clone();
+    let mut export_multimc = export_pack;
+
+    let installation_for_shortcut = installation.clone();
+    let create_shortcut = move |_| {
+        match crate::shortcuts::create_desktop_shortcut(&installation_for_shortcut) {
+            Ok(path) => {
+                operation_error.set(None);
+                backup_success.set(Some(format!("Created a shortcut at {}", path.display())));
+            }
+            Err(e) => {
+                error!("Failed to create a desktop shortcut: {}", e);
+                operation_error.set(Some(format!("Failed to create a desktop shortcut: {}", e)));
+            }
+        }
+    };
+    
 rsx! {
     div { class: "settings-tab",
         // Display operation error if any
@@ -308,6 +1293,10 @@ rsx! {
                     div { class: "info-label", "Launcher: ", "{launcher_type}" }
                 }
                 
+                div { class: "info-row",
+                    div { class: "info-label", "Disk Usage: ", "{disk_usage_display}" }
+                }
+                
                 div { class: "info-row path-row",
                     div { class: "info-label", "Path:" }
                     div { class: "path-container", 
@@ -336,6 +1325,16 @@ rsx! {
                     }
                     div { class: "stat-label", "Last Launch" }
                 }
+                
+                div { class: "stat-item",
+                    div { class: "stat-value", "{time_played}" }
+                    div { class: "stat-label", "Time Played" }
+                }
+                
+                div { class: "stat-item",
+                    div { class: "stat-value", "{last_session}" }
+                    div { class: "stat-label", "Last Session" }
+                }
             }
         }
         
@@ -428,6 +1427,69 @@ rsx! {
                             })}
                         }
                         
+                        // Automatic backups before updates
+                        div { class: "backup-auto-section",
+                            h5 { "Automatic Backups" }
+                            label { class: "backup-auto-toggle",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: backup_config.read().auto_backup,
+                                    onchange: move |evt| {
+                                        let mut config = backup_config.read().clone();
+                                        config.auto_backup = evt.value() == "true";
+                                        backup_config.set(config.clone());
+                                        save_backup_config(config);
+                                    }
+                                }
+                                span { "Back up {auto_backup_items} before every update" }
+                            }
+                            div { class: "backup-auto-retention",
+                                span { "Keep the last" }
+                                input {
+                                    r#type: "number",
+                                    min: "1",
+                                    max: "50",
+                                    value: "{backup_config.read().auto_backup_retention}",
+                                    disabled: !backup_config.read().auto_backup,
+                                    onchange: move |evt| {
+                                        if let Ok(retention) = evt.value().parse::<usize>() {
+                                            let mut config = backup_config.read().clone();
+                                            config.auto_backup_retention = retention.clamp(1, 50);
+                                            backup_config.set(config.clone());
+                                            save_backup_config(config);
+                                        }
+                                    }
+                                }
+                                span { "automatic backups" }
+                            }
+                        }
+
+                        // Set aside before updates and put back afterwards, see config_protection.rs
+                        div { class: "backup-auto-section",
+                            h5 { "Protected Config" }
+                            p { class: "setting-description",
+                                "These folders and files keep your changes through updates. Separate them with commas, leave empty to turn it off."
+                            }
+                            input {
+                                class: "setting-input",
+                                r#type: "text",
+                                placeholder: "wynntils, config/example.json",
+                                value: "{protected_paths}",
+                                onchange: move |evt| {
+                                    let paths: Vec<String> = evt
+                                        .value()
+                                        .split(',')
+                                        .map(|p| p.trim().trim_matches('/').to_string())
+                                        .filter(|p| !p.is_empty())
+                                        .collect();
+                                    let mut config = backup_config.read().clone();
+                                    config.protected_paths = paths;
+                                    backup_config.set(config.clone());
+                                    save_backup_config(config);
+                                }
+                            }
+                        }
+
                         // Available backups list
                         div { class: "backup-list-section",
                             h5 { "Available Backups ({available_backups.read().len()})" }
@@ -451,6 +1513,7 @@ rsx! {
                                             let backup_id = backup.id.clone();
                                             let backup_id_for_onclick = backup_id.clone();
                                             let backup_id_for_delete = backup_id.clone();
+                                            let backup_for_browse = backup.clone();
                                             let installation_clone = installation.clone();
                                             let is_selected = selected_backup.read().as_ref() == Some(&backup_id);
                                             let age_desc = backup.age_description();
@@ -461,6 +1524,8 @@ rsx! {
                                                 crate::backup::BackupType::PreUpdate => "Pre-Update", 
                                                 crate::backup::BackupType::PreInstall => "Pre-Install",
                                                 crate::backup::BackupType::Scheduled => "Scheduled",
+                                                crate::backup::BackupType::Safety => "Safety",
+                                                crate::backup::BackupType::World => "World",
                                             };
                                             
                                             rsx! {
@@ -489,6 +1554,8 @@ rsx! {
                                                                     crate::backup::BackupType::PreUpdate => "background: #ffc107; color: black;",
                                                                     crate::backup::BackupType::PreInstall => "background: #17a2b8; color: white;",
                                                                     crate::backup::BackupType::Scheduled => "background: #6f42c1; color: white;",
+                                                                    crate::backup::BackupType::Safety => "background: #6c757d; color: white;",
+                                                                    crate::backup::BackupType::World => "background: #20c997; color: black;",
                                                                 },
                                                                 "{backup_type_badge}"
                                                             }
@@ -532,6 +1599,16 @@ rsx! {
                                                                     "Restore"
                                                                 }
                                                                 
+                                                                button {
+                                                                    class: "browse-backup-button-mini",
+                                                                    onclick: move |evt| {
+                                                                        evt.stop_propagation();
+                                                                        browse_backup.set(Some(backup_for_browse.clone()));
+                                                                    },
+                                                                    style: "margin-left: 8px;",
+                                                                    "Browse"
+                                                                }
+                                                                
                                                                 button {
                                                                     class: "delete-backup-button-mini",
                                                                     onclick: move |evt| {
@@ -678,6 +1755,53 @@ rsx! {
                 rsx! { span {} }
             }}
             
+            div { class: "advanced-option",
+                div { class: "advanced-option-info",
+                    h4 { "Clean Up Unused Files" }
+                    p {
+                        "Lists jars and packs in mods, resourcepacks and shaderpacks that aren't part of the modpack anymore, "
+                        "like leftovers of features you turned off. Nothing is deleted until you confirm."
+                    }
+                }
+                button {
+                    class: "advanced-button",
+                    disabled: !installation.installed || *is_operating.read(),
+                    onclick: move |_| show_orphan_files.set(true),
+                    "Review Unused Files"
+                }
+            }
+            
+            div { class: "advanced-option",
+                div { class: "advanced-option-info",
+                    h4 { "Verify Files" }
+                    p {
+                        "Checks that every mod, pack and included file is still there and undamaged, "
+                        "and downloads only the broken ones again. Your configs aren't touched."
+                    }
+                }
+                button {
+                    class: "advanced-button",
+                    disabled: !installation.installed || *is_operating.read(),
+                    onclick: move |_| show_verify.set(true),
+                    "Verify Files"
+                }
+            }
+            
+            div { class: "advanced-option",
+                div { class: "advanced-option-info",
+                    h4 { "Install Log" }
+                    p {
+                        "What the last installs and updates did with each component of this installation, "
+                        "which downloads failed and which includes were skipped."
+                    }
+                }
+                button {
+                    class: "advanced-button",
+                    onclick: move |_| show_install_log.set(true),
+                    "View Install Log"
+                }
+            }
+            
             div { class: "advanced-option",
                 div { class: "advanced-option-info",
                     h4 { "Reset Installation Cache" }
@@ -711,7 +1835,7 @@ rsx! {
                         spawn(async move {
                             // Load the universal manifest to check can_reset flags
                             let http_client = crate::CachedHttpClient::new();
-                            let universal_manifest = match crate::universal::load_universal_manifest(&http_client, None).await {
+                            let universal_manifest = match installation_clone_for_async.load_universal_manifest(&http_client).await {
                                 Ok(manifest) => Some(manifest),
                                 Err(e) => {
                                     error!("Failed to load universal manifest for reset: {}", e);
@@ -864,6 +1988,50 @@ rsx! {
                     span { class: "action-icon", "📂" }
                     "Open Installation Folder"
                 }
+
+                button {
+                    class: "settings-action-button shortcut-button",
+                    disabled: *is_operating.read() || !installation.installed,
+                    title: "Starts the game straight from the desktop",
+                    onclick: create_shortcut,
+                    span { class: "action-icon", "🖥️" }
+                    "Create Desktop Shortcut"
+                }
+                
+                // Share button
+                button {
+                    class: "settings-action-button share-button",
+                    disabled: *is_operating.read() || *is_sharing.read(),
+                    onclick: share_installation,
+                    span { class: "action-icon", "🔗" }
+                    {if *is_sharing.read() { "Sharing..." } else { "Share Installation" }}
+                }
+                
+                // Export button
+                button {
+                    class: "settings-action-button export-button",
+                    disabled: *is_operating.read(),
+                    onclick: export_installation,
+                    span { class: "action-icon", "💾" }
+                    "Export to File"
+                }
+                
+                // Export the game folder for other launchers
+                button {
+                    class: "settings-action-button export-button",
+                    disabled: *is_operating.read() || !installation.installed,
+                    onclick: move |_| export_mrpack(crate::pack_export::ExportFormat::Mrpack),
+                    span { class: "action-icon", "📦" }
+                    "Export as .mrpack"
+                }
+                
+                button {
+                    class: "settings-action-button export-button",
+                    disabled: *is_operating.read() || !installation.installed,
+                    onclick: move |_| export_multimc(crate::pack_export::ExportFormat::MultiMC),
+                    span { class: "action-icon", "📦" }
+                    "Export as MultiMC Instance"
+                }
                 
                 // Delete button
                 button {
@@ -878,6 +2046,87 @@ rsx! {
             }
         }
         
+        if show_install_log() {
+            InstallLogDialog {
+                installation_id: installation_for_browser.id.clone(),
+                onclose: move |_| show_install_log.set(false),
+            }
+        }
+        
+        if show_verify() {
+            VerifyDialog {
+                installation: installation_for_browser.clone(),
+                onclose: move |_| show_verify.set(false),
+                onrepaired: move |message: String| {
+                    show_verify.set(false);
+                    backup_success.set(Some(message));
+                }
+            }
+        }
+        
+        if show_orphan_files() {
+            OrphanFilesDialog {
+                installation: installation_for_browser.clone(),
+                onclose: move |_| show_orphan_files.set(false),
+                oncleaned: move |message: String| {
+                    show_orphan_files.set(false);
+                    backup_success.set(Some(message));
+                }
+            }
+        }
+        
+        // Backup browser for selective restores
+        {browse_backup.read().clone().map(|backup| rsx! {
+            BackupBrowser {
+                installation: installation_for_browser.clone(),
+                backup: backup,
+                onclose: move |_| browse_backup.set(None),
+                onrestored: move |message: String| {
+                    browse_backup.set(None);
+                    backup_success.set(Some(message));
+                }
+            }
+        })}
+        
+        // Share dialog
+        {share_code.read().clone().map(|code| {
+            let qr = crate::share::qr_data_url(&code);
+            rsx! {
+                div { class: "modal-overlay",
+                    div { class: "modal-container share-dialog",
+                        div { class: "modal-header",
+                            h3 { "Share Installation" }
+                            button { 
+                                class: "modal-close",
+                                onclick: move |_| share_code.set(None),
+                                "×"
+                            }
+                        }
+                        
+                        div { class: "modal-content",
+                            p { "Others can enter this code when creating a new installation to get the exact same setup." }
+                            div { class: "share-code", "{code}" }
+                            
+                            {match qr {
+                                Ok(src) => rsx! { img { class: "share-qr", src: "{src}", alt: "Share QR code" } },
+                                Err(e) => rsx! { p { class: "share-qr-error", "{e}" } },
+                            }}
+                            
+                            p { class: "share-note", "Only your feature selection and performance settings are shared, none of your files." }
+                        }
+                        
+                        div { class: "modal-footer",
+                            button { 
+                                class: "cancel-button",
+                                onclick: move |_| share_code.set(None),
+                                "Close"
+                            }
+                        }
+                    }
+                }
+            }
+        })}
+        
         // All the existing modals (rename, delete, backup config, restore confirm)
         // Rename dialog
         {if *show_rename_dialog.read() {
@@ -1027,7 +2276,8 @@ rsx! {
                     estimated_size: installation_for_config.get_backup_size_estimate(&backup_config.read()).unwrap_or(0),
                     onclose: move |_| show_backup_config.set(false),
                     onupdate: move |new_config: BackupConfig| {
-                        backup_config.set(new_config);
+                        backup_config.set(new_config.clone());
+                        save_backup_config(new_config);
                     }
                 }
             })
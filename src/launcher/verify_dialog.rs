@@ -0,0 +1,129 @@
+use dioxus::prelude::*;
+use log::{debug, error};
+
+use crate::installation::Installation;
+use crate::verify::{self, Report};
+
+// Checks the installed files as soon as it opens, repairing downloads only the broken ones
+#[component]
+pub fn VerifyDialog(
+    installation: Installation,
+    onclose: EventHandler<()>,
+    onrepaired: EventHandler<String>,
+) -> Element {
+    let mut report = use_signal(|| Option::<Result<Report, String>>::None);
+    let mut is_repairing = use_signal(|| false);
+    let mut repair_error = use_signal(|| Option::<String>::None);
+
+    use_hook({
+        let installation = installation.clone();
+        move || {
+            spawn(async move {
+                let result = tokio::task::spawn_blocking(move || verify::check(&installation))
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Verification stopped unexpectedly: {}", e)));
+                report.set(Some(result));
+            });
+        }
+    });
+
+    let problems = match &*report.read() {
+        Some(Ok(report)) => report.problems.clone(),
+        _ => Vec::new(),
+    };
+    let problem_count = problems.len();
+
+    let repair = {
+        let installation = installation.clone();
+        let problems = problems.clone();
+        move |_| {
+            let installation = installation.clone();
+            let problems = problems.clone();
+            is_repairing.set(true);
+            repair_error.set(None);
+            spawn(async move {
+                let http_client = crate::CachedHttpClient::new();
+                match verify::repair(&installation, &http_client, &problems).await {
+                    Ok(fixed) => {
+                        debug!("Repaired {} file(s)", fixed);
+                        onrepaired.call(format!("Repaired {} file(s)", fixed));
+                    }
+                    Err(e) => {
+                        error!("Repair failed: {}", e);
+                        repair_error.set(Some(e));
+                    }
+                }
+                is_repairing.set(false);
+            });
+        }
+    };
+
+    let body = match report.read().clone() {
+        None => rsx! { p { "Checking the files of {installation.name}..." } },
+        Some(Err(e)) => rsx! { div { class: "error-message", "Failed to verify the files: {e}" } },
+        Some(Ok(report)) if report.problems.is_empty() => rsx! {
+            p { "All {report.checked} files of {installation.name} are in place and intact." }
+        },
+        Some(Ok(report)) => rsx! {
+            p {
+                "{report.problems.len()} of {report.checked} files are missing or damaged. "
+                "Repairing downloads just these again, your configs and settings stay as they are."
+            }
+            div { class: "verify-problem-list",
+                for problem in report.problems {
+                    {
+                        let (kind, label) = if problem.corrupted { ("corrupted", "Damaged") } else { ("missing", "Missing") };
+                        rsx! {
+                            div { key: "{problem.relative}", class: "verify-problem",
+                                span { class: "verify-problem-kind {kind}", "{label}" }
+                                span { class: "verify-problem-path", "{problem.relative}" }
+                                span { class: "verify-problem-component", "{problem.component}" }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            div { class: "modal-container verify-dialog",
+                div { class: "modal-header",
+                    h3 { "Verify Files" }
+                    button {
+                        class: "modal-close",
+                        disabled: *is_repairing.read(),
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "modal-content",
+                    if let Some(error) = &*repair_error.read() {
+                        div { class: "error-message", "{error}" }
+                    }
+
+                    {body}
+                }
+
+                div { class: "modal-footer",
+                    button {
+                        class: "cancel-button",
+                        disabled: *is_repairing.read(),
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    if problem_count > 0 {
+                        button {
+                            class: "update-proceed-button",
+                            disabled: *is_repairing.read(),
+                            onclick: repair,
+                            {if *is_repairing.read() { "Repairing...".to_string() } else { format!("Repair {} file(s)", problem_count) }}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
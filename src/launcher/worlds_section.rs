@@ -0,0 +1,274 @@
+use chrono::Local;
+use dioxus::prelude::*;
+use log::{debug, error};
+
+use crate::backup::{format_bytes, BackupMetadata, WorldInfo};
+use crate::installation::Installation;
+
+// Singleplayer worlds with their own backups. A world backup is an ordinary backup that holds only
+// saves/<world>, so it also shows up (and is cleaned up) with the other backups, the history here
+// is just the backups whose metadata names that one world.
+#[component]
+pub fn WorldsSection(installation: Installation) -> Element {
+    let mut worlds = use_resource({
+        let installation = installation.clone();
+        move || {
+            let installation = installation.clone();
+            async move {
+                // Sizing every world walks all their region files
+                tokio::task::spawn_blocking(move || installation.list_worlds())
+                    .await
+                    .map_err(|e| format!("Listing worlds stopped: {}", e))
+                    .and_then(|r| r)
+            }
+        }
+    });
+    let mut expanded = use_signal(|| Option::<String>::None);
+    let mut busy = use_signal(|| false);
+    let mut message = use_signal(|| Option::<String>::None);
+    let mut world_error = use_signal(|| Option::<String>::None);
+    let mut confirm_restore = use_signal(|| Option::<(String, BackupMetadata)>::None);
+
+    let backup_world = {
+        let installation = installation.clone();
+        move |folder: String| {
+            let installation = installation.clone();
+            busy.set(true);
+            world_error.set(None);
+            spawn(async move {
+                match installation.backup_world(&folder, format!("World backup: {}", folder)).await {
+                    Ok(backup) => {
+                        debug!("Backed up world {} as {}", folder, backup.id);
+                        message.set(Some(format!("Backed up {} ({})", folder, backup.formatted_size())));
+                    }
+                    Err(e) => {
+                        error!("World backup failed: {}", e);
+                        world_error.set(Some(e));
+                    }
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    let export_world = {
+        let installation = installation.clone();
+        move |folder: String| {
+            let dialog = rfd::FileDialog::new()
+                .set_title("Export world")
+                .set_file_name(format!("{}.zip", folder))
+                .add_filter("Zip archive", &["zip"]);
+            let Some(path) = dialog.save_file() else {
+                return;
+            };
+            let installation = installation.clone();
+            busy.set(true);
+            world_error.set(None);
+            spawn(async move {
+                let target = path.clone();
+                let result = tokio::task::spawn_blocking(move || installation.export_world(&folder, &target))
+                    .await
+                    .map_err(|e| format!("Export stopped: {}", e))
+                    .and_then(|r| r);
+                match result {
+                    Ok(size) => message.set(Some(format!("Exported to {} ({})", path.display(), format_bytes(size)))),
+                    Err(e) => {
+                        error!("World export failed: {}", e);
+                        world_error.set(Some(e));
+                    }
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    let restore_world = {
+        let installation = installation.clone();
+        move |_| {
+            let Some((folder, backup)) = confirm_restore() else {
+                return;
+            };
+            confirm_restore.set(None);
+            let installation = installation.clone();
+            busy.set(true);
+            world_error.set(None);
+            spawn(async move {
+                match installation.restore_world(&backup.id, &folder).await {
+                    Ok(count) => {
+                        message.set(Some(format!("Restored {} from {} ({} files)", folder, backup.age_description().to_lowercase(), count)));
+                        worlds.restart();
+                    }
+                    Err(e) => {
+                        error!("World restore failed: {}", e);
+                        world_error.set(Some(e));
+                    }
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    let listing = worlds.read().clone();
+    let is_busy = busy();
+    let pending_restore = confirm_restore().map(|(folder, backup)| {
+        (folder, backup.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+    });
+    rsx! {
+        div { class: "settings-section worlds-section",
+            h3 { "Worlds" }
+            p { class: "notes-description",
+                "Back up, restore or export single worlds. Close the game before restoring a world."
+            }
+
+            if let Some(error) = world_error() {
+                div { class: "error-message", "{error}" }
+            }
+            if let Some(text) = message() {
+                div { class: "worlds-message", "{text}" }
+            }
+
+            match listing {
+                None => rsx! { p { class: "worlds-empty", "Looking for worlds..." } },
+                Some(Err(e)) => rsx! { div { class: "error-message", "{e}" } },
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    p { class: "worlds-empty", "No singleplayer worlds yet." }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "worlds-list",
+                        for world in list {
+                            {
+                                let folder = world.folder.clone();
+                                let is_expanded = expanded.read().as_deref() == Some(folder.as_str());
+                                let mut backup_world = backup_world.clone();
+                                let mut export_world = export_world.clone();
+                                let folder_for_backup = folder.clone();
+                                let folder_for_export = folder.clone();
+                                let folder_for_toggle = folder.clone();
+                                let folder_for_restore = folder.clone();
+                                rsx! {
+                                    WorldRow {
+                                        key: "{folder}",
+                                        world: world.clone(),
+                                        expanded: is_expanded,
+                                        busy: is_busy,
+                                        ontoggle: move |_| {
+                                            if is_expanded {
+                                                expanded.set(None);
+                                            } else {
+                                                expanded.set(Some(folder_for_toggle.clone()));
+                                            }
+                                        },
+                                        onbackup: move |_| backup_world(folder_for_backup.clone()),
+                                        onexport: move |_| export_world(folder_for_export.clone()),
+                                    }
+                                    if is_expanded {
+                                        WorldHistory {
+                                            // Read on every render, messages after a backup or restore re-render this
+                                            backups: installation.world_backups(&folder),
+                                            busy: is_busy,
+                                            onrestore: move |backup| confirm_restore.set(Some((folder_for_restore.clone(), backup))),
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+
+            if let Some((folder, created)) = pending_restore {
+                div { class: "modal-overlay",
+                    div { class: "modal-container",
+                        div { class: "modal-header",
+                            h3 { "Restore {folder}" }
+                            button { class: "modal-close", onclick: move |_| confirm_restore.set(None), "×" }
+                        }
+                        div { class: "modal-content",
+                            p { "Replace {folder} with the backup from {created}?" }
+                            p { "The world as it is now is backed up first, so this can be undone from the history." }
+                        }
+                        div { class: "modal-footer",
+                            button { class: "cancel-button", onclick: move |_| confirm_restore.set(None), "Cancel" }
+                            button { class: "primary-button", onclick: restore_world, "Restore World" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WorldRow(
+    world: WorldInfo,
+    expanded: bool,
+    busy: bool,
+    ontoggle: EventHandler<()>,
+    onbackup: EventHandler<()>,
+    onexport: EventHandler<()>,
+) -> Element {
+    let last_played = world
+        .last_played
+        .map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let size = format_bytes(world.size_bytes);
+
+    rsx! {
+        div { class: if expanded { "world-row expanded" } else { "world-row" },
+            div { class: "world-info", onclick: move |_| ontoggle.call(()),
+                span { class: "world-name", "{world.folder}" }
+                span { class: "world-meta", "Last played {last_played} · {size}" }
+            }
+            div { class: "world-actions",
+                button { class: "secondary-button", disabled: busy, onclick: move |_| onbackup.call(()), "Back Up" }
+                button { class: "secondary-button", disabled: busy, onclick: move |_| onexport.call(()), "Export Zip" }
+                button {
+                    class: "secondary-button",
+                    onclick: move |_| ontoggle.call(()),
+                    if expanded { "Hide History" } else { "History" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WorldHistory(
+    backups: Result<Vec<BackupMetadata>, String>,
+    busy: bool,
+    onrestore: EventHandler<BackupMetadata>,
+) -> Element {
+    rsx! {
+        div { class: "world-history",
+            match backups {
+                Err(e) => rsx! { div { class: "error-message", "{e}" } },
+                Ok(list) if list.is_empty() => rsx! {
+                    p { class: "worlds-empty", "No backups of this world yet." }
+                },
+                Ok(list) => rsx! {
+                    for backup in list {
+                        {
+                            let created = backup.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string();
+                            let age = backup.age_description();
+                            let size = backup.formatted_size();
+                            rsx! {
+                                div { key: "{backup.id}", class: "world-backup",
+                                    div { class: "world-info",
+                                        span { class: "world-name", "{backup.description}" }
+                                        span { class: "world-meta", "{created} ({age}) · {size}" }
+                                    }
+                                    button {
+                                        class: "secondary-button",
+                                        disabled: busy,
+                                        onclick: move |_| onrestore.call(backup.clone()),
+                                        "Restore"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
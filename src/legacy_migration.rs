@@ -0,0 +1,188 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info, warn};
+use serde_json::Value as JsonValue;
+
+use crate::installation::{self, Installation};
+use crate::{get_installer_dir, get_minecraft_folder};
+
+// Before installations existed, vanilla launcher packs were installed straight into
+// .WC_OVHL/<pack uuid>. Those are moved to .WC_OVHL/installations/<pack uuid> and get an
+// installation.json, so they show up in the UI instead of being reinstalled next to the old copy.
+// Safe to run on every start, there's nothing to do once no legacy folders are left.
+
+const LEGACY_MANIFEST: &str = "manifest.json";
+
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub migrated: Vec<String>,
+    // Legacy folders that couldn't be moved completely, left in place so nothing is lost
+    pub leftovers: Vec<PathBuf>,
+}
+
+/// Legacy pack folders, recognisable by a manifest.json directly inside .WC_OVHL/<uuid>.
+fn find_legacy_dirs(installer_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(installer_dir) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join(LEGACY_MANIFEST).is_file())
+        .collect()
+}
+
+// Moves every entry of `from` into `to`. Entries that already exist in `to` are left where they
+// are, those can only come from an earlier interrupted migration and we never overwrite.
+fn move_entries(from: &Path, to: &Path) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let mut conflicts = Vec::new();
+    let entries = fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries.flatten() {
+        let target = to.join(entry.file_name());
+        if target.exists() {
+            conflicts.push(entry.path());
+            continue;
+        }
+        fs::rename(entry.path(), &target)
+            .map_err(|e| format!("Failed to move {} to {}: {}", entry.path().display(), target.display(), e))?;
+    }
+    Ok(conflicts)
+}
+
+// Item paths in the manifest are absolute, point them at the new folder
+fn rewrite_paths(value: &mut JsonValue, old_root: &str, new_root: &str) {
+    match value {
+        // Only whole path components, .WC_OVHL/abc must not match .WC_OVHL/abcdef
+        JsonValue::String(s)
+            if s.starts_with(old_root)
+                && s[old_root.len()..].chars().next().map_or(true, std::path::is_separator) =>
+        {
+            *s = format!("{}{}", new_root, &s[old_root.len()..]);
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(|v| rewrite_paths(v, old_root, new_root)),
+        JsonValue::Object(map) => map.values_mut().for_each(|v| rewrite_paths(v, old_root, new_root)),
+        _ => {}
+    }
+}
+
+fn reconcile_manifest(root: &Path, old_root: &Path) -> Result<JsonValue, String> {
+    let path = root.join(LEGACY_MANIFEST);
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut manifest: JsonValue =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    rewrite_paths(&mut manifest, &old_root.to_string_lossy(), &root.to_string_lossy());
+    let json = serde_json::to_string(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(manifest)
+}
+
+fn update_launcher_profiles(old_root: &Path, new_root: &Path) -> Result<usize, String> {
+    let lp_path = get_minecraft_folder().join("launcher_profiles.json");
+    if !lp_path.exists() {
+        return Ok(0);
+    }
+    let content = fs::read_to_string(&lp_path).map_err(|e| format!("Failed to read launcher profiles: {}", e))?;
+    let mut lp_obj: JsonValue =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse launcher profiles: {}", e))?;
+    let Some(profiles) = lp_obj.get_mut("profiles").and_then(|p| p.as_object_mut()) else {
+        return Ok(0);
+    };
+
+    let old_root = old_root.to_string_lossy();
+    let mut updated = 0;
+    for profile in profiles.values_mut() {
+        let points_here = profile
+            .get("gameDir")
+            .and_then(|d| d.as_str())
+            .map_or(false, |dir| Path::new(dir) == Path::new(old_root.as_ref()));
+        if points_here {
+            profile["gameDir"] = JsonValue::String(new_root.to_string_lossy().to_string());
+            updated += 1;
+        }
+    }
+    if updated > 0 {
+        let json = serde_json::to_string_pretty(&lp_obj).map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+        fs::write(&lp_path, json).map_err(|e| format!("Failed to write launcher profiles: {}", e))?;
+    }
+    Ok(updated)
+}
+
+/// Installation state for a pack installed without one, also used to adopt launcher profiles.
+pub fn adopt_installation(id: &str, root: &Path, manifest: &JsonValue) -> Result<(), String> {
+    if installation::load_installation(id).is_ok() {
+        return Ok(());
+    }
+    let text = |value: &JsonValue| value.as_str().unwrap_or_default().to_string();
+    let name = match text(&manifest["subtitle"]) {
+        subtitle if !subtitle.is_empty() => subtitle,
+        _ => text(&manifest["name"]),
+    };
+    let mut adopted = Installation::new_custom(
+        name,
+        text(&manifest["loader"]["minecraft_version"]),
+        text(&manifest["loader"]["type"]),
+        text(&manifest["loader"]["version"]),
+        "vanilla".to_string(),
+        text(&manifest["modpack_version"]),
+    );
+    adopted.id = id.to_string();
+    adopted.installation_path = root.to_path_buf();
+    if let Some(features) = manifest["enabled_features"].as_array() {
+        let features: Vec<String> = features.iter().filter_map(|f| f.as_str().map(str::to_string)).collect();
+        adopted.enabled_features = features.clone();
+        adopted.pre_install_features = features.clone();
+        adopted.pending_features = features.clone();
+        adopted.installed_features = features;
+    }
+    adopted.installed = true;
+    adopted.save()?;
+    installation::register_installation(&adopted)
+}
+
+fn migrate_dir(legacy: &Path, report: &mut MigrationReport) -> Result<(), String> {
+    let id = legacy
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Unexpected folder name {}", legacy.display()))?
+        .to_string();
+    let target = installation::get_installations_dir().join(&id);
+    info!("Migrating legacy install {} to {}", legacy.display(), target.display());
+
+    let conflicts = move_entries(legacy, &target)?;
+    let manifest = reconcile_manifest(&target, legacy)?;
+    let profiles = update_launcher_profiles(legacy, &target)?;
+    debug!("Pointed {} launcher profile(s) at {}", profiles, target.display());
+    adopt_installation(&id, &target, &manifest)?;
+
+    if conflicts.is_empty() {
+        if let Err(e) = fs::remove_dir(legacy) {
+            warn!("Failed to remove empty legacy folder {}: {}", legacy.display(), e);
+        }
+    } else {
+        warn!(
+            "{} entries of {} already existed in {} and were left in place",
+            conflicts.len(),
+            legacy.display(),
+            target.display()
+        );
+        report.leftovers.push(legacy.to_path_buf());
+    }
+    report.migrated.push(id);
+    Ok(())
+}
+
+pub fn run() -> MigrationReport {
+    let mut report = MigrationReport::default();
+    let legacy_dirs = find_legacy_dirs(&get_installer_dir());
+    if legacy_dirs.is_empty() {
+        return report;
+    }
+    info!("Found {} legacy install(s) to migrate", legacy_dirs.len());
+    for legacy in legacy_dirs {
+        if let Err(e) = migrate_dir(&legacy, &mut report) {
+            warn!("Failed to migrate {}: {}", legacy.display(), e);
+            report.leftovers.push(legacy);
+        }
+    }
+    report
+}
@@ -0,0 +1,3961 @@
+use async_trait::async_trait;
+use cached::proc_macro::cached;
+use cached::{Cached, SizedCache};
+use chrono::{DateTime, Utc};
+use dioxus::desktop::tao::window::Icon;
+use dioxus::prelude::LaunchBuilder;
+use dioxus::desktop::{Config as DioxusConfig, LogicalSize, WindowBuilder, WindowCloseBehaviour};
+use futures::StreamExt;
+use isahc::config::RedirectPolicy;
+use isahc::http::{HeaderMap, HeaderValue, StatusCode};
+use isahc::prelude::Configurable;
+use isahc::{AsyncBody, AsyncReadResponseExt, HttpClient, Request, Response};
+use log::{error, info, warn, debug};
+use platform_info::{PlatformInfo, PlatformInfoAPI, UNameAPI};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use simplelog::{
+    ColorChoice, CombinedLogger, Config as LogConfig, LevelFilter, TermLogger, TerminalMode,
+    WriteLogger,
+};
+use std::sync::Mutex;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::fs::File;
+use std::{backtrace::Backtrace, panic};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use std::boxed::Box;
+use std::pin::Pin;
+use futures::Future;
+use lazy_static::lazy_static;
+
+mod gui;
+mod launcher;
+mod changelog;
+mod installation;
+mod preset;
+mod universal;
+mod backup;
+mod capabilities;
+mod config_migration;
+mod crash_report;
+mod watch_mode;
+mod lock;
+mod share;
+mod verify_remote;
+mod http_mock;
+mod hashing;
+mod backup_store;
+mod transfer;
+mod wynncraft_compat;
+mod feature_usage;
+mod markdown;
+mod reminders;
+mod portable;
+mod artifact_cache;
+mod legacy_migration;
+mod profile_reconcile;
+mod loader_repair;
+mod forge;
+mod curseforge;
+mod partial_download;
+mod doctor;
+mod offline;
+mod icons;
+mod config_merge;
+mod manifest_diff;
+mod on_demand;
+mod download_schedule;
+mod download_queue;
+mod download_progress;
+mod retry;
+mod github;
+mod sources;
+mod install_journal;
+pub mod embed;
+mod java;
+mod pack_export;
+mod config_protection;
+mod orphan_files;
+mod feature_profiles;
+mod preset_diff;
+mod install_log;
+mod diagnostics;
+mod ms_auth;
+mod servers;
+mod options_merge;
+mod config_patches;
+mod safe_extract;
+mod direct_launch;
+mod verify;
+mod batch_update;
+mod instance_import;
+mod news;
+mod telemetry;
+mod i18n;
+mod theme;
+mod jvm_presets;
+mod screenshots;
+mod pack_order;
+mod manifest_validation;
+mod dev_manifest;
+mod channels;
+mod instance_lock;
+mod health;
+mod proxy;
+mod mirrors;
+mod content_store;
+mod install_location;
+mod shortcuts;
+mod tray;
+mod auto_update;
+mod secret;
+
+// Update your re-exports
+pub use launcher::{launch_modpack, update_jvm_args, get_jvm_args};
+pub use installation::{Installation, get_active_installation, load_all_installations};
+pub use preset::{Preset, load_presets};
+pub use universal::{UniversalManifest, load_universal_manifest, ModComponent};
+pub use universal::{ManifestError, ManifestErrorType};
+pub use changelog::{
+    Changelog, ChangelogEntry, HomePageStats, FooterButton, HomePageConfig, fetch_changelog
+};
+
+
+// CORRECTED: Use direct exports from backup module
+pub use backup::{
+    BackupConfig, BackupType, BackupMetadata, BackupProgress, BackupItem,
+    RollbackManager, RollbackOption, format_bytes,
+    calculate_directory_size, count_files_recursive,
+    create_zip_archive, extract_zip_archive
+};
+
+
+const CURRENT_MANIFEST_VERSION: i32 = 3;
+const GH_API: &str = "https://api.github.com/repos/";
+const GH_RAW: &str = "https://raw.githubusercontent.com/";
+const CONCURRENCY: usize = 14;
+const ATTEMPTS: usize = 3;
+const REPO: &str = "Wynncraft-Overhaul/majestic-overhaul/";
+
+const DEFAULT_UNIVERSAL_URL: &str = "https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/universal.json";
+const DEFAULT_PRESETS_URL: &str = "https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/presets.json";
+const DEFAULT_CHANGELOG_URL: &str = "https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/changelog.json";
+
+fn validate_safe_path(base: &Path, user_path: &str) -> Result<PathBuf, String> {
+    // Reject traversal by component, "v1..2.txt" is a fine file name
+    if user_path.contains('\0') || !safe_extract::is_contained(Path::new(user_path)) {
+        return Err("Invalid path detected".to_string());
+    }
+    
+    let target = base.join(user_path);
+    
+    // Canonicalize and verify it's still within base directory
+    match target.canonicalize() {
+        Ok(canonical) => {
+            match base.canonicalize() {
+                Ok(canonical_base) => {
+                    if canonical.starts_with(canonical_base) {
+                        Ok(canonical)
+                    } else {
+                        Err("Path traversal attempt detected".to_string())
+                    }
+                },
+                Err(_) => Err("Invalid base directory".to_string())
+            }
+        },
+        Err(_) => {
+            // If canonicalize fails, do a basic check
+            if target.starts_with(base) {
+                Ok(target)
+            } else {
+                Err("Invalid path".to_string())
+            }
+        }
+    }
+}
+
+// Installer events for the tracking worker. Analytics::record queues an event and writes the queue
+// to disk right away, a background thread sends what's queued a batch at a time. When sending fails
+// (offline, server down) the rest stays queued and the next try waits twice as long; whatever is
+// still queued when the installer closes goes out on the next start. The usage statistics setting
+// is checked when recording and again before sending, see telemetry.rs.
+
+const TRACKING_URL: &str = "https://tracking.commander07.workers.dev/track";
+const TRACKING_PROJECT_ID: &str = "55db8403a4f24f3aa5afd33fd1962888";
+const ANALYTICS_QUEUE_FILE: &str = "analytics_queue.json";
+// Events recorded close together (install + feature usage) go out together
+const ANALYTICS_BATCH_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+const ANALYTICS_BATCH_SIZE: usize = 20;
+const ANALYTICS_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+// A long time offline shouldn't grow the queue forever, the oldest events are dropped past this
+const ANALYTICS_MAX_QUEUED: usize = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Update { uuid: String, old_version: Option<String>, new_version: String },
+    Modify { uuid: String, features: Vec<String> },
+    Uninstall { uuid: String },
+    FeatureUsage { uuid: String, modpack_version: String, features: Vec<String> },
+    CrashReport { id: String, report: serde_json::Value },
+}
+
+impl Event {
+    fn action(&self) -> &'static str {
+        match self {
+            Event::Update { .. } => "update",
+            Event::Modify { .. } => "modify",
+            Event::Uninstall { .. } => "uninstall",
+            Event::FeatureUsage { .. } => feature_usage::FEATURE_USAGE_ACTION,
+            Event::CrashReport { .. } => "crash_report",
+        }
+    }
+
+    fn data_source_id(&self) -> &str {
+        match self {
+            Event::Update { uuid, .. }
+            | Event::Modify { uuid, .. }
+            | Event::Uninstall { uuid }
+            | Event::FeatureUsage { uuid, .. } => uuid,
+            Event::CrashReport { id, .. } => id,
+        }
+    }
+
+    fn additional_data(&self) -> serde_json::Value {
+        match self {
+            Event::Update { old_version, new_version, .. } => serde_json::json!({
+                "old_version": old_version,
+                "new_version": new_version,
+            }),
+            Event::Modify { features, .. } => serde_json::json!({ "features": features }),
+            Event::Uninstall { .. } => serde_json::json!({}),
+            Event::FeatureUsage { modpack_version, features, .. } => serde_json::json!({
+                "modpack_version": modpack_version,
+                "features": features,
+            }),
+            Event::CrashReport { report, .. } => report.clone(),
+        }
+    }
+}
+
+// The payload the tracking worker takes, stamped when the event happened rather than when it's sent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct QueuedEvent {
+    project_id: String,
+    data_source_id: String,
+    user_action: String,
+    additional_data: serde_json::Value,
+    timestamp: String,
+    version: String,
+    platform: String,
+}
+
+fn analytics_queue_path() -> PathBuf {
+    get_installer_dir().join(ANALYTICS_QUEUE_FILE)
+}
+
+fn load_analytics_queue() -> Vec<QueuedEvent> {
+    match fs::read_to_string(analytics_queue_path()) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            warn!("Dropping unreadable analytics queue: {}", e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn save_analytics_queue(events: &[QueuedEvent]) {
+    let path = analytics_queue_path();
+    let result = if events.is_empty() {
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    } else {
+        serde_json::to_string(events)
+            .map_err(|e| e.to_string())
+            .and_then(|text| fs::write(&path, text).map_err(|e| e.to_string()))
+    };
+    if let Err(e) = result {
+        warn!("Failed to save the analytics queue: {}", e);
+    }
+}
+
+lazy_static! {
+    // Loaded from disk on first use, the condvar wakes the sender when an event is queued
+    static ref ANALYTICS_QUEUE: (Mutex<Vec<QueuedEvent>>, std::sync::Condvar) =
+        (Mutex::new(load_analytics_queue()), std::sync::Condvar::new());
+}
+
+pub struct Analytics;
+
+impl Analytics {
+    /// Queues an event for sending, or keeps or drops it depending on the usage statistics setting.
+    pub fn record(event: Event) {
+        let action = event.action();
+        match telemetry::mode() {
+            telemetry::Mode::Send => {}
+            telemetry::Mode::LocalOnly => {
+                telemetry::record_local(action, event.data_source_id(), event.additional_data());
+                return;
+            }
+            telemetry::Mode::Off => {
+                debug!("Usage statistics are off, skipping event: {}", action);
+                return;
+            }
+        }
+        if let http_mock::HttpMode::Replay(_) = http_mock::mode() {
+            debug!("Replaying recorded HTTP, skipping event: {}", action);
+            return;
+        }
+
+        let (queue, wake) = &*ANALYTICS_QUEUE;
+        let mut events = queue.lock().unwrap_or_else(|e| e.into_inner());
+        events.push(QueuedEvent {
+            project_id: TRACKING_PROJECT_ID.to_string(),
+            data_source_id: event.data_source_id().to_string(),
+            user_action: action.to_string(),
+            additional_data: event.additional_data(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: env::consts::OS.to_string(),
+        });
+        let overflow = events.len().saturating_sub(ANALYTICS_MAX_QUEUED);
+        events.drain(..overflow);
+        save_analytics_queue(&events);
+        debug!("Queued event: {} ({} waiting)", action, events.len());
+        wake.notify_one();
+    }
+
+    /// Starts sending queued events, including ones left from earlier runs. Call once the config is applied.
+    pub fn start() {
+        if let Err(e) = std::thread::Builder::new()
+            .name("analytics".to_string())
+            .spawn(send_analytics_queue)
+        {
+            warn!("Failed to start sending usage statistics: {}", e);
+        }
+    }
+}
+
+fn send_analytics_queue() {
+    let (queue, wake) = &*ANALYTICS_QUEUE;
+    let http_client = build_http_client();
+    let mut delay = ANALYTICS_BATCH_DELAY;
+    loop {
+        {
+            let events = queue.lock().unwrap_or_else(|e| e.into_inner());
+            let _events = wake
+                .wait_while(events, |events| events.is_empty())
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        // Gives events recorded right after this one a chance to join the batch
+        std::thread::sleep(delay);
+
+        let batch: Vec<QueuedEvent> = {
+            let mut events = queue.lock().unwrap_or_else(|e| e.into_inner());
+            if telemetry::mode() != telemetry::Mode::Send {
+                // Turned off while events were waiting, they must not go out later
+                info!("Usage statistics are no longer sent, dropping {} queued event(s)", events.len());
+                events.clear();
+                save_analytics_queue(&events);
+                continue;
+            }
+            events.iter().take(ANALYTICS_BATCH_SIZE).cloned().collect()
+        };
+
+        let mut sent = 0;
+        if offline::is_enabled() {
+            debug!("Working offline, keeping {} event(s) queued", batch.len());
+        } else {
+            // The worker takes one event per request
+            for event in &batch {
+                match send_analytics_event(&http_client, event) {
+                    Ok(_) => sent += 1,
+                    Err(e) => {
+                        warn!("Failed to send tracking event: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut events = queue.lock().unwrap_or_else(|e| e.into_inner());
+        events.retain(|event| !batch[..sent].contains(event));
+        save_analytics_queue(&events);
+        if sent == batch.len() {
+            debug!("Sent {} tracking event(s)", sent);
+            delay = ANALYTICS_BATCH_DELAY;
+        } else {
+            delay = (delay * 2).min(ANALYTICS_MAX_BACKOFF);
+            debug!("{} event(s) still queued, trying again in {}s", events.len(), delay.as_secs());
+        }
+    }
+}
+
+fn send_analytics_event(http_client: &HttpClient, event: &QueuedEvent) -> Result<(), String> {
+    let body = serde_json::to_string(event).map_err(|e| format!("Failed to serialize event: {}", e))?;
+    let request = Request::post(TRACKING_URL)
+        .header("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .body(body)
+        .map_err(|e| format!("Failed to create tracking request: {}", e))?;
+    let response = http_client.send(request).map_err(|e| e.to_string())?;
+    // A rejected event won't be accepted on a retry either
+    if response.status().is_client_error() {
+        warn!("Tracking server rejected '{}': {}", event.user_action, response.status());
+        return Ok(());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Tracking server returned status: {}", response.status()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PackName {
+    name: String,
+    uuid: String,
+}
+
+fn default_id() -> String {
+    String::from("default")
+}
+
+fn default_enabled_features() -> Vec<String> {
+    vec![default_id()]
+}
+
+fn default_hidden() -> bool {
+    false
+}
+
+fn default_false() -> bool {
+    false
+}
+
+macro_rules! add_headers {
+    ($items:expr, $($headers:expr),*) => {
+        $items.$(header($headers.next().unwrap().0, $headers.next().unwrap().1))*
+    };
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    resp: Response<AsyncBody>,
+    bytes: Vec<u8>,
+}
+
+fn resp_rebuilder(resp: &Response<AsyncBody>, bytes: &Vec<u8>) -> Response<AsyncBody> {
+    let builder = Response::builder()
+        .status(resp.status())
+        .version(resp.version());
+    let builder = add_headers!(builder, resp.headers().into_iter());
+    builder.body(AsyncBody::from(bytes.to_owned())).unwrap()
+}
+
+impl CachedResponse {
+    async fn new(mut resp: Response<AsyncBody>) -> Self {
+        let bytes = resp.bytes().await.unwrap();
+
+        Self {
+            resp: resp_rebuilder(&resp, &bytes),
+            bytes,
+        }
+    }
+}
+
+impl Clone for CachedResponse {
+    fn clone(&self) -> Self {
+        Self {
+            resp: resp_rebuilder(&self.resp, &self.bytes),
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedHttpClient {
+    http_client: HttpClient,
+}
+
+impl CachedHttpClient {
+    fn new() -> CachedHttpClient {
+        CachedHttpClient {
+            http_client: build_http_client(),
+        }
+    }
+
+    async fn get_async<T: Into<String> + Clone + Debug>(
+        &self,
+        url: T,
+    ) -> Result<Response<AsyncBody>, isahc::Error> {
+        if let Some(replayed) = http_mock::replay("GET", &url.clone().into()) {
+            return replayed;
+        }
+        let url_string: String = url.clone().into();
+        if offline::is_enabled() {
+            return offline::recall(&url_string);
+        }
+        let mut err = None;
+        for attempt in 0..ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(retry::delay(attempt)).await;
+            }
+            retry::check(&url_string)?;
+            let resp = get_cached(&self.http_client, url_string.clone()).await;
+            match resp {
+                Ok(v) if retry::is_retryable_status(v.resp.status().as_u16()) && attempt + 1 < ATTEMPTS => {
+                    warn!("Got {} for '{url:?}'. Retrying!", v.resp.status());
+                    retry::record_failure(&url_string);
+                    forget_cached(&url_string).await;
+                }
+                Ok(v) => {
+                    if v.resp.status() == StatusCode::OK {
+                        retry::record_success(&url_string);
+                        offline::remember(&url_string, &v.bytes);
+                        offline::remember_validators(&url_string, v.resp.headers());
+                    } else {
+                        // Only good answers stay in the cache, the next call asks again
+                        forget_cached(&url_string).await;
+                    }
+                    return Ok(v.resp);
+                }
+                Err(v) => {
+                    warn!("Failed to get '{url:?}', returned '{v:#?}'. Retrying!");
+                    retry::record_failure(&url_string);
+                    forget_cached(&url_string).await;
+                    err = Some(v);
+                }
+            }
+        }
+        error!("Failed to get '{url:?}', returned '{err:#?}'.");
+        Err(err.unwrap()) // unwrap can't fail
+    }
+
+    async fn get_nocache<T: Into<String> + Clone>(
+        &self,
+        url: T,
+    ) -> Result<Response<AsyncBody>, isahc::Error> {
+        let url: String = url.into();
+        if let Some(replayed) = http_mock::replay("GET", &url) {
+            return replayed;
+        }
+        let mut err = None;
+        for attempt in 0..ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(retry::delay(attempt)).await;
+            }
+            retry::check(&url)?;
+            let mut request = Request::get(&url);
+            if let Some((key, value)) = github::auth_header(&url) {
+                request = request.header(key, value);
+            }
+            let request = request.body(()).expect("GET request is always valid");
+            let resp = self.http_client.send_async(request).await;
+            match resp {
+                Ok(v) if retry::is_retryable_status(v.status().as_u16()) && attempt + 1 < ATTEMPTS => {
+                    warn!("Got {} for '{}'. Retrying!", v.status(), url);
+                    retry::record_failure(&url);
+                }
+                Ok(v) => {
+                    if v.status().is_success() {
+                        retry::record_success(&url);
+                    }
+                    return Ok(http_mock::record("GET", &url, v).await);
+                }
+                Err(v) => {
+                    retry::record_failure(&url);
+                    err = Some(v);
+                }
+            }
+        }
+        Err(err.unwrap()) // unwrap can't fail
+    }
+
+    async fn with_headers<T: Into<String>>(
+        &self,
+        url: T,
+        headers: &[(&str, &str)],
+    ) -> Result<Response<AsyncBody>, isahc::Error> {
+        let url: String = url.into();
+        if let Some(replayed) = http_mock::replay("GET", &url) {
+            return replayed;
+        }
+        let mut request = add_headers!(Request::get(url.clone()), headers.iter());
+        if let Some((key, value)) = github::auth_header(&url) {
+            request = request.header(key, value);
+        }
+        let resp = self.http_client.send_async(request.body(()).unwrap()).await?;
+        Ok(http_mock::record("GET", &url, resp).await)
+    }
+}
+
+#[cached(
+    ty = "SizedCache<String, Result<CachedResponse, isahc::Error>>",
+    create = "{ SizedCache::with_size(100) }",
+    convert = r#"{ format!("{}", url) }"#
+)]
+async fn get_cached(http_client: &HttpClient, url: String) -> Result<CachedResponse, isahc::Error> {
+    let resp = http_client.send_async(conditional_request(&url)).await;
+    match resp {
+        Ok(val) if val.status() == StatusCode::NOT_MODIFIED => {
+            let val = not_modified_response(&url, val)?;
+            Ok(CachedResponse::new(http_mock::record("GET", &url, val).await).await)
+        }
+        Ok(val) => Ok(CachedResponse::new(http_mock::record("GET", &url, val).await).await),
+        Err(err) => Err(err),
+    }
+}
+
+// Asks only for changes when a copy from an earlier run is kept, see offline.rs
+fn conditional_request(url: &str) -> Request<()> {
+    let mut request = Request::get(url);
+    if let Some((key, value)) = github::auth_header(url) {
+        request = request.header(key, value);
+    }
+    if let Some(validators) = offline::validators(url) {
+        if let Some(etag) = validators.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+    }
+    request.body(()).expect("GET request is always valid")
+}
+
+// A 304 is the kept copy with the headers the server sent now
+fn not_modified_response(url: &str, resp: Response<AsyncBody>) -> Result<Response<AsyncBody>, isahc::Error> {
+    let bytes = offline::recall_bytes(url).ok_or_else(|| {
+        isahc::Error::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} wasn't modified but the kept copy is gone", url),
+        ))
+    })?;
+    debug!("{} not modified, using the kept copy", url);
+    let mut builder = Response::builder().status(StatusCode::OK).version(resp.version());
+    for (key, value) in resp.headers() {
+        builder = builder.header(key, value);
+    }
+    Ok(builder.body(AsyncBody::from(bytes)).expect("Cached response is always valid"))
+}
+
+// Failures would otherwise be served from the cache to every retry
+async fn forget_cached(url: &str) {
+    GET_CACHED.lock().await.cache_remove(&url.to_string());
+}
+
+fn build_http_client() -> HttpClient {
+    let builder = HttpClient::builder()
+        .redirect_policy(RedirectPolicy::Limit(5))
+        .default_headers(&[(
+            "User-Agent",
+            concat!("wynncraft-overhaul/installer/", env!("CARGO_PKG_VERSION")),
+        )]);
+    proxy::apply(builder).build().unwrap()
+}
+
+#[async_trait]
+trait Downloadable {
+    async fn download(
+        &self,
+        modpack_root: &Path,
+        loader_type: &str,
+        http_client: &CachedHttpClient,
+    ) -> Result<PathBuf, DownloadError>;
+
+    fn new(
+        name: String,
+        source: String,
+        location: String,
+        version: String,
+        path: Option<PathBuf>,
+        id: String,
+        authors: Vec<Author>,
+    ) -> Self;
+    fn get_name(&self) -> &String;
+    fn get_location(&self) -> &String;
+    fn get_version(&self) -> &String;
+    fn get_path(&self) -> &Option<PathBuf>;
+    fn get_id(&self) -> &String;
+    fn get_source(&self) -> &String;
+    fn get_authors(&self) -> &Vec<Author>;
+    fn get_mirrors(&self) -> &Vec<universal::Mirror>;
+    // new() leaves them empty, kept so the installed manifest can still repair from them
+    fn with_mirrors(self, mirrors: Vec<universal::Mirror>) -> Self;
+}
+
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Include {
+    location: String,
+    #[serde(default = "default_id")]
+    id: String,
+    name: Option<String>,
+    authors: Option<Vec<Author>>,
+    #[serde(default = "default_false")]
+    optional: bool,
+    #[serde(default = "default_false")]
+    default_enabled: bool,
+    #[serde(default = "default_false")]
+    ignore_update: bool,
+    // NEW: Add can_reset field
+    #[serde(default = "default_false")]
+    pub can_reset: bool,
+    // NEW: Add missing fields for consistency
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub dependencies: Option<Vec<String>>,
+    // Option file keys that replace the player's value on every install, see options_merge.rs
+    #[serde(default)]
+    pub force_keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Config {
+    // Missing in configs written before versioning, see config_migration
+    #[serde(default)]
+    config_version: u32,
+    launcher: String,
+    first_launch: Option<bool>, // option for backwars compatibiliy
+    // Global preferences, all defaulted so old config files keep loading
+    #[serde(default)]
+    general: GeneralSettings,
+    #[serde(default)]
+    network: NetworkSettings,
+    #[serde(default)]
+    privacy: PrivacySettings,
+    #[serde(default)]
+    storage: StorageSettings,
+    #[serde(default)]
+    advanced: AdvancedSettings,
+    // Only used when developer mode is on
+    #[serde(default)]
+    watch: watch_mode::WatchSettings,
+    // Only used when developer mode is on, see dev_manifest.rs
+    #[serde(default)]
+    dev_manifest: dev_manifest::DevManifestSettings,
+    #[serde(default)]
+    lock: lock::LockSettings,
+    // Modpack sources besides the default repo, see sources.rs
+    #[serde(default)]
+    sources: Vec<sources::PackSource>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            config_version: config_migration::CURRENT_CONFIG_VERSION,
+            launcher: String::from("vanilla"),
+            first_launch: Some(true),
+            general: GeneralSettings::default(),
+            network: NetworkSettings::default(),
+            privacy: PrivacySettings::default(),
+            storage: StorageSettings::default(),
+            advanced: AdvancedSettings::default(),
+            watch: watch_mode::WatchSettings::default(),
+            dev_manifest: dev_manifest::DevManifestSettings::default(),
+            lock: lock::LockSettings::default(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default)]
+struct GeneralSettings {
+    language: String,
+    theme: String,
+    // "default" or "colorblind", status colors are also shape/icon coded either way
+    color_palette: String,
+    check_updates_on_startup: bool,
+    // MultiMC and Prism start the instance themselves, so the game can come up right after an install
+    launch_after_install: bool,
+    // Keep running in the tray when the window is closed, see tray.rs
+    tray_mode: bool,
+    tray_check_hours: u64,
+}
+
+impl Default for GeneralSettings {
+    fn default() -> Self {
+        Self {
+            language: String::from("en"),
+            theme: String::from("default"),
+            color_palette: String::from("default"),
+            check_updates_on_startup: true,
+            launch_after_install: false,
+            tray_mode: false,
+            tray_check_hours: 6,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default)]
+struct NetworkSettings {
+    // e.g. http://host:port or socks5://host:port
+    proxy: Option<String>,
+    // Without a proxy above, use the system's one, see proxy.rs
+    use_system_proxy: bool,
+    proxy_username: Option<String>,
+    proxy_password: Option<secret::Secret>,
+    // PEM file trusted on top of the system certificates, for firewalls that intercept TLS
+    ca_bundle: Option<String>,
+    // KiB/s, None means unlimited
+    bandwidth_limit_kbps: Option<u64>,
+    max_concurrent_downloads: usize,
+    curseforge_api_key: Option<secret::Secret>,
+    // Raises the GitHub API rate limit, see github.rs
+    github_token: Option<secret::Secret>,
+    // Install only from the download cache, see offline.rs
+    work_offline: bool,
+    // Downloads wait while Windows says the connection is metered, see download_schedule.rs
+    pause_on_metered: bool,
+    // Local hours, "update tonight" starts at off_peak_start_hour
+    off_peak_start_hour: u8,
+    off_peak_end_hour: u8,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            use_system_proxy: true,
+            proxy_username: None,
+            proxy_password: None,
+            ca_bundle: None,
+            bandwidth_limit_kbps: None,
+            max_concurrent_downloads: CONCURRENCY,
+            curseforge_api_key: None,
+            github_token: None,
+            work_offline: false,
+            pause_on_metered: false,
+            off_peak_start_hour: 2,
+            off_peak_end_hour: 6,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default)]
+struct PrivacySettings {
+    telemetry_enabled: bool,
+    // Statistics are only kept on this computer, see telemetry.rs
+    local_only: bool,
+    // Opt-in, reports which optional components are enabled after an install
+    share_feature_usage: bool,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self {
+            telemetry_enabled: true,
+            local_only: false,
+            share_feature_usage: false,
+        }
+    }
+}
+
+impl PrivacySettings {
+    fn telemetry_mode(&self) -> telemetry::Mode {
+        match (self.telemetry_enabled, self.local_only) {
+            (false, _) => telemetry::Mode::Off,
+            (true, true) => telemetry::Mode::LocalOnly,
+            (true, false) => telemetry::Mode::Send,
+        }
+    }
+
+    fn feature_usage_consent(&self) -> bool {
+        self.telemetry_enabled && self.share_feature_usage
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default)]
+struct StorageSettings {
+    // None means the default location inside .WC_OVHL
+    cache_dir: Option<String>,
+    cache_limit_mb: u64,
+    // Machine-wide download cache shared by every OS user, None keeps downloads per user
+    shared_cache_dir: Option<String>,
+    // Hardlink identical files of all installations to one copy, see content_store.rs
+    deduplicate: bool,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            cache_dir: None,
+            cache_limit_mb: 2048,
+            shared_cache_dir: None,
+            deduplicate: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default)]
+struct AdvancedSettings {
+    log_level: String,
+    developer_mode: bool,
+    // Start vanilla installations without the launcher, see direct_launch.rs
+    direct_launch: bool,
+    microsoft_client_id: Option<String>,
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        Self {
+            log_level: String::from("debug"),
+            developer_mode: false,
+            direct_launch: false,
+            microsoft_client_id: None,
+        }
+    }
+}
+
+impl AdvancedSettings {
+    fn level_filter(&self) -> LevelFilter {
+        match self.log_level.to_lowercase().as_str() {
+            "error" => LevelFilter::Error,
+            "warn" => LevelFilter::Warn,
+            "info" => LevelFilter::Info,
+            "trace" => LevelFilter::Trace,
+            _ => LevelFilter::Debug,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Author {
+    name: String,
+    link: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Included {
+    md5: String,
+    files: Vec<String>,
+}
+
+macro_rules! gen_downloadble_impl {
+    ($item:ty, $type:literal) => {
+        #[async_trait]
+        impl Downloadable for $item {
+            async fn download(
+                &self,
+                modpack_root: &Path,
+                loader_type: &str,
+                http_client: &CachedHttpClient,
+            ) -> Result<PathBuf, DownloadError> {
+                debug!("Downloading: {self:#?}");
+                let cache_key = artifact_cache::key(&self.source, &self.location, &self.version, loader_type, $type);
+                if let Some(path) = artifact_cache::restore(&cache_key, &item_dir(modpack_root, $type)?) {
+                    return Ok(path);
+                }
+                if offline::is_enabled() {
+                    return Err(DownloadError::NotCached(self.name.clone()));
+                }
+                let res = match self.source.as_str() {
+                    "modrinth" => {
+                        download_from_modrinth(self, modpack_root, loader_type, $type, http_client)
+                            .await
+                    }
+                    "ddl" => download_from_ddl(self, modpack_root, $type, http_client).await,
+                    "mediafire" => {
+                        download_from_mediafire(self, modpack_root, $type, http_client).await
+                    }
+                    "curseforge" => download_from_curseforge(self, modpack_root, $type, http_client).await,
+                    other => Err(DownloadError::UnsupportedSource(self.name.clone(), other.to_string())),
+                };
+                debug!("Downloaded '{}' with result: {:#?}", self.get_name(), res);
+                if let Ok(path) = &res {
+                    artifact_cache::store(&cache_key, path);
+                }
+                res
+            }
+
+            fn new(
+                name: String,
+                source: String,
+                location: String,
+                version: String,
+                path: Option<PathBuf>,
+                id: String,
+                authors: Vec<Author>,
+            ) -> Self {
+                Self {
+                    name,
+                    source,
+                    location,
+                    version,
+                    path,
+                    id,
+                    authors,
+                    ignore_update: false, // Add this line with default value
+                    mirrors: Vec::new(),
+                    size_bytes: None,
+                }
+            }
+
+            fn get_name(&self) -> &String {
+                &self.name
+            }
+            fn get_location(&self) -> &String {
+                &self.location
+            }
+            fn get_version(&self) -> &String {
+                &self.version
+            }
+            fn get_path(&self) -> &Option<PathBuf> {
+                &self.path
+            }
+            fn get_id(&self) -> &String {
+                &self.id
+            }
+            fn get_source(&self) -> &String {
+                &self.source
+            }
+            fn get_authors(&self) -> &Vec<Author> {
+                &self.authors
+            }
+            fn get_mirrors(&self) -> &Vec<universal::Mirror> {
+                &self.mirrors
+            }
+            fn with_mirrors(mut self, mirrors: Vec<universal::Mirror>) -> Self {
+                self.mirrors = mirrors;
+                self
+            }
+        }
+    };
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Mod {
+    name: String,
+    source: String,
+    location: String,
+    version: String,
+    path: Option<PathBuf>,
+    #[serde(default = "default_id")]
+    id: String,
+    authors: Vec<Author>,
+    // NEW: Add ignore_update support
+    #[serde(default = "default_false")]
+    ignore_update: bool,
+    // Fallback locations, see mirrors.rs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mirrors: Vec<universal::Mirror>,
+    // Download size, lets the features tab estimate a selection before anything is downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Shaderpack {
+    name: String,
+    source: String,
+    location: String,
+    version: String,
+    path: Option<PathBuf>,
+    #[serde(default = "default_id")]
+    id: String,
+    authors: Vec<Author>,
+    // NEW: Add ignore_update support
+    #[serde(default = "default_false")]
+    ignore_update: bool,
+    // Fallback locations, see mirrors.rs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mirrors: Vec<universal::Mirror>,
+    // Download size, lets the features tab estimate a selection before anything is downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct Resourcepack {
+    name: String,
+    source: String,
+    location: String,
+    version: String,
+    path: Option<PathBuf>,
+    #[serde(default = "default_id")]
+    id: String,
+    authors: Vec<Author>,
+    // NEW: Add ignore_update support
+    #[serde(default = "default_false")]
+    ignore_update: bool,
+    // Fallback locations, see mirrors.rs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mirrors: Vec<universal::Mirror>,
+    // Download size, lets the features tab estimate a selection before anything is downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+}
+
+gen_downloadble_impl!(Mod, "mod");
+gen_downloadble_impl!(Shaderpack, "shaderpack");
+gen_downloadble_impl!(Resourcepack, "resourcepack");
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+struct Loader {
+    r#type: String,
+    version: String,
+    minecraft_version: String,
+}
+
+impl Loader {
+    // Name of the version the launcher profile starts, also the folder under .minecraft/versions
+    fn version_id(&self) -> Option<String> {
+        match self.r#type.as_str() {
+            "fabric" => Some(format!("fabric-loader-{}-{}", self.version, self.minecraft_version)),
+            "quilt" => Some(format!("quilt-loader-{}-{}", self.version, self.minecraft_version)),
+            other => forge::version_id(other, &self.minecraft_version, &self.version),
+        }
+    }
+
+    fn profile_url(&self) -> Option<String> {
+        match self.r#type.as_str() {
+            "fabric" => Some(format!(
+                "https://meta.fabricmc.net/v2/versions/loader/{}/{}/profile/json",
+                self.minecraft_version, self.version
+            )),
+            "quilt" => Some(format!(
+                "https://meta.quiltmc.org/v3/versions/loader/{}/{}/profile/json",
+                self.minecraft_version, self.version
+            )),
+            _ => None,
+        }
+    }
+
+    async fn download(&self, root: &Path, _: &str, http_client: &CachedHttpClient) -> Result<PathBuf, DownloadError> {
+        if forge::is_forge_like(&self.r#type) {
+            // The installer runs Java for a while, keep it off the async executor
+            let loader = self.clone();
+            let root = root.to_path_buf();
+            return tokio::task::spawn_blocking(move || {
+                forge::install(&loader.r#type, &loader.minecraft_version, &loader.version, &root)
+            })
+            .await
+            .map_err(|e| DownloadError::LoaderFailed(self.r#type.clone(), format!("installer task panicked: {}", e)))?
+            .map_err(|e| DownloadError::LoaderFailed(self.r#type.clone(), e));
+        }
+        match (self.profile_url(), self.version_id()) {
+            (Some(url), Some(loader_name)) => download_loader_json(&url, &loader_name, root, http_client).await,
+            _ => Err(DownloadError::LoaderFailed(self.r#type.clone(), "unsupported loader".to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+struct Feature {
+    id: String,
+    name: String,
+    default: bool,
+    #[serde(default = "default_hidden")]
+    hidden: bool,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+struct RemoteInclude {
+    pub location: String,
+    pub path: Option<String>,
+    #[serde(default = "default_id")]
+    pub id: String,
+    pub version: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub authors: Option<Vec<Author>>,
+    // ADD MISSING FIELDS:
+    #[serde(default = "default_false")]
+    pub optional: bool,
+    #[serde(default = "default_false")]
+    pub default_enabled: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<universal::Mirror>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+struct Manifest {
+    manifest_version: i32,
+    modpack_version: String,
+    name: String,
+    subtitle: String,
+    tab_group: Option<usize>,
+    tab_title: Option<String>,
+    tab_color: Option<String>,
+    tab_background: Option<String>,
+    tab_primary_font: Option<String>,
+    tab_secondary_font: Option<String>,
+    settings_background: Option<String>,
+    popup_title: Option<String>,
+    popup_contents: Option<String>,
+    description: String,
+    icon: bool,
+    uuid: String,
+    loader: Loader,
+    mods: Vec<Mod>,
+    shaderpacks: Vec<Shaderpack>,
+    resourcepacks: Vec<Resourcepack>,
+    remote_include: Option<Vec<RemoteInclude>>,
+    include: Vec<Include>,
+    features: Vec<Feature>,
+    // Add trending indicator field
+    trend: Option<bool>,
+    #[serde(default = "default_enabled_features")]
+    enabled_features: Vec<String>,
+    included_files: Option<HashMap<String, Included>>,
+    source: Option<String>,
+    installer_path: Option<String>,
+    max_mem: Option<i32>,
+    min_mem: Option<i32>,
+    java_args: Option<String>,
+    
+    // Add the new fields
+    category: Option<String>,
+    is_new: Option<bool>,
+    short_description: Option<String>,
+
+    #[serde(default)]
+    capabilities: Option<capabilities::ManifestCapabilities>,
+
+    #[serde(default)]
+    config_presets: Vec<universal::ConfigPreset>,
+
+    #[serde(default)]
+    shader_tiers: Vec<universal::ShaderTier>,
+
+    // Protected config paths this version replaces on purpose, see config_protection.rs
+    #[serde(default)]
+    config_overrides: Vec<String>,
+
+    #[serde(default)]
+    servers: Vec<universal::ServerEntry>,
+
+    #[serde(default)]
+    config_patches: Vec<config_patches::ConfigPatch>,
+
+    // Resource pack ids, highest priority first, see pack_order.rs
+    #[serde(default)]
+    resourcepack_order: Vec<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize)]
+struct LauncherProfile {
+    lastUsed: String,
+    lastVersionId: String,
+    created: String,
+    name: String,
+    icon: Option<String>,
+    r#type: String,
+    gameDir: Option<String>,
+    javaDir: Option<String>,
+    javaArgs: Option<String>,
+    logConfig: Option<String>,
+    logConfigIsXML: Option<bool>,
+    resolution: Option<HashMap<String, i32>>,
+}
+#[derive(Debug, Deserialize, Serialize)]
+struct ModrinthFile {
+    url: String,
+    filename: String,
+}
+#[derive(Debug, Deserialize, Serialize)]
+struct ModrinthObject {
+    version_number: String,
+    files: Vec<ModrinthFile>,
+    loaders: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GithubRepo {
+    // Theres a lot more fields but we only care about default_branch
+    // https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#get-a-repository
+    default_branch: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct GithubAsset {
+    name: String,
+    id: i32,
+    browser_download_url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct GithubRelease {
+    tag_name: String,
+    body: Option<String>,
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+struct GithubBranch {
+    name: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize)]
+struct MMCComponent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cachedVolatile: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dependencyOnly: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    important: Option<bool>,
+    uid: String,
+    version: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize)]
+struct MMCPack {
+    components: Vec<MMCComponent>,
+    formatVersion: i32,
+}
+
+fn mmc_instance_cfg(manifest: &Manifest) -> String {
+    format!(
+        "InstanceType=OneSix\nname={}\nOverrideMemory=true\nMaxMemory={}\nMinMemory={}\nOverrideJavaArgs=true\nJvmArgs={}\nMinecraftWinWidth=854\nMinecraftWinHeight=480\n",
+        manifest.name,
+        manifest.max_mem.unwrap_or(4096),
+        manifest.min_mem.unwrap_or(1024),
+        manifest.java_args.as_ref().unwrap_or(&String::from("-XX:+UseG1GC"))
+    )
+}
+
+// None for loaders MultiMC doesn't know
+fn mmc_pack(loader: &Loader) -> Option<MMCPack> {
+    let loader_uid = match &loader.r#type[..] {
+        "fabric" => "net.fabricmc.fabric-loader",
+        "quilt" => "org.quiltmc.quilt-loader",
+        other => forge::mmc_uid(other)?,
+    };
+    Some(MMCPack {
+        components: vec![
+            MMCComponent {
+                cachedVolatile: Some(true),
+                dependencyOnly: Some(false),
+                important: Some(false),
+                uid: String::from("net.minecraft"),
+                version: loader.minecraft_version.clone(),
+            },
+            MMCComponent {
+                cachedVolatile: Some(true),
+                dependencyOnly: Some(false),
+                important: Some(false),
+                uid: loader_uid.to_string(),
+                version: loader.version.clone(),
+            },
+        ],
+        formatVersion: 1,
+    })
+}
+
+#[derive(Debug)]
+enum DownloadError {
+    Non200StatusCode(String, u16),
+    FailedToParseResponse(String, serde_json::Error),
+    IoError(String, std::io::Error),
+    HttpError(String, isahc::Error),
+    MissingFilename(String),
+    CouldNotFindItem(String),
+    MedafireMissingDDL(String),
+    CurseForgeMissingApiKey(String),
+    // Item, project page, folder the file has to be put in
+    CurseForgeDistributionDenied(String, String, PathBuf),
+    // Working offline and the item isn't in the download cache
+    NotCached(String),
+    // Item, source
+    UnsupportedSource(String, String),
+    UnsupportedType(String),
+    // Loader, what went wrong
+    LoaderFailed(String, String),
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadError::Non200StatusCode(item, x) => write!(
+                f,
+                "Encountered '{x}' error code when attempting to download: '{item}'"
+            ),
+
+            DownloadError::FailedToParseResponse(item, e) => write!(
+                f,
+                "Failed to parse download response: '{e:#?}' when attempting to download: '{item}'"
+            ),
+            DownloadError::IoError(item, e) => write!(
+                f,
+                "Encountered io error: '{e:#?}' when attempting to download: '{item}'"
+            ),
+            DownloadError::HttpError(item, e) => match retry::host_unavailable(e) {
+                Some(unavailable) => write!(f, "Couldn't download '{item}': {unavailable}"),
+                None => write!(
+                    f,
+                    "Encountered http error: '{e:#?}' when attempting to download: '{item}'"
+                ),
+            },
+            DownloadError::MissingFilename(item) => {
+                write!(f, "Could not get filename for: '{item}'")
+            }
+            DownloadError::CouldNotFindItem(item) => {
+                write!(f, "Could not find item: '{item}'")
+            }
+            DownloadError::MedafireMissingDDL(item) => {
+                write!(f, "Could not get DDL link from Nediafire: '{item}'")
+            }
+            DownloadError::CurseForgeMissingApiKey(item) => write!(
+                f,
+                "'{item}' is hosted on CurseForge, set a CurseForge API key in the preferences to download it"
+            ),
+            DownloadError::CurseForgeDistributionDenied(item, page, dir) => write!(
+                f,
+                "The author of '{item}' doesn't allow downloads outside of CurseForge. Download it from {page}, put it in '{}' and try again",
+                dir.display()
+            ),
+            DownloadError::NotCached(item) => {
+                write!(f, "'{item}' isn't in the download cache, turn off 'Work offline' to download it")
+            }
+            DownloadError::UnsupportedSource(item, source) => {
+                write!(f, "'{item}' uses the unsupported source '{source}'")
+            }
+            DownloadError::UnsupportedType(r#type) => {
+                write!(f, "Unsupported item type: '{}'", r#type)
+            }
+            DownloadError::LoaderFailed(loader, e) => {
+                write!(f, "Failed to install the {loader} loader: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+#[derive(Debug)]
+enum LauncherProfileError {
+    IoError(std::io::Error),
+    InvalidJson(serde_json::Error),
+    ProfilesNotObject,
+    NoProfiles,
+    RootNotObject,
+    IconNotFound,
+    InvalidIcon(image::error::ImageError),
+    // A loader type from a newer manifest this installer has no profile for
+    UnsupportedLoader(String),
+}
+
+impl Display for LauncherProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LauncherProfileError::IoError(e) => write!(
+                f,
+                "Encountered IO error when creating launcher profile: {e}"
+            ),
+            LauncherProfileError::InvalidJson(e) => {
+                write!(f, "Invalid 'launcher_profiles.json': {e}")
+            }
+            LauncherProfileError::NoProfiles => {
+                write!(f, "'launcher_profiles.json' missing 'profiles' key")
+            }
+            LauncherProfileError::ProfilesNotObject => {
+                write!(f, "Expected 'launcher_profiles.profiles' to be 'object'")
+            }
+            LauncherProfileError::RootNotObject => {
+                write!(f, "Expected 'launcher_profiles' to be 'object'")
+            }
+            LauncherProfileError::IconNotFound => {
+                write!(f, "'manifest.icon' was set to true but no icon was found")
+            }
+            LauncherProfileError::InvalidIcon(e) => write!(
+                f,
+                "Encountered image error when creating launcher profile: {e}"
+            ),
+            LauncherProfileError::UnsupportedLoader(loader) => write!(
+                f,
+                "The modpack uses the loader '{loader}', which this installer doesn't support. Update the installer and try again"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LauncherProfileError {}
+
+impl From<std::io::Error> for LauncherProfileError {
+    fn from(value: std::io::Error) -> Self {
+        LauncherProfileError::IoError(value)
+    }
+}
+
+impl From<serde_json::Error> for LauncherProfileError {
+    fn from(value: serde_json::Error) -> Self {
+        LauncherProfileError::InvalidJson(value)
+    }
+}
+
+impl From<image::error::ImageError> for LauncherProfileError {
+    fn from(value: image::error::ImageError) -> Self {
+        LauncherProfileError::InvalidIcon(value)
+    }
+}
+
+
+fn get_filename(headers: &HeaderMap<HeaderValue>, url: &str) -> Result<String, DownloadError> {
+    let filename = if let Some(x) = headers.get("content-disposition") {
+        let x = x.to_str().unwrap();
+        if x.contains("attachment") {
+            let re = Regex::new(r#"filename="(.*?)""#).unwrap();
+            match match re.captures(x) {
+                Some(v) => Ok(v),
+                None => Err(DownloadError::MissingFilename(url.to_string())),
+            } {
+                Ok(v) => v[1].to_string(),
+                Err(e) => match url.split('/').last() {
+                    Some(v) => v.to_string(),
+                    None => {
+                        return Err(e);
+                    }
+                }
+                .to_string(),
+            }
+        } else {
+            url
+                .split('/')
+                .last()
+                .unwrap() // this should be impossible to error because all urls will have "/"s in them and if they dont it gets caught earlier
+                .to_string()
+        }
+    } else {
+        url
+            .split('/')
+            .last()
+            .unwrap() // this should be impossible to error because all urls will have "/"s in them and if they dont it gets caught earlier
+            .to_string()
+    };
+    Ok(filename)
+}
+
+async fn download_loader_json(
+    url: &str,
+    loader_name: &str,
+    root: &Path,
+    http_client: &CachedHttpClient,
+) -> Result<PathBuf, DownloadError> {
+    match loader_repair::check_files(root, loader_name, true) {
+        loader_repair::LoaderHealth::Ok => return Ok(PathBuf::new()),
+        loader_repair::LoaderHealth::Missing => {}
+        health => warn!("Replacing broken loader profile {}: {:?}", loader_name, health),
+    }
+    let mut resp = http_client
+        .get_async(url)
+        .await
+        .map_err(|e| DownloadError::HttpError(loader_name.to_string(), e))?;
+    if resp.status() != StatusCode::OK {
+        return Err(DownloadError::Non200StatusCode(loader_name.to_string(), resp.status().as_u16()));
+    }
+    let json = resp
+        .text()
+        .await
+        .map_err(|e| DownloadError::IoError(loader_name.to_string(), e))?;
+    write_loader_profile(root, loader_name, &json).map_err(|e| DownloadError::LoaderFailed(loader_name.to_string(), e))
+}
+
+// The launcher only needs the version json, the jar is an empty placeholder it expects to exist
+fn write_loader_profile(root: &Path, loader_name: &str, json: &str) -> Result<PathBuf, String> {
+    let loader_path = root.join(Path::new(&format!("versions/{}", &loader_name)));
+    fs::create_dir_all(&loader_path).map_err(|e| format!("Failed to create loader directory: {}", e))?;
+    fs::write(loader_path.join(Path::new(&format!("{}.json", &loader_name))), json)
+        .map_err(|e| format!("Failed to write loader json: {}", e))?;
+    fs::write(loader_path.join(Path::new(&format!("{}.jar", &loader_name))), "")
+        .map_err(|e| format!("Failed to write loader dummy jar: {}", e))?;
+    Ok(loader_path)
+}
+
+fn item_dir(modpack_root: &Path, r#type: &str) -> Result<PathBuf, DownloadError> {
+    match r#type {
+        "mod" => Ok(modpack_root.join(Path::new("mods"))),
+        "resourcepack" => Ok(modpack_root.join(Path::new("resourcepacks"))),
+        "shaderpack" => Ok(modpack_root.join(Path::new("shaderpacks"))),
+        _ => Err(DownloadError::UnsupportedType(r#type.to_string())), // this should be impossible
+    }
+}
+
+async fn download_from_ddl<T: Downloadable + Debug>(
+    item: &T,
+    modpack_root: &Path,
+    r#type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<PathBuf, DownloadError> {
+    let dist = item_dir(modpack_root, r#type)?;
+    let part = http_client
+        .download_resumable(item.get_name(), item.get_location(), &dist, &[])
+        .await?;
+    let filename = get_filename(&part.headers, item.get_location())?;
+    let final_dist = dist.join(filename);
+    debug!("Writing '{}' to '{:#?}'", item.get_name(), final_dist);
+    part.persist(&final_dist)
+        .map_err(|e| DownloadError::IoError(item.get_name().to_string(), e))
+}
+
+async fn download_from_modrinth<T: Downloadable + Debug>(
+    item: &T,
+    modpack_root: &Path,
+    loader_type: &str,
+    r#type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<PathBuf, DownloadError> {
+    let mut resp = match http_client
+        .get_nocache(format!(
+            "https://api.modrinth.com/v2/project/{}/version",
+            item.get_location()
+        ))
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(DownloadError::HttpError(item.get_name().to_string(), e));
+        }
+    };
+    if resp.status() != StatusCode::OK {
+        return Err(DownloadError::Non200StatusCode(
+            item.get_name().to_string(),
+            resp.status().as_u16(),
+        ));
+    }
+    let resp_text = match resp.text().await {
+        Ok(v) => v,
+        Err(e) => return Err(DownloadError::IoError(item.get_name().to_string(), e)),
+    };
+    let resp_obj: Vec<ModrinthObject> = match serde_json::from_str(&resp_text) {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(DownloadError::FailedToParseResponse(
+                item.get_name().to_string(),
+                e,
+            ));
+        }
+    };
+    let dist = item_dir(modpack_root, r#type)?;
+    match fs::create_dir_all(&dist) {
+        Ok(_) => (),
+        Err(e) => return Err(DownloadError::IoError(item.get_name().to_string(), e)),
+    }
+    for _mod in resp_obj {
+        if &_mod.version_number == item.get_version()
+            && (_mod.loaders.contains(&String::from("minecraft"))
+                || _mod.loaders.contains(&String::from(loader_type))
+                || r#type == "shaderpack")
+        {
+            let part = http_client
+                .download_resumable(item.get_name(), &_mod.files[0].url, &dist, &[])
+                .await?;
+            let final_dist = dist.join(Path::new(&_mod.files[0].filename));
+            debug!("Writing '{}' to '{:#?}'", item.get_name(), final_dist);
+            return part
+                .persist(&final_dist)
+                .map_err(|e| DownloadError::IoError(item.get_name().to_string(), e));
+        }
+    }
+    Err(DownloadError::CouldNotFindItem(item.get_name().to_string()))
+}
+
+async fn download_from_curseforge<T: Downloadable + Debug>(
+    item: &T,
+    modpack_root: &Path,
+    r#type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<PathBuf, DownloadError> {
+    let Some(api_key) = curseforge::api_key() else {
+        return Err(DownloadError::CurseForgeMissingApiKey(item.get_name().to_string()));
+    };
+    let mut resp = match http_client
+        .with_headers(
+            curseforge::file_url(item.get_location(), item.get_version()),
+            &[("x-api-key", api_key.as_str()), ("Accept", "application/json")],
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return Err(DownloadError::HttpError(item.get_name().to_string(), e)),
+    };
+    if resp.status() != StatusCode::OK {
+        return Err(DownloadError::Non200StatusCode(
+            item.get_name().to_string(),
+            resp.status().as_u16(),
+        ));
+    }
+    let resp_text = match resp.text().await {
+        Ok(v) => v,
+        Err(e) => return Err(DownloadError::IoError(item.get_name().to_string(), e)),
+    };
+    let file = match curseforge::parse_file(&resp_text) {
+        Ok(v) => v,
+        Err(e) => return Err(DownloadError::FailedToParseResponse(item.get_name().to_string(), e)),
+    };
+    let dist = item_dir(modpack_root, r#type)?;
+    match fs::create_dir_all(&dist) {
+        Ok(_) => (),
+        Err(e) => return Err(DownloadError::IoError(item.get_name().to_string(), e)),
+    }
+    let final_dist = dist.join(Path::new(&file.file_name));
+    let Some(url) = file.download_url else {
+        // Put there by hand after an earlier attempt told the user to
+        if final_dist.is_file() {
+            debug!("Using manually downloaded '{}' for '{}'", file.file_name, item.get_name());
+            return Ok(final_dist);
+        }
+        return Err(DownloadError::CurseForgeDistributionDenied(
+            item.get_name().to_string(),
+            curseforge::project_page(item.get_location()),
+            dist,
+        ));
+    };
+    let part = http_client.download_resumable(item.get_name(), &url, &dist, &[]).await?;
+    debug!("Writing '{}' to '{:#?}'", item.get_name(), final_dist);
+    part.persist(&final_dist)
+        .map_err(|e| DownloadError::IoError(item.get_name().to_string(), e))
+}
+
+async fn download_from_mediafire<T: Downloadable + Debug>(
+    item: &T,
+    modpack_root: &Path,
+    r#type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<PathBuf, DownloadError> {
+    let mut resp = match http_client.get_nocache(item.get_location()).await {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(DownloadError::HttpError(item.get_name().to_string(), e));
+        }
+    };
+    if resp.status() != StatusCode::OK {
+        return Err(DownloadError::Non200StatusCode(
+            item.get_name().to_string(),
+            resp.status().as_u16(),
+        ));
+    }
+    let mediafire = match resp.text().await {
+        Ok(v) => v,
+        Err(e) => return Err(DownloadError::IoError(item.get_name().to_string(), e)),
+    };
+    let re = Regex::new(r#"Download file"\s*href="(.*?)""#).unwrap(); // wont error pattern is valid
+    let ddl = &(match re.captures(&mediafire) {
+        Some(v) => v,
+        None => {
+            return Err(DownloadError::MedafireMissingDDL(
+                item.get_name().to_string(),
+            ))
+        }
+    })[1];
+    let dist = item_dir(modpack_root, r#type)?;
+    let part = http_client.download_resumable(item.get_name(), ddl, &dist, &[]).await?;
+    let cd_header = match std::str::from_utf8(
+        match part.headers.get("content-disposition") {
+            Some(v) => v,
+            None => return Err(DownloadError::MissingFilename(item.get_name().to_string())),
+        }
+        .as_bytes(),
+    ) {
+        Ok(v) => v,
+        Err(_) => return Err(DownloadError::MissingFilename(item.get_name().to_string())),
+    };
+    let filename = if cd_header.contains("attachment") {
+        match cd_header.split("filename=").last() {
+            Some(v) => v,
+            None => return Err(DownloadError::MissingFilename(item.get_name().to_string())),
+        }
+        .replace('"', "")
+    } else {
+        return Err(DownloadError::MissingFilename(item.get_name().to_string()));
+    };
+    let final_dist = dist.join(filename);
+    debug!("Writing '{}' to '{:#?}'", item.get_name(), final_dist);
+    part.persist(&final_dist)
+        .map_err(|e| DownloadError::IoError(item.get_name().to_string(), e))
+}
+
+fn get_app_data() -> PathBuf {
+    if env::consts::OS == "linux" {
+        dirs::home_dir().unwrap()
+    } else if env::consts::OS == "windows" || env::consts::OS == "macos" {
+        dirs::config_dir().unwrap()
+    } else {
+        panic!("Unsupported os '{}'!", env::consts::OS)
+    }
+}
+
+// Where the installer keeps its own files, next to the executable in portable mode
+fn get_installer_dir() -> PathBuf {
+    match portable::root() {
+        Some(root) => root.join(".WC_OVHL"),
+        None => get_app_data().join(".WC_OVHL"),
+    }
+}
+
+fn get_multimc_folder(multimc: &str) -> Result<PathBuf, String> {
+    let path = match env::consts::OS {
+        "linux" => {
+            // For system packages
+            let lpath = get_app_data().join(format!(".local/share/{}", multimc));
+
+            if lpath.exists() {
+                lpath
+            } else {
+                // For Flatpak packages, multimc doesnt have a flatpak so we only need prism
+                get_app_data().join(format!(".var/app/org.prismlauncher.PrismLauncher/data/{}", multimc))
+            }
+        },
+        "windows" | "macos" => get_app_data().join(multimc),
+        _ => panic!("Unsupported os '{}'!", env::consts::OS),
+    };
+    match path.metadata() {
+        Ok(metadata) => {
+            if metadata.is_dir() && path.join("instances").is_dir() {
+                Ok(path)
+            } else {
+                Err(String::from("MultiMC directory is not a valid directory!"))
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn get_minecraft_folder() -> PathBuf {
+    if env::consts::OS == "macos" {
+        get_app_data().join("minecraft")
+    } else {
+        get_app_data().join(".minecraft")
+    }
+}
+
+fn get_modpack_root(launcher: &Launcher, uuid: &str) -> PathBuf {
+    match launcher {
+        Launcher::Vanilla(_) => {
+            // The installations directory structure, unless the installation was put elsewhere
+            let root = install_location::vanilla_game_dir(uuid);
+            fs::create_dir_all(&root).expect("Failed to create modpack folder");
+            root
+        }
+        Launcher::MultiMC(root) => {
+            let root = root.join(Path::new(&format!("instances/{}/.minecraft", uuid)));
+            fs::create_dir_all(&root).expect("Failed to create modpack folder");
+            root
+        }
+    }
+}
+
+fn create_launcher_profile(
+    installer_profile: &InstallerProfile,
+    icon: Option<icons::ProfileIcon>,
+    java_dir: Option<PathBuf>,
+) -> Result<(), LauncherProfileError> {
+    let now = SystemTime::now();
+    let now: DateTime<Utc> = now.into();
+    let now = now.to_rfc3339();
+    let manifest = &installer_profile.manifest;
+    let modpack_root = get_modpack_root(
+        installer_profile
+            .launcher
+            .as_ref()
+            .expect("No launcher selected!"),
+        &manifest.uuid,
+    );
+    
+    match installer_profile
+        .launcher
+        .as_ref()
+        .expect("Asked to create launcher profile without knowing launcher!")
+    {
+        Launcher::Vanilla(_) => {
+            // Already scaled down and encoded, see icons.rs
+            let icon = match &icon {
+                Some(icon) => icon.data_url.clone(),
+                None => String::from("Furnace"),
+            };
+
+            // Build JVM args properly
+            let mut jvm_args = String::new();
+            
+            // Add default optimization flags if no custom args provided
+            if manifest.java_args.is_none() {
+                jvm_args.push_str("-XX:+UseG1GC -XX:+UnlockExperimentalVMOptions -XX:G1NewSizePercent=20 -XX:G1ReservePercent=20 -XX:MaxGCPauseMillis=50 -XX:G1HeapRegionSize=32M");
+            }
+            
+            if let Some(x) = &manifest.java_args {
+                if !jvm_args.is_empty() {
+                    jvm_args.push(' ');
+                }
+                jvm_args.push_str(x);
+            }
+            
+            if let Some(x) = manifest.max_mem {
+                if !jvm_args.is_empty() {
+                    jvm_args.push(' ');
+                }
+                jvm_args.push_str(&format!("-Xmx{}M", x));
+            }
+            
+            if let Some(x) = manifest.min_mem {
+                if !jvm_args.is_empty() {
+                    jvm_args.push(' ');
+                }
+                jvm_args.push_str(&format!("-Xms{}M", x));
+            }
+
+            let profile = LauncherProfile {
+                lastUsed: now.to_string(),
+                lastVersionId: manifest
+                    .loader
+                    .version_id()
+                    .ok_or_else(|| LauncherProfileError::UnsupportedLoader(manifest.loader.r#type.clone()))?,
+                created: now,
+                name: manifest.name.clone(), // Use the installation name, not subtitle
+                icon: Some(icon),
+                r#type: String::from("custom"),
+                gameDir: Some(modpack_root.to_str().unwrap().to_string()),
+                javaDir: java_dir.map(|p| p.to_string_lossy().to_string()),
+                javaArgs: if jvm_args.is_empty() {
+                    None
+                } else {
+                    Some(jvm_args)
+                },
+                logConfig: None,
+                logConfigIsXML: None,
+                resolution: None,
+            };
+
+            let lp_file_path = get_minecraft_folder().join(Path::new("launcher_profiles.json"));
+
+            fs::create_dir_all(get_minecraft_folder())?;
+            
+            // Ensure launcher_profiles.json exists
+            if !lp_file_path.exists() {
+                // Create default launcher_profiles.json
+                let default_profiles = serde_json::json!({
+                    "profiles": {},
+                    "settings": {
+                        "enableAdvanced": false,
+                        "enableAnalytics": true,
+                        "enableHistorical": false,
+                        "enableReleases": true,
+                        "enableSnapshots": false,
+                        "keepLauncherOpen": false,
+                        "profileSorting": "byName",
+                        "showGameLog": false,
+                        "showMenu": false,
+                        "soundOn": false
+                    },
+                    "version": 3
+                });
+                
+                fs::write(&lp_file_path, serde_json::to_string_pretty(&default_profiles)?)?;
+                debug!("Created default launcher_profiles.json");
+            }
+
+            let mut lp_obj: JsonValue = serde_json::from_str(&fs::read_to_string(&lp_file_path)?)?;
+            
+            // Ensure profiles object exists
+            if !lp_obj.is_object() {
+                return Err(LauncherProfileError::RootNotObject);
+            }
+            
+            let obj = lp_obj.as_object_mut().unwrap();
+            if !obj.contains_key("profiles") {
+                obj.insert("profiles".to_string(), serde_json::Value::Object(serde_json::Map::new()));
+            }
+            
+            let profiles = obj.get_mut("profiles").unwrap().as_object_mut()
+                .ok_or(LauncherProfileError::ProfilesNotObject)?;
+            
+            // Profiles restored from a backup can point here under another key, keep only ours
+            for key in profile_reconcile::duplicate_keys(profiles, &manifest.uuid, &modpack_root) {
+                debug!("Removing duplicate launcher profile '{}' for {}", key, manifest.uuid);
+                profiles.remove(&key);
+            }
+
+            // Insert or update the profile using the UUID as the key
+            profiles.insert(manifest.uuid.clone(), serde_json::to_value(profile)?);
+            
+            fs::write(lp_file_path, serde_json::to_string_pretty(&lp_obj)?)?;
+            debug!("Successfully created/updated launcher profile for: {} (UUID: {})", manifest.name, manifest.uuid);
+        }
+        Launcher::MultiMC(root) => {
+            // MultiMC/Prism Launcher profile creation
+            let instance_path = root.join("instances").join(&manifest.uuid);
+            fs::create_dir_all(&instance_path)?;
+            
+            // Save icon if available
+            if let Some(icon) = &icon {
+                let icon_path = instance_path.join("icon.png");
+                match icon.image.save(&icon_path) {
+                    Ok(_) => debug!("Saved instance icon to: {:?}", icon_path),
+                    Err(e) => debug!("Failed to save instance icon: {}", e),
+                }
+            }
+            
+            // Create instance.cfg and mmc-pack.json for MultiMC/Prism
+            fs::write(instance_path.join("instance.cfg"), mmc_instance_cfg(manifest))?;
+            let pack = mmc_pack(&manifest.loader)
+                .ok_or_else(|| LauncherProfileError::UnsupportedLoader(manifest.loader.r#type.clone()))?;
+            
+            fs::write(
+                instance_path.join("mmc-pack.json"),
+                serde_json::to_string_pretty(&pack)?,
+            )?;
+
+            debug!("Successfully created MultiMC/Prism instance: {} (UUID: {})", manifest.name, manifest.uuid);
+        }
+    }
+    Ok(())
+}
+
+// Add function to delete launcher profile
+pub fn delete_launcher_profile(installation_uuid: &str, launcher_type: &str) -> Result<(), String> {
+    debug!("Deleting launcher profile for installation: {}", installation_uuid);
+    
+    match launcher_type {
+        "vanilla" => {
+            let lp_file_path = get_minecraft_folder().join("launcher_profiles.json");
+            
+            if lp_file_path.exists() {
+                let content = fs::read_to_string(&lp_file_path)
+                    .map_err(|e| format!("Failed to read launcher profiles: {}", e))?;
+                    
+                let mut lp_obj: JsonValue = serde_json::from_str(&content)
+                    .map_err(|e| format!("Failed to parse launcher profiles: {}", e))?;
+                
+                if let Some(profiles) = lp_obj.get_mut("profiles").and_then(|p| p.as_object_mut()) {
+                    if profiles.remove(installation_uuid).is_some() {
+                        fs::write(&lp_file_path, serde_json::to_string_pretty(&lp_obj)
+                            .map_err(|e| format!("Failed to serialize profiles: {}", e))?)
+                            .map_err(|e| format!("Failed to write launcher profiles: {}", e))?;
+                        
+                        debug!("Successfully removed profile from vanilla launcher");
+                    } else {
+                        debug!("Profile {} not found in vanilla launcher", installation_uuid);
+                    }
+                }
+            }
+        },
+        launcher_type if launcher_type.starts_with("multimc") || launcher_type.starts_with("custom") => {
+            // For MultiMC/Prism, the instance directory is already deleted by the installation deletion
+            debug!("MultiMC/Prism instance will be deleted with installation directory");
+        },
+        _ => {
+            debug!("Unknown launcher type for profile deletion: {}", launcher_type);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Panics:
+///     If path is not located in modpack_root
+macro_rules! validate_item_path {
+    ($item:expr, $modpack_root:expr) => {
+        if $item.get_path().is_some() {
+            if $item
+                .get_path()
+                .as_ref()
+                .unwrap()
+                .parent()
+                .expect("Illegal item file path!")
+                .parent()
+                .expect("Illegal item dir path!")
+                == $modpack_root
+            {
+                $item
+            } else {
+                panic!("{:?}'s path was not located in modpack root!", $item);
+            }
+        } else {
+            $item
+        }
+    };
+}
+
+fn get_installed_packs(launcher: &Launcher) -> Result<Vec<PackName>, std::io::Error> {
+    let mut packs = vec![];
+    let manifest_paths: Vec<PathBuf> = match launcher {
+        Launcher::Vanilla(_) => {
+            fs::read_dir(installation::get_installations_dir())?.filter_map(|entry| {
+                let path = entry.ok()?.path().join("manifest.json");
+                if path.exists() {Some(path)} else {None}
+            }).collect()
+        },
+        Launcher::MultiMC(root) => {
+            fs::read_dir(root.join("instances/"))?.filter_map(|entry| {
+                let path = entry.ok()?.path().join(".minecraft/manifest.json");
+                if path.exists() {Some(path)} else {None}
+            }).collect()
+        },
+    };
+    for path in manifest_paths {
+        let manifest: Result<Manifest, serde_json::Error> = serde_json::from_str(&fs::read_to_string(path).unwrap());
+        if let Ok(manifest) = manifest {
+            packs.push(PackName { name: manifest.subtitle, uuid: manifest.uuid })
+        }
+    }
+    
+    Ok(packs)
+}
+
+fn uninstall(launcher: &Launcher, uuid: &str) -> Result<(), std::io::Error> {
+    info!("Uninstalling modpack: '{uuid}'!");
+    let instance = match launcher {
+        Launcher::Vanilla(_) => {
+            // Custom game dirs only when the installer set them up, see install_location.rs
+            if !install_location::owns_game_dir(uuid) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    format!("the game dir of '{uuid}' wasn't set up by the installer, not emptying it"),
+                ));
+            }
+            install_location::vanilla_game_dir(uuid)
+        }
+        Launcher::MultiMC(root) => {
+            root.join(format!("instances/{uuid}/.minecraft"))
+        }
+    };
+    if instance.is_dir() {
+        fs::remove_dir_all(&instance)?;
+        info!("Removed: {instance:#?}");
+        fs::create_dir(instance)?;
+        if let Launcher::Vanilla(_) = launcher {
+            install_location::mark_game_dir(uuid)?;
+        }
+    } else {
+        error!("Failed to uninstall '{uuid}'");
+    }
+    Analytics::record(Event::Uninstall { uuid: uuid.to_string() });
+    info!("Uninstalled modpack!");
+    Ok(())
+}
+
+async fn download_helper<T: Downloadable + Debug, F: FnMut() + Clone>(
+    items: Vec<T>,
+    enabled_features: &Vec<String>,
+    modpack_root: &Path,
+    loader_type: &str,
+    http_client: &CachedHttpClient,
+    progress_callback: F,
+    is_update: bool,
+    ignore_update_items: &std::collections::HashSet<String>,
+    installation_id: &str,
+    phase: &str,
+) -> Result<Vec<T>, DownloadError> {
+    let results = futures::stream::iter(items.into_iter().map(|item| async {
+        // FIXED: Proper logic for determining if item should be included
+        let should_include = if item.get_id() == "default" {
+            // Always include the "default" item
+            debug!("Including default item: {}", item.get_name());
+            true
+        } else {
+            // Check if this item should be included based on enabled_features
+            let is_enabled = enabled_features.contains(item.get_id());
+            debug!("Item '{}' (ID: {}) - enabled: {}, in features: {:?}", 
+                   item.get_name(), item.get_id(), is_enabled, enabled_features);
+            is_enabled
+        };
+        
+        // Check if we should ignore this item during updates
+        let should_ignore_update = is_update && ignore_update_items.contains(item.get_id());
+        
+        if item.get_path().is_none() && should_include && !should_ignore_update {
+            debug!("Downloading item: {} (ID: {})", item.get_name(), item.get_id());
+            let (path, mirror) = match mirrors::download(&item, modpack_root, loader_type, http_client).await {
+                Ok(downloaded) => downloaded,
+                Err(e) => {
+                    install_log::record(installation_id, install_log::Level::Error, phase, Some(item.get_name()), &e.to_string());
+                    return Err(e);
+                }
+            };
+            let message = match mirror {
+                Some(mirror) => format!("downloaded {} from mirror {}", item.get_version(), mirror),
+                None => format!("downloaded {}", item.get_version()),
+            };
+            install_log::record(installation_id, install_log::Level::Info, phase, Some(item.get_name()), &message);
+            (progress_callback.clone())();
+            Ok(T::new(
+                item.get_name().to_owned(),
+                item.get_source().to_owned(),
+                item.get_location().to_owned(),
+                item.get_version().to_owned(),
+                Some(path),
+                item.get_id().to_owned(),
+                item.get_authors().to_owned(),
+            )
+            .with_mirrors(item.get_mirrors().clone()))
+        } else {
+            let item = validate_item_path!(item, modpack_root);
+            let path;
+            
+            if should_ignore_update && item.get_path().is_some() {
+                debug!("Ignoring update for: '{}' (ignore_update=true)", item.get_name());
+                path = item.get_path().to_owned();
+            } else if !should_include && item.get_path().is_some() {
+                debug!("Removing disabled item: '{}' (not in enabled_features)", item.get_name());
+                let _ = fs::remove_file(item.get_path().as_ref().unwrap());
+                install_log::record(installation_id, install_log::Level::Info, phase, Some(item.get_name()), "removed, turned off");
+                path = None;
+            } else if !should_include {
+                debug!("Skipping disabled item: '{}' (not in enabled_features)", item.get_name());
+                path = None;
+            } else {
+                debug!("Keeping existing item: '{}' (enabled)", item.get_name());
+                path = item.get_path().to_owned();
+            }
+            
+            Ok(T::new(
+                item.get_name().to_owned(),
+                item.get_source().to_owned(),
+                item.get_location().to_owned(),
+                item.get_version().to_owned(),
+                path,
+                item.get_id().to_owned(),
+                item.get_authors().to_owned(),
+            )
+            .with_mirrors(item.get_mirrors().clone()))
+        }
+    }))
+    .buffer_unordered(download_queue::QUEUE.max_parallel())
+    .collect::<Vec<Result<T, DownloadError>>>()
+    .await;
+    
+    let mut return_vec = vec![];
+    for res in results {
+        match res {
+            Ok(v) => return_vec.push(v),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(return_vec)
+}
+
+async fn download_zip(
+    name: &str,
+    http_client: &CachedHttpClient,
+    url: &str,
+    path: &Path,
+    cache_key: &str,
+    // Only these entries (relative to `path`) are extracted, for repairs
+    only: Option<std::collections::HashSet<PathBuf>>,
+) -> Result<Vec<String>, DownloadError> {
+    debug!("Downloading '{}'", name);
+    // Own folder per include, several remote includes can be downloaded into the same path at once
+    let staging = path.join(format!(".tmp_include_{}", &cache_key[..12]));
+    let zipfile_path = match artifact_cache::restore(cache_key, &staging) {
+        Some(cached) => cached,
+        None => {
+            fs::create_dir_all(&staging).map_err(|e| DownloadError::IoError(name.to_string(), e))?;
+            let part = http_client
+                .download_resumable(name, url, &staging, &[("Accept", "application/octet-stream")])
+                .await?;
+            let zipfile_path = part
+                .persist(&staging.join("tmp_include.zip"))
+                .map_err(|e| DownloadError::IoError(name.to_string(), e))?;
+            artifact_cache::store(cache_key, &zipfile_path);
+            zipfile_path
+        }
+    };
+    debug!("Downloaded '{}'", name);
+    // Big includes take a while to unpack, that shouldn't hold up the executor
+    let (task_name, target) = (name.to_string(), path.to_path_buf());
+    let files = tokio::task::spawn_blocking(move || extract_zip(&task_name, &zipfile_path, &target, only.as_ref()))
+        .await
+        .map_err(|e| DownloadError::IoError(name.to_string(), std::io::Error::new(std::io::ErrorKind::Other, e)))??;
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        warn!("Failed to remove {}: {}", staging.display(), e);
+    }
+    Ok(files)
+}
+
+// Files extracted between two progress events, a pack of small configs would flood the channel
+const EXTRACT_PROGRESS_STEP: usize = 25;
+
+fn extract_zip(
+    name: &str,
+    zipfile_path: &Path,
+    path: &Path,
+    only: Option<&std::collections::HashSet<PathBuf>>,
+) -> Result<Vec<String>, DownloadError> {
+    let io_error = |e: std::io::Error| DownloadError::IoError(name.to_string(), e);
+    debug!("Unzipping '{}'", name);
+    let zipfile = fs::File::open(zipfile_path).map_err(io_error)?;
+    let mut archive = zip::ZipArchive::new(zipfile).map_err(|e| io_error(e.into()))?;
+    let wanted = |relative: &Path| only.map_or(true, |only| only.contains(relative)).then(|| relative.to_path_buf());
+    let files = safe_extract::extract_all(&mut archive, path, wanted, |done, total| {
+        if done % EXTRACT_PROGRESS_STEP == 0 {
+            download_progress::publish_extraction(name, done as u64, total as u64, false);
+        }
+    })
+    .map_err(|e| io_error(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+    download_progress::publish_extraction(name, files.len() as u64, files.len() as u64, true);
+    debug!("Unzipped '{}'", name);
+    Ok(files.into_iter().map(|file| file.to_string_lossy().to_string()).collect())
+}
+
+fn remote_include_cache_key(location: &str, version: &str) -> String {
+    artifact_cache::key("remote_include", location, version, "", "remote_include")
+}
+
+
+// What the last install extracted for `remote`, if it's still that version and all of it is still there
+fn unchanged_remote_include<'a>(installer_profile: &'a InstallerProfile, remote: &RemoteInclude) -> Option<&'a Included> {
+    let previous = installer_profile.local_manifest.as_ref()?.included_files.as_ref()?.get(&remote.id)?;
+    if installer_profile.installed && previous.md5 == remote.version && previous.files.iter().all(|f| Path::new(f).exists()) {
+        Some(previous)
+    } else {
+        None
+    }
+}
+
+// Everything an offline install would have to download but can't find in the cache
+fn missing_offline<T: Downloadable>(
+    items: &[T],
+    enabled_features: &[String],
+    loader_type: &str,
+    r#type: &str,
+    missing: &mut Vec<String>,
+) {
+    for item in items {
+        let included = item.get_id() == "default" || enabled_features.contains(item.get_id());
+        if !included || item.get_path().is_some() {
+            continue;
+        }
+        let key = artifact_cache::key(item.get_source(), item.get_location(), item.get_version(), loader_type, r#type);
+        if !artifact_cache::contains(&key) {
+            missing.push(item.get_name().clone());
+        }
+    }
+}
+
+// Add this helper function before the install function
+
+fn resolve_dependencies(
+    feature_id: &str,
+    enabled_features: &mut Vec<String>,
+    universal_manifest: &UniversalManifest,
+) {
+    // Check all component types for dependencies
+    let all_components: Vec<(&str, Option<&Vec<String>>)> = universal_manifest.mods.iter()
+        .map(|c| (c.id.as_str(), c.dependencies.as_ref()))
+        .chain(universal_manifest.shaderpacks.iter()
+            .map(|c| (c.id.as_str(), c.dependencies.as_ref())))
+        .chain(universal_manifest.resourcepacks.iter()
+            .map(|c| (c.id.as_str(), c.dependencies.as_ref())))
+        .chain(universal_manifest.include.iter()
+            .map(|c| (c.id.as_str(), c.dependencies.as_ref())))
+        .chain(universal_manifest.remote_include.iter()
+            .map(|c| (c.id.as_str(), c.dependencies.as_ref())))
+        .collect();
+    
+    // Find the component
+    if let Some((_, Some(deps))) = all_components.iter().find(|(id, _)| id == &feature_id) {
+        for dep in deps.iter() {
+            if !enabled_features.contains(dep) {
+                debug!("Auto-enabling dependency {} for {}", dep, feature_id);
+                enabled_features.push(dep.clone());
+                // Recursively resolve dependencies of dependencies
+                resolve_dependencies(dep, enabled_features, universal_manifest);
+            }
+        }
+    }
+}
+
+// Update the install function's feature resolution section
+
+async fn install<F: FnMut() + Clone>(installer_profile: &InstallerProfile, mut progress_callback: F) -> Result<(), String> {
+    info!("Installing modpack");
+    
+    // Get the universal manifest to properly determine what should be installed
+    let pack_source = installer_profile.pack_source();
+    let universal_manifest = match crate::universal::load_universal_manifest(&installer_profile.http_client, Some(&pack_source.universal_url())).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            error!("Failed to load universal manifest: {:?}", e);
+            return Err(format!("Failed to load universal manifest: {:?}", e));
+        }
+    };
+    
+    // Build the complete list of features that should be enabled
+    let mut effective_enabled_features = installer_profile.enabled_features.clone();
+    
+    // Add all default-enabled components (keeping existing logic)
+    for component in &universal_manifest.mods {
+        if component.default_enabled && !effective_enabled_features.contains(&component.id) {
+            debug!("Adding default-enabled mod: {} ({})", component.id, component.name);
+            effective_enabled_features.push(component.id.clone());
+        }
+    }
+    
+    for component in &universal_manifest.shaderpacks {
+        if component.default_enabled && !effective_enabled_features.contains(&component.id) {
+            debug!("Adding default-enabled shaderpack: {} ({})", component.id, component.name);
+            effective_enabled_features.push(component.id.clone());
+        }
+    }
+    
+    for component in &universal_manifest.resourcepacks {
+        if component.default_enabled && !effective_enabled_features.contains(&component.id) {
+            debug!("Adding default-enabled resourcepack: {} ({})", component.id, component.name);
+            effective_enabled_features.push(component.id.clone());
+        }
+    }
+    
+    for include in &universal_manifest.include {
+        if include.default_enabled && !include.id.is_empty() && !effective_enabled_features.contains(&include.id) {
+            debug!("Adding default-enabled include: {} ({})", include.id, include.location);
+            effective_enabled_features.push(include.id.clone());
+        }
+    }
+    
+    for remote in &universal_manifest.remote_include {
+        if remote.default_enabled && !effective_enabled_features.contains(&remote.id) {
+            debug!("Adding default-enabled remote include: {} ({})", 
+                   remote.id, remote.name.as_ref().unwrap_or(&remote.id));
+            effective_enabled_features.push(remote.id.clone());
+        }
+    }
+    
+    // Always ensure "default" is in the list
+    if !effective_enabled_features.contains(&"default".to_string()) {
+        effective_enabled_features.insert(0, "default".to_string());
+    }
+    
+    // Resolve dependencies for all enabled features
+    let features_to_check = effective_enabled_features.clone();
+    for feature in features_to_check {
+        resolve_dependencies(&feature, &mut effective_enabled_features, &universal_manifest);
+    }
+    
+    // Remove duplicates while preserving order
+    let mut seen = std::collections::HashSet::new();
+    effective_enabled_features.retain(|item| seen.insert(item.clone()));
+    
+    debug!("Final enabled features list: {:?}", effective_enabled_features);
+
+    // The features tab keeps conflicts and requires_any in check, this catches presets, imported
+    // selections and manifest changes that got past it
+    if let Err(e) = universal_manifest.validate_selection(&effective_enabled_features) {
+        error!("{}", e);
+        return Err(e.to_string());
+    }
+    
+    // UPDATED: Calculate weighted progress points based on expected time/complexity
+    let mut total_progress_points = 0;
+    let mut download_counts = (0, 0, 0, 0, 0); // (mods, shaders, resources, includes, remote_includes)
+    
+    let is_update = installer_profile.installed;
+    
+    // Count what will be downloaded
+    for mod_item in &installer_profile.manifest.mods {
+        let should_include = mod_item.id == "default" || effective_enabled_features.contains(&mod_item.id);
+        let needs_download = should_include && mod_item.path.is_none();
+        if needs_download {
+            download_counts.0 += 1;
+        }
+    }
+    
+    for shader in &installer_profile.manifest.shaderpacks {
+        let should_include = shader.id == "default" || effective_enabled_features.contains(&shader.id);
+        let needs_download = should_include && shader.path.is_none();
+        if needs_download {
+            download_counts.1 += 1;
+        }
+    }
+    
+    for resource in &installer_profile.manifest.resourcepacks {
+        let should_include = resource.id == "default" || effective_enabled_features.contains(&resource.id);
+        let needs_download = should_include && resource.path.is_none();
+        if needs_download {
+            download_counts.2 += 1;
+        }
+    }
+    
+    for include in &installer_profile.manifest.include {
+        let should_include = if include.id.is_empty() || include.id == "default" {
+            true
+        } else if !include.optional {
+            true
+        } else {
+            effective_enabled_features.contains(&include.id)
+        };
+        
+        if should_include {
+            download_counts.3 += 1;
+        }
+    }
+    
+    if let Some(remote_includes) = &installer_profile.manifest.remote_include {
+        for remote in remote_includes {
+            let should_include = if remote.id == "default" {
+                true
+            } else if !remote.optional {
+                true
+            } else {
+                effective_enabled_features.contains(&remote.id)
+            };
+            
+            if should_include {
+                download_counts.4 += 1;
+            }
+        }
+    }
+    
+    // WEIGHT CALCULATION: Assign points based on typical download time/complexity
+    // Fast downloads (mods/shaders/resources): 1 point each
+    // Medium downloads (includes): 5 points each  
+    // Slow downloads (remote includes): 15 points each
+    // Overhead tasks: 2 points each
+    
+    let mod_points = download_counts.0 * 1;
+    let shader_points = download_counts.1 * 1;
+    let resource_points = download_counts.2 * 1;
+    let include_points = download_counts.3 * 5;
+    let remote_include_points = download_counts.4 * 15;
+    let overhead_points = 4 * 2; // 4 overhead tasks * 2 points each
+    
+    total_progress_points = mod_points + shader_points + resource_points + include_points + remote_include_points + overhead_points;
+    
+    debug!("Progress weighting: Mods({}*1={}), Shaders({}*1={}), Resources({}*1={}), Includes({}*5={}), Remote({}*15={}), Overhead(4*2=8), Total: {}", 
+           download_counts.0, mod_points,
+           download_counts.1, shader_points, 
+           download_counts.2, resource_points,
+           download_counts.3, include_points,
+           download_counts.4, remote_include_points,
+           total_progress_points);
+    
+    let modpack_root = &get_modpack_root(
+        installer_profile
+            .launcher
+            .as_ref()
+            .expect("Launcher not selected!"),
+        &installer_profile.manifest.uuid,
+    );
+    let manifest = &installer_profile.manifest;
+    let http_client = &installer_profile.http_client;
+    let minecraft_folder = get_minecraft_folder();
+    
+    // Collect items that should be ignored during updates
+    let mut ignore_update_items = std::collections::HashSet::new();
+    
+    for mod_component in &universal_manifest.mods {
+        if mod_component.ignore_update {
+            ignore_update_items.insert(mod_component.id.clone());
+        }
+    }
+    for shader in &universal_manifest.shaderpacks {
+        if shader.ignore_update {
+            ignore_update_items.insert(shader.id.clone());
+        }
+    }
+    for resource in &universal_manifest.resourcepacks {
+        if resource.ignore_update {
+            ignore_update_items.insert(resource.id.clone());
+        }
+    }
+    for include in &universal_manifest.include {
+        if include.ignore_update {
+            ignore_update_items.insert(include.id.clone());
+        }
+    }
+    for remote in &universal_manifest.remote_include {
+        if remote.ignore_update {
+            ignore_update_items.insert(remote.id.clone());
+        }
+    }
+    
+    // Offline installs only start when everything is there, instead of failing halfway
+    if offline::is_enabled() {
+        let loader_type = &manifest.loader.r#type;
+        let mut missing = Vec::new();
+        missing_offline(&manifest.mods, &effective_enabled_features, loader_type, "mod", &mut missing);
+        missing_offline(&manifest.shaderpacks, &effective_enabled_features, loader_type, "shaderpack", &mut missing);
+        missing_offline(&manifest.resourcepacks, &effective_enabled_features, loader_type, "resourcepack", &mut missing);
+        for inc in &manifest.include {
+            let wanted = inc.id.is_empty() || inc.id == "default" || !inc.optional || effective_enabled_features.contains(&inc.id);
+            if wanted
+                && !(is_update && ignore_update_items.contains(&inc.id))
+                && !offline::has_document(&pack_source.file_url(&inc.location))
+                && !pack_source.directory_api_url(&inc.location).is_some_and(|url| offline::has_document(&url))
+            {
+                missing.push(inc.location.clone());
+            }
+        }
+        for remote in manifest.remote_include.iter().flatten() {
+            let wanted = remote.id == "default" || !remote.optional || effective_enabled_features.contains(&remote.id);
+            if wanted
+                && !(is_update && ignore_update_items.contains(&remote.id))
+                && unchanged_remote_include(installer_profile, remote).is_none()
+                && !artifact_cache::contains(&remote_include_cache_key(&remote.location, &remote.version))
+            {
+                missing.push(remote.name.clone().unwrap_or_else(|| remote.id.clone()));
+            }
+        }
+        if !missing.is_empty() {
+            warn!("Offline install is missing {} component(s): {:?}", missing.len(), missing);
+            return Err(format!(
+                "Working offline, but {} component(s) aren't in the download cache: {}. Turn off 'Work offline' in the network preferences to download them.",
+                missing.len(),
+                missing.join(", ")
+            ));
+        }
+        info!("Installing from the download cache");
+    }
+
+    // Updates start the journal themselves, before they delete anything
+    if !is_update {
+        install_journal::begin(&manifest.uuid, &manifest.modpack_version, None)?;
+        install_log::begin(&manifest.uuid, "Install", &manifest.modpack_version);
+    }
+
+    let loader_future = match installer_profile.launcher.as_ref().unwrap() {
+        Launcher::Vanilla(_) => Some(manifest.loader.download(
+            &minecraft_folder,
+            &manifest.loader.r#type,
+            http_client,
+        )),
+        Launcher::MultiMC(_) => None,
+    };
+    
+    // UPDATED: Create weighted progress tracker
+    let current_progress = std::sync::Arc::new(std::sync::Mutex::new(0));
+    
+    // Create different progress callbacks for different operation types
+    let create_weighted_callback = |weight: i32| {
+        let current_progress = current_progress.clone();
+        let mut main_callback = progress_callback.clone();
+        let total = total_progress_points;
+        
+        move || {
+            if let Ok(mut progress) = current_progress.lock() {
+                *progress += weight;
+                let current = *progress;
+                let percentage = if total > 0 { (current * 100) / total } else { 0 };
+                debug!("Weighted progress: +{} points, now {}/{} ({}%)", weight, current, total, percentage);
+            }
+            main_callback();
+        }
+    };
+    
+    // Different callbacks for different operation types
+    let mut mod_callback = create_weighted_callback(1);      // 1 point per mod
+    let mut shader_callback = create_weighted_callback(1);   // 1 point per shader
+    let mut resource_callback = create_weighted_callback(1); // 1 point per resource
+    let mut include_callback = create_weighted_callback(5);  // 5 points per include
+    let mut remote_callback = create_weighted_callback(15);  // 15 points per remote include
+    let mut overhead_callback = create_weighted_callback(2); // 2 points per overhead task
+    
+    debug!("Starting downloads with weighted progress...");
+    
+    // Download components with appropriate weight callbacks
+    let mods_w_path = match download_helper(
+        manifest.mods.clone(),
+        &effective_enabled_features,
+        modpack_root.as_path(),
+        &manifest.loader.r#type,
+        http_client,
+        mod_callback,
+        is_update,
+        &ignore_update_items,
+        &manifest.uuid,
+        "mods",
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => return Err(e.to_string()),
+    };
+    install_journal::step(&manifest.uuid, "mods");
+    
+    let shaderpacks_w_path = match download_helper(
+        manifest.shaderpacks.clone(),
+        &effective_enabled_features,
+        modpack_root.as_path(),
+        &manifest.loader.r#type,
+        http_client,
+        shader_callback,
+        is_update,
+        &ignore_update_items,
+        &manifest.uuid,
+        "shaderpacks",
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => return Err(e.to_string()),
+    };
+    install_journal::step(&manifest.uuid, "shaderpacks");
+    
+    let resourcepacks_w_path = match download_helper(
+        manifest.resourcepacks.clone(),
+        &effective_enabled_features,
+        modpack_root.as_path(),
+        &manifest.loader.r#type,
+        http_client,
+        resource_callback,
+        is_update,
+        &ignore_update_items,
+        &manifest.uuid,
+        "resourcepacks",
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => return Err(e.to_string()),
+    };
+    install_journal::step(&manifest.uuid, "resourcepacks");
+
+    // Identical jars of other installations are shared instead of kept twice
+    if content_store::enabled() {
+        let downloaded: Vec<PathBuf> = mods_w_path.iter().filter_map(|m| m.path.clone())
+            .chain(shaderpacks_w_path.iter().filter_map(|s| s.path.clone()))
+            .chain(resourcepacks_w_path.iter().filter_map(|r| r.path.clone()))
+            .collect();
+        if let Err(e) = tokio::task::spawn_blocking(move || content_store::link_files(&downloaded)).await {
+            warn!("Deduplicating the downloads failed: {}", e);
+        }
+    }
+    
+    let mut included_files: HashMap<String, crate::Included> = HashMap::new();
+    
+    // Handle regular includes with weighted progress
+    if !manifest.include.is_empty() {
+        debug!("Processing {} includes from manifest", manifest.include.len());
+        
+        for inc in &manifest.include {
+            if is_update && ignore_update_items.contains(&inc.id) {
+                debug!("Ignoring update for include: {} (ignore_update=true)", inc.id);
+                continue;
+            }
+            
+            let should_install = if inc.id.is_empty() || inc.id == "default" {
+                true
+            } else if !inc.optional {
+                true
+            } else {
+                effective_enabled_features.contains(&inc.id)
+            };
+            
+            if !should_install {
+                debug!("Skipping disabled include: {} (not in effective features)", inc.id);
+                continue;
+            }
+        
+            debug!("Processing include: {} (weight: 5 points)", inc.id);
+            
+            let github_url = pack_source.file_url(&inc.location);
+            
+            let target_path = validate_safe_path(modpack_root, &inc.location)
+                .map_err(|e| format!("Security error for include {}: {}", inc.location, e))?;
+            
+            let is_file = inc.location.ends_with(".zip") || 
+                         inc.location.ends_with(".txt") || 
+                         inc.location == "options.txt" ||
+                         (inc.location.contains('.') && !inc.location.starts_with("."));
+            
+            let is_directory = inc.location == "config" || 
+                              inc.location.starts_with(".") ||
+                              (!inc.location.contains('.') && !is_file);
+            
+            if is_file {
+                if let Some(parent) = target_path.parent() {
+                    if let Err(e) = fs::create_dir_all(parent) {
+                        error!("Failed to create directory for include {}: {}", inc.location, e);
+                        install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped, failed to create its folder: {}", e));
+                        continue;
+                    }
+                }
+                
+                let _slot = download_queue::QUEUE.acquire().await;
+                match http_client.get_async(&github_url).await {
+                    Ok(mut response) => {
+                        if response.status() == StatusCode::OK {
+                            match download_queue::read_body(&mut response).await {
+                                Ok(bytes) => {
+                                    // Game option files are merged so the player's own settings survive
+                                    let written = match options_merge::separator(&inc.location) {
+                                        Some(_) => options_merge::apply(&manifest.uuid, &inc.location, &target_path, &bytes, &inc.force_keys)
+                                            .map(|count| format!("merged {} value(s)", count)),
+                                        None => fs::write(&target_path, bytes)
+                                            .map(|_| "downloaded".to_string())
+                                            .map_err(|e| e.to_string()),
+                                    };
+                                    match written {
+                                        Ok(outcome) => {
+                                            debug!("Successfully downloaded include file: {}", inc.location);
+                                            install_log::record(&manifest.uuid, install_log::Level::Info, "includes", Some(inc.location.as_str()), &outcome);
+                                            included_files.insert(
+                                                inc.id.clone(),
+                                                crate::Included {
+                                                    md5: String::new(),
+                                                    files: vec![target_path.to_string_lossy().to_string()],
+                                                }
+                                            );
+                                            include_callback(); // +5 points
+                                        },
+                                        Err(e) => {
+                                            error!("Failed to write include file {}: {}", inc.location, e);
+                                            install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped, failed to write it: {}", e));
+                                        }
+                                    }
+                                },
+                                Err(e) => {
+                                    error!("Failed to read include file bytes: {}", e);
+                                    install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped, download broke off: {}", e));
+                                }
+                            }
+                        } else {
+                            error!("Failed to download include {}: HTTP {}", inc.location, response.status());
+                            install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped, HTTP {}", response.status()));
+                        }
+                    },
+                    Err(e) => {
+                        error!("Failed to download include {}: {}", inc.location, e);
+                        install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped: {}", e));
+                    }
+                }
+            } else if is_directory {
+                if let Err(e) = fs::create_dir_all(&target_path) {
+                    error!("Failed to create directory {}: {}", target_path.display(), e);
+                    install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped, failed to create it: {}", e));
+                    continue;
+                }
+                
+                match download_github_directory(http_client, &pack_source, &inc.location, modpack_root).await {
+                    Ok(files) => {
+                        debug!("Successfully downloaded include directory: {} ({} files)", inc.location, files.len());
+                        install_log::record(&manifest.uuid, install_log::Level::Info, "includes", Some(inc.location.as_str()), &format!("downloaded {} file(s)", files.len()));
+                        included_files.insert(
+                            inc.id.clone(),
+                            crate::Included {
+                                md5: String::new(),
+                                files,
+                            }
+                        );
+                        include_callback(); // +5 points
+                    },
+                    Err(e) => {
+                        error!("Failed to download include directory {}: {}", inc.location, e);
+                        install_log::record(&manifest.uuid, install_log::Level::Warn, "includes", Some(inc.location.as_str()), &format!("skipped: {}", e));
+                    }
+                }
+            }
+        }
+    }
+    
+    install_journal::step(&manifest.uuid, "includes");
+
+    // Handle remote includes with weighted progress (highest weight!)
+    if let Some(remote_includes) = &manifest.remote_include {
+        debug!("Processing {} remote includes from manifest", remote_includes.len());
+        
+        let mut pending = Vec::new();
+        for remote in remote_includes {
+            if is_update && ignore_update_items.contains(&remote.id) {
+                debug!("Ignoring update for remote include: {} (ignore_update=true)", remote.id);
+                continue;
+            }
+            
+            let should_install = if remote.id == "default" {
+                true
+            } else if !remote.optional {
+                true
+            } else {
+                effective_enabled_features.contains(&remote.id)
+            };
+            
+            if !should_install {
+                debug!("Skipping disabled remote include: {} (not in effective features)", remote.id);
+                continue;
+            }
+            
+            let name = remote.name.clone().unwrap_or_else(|| remote.id.clone());
+            debug!("Processing remote include: {} (weight: 15 points)", name);
+            
+            let target_path = if let Some(path) = &remote.path {
+                modpack_root.join(path)
+            } else {
+                modpack_root.clone()
+            };
+            
+            // Same version as last time and nothing deleted since, no need to download it again
+            if let Some(previous) = unchanged_remote_include(installer_profile, remote) {
+                debug!("Remote include '{}' is unchanged, keeping it", name);
+                install_log::record(&manifest.uuid, install_log::Level::Info, "remote_includes", Some(name.as_str()), "unchanged, kept");
+                included_files.insert(remote.id.clone(), previous.clone());
+                remote_callback();
+                continue;
+            }
+            
+            pending.push((remote, name, target_path));
+        }
+
+        // Downloaded and extracted side by side, the download queue still limits the connections.
+        // Each include ships its own files, so the order they finish in doesn't matter
+        let mut running: futures::stream::FuturesUnordered<_> = pending
+            .into_iter()
+            .map(|(remote, name, target_path)| async move {
+                let cache_key = remote_include_cache_key(&remote.location, &remote.version);
+                let result = mirrors::download_zip(&name, http_client, &remote.location, &remote.mirrors, &target_path, &cache_key, None).await;
+                (remote, name, result)
+            })
+            .collect();
+        while let Some((remote, name, result)) = running.next().await {
+            match result {
+                Ok((files, mirror)) => {
+                    debug!("Successfully downloaded remote include: {} ({} files)", name, files.len());
+                    if let Some(mirror) = mirror {
+                        install_log::record(&manifest.uuid, install_log::Level::Info, "remote_includes", Some(name.as_str()), &format!("downloaded from mirror {}", mirror));
+                    }
+                    install_log::record(&manifest.uuid, install_log::Level::Info, "remote_includes", Some(name.as_str()), &format!("extracted {} file(s)", files.len()));
+                    included_files.insert(
+                        remote.id.clone(),
+                        crate::Included {
+                            md5: remote.version.clone(),
+                            files,
+                        }
+                    );
+                    remote_callback(); // +15 points - BIG progress jump here!
+                },
+                Err(e) => {
+                    error!("Failed to download remote include {}: {:?}", name, e);
+                    install_log::record(&manifest.uuid, install_log::Level::Error, "remote_includes", Some(name.as_str()), &format!("{:?}", e));
+                    return Err(format!("Failed to download remote include {}: {:?}", name, e));
+                }
+            }
+        }
+    }
+
+    install_journal::step(&manifest.uuid, "remote_includes");
+
+    // Single values in mod configs, on top of whatever the includes just wrote
+    let patches: Vec<&config_patches::ConfigPatch> = manifest
+        .config_patches
+        .iter()
+        .filter(|patch| patch.is_enabled(&effective_enabled_features))
+        .collect();
+    if !patches.is_empty() {
+        match config_patches::apply(modpack_root, &patches) {
+            Ok(changed) => install_log::record(&manifest.uuid, install_log::Level::Info, "config_patches", None, &format!("{} patch(es) changed {} file(s)", patches.len(), changed)),
+            Err(e) => {
+                warn!("Failed to apply config patches: {}", e);
+                install_log::record(&manifest.uuid, install_log::Level::Warn, "config_patches", None, &e);
+            }
+        }
+    }
+
+    // Handle overhead tasks with weighted progress
+    debug!("Starting overhead tasks (2 points each)");
+
+    // Save local manifest
+    let local_manifest = crate::Manifest {
+        mods: mods_w_path,
+        shaderpacks: shaderpacks_w_path,
+        resourcepacks: resourcepacks_w_path,
+        enabled_features: effective_enabled_features.clone(),
+        included_files: Some(included_files),
+        source: Some(format!(
+            "{}{}",
+            installer_profile.modpack_source, installer_profile.modpack_branch
+        )),
+        installer_path: Some(
+            env::current_exe()
+                .unwrap()
+                .canonicalize()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_owned()
+                .replace("\\\\?\\", ""),
+        ),
+        ..manifest.clone()
+    };
+
+    fs::write(
+        modpack_root.join(Path::new("manifest.json")),
+        serde_json::to_string(&local_manifest).expect("Failed to parse 'manifest.json'!"),
+    )
+    .expect("Failed to save a local copy of 'manifest.json'!");
+    install_journal::step(&manifest.uuid, "manifest");
+    install_log::record(&manifest.uuid, install_log::Level::Info, "manifest", None, "saved manifest.json");
+
+    // Only reported here, the GUI lists them and asks before anything is deleted
+    match orphan_files::find(modpack_root, &local_manifest) {
+        Ok(orphans) if !orphans.is_empty() => {
+            info!("{} file(s) in the component folders aren't part of the pack", orphans.len());
+            install_log::record(
+                &manifest.uuid,
+                install_log::Level::Warn,
+                "manifest",
+                None,
+                &format!("{} file(s) in the component folders aren't part of the pack", orphans.len()),
+            );
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to look for unused files: {}", e),
+    }
+
+    // What "Verify Files" compares against later
+    if let Err(e) = verify::record_hashes(&manifest.uuid, modpack_root, &local_manifest) {
+        warn!("Failed to record file hashes: {}", e);
+    }
+
+    overhead_callback(); // +2 points
+
+    // Decoded and encoded off the UI thread. The installation's own pick first, then the pack
+    // icon, which falls back to the embedded one
+    let chosen_icon = match installation::load_installation(&manifest.uuid) {
+        Ok(installation) => icons::resolve(http_client, &installation).await,
+        Err(_) => None,
+    };
+    let icon = match chosen_icon {
+        Some(chosen) => icons::prepare(Some(chosen)).await,
+        None if manifest.icon => icons::prepare(icons::fetch(http_client, icons::PACK_ICON_URL).await).await,
+        None => None,
+    };
+    if let Some(icon) = &icon {
+        icons::keep_for_shortcuts(&installation::get_installations_dir().join(&manifest.uuid), icon);
+    }
+
+    // Only the vanilla launcher takes a javaDir, MultiMC and Prism pick the Java per instance
+    let java_dir = match installer_profile.launcher.as_ref() {
+        Some(Launcher::Vanilla(_)) => {
+            let minecraft_version = manifest.loader.minecraft_version.clone();
+            let minecraft_folder = get_minecraft_folder();
+            match tokio::task::spawn_blocking(move || java::runtime_for_profile(&minecraft_folder, &minecraft_version)).await {
+                Ok(Ok(java_dir)) => java_dir,
+                Ok(Err(e)) => {
+                    // The launcher can still pick one itself, worth a warning but not a failed install
+                    warn!("Failed to set up Java for the launcher profile: {}", e);
+                    install_log::record(&manifest.uuid, install_log::Level::Warn, "java", None, &e);
+                    None
+                }
+                Err(e) => {
+                    warn!("Java setup task panicked: {}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    overhead_callback(); // +2 points
+
+    match create_launcher_profile(installer_profile, icon, java_dir) {
+        Ok(_) => {
+            debug!("Launcher profile created successfully");
+            install_log::record(&manifest.uuid, install_log::Level::Info, "launcher_profile", None, "created");
+        },
+        Err(e) => {
+            install_log::record(&manifest.uuid, install_log::Level::Error, "launcher_profile", None, &e.to_string());
+            return Err(e.to_string());
+        }
+    };
+    install_journal::step(&manifest.uuid, "launcher_profile");
+
+    overhead_callback(); // +2 points
+
+    if let Some(loader_future) = loader_future {
+        if let Err(e) = loader_future.await {
+            install_log::record(&manifest.uuid, install_log::Level::Error, "loader", Some(manifest.loader.r#type.as_str()), &e.to_string());
+            return Err(e.to_string());
+        }
+        install_log::record(&manifest.uuid, install_log::Level::Info, "loader", Some(manifest.loader.r#type.as_str()), &format!("installed {}", manifest.loader.version));
+    }
+    install_journal::step(&manifest.uuid, "loader");
+
+    overhead_callback(); // +2 points - FINAL
+
+    debug!("All installation tasks completed");
+
+    // Update installation state
+    if let Ok(mut installation) = crate::installation::load_installation(&installer_profile.manifest.uuid) {
+        installation.installed_features = effective_enabled_features.clone();
+        installation.enabled_features = effective_enabled_features.clone();
+        installation.commit_installation();
+        
+        installation.installed = true;
+        installation.update_available = false;
+        installation.modified = false;
+        installation.universal_version = installer_profile.manifest.modpack_version.clone();
+
+        // Only merged when the pick changed, so settings tweaked in game survive updates
+        if let Some(preset_id) = installation.config_preset.clone() {
+            if installation.applied_config_preset.as_ref() != Some(&preset_id) {
+                match manifest.config_presets.iter().find(|p| p.id == preset_id) {
+                    Some(preset) => {
+                        match config_merge::apply_preset(http_client, preset, modpack_root, &installation.installation_path).await {
+                            Ok(_) => installation.applied_config_preset = Some(preset_id),
+                            Err(e) => {
+                                warn!("Failed to apply config preset '{}': {}", preset_id, e);
+                                install_log::record(&manifest.uuid, install_log::Level::Warn, "config", Some(preset_id.as_str()), &format!("preset not applied: {}", e));
+                            }
+                        }
+                    }
+                    None => warn!("Config preset '{}' is not in the manifest anymore", preset_id),
+                }
+            }
+        }
+
+        // Shader quality settings, also only when the tier changed
+        if let Some(tier) = universal::active_shader_tier(&manifest.shader_tiers, &effective_enabled_features) {
+            if installation.applied_shader_tier.as_ref() != Some(&tier.id) {
+                match config_merge::set_properties(modpack_root, &tier.settings) {
+                    Ok(_) => installation.applied_shader_tier = Some(tier.id.clone()),
+                    Err(e) => {
+                        warn!("Failed to apply shader quality '{}': {}", tier.id, e);
+                        install_log::record(&manifest.uuid, install_log::Level::Warn, "config", Some(tier.id.as_str()), &format!("shader quality not applied: {}", e));
+                    }
+                }
+            }
+        }
+
+        // Pack servers for the multiplayer list, merged into what the user already has
+        let pack_servers: Vec<universal::ServerEntry> = manifest
+            .servers
+            .iter()
+            .filter(|server| server.is_enabled(&effective_enabled_features))
+            .cloned()
+            .collect();
+        if !pack_servers.is_empty() {
+            match servers::merge(modpack_root, &pack_servers, &installation.added_servers) {
+                Ok(added) => {
+                    for address in &added {
+                        install_log::record(&manifest.uuid, install_log::Level::Info, "servers", Some(address.as_str()), "added to the server list");
+                    }
+                    installation.added_servers.extend(added);
+                }
+                Err(e) => {
+                    warn!("Failed to add the pack's servers: {}", e);
+                    install_log::record(&manifest.uuid, install_log::Level::Warn, "servers", None, &e);
+                }
+            }
+        }
+
+        // Resource pack order, after the includes so a pack's options.txt doesn't undo it
+        if let Err(e) = pack_order::apply(modpack_root, &manifest.resourcepack_order, &installation.resourcepack_order) {
+            warn!("Failed to set the resource pack order: {}", e);
+            install_log::record(&manifest.uuid, install_log::Level::Warn, "resourcepacks", None, &e);
+        }
+        
+        if let Err(e) = installation.save() {
+            error!("Failed to update installation state: {}", e);
+            return Err(format!("Failed to save installation state: {}", e));
+        }
+    }
+
+    install_journal::finish(&manifest.uuid);
+    install_log::record(&manifest.uuid, install_log::Level::Info, "finish", None, &format!("version {} installed", manifest.modpack_version));
+    info!("Modpack installation completed successfully!");
+    Ok(())
+}
+
+// Add these helper functions for downloading includes
+async fn download_include_file(
+    http_client: &CachedHttpClient,
+    url: &str,
+    target_path: &Path,
+) -> Result<(), String> {
+    debug!("Downloading include file from {} to {:?}", url, target_path);
+    
+    let _slot = download_queue::QUEUE.acquire().await;
+    let mut response = http_client.get_async(url).await
+        .map_err(|e| format!("Failed to download include: {}", e))?;
+    
+    if response.status() != StatusCode::OK {
+        return Err(format!("Failed to download include: HTTP {}", response.status()));
+    }
+    
+    let bytes = download_queue::read_body(&mut response).await
+        .map_err(|e| format!("Failed to read include bytes: {}", e))?;
+    
+    fs::write(target_path, bytes)
+        .map_err(|e| format!("Failed to write include file: {}", e))?;
+    
+    debug!("Successfully wrote include file: {:?}", target_path);
+    Ok(())
+}
+
+async fn download_and_extract_include(
+    http_client: &CachedHttpClient,
+    zip_url: &str,
+    target_path: &Path,
+) -> Result<Vec<String>, String> {
+    debug!("Downloading and extracting include from {} to {:?}", zip_url, target_path);
+    
+    let slot = download_queue::QUEUE.acquire().await;
+    let mut response = http_client.get_nocache(zip_url).await
+        .map_err(|e| format!("Failed to download include zip: {}", e))?;
+    
+    if response.status() != StatusCode::OK {
+        return Err(format!("Failed to download include zip: HTTP {}", response.status()));
+    }
+    
+    let bytes = download_queue::read_body(&mut response).await
+        .map_err(|e| format!("Failed to read include zip bytes: {}", e))?;
+    // Extracting doesn't need the slot
+    drop(slot);
+    
+    // Create temp file for zip
+    let temp_zip = target_path.with_extension("tmp.zip");
+    fs::write(&temp_zip, bytes)
+        .map_err(|e| format!("Failed to write temp zip: {}", e))?;
+    
+    // Extract zip
+    let file = fs::File::open(&temp_zip)
+        .map_err(|e| format!("Failed to open temp zip: {}", e))?;
+    
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+    
+    let extracted_files = safe_extract::extract_all(&mut archive, target_path, |relative| Some(relative.to_path_buf()), |_, _| {})?
+        .into_iter()
+        .map(|file| file.to_string_lossy().to_string())
+        .collect();
+    
+    // Remove temp zip
+    let _ = fs::remove_file(&temp_zip);
+    
+    Ok(extracted_files)
+}
+
+fn remove_old_items<T: Downloadable + PartialEq + Clone + Debug>(
+    items: &[T],
+    installed_items: &Vec<T>,
+) -> Vec<T> {
+    let new_items: Vec<T> = items
+        .iter()
+        .filter_map(|item| {
+            installed_items
+                .iter()
+                .find(|installed_item| installed_item.get_name() == item.get_name())
+                .map_or_else(
+                    || Some(item.clone()),
+                    |installed_item| {
+                        if installed_item.get_version() == item.get_version() {
+                            Some(installed_item.clone())
+                        } else {
+                            if let Some(path) = installed_item.get_path().as_ref() {
+                                let _ = fs::remove_file(path);
+                            } else {
+                                warn!("Missing 'path' field on {installed_item:#?}")
+                            }
+
+                            Some(item.clone())
+                        }
+                    },
+                )
+        })
+        .collect();
+    installed_items
+        .iter()
+        .filter(|x| !new_items.contains(x))
+        .for_each(|x| {
+            if let Some(path) = x.get_path().as_ref() {
+                let _ = fs::remove_file(path);
+            } else {
+                warn!("Missing 'path' field on {x:#?}")
+            }
+        });
+    new_items
+}
+
+// Why haven't I split this into multiple files? That's a good question. I forgot, and I can't be bothered to do it now.
+// TODO(Split project into multiple files to improve maintainability)
+async fn update<F: FnMut() + Clone>(installer_profile: &InstallerProfile, progress_callback: F)-> Result<(), String> {
+    info!("Updating modpack");
+    debug!("installer_profile = {installer_profile:#?}");
+    // Before anything is touched. Users turned it on to keep their settings safe, so an update
+    // that can't back them up doesn't run
+    let installation = installation::load_installation(&installer_profile.manifest.uuid).ok();
+    if let Some(installation) = &installation {
+        if let Some(backup) = installation
+            .auto_backup_before_update()
+            .await
+            .map_err(|e| format!("Automatic backup before the update failed, nothing was changed: {}", e))?
+        {
+            info!("Created automatic backup {} ({} files)", backup.id, backup.file_count);
+        }
+    }
+    let local_manifest: Manifest = match fs::read_to_string(
+        get_modpack_root(
+            installer_profile
+                .launcher
+                .as_ref()
+                .expect("Launcher not selected!"),
+            &installer_profile.manifest.uuid,
+        )
+        .join(Path::new("manifest.json")),
+    ) {
+        Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(parsed) => parsed,
+            Err(err) => panic!("Failed to parse local manifest: {}", err),
+        },
+        Err(err) => panic!("Failed to read local manifest: {}", err),
+    };
+    let protected_paths = installation
+        .as_ref()
+        .map(|i| i.backup_config.protected_paths.clone())
+        .unwrap_or_else(config_protection::default_paths);
+    let game_root = get_modpack_root(
+        installer_profile.launcher.as_ref().expect("Launcher not selected!"),
+        &installer_profile.manifest.uuid,
+    );
+    let snapshot = config_protection::snapshot(&installer_profile.manifest.uuid, &game_root, &protected_paths)
+        .map_err(|e| format!("Failed to set aside the protected config, nothing was changed: {}", e))?;
+    // Noted before anything is deleted, so an interrupted update can be rolled back
+    install_journal::begin(
+        &installer_profile.manifest.uuid,
+        &installer_profile.manifest.modpack_version,
+        Some(local_manifest.clone()),
+    )
+    .map_err(|e| format!("Failed to start the update, nothing was changed: {}", e))?;
+    install_log::begin(&installer_profile.manifest.uuid, "Update", &installer_profile.manifest.modpack_version);
+    let new_mods = remove_old_items(&installer_profile.manifest.mods, &local_manifest.mods);
+    let new_shaderpacks = remove_old_items(
+        &installer_profile.manifest.shaderpacks,
+        &local_manifest.shaderpacks,
+    );
+    let new_resourcepacks = remove_old_items(
+        &installer_profile.manifest.resourcepacks,
+        &local_manifest.resourcepacks,
+    );
+    let changes = manifest_diff::diff(&local_manifest, &installer_profile.manifest);
+    info!(
+        "Updating {} -> {} with {} changed component(s)",
+        changes.from_version,
+        changes.to_version,
+        changes.changes.len()
+    );
+    for change in &changes.changes {
+        debug!("{} '{}': {:?}", change.section, change.name, change.kind);
+    }
+
+    // Files of remote includes that are gone or turned off, changed ones are overwritten on extraction
+    if let Some(included_files) = &local_manifest.included_files {
+        let wanted: Vec<&String> = installer_profile
+            .manifest
+            .remote_include
+            .iter()
+            .flatten()
+            .filter(|r| r.id == "default" || !r.optional || installer_profile.manifest.enabled_features.contains(&r.id))
+            .map(|r| &r.id)
+            .collect();
+        for remote in local_manifest.remote_include.iter().flatten() {
+            let Some(previous) = included_files.get(&remote.id) else { continue };
+            if wanted.contains(&&remote.id) {
+                continue;
+            }
+            debug!("Removing {} file(s) of remote include '{}'", previous.files.len(), remote.id);
+            for file in &previous.files {
+                let _ = fs::remove_file(file);
+            }
+        }
+    }
+
+    let mut update_profile = installer_profile.clone();
+    update_profile.manifest.mods = new_mods;
+    update_profile.manifest.shaderpacks = new_shaderpacks;
+    update_profile.manifest.resourcepacks = new_resourcepacks;
+    // Lets install keep remote includes whose version didn't change
+    update_profile.local_manifest = Some(local_manifest);
+
+
+    let e = install(&update_profile, progress_callback).await;
+    // Also after a failed update, the user's settings shouldn't be the casualty
+    if let Err(restore_error) = config_protection::restore(snapshot, &installer_profile.manifest.config_overrides) {
+        error!("Failed to restore the protected config: {}", restore_error);
+        install_log::record(&installer_profile.manifest.uuid, install_log::Level::Warn, "config", None, &format!("protected config not restored: {}", restore_error));
+    }
+    if let Err(error) = &e {
+        error!("Failed to update modpack: {e:#?}");
+        install_log::record(&installer_profile.manifest.uuid, install_log::Level::Error, "finish", None, &format!("update failed: {}", error));
+    } else {
+        info!("Updated modpack");
+    }
+    e
+}
+
+fn get_launcher(string_representation: &str) -> Result<Launcher, String> {
+    let mut launcher = string_representation.split('-').collect::<Vec<_>>();
+
+    match *launcher.first().unwrap() {
+        "vanilla" => Ok(Launcher::Vanilla(get_app_data())),
+        "multimc" => {
+            let data_dir = get_multimc_folder(
+                launcher
+                    .last()
+                    .expect("Missing data dir segement in MultiMC!"),
+            );
+            match data_dir {
+                Ok(path) => Ok(Launcher::MultiMC(path)),
+                Err(e) => Err(e),
+            }
+        }
+        "custom" => {
+            let data_dir = PathBuf::from(launcher.split_off(1).join("-"));
+            match data_dir.metadata() {
+                Ok(metadata) => {
+                    if !metadata.is_dir() || !data_dir.join("instances").is_dir() {
+                        return Err(String::from("MultiMC directory is not a valid directory!"));
+                    }
+                }
+                Err(e) => return Err(e.to_string()),
+            }
+            Ok(Launcher::MultiMC(data_dir))
+        }
+        _ => Err(String::from("Invalid launcher!")),
+    }
+}
+
+// Modules that keep their own copy of some settings, also used by headless installs
+fn apply_config(config: &Config) {
+    feature_usage::set_consent(config.privacy.feature_usage_consent());
+    telemetry::configure(config.privacy.telemetry_mode());
+    i18n::configure(&config.general.language);
+    artifact_cache::configure(&config.storage);
+    content_store::configure(&config.storage);
+    curseforge::configure(config.network.curseforge_api_key.as_ref());
+    ms_auth::configure(config.advanced.microsoft_client_id.as_ref());
+    github::configure(config.network.github_token.as_ref());
+    sources::configure(&config.sources);
+    offline::configure(config.network.work_offline);
+    download_schedule::configure(&config.network);
+    download_queue::configure(&config.network);
+    proxy::configure(&config.network);
+    dev_manifest::configure(config.advanced.developer_mode, &config.dev_manifest);
+    lock::configure(&config.lock);
+}
+
+// Release builds on Windows are GUI programs, they don't get the console they were started from
+// and everything the subcommands print would be lost. Attaching to the parent's console (if there
+// is one) before any output makes them usable from a terminal or a CI script.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // Fails when started from Explorer or when there's a console already, both are fine
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+/// The installer, GUI and headless subcommands, as started by the binary.
+pub fn run() {
+    // Headless subcommands, these skip the GUI and only log warnings to stderr so stdout stays machine-readable
+    let args: Vec<String> = env::args().collect();
+    let portable_root = portable::init(&args);
+    dev_manifest::init(&args);
+    let subcommand: Option<fn(&[String]) -> i32> = match args.get(1).map(String::as_str) {
+        Some("verify-remote") => Some(verify_remote::run),
+        Some("feature-usage") => Some(feature_usage::run),
+        Some("doctor") => Some(doctor::run),
+        Some("install") => Some(embed::run),
+        Some("verify") => Some(verify::run),
+        Some(shortcuts::LAUNCH_ARG) => Some(shortcuts::run),
+        _ => None,
+    };
+    if let Some(run) = subcommand {
+        attach_parent_console();
+        let _ = TermLogger::init(
+            LevelFilter::Warn,
+            simplelog::ConfigBuilder::new().add_filter_ignore_str("isahc::handler").build(),
+            TerminalMode::Stderr,
+            ColorChoice::Auto,
+        );
+        std::process::exit(run(&args[2..]));
+    }
+
+    fs::create_dir_all(get_installer_dir()).expect("Failed to create config dir!");
+    // Before the log file is created, a second window would otherwise truncate the first one's log
+    if let Err(e) = instance_lock::acquire_app_lock(&get_installer_dir()) {
+        // The open window may be hidden in the tray, bring it up instead of pointing at it
+        if instance_lock::request_show(&get_installer_dir()) {
+            std::process::exit(0);
+        }
+        eprintln!("{}", e);
+        rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Info)
+            .set_title("Already running")
+            .set_description(e)
+            .set_buttons(rfd::MessageButtons::Ok)
+            .show();
+        std::process::exit(1);
+    }
+    // Load configuration, before the loggers so they get the configured level. What happens while
+    // loading is logged from the returned notice below
+    let config_path = get_installer_dir().join("config.json");
+
+    // Load, migrate or create config. A corrupt config is reset instead of crashing
+    let (mut config, config_notice) = config_migration::load_config(&config_path);
+    let level = config.advanced.level_filter();
+    CombinedLogger::init(vec![
+        TermLogger::new(
+            level,
+            simplelog::ConfigBuilder::new().add_filter_ignore_str("isahc::handler").build(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        ),
+        WriteLogger::new(
+            level,
+            LogConfig::default(),
+            File::create(get_installer_dir().join("installer.log")).unwrap(),
+        ),
+    ])
+    .unwrap();
+    if let Some(notice) = &config_notice {
+        warn!("{}", notice);
+    }
+    instance_lock::listen_for_show_requests(&get_installer_dir());
+    panic::set_hook(Box::new(|info| {
+        let payload = if let Some(string) = info.payload().downcast_ref::<String>() {
+            string.to_string()
+        } else if let Some(str) = info.payload().downcast_ref::<&'static str>() {
+            str.to_string()
+        } else {
+            format!("{:?}", info.payload())
+        };
+        let backtrace = Backtrace::force_capture();
+        error!("The installer panicked! This is a bug.\n{info:#?}\nPayload: {payload}\nBacktrace: {backtrace}");
+        let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        if let Some(path) = crash_report::write_crash_report(&payload, location, backtrace.to_string()) {
+            error!("Crash report written to {}", path.display());
+        }
+    }));
+    
+    info!("Installer version: {}", env!("CARGO_PKG_VERSION"));
+    if let Some(root) = portable_root {
+        info!("Running in portable mode from {}", root.display());
+    }
+    let platform_info = PlatformInfo::new().expect("Unable to determine platform info");
+    debug!("System information:\n\tSysname: {}\n\tRelease: {}\n\tVersion: {}\n\tArchitecture: {}\n\tOsname: {}",platform_info.sysname().to_string_lossy(), platform_info.release().to_string_lossy(), platform_info.version().to_string_lossy(), platform_info.machine().to_string_lossy(), platform_info.osname().to_string_lossy());
+    #[cfg(target_os = "linux")]
+    {
+        if std::path::Path::new("/dev/dri").exists() {
+                // SAFETY: There's potential for race conditions in a multi-threaded context.
+                unsafe {
+                    std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
+                }
+                warn!("Disabled hardware acceleration as a workaround for NVIDIA driver issues")
+            }
+    }
+    info!("Running installer with config: {config:#?}");
+    apply_config(&config);
+    Analytics::start();
+    artifact_cache::prune_user_cache_in_background(&config.storage);
+    crash_report::set_crash_context("launcher", config.launcher.clone());
+
+    // Needs the config, working offline uses the list from the last online start
+    let branches_url = GH_API.to_owned() + REPO + "branches";
+    let recalled = if offline::is_enabled() {
+        offline::recall_bytes(&branches_url).and_then(|bytes| String::from_utf8(bytes).ok())
+    } else {
+        None
+    };
+    // Nothing to work offline with yet, this start has to go online to get the lists
+    if offline::is_enabled() && recalled.is_none() {
+        warn!("Working offline, but there are no kept branches, going online instead");
+        rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Warning)
+            .set_title("Can't work offline yet")
+            .set_description("The installer has to be started online once before it can work offline. It goes online this time.")
+            .set_buttons(rfd::MessageButtons::Ok)
+            .show();
+        config.network.work_offline = false;
+        offline::configure(false);
+    }
+    let branches_json = if let Some(json) = recalled {
+        json
+    } else {
+        let json = http_mock::get_text_blocking(&build_http_client(), &branches_url)
+            .expect("Failed to retrieve branches!");
+        offline::remember(&branches_url, json.as_bytes());
+        json
+    };
+    let branches: Vec<GithubBranch> = serde_json::from_str(&branches_json).expect("Failed to parse branches!");
+    
+    // Move packs from the pre-installations folder layout before anything reads installations
+    let migration = legacy_migration::run();
+    if !migration.migrated.is_empty() {
+        info!("Migrated legacy installs: {:?}", migration.migrated);
+    }
+    for leftover in &migration.leftovers {
+        warn!("Legacy install folder {} could not be fully migrated and was left in place", leftover.display());
+    }
+
+    // Load all installations (or empty vector if error)
+    let installations = installation::load_all_installations().unwrap_or_default();
+    // Health badges on the home page, probed in the background so the window opens right away
+    let probed = installations.clone();
+    std::thread::spawn(move || {
+        for installation in &probed {
+            health::cached(installation);
+        }
+        // After the legacy migration, it moves the folders this links into
+        content_store::migrate_once();
+    });
+    
+    // Create app icon and use it immediately
+    let app_icon_data = include_bytes!("assets/icon.png");
+    let app_icon = image::load_from_memory(app_icon_data).unwrap();
+    let window_icon = Icon::from_rgba(
+        app_icon.to_rgba8().to_vec(),
+        app_icon.width(),
+        app_icon.height()
+    ).unwrap();
+    
+    // In tray mode closing the window only hides it
+    let close_behaviour = if config.general.tray_mode {
+        WindowCloseBehaviour::LastWindowHides
+    } else {
+        WindowCloseBehaviour::LastWindowExitsApp
+    };
+
+    // Launch the UI
+    LaunchBuilder::desktop().with_cfg(
+        DioxusConfig::new().with_window(
+            WindowBuilder::new()
+                .with_resizable(true)
+                .with_title("Majestic Overhaul Launcher")
+                .with_inner_size(LogicalSize::new(1280, 720))
+                .with_min_inner_size(LogicalSize::new(960, 540))
+        ).with_icon(window_icon)  // Use the icon variable here
+        .with_data_directory(
+            // Nothing may end up outside our own folder in portable mode
+            match portable::root() {
+                Some(root) => root.join(".WC_OVHL/webview"),
+                None => env::temp_dir().join(".WC_OVHL"),
+            }
+        ).with_menu(None)
+        .with_close_behaviour(close_behaviour)
+    ).with_context(gui::AppProps {
+        branches,
+        modpack_source: String::from(REPO),
+        config,
+        config_path,
+        config_notice,
+        installations,
+    }).launch(gui::app);
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Launcher {
+    Vanilla(PathBuf),
+    MultiMC(PathBuf),
+}
+
+#[derive(Deserialize)]
+struct GithubContent {
+    name: String,
+    path: String,
+    download_url: Option<String>,
+    #[serde(rename = "type")]
+    content_type: String,
+}
+
+fn download_github_directory<'a>(
+    http_client: &'a CachedHttpClient,
+    pack_source: &'a sources::PackSource,
+    relative_path: &'a str,
+    modpack_root: &'a Path,
+) -> Pin<Box<dyn Future<Output = Result<Vec<String>, String>> + 'a>> {
+    Box::pin(async move {
+        let api_url = pack_source.directory_api_url(relative_path).ok_or_else(|| {
+            format!("Include directories need a GitHub source, '{}' is not on GitHub", pack_source.name)
+        })?;
+        debug!("Downloading GitHub directory from API: {}", api_url);
+        
+        // Add GitHub token if available to avoid rate limits
+        let mut response = http_client.get_async(&api_url).await
+            .map_err(|e| format!("Failed to fetch directory listing: {}", e))?;
+            
+        if response.status() != StatusCode::OK {
+            error!("GitHub API returned status {} for URL: {}", response.status(), api_url);
+            
+            // Check if it's a rate limit issue
+            if github::is_rate_limited(response.status().as_u16(), response.headers()) {
+                let reset = github::rate_limit_reset_in(response.headers())
+                    .map(|secs| format!(", it resets in {} minutes", secs.div_ceil(60)))
+                    .unwrap_or_default();
+                let hint = if github::token().is_some() {
+                    String::new()
+                } else {
+                    format!(". Adding a GitHub token in Settings → Network (or {}) raises the limit", github::TOKEN_ENV)
+                };
+                return Err(format!("GitHub API rate limit reached{}{}", reset, hint));
+            }
+            
+            return Err(format!("GitHub API returned status: {}", response.status()));
+        }
+        
+        let json_text = response.text().await
+            .map_err(|e| format!("Failed to read directory listing: {}", e))?;
+            
+        debug!("Got directory listing response of {} bytes", json_text.len());
+            
+        let contents: Vec<GithubContent> = serde_json::from_str(&json_text)
+            .map_err(|e| {
+                error!("Failed to parse JSON: {}", e);
+                error!("JSON content: {}", json_text);
+                format!("Failed to parse directory listing: {}", e)
+            })?;
+        
+        debug!("Found {} items in directory {}", contents.len(), relative_path);
+        
+        let mut downloaded_files = Vec::new();
+        
+        for item in contents {
+            debug!("Processing item: {} (type: {})", item.name, item.content_type);
+            let target_path = modpack_root.join(&item.path);
+            
+            if item.content_type == "file" {
+                if let Some(download_url) = item.download_url {
+                    // Create parent directory
+                    if let Some(parent) = target_path.parent() {
+                        fs::create_dir_all(parent)
+                            .map_err(|e| format!("Failed to create directory: {}", e))?;
+                    }
+                    
+                    // Download the file
+                    debug!("Downloading file: {} -> {:?}", download_url, target_path);
+                    let slot = download_queue::QUEUE.acquire().await;
+                    let mut file_response = http_client.get_async(&download_url).await
+                        .map_err(|e| format!("Failed to download file {}: {}", item.name, e))?;
+                        
+                    let file_bytes = download_queue::read_body(&mut file_response).await
+                        .map_err(|e| format!("Failed to read file bytes: {}", e))?;
+                    // Subdirectories below take their own slots
+                    drop(slot);
+                        
+                    fs::write(&target_path, file_bytes)
+                        .map_err(|e| format!("Failed to write file: {}", e))?;
+                        
+                    downloaded_files.push(target_path.to_string_lossy().to_string());
+                    debug!("Downloaded file: {}", item.path);
+                } else {
+                    error!("No download URL for file: {}", item.name);
+                }
+            } else if item.content_type == "dir" {
+                // Create the directory
+                fs::create_dir_all(&target_path)
+                    .map_err(|e| format!("Failed to create directory {}: {}", target_path.display(), e))?;
+                
+                // Recursively download subdirectories
+                match download_github_directory(http_client, pack_source, &item.path, modpack_root).await {
+                    Ok(mut subfiles) => {
+                        debug!("Downloaded {} files from subdirectory {}", subfiles.len(), item.path);
+                        downloaded_files.append(&mut subfiles);
+                    },
+                    Err(e) => {
+                        error!("Failed to download subdirectory {}: {}", item.path, e);
+                        // Continue with other files instead of failing completely
+                    }
+                }
+            }
+        }
+        
+        debug!("Downloaded {} files total for directory {}", downloaded_files.len(), relative_path);
+        Ok(downloaded_files)
+    })
+}
+
+impl Display for Launcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Launcher::Vanilla(_) => write!(f, "Vanilla"),
+            Launcher::MultiMC(_) => write!(f, "MultiMC"),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+struct InstallerProfile {
+    manifest: Manifest,
+    http_client: CachedHttpClient,
+    installed: bool,
+    update_available: bool,
+    modpack_source: String,
+    modpack_branch: String,
+    enabled_features: Vec<String>,
+    launcher: Option<Launcher>,
+    local_manifest: Option<Manifest>,
+    changelog: Option<Changelog>, // This now uses the imported type
+}
+           
+impl InstallerProfile {
+    fn pack_source(&self) -> sources::PackSource {
+        sources::PackSource::from_profile(&self.modpack_source, &self.modpack_branch)
+    }
+}
+
+impl PartialEq for InstallerProfile {
+    fn eq(&self, other: &Self) -> bool {
+        self.manifest == other.manifest && 
+        self.installed == other.installed && 
+        self.update_available == other.update_available && 
+        self.modpack_source == other.modpack_source && 
+        self.modpack_branch == other.modpack_branch && 
+        self.enabled_features == other.enabled_features && 
+        self.launcher == other.launcher && 
+        self.local_manifest == other.local_manifest
+        // We're intentionally not comparing changelog for equality
+        // as it's not critical for determining if profiles are equal
+    }
+}
+
+async fn init(
+    modpack_source: String,
+    modpack_branch: String,
+    launcher: Launcher,
+) -> Result<InstallerProfile, String> {
+    debug!("Initializing with:");
+    debug!("  Source: {}", modpack_source);
+    debug!("  Branch: {}", modpack_branch);
+    debug!("  Launcher: {:?}", launcher);
+
+    // Create http_client first
+    let http_client = CachedHttpClient::new();
+    
+    // Construct full URL for manifest
+    let full_url = format!("{}{}{}/manifest.json", GH_RAW, modpack_source, modpack_branch);
+    debug!("Fetching manifest from URL: {}", full_url);
+
+    // Fetch manifest
+    let mut manifest_resp = match http_client.get_async(full_url.clone()).await {
+        Ok(val) => val,
+        Err(e) => {
+            error!("Failed to fetch manifest. Error: {:?}", e);
+            return Err(e.to_string());
+        }
+    };
+
+    let manifest_text = match manifest_resp.text().await {
+        Ok(text) => {
+            debug!("Received manifest text");
+            text
+        },
+        Err(e) => {
+            error!("Failed to get manifest text. Error: {:?}", e);
+            return Err(e.to_string());
+        }
+    };
+
+    let manifest: Manifest = match serde_json::from_str(&manifest_text) {
+        Ok(val) => val,
+        Err(e) => {
+            error!("Failed to parse manifest. Error: {:?}", e);
+            return Err(e.to_string());
+        }
+    };
+
+    // Its not guaranteed that a manifest with a different version manages to parse however we handle parsing failures and therefore we should be fine to just return an error here
+    // Manifests declaring capabilities skip the exact version check as long as we support what they require
+    capabilities::check_manifest_capabilities(
+        manifest.manifest_version,
+        manifest.capabilities.as_ref(),
+    )?;
+
+    // Now try to fetch the changelog
+    let full_source = format!("{}{}", modpack_source, modpack_branch);
+    let changelog = match crate::changelog::fetch_changelog(&full_source, &http_client).await {
+        Ok(changelog) => {
+            debug!("Successfully fetched changelog with {} entries", changelog.entries.len());
+            Some(changelog)
+        },
+        Err(e) => {
+            // Just log the error but don't fail - changelog is optional
+            warn!("Couldn't fetch changelog: {}", e);
+            None
+        }
+    };
+
+    let modpack_root = get_modpack_root(&launcher, &manifest.uuid);
+    let mut installed = modpack_root.join(Path::new("manifest.json")).exists();
+    let local_manifest: Option<Result<Manifest, serde_json::Error>> = if installed {
+        let local_manifest_content =
+            match fs::read_to_string(modpack_root.join(Path::new("manifest.json"))) {
+                Ok(val) => val,
+                Err(e) => return Err(e.to_string()),
+            };
+        Some(serde_json::from_str(&local_manifest_content))
+    } else {
+        installed = false;
+        None
+    };
+    let update_available = if installed {
+        match local_manifest.as_ref().unwrap() {
+            Ok(val) => manifest.modpack_version != val.modpack_version,
+            Err(_) => false,
+        }
+    } else {
+        false
+    };
+    let mut enabled_features = vec![default_id()];
+    if !installed {
+        for feat in &manifest.features {
+            if feat.default {
+                enabled_features.push(feat.id.clone());
+            }
+        }
+    }
+    Ok(InstallerProfile {
+        manifest,
+        http_client,
+        installed,
+        update_available,
+        modpack_source,
+        modpack_branch,
+        enabled_features,
+        launcher: Some(launcher),
+        local_manifest: if local_manifest.is_some() && local_manifest.as_ref().unwrap().is_ok() {
+            Some(local_manifest.unwrap().unwrap())
+        } else {
+            None
+        },
+        changelog, // Add the changelog field
+    })
+}
+
+fn compare_versions(v1: &str, v2: &str) -> std::cmp::Ordering {
+    let parse_version = |v: &str| -> Vec<u32> {
+        v.split('.')
+            .filter_map(|s| s.parse::<u32>().ok())
+            .collect()
+    };
+    
+    let v1_parts = parse_version(v1);
+    let v2_parts = parse_version(v2);
+    
+    for i in 0..std::cmp::max(v1_parts.len(), v2_parts.len()) {
+        let p1 = v1_parts.get(i).copied().unwrap_or(0);
+        let p2 = v2_parts.get(i).copied().unwrap_or(0);
+        
+        match p1.cmp(&p2) {
+            std::cmp::Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    
+    std::cmp::Ordering::Equal
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+
+use log::{debug, info, warn};
+use serde_json::Value as JsonValue;
+
+use crate::installation::Installation;
+use crate::{build_http_client, forge, get_minecraft_folder, http_mock, write_loader_profile, Loader};
+
+// The loader's version json (and for fabric/quilt the dummy jar next to it) live in the shared
+// .minecraft/versions folder, where launcher cleanups (or the user) can delete them. The profile
+// then fails to start with a vague "version not found", so we check them before launching and
+// download them again.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoaderHealth {
+    Ok,
+    Missing,
+    Invalid(String),
+}
+
+fn loader_for(installation: &Installation) -> Loader {
+    Loader {
+        r#type: installation.loader_type.clone(),
+        version: installation.loader_version.clone(),
+        minecraft_version: installation.minecraft_version.clone(),
+    }
+}
+
+/// Checks versions/<loader_name> under `root`, used by installs as well so broken files get replaced.
+/// Forge and NeoForge installers don't write a jar there, so only fabric/quilt expect one.
+pub fn check_files(root: &Path, loader_name: &str, expect_jar: bool) -> LoaderHealth {
+    let dir = root.join("versions").join(loader_name);
+    let json_path = dir.join(format!("{}.json", loader_name));
+    if !json_path.exists() {
+        return LoaderHealth::Missing;
+    }
+    let content = match fs::read_to_string(&json_path) {
+        Ok(content) => content,
+        Err(e) => return LoaderHealth::Invalid(format!("Can't read {}: {}", json_path.display(), e)),
+    };
+    let json: JsonValue = match serde_json::from_str(&content) {
+        Ok(json) => json,
+        Err(e) => return LoaderHealth::Invalid(format!("{} is not valid JSON: {}", json_path.display(), e)),
+    };
+    // Truncated downloads and error pages from the meta server still parse, they just lack the id
+    if json.get("id").and_then(|id| id.as_str()) != Some(loader_name) {
+        return LoaderHealth::Invalid(format!("{} doesn't describe {}", json_path.display(), loader_name));
+    }
+    if expect_jar && !dir.join(format!("{}.jar", loader_name)).exists() {
+        return LoaderHealth::Invalid(format!("{}.jar is missing", loader_name));
+    }
+    LoaderHealth::Ok
+}
+
+/// Health of the loader a vanilla launcher installation starts with.
+pub fn check(installation: &Installation) -> Result<LoaderHealth, String> {
+    let loader = loader_for(installation);
+    let loader_name = loader
+        .version_id()
+        .ok_or_else(|| format!("Unsupported loader '{}'", installation.loader_type))?;
+    let expect_jar = !forge::is_forge_like(&loader.r#type);
+    Ok(check_files(&get_minecraft_folder(), &loader_name, expect_jar))
+}
+
+/// Downloads the loader profile again, overwriting whatever is left of it.
+pub fn repair(installation: &Installation) -> Result<(), String> {
+    let loader = loader_for(installation);
+    if forge::is_forge_like(&loader.r#type) {
+        // The installer overwrites the version, so a broken one is fixed by running it again
+        forge::install(&loader.r#type, &loader.minecraft_version, &loader.version, &get_minecraft_folder())?;
+        return Ok(());
+    }
+    let (Some(url), Some(loader_name)) = (loader.profile_url(), loader.version_id()) else {
+        return Err(format!("Unsupported loader '{}'", installation.loader_type));
+    };
+    let json = http_mock::get_text_blocking(&build_http_client(), &url)?;
+    // Don't replace a broken profile with another broken one
+    let parsed: JsonValue =
+        serde_json::from_str(&json).map_err(|e| format!("Loader profile from {} is not valid JSON: {}", url, e))?;
+    if parsed.get("id").and_then(|id| id.as_str()) != Some(loader_name.as_str()) {
+        return Err(format!("Loader profile from {} doesn't describe {}", url, loader_name));
+    }
+    write_loader_profile(&get_minecraft_folder(), &loader_name, &json)?;
+    info!("Restored loader profile {}", loader_name);
+    Ok(())
+}
+
+/// Repairs the loader if needed, returns whether anything had to be done.
+pub fn ensure(installation: &Installation) -> Result<bool, String> {
+    match check(installation)? {
+        LoaderHealth::Ok => {
+            debug!("Loader for {} is intact", installation.id);
+            Ok(false)
+        }
+        health => {
+            warn!("Loader for {} needs repair: {:?}", installation.id, health);
+            repair(installation)?;
+            Ok(true)
+        }
+    }
+}
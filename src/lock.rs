@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::{debug, error, warn};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const KEYCHAIN_SERVICE: &str = "wynncraft-overhaul-installer";
+const KEYCHAIN_USER: &str = "lock-pin";
+
+// Background work that changes installations (automatic updates) has no UI to be locked out of,
+// it checks these instead: whether the lock is set up and whether the UI is locked right now
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ENGAGED: AtomicBool = AtomicBool::new(false);
+
+// Locked mode: installations become read-only in the UI until the PIN is entered.
+// This is meant to stop accidental changes on a shared PC, not a determined attacker.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct LockSettings {
+    pub enabled: bool,
+    // Keep the PIN hash in the OS keychain instead of config.json
+    pub use_keychain: bool,
+    pub pin_hash: Option<String>,
+    pub salt: String,
+}
+
+pub fn configure(settings: &LockSettings) {
+    ENABLED.store(settings.enabled, Ordering::Relaxed);
+}
+
+/// Mirrors the header's lock button.
+pub fn set_engaged(locked: bool) {
+    ENGAGED.store(locked, Ordering::Relaxed);
+}
+
+/// Whether installations may only be changed by someone who entered the PIN.
+pub fn blocks_background_changes() -> bool {
+    ENABLED.load(Ordering::Relaxed) || ENGAGED.load(Ordering::Relaxed)
+}
+
+fn hash_pin(salt: &str, pin: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(pin.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn keychain() -> keyring::Keyring<'static> {
+    keyring::Keyring::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+}
+
+fn stored_hash(settings: &LockSettings) -> Option<String> {
+    if settings.use_keychain {
+        match keychain().get_password() {
+            Ok(hash) => Some(hash),
+            Err(e) => {
+                error!("Failed to read lock PIN from keychain: {}", e);
+                None
+            }
+        }
+    } else {
+        settings.pin_hash.clone()
+    }
+}
+
+pub fn validate_pin(pin: &str) -> Result<(), String> {
+    if pin.len() < 4 || pin.len() > 12 || !pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err("The PIN must be 4 to 12 digits".to_string());
+    }
+    Ok(())
+}
+
+/// Sets a new PIN and enables the lock.
+pub fn set_pin(settings: &mut LockSettings, pin: &str) -> Result<(), String> {
+    validate_pin(pin)?;
+    let salt: String = rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect();
+    let hash = hash_pin(&salt, pin);
+
+    if settings.use_keychain {
+        keychain()
+            .set_password(&hash)
+            .map_err(|e| format!("Failed to store PIN in the system keychain: {}", e))?;
+        settings.pin_hash = None;
+    } else {
+        settings.pin_hash = Some(hash);
+    }
+    settings.salt = salt;
+    settings.enabled = true;
+    debug!("Lock PIN updated (keychain: {})", settings.use_keychain);
+    Ok(())
+}
+
+pub fn verify_pin(settings: &LockSettings, pin: &str) -> bool {
+    match stored_hash(settings) {
+        Some(hash) => hash_pin(&settings.salt, pin) == hash,
+        None => {
+            // No PIN stored anywhere, refusing would lock the user out for good
+            warn!("Lock is enabled but no PIN is stored, treating as unlocked");
+            true
+        }
+    }
+}
+
+/// Disables the lock and removes any stored PIN.
+pub fn clear_pin(settings: &mut LockSettings) {
+    if settings.use_keychain {
+        if let Err(e) = keychain().delete_password() {
+            warn!("Failed to remove PIN from keychain: {}", e);
+        }
+    }
+    settings.enabled = false;
+    settings.pin_hash = None;
+    settings.salt = String::new();
+}
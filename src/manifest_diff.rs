@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::{Downloadable, Manifest};
+
+// Compares the local manifest.json (what was installed last time) with the remote manifest for the
+// installation's current feature selection. Updates use it to skip everything that didn't change,
+// and the update dialog lists it so users know what's about to happen before it happens.
+// file_changes turns the same comparison into files on disk for the "Preview changes" list.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Updated { from: String, to: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentChange {
+    pub section: &'static str,
+    pub name: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChangeSet {
+    pub from_version: String,
+    pub to_version: String,
+    pub changes: Vec<ComponentChange>,
+}
+
+impl ChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, kind: fn(&ChangeKind) -> bool) -> usize {
+        self.changes.iter().filter(|c| kind(&c.kind)).count()
+    }
+}
+
+fn wanted(id: &str, optional: bool, enabled_features: &[String]) -> bool {
+    id.is_empty() || id == "default" || !optional || enabled_features.iter().any(|f| f == id)
+}
+
+// Matched by name like remove_old_items does, only what has a path was actually installed
+fn diff_items<T: Downloadable>(section: &'static str, local: &[T], remote: &[T], enabled_features: &[String], changes: &mut Vec<ComponentChange>) {
+    let remote_wanted: Vec<&T> = remote
+        .iter()
+        .filter(|item| wanted(item.get_id(), true, enabled_features))
+        .collect();
+    let installed: Vec<&T> = local.iter().filter(|item| item.get_path().is_some()).collect();
+
+    for item in &remote_wanted {
+        match installed.iter().find(|i| i.get_name() == item.get_name()) {
+            None => changes.push(ComponentChange {
+                section,
+                name: item.get_name().clone(),
+                kind: ChangeKind::Added,
+            }),
+            Some(old) if old.get_version() != item.get_version() => changes.push(ComponentChange {
+                section,
+                name: item.get_name().clone(),
+                kind: ChangeKind::Updated {
+                    from: old.get_version().clone(),
+                    to: item.get_version().clone(),
+                },
+            }),
+            Some(_) => {}
+        }
+    }
+    for old in installed {
+        if !remote_wanted.iter().any(|item| item.get_name() == old.get_name()) {
+            changes.push(ComponentChange {
+                section,
+                name: old.get_name().clone(),
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+}
+
+pub fn diff(local: &Manifest, remote: &Manifest) -> ChangeSet {
+    let enabled = &remote.enabled_features;
+    let mut changes = Vec::new();
+    diff_items("Mod", &local.mods, &remote.mods, enabled, &mut changes);
+    diff_items("Shaderpack", &local.shaderpacks, &remote.shaderpacks, enabled, &mut changes);
+    diff_items("Resourcepack", &local.resourcepacks, &remote.resourcepacks, enabled, &mut changes);
+
+    // Includes aren't versioned, they're refetched on every update. Only additions and removals show up
+    let local_includes: HashSet<&str> = local
+        .include
+        .iter()
+        .filter(|inc| wanted(&inc.id, inc.optional, &local.enabled_features))
+        .map(|inc| inc.location.as_str())
+        .collect();
+    let remote_includes: Vec<_> = remote
+        .include
+        .iter()
+        .filter(|inc| wanted(&inc.id, inc.optional, enabled))
+        .collect();
+    for inc in &remote_includes {
+        if !local_includes.contains(inc.location.as_str()) {
+            changes.push(ComponentChange {
+                section: "Include",
+                name: inc.name.clone().unwrap_or_else(|| inc.location.clone()),
+                kind: ChangeKind::Added,
+            });
+        }
+    }
+    for inc in local.include.iter().filter(|inc| local_includes.contains(inc.location.as_str())) {
+        if !remote_includes.iter().any(|r| r.location == inc.location) {
+            changes.push(ComponentChange {
+                section: "Include",
+                name: inc.name.clone().unwrap_or_else(|| inc.location.clone()),
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+
+    // Remote includes remember the version they were extracted from in included_files
+    let installed_files = local.included_files.clone().unwrap_or_default();
+    let remote_remotes: Vec<_> = remote
+        .remote_include
+        .iter()
+        .flatten()
+        .filter(|r| wanted(&r.id, r.optional, enabled))
+        .collect();
+    for remote_include in &remote_remotes {
+        let name = remote_include.name.clone().unwrap_or_else(|| remote_include.id.clone());
+        match installed_files.get(&remote_include.id) {
+            None => changes.push(ComponentChange { section: "Remote include", name, kind: ChangeKind::Added }),
+            Some(previous) if previous.md5 != remote_include.version => changes.push(ComponentChange {
+                section: "Remote include",
+                name,
+                kind: ChangeKind::Updated {
+                    from: previous.md5.clone(),
+                    to: remote_include.version.clone(),
+                },
+            }),
+            Some(_) => {}
+        }
+    }
+    for old in local.remote_include.iter().flatten() {
+        if installed_files.contains_key(&old.id) && !remote_remotes.iter().any(|r| r.id == old.id) {
+            changes.push(ComponentChange {
+                section: "Remote include",
+                name: old.name.clone().unwrap_or_else(|| old.id.clone()),
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+
+    ChangeSet {
+        from_version: local.modpack_version.clone(),
+        to_version: remote.modpack_version.clone(),
+        changes,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileAction {
+    Add,
+    Replace,
+    // Game option files, the pack's values go into the player's file
+    Merge,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub action: FileAction,
+    // Relative to the game directory, a folder ("mods/") when the file name is only known after the download
+    pub path: String,
+    pub component: String,
+}
+
+fn relative_name(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+fn component_files<T: Downloadable>(folder: &str, local: &[T], remote: &[T], enabled_features: &[String], root: &Path, files: &mut Vec<FileChange>) {
+    let remote_wanted: Vec<&T> = remote
+        .iter()
+        .filter(|item| wanted(item.get_id(), true, enabled_features))
+        .collect();
+    let installed: Vec<(&T, &Path)> = local
+        .iter()
+        .filter_map(|item| item.get_path().as_deref().map(|path| (item, path)))
+        .collect();
+
+    for item in &remote_wanted {
+        let (action, path) = match installed.iter().find(|(i, _)| i.get_name() == item.get_name()) {
+            None => (FileAction::Add, format!("{}/", folder)),
+            // The old version is deleted, the new one can have another file name
+            Some((old, path)) if old.get_version() != item.get_version() => (FileAction::Replace, relative_name(root, path)),
+            Some(_) => continue,
+        };
+        files.push(FileChange { action, path, component: item.get_name().clone() });
+    }
+    for (old, path) in installed {
+        if !remote_wanted.iter().any(|item| item.get_name() == old.get_name()) {
+            files.push(FileChange { action: FileAction::Delete, path: relative_name(root, path), component: old.get_name().clone() });
+        }
+    }
+}
+
+/// The files under `root` that installing `remote` over `local` (None for a first install) adds,
+/// replaces and deletes. Includes are fetched again on every install, so every one of them shows up.
+pub fn file_changes(local: Option<&Manifest>, remote: &Manifest, root: &Path) -> Vec<FileChange> {
+    let enabled = &remote.enabled_features;
+    let mut files = Vec::new();
+    component_files("mods", local.map_or(&[][..], |l| l.mods.as_slice()), &remote.mods, enabled, root, &mut files);
+    component_files("shaderpacks", local.map_or(&[][..], |l| l.shaderpacks.as_slice()), &remote.shaderpacks, enabled, root, &mut files);
+    component_files("resourcepacks", local.map_or(&[][..], |l| l.resourcepacks.as_slice()), &remote.resourcepacks, enabled, root, &mut files);
+
+    let installed_files = local.and_then(|l| l.included_files.clone()).unwrap_or_default();
+    for inc in remote.include.iter().filter(|inc| wanted(&inc.id, inc.optional, enabled)) {
+        // Updates leave these alone, install treats the same way
+        if local.is_some() && inc.ignore_update {
+            continue;
+        }
+        let component = inc.name.clone().unwrap_or_else(|| inc.location.clone());
+        let is_file = inc.location.contains('.') && !inc.location.starts_with('.');
+        if is_file {
+            let exists = root.join(&inc.location).exists();
+            let action = match (crate::options_merge::separator(&inc.location), exists) {
+                (_, false) => FileAction::Add,
+                (Some(_), true) => FileAction::Merge,
+                (None, true) => FileAction::Replace,
+            };
+            files.push(FileChange { action, path: inc.location.clone(), component });
+            continue;
+        }
+        // Folder includes: what the last install put there is overwritten, new files aren't known yet
+        let previous: Vec<&String> = installed_files
+            .get(&inc.id)
+            .map(|included| included.files.iter().filter(|f| Path::new(f).exists()).collect())
+            .unwrap_or_default();
+        if previous.is_empty() {
+            files.push(FileChange { action: FileAction::Add, path: format!("{}/", inc.location), component });
+        }
+        for file in previous {
+            files.push(FileChange { action: FileAction::Replace, path: relative_name(root, Path::new(file)), component: component.clone() });
+        }
+    }
+
+    let remote_wanted: Vec<_> = remote
+        .remote_include
+        .iter()
+        .flatten()
+        .filter(|r| wanted(&r.id, r.optional, enabled))
+        .collect();
+    for remote_include in &remote_wanted {
+        let component = remote_include.name.clone().unwrap_or_else(|| remote_include.id.clone());
+        match installed_files.get(&remote_include.id) {
+            None => {
+                let path = remote_include.path.clone().map_or_else(|| "./".to_string(), |p| format!("{}/", p.trim_end_matches('/')));
+                files.push(FileChange { action: FileAction::Add, path, component });
+            }
+            Some(previous) if previous.md5 != remote_include.version => {
+                for file in previous.files.iter().filter(|f| Path::new(f).exists()) {
+                    files.push(FileChange { action: FileAction::Replace, path: relative_name(root, Path::new(file)), component: component.clone() });
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    for old in local.and_then(|l| l.remote_include.as_ref()).into_iter().flatten() {
+        let Some(previous) = installed_files.get(&old.id) else { continue };
+        if remote_wanted.iter().any(|r| r.id == old.id) {
+            continue;
+        }
+        let component = old.name.clone().unwrap_or_else(|| old.id.clone());
+        for file in previous.files.iter().filter(|f| Path::new(f).exists()) {
+            files.push(FileChange { action: FileAction::Delete, path: relative_name(root, Path::new(file)), component: component.clone() });
+        }
+    }
+
+    files
+}
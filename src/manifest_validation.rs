@@ -0,0 +1,164 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::universal::{ManifestError, ManifestErrorType};
+
+// Parsing of universal.json, presets.json and changelog.json that says where a problem is instead
+// of just passing serde's message on. The structs the files deserialize into are the schema:
+// serde_path_to_error tracks the path serde was at when it gave up, which becomes a JSON pointer
+// with the expected type and the offending part of the file.
+
+// Lines of the file (or of the offending value) shown in a report
+const SNIPPET_LINES: usize = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorLocation {
+    // JSON pointer (RFC 6901) of the value, "" for the whole file
+    pub pointer: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub expected: Option<String>,
+    pub snippet: Option<String>,
+}
+
+impl ErrorLocation {
+    /// "/mods/3/version (line 40, column 18)"
+    pub fn describe(&self) -> String {
+        let pointer = if self.pointer.is_empty() { "the top level".to_string() } else { self.pointer.clone() };
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{} (line {}, column {})", pointer, line, column),
+            (Some(line), None) => format!("{} (line {})", pointer, line),
+            _ => pointer,
+        }
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn to_pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            serde_path_to_error::Segment::Seq { index } => Some(index.to_string()),
+            serde_path_to_error::Segment::Map { key } => Some(escape_pointer_token(key)),
+            serde_path_to_error::Segment::Enum { variant } => Some(escape_pointer_token(variant)),
+            serde_path_to_error::Segment::Unknown => None,
+        })
+        .map(|token| format!("/{}", token))
+        .collect()
+}
+
+// "invalid type: string "yes", expected a boolean at line 4 column 20" -> "a boolean"
+fn expected_from_message(message: &str) -> Option<String> {
+    if let Some(field) = message.strip_prefix("missing field `").and_then(|rest| rest.split('`').next()) {
+        return Some(format!("a \"{}\" field", field));
+    }
+    let (_, rest) = message.split_once("expected ")?;
+    let expected = rest.split(" at line ").next().unwrap_or(rest).trim();
+    (!expected.is_empty()).then(|| expected.to_string())
+}
+
+// Strips serde_json's " at line X column Y", the location is reported separately
+fn without_position(message: &str) -> String {
+    message.split(" at line ").next().unwrap_or(message).to_string()
+}
+
+fn truncate_lines(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() <= SNIPPET_LINES {
+        return text.to_string();
+    }
+    format!("{}\n  … {} more lines", lines[..SNIPPET_LINES].join("\n"), lines.len() - SNIPPET_LINES)
+}
+
+/// The lines around `line` (1-based) with a marker under `column`, for syntax errors where there's
+/// no value to show.
+fn snippet_around(content: &str, line: usize, column: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    if line == 0 || line > lines.len() {
+        return None;
+    }
+    let first = line.saturating_sub(3);
+    let last = (line + 2).min(lines.len());
+    let mut snippet = Vec::new();
+    for (index, text) in lines[first..last].iter().enumerate() {
+        let number = first + index + 1;
+        snippet.push(format!("{:>5} | {}", number, text));
+        if number == line {
+            snippet.push(format!("{:>5} | {}^", "", " ".repeat(column.saturating_sub(1))));
+        }
+    }
+    Some(snippet.join("\n"))
+}
+
+/// The value at `pointer`, or its parent when it's a bare string/number so there's some context.
+fn snippet_at(value: &Value, pointer: &str) -> Option<String> {
+    let target = value.pointer(pointer)?;
+    let shown = match target {
+        Value::Object(_) | Value::Array(_) => target,
+        _ => pointer
+            .rsplit_once('/')
+            .and_then(|(parent, _)| value.pointer(parent))
+            .unwrap_or(target),
+    };
+    serde_json::to_string_pretty(shown).ok().map(|text| truncate_lines(&text))
+}
+
+/// Deserializes `content` as `file_name`, on failure with the location of the problem.
+pub fn parse<T: DeserializeOwned>(content: &str, file_name: &str) -> Result<T, ManifestError> {
+    let error = |message: String, error_type: ManifestErrorType, location: ErrorLocation| ManifestError {
+        message,
+        error_type,
+        file_name: file_name.to_string(),
+        raw_content: Some(content.to_string()),
+        location: Some(location),
+    };
+
+    let value: Value = serde_json::from_str(content).map_err(|e| {
+        error(
+            format!("Invalid JSON syntax: {}", without_position(&e.to_string())),
+            ManifestErrorType::SyntaxError,
+            ErrorLocation {
+                pointer: String::new(),
+                line: Some(e.line()),
+                column: Some(e.column()),
+                expected: None,
+                snippet: snippet_around(content, e.line(), e.column()),
+            },
+        )
+    })?;
+
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let pointer = to_pointer(e.path());
+        let inner = e.inner();
+        let message = inner.to_string();
+        error(
+            without_position(&message),
+            ManifestErrorType::DeserializationError,
+            ErrorLocation {
+                snippet: snippet_at(&value, &pointer),
+                line: Some(inner.line()).filter(|line| *line > 0),
+                column: Some(inner.column()).filter(|_| inner.line() > 0),
+                expected: expected_from_message(&message),
+                pointer,
+            },
+        )
+    })
+}
+
+/// Plain text of everything known about `error`, for copying into a bug report.
+pub fn report(error: &ManifestError) -> String {
+    let mut report = format!("{} in {}\n{}\n", error.error_type, error.file_name, error.message);
+    if let Some(location) = &error.location {
+        report.push_str(&format!("\nLocation: {}\n", location.describe()));
+        if let Some(expected) = &location.expected {
+            report.push_str(&format!("Expected: {}\n", expected));
+        }
+        if let Some(snippet) = &location.snippet {
+            report.push_str(&format!("\n{}\n", snippet));
+        }
+    }
+    report
+}
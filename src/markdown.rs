@@ -0,0 +1,55 @@
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+
+// Component descriptions come from the manifest, which anyone can PR into. Raw HTML is shown as
+// text, images are dropped (their alt text stays) and only http(s)/mailto links survive, so the
+// output is safe to put into the webview with dangerous_inner_html.
+
+fn is_safe_link(url: &str) -> bool {
+    let url = url.trim().to_lowercase();
+    url.starts_with("https://") || url.starts_with("http://") || url.starts_with("mailto:")
+}
+
+pub fn render_markdown(text: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+
+    let mut unsafe_links = 0usize;
+    let events = Parser::new_ext(text, options).filter_map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Some(Event::Text(raw)),
+        Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => None,
+        Event::Start(Tag::Link { link_type, dest_url, title, id }) => {
+            if is_safe_link(&dest_url) {
+                Some(Event::Start(Tag::Link { link_type, dest_url, title, id }))
+            } else {
+                // Keep the link text, drop the link
+                unsafe_links += 1;
+                None
+            }
+        }
+        Event::End(TagEnd::Link) if unsafe_links > 0 => {
+            unsafe_links -= 1;
+            None
+        }
+        other => Some(other),
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    // Links open in the system browser instead of replacing the installer
+    output.replace("<a href=", "<a target=\"_blank\" rel=\"noopener noreferrer\" href=")
+}
+
+/// Plain text preview for card descriptions, markdown syntax stripped.
+pub fn plain_text(text: &str) -> String {
+    let mut output = String::new();
+    for event in Parser::new(text) {
+        match event {
+            Event::Text(t) | Event::Code(t) => output.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => output.push(' '),
+            Event::End(TagEnd::Paragraph) => output.push(' '),
+            _ => {}
+        }
+    }
+    output.trim().to_string()
+}
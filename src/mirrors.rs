@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+
+use crate::universal::Mirror;
+use crate::{CachedHttpClient, DownloadError, Downloadable};
+
+// Mirror fallback for downloads. Manifest entries can list mirrors, other places to get the same
+// file from, which are tried in order when the primary source doesn't deliver: a 404, a rate limit
+// that outlasted the retries, a host the circuit breaker gave up on. Errors on our side (the disk,
+// offline mode) would hit every mirror the same way, those fail right away.
+
+fn worth_a_mirror(e: &DownloadError) -> bool {
+    !matches!(
+        e,
+        DownloadError::IoError(..)
+            | DownloadError::NotCached(..)
+            | DownloadError::UnsupportedType(..)
+            | DownloadError::LoaderFailed(..)
+    )
+}
+
+/// Downloads `item`, falling back to its mirrors. Also returns the location of the mirror that
+/// worked, None when the primary did.
+pub async fn download<T: Downloadable>(
+    item: &T,
+    modpack_root: &Path,
+    loader_type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<(PathBuf, Option<String>), DownloadError> {
+    let primary_error = match item.download(modpack_root, loader_type, http_client).await {
+        Ok(path) => return Ok((path, None)),
+        Err(e) if item.get_mirrors().is_empty() || !worth_a_mirror(&e) => return Err(e),
+        Err(e) => e,
+    };
+    warn!("'{}' failed from {}, trying its mirrors: {}", item.get_name(), item.get_location(), primary_error);
+    for mirror in item.get_mirrors() {
+        let candidate = mirror_item(item, mirror);
+        match candidate.download(modpack_root, loader_type, http_client).await {
+            Ok(path) => {
+                info!("Downloaded '{}' from mirror {}", item.get_name(), mirror.location);
+                return Ok((path, Some(mirror.location.clone())));
+            }
+            Err(e) => warn!("Mirror {} of '{}' failed too: {}", mirror.location, item.get_name(), e),
+        }
+    }
+    // The primary's error says the most about what's wrong
+    Err(primary_error)
+}
+
+fn mirror_item<T: Downloadable>(item: &T, mirror: &Mirror) -> T {
+    T::new(
+        item.get_name().to_owned(),
+        mirror.source.clone(),
+        mirror.location.clone(),
+        item.get_version().to_owned(),
+        None,
+        item.get_id().to_owned(),
+        item.get_authors().to_owned(),
+    )
+}
+
+/// download_zip for a remote include, its mirrors (direct links to the same zip) tried after
+/// `location`. Also returns the mirror that worked, like download().
+pub async fn download_zip(
+    name: &str,
+    http_client: &CachedHttpClient,
+    location: &str,
+    mirrors: &[Mirror],
+    path: &Path,
+    cache_key: &str,
+    only: Option<HashSet<PathBuf>>,
+) -> Result<(Vec<String>, Option<String>), DownloadError> {
+    let primary_error = match crate::download_zip(name, http_client, location, path, cache_key, only.clone()).await {
+        Ok(files) => return Ok((files, None)),
+        Err(e) if mirrors.is_empty() || !worth_a_mirror(&e) => return Err(e),
+        Err(e) => e,
+    };
+    warn!("'{}' failed from {}, trying its mirrors: {}", name, location, primary_error);
+    for mirror in mirrors {
+        // Same zip, the cache key stays the primary's
+        match crate::download_zip(name, http_client, &mirror.location, path, cache_key, only.clone()).await {
+            Ok(files) => {
+                info!("Downloaded '{}' from mirror {}", name, mirror.location);
+                return Ok((files, Some(mirror.location.clone())));
+            }
+            Err(e) => warn!("Mirror {} of '{}' failed too: {}", mirror.location, name, e),
+        }
+    }
+    Err(primary_error)
+}
@@ -0,0 +1,360 @@
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use isahc::{ReadResponseExt, Request};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::build_http_client;
+
+// Signing in with a Microsoft account for the direct launch (see direct_launch.rs), which starts
+// the game without the vanilla launcher and so needs a Minecraft access token of its own.
+// The device code flow shows the user a short code to enter on microsoft.com, then the token goes
+// Microsoft -> Xbox Live -> XSTS -> Minecraft services. Only the Microsoft refresh token and the
+// current Minecraft token are kept, as one JSON entry in the OS keychain.
+//
+// Microsoft only hands out tokens for Minecraft to registered Azure applications, the client id
+// is set in the preferences or through WC_OVHL_MSA_CLIENT_ID.
+
+pub const CLIENT_ID_ENV: &str = "WC_OVHL_MSA_CLIENT_ID";
+const KEYCHAIN_SERVICE: &str = "wynncraft-overhaul-installer";
+const KEYCHAIN_USER: &str = "microsoft-account";
+
+const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
+const TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
+const XBL_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
+const XSTS_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
+const MC_LOGIN_URL: &str = "https://api.minecraftservices.com/authentication/login_with_xbox";
+const MC_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
+const SCOPE: &str = "XboxLive.signin offline_access";
+// Refreshed a bit early, a launch shouldn't start with a token that runs out while loading
+const EXPIRY_MARGIN: i64 = 5 * 60;
+
+// Mirrors config.advanced.microsoft_client_id, set at startup and whenever preferences are saved
+static CLIENT_ID: RwLock<Option<String>> = RwLock::new(None);
+
+pub fn configure(client_id: Option<&String>) {
+    let client_id = std::env::var(CLIENT_ID_ENV)
+        .ok()
+        .or_else(|| client_id.cloned())
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    debug!("Microsoft client id configured: {}", client_id.is_some());
+    if let Ok(mut current) = CLIENT_ID.write() {
+        *current = client_id;
+    }
+}
+
+fn client_id() -> Result<String, String> {
+    CLIENT_ID
+        .read()
+        .ok()
+        .and_then(|id| id.clone())
+        .ok_or_else(|| format!("No Microsoft client id is set, add one in the preferences or set {}", CLIENT_ID_ENV))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+// What's kept in the keychain. Not Debug, tokens shouldn't end up in the log
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Account {
+    pub username: String,
+    pub uuid: String,
+    pub xuid: String,
+    refresh_token: String,
+    access_token: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl Account {
+    pub fn access_token(&self) -> &str {
+        &self.access_token
+    }
+
+    fn is_expired(&self) -> bool {
+        Utc::now() + chrono::Duration::seconds(EXPIRY_MARGIN) >= self.expires_at
+    }
+}
+
+#[derive(Deserialize)]
+struct OAuthToken {
+    access_token: String,
+    refresh_token: String,
+}
+
+#[derive(Deserialize)]
+struct OAuthError {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XboxToken {
+    token: String,
+    display_claims: XboxClaims,
+}
+
+#[derive(Deserialize)]
+struct XboxClaims {
+    xui: Vec<XboxUser>,
+}
+
+#[derive(Deserialize)]
+struct XboxUser {
+    uhs: String,
+    #[serde(default)]
+    xid: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XstsError {
+    x_err: u64,
+}
+
+#[derive(Deserialize)]
+struct MinecraftToken {
+    access_token: String,
+    expires_in: i64,
+}
+
+#[derive(Deserialize)]
+struct MinecraftProfile {
+    id: String,
+    name: String,
+}
+
+fn post_form(url: &str, form: &[(&str, &str)]) -> Result<(u16, String), String> {
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(form)
+        .finish();
+    let request = Request::post(url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body)
+        .map_err(|e| format!("Failed to create request: {}", e))?;
+    let mut response = build_http_client()
+        .send(request)
+        .map_err(|e| format!("Failed to reach {}: {}", url, e))?;
+    let text = response.text().map_err(|e| format!("Failed to read the answer of {}: {}", url, e))?;
+    Ok((response.status().as_u16(), text))
+}
+
+fn post_json(url: &str, body: serde_json::Value) -> Result<(u16, String), String> {
+    let request = Request::post(url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json")
+        .body(body.to_string())
+        .map_err(|e| format!("Failed to create request: {}", e))?;
+    let mut response = build_http_client()
+        .send(request)
+        .map_err(|e| format!("Failed to reach {}: {}", url, e))?;
+    let text = response.text().map_err(|e| format!("Failed to read the answer of {}: {}", url, e))?;
+    Ok((response.status().as_u16(), text))
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(what: &str, text: &str) -> Result<T, String> {
+    serde_json::from_str(text).map_err(|e| format!("Unexpected {} response: {}", what, e))
+}
+
+/// Asks Microsoft for a code the user enters at `verification_uri`.
+pub fn start_device_flow() -> Result<DeviceCode, String> {
+    let client_id = client_id()?;
+    let (status, text) = post_form(DEVICE_CODE_URL, &[("client_id", client_id.as_str()), ("scope", SCOPE)])?;
+    if status != 200 {
+        let error: Option<OAuthError> = serde_json::from_str(&text).ok();
+        return Err(format!(
+            "Microsoft refused the sign in: {}",
+            error.and_then(|e| e.error_description).unwrap_or_else(|| format!("status {}", status))
+        ));
+    }
+    parse("device code", &text)
+}
+
+/// Waits until the user entered the code, then signs in to Minecraft and keeps the account.
+/// Blocks for up to the code's lifetime.
+pub fn finish_device_flow(code: &DeviceCode) -> Result<Account, String> {
+    let client_id = client_id()?;
+    let mut interval = Duration::from_secs(code.interval.max(1));
+    let deadline = std::time::Instant::now() + Duration::from_secs(code.expires_in);
+    let token = loop {
+        if std::time::Instant::now() >= deadline {
+            return Err("The sign in code expired, start again".to_string());
+        }
+        std::thread::sleep(interval);
+        let (status, text) = post_form(
+            TOKEN_URL,
+            &[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", client_id.as_str()),
+                ("device_code", code.device_code.as_str()),
+            ],
+        )?;
+        if status == 200 {
+            break parse::<OAuthToken>("token", &text)?;
+        }
+        let error: OAuthError = parse("token error", &text)?;
+        match error.error.as_str() {
+            "authorization_pending" => {}
+            "slow_down" => interval += Duration::from_secs(5),
+            "authorization_declined" => return Err("The sign in was declined".to_string()),
+            "expired_token" => return Err("The sign in code expired, start again".to_string()),
+            other => return Err(format!("Sign in failed: {}", error.error_description.unwrap_or_else(|| other.to_string()))),
+        }
+    };
+    let account = sign_in_to_minecraft(token)?;
+    store(&account)?;
+    info!("Signed in to Minecraft as {}", account.username);
+    Ok(account)
+}
+
+fn sign_in_to_minecraft(token: OAuthToken) -> Result<Account, String> {
+    let (status, text) = post_json(
+        XBL_URL,
+        json!({
+            "Properties": {
+                "AuthMethod": "RPS",
+                "SiteName": "user.auth.xboxlive.com",
+                "RpsTicket": format!("d={}", token.access_token),
+            },
+            "RelyingParty": "http://auth.xboxlive.com",
+            "TokenType": "JWT",
+        }),
+    )?;
+    if status != 200 {
+        return Err(format!("Xbox Live sign in failed with status {}", status));
+    }
+    let xbl: XboxToken = parse("Xbox Live", &text)?;
+
+    let (status, text) = post_json(
+        XSTS_URL,
+        json!({
+            "Properties": { "SandboxId": "RETAIL", "UserTokens": [xbl.token] },
+            "RelyingParty": "rp://api.minecraftservices.com/",
+            "TokenType": "JWT",
+        }),
+    )?;
+    if status == 401 {
+        let reason = match serde_json::from_str::<XstsError>(&text).map(|e| e.x_err) {
+            Ok(2148916233) => "This Microsoft account has no Xbox profile yet, sign in on minecraft.net once to create one",
+            Ok(2148916235) => "Xbox Live isn't available in your country",
+            Ok(2148916236) | Ok(2148916237) => "This account needs adult verification on xbox.com first",
+            Ok(2148916238) => "This is a child account, it has to be added to a family by an adult first",
+            _ => "Xbox Live refused the account",
+        };
+        return Err(reason.to_string());
+    }
+    if status != 200 {
+        return Err(format!("Xbox Live authorization failed with status {}", status));
+    }
+    let xsts: XboxToken = parse("XSTS", &text)?;
+    let user = xsts
+        .display_claims
+        .xui
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Xbox Live returned no user".to_string())?;
+
+    let (status, text) = post_json(
+        MC_LOGIN_URL,
+        json!({ "identityToken": format!("XBL3.0 x={};{}", user.uhs, xsts.token) }),
+    )?;
+    if status != 200 {
+        return Err(format!("Minecraft sign in failed with status {}", status));
+    }
+    let minecraft: MinecraftToken = parse("Minecraft login", &text)?;
+
+    let request = Request::get(MC_PROFILE_URL)
+        .header("Authorization", format!("Bearer {}", minecraft.access_token))
+        .body(())
+        .map_err(|e| format!("Failed to create request: {}", e))?;
+    let mut response = build_http_client()
+        .send(request)
+        .map_err(|e| format!("Failed to load the Minecraft profile: {}", e))?;
+    if response.status().as_u16() == 404 {
+        return Err("This Microsoft account doesn't own Minecraft: Java Edition".to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Failed to load the Minecraft profile: status {}", response.status()));
+    }
+    let text = response.text().map_err(|e| format!("Failed to read the Minecraft profile: {}", e))?;
+    let profile: MinecraftProfile = parse("Minecraft profile", &text)?;
+
+    Ok(Account {
+        username: profile.name,
+        uuid: profile.id,
+        xuid: user.xid.unwrap_or_default(),
+        refresh_token: token.refresh_token,
+        access_token: minecraft.access_token,
+        expires_at: Utc::now() + chrono::Duration::seconds(minecraft.expires_in),
+    })
+}
+
+fn keychain() -> keyring::Keyring<'static> {
+    keyring::Keyring::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+}
+
+fn store(account: &Account) -> Result<(), String> {
+    let json = serde_json::to_string(account).map_err(|e| format!("Failed to serialize the account: {}", e))?;
+    keychain()
+        .set_password(&json)
+        .map_err(|e| format!("Failed to store the account in the system keychain: {}", e))
+}
+
+/// The signed in account as stored, None when nobody signed in. Tokens may be expired.
+pub fn stored_account() -> Option<Account> {
+    let json = keychain().get_password().ok()?;
+    match serde_json::from_str(&json) {
+        Ok(account) => Some(account),
+        Err(e) => {
+            warn!("Ignoring the unreadable account in the keychain: {}", e);
+            None
+        }
+    }
+}
+
+/// The stored account with a usable Minecraft token, refreshing it when needed.
+pub fn current_account() -> Result<Account, String> {
+    let account = stored_account().ok_or_else(|| "Not signed in to a Microsoft account".to_string())?;
+    if !account.is_expired() {
+        return Ok(account);
+    }
+    debug!("Minecraft token of {} expired, refreshing", account.username);
+    let client_id = client_id()?;
+    let (status, text) = post_form(
+        TOKEN_URL,
+        &[
+            ("grant_type", "refresh_token"),
+            ("client_id", client_id.as_str()),
+            ("refresh_token", account.refresh_token.as_str()),
+            ("scope", SCOPE),
+        ],
+    )?;
+    if status != 200 {
+        return Err("The Microsoft sign in expired, sign in again in the preferences".to_string());
+    }
+    let refreshed = sign_in_to_minecraft(parse("token", &text)?)?;
+    store(&refreshed)?;
+    Ok(refreshed)
+}
+
+pub fn sign_out() -> Result<(), String> {
+    match keychain().delete_password() {
+        Ok(_) => {
+            info!("Signed out of the Microsoft account");
+            Ok(())
+        }
+        Err(keyring::KeyringError::NoPasswordFound) => Ok(()),
+        Err(e) => Err(format!("Failed to remove the account from the system keychain: {}", e)),
+    }
+}
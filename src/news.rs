@@ -0,0 +1,134 @@
+use log::{debug, warn};
+use regex::Regex;
+use serde::Deserialize;
+
+use isahc::http::StatusCode;
+use isahc::AsyncReadResponseExt;
+
+use crate::CachedHttpClient;
+
+// Announcements on the home page, from the feed the universal manifest points `news_feed` at.
+// Either a json list of items or an RSS/Atom feed, whatever the pack team's site produces. Goes
+// through CachedHttpClient like everything else; when working offline or when the feed can't be
+// loaded the panel just isn't there.
+
+const MAX_ITEMS: usize = 6;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct NewsItem {
+    pub title: String,
+    #[serde(default, alias = "url")]
+    pub link: Option<String>,
+    #[serde(default, alias = "image_url")]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default, alias = "body")]
+    pub summary: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFeed {
+    Items(Vec<NewsItem>),
+    Wrapped { items: Vec<NewsItem> },
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// Text of the first <tag>, CDATA and markup removed
+fn tag_text(item: &str, tags: &[&str]) -> Option<String> {
+    tags.iter().find_map(|tag| {
+        let pattern = format!(r"(?s)<{0}(?:\s[^>]*)?>(.*?)</{0}>", regex::escape(tag));
+        let captured = Regex::new(&pattern).ok()?.captures(item)?.get(1)?.as_str();
+        let text = captured.trim().trim_start_matches("<![CDATA[").trim_end_matches("]]>");
+        let text = decode_entities(text);
+        // Descriptions often carry html, the card only shows text
+        let text = Regex::new(r"(?s)<[^>]*>").ok()?.replace_all(&text, "").trim().to_string();
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+fn tag_attribute(item: &str, tags: &[&str], attribute: &str) -> Option<String> {
+    tags.iter().find_map(|tag| {
+        let pattern = format!(r#"<{}\s[^>]*\b{}="([^"]*)""#, regex::escape(tag), attribute);
+        Regex::new(&pattern)
+            .ok()?
+            .captures(item)?
+            .get(1)
+            .map(|m| decode_entities(m.as_str()))
+    })
+}
+
+fn parse_xml(text: &str) -> Vec<NewsItem> {
+    let Ok(items) = Regex::new(r"(?s)<(item|entry)\b.*?</(item|entry)>") else { return Vec::new() };
+    items
+        .find_iter(text)
+        .filter_map(|item| {
+            let item = item.as_str();
+            Some(NewsItem {
+                title: tag_text(item, &["title"])?,
+                // RSS has the link as text, Atom as href
+                link: tag_text(item, &["link"]).or_else(|| tag_attribute(item, &["link"], "href")),
+                image: tag_attribute(item, &["media:content", "media:thumbnail", "enclosure"], "url"),
+                date: tag_text(item, &["pubDate", "published", "updated"]),
+                summary: tag_text(item, &["description", "summary"]),
+            })
+        })
+        .collect()
+}
+
+/// Reads a json or RSS/Atom feed.
+pub fn parse(text: &str) -> Result<Vec<NewsItem>, String> {
+    let mut items = if text.trim_start().starts_with('<') {
+        parse_xml(text)
+    } else {
+        match serde_json::from_str(text).map_err(|e| format!("Failed to parse news feed: {}", e))? {
+            JsonFeed::Items(items) | JsonFeed::Wrapped { items } => items,
+        }
+    };
+    // Only web links are opened from the cards
+    for item in &mut items {
+        item.link = item.link.take().filter(|l| l.starts_with("https://") || l.starts_with("http://"));
+    }
+    items.truncate(MAX_ITEMS);
+    Ok(items)
+}
+
+/// The news of the default pack, empty when there's no feed or nothing can be loaded.
+pub async fn load(http_client: &CachedHttpClient) -> Vec<NewsItem> {
+    if crate::offline::is_enabled() {
+        return Vec::new();
+    }
+    let manifest = match crate::universal::load_universal_manifest(http_client, None).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            debug!("No news, the manifest didn't load: {}", e);
+            return Vec::new();
+        }
+    };
+    let Some(url) = manifest.news_feed else { return Vec::new() };
+    let result = async {
+        let mut response = http_client.get_async(url.as_str()).await.map_err(|e| e.to_string())?;
+        if response.status() != StatusCode::OK {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        let text = response.text().await.map_err(|e| e.to_string())?;
+        parse(&text)
+    }
+    .await;
+    match result {
+        Ok(items) => items,
+        Err(e) => {
+            warn!("Failed to load news from {}: {}", url, e);
+            Vec::new()
+        }
+    }
+}
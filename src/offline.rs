@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use isahc::http::HeaderMap;
+use isahc::{AsyncBody, Response};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// "Work offline" mode. Jars, packs and remote include zips are already kept in the artifact cache,
+// what's missing for an install without network are the small documents around them: manifests,
+// branch lists, changelogs and the include files on GitHub. Every successful GET through
+// CachedHttpClient is kept on disk, and while working offline requests are answered from there.
+// The ETag and Last-Modified of a kept document are stored next to it, so online requests for it
+// can be conditional: a 304 answer is served from the kept copy and doesn't use up GitHub quota.
+
+const DOCUMENTS_DIR: &str = "documents";
+
+// Mirrors config.network.work_offline, set at startup and whenever preferences are saved
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn configure(enabled: bool) {
+    if enabled != ENABLED.swap(enabled, Ordering::Relaxed) {
+        info!("Working {}", if enabled { "offline" } else { "online" });
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn document_path(url: &str) -> PathBuf {
+    let digest = Sha256::digest(url.as_bytes());
+    let hash: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    crate::artifact_cache::default_cache_dir().join(DOCUMENTS_DIR).join(hash)
+}
+
+/// Keeps the body of a successful response for later offline use. Failures only cost offline support.
+pub fn remember(url: &str, bytes: &[u8]) {
+    let path = document_path(url);
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| {
+            // Written next to it first, a half written document would be served as the real one
+            let tmp = path.with_extension(format!("tmp-{}", std::process::id()));
+            fs::write(&tmp, bytes)?;
+            fs::rename(&tmp, &path)
+        });
+    if let Err(e) = result {
+        warn!("Failed to keep {} for offline use: {}", url, e);
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+fn validators_path(url: &str) -> PathBuf {
+    document_path(url).with_extension("validators")
+}
+
+/// Keeps the ETag and Last-Modified of a response whose body was just remembered.
+pub fn remember_validators(url: &str, headers: &HeaderMap) {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(|v| v.to_string());
+    let validators = Validators {
+        etag: header("etag"),
+        last_modified: header("last-modified"),
+    };
+    let path = validators_path(url);
+    if validators == Validators::default() {
+        // A stale one would make the server answer 304 for a body we don't have anymore
+        let _ = fs::remove_file(&path);
+        return;
+    }
+    let result = serde_json::to_vec(&validators)
+        .map_err(std::io::Error::from)
+        .and_then(|json| fs::write(&path, json));
+    if let Err(e) = result {
+        debug!("Failed to keep the validators of {}: {}", url, e);
+    }
+}
+
+/// The validators of a kept document, None when there is no document to fall back to.
+pub fn validators(url: &str) -> Option<Validators> {
+    if !has_document(url) {
+        return None;
+    }
+    serde_json::from_slice(&fs::read(validators_path(url)).ok()?).ok()
+}
+
+pub fn has_document(url: &str) -> bool {
+    document_path(url).is_file()
+}
+
+pub fn recall_bytes(url: &str) -> Option<Vec<u8>> {
+    fs::read(document_path(url)).ok()
+}
+
+/// Answers a GET from the kept documents, like it was received just now.
+pub fn recall(url: &str) -> Result<Response<AsyncBody>, isahc::Error> {
+    match recall_bytes(url) {
+        Some(bytes) => {
+            debug!("Answering {} from the offline cache", url);
+            Ok(Response::builder()
+                .status(200)
+                .body(AsyncBody::from(bytes))
+                .expect("Offline response is always valid"))
+        }
+        None => Err(isahc::Error::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Working offline and {} was never downloaded", url),
+        ))),
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info, warn};
+
+use crate::installation::Installation;
+use crate::universal::UniversalManifest;
+use crate::{CachedHttpClient, Downloadable, Manifest};
+
+// Some optional content is too big to download on every install just in case, the music resource
+// pack alone is hundreds of MB. Components marked on_demand are downloaded as soon as they're turned
+// on in an installed pack and deleted as soon as they're turned off, without a full update. The
+// local manifest.json is kept in sync so the next update treats them like everything else.
+
+fn read_local_manifest(path: &Path) -> Result<Manifest, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn write_local_manifest(path: &Path, manifest: &Manifest) -> Result<(), String> {
+    let json = serde_json::to_string(manifest).map_err(|e| format!("Failed to serialize local manifest: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn with_path<T: Downloadable>(item: &T, path: Option<PathBuf>) -> T {
+    T::new(
+        item.get_name().to_owned(),
+        item.get_source().to_owned(),
+        item.get_location().to_owned(),
+        item.get_version().to_owned(),
+        path,
+        item.get_id().to_owned(),
+        item.get_authors().to_owned(),
+    )
+    .with_mirrors(item.get_mirrors().clone())
+}
+
+// Downloads the remote version of `id` into its section, returns the size or None if it isn't in this section
+async fn install_item<T: Downloadable + Sync>(
+    installed: &mut Vec<T>,
+    remote: &[T],
+    id: &str,
+    modpack_root: &Path,
+    loader_type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<Option<u64>, String> {
+    let Some(item) = remote.iter().find(|item| item.get_id() == id) else {
+        return Ok(None);
+    };
+    let (path, _) = crate::mirrors::download(item, modpack_root, loader_type, http_client)
+        .await
+        .map_err(|e| e.to_string())?;
+    let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let entry = with_path(item, Some(path));
+    match installed.iter().position(|i| i.get_name() == item.get_name()) {
+        Some(index) => {
+            // An older version that's still around is replaced
+            if let Some(old) = installed[index].get_path() {
+                if Some(old) != entry.get_path().as_ref() {
+                    let _ = fs::remove_file(old);
+                }
+            }
+            installed[index] = entry;
+        }
+        None => installed.push(entry),
+    }
+    Ok(Some(size))
+}
+
+// Deletes every installed file of `id` in this section and its copy in the download cache, returns
+// the bytes freed
+fn remove_item<T: Downloadable>(installed: &mut [T], id: &str, loader_type: &str, item_type: &str) -> u64 {
+    let mut freed = 0;
+    for item in installed.iter_mut().filter(|i| i.get_id() == id) {
+        let key = crate::artifact_cache::key(item.get_source(), item.get_location(), item.get_version(), loader_type, item_type);
+        freed += crate::artifact_cache::evict(&key);
+        let Some(path) = item.get_path().clone() else { continue };
+        let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        match fs::remove_file(&path) {
+            Ok(_) => freed += size,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove {}: {}", path.display(), e),
+        }
+        *item = with_path(item, None);
+    }
+    freed
+}
+
+fn local_paths(installation: &Installation) -> Result<(PathBuf, PathBuf), String> {
+    let launcher = crate::get_launcher(&installation.launcher_type)?;
+    let modpack_root = crate::get_modpack_root(&launcher, &installation.id);
+    let manifest_path = modpack_root.join("manifest.json");
+    Ok((modpack_root, manifest_path))
+}
+
+// The feature is now on disk (or gone), so it counts as installed rather than as a pending change
+fn record_feature(installation: &mut Installation, id: &str, enabled: bool) -> Result<(), String> {
+    for features in [&mut installation.enabled_features, &mut installation.installed_features] {
+        features.retain(|f| f != id);
+        if enabled {
+            features.push(id.to_string());
+        }
+    }
+    if !installation.pending_features.is_empty() {
+        installation.pending_features.retain(|f| f != id);
+        if enabled {
+            installation.pending_features.push(id.to_string());
+        }
+    }
+    installation.save()
+}
+
+/// Downloads an on-demand component straight into the installed pack, returns its size on disk.
+pub async fn install(
+    http_client: &CachedHttpClient,
+    installation: &mut Installation,
+    universal_manifest: &UniversalManifest,
+    id: &str,
+) -> Result<u64, String> {
+    if !installation.installed {
+        return Err("The installation isn't installed yet".to_string());
+    }
+    let (modpack_root, manifest_path) = local_paths(installation)?;
+    let mut local = read_local_manifest(&manifest_path)?;
+    let mut features = local.enabled_features.clone();
+    if !features.iter().any(|f| f == id) {
+        features.push(id.to_string());
+    }
+    let universal_manifest = installation.resolve_manifest(universal_manifest)?;
+    let remote = crate::universal::universal_to_manifest(&universal_manifest, features.clone());
+    let loader_type = remote.loader.r#type.clone();
+
+    info!("Installing on-demand component '{}' into {}", id, modpack_root.display());
+    let size = match install_item(&mut local.mods, &remote.mods, id, &modpack_root, &loader_type, http_client).await? {
+        Some(size) => size,
+        None => match install_item(&mut local.shaderpacks, &remote.shaderpacks, id, &modpack_root, &loader_type, http_client).await? {
+            Some(size) => size,
+            None => install_item(&mut local.resourcepacks, &remote.resourcepacks, id, &modpack_root, &loader_type, http_client)
+                .await?
+                .ok_or_else(|| format!("Component '{}' isn't in the manifest", id))?,
+        },
+    };
+    local.enabled_features = features;
+    write_local_manifest(&manifest_path, &local)?;
+    record_feature(installation, id, true)?;
+    debug!("Installed on-demand component '{}' ({} bytes)", id, size);
+    Ok(size)
+}
+
+/// Deletes an on-demand component from the installed pack, returns the bytes freed.
+pub fn remove(installation: &mut Installation, id: &str) -> Result<u64, String> {
+    let (_, manifest_path) = local_paths(installation)?;
+    let mut freed = 0;
+    // Never installed is fine, there's just nothing to free
+    if installation.installed && manifest_path.exists() {
+        let mut local = read_local_manifest(&manifest_path)?;
+        let loader_type = local.loader.r#type.clone();
+        freed += remove_item(&mut local.mods, id, &loader_type, "mod");
+        freed += remove_item(&mut local.shaderpacks, id, &loader_type, "shaderpack");
+        freed += remove_item(&mut local.resourcepacks, id, &loader_type, "resourcepack");
+        local.enabled_features.retain(|f| f != id);
+        write_local_manifest(&manifest_path, &local)?;
+    }
+    record_feature(installation, id, false)?;
+    info!("Removed on-demand component '{}', freed {} bytes", id, freed);
+    Ok(freed)
+}
+
+fn collect_paths<T: Downloadable>(items: &[T], id: &str, paths: &mut Vec<PathBuf>) {
+    paths.extend(items.iter().filter(|i| i.get_id() == id).filter_map(|i| i.get_path().clone()));
+}
+
+/// Size of what's installed for `id`, None when it isn't on disk.
+pub fn installed_size(installation: &Installation, id: &str) -> Option<u64> {
+    let (_, manifest_path) = local_paths(installation).ok()?;
+    let local = read_local_manifest(&manifest_path).ok()?;
+    let mut paths = Vec::new();
+    collect_paths(&local.mods, id, &mut paths);
+    collect_paths(&local.shaderpacks, id, &mut paths);
+    collect_paths(&local.resourcepacks, id, &mut paths);
+    let sizes: Vec<u64> = paths.iter().filter_map(|p| fs::metadata(p).ok()).map(|m| m.len()).collect();
+    if sizes.is_empty() {
+        None
+    } else {
+        Some(sizes.iter().sum())
+    }
+}
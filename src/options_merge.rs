@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info};
+
+use crate::installation::get_installations_dir;
+
+// options.txt used to be written over on every update, taking the player's keybinds, FOV and
+// volume with it. Now only the keys the pack's copy lists are applied: a key the player never
+// touched follows the pack, one they changed keeps their value unless the include lists it in
+// `force_keys`. Whether a value was changed is decided against the pack's copy from the previous
+// install, kept per installation. Lines the pack doesn't list are never touched.
+
+const TEMPLATES_DIR: &str = "options-templates";
+
+/// The separator of the game option files the merge handles, None for other files.
+pub fn separator(location: &str) -> Option<char> {
+    let name = location.rsplit('/').next().unwrap_or(location);
+    match name {
+        "options.txt" | "optionsof.txt" => Some(':'),
+        "optionsshaders.txt" => Some('='),
+        _ => None,
+    }
+}
+
+fn parse(content: &str, separator: char) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once(separator))
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .collect()
+}
+
+fn value_of<'a>(pairs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+}
+
+fn template_path(installation_id: &str, location: &str) -> PathBuf {
+    get_installations_dir()
+        .join(installation_id)
+        .join(TEMPLATES_DIR)
+        .join(location.replace(['/', '\\'], "_"))
+}
+
+/// Merges the pack's `template` into the option file at `target`, see the top of the file.
+/// Returns how many values were written.
+pub fn apply(
+    installation_id: &str,
+    location: &str,
+    target: &Path,
+    template: &[u8],
+    force_keys: &[String],
+) -> Result<usize, String> {
+    let separator = separator(location).ok_or_else(|| format!("{} isn't a game option file", location))?;
+    let template = String::from_utf8_lossy(template).into_owned();
+    let wanted = parse(&template, separator);
+
+    let current = match fs::read_to_string(target) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read {}: {}", target.display(), e)),
+    };
+    let previous_path = template_path(installation_id, location);
+    let previous = fs::read_to_string(&previous_path)
+        .map(|content| parse(&content, separator))
+        .unwrap_or_default();
+
+    let (merged, written) = match &current {
+        // First install, the pack's copy as it is
+        None => (template.clone(), wanted.len()),
+        Some(content) => {
+            let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+            let mut written = 0;
+            for (key, value) in &wanted {
+                let line = format!("{}{}{}", key, separator, value);
+                let existing = lines
+                    .iter_mut()
+                    .find(|l| l.split_once(separator).map_or(false, |(k, _)| k.trim() == key));
+                match existing {
+                    Some(existing) => {
+                        let Some((_, current_value)) = existing.split_once(separator) else { continue };
+                        let untouched = value_of(&previous, key) == Some(current_value);
+                        let forced = force_keys.iter().any(|k| k == key);
+                        if current_value != value && (untouched || forced) {
+                            *existing = line;
+                            written += 1;
+                        } else if current_value != value {
+                            debug!("Keeping the player's {} in {}", key, location);
+                        }
+                    }
+                    None => {
+                        lines.push(line);
+                        written += 1;
+                    }
+                }
+            }
+            (lines.join("\n") + "\n", written)
+        }
+    };
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(target, merged).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    if let Some(parent) = previous_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&previous_path, &template).map_err(|e| format!("Failed to write {}: {}", previous_path.display(), e))?;
+    info!("Merged {} of the pack's {} value(s) into {}", written, wanted.len(), target.display());
+    Ok(written)
+}
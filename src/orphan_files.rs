@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info, warn};
+
+use crate::installation::Installation;
+use crate::{get_launcher, get_modpack_root, Manifest};
+
+// Turning features off removes their files, but jars from older installer versions, renamed
+// downloads and things dropped in by hand stay in the game folder forever. This lists what in the
+// component folders the local manifest doesn't know about. Nothing is deleted on its own, the GUI
+// shows the list first since some of it may be mods the user added on purpose.
+
+pub const SCANNED_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanFile {
+    pub path: PathBuf,
+    // e.g. "mods/old-sodium.jar"
+    pub relative: String,
+    pub size: u64,
+}
+
+fn relative_name(root: &Path, path: &Path) -> Option<String> {
+    Some(path.strip_prefix(root).ok()?.to_string_lossy().replace('\\', "/"))
+}
+
+// Everything the local manifest says the installer put into the game folder
+fn referenced(root: &Path, manifest: &Manifest) -> HashSet<String> {
+    let mut paths: Vec<&Path> = Vec::new();
+    paths.extend(manifest.mods.iter().filter_map(|m| m.path.as_deref()));
+    paths.extend(manifest.shaderpacks.iter().filter_map(|s| s.path.as_deref()));
+    paths.extend(manifest.resourcepacks.iter().filter_map(|r| r.path.as_deref()));
+    let mut referenced: HashSet<String> = paths.into_iter().filter_map(|p| relative_name(root, p)).collect();
+    for included in manifest.included_files.iter().flat_map(|i| i.values()) {
+        referenced.extend(included.files.iter().filter_map(|f| relative_name(root, Path::new(f))));
+    }
+    referenced
+}
+
+fn is_referenced(relative: &str, is_dir: bool, referenced: &HashSet<String>) -> bool {
+    if referenced.contains(relative) {
+        return true;
+    }
+    if is_dir {
+        let prefix = format!("{}/", relative);
+        return referenced.iter().any(|r| r.starts_with(&prefix));
+    }
+    // Iris and Optifine keep a shaderpack's settings in "<pack>.txt" next to it
+    if let Some(pack) = relative.strip_prefix("shaderpacks/").and_then(|name| name.strip_suffix(".txt")) {
+        return referenced.contains(&format!("shaderpacks/{}", pack));
+    }
+    false
+}
+
+fn entry_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        crate::backup::calculate_directory_size(path).unwrap_or(0)
+    } else {
+        path.metadata().map(|m| m.len()).unwrap_or(0)
+    }
+}
+
+/// Top level entries of the component folders that aren't part of the installed pack.
+pub fn find(root: &Path, manifest: &Manifest) -> Result<Vec<OrphanFile>, String> {
+    let referenced = referenced(root, manifest);
+    let mut orphans = Vec::new();
+    for dir in SCANNED_DIRS {
+        let dir = root.join(dir);
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Hidden files belong to other tools, .part files to downloads that can still resume
+            if name.starts_with('.') || name.ends_with(".part") {
+                continue;
+            }
+            let Some(relative) = relative_name(root, &path) else { continue };
+            if is_referenced(&relative, path.is_dir(), &referenced) {
+                continue;
+            }
+            orphans.push(OrphanFile { size: entry_size(&path), path, relative });
+        }
+    }
+    orphans.sort_by(|a, b| a.relative.cmp(&b.relative));
+    debug!("{} unreferenced file(s) in {}", orphans.len(), root.display());
+    Ok(orphans)
+}
+
+/// Same as find, for an installed installation.
+pub fn find_for_installation(installation: &Installation) -> Result<Vec<OrphanFile>, String> {
+    if !installation.installed {
+        return Ok(Vec::new());
+    }
+    let launcher = get_launcher(&installation.launcher_type)?;
+    let root = get_modpack_root(&launcher, &installation.id);
+    let manifest_path = root.join("manifest.json");
+    let manifest: Manifest = serde_json::from_str(
+        &fs::read_to_string(&manifest_path).map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?,
+    )
+    .map_err(|e| format!("Failed to parse {}: {}", manifest_path.display(), e))?;
+    find(&root, &manifest)
+}
+
+/// Deletes the picked orphans, returns how many were removed.
+pub fn remove(orphans: &[OrphanFile]) -> Result<usize, String> {
+    let mut removed = 0;
+    let mut failed = Vec::new();
+    for orphan in orphans {
+        let result = if orphan.path.is_dir() {
+            fs::remove_dir_all(&orphan.path)
+        } else {
+            fs::remove_file(&orphan.path)
+        };
+        match result {
+            Ok(_) => removed += 1,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                warn!("Failed to remove {}: {}", orphan.path.display(), e);
+                failed.push(orphan.relative.clone());
+            }
+        }
+    }
+    info!("Removed {} unused file(s)", removed);
+    if failed.is_empty() {
+        Ok(removed)
+    } else {
+        Err(format!("Removed {} file(s), these couldn't be deleted: {}", removed, failed.join(", ")))
+    }
+}
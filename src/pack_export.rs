@@ -0,0 +1,177 @@
+use std::fs::{self, File};
+use std::io::{self, Seek, Write};
+use std::path::Path;
+
+use log::{debug, info};
+use serde_json::json;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::installation::Installation;
+use crate::{get_launcher, get_modpack_root, Manifest};
+
+// Turns an installed pack into something other launchers import directly, for machines that don't
+// have the installer. Both formats carry the game folder as it is, so they work offline and keep
+// the user's config: a Modrinth .mrpack lists no downloads and puts every file under overrides/,
+// a MultiMC/Prism instance zip is instance.cfg and mmc-pack.json next to the .minecraft folder.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Mrpack,
+    MultiMC,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Mrpack => "mrpack",
+            ExportFormat::MultiMC => "zip",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Mrpack => "Modrinth modpack",
+            ExportFormat::MultiMC => "MultiMC/Prism instance",
+        }
+    }
+}
+
+// Installer bookkeeping and things that only matter on this machine
+const SKIPPED: &[&str] = &[
+    "manifest.json",
+    "installation.json",
+    "install-journal.json",
+    "launcher_profiles.json",
+    "usercache.json",
+    "usernamecache.json",
+    // The profile icon kept for desktop shortcuts
+    "icon.png",
+    "backups",
+    "logs",
+    "crash-reports",
+    "screenshots",
+    ".cache",
+];
+
+fn is_skipped(relative: &Path) -> bool {
+    let name = relative.to_string_lossy();
+    let top = relative
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .unwrap_or_default();
+    SKIPPED.contains(&top.as_str()) || name.ends_with(".part") || name.ends_with(".tmp") || name.ends_with(".lock")
+}
+
+fn zip_options(path: &str) -> zip::write::FileOptions<'static, ()> {
+    // Jars and zips are compressed already
+    let method = if path.ends_with(".jar") || path.ends_with(".zip") {
+        CompressionMethod::Stored
+    } else {
+        CompressionMethod::Deflated
+    };
+    zip::write::FileOptions::<()>::default()
+        .compression_method(method)
+        .large_file(true)
+}
+
+fn add_bytes<W: Write + Seek>(zip: &mut ZipWriter<W>, name: &str, bytes: &[u8]) -> Result<(), String> {
+    zip.start_file(name, zip_options(name))
+        .map_err(|e| format!("Failed to add {} to the export: {}", name, e))?;
+    zip.write_all(bytes)
+        .map_err(|e| format!("Failed to add {} to the export: {}", name, e))
+}
+
+// Every file of the game folder under `prefix`, returns how many were added
+fn add_game_folder<W: Write + Seek>(zip: &mut ZipWriter<W>, root: &Path, prefix: &str) -> Result<usize, String> {
+    let mut count = 0;
+    for path in crate::hashing::walk_files(root)? {
+        let Ok(relative) = path.strip_prefix(root) else { continue };
+        if is_skipped(relative) {
+            continue;
+        }
+        // Zip paths always use forward slashes
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let name = format!("{}{}", prefix, relative);
+        let mut file = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        zip.start_file(name.as_str(), zip_options(&name))
+            .map_err(|e| format!("Failed to add {} to the export: {}", name, e))?;
+        io::copy(&mut file, zip).map_err(|e| format!("Failed to add {} to the export: {}", name, e))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+// modrinth.index.json, the loader goes in as a dependency so the importing launcher installs it
+fn mrpack_index(installation: &Installation, manifest: &Manifest) -> Result<String, String> {
+    let loader_key = match manifest.loader.r#type.as_str() {
+        "fabric" => "fabric-loader",
+        "quilt" => "quilt-loader",
+        "forge" => "forge",
+        "neoforge" => "neoforge",
+        other => return Err(format!("Modrinth packs don't support the {} loader", other)),
+    };
+    let index = json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": manifest.modpack_version,
+        "name": installation.name,
+        "summary": manifest.subtitle,
+        "files": [],
+        "dependencies": {
+            "minecraft": manifest.loader.minecraft_version,
+            loader_key: manifest.loader.version,
+        },
+    });
+    serde_json::to_string_pretty(&index).map_err(|e| format!("Failed to write modrinth.index.json: {}", e))
+}
+
+/// Writes the installed game folder of `installation` to `dest`, returns how many files it holds.
+pub fn export(installation: &Installation, format: ExportFormat, dest: &Path) -> Result<usize, String> {
+    if !installation.installed {
+        return Err("Install it first, there is nothing to export yet".to_string());
+    }
+    let launcher = get_launcher(&installation.launcher_type)?;
+    let root = get_modpack_root(&launcher, &installation.id);
+    let manifest_path = root.join("manifest.json");
+    let manifest: Manifest = serde_json::from_str(
+        &fs::read_to_string(&manifest_path).map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?,
+    )
+    .map_err(|e| format!("Failed to parse {}: {}", manifest_path.display(), e))?;
+
+    // Written next to the destination first, a failed export doesn't leave half a file behind
+    let tmp = dest.with_extension("tmp");
+    let file = File::create(&tmp).map_err(|e| format!("Failed to create {}: {}", tmp.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let written = (|| {
+        let count = match format {
+            ExportFormat::Mrpack => {
+                add_bytes(&mut zip, "modrinth.index.json", mrpack_index(installation, &manifest)?.as_bytes())?;
+                add_game_folder(&mut zip, &root, "overrides/")?
+            }
+            ExportFormat::MultiMC => {
+                let mut instance_manifest = manifest.clone();
+                instance_manifest.name = installation.name.clone();
+                let pack = crate::mmc_pack(&manifest.loader)
+                    .ok_or_else(|| format!("MultiMC doesn't support the {} loader", manifest.loader.r#type))?;
+                let pack = serde_json::to_string_pretty(&pack).map_err(|e| format!("Failed to write mmc-pack.json: {}", e))?;
+                add_bytes(&mut zip, "instance.cfg", crate::mmc_instance_cfg(&instance_manifest).as_bytes())?;
+                add_bytes(&mut zip, "mmc-pack.json", pack.as_bytes())?;
+                add_game_folder(&mut zip, &root, ".minecraft/")?
+            }
+        };
+        zip.finish().map_err(|e| format!("Failed to finish the export: {}", e))?;
+        Ok::<usize, String>(count)
+    })();
+    let count = match written {
+        Ok(count) => count,
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            return Err(e);
+        }
+    };
+    fs::rename(&tmp, dest).map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    debug!("Exported {} files of {}", count, root.display());
+    info!("Exported '{}' as a {} to {}", installation.name, format.label(), dest.display());
+    Ok(count)
+}
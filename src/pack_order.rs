@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use log::{debug, info};
+
+// Resource pack order. Packs higher up win where they change the same textures, so the pack lists
+// a default order (`resourcepack_order` in the manifest) and users can rearrange it per
+// installation. Orders here are component ids, highest priority first. options.txt stores
+// `resourcePacks` the other way around, lowest first, as "file/<name>" entries next to built-in
+// ones like "vanilla" and "fabric".
+
+const OPTIONS_FILE: &str = "options.txt";
+const KEY: &str = "resourcePacks";
+
+/// Installed resource packs as (component id, file name), in manifest order. Read from the
+/// manifest.json the install leaves in the game directory, only packs that were downloaded have a
+/// path there.
+pub fn installed_packs(game_dir: &Path) -> Result<Vec<(String, String)>, String> {
+    let path = game_dir.join("manifest.json");
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let manifest: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    Ok(manifest["resourcepacks"]
+        .as_array()
+        .map(|packs| {
+            packs
+                .iter()
+                .filter_map(|pack| {
+                    let id = pack["id"].as_str()?;
+                    let file = Path::new(pack["path"].as_str()?).file_name()?.to_string_lossy().to_string();
+                    Some((id.to_string(), file))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// `available` ids arranged by the user's order, then the pack's, then whatever neither mentions in
+/// the order given.
+pub fn resolve(available: &[String], default_order: &[String], user_order: &[String]) -> Vec<String> {
+    let mut order: Vec<String> = Vec::new();
+    for id in user_order.iter().chain(default_order).chain(available) {
+        if available.contains(id) && !order.contains(id) {
+            order.push(id.clone());
+        }
+    }
+    order
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    serde_json::from_str(value.trim()).unwrap_or_default()
+}
+
+/// Writes `files` (highest priority first) into options.txt's resource pack list. Entries that
+/// aren't one of these files, built-in packs and ones the user added themselves, keep their place
+/// below them. Returns whether the file changed.
+pub fn write_order(game_dir: &Path, files: &[String]) -> Result<bool, String> {
+    let path = game_dir.join(OPTIONS_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let index = lines
+        .iter()
+        .position(|line| line.split_once(':').map_or(false, |(key, _)| key.trim() == KEY));
+    let current = index
+        .and_then(|i| lines[i].split_once(':').map(|(_, value)| parse_list(value)))
+        // The game's own default when it never wrote the key
+        .unwrap_or_else(|| vec!["vanilla".to_string()]);
+
+    let ours: Vec<String> = files.iter().rev().map(|file| format!("file/{}", file)).collect();
+    let mut list: Vec<String> = current.into_iter().filter(|entry| !ours.contains(entry)).collect();
+    list.extend(ours);
+
+    let line = format!(
+        "{}:{}",
+        KEY,
+        serde_json::to_string(&list).map_err(|e| format!("Failed to write the resource pack list: {}", e))?
+    );
+    match index {
+        Some(i) if lines[i] == line => return Ok(false),
+        Some(i) => lines[i] = line,
+        None => lines.push(line),
+    }
+    fs::write(&path, lines.join("\n") + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    debug!("Resource pack order in {}: {:?}", path.display(), list);
+    Ok(true)
+}
+
+/// Puts the installed packs of `game_dir` into options.txt in the resolved order, see resolve().
+/// Packs of disabled features are left where they are, the game drops entries whose file is gone.
+pub fn apply(game_dir: &Path, default_order: &[String], user_order: &[String]) -> Result<bool, String> {
+    let installed = installed_packs(game_dir)?;
+    let files: HashMap<&str, &str> = installed.iter().map(|(id, file)| (id.as_str(), file.as_str())).collect();
+    let available: Vec<String> = installed.iter().map(|(id, _)| id.clone()).collect();
+    let ordered: Vec<String> = resolve(&available, default_order, user_order)
+        .iter()
+        .filter_map(|id| files.get(id.as_str()).map(|file| file.to_string()))
+        .collect();
+
+    let changed = write_order(game_dir, &ordered)?;
+    if changed {
+        info!("Set the order of {} resource pack(s) in {}", ordered.len(), game_dir.display());
+    }
+    Ok(changed)
+}
+
+/// apply() for an installation outside of an install, when its order was changed in the settings.
+pub fn apply_to_installation(installation: &crate::installation::Installation, default_order: &[String]) -> Result<bool, String> {
+    let launcher = crate::get_launcher(&installation.launcher_type)?;
+    let game_dir = crate::get_modpack_root(&launcher, &installation.id);
+    apply(&game_dir, default_order, &installation.resourcepack_order)
+}
@@ -0,0 +1,269 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures::AsyncReadExt;
+use isahc::http::{HeaderMap, StatusCode};
+use isahc::{AsyncBody, Request, Response};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use sha2::{Digest, Sha256};
+
+use crate::download_progress::{self, DownloadProgress};
+use crate::{download_queue, download_schedule, http_mock, offline, retry, CachedHttpClient, DownloadError, ATTEMPTS};
+
+// Big downloads (ddl jars, remote include zips) are written to a .part file next to their target.
+// When the connection drops the next attempt asks for the rest with a Range header instead of
+// starting over, and a .part left behind by an interrupted install is picked up the same way.
+
+const PART_EXTENSION: &str = "part";
+const CHUNK_SIZE: usize = 64 * 1024;
+// How often a running download checks whether it should pause for a metered connection
+const PAUSE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+lazy_static! {
+    // .part files some download in this process is writing to right now
+    static ref ACTIVE_PARTS: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
+    // Download name -> bytes received and expected size, for the progress bar of big downloads
+    static ref BYTE_PROGRESS: Mutex<HashMap<String, (u64, Option<u64>)>> = Mutex::new(HashMap::new());
+}
+
+/// Bytes received so far and the expected total of a running download, None once it finished.
+pub fn byte_progress(name: &str) -> Option<(u64, Option<u64>)> {
+    BYTE_PROGRESS.lock().ok()?.get(name).copied()
+}
+
+fn report_progress(name: &str, received: u64, total: Option<u64>) {
+    if let Ok(mut progress) = BYTE_PROGRESS.lock() {
+        progress.insert(name.to_string(), (received, total));
+    }
+    download_progress::publish(DownloadProgress { name: name.to_string(), received, total, finished: false, extracting: false });
+}
+
+// Clears the progress of a download however it ends
+struct ProgressGuard<'a> {
+    name: &'a str,
+}
+
+impl Drop for ProgressGuard<'_> {
+    fn drop(&mut self) {
+        let last = BYTE_PROGRESS.lock().ok().and_then(|mut progress| progress.remove(self.name));
+        let (received, total) = last.unwrap_or((0, None));
+        download_progress::publish(DownloadProgress { name: self.name.to_string(), received, total, finished: true, extracting: false });
+    }
+}
+
+/// Stable .part name for `url` in `dir`, so a retry or the next install finds it again.
+pub fn part_path(dir: &Path, url: &str) -> PathBuf {
+    let digest = Sha256::digest(url.as_bytes());
+    let hash: String = digest.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    dir.join(format!(".{}.{}", hash, PART_EXTENSION))
+}
+
+// Two downloads of the same url into the same folder must not append to the same file, the
+// second one gets a private .part that isn't resumed later.
+struct PartGuard {
+    path: PathBuf,
+}
+
+impl PartGuard {
+    fn claim(preferred: &Path) -> PartGuard {
+        let mut active = ACTIVE_PARTS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut path = preferred.to_path_buf();
+        let mut n = 1;
+        while active.contains(&path) {
+            path = preferred.with_extension(format!("{}-{}-{}", PART_EXTENSION, std::process::id(), n));
+            n += 1;
+        }
+        active.insert(path.clone());
+        PartGuard { path }
+    }
+}
+
+impl Drop for PartGuard {
+    fn drop(&mut self) {
+        if let Ok(mut active) = ACTIVE_PARTS.lock() {
+            active.remove(&self.path);
+        }
+    }
+}
+
+// "bytes 100-199/200" -> 100
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get("content-range")?.to_str().ok()?;
+    let range = value.strip_prefix("bytes ")?;
+    range.split('-').next()?.trim().parse().ok()
+}
+
+fn paused_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Interrupted, "paused on a metered connection")
+}
+
+// Appends the body to `part`, whatever was received stays there when the connection drops or
+// the download pauses
+async fn write_body(resp: &mut Response<AsyncBody>, part: &Path, append: bool, name: &str) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(part)?;
+    let mut received = if append { file.metadata()?.len() } else { 0 };
+    let total = resp
+        .headers()
+        .get("content-length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(|len| len + received);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut last_pause_check = Instant::now();
+    loop {
+        if last_pause_check.elapsed() >= PAUSE_CHECK_INTERVAL {
+            last_pause_check = Instant::now();
+            if download_schedule::should_pause().await {
+                file.flush()?;
+                return Err(paused_error());
+            }
+        }
+        let read = resp.body_mut().read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        download_queue::QUEUE.throttle(read).await;
+        file.write_all(&buf[..read])?;
+        received += read as u64;
+        report_progress(name, received, total);
+    }
+    file.flush()
+}
+
+/// A finished download still in its .part file, the file name often depends on the headers.
+pub struct DownloadedPart {
+    pub path: PathBuf,
+    pub headers: HeaderMap,
+    // Held until the file is moved, a parallel download would otherwise resume the finished file
+    _guard: PartGuard,
+}
+
+impl DownloadedPart {
+    pub fn persist(self, target: &Path) -> std::io::Result<PathBuf> {
+        fs::rename(&self.path, target)?;
+        Ok(target.to_path_buf())
+    }
+}
+
+impl CachedHttpClient {
+    /// Downloads `url` into a .part file in `dir`, resuming what an earlier attempt left there.
+    pub async fn download_resumable(
+        &self,
+        name: &str,
+        url: &str,
+        dir: &Path,
+        headers: &[(&str, &str)],
+    ) -> Result<DownloadedPart, DownloadError> {
+        fs::create_dir_all(dir).map_err(|e| DownloadError::IoError(name.to_string(), e))?;
+        let guard = PartGuard::claim(&part_path(dir, url));
+        let part = guard.path.clone();
+        let _progress = ProgressGuard { name };
+
+        // Fixtures always hold the whole body
+        if let Some(replayed) = http_mock::replay("GET", url) {
+            let mut resp = replayed.map_err(|e| DownloadError::HttpError(name.to_string(), e))?;
+            if resp.status() != StatusCode::OK {
+                return Err(DownloadError::Non200StatusCode(name.to_string(), resp.status().as_u16()));
+            }
+            write_body(&mut resp, &part, false, name)
+                .await
+                .map_err(|e| DownloadError::IoError(name.to_string(), e))?;
+            return Ok(DownloadedPart { path: part, headers: resp.headers().clone(), _guard: guard });
+        }
+        if offline::is_enabled() {
+            return Err(DownloadError::NotCached(name.to_string()));
+        }
+        let _slot = download_queue::QUEUE.acquire().await;
+
+        let mut last_error = None;
+        let mut attempt = 0;
+        while attempt < ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(retry::delay(attempt)).await;
+            }
+            attempt += 1;
+            download_schedule::wait_until_allowed(name).await;
+            if let Err(e) = retry::check(url) {
+                return Err(DownloadError::HttpError(name.to_string(), e.into()));
+            }
+            let offset = part.metadata().map(|m| m.len()).unwrap_or(0);
+            let mut request = Request::get(url);
+            for (key, value) in headers {
+                request = request.header(*key, *value);
+            }
+            if offset > 0 {
+                debug!("Resuming '{}' at byte {}", name, offset);
+                request = request.header("Range", format!("bytes={}-", offset));
+            }
+            let request = request.body(()).expect("Download request is always valid");
+
+            let mut resp = match self.http_client.send_async(request).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    warn!("Failed to download '{}' (attempt {}/{}): {}", name, attempt, ATTEMPTS, e);
+                    retry::record_failure(url);
+                    last_error = Some(DownloadError::HttpError(name.to_string(), e));
+                    continue;
+                }
+            };
+
+            if resp.status().is_success() {
+                retry::record_success(url);
+            }
+            let append = match resp.status() {
+                StatusCode::PARTIAL_CONTENT if offset > 0 && content_range_start(resp.headers()) == Some(offset) => true,
+                StatusCode::PARTIAL_CONTENT => {
+                    // Not the range we asked for, start over
+                    let _ = fs::remove_file(&part);
+                    last_error = Some(DownloadError::Non200StatusCode(name.to_string(), 206));
+                    continue;
+                }
+                StatusCode::RANGE_NOT_SATISFIABLE => {
+                    // The file changed on the server or the .part is longer than it, start over
+                    debug!("Discarding stale partial download of '{}'", name);
+                    let _ = fs::remove_file(&part);
+                    last_error = Some(DownloadError::Non200StatusCode(name.to_string(), 416));
+                    continue;
+                }
+                status if retry::is_retryable_status(status.as_u16()) => {
+                    warn!("Got {} for '{}' (attempt {}/{})", status, name, attempt, ATTEMPTS);
+                    retry::record_failure(url);
+                    last_error = Some(DownloadError::Non200StatusCode(name.to_string(), status.as_u16()));
+                    continue;
+                }
+                // Also what servers without range support answer, the body is the whole file
+                StatusCode::OK => {
+                    if offset == 0 {
+                        resp = http_mock::record("GET", url, resp).await;
+                    }
+                    false
+                }
+                status => return Err(DownloadError::Non200StatusCode(name.to_string(), status.as_u16())),
+            };
+
+            match write_body(&mut resp, &part, append, name).await {
+                Ok(_) => return Ok(DownloadedPart { path: part, headers: resp.headers().clone(), _guard: guard }),
+                // Not a failure, the next round waits for an unmetered connection and resumes
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted && download_schedule::should_pause().await => {
+                    debug!("Pausing '{}' at byte {}", name, part.metadata().map(|m| m.len()).unwrap_or(0));
+                    attempt -= 1;
+                }
+                Err(e) => {
+                    warn!("Download of '{}' interrupted (attempt {}/{}): {}", name, attempt, ATTEMPTS, e);
+                    last_error = Some(DownloadError::IoError(name.to_string(), e));
+                }
+            }
+        }
+        Err(last_error.unwrap()) // ATTEMPTS is never 0
+    }
+}
@@ -0,0 +1,35 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Portable mode keeps everything the installer writes (config, logs, installations, webview data)
+// next to the executable, for running from a USB stick or without access to the OS config dir.
+// Enabled by a portable.flag file next to the binary or by passing --portable.
+
+pub const FLAG_FILE: &str = "portable.flag";
+pub const FLAG_ARG: &str = "--portable";
+
+static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+fn exe_dir() -> Option<PathBuf> {
+    env::current_exe().ok()?.parent().map(Path::to_path_buf)
+}
+
+fn detect(args: &[String]) -> Option<PathBuf> {
+    let dir = exe_dir()?;
+    if args.iter().any(|arg| arg == FLAG_ARG) || dir.join(FLAG_FILE).is_file() {
+        Some(dir)
+    } else {
+        None
+    }
+}
+
+/// Decides once at startup whether we're portable, has to run before anything touches the disk.
+pub fn init(args: &[String]) -> Option<&'static Path> {
+    ROOT.get_or_init(|| detect(args)).as_deref()
+}
+
+/// The executable's directory when running portable.
+pub fn root() -> Option<&'static Path> {
+    ROOT.get().and_then(|root| root.as_deref())
+}
@@ -86,6 +86,8 @@ impl Preset {
         
         if let Some(java_args) = &self.recommended_java_args {
             installation.java_args = java_args.clone();
+            // The preset's own arguments replace whatever JVM preset was picked
+            installation.jvm_preset = None;
             debug!("Updated Java args to '{}'", java_args);
         }
         
@@ -96,7 +98,20 @@ impl Preset {
 
 // Function to load presets from a URL - UPDATED to use new repository
 pub async fn load_presets(http_client: &CachedHttpClient, url: Option<&str>) -> Result<Vec<Preset>, ManifestError> {
-    let presets_url = url.unwrap_or("https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/presets.json");
+    // Developer mode, see dev_manifest.rs
+    if let Some(local) = crate::dev_manifest::read_local("presets.json") {
+        let presets_json = local.map_err(|e| ManifestError {
+            message: e,
+            error_type: ManifestErrorType::UnknownError,
+            file_name: "presets.json".to_string(),
+            raw_content: None,
+            location: None,
+        })?;
+        return crate::manifest_validation::parse::<PresetsContainer>(&presets_json, "presets.json")
+            .map(|container| container.presets);
+    }
+    let presets_url = crate::dev_manifest::resolve_url(url.unwrap_or("https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/presets.json"));
+    let presets_url = presets_url.as_str();
     debug!("Loading presets from: {}", presets_url);
     
     // Add retry logic for more reliability
@@ -124,6 +139,7 @@ pub async fn load_presets(http_client: &CachedHttpClient, url: Option<&str>) ->
                         error_type: ManifestErrorType::NetworkError,
                         file_name: "presets.json".to_string(),
                         raw_content: None,
+                        location: None,
                     });
                 }
                 
@@ -132,21 +148,8 @@ pub async fn load_presets(http_client: &CachedHttpClient, url: Option<&str>) ->
                     Ok(presets_json) => {
                         debug!("Received presets JSON of length: {}", presets_json.len());
                         
-                        // Store the raw JSON for debugging
-                        let raw_content = Some(presets_json.clone());
-                        
-                        // Try parsing as regular JSON first to catch syntax errors
-                        if let Err(json_err) = serde_json::from_str::<serde_json::Value>(&presets_json) {
-                            return Err(ManifestError {
-                                message: format!("Invalid JSON syntax: {}", json_err),
-                                error_type: ManifestErrorType::SyntaxError,
-                                file_name: "presets.json".to_string(),
-                                raw_content,
-                            });
-                        }
-                        
-                        // Parse the presets container
-                        match serde_json::from_str::<PresetsContainer>(&presets_json) {
+                        // Syntax and structure problems come back with where in the file they are
+                        match crate::manifest_validation::parse::<PresetsContainer>(&presets_json, "presets.json") {
                             Ok(container) => {
                                 debug!("Successfully loaded {} presets (version: {})", 
                                       container.presets.len(), container.version);
@@ -161,13 +164,7 @@ pub async fn load_presets(http_client: &CachedHttpClient, url: Option<&str>) ->
                             },
                             Err(e) => {
                                 error!("Failed to parse presets JSON: {}", e);
-                                
-                                return Err(ManifestError {
-                                    message: format!("Failed to parse presets: {}", e),
-                                    error_type: ManifestErrorType::DeserializationError,
-                                    file_name: "presets.json".to_string(),
-                                    raw_content,
-                                });
+                                return Err(e);
                             }
                         }
                     },
@@ -186,6 +183,7 @@ pub async fn load_presets(http_client: &CachedHttpClient, url: Option<&str>) ->
                             error_type: ManifestErrorType::NetworkError,
                             file_name: "presets.json".to_string(),
                             raw_content: None,
+                            location: None,
                         });
                     }
                 }
@@ -205,6 +203,7 @@ pub async fn load_presets(http_client: &CachedHttpClient, url: Option<&str>) ->
                     error_type: ManifestErrorType::NetworkError,
                     file_name: "presets.json".to_string(),
                     raw_content: None,
+                    location: None,
                 });
             }
         }
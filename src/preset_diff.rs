@@ -0,0 +1,96 @@
+use log::{debug, info};
+
+use crate::installation::Installation;
+use crate::preset::Preset;
+
+// When a preset gets a new preset_version the installation only learns that something changed.
+// This works out what exactly: which features the new version turns on or off compared to what
+// the user has selected right now. Features the user added themselves aren't listed as removals,
+// they were never the preset's, and ones the user turned off before are listed but not picked by
+// default. Whatever the user doesn't adopt is remembered as their own change to the preset.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetChange {
+    pub id: String,
+    // True when the new preset turns it on, false when it turns it off
+    pub enable: bool,
+    // The user changed this one by hand before, so the preset's choice isn't picked by default
+    pub user_override: bool,
+}
+
+impl PresetChange {
+    pub fn adopt_by_default(&self) -> bool {
+        !self.user_override
+    }
+}
+
+/// True when the installation's preset has a newer version than the one it was set up with.
+pub fn has_update(installation: &Installation, preset: &Preset) -> bool {
+    installation.base_preset_id.as_deref() == Some(preset.id.as_str())
+        && matches!(
+            (&preset.preset_version, &installation.base_preset_version),
+            (Some(new), Some(old)) if crate::compare_versions(new, old) == std::cmp::Ordering::Greater
+        )
+}
+
+/// What `preset` changes about `current`, enables first.
+pub fn diff(installation: &Installation, current: &[String], preset: &Preset) -> Vec<PresetChange> {
+    let mut changes: Vec<PresetChange> = preset.enabled_features.iter()
+        .filter(|id| *id != "default" && !current.contains(id))
+        .map(|id| PresetChange {
+            id: id.clone(),
+            enable: true,
+            user_override: installation.removed_features.contains(id),
+        })
+        .collect();
+    changes.extend(
+        current.iter()
+            .filter(|id| *id != "default" && !preset.enabled_features.contains(id) && !installation.custom_features.contains(id))
+            .map(|id| PresetChange { id: id.clone(), enable: false, user_override: false }),
+    );
+    debug!("Preset '{}' {:?} changes {} feature(s)", preset.id, preset.preset_version, changes.len());
+    changes
+}
+
+/// Applies the `adopted` changes to `current` and moves the installation to the new preset
+/// version. Returns the new selection; the installation still has to be saved.
+pub fn adopt(installation: &mut Installation, current: &[String], preset: &Preset, changes: &[PresetChange], adopted: &[String]) -> Vec<String> {
+    let mut features = current.to_vec();
+    for change in changes {
+        let is_adopted = adopted.contains(&change.id);
+        match (change.enable, is_adopted) {
+            (true, true) => {
+                if !features.contains(&change.id) {
+                    features.push(change.id.clone());
+                }
+                installation.removed_features.retain(|id| id != &change.id);
+            }
+            (false, true) => features.retain(|id| id != &change.id),
+            // Declined: the user keeps it off, which from now on counts as their own removal
+            (true, false) => {
+                if !installation.removed_features.contains(&change.id) {
+                    installation.removed_features.push(change.id.clone());
+                }
+            }
+            // Declined: kept on although the preset dropped it, that's a custom addition now
+            (false, false) => {
+                if !installation.custom_features.contains(&change.id) {
+                    installation.custom_features.push(change.id.clone());
+                }
+            }
+        }
+    }
+    info!(
+        "Adopted {} of {} change(s) of preset '{}' {:?}",
+        adopted.len(),
+        changes.len(),
+        preset.id,
+        preset.preset_version
+    );
+    installation.base_preset_version = preset.preset_version.clone();
+    installation.preset_update_available = false;
+    installation.enabled_features = features.clone();
+    installation.pending_features = features.clone();
+    installation.modified = true;
+    features
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info};
+use serde_json::Value as JsonValue;
+
+use crate::get_minecraft_folder;
+use crate::installation;
+
+// Vanilla launcher profiles can end up out of sync with our installations when launcher_profiles.json
+// was edited by hand or restored from a backup. This finds profiles whose gameDir points into our
+// installations folder but that we don't manage, so the user can adopt or remove them.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileIssueKind {
+    // Points at an existing installation but is stored under another key, launching the installation
+    // would create a second profile next to it
+    DuplicateKey { installation_id: String },
+    // Points at a folder with installed game files but no installation state
+    Unmanaged { installation_id: String },
+    // Points at a folder that no longer exists
+    MissingDir,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileIssue {
+    pub profile_key: String,
+    pub profile_name: String,
+    pub game_dir: PathBuf,
+    pub kind: ProfileIssueKind,
+}
+
+impl ProfileIssue {
+    pub fn description(&self) -> String {
+        match &self.kind {
+            ProfileIssueKind::DuplicateKey { .. } => "Duplicate profile for an installation managed by the installer".to_string(),
+            ProfileIssueKind::Unmanaged { .. } => "Installed modpack the installer doesn't know about".to_string(),
+            ProfileIssueKind::MissingDir => "Points at a folder that no longer exists".to_string(),
+        }
+    }
+
+    pub fn can_adopt(&self) -> bool {
+        !matches!(self.kind, ProfileIssueKind::MissingDir)
+    }
+}
+
+fn profiles_path() -> PathBuf {
+    get_minecraft_folder().join("launcher_profiles.json")
+}
+
+fn load_profiles() -> Result<Option<JsonValue>, String> {
+    let path = profiles_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read launcher profiles: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse launcher profiles: {}", e))
+}
+
+fn save_profiles(lp_obj: &JsonValue) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(lp_obj).map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+    fs::write(profiles_path(), json).map_err(|e| format!("Failed to write launcher profiles: {}", e))
+}
+
+// installations/<id>[/...] -> id
+fn installation_id_for(game_dir: &Path, installations_dir: &Path) -> Option<String> {
+    let relative = game_dir.strip_prefix(installations_dir).ok()?;
+    relative
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+}
+
+/// Profiles pointing into our installations folder that aren't the installation's own profile.
+pub fn scan() -> Result<Vec<ProfileIssue>, String> {
+    let Some(lp_obj) = load_profiles()? else { return Ok(Vec::new()) };
+    let Some(profiles) = lp_obj.get("profiles").and_then(|p| p.as_object()) else {
+        return Ok(Vec::new());
+    };
+    let installations_dir = installation::get_installations_dir();
+
+    let mut issues = Vec::new();
+    for (key, profile) in profiles {
+        let Some(game_dir) = profile.get("gameDir").and_then(|d| d.as_str()).map(PathBuf::from) else { continue };
+        let Some(installation_id) = installation_id_for(&game_dir, &installations_dir) else { continue };
+        let managed = installation::load_installation(&installation_id).is_ok();
+        if managed && *key == installation_id {
+            continue;
+        }
+
+        let kind = if !game_dir.is_dir() {
+            ProfileIssueKind::MissingDir
+        } else if managed {
+            ProfileIssueKind::DuplicateKey { installation_id }
+        } else if game_dir.join("manifest.json").is_file() {
+            ProfileIssueKind::Unmanaged { installation_id }
+        } else {
+            // An empty or half-deleted folder, nothing there worth adopting
+            ProfileIssueKind::MissingDir
+        };
+        issues.push(ProfileIssue {
+            profile_key: key.clone(),
+            profile_name: profile.get("name").and_then(|n| n.as_str()).unwrap_or(key).to_string(),
+            game_dir,
+            kind,
+        });
+    }
+    debug!("Found {} launcher profile issue(s)", issues.len());
+    Ok(issues)
+}
+
+/// Removes the profile from the launcher, game files are left alone.
+pub fn remove(issue: &ProfileIssue) -> Result<(), String> {
+    let Some(mut lp_obj) = load_profiles()? else { return Ok(()) };
+    if let Some(profiles) = lp_obj.get_mut("profiles").and_then(|p| p.as_object_mut()) {
+        if profiles.remove(&issue.profile_key).is_some() {
+            info!("Removed launcher profile '{}' ({})", issue.profile_name, issue.profile_key);
+            save_profiles(&lp_obj)?;
+        }
+    }
+    Ok(())
+}
+
+/// Makes the profile the installation's own, returns the installation id.
+pub fn adopt(issue: &ProfileIssue) -> Result<String, String> {
+    let installation_id = match &issue.kind {
+        ProfileIssueKind::DuplicateKey { installation_id } => installation_id.clone(),
+        ProfileIssueKind::Unmanaged { installation_id } => {
+            let manifest_path = issue.game_dir.join("manifest.json");
+            let content = fs::read_to_string(&manifest_path)
+                .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
+            let manifest: JsonValue = serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse {}: {}", manifest_path.display(), e))?;
+            crate::legacy_migration::adopt_installation(installation_id, &issue.game_dir, &manifest)?;
+            installation_id.clone()
+        }
+        ProfileIssueKind::MissingDir => return Err("The profile's folder no longer exists".to_string()),
+    };
+
+    let Some(mut lp_obj) = load_profiles()? else {
+        return Err("launcher_profiles.json not found".to_string());
+    };
+    let profiles = lp_obj
+        .get_mut("profiles")
+        .and_then(|p| p.as_object_mut())
+        .ok_or_else(|| "Invalid launcher_profiles.json format".to_string())?;
+    if issue.profile_key != installation_id {
+        let Some(profile) = profiles.remove(&issue.profile_key) else {
+            return Err(format!("Profile '{}' no longer exists", issue.profile_name));
+        };
+        // The installation's own profile wins, the other one is just dropped
+        if !profiles.contains_key(&installation_id) {
+            profiles.insert(installation_id.clone(), profile);
+        }
+    }
+    save_profiles(&lp_obj)?;
+    info!("Adopted launcher profile '{}' as installation {}", issue.profile_name, installation_id);
+    Ok(installation_id)
+}
+
+/// Keys of other profiles with the same gameDir, removed before writing an installation's profile.
+pub fn duplicate_keys(profiles: &serde_json::Map<String, JsonValue>, key: &str, game_dir: &Path) -> Vec<String> {
+    profiles
+        .iter()
+        .filter(|(other, profile)| {
+            other.as_str() != key
+                && profile
+                    .get("gameDir")
+                    .and_then(|d| d.as_str())
+                    .map_or(false, |dir| Path::new(dir) == game_dir)
+        })
+        .map(|(other, _)| other.clone())
+        .collect()
+}
@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use isahc::auth::{Authentication, Credentials};
+use isahc::config::{CaCertificate, Configurable};
+use isahc::HttpClientBuilder;
+use log::{debug, info, warn};
+
+use crate::NetworkSettings;
+
+// Proxy and extra CA certificates for networks that need them, applied to every client
+// build_http_client makes. A proxy set in the preferences wins, otherwise the system's is used
+// (Internet Options on Windows, HTTPS_PROXY/HTTP_PROXY/ALL_PROXY elsewhere) unless that's turned
+// off. Firewalls that intercept TLS sign with their own CA, its bundle is trusted on top of the
+// system certificates: curl replaces its bundle when given one, so both go into one file.
+
+const MERGED_BUNDLE: &str = "ca-bundle.pem";
+
+// Where distributions keep the system bundle, the first one that exists is used
+const SYSTEM_BUNDLES: [&str; 4] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/cert.pem",
+    "/usr/local/etc/openssl/cert.pem",
+];
+
+#[derive(Debug, Clone, Default)]
+struct ProxyConfig {
+    // None with use_system off means no proxy at all, not even from the environment
+    url: Option<String>,
+    use_system: bool,
+    username: Option<String>,
+    password: Option<String>,
+    ca_bundle: Option<PathBuf>,
+}
+
+static CONFIG: RwLock<Option<ProxyConfig>> = RwLock::new(None);
+
+/// The system's proxy, if it has one.
+pub fn detect_system_proxy() -> Option<String> {
+    #[cfg(windows)]
+    {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+        let settings = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings")
+            .ok()?;
+        let enabled: u32 = settings.get_value("ProxyEnable").ok()?;
+        let server: String = settings.get_value("ProxyServer").ok()?;
+        if enabled == 0 || server.trim().is_empty() {
+            return None;
+        }
+        // Either "host:port" for everything or "http=host:port;https=host:port"
+        let server = server
+            .split(';')
+            .find_map(|entry| entry.strip_prefix("https="))
+            .or_else(|| server.split(';').find_map(|entry| entry.strip_prefix("http=")))
+            .unwrap_or(&server)
+            .trim()
+            .to_string();
+        Some(if server.contains("://") { server } else { format!("http://{}", server) })
+    }
+    #[cfg(not(windows))]
+    {
+        ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Checks that `path` is a PEM file with at least one certificate.
+pub fn check_ca_bundle(path: &Path) -> Result<usize, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let count = content.matches("-----BEGIN CERTIFICATE-----").count();
+    if count == 0 {
+        return Err(format!("{} has no PEM certificates in it", path.display()));
+    }
+    Ok(count)
+}
+
+// The extra bundle with the system one in front, written to the installer dir
+fn merged_bundle(extra: &Path) -> Result<PathBuf, String> {
+    check_ca_bundle(extra)?;
+    let mut content = SYSTEM_BUNDLES
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&fs::read_to_string(extra).map_err(|e| format!("Failed to read {}: {}", extra.display(), e))?);
+    let path = crate::get_installer_dir().join(MERGED_BUNDLE);
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+pub fn configure(network: &NetworkSettings) {
+    let ca_bundle = network
+        .ca_bundle
+        .as_ref()
+        .filter(|path| !path.trim().is_empty())
+        .and_then(|path| match merged_bundle(Path::new(path)) {
+            Ok(merged) => Some(merged),
+            Err(e) => {
+                warn!("Not using the extra CA certificates: {}", e);
+                None
+            }
+        });
+    let config = ProxyConfig {
+        url: network.proxy.clone().filter(|url| !url.trim().is_empty()),
+        use_system: network.use_system_proxy,
+        username: network.proxy_username.clone().filter(|user| !user.is_empty()),
+        password: network.proxy_password.as_ref().map(|p| p.0.clone()),
+        ca_bundle,
+    };
+    info!(
+        "Proxy: {}, extra CA certificates: {}",
+        config.url.as_deref().unwrap_or(if config.use_system { "system" } else { "none" }),
+        config.ca_bundle.is_some()
+    );
+    if let Ok(mut current) = CONFIG.write() {
+        *current = Some(config);
+    }
+}
+
+/// Sets the configured proxy and certificates on `builder`.
+pub fn apply(builder: HttpClientBuilder) -> HttpClientBuilder {
+    let Some(config) = CONFIG.read().ok().and_then(|c| c.clone()) else {
+        // Before configure(), e.g. the headless subcommands: curl's defaults
+        return builder;
+    };
+    let proxy = config.url.clone().or_else(|| config.use_system.then(detect_system_proxy).flatten());
+    let mut builder = match proxy.as_deref().map(str::parse::<isahc::http::Uri>) {
+        Some(Ok(uri)) => builder.proxy(Some(uri)),
+        Some(Err(e)) => {
+            warn!("Ignoring the proxy, it isn't a valid url: {}", e);
+            builder
+        }
+        None if config.use_system => builder,
+        None => builder.proxy(None),
+    };
+    if let (Some(username), true) = (&config.username, proxy.is_some()) {
+        debug!("Authenticating with the proxy as {}", username);
+        builder = builder
+            .proxy_authentication(Authentication::basic())
+            .proxy_credentials(Credentials::new(username.as_str(), config.password.clone().unwrap_or_default()));
+    }
+    if let Some(bundle) = &config.ca_bundle {
+        builder = builder.ssl_ca_certificate(CaCertificate::file(bundle));
+    }
+    builder
+}
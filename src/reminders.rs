@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use log::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+
+// Per-installation reminders ("re-enable shaders after the event on Friday"). They live in the
+// installation state and a background loop fires them once their time has come.
+
+const CHECK_INTERVAL_SECS: u64 = 30;
+// Format of <input type="datetime-local">
+const INPUT_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Reminder {
+    pub id: String,
+    pub message: String,
+    pub due: DateTime<Utc>,
+    // Set once the notification went out so it's only shown once
+    #[serde(default)]
+    pub fired: bool,
+}
+
+impl Reminder {
+    pub fn new(message: String, due: DateTime<Utc>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            message,
+            due,
+            fired: false,
+        }
+    }
+
+    pub fn due_display(&self) -> String {
+        self.due.with_timezone(&Local).format("%a %B %d, %Y %H:%M").to_string()
+    }
+}
+
+// A reminder that just fired, with enough context to show it anywhere in the UI
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueReminder {
+    pub installation_id: String,
+    pub installation_name: String,
+    pub reminder: Reminder,
+}
+
+/// Parses the value of a datetime-local input, which is in the user's local time.
+pub fn parse_local_input(value: &str) -> Result<DateTime<Utc>, String> {
+    let naive = NaiveDateTime::parse_from_str(value.trim(), INPUT_FORMAT)
+        .map_err(|e| format!("Invalid date '{}': {}", value, e))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .ok_or_else(|| format!("'{}' doesn't exist in the local time zone", value))
+}
+
+/// Marks every due reminder as fired, saves the affected installations and returns what fired.
+pub fn collect_due(now: DateTime<Utc>) -> Result<Vec<DueReminder>, String> {
+    let mut due = Vec::new();
+    for mut installation in crate::installation::load_all_installations()? {
+        let mut changed = false;
+        for reminder in installation.reminders.iter_mut().filter(|r| !r.fired && r.due <= now) {
+            reminder.fired = true;
+            changed = true;
+            due.push(DueReminder {
+                installation_id: installation.id.clone(),
+                installation_name: installation.name.clone(),
+                reminder: reminder.clone(),
+            });
+        }
+        if changed {
+            // Better to fire twice after a failed save than never
+            if let Err(e) = installation.save() {
+                error!("Failed to save fired reminders for {}: {}", installation.id, e);
+            }
+        }
+    }
+    Ok(due)
+}
+
+/// Runs forever, showing a desktop notification and calling `on_due` for every reminder that fires.
+/// Reminders that came due while the installer was closed fire on the first check.
+pub async fn run_reminder_loop<F: FnMut(Vec<DueReminder>)>(mut on_due: F) {
+    loop {
+        match collect_due(Utc::now()) {
+            Ok(due) if !due.is_empty() => {
+                debug!("{} reminder(s) due", due.len());
+                for reminder in &due {
+                    crate::watch_mode::notify(
+                        &format!("Reminder: {}", reminder.installation_name),
+                        &reminder.reminder.message,
+                    );
+                }
+                on_due(due);
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to check reminders: {}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
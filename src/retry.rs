@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use rand::Rng;
+
+// Retries back off exponentially with full jitter, so the parallel downloads of an install don't
+// hit a struggling server again all at the same moment. Each host also gets a circuit breaker:
+// after a few failures in a row requests to it fail right away for a while instead of every
+// component waiting through its own retries when Modrinth or GitHub is down.
+
+const BASE_DELAY: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(15);
+// Consecutive failures that open the breaker of a host
+const FAILURE_THRESHOLD: u32 = 5;
+const OPEN_FOR: Duration = Duration::from_secs(60);
+
+/// How long to wait before retry number `attempt` (1 for the first retry).
+pub fn delay(attempt: usize) -> Duration {
+    let exp = BASE_DELAY.saturating_mul(1u32 << attempt.saturating_sub(1).min(16));
+    let cap = exp.min(MAX_DELAY);
+    // Full jitter, anywhere between nothing and the cap
+    Duration::from_millis(rand::thread_rng().gen_range(0..=cap.as_millis() as u64))
+}
+
+/// Requests to `host` fail fast because it failed too often in a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostUnavailable {
+    pub host: String,
+    pub retry_in: Duration,
+}
+
+impl Display for HostUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is unreachable right now (it failed {} times in a row), trying again in {}s",
+            self.host,
+            FAILURE_THRESHOLD,
+            self.retry_in.as_secs().max(1)
+        )
+    }
+}
+
+impl std::error::Error for HostUnavailable {}
+
+impl From<HostUnavailable> for isahc::Error {
+    fn from(value: HostUnavailable) -> Self {
+        std::io::Error::new(std::io::ErrorKind::ConnectionRefused, value).into()
+    }
+}
+
+/// The HostUnavailable behind an isahc error, when that's why the request failed.
+pub fn host_unavailable(error: &isahc::Error) -> Option<&HostUnavailable> {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = std::error::Error::source(error);
+    while let Some(error) = source {
+        if let Some(io) = error.downcast_ref::<std::io::Error>() {
+            if let Some(unavailable) = io.get_ref().and_then(|e| e.downcast_ref::<HostUnavailable>()) {
+                return Some(unavailable);
+            }
+        }
+        source = error.source();
+    }
+    None
+}
+
+#[derive(Debug, Default)]
+struct HostHealth {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+lazy_static! {
+    static ref HOSTS: Mutex<HashMap<String, HostHealth>> = Mutex::new(HashMap::new());
+}
+
+pub fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Fails fast while the breaker of the url's host is open.
+pub fn check(url: &str) -> Result<(), HostUnavailable> {
+    let host = host_of(url);
+    let hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+    match hosts.get(&host).and_then(|h| h.open_until) {
+        Some(until) if until > Instant::now() => Err(HostUnavailable {
+            host,
+            retry_in: until - Instant::now(),
+        }),
+        // Open breakers let requests through once the time is up, one more failure opens it again
+        _ => Ok(()),
+    }
+}
+
+pub fn record_success(url: &str) {
+    let host = host_of(url);
+    let mut hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(health) = hosts.remove(&host) {
+        if health.consecutive_failures >= FAILURE_THRESHOLD {
+            debug!("{} is reachable again", host);
+        }
+    }
+}
+
+pub fn record_failure(url: &str) {
+    let host = host_of(url);
+    let mut hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+    let health = hosts.entry(host.clone()).or_default();
+    health.consecutive_failures += 1;
+    if health.consecutive_failures >= FAILURE_THRESHOLD {
+        warn!(
+            "{} failed {} times in a row, not contacting it for {}s",
+            host,
+            health.consecutive_failures,
+            OPEN_FOR.as_secs()
+        );
+        health.open_until = Some(Instant::now() + OPEN_FOR);
+    }
+}
+
+/// Server errors and rate limits are worth retrying, other statuses won't change.
+pub fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
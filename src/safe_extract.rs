@@ -0,0 +1,239 @@
+use std::fs;
+use std::io::{self, Read, Seek};
+use std::path::{Component, Path, PathBuf};
+
+use log::warn;
+use zip::ZipArchive;
+
+// Every zip the installer unpacks (remote includes, include zips, backups, exports, Java natives)
+// comes through here. enclosed_name already drops absolute paths and ".." components, on top of
+// that symlink entries are skipped and nothing is written through a symlink that's already in the
+// destination, a link to ~/.ssh in an old include folder must not turn into a write there.
+
+const SYMLINK_MODE: u32 = 0o120000;
+const FILE_TYPE_MASK: u32 = 0o170000;
+
+/// True when `relative` stays inside whatever it's joined to: no root, drive prefix or "..".
+/// Names merely containing two dots ("v1..2.txt") are fine.
+pub fn is_contained(relative: &Path) -> bool {
+    relative.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+// A symlink on the way from `dest` to `relative` would send the write somewhere else
+fn crosses_symlink(dest: &Path, relative: &Path) -> bool {
+    let mut current = dest.to_path_buf();
+    relative.components().any(|component| {
+        current.push(component);
+        fs::symlink_metadata(&current).map_or(false, |m| m.file_type().is_symlink())
+    })
+}
+
+/// Extracts the entries of `archive` into `dest`. `map` gets each entry's safe relative path and
+/// returns where it goes under `dest` (None skips it), `progress` is called with the entries done
+/// and the total. Returns the written files.
+pub fn extract_all<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    dest: &Path,
+    mut map: impl FnMut(&Path) -> Option<PathBuf>,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<Vec<PathBuf>, String> {
+    let total = archive.len();
+    let mut written = Vec::new();
+    for i in 0..total {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let Some(name) = entry.enclosed_name() else {
+            warn!("Skipping unsafe path in zip: {}", entry.name());
+            continue;
+        };
+        if entry.unix_mode().map_or(false, |mode| mode & FILE_TYPE_MASK == SYMLINK_MODE) {
+            warn!("Skipping symlink in zip: {}", entry.name());
+            continue;
+        }
+        let Some(relative) = map(&name) else { continue };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        if !is_contained(&relative) {
+            warn!("Skipping {}, it would end up outside {}", relative.display(), dest.display());
+            continue;
+        }
+        let parent_relative = relative.parent().unwrap_or(Path::new(""));
+        if crosses_symlink(dest, parent_relative) {
+            warn!("Skipping {}, a symlink is in the way", relative.display());
+            continue;
+        }
+
+        let target = dest.join(&relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            // Replace a symlink or hardlink at the target itself instead of writing to whatever it
+            // points at, another folder or a file shared through the content store
+            crate::content_store::detach(&target).map_err(|e| format!("Failed to replace {}: {}", target.display(), e))?;
+            let mut out = fs::File::create(&target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+            io::copy(&mut entry, &mut out).map_err(|e| format!("Failed to extract {}: {}", target.display(), e))?;
+            written.push(target);
+        }
+        progress(i + 1, total);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::{FileOptions, ZipWriter};
+
+    // A fresh empty folder per test, removed again by Drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = std::env::temp_dir().join(format!("safe-extract-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn build_zip(build: impl FnOnce(&mut ZipWriter<Cursor<Vec<u8>>>)) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        build(&mut writer);
+        ZipArchive::new(writer.finish().unwrap()).unwrap()
+    }
+
+    fn add_file(writer: &mut ZipWriter<Cursor<Vec<u8>>>, name: &str, content: &[u8]) {
+        writer.start_file(name, FileOptions::<()>::default()).unwrap();
+        writer.write_all(content).unwrap();
+    }
+
+    fn extract(archive: &mut ZipArchive<Cursor<Vec<u8>>>, dest: &Path) -> Vec<PathBuf> {
+        extract_all(archive, dest, |relative| Some(relative.to_path_buf()), |_, _| {}).unwrap()
+    }
+
+    #[test]
+    fn is_contained_rejects_escapes() {
+        assert!(is_contained(Path::new("config/wynntils/settings.json")));
+        assert!(is_contained(Path::new("./mods/a.jar")));
+        assert!(is_contained(Path::new("notes/v1..2.txt")));
+        assert!(!is_contained(Path::new("../outside.txt")));
+        assert!(!is_contained(Path::new("config/../../outside.txt")));
+        assert!(!is_contained(Path::new("/etc/passwd")));
+    }
+
+    #[test]
+    fn traversal_entries_are_skipped() {
+        let root = TempDir::new("traversal");
+        let dest = root.0.join("dest");
+        let mut archive = build_zip(|writer| {
+            add_file(writer, "../escaped.txt", b"no");
+            add_file(writer, "config/../../escaped2.txt", b"no");
+            add_file(writer, "/absolute.txt", b"no");
+            add_file(writer, "config/ok.txt", b"yes");
+        });
+        let written = extract(&mut archive, &dest);
+        assert_eq!(written, vec![dest.join("config/ok.txt")]);
+        assert!(!root.0.join("escaped.txt").exists());
+        assert!(!root.0.join("escaped2.txt").exists());
+        assert_eq!(fs::read(dest.join("config/ok.txt")).unwrap(), b"yes");
+    }
+
+    #[test]
+    fn unicode_names_are_extracted() {
+        let root = TempDir::new("unicode");
+        let mut archive = build_zip(|writer| {
+            add_file(writer, "wörlds/日本語/level.dat", b"data");
+            add_file(writer, "résumé v1..2.txt", b"text");
+        });
+        let written = extract(&mut archive, &root.0);
+        assert_eq!(written.len(), 2);
+        assert_eq!(fs::read(root.0.join("wörlds/日本語/level.dat")).unwrap(), b"data");
+        assert_eq!(fs::read(root.0.join("résumé v1..2.txt")).unwrap(), b"text");
+    }
+
+    #[test]
+    fn map_can_skip_and_move_entries() {
+        let root = TempDir::new("map");
+        let mut archive = build_zip(|writer| {
+            add_file(writer, "pack/mods/a.jar", b"a");
+            add_file(writer, "README.md", b"readme");
+        });
+        let written = extract_all(
+            &mut archive,
+            &root.0,
+            |relative| relative.strip_prefix("pack").ok().map(Path::to_path_buf),
+            |_, _| {},
+        )
+        .unwrap();
+        assert_eq!(written, vec![root.0.join("mods/a.jar")]);
+        assert!(!root.0.join("README.md").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_entries_are_skipped() {
+        let root = TempDir::new("symlink-entry");
+        let dest = root.0.join("dest");
+        let mut archive = build_zip(|writer| {
+            writer.add_symlink("link", "/etc", FileOptions::<()>::default()).unwrap();
+            add_file(writer, "file.txt", b"ok");
+        });
+        let written = extract(&mut archive, &dest);
+        assert_eq!(written, vec![dest.join("file.txt")]);
+        assert!(fs::symlink_metadata(dest.join("link")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn existing_symlinks_are_not_followed() {
+        let root = TempDir::new("symlink-dest");
+        let dest = root.0.join("dest");
+        let outside = root.0.join("outside");
+        fs::create_dir_all(&dest).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, dest.join("config")).unwrap();
+        fs::write(outside.join("target.txt"), b"original").unwrap();
+        std::os::unix::fs::symlink(outside.join("target.txt"), dest.join("linked.txt")).unwrap();
+
+        assert!(crosses_symlink(&dest, Path::new("config")));
+        assert!(crosses_symlink(&dest, Path::new("config/sub")));
+        assert!(!crosses_symlink(&dest, Path::new("mods")));
+
+        let mut archive = build_zip(|writer| {
+            add_file(writer, "config/evil.txt", b"no");
+            add_file(writer, "linked.txt", b"replaced");
+        });
+        let written = extract(&mut archive, &dest);
+        assert_eq!(written, vec![dest.join("linked.txt")]);
+        assert!(!outside.join("evil.txt").exists());
+        // The link itself was replaced, the file it pointed at is untouched
+        assert_eq!(fs::read(outside.join("target.txt")).unwrap(), b"original");
+        assert_eq!(fs::read(dest.join("linked.txt")).unwrap(), b"replaced");
+        assert!(!fs::symlink_metadata(dest.join("linked.txt")).unwrap().file_type().is_symlink());
+    }
+
+    #[test]
+    fn hardlinked_targets_get_their_own_file() {
+        let root = TempDir::new("hardlink");
+        let dest = root.0.join("dest");
+        fs::create_dir_all(dest.join("mods")).unwrap();
+        let shared = root.0.join("shared.jar");
+        fs::write(&shared, b"shared").unwrap();
+        fs::hard_link(&shared, dest.join("mods/a.jar")).unwrap();
+
+        let mut archive = build_zip(|writer| add_file(writer, "mods/a.jar", b"restored"));
+        extract(&mut archive, &dest);
+        assert_eq!(fs::read(&shared).unwrap(), b"shared");
+        assert_eq!(fs::read(dest.join("mods/a.jar")).unwrap(), b"restored");
+    }
+}
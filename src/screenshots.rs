@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use base64::{engine, Engine};
+use chrono::{DateTime, Local};
+use image::imageops::FilterType;
+use image::ImageFormat;
+use log::{debug, info};
+use once_cell::sync::Lazy;
+
+use crate::installation::Installation;
+
+// The game's screenshots/ folder of an installation, for the Screenshots tab. Thumbnails are
+// decoded and scaled on the blocking pool like icons, a folder of 4K screenshots would freeze the
+// window otherwise, and kept in memory for the run keyed by path and modification time.
+
+const THUMBNAIL_WIDTH: u32 = 320;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Screenshot {
+    pub path: PathBuf,
+    pub name: String,
+    pub taken: Option<DateTime<Local>>,
+    pub size: u64,
+}
+
+static THUMBNAILS: Lazy<Mutex<HashMap<(PathBuf, Option<SystemTime>), String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn screenshots_dir(installation: &Installation) -> Result<PathBuf, String> {
+    let launcher = crate::get_launcher(&installation.launcher_type)?;
+    Ok(crate::get_modpack_root(&launcher, &installation.id).join("screenshots"))
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map_or(false, |e| matches!(e.to_ascii_lowercase().as_str(), "png" | "jpg" | "jpeg"))
+}
+
+/// Screenshots of the installation, newest first. Empty when the game never took one.
+pub fn list(installation: &Installation) -> Result<Vec<Screenshot>, String> {
+    let dir = screenshots_dir(installation)?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut screenshots: Vec<Screenshot> = entries
+        .flatten()
+        .filter(|entry| is_image(&entry.path()))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| Screenshot {
+                name: entry.file_name().to_string_lossy().to_string(),
+                taken: metadata.modified().ok().map(DateTime::<Local>::from),
+                size: metadata.len(),
+                path: entry.path(),
+            })
+        })
+        .collect();
+    screenshots.sort_by(|a, b| b.taken.cmp(&a.taken).then_with(|| b.name.cmp(&a.name)));
+    debug!("{} screenshots in {}", screenshots.len(), dir.display());
+    Ok(screenshots)
+}
+
+fn encode(img: &image::DynamicImage, format: ImageFormat, mime: &str) -> Result<String, String> {
+    let mut data = Vec::new();
+    img.write_to(&mut Cursor::new(&mut data), format)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(format!("data:{};base64,{}", mime, engine::general_purpose::STANDARD.encode(data)))
+}
+
+/// Small JPEG of the screenshot as a data url.
+pub async fn thumbnail(path: PathBuf) -> Result<String, String> {
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let key = (path.clone(), modified);
+    if let Some(cached) = THUMBNAILS.lock().ok().and_then(|cache| cache.get(&key).cloned()) {
+        return Ok(cached);
+    }
+    let data_url = tokio::task::spawn_blocking(move || {
+        let img = image::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let thumbnail = img.resize(THUMBNAIL_WIDTH, THUMBNAIL_WIDTH, FilterType::Triangle);
+        // JPEG has no alpha channel
+        encode(&image::DynamicImage::ImageRgb8(thumbnail.to_rgb8()), ImageFormat::Jpeg, "image/jpeg")
+    })
+    .await
+    .map_err(|e| format!("Thumbnail stopped: {}", e))??;
+    if let Ok(mut cache) = THUMBNAILS.lock() {
+        cache.insert(key, data_url.clone());
+    }
+    Ok(data_url)
+}
+
+/// The full screenshot as a PNG data url, what the webview's clipboard takes.
+pub async fn png_data_url(path: PathBuf) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        let img = image::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        encode(&img, ImageFormat::Png, "image/png")
+    })
+    .await
+    .map_err(|e| format!("Copying stopped: {}", e))?
+}
+
+pub fn delete(screenshot: &Screenshot) -> Result<(), String> {
+    fs::remove_file(&screenshot.path)
+        .map_err(|e| format!("Failed to delete {}: {}", screenshot.name, e))?;
+    if let Ok(mut cache) = THUMBNAILS.lock() {
+        cache.retain(|(path, _), _| *path != screenshot.path);
+    }
+    info!("Deleted screenshot {}", screenshot.path.display());
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// Settings like passwords, API keys and tokens. The config is logged at startup and ends up in
+// crash reports, so a secret never shows its value in Debug or Display output.
+
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(pub String);
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            write!(f, "Secret(none)")
+        } else {
+            write!(f, "Secret(<redacted>)")
+        }
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", if self.0.is_empty() { "" } else { "<redacted>" })
+    }
+}
@@ -0,0 +1,272 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use log::{debug, info};
+
+use crate::universal::ServerEntry;
+
+// Adds the pack's servers (Wynncraft, event servers) to the multiplayer list. Minecraft keeps that
+// list in servers.dat, an uncompressed NBT file: a root compound with a "servers" list of
+// compounds holding name, ip and a few optional fields. Existing entries are read back in full so
+// whatever the game stored for them (icons, resource pack choice) survives the rewrite.
+// Servers are only added once per installation, a server the user removed stays removed.
+
+const SERVERS_FILE: &str = "servers.dat";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    // Element type and elements, the type matters for empty lists
+    List(u8, Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(..) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(entries) => entries.iter().find(|(key, _)| key == name).map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+}
+
+fn read_bytes<const N: usize>(input: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    input.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_len(input: &mut impl Read) -> io::Result<usize> {
+    let len = i32::from_be_bytes(read_bytes(input)?);
+    usize::try_from(len).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "negative length"))
+}
+
+// Java writes modified UTF-8, which only differs from UTF-8 for NUL and characters outside the BMP
+fn read_string(input: &mut impl Read) -> io::Result<String> {
+    let len = u16::from_be_bytes(read_bytes(input)?) as usize;
+    let mut buf = vec![0u8; len];
+    input.read_exact(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn read_payload(input: &mut impl Read, id: u8) -> io::Result<Tag> {
+    Ok(match id {
+        1 => Tag::Byte(i8::from_be_bytes(read_bytes(input)?)),
+        2 => Tag::Short(i16::from_be_bytes(read_bytes(input)?)),
+        3 => Tag::Int(i32::from_be_bytes(read_bytes(input)?)),
+        4 => Tag::Long(i64::from_be_bytes(read_bytes(input)?)),
+        5 => Tag::Float(f32::from_be_bytes(read_bytes(input)?)),
+        6 => Tag::Double(f64::from_be_bytes(read_bytes(input)?)),
+        7 => {
+            let len = read_len(input)?;
+            let mut buf = vec![0u8; len];
+            input.read_exact(&mut buf)?;
+            Tag::ByteArray(buf.into_iter().map(|b| b as i8).collect())
+        }
+        8 => Tag::String(read_string(input)?),
+        9 => {
+            let element = read_bytes::<1>(input)?[0];
+            let len = read_len(input)?;
+            let mut elements = Vec::new();
+            for _ in 0..len {
+                elements.push(read_payload(input, element)?);
+            }
+            Tag::List(element, elements)
+        }
+        10 => {
+            let mut entries = Vec::new();
+            loop {
+                let id = read_bytes::<1>(input)?[0];
+                if id == 0 {
+                    break;
+                }
+                let name = read_string(input)?;
+                entries.push((name, read_payload(input, id)?));
+            }
+            Tag::Compound(entries)
+        }
+        11 => {
+            let len = read_len(input)?;
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(i32::from_be_bytes(read_bytes(input)?));
+            }
+            Tag::IntArray(values)
+        }
+        12 => {
+            let len = read_len(input)?;
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(i64::from_be_bytes(read_bytes(input)?));
+            }
+            Tag::LongArray(values)
+        }
+        other => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown tag type {}", other))),
+    })
+}
+
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    // Longer names can't be stored, the game would cut them as well
+    let bytes = &value.as_bytes()[..value.len().min(u16::MAX as usize)];
+    out.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Short(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Long(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::Double(v) => out.extend_from_slice(&v.to_be_bytes()),
+        Tag::ByteArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            out.extend(values.iter().map(|b| *b as u8));
+        }
+        Tag::String(value) => write_string(out, value),
+        Tag::List(element, elements) => {
+            out.push(*element);
+            out.extend_from_slice(&(elements.len() as i32).to_be_bytes());
+            for element in elements {
+                write_payload(out, element);
+            }
+        }
+        Tag::Compound(entries) => {
+            for (name, tag) in entries {
+                out.push(tag.id());
+                write_string(out, name);
+                write_payload(out, tag);
+            }
+            out.push(0);
+        }
+        Tag::IntArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for v in values {
+                out.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+        Tag::LongArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            for v in values {
+                out.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+    }
+}
+
+// The root is a named compound, its name is empty in servers.dat
+fn decode(bytes: &[u8]) -> io::Result<Tag> {
+    let mut input = bytes;
+    let id = read_bytes::<1>(&mut input)?[0];
+    if id != 10 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "root isn't a compound"));
+    }
+    read_string(&mut input)?;
+    read_payload(&mut input, id)
+}
+
+fn encode(root: &Tag) -> Vec<u8> {
+    let mut out = vec![root.id()];
+    write_string(&mut out, "");
+    write_payload(&mut out, root);
+    out
+}
+
+// "play.wynncraft.com" and "Play.Wynncraft.com:25565" are the same server
+fn normalize_address(address: &str) -> String {
+    let address = address.trim().to_lowercase();
+    address.strip_suffix(":25565").map(str::to_string).unwrap_or(address)
+}
+
+fn server_tag(server: &ServerEntry) -> Tag {
+    let mut entries = vec![
+        ("name".to_string(), Tag::String(server.name.clone())),
+        ("ip".to_string(), Tag::String(server.address.clone())),
+    ];
+    if let Some(accept) = server.accept_textures {
+        entries.push(("acceptTextures".to_string(), Tag::Byte(accept as i8)));
+    }
+    Tag::Compound(entries)
+}
+
+/// Adds the servers of `servers` that aren't in servers.dat under `game_dir` yet and weren't added
+/// before (`already_added`, normalized addresses). Pack servers go on top in manifest order.
+/// Returns the addresses that were added.
+pub fn merge(game_dir: &Path, servers: &[ServerEntry], already_added: &[String]) -> Result<Vec<String>, String> {
+    let path = game_dir.join(SERVERS_FILE);
+    let mut root = match fs::read(&path) {
+        Ok(bytes) => decode(&bytes).map_err(|e| format!("{} is damaged: {}", path.display(), e))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Tag::Compound(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    let existing: Vec<String> = match root.get("servers") {
+        Some(Tag::List(_, entries)) => entries
+            .iter()
+            .filter_map(|entry| match entry.get("ip") {
+                Some(Tag::String(ip)) => Some(normalize_address(ip)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut added = Vec::new();
+    let mut new_entries = Vec::new();
+    for server in servers {
+        let address = normalize_address(&server.address);
+        if address.is_empty() || existing.contains(&address) || already_added.contains(&address) || added.contains(&address) {
+            continue;
+        }
+        new_entries.push(server_tag(server));
+        added.push(address);
+    }
+    if new_entries.is_empty() {
+        debug!("No servers to add to {}", path.display());
+        return Ok(added);
+    }
+
+    let Tag::Compound(root_entries) = &mut root else { unreachable!() };
+    match root_entries.iter_mut().find(|(key, _)| key == "servers") {
+        Some((_, Tag::List(element, entries))) => {
+            *element = 10;
+            new_entries.append(entries);
+            *entries = new_entries;
+        }
+        Some((_, other)) => *other = Tag::List(10, new_entries),
+        None => root_entries.push(("servers".to_string(), Tag::List(10, new_entries))),
+    }
+
+    let tmp = path.with_extension("dat.tmp");
+    fs::write(&tmp, encode(&root)).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    info!("Added {} server(s) to {}", added.len(), path.display());
+    Ok(added)
+}
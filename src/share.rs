@@ -0,0 +1,197 @@
+use std::fs;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use isahc::AsyncReadResponseExt;
+use log::{debug, warn};
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::{Deserialize, Serialize};
+
+use crate::installation::Installation;
+use crate::universal::UniversalManifest;
+use crate::CachedHttpClient;
+
+// Paste backend used for share codes, the paste id doubles as the short code
+const PASTE_URL: &str = "https://paste.rs/";
+pub const SHARE_FORMAT_VERSION: u32 = 1;
+// Same json as a share code, saved to a file for sharing without the paste backend
+pub const PACK_FILE_EXTENSION: &str = "wcpack";
+// What the performance tab's memory slider allows, in MB
+const MEMORY_RANGE: (i32, i32) = (1024, 8 * 1024);
+
+// Everything needed to recreate an installation's setup on another machine.
+// Paths, launch stats and install state are deliberately left out.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SharedInstallation {
+    pub format_version: u32,
+    pub name: String,
+    pub universal_version: String,
+    pub minecraft_version: String,
+    pub loader_type: String,
+    pub loader_version: String,
+    #[serde(default)]
+    pub base_preset_id: Option<String>,
+    pub enabled_features: Vec<String>,
+    pub memory_allocation: i32,
+    pub java_args: String,
+}
+
+impl SharedInstallation {
+    pub fn from_installation(installation: &Installation) -> Self {
+        Self {
+            format_version: SHARE_FORMAT_VERSION,
+            name: installation.name.clone(),
+            universal_version: installation.universal_version.clone(),
+            minecraft_version: installation.minecraft_version.clone(),
+            loader_type: installation.loader_type.clone(),
+            loader_version: installation.loader_version.clone(),
+            base_preset_id: installation.selected_preset_id.clone(),
+            enabled_features: installation.enabled_features.clone(),
+            memory_allocation: installation.memory_allocation,
+            java_args: installation.java_args.clone(),
+        }
+    }
+
+    /// Whether the setup comes with Java arguments, which only apply after the user agreed to them.
+    pub fn has_java_args(&self) -> bool {
+        !self.java_args.trim().is_empty()
+    }
+
+    /// Copies the shared setup onto a freshly created installation.
+    /// Features the current manifest doesn't know about are dropped and returned.
+    /// Setups come from anyone, JVM flags like -XX:OnOutOfMemoryError run commands, so the Java
+    /// arguments are only taken with `accept_java_args`, after the user saw them.
+    pub fn apply_to(&self, installation: &mut Installation, manifest: &UniversalManifest, accept_java_args: bool) -> Vec<String> {
+        let known = |id: &String| {
+            id == "default"
+                || manifest.mods.iter().any(|c| &c.id == id)
+                || manifest.shaderpacks.iter().any(|c| &c.id == id)
+                || manifest.resourcepacks.iter().any(|c| &c.id == id)
+                || manifest.include.iter().any(|c| &c.id == id)
+                || manifest.remote_include.iter().any(|c| &c.id == id)
+        };
+        let (features, dropped): (Vec<String>, Vec<String>) =
+            self.enabled_features.iter().cloned().partition(|id| known(id));
+
+        if self.universal_version != manifest.modpack_version {
+            warn!(
+                "Shared setup was made for {} but the current modpack is {}",
+                self.universal_version, manifest.modpack_version
+            );
+        }
+
+        installation.enabled_features = features.clone();
+        installation.pending_features = features.clone();
+        installation.pre_install_features = features;
+        installation.memory_allocation = self.memory_allocation.clamp(MEMORY_RANGE.0, MEMORY_RANGE.1);
+        if accept_java_args {
+            installation.java_args = self.java_args.clone();
+        } else if self.has_java_args() {
+            debug!("Not using the shared Java arguments of '{}'", self.name);
+        }
+        installation.selected_preset_id = self.base_preset_id.clone();
+        installation.base_preset_id = self.base_preset_id.clone();
+        installation.is_custom_configuration = self.base_preset_id.is_none();
+        dropped
+    }
+}
+
+pub fn share_url(code: &str) -> String {
+    format!("{}{}", PASTE_URL, code)
+}
+
+// Accepts either the bare code or the full paste url (what the QR code contains)
+pub fn normalize_code(input: &str) -> Result<String, String> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let code = trimmed.strip_prefix(PASTE_URL).unwrap_or(trimmed);
+    if code.is_empty() || code.len() > 32 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("That doesn't look like a valid share code".to_string());
+    }
+    Ok(code.to_string())
+}
+
+/// Uploads the setup and returns the short code.
+pub async fn publish(http_client: &CachedHttpClient, shared: &SharedInstallation) -> Result<String, String> {
+    let body = serde_json::to_string(shared)
+        .map_err(|e| format!("Failed to serialize installation: {}", e))?;
+    let request = isahc::Request::post(PASTE_URL)
+        .header("Content-Type", "text/plain")
+        .body(body)
+        .map_err(|e| format!("Failed to create share request: {}", e))?;
+
+    let mut response = http_client
+        .http_client
+        .send_async(request)
+        .await
+        .map_err(|e| format!("Failed to upload installation: {}", e))?;
+    // paste.rs answers 206 when the paste was truncated, which would break the json
+    if response.status().as_u16() != 201 {
+        return Err(format!("Share server returned {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read share response: {}", e))?;
+    let code = normalize_code(&text)?;
+    debug!("Published installation '{}' as {}", shared.name, code);
+    Ok(code)
+}
+
+pub async fn fetch_shared(http_client: &CachedHttpClient, input: &str) -> Result<SharedInstallation, String> {
+    let code = normalize_code(input)?;
+    let url = share_url(&code);
+    let mut response = http_client
+        .with_headers(url.as_str(), &[])
+        .await
+        .map_err(|e| format!("Failed to fetch shared installation: {}", e))?;
+    if response.status().as_u16() == 404 {
+        return Err(format!("No shared installation found for code '{}'", code));
+    }
+    if !response.status().is_success() {
+        return Err(format!("Share server returned {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read shared installation: {}", e))?;
+    parse_shared(&text).map_err(|e| format!("Share code '{}' does not contain an installation: {}", code, e))
+}
+
+fn parse_shared(text: &str) -> Result<SharedInstallation, String> {
+    let shared: SharedInstallation = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    if shared.format_version > SHARE_FORMAT_VERSION {
+        return Err("This installation was shared from a newer version of the installer. Please update first.".to_string());
+    }
+    Ok(shared)
+}
+
+pub fn write_pack_file(shared: &SharedInstallation, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(shared)
+        .map_err(|e| format!("Failed to serialize installation: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    debug!("Exported installation '{}' to {}", shared.name, path.display());
+    Ok(())
+}
+
+pub fn read_pack_file(path: &Path) -> Result<SharedInstallation, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let shared = parse_shared(&text).map_err(|e| format!("{} is not an installation file: {}", path.display(), e))?;
+    if shared.has_java_args() {
+        warn!("{} comes with Java arguments, they're only used if accepted: {}", path.display(), shared.java_args);
+    }
+    Ok(shared)
+}
+
+/// Renders the share url as an svg QR code, ready to drop into an img src.
+pub fn qr_data_url(code: &str) -> Result<String, String> {
+    let qr = QrCode::new(share_url(code).as_bytes())
+        .map_err(|e| format!("Failed to generate QR code: {}", e))?;
+    let image = qr
+        .render()
+        .min_dimensions(200, 200)
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"))
+        .build();
+    Ok(format!("data:image/svg+xml;base64,{}", STANDARD.encode(image)))
+}
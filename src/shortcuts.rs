@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use log::info;
+
+use crate::installation::{load_installation, Installation};
+
+// Desktop shortcuts that start an installation without going through the installer's window. They
+// run `installer --launch <id>`, which launches like the Play button does and stays in the
+// background until the game closes so the play time is still recorded. Windows gets a .lnk, Linux
+// a .desktop file and macOS a small .app that runs the installer.
+
+pub const LAUNCH_ARG: &str = "--launch";
+
+fn print_usage() {
+    eprintln!("Usage: wynncraft-overhaul-installer --launch <installation id> [--portable]");
+    eprintln!("Exit codes: 0 launched, 1 launch failed, 2 invalid usage");
+}
+
+// Arguments every run understands, they can come along with --launch
+fn takes_value(arg: &str) -> Option<bool> {
+    match arg {
+        crate::portable::FLAG_ARG => Some(false),
+        crate::dev_manifest::DIR_ARG | crate::dev_manifest::BRANCH_ARG => Some(true),
+        _ => None,
+    }
+}
+
+fn parse_id(args: &[String]) -> Result<Option<String>, String> {
+    let mut id = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match (arg.as_str(), takes_value(arg)) {
+            ("-h" | "--help", _) => return Ok(None),
+            (_, Some(true)) => {
+                iter.next();
+            }
+            (_, Some(false)) => {}
+            (other, None) if other.starts_with('-') => return Err(format!("Unknown argument '{}'", other)),
+            (other, None) if id.is_none() => id = Some(other.to_string()),
+            (other, None) => return Err(format!("Unexpected argument '{}'", other)),
+        }
+    }
+    id.map(Some).ok_or_else(|| "Missing the installation id".to_string())
+}
+
+// Nobody sees stderr when started from a shortcut
+fn show_error(message: &str) {
+    eprintln!("{}", message);
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_title("Couldn't start Minecraft")
+        .set_description(message)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+}
+
+/// Entry point for `--launch`.
+pub fn run(args: &[String]) -> i32 {
+    let id = match parse_id(args) {
+        Ok(Some(id)) => id,
+        Ok(None) => {
+            print_usage();
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            print_usage();
+            return 2;
+        }
+    };
+    let installation = match load_installation(&id) {
+        Ok(installation) => installation,
+        Err(e) => {
+            show_error(&format!("{}. The shortcut may belong to a deleted installation.", e));
+            return 1;
+        }
+    };
+    if !installation.installed {
+        show_error(&format!("{} isn't installed yet, open the installer to install it.", installation.name));
+        return 1;
+    }
+    if let Err(e) = crate::instance_lock::check_installation(&installation) {
+        show_error(&e);
+        return 1;
+    }
+
+    let (config, _) = crate::config_migration::load_config(&crate::get_installer_dir().join("config.json"));
+    crate::apply_config(&config);
+    // Subscribed first, a game that closes right away must not be missed
+    let mut exits = crate::launcher::subscribe_exits();
+    if let Err(e) = crate::launch_modpack(&installation.id) {
+        show_error(&format!("Failed to launch {}: {}", installation.name, e));
+        return 1;
+    }
+    info!("Launched {} from a shortcut, waiting for it to close", installation.name);
+    while let Ok(exit) = exits.blocking_recv() {
+        if exit.installation_id == installation.id {
+            break;
+        }
+    }
+    0
+}
+
+// Installation names may contain anything, file names may not
+fn file_name(installation: &Installation) -> String {
+    let name: String = installation
+        .name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    match name.trim() {
+        "" => installation.id.clone(),
+        name => name.to_string(),
+    }
+}
+
+// What the shortcut has to pass along so the launched run finds the same installer dir
+fn launch_args(installation: &Installation) -> Vec<String> {
+    let mut args = vec![LAUNCH_ARG.to_string(), installation.id.clone()];
+    let flag_file = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(crate::portable::FLAG_FILE)));
+    if crate::portable::root().is_some() && !flag_file.map_or(false, |flag| flag.is_file()) {
+        args.push(crate::portable::FLAG_ARG.to_string());
+    }
+    args
+}
+
+/// Creates a desktop shortcut that launches `installation`, returns where it was written.
+pub fn create_desktop_shortcut(installation: &Installation) -> Result<PathBuf, String> {
+    let desktop = dirs::desktop_dir().ok_or_else(|| "Couldn't find the desktop folder".to_string())?;
+    let exe = std::env::current_exe().map_err(|e| format!("Couldn't find the installer's executable: {}", e))?;
+    let path = write_shortcut(&desktop, &exe, installation)?;
+    info!("Created a shortcut for {} at {}", installation.id, path.display());
+    Ok(path)
+}
+
+#[cfg(target_os = "windows")]
+fn write_shortcut(desktop: &Path, exe: &Path, installation: &Installation) -> Result<PathBuf, String> {
+    let path = desktop.join(format!("{}.lnk", file_name(installation)));
+    let mut link = mslnk::ShellLink::new(exe).map_err(|e| format!("Failed to create the shortcut: {}", e))?;
+    link.set_arguments(Some(launch_args(installation).join(" ")));
+    link.set_name(Some(format!("Play {}", installation.name)));
+    link.set_icon_location(Some(exe.to_string_lossy().to_string()));
+    if let Some(dir) = exe.parent() {
+        link.set_working_dir(Some(dir.to_string_lossy().to_string()));
+    }
+    link.create_lnk(&path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(target_os = "linux")]
+fn write_shortcut(desktop: &Path, exe: &Path, installation: &Installation) -> Result<PathBuf, String> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let icon = crate::get_installer_dir().join("icon.png");
+    if let Err(e) = fs::write(&icon, include_bytes!("assets/icon.png")) {
+        log::warn!("Failed to write the shortcut icon: {}", e);
+    }
+    // Exec quoting per the desktop entry spec, ids and flags never need it
+    let exec = std::iter::once(format!("\"{}\"", exe.to_string_lossy().replace('\\', "\\\\").replace('"', "\\\"")))
+        .chain(launch_args(installation))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName={}\nComment=Launch the Wynncraft Overhaul installation {}\nExec={}\nIcon={}\nTerminal=false\nCategories=Game;\n",
+        installation.name,
+        installation.name,
+        exec,
+        icon.display()
+    );
+    let path = desktop.join(format!("{}.desktop", file_name(installation)));
+    fs::write(&path, entry).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    // Desktops only start entries that are executable
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to make {} executable: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn write_shortcut(desktop: &Path, exe: &Path, installation: &Installation) -> Result<PathBuf, String> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let app = desktop.join(format!("{}.app", file_name(installation)));
+    let macos = app.join("Contents").join("MacOS");
+    fs::create_dir_all(&macos).map_err(|e| format!("Failed to create {}: {}", macos.display(), e))?;
+    let quoted: Vec<String> = std::iter::once(exe.to_string_lossy().to_string())
+        .chain(launch_args(installation))
+        .map(|arg| format!("'{}'", arg.replace('\'', "'\\''")))
+        .collect();
+    let script = macos.join("launch");
+    fs::write(&script, format!("#!/bin/sh\nexec {}\n", quoted.join(" ")))
+        .map_err(|e| format!("Failed to write {}: {}", script.display(), e))?;
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to make {} executable: {}", script.display(), e))?;
+    let plist = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n<dict>\n    <key>CFBundleExecutable</key>\n    <string>launch</string>\n    <key>CFBundleIdentifier</key>\n    <string>com.wynncraftoverhaul.launch.{}</string>\n    <key>CFBundleName</key>\n    <string>{}</string>\n    <key>CFBundlePackageType</key>\n    <string>APPL</string>\n</dict>\n</plist>\n",
+        installation.id,
+        xml_escape(&installation.name)
+    );
+    let info = app.join("Contents").join("Info.plist");
+    fs::write(&info, plist).map_err(|e| format!("Failed to write {}: {}", info.display(), e))?;
+    Ok(app)
+}
+
+#[cfg(target_os = "macos")]
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn write_shortcut(_desktop: &Path, _exe: &Path, _installation: &Installation) -> Result<PathBuf, String> {
+    Err("Shortcuts aren't supported on this platform".to_string())
+}
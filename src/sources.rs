@@ -0,0 +1,184 @@
+use std::sync::RwLock;
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{DEFAULT_UNIVERSAL_URL, GH_API, GH_RAW};
+
+// Where modpacks come from. The Wynncraft Overhaul repo is always there, config.json can add more
+// under `sources`: a GitHub repo (universal.json, presets.json and the include files are read from
+// its branch) or the url of a universal.json anywhere, with presets.json and include files looked
+// up next to it. Installations remember the source they were created from, so updates keep coming
+// from there even if the source is removed from the config later.
+
+pub const DEFAULT_REPO: &str = "Wynncraft-Overhaul/majestic-overhaul";
+const DEFAULT_BRANCH: &str = "master";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackSource {
+    pub name: String,
+    // "owner/repo"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<String>,
+    // Defaults to master
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    // A universal.json outside of GitHub
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_url: Option<String>,
+}
+
+impl Default for PackSource {
+    fn default() -> Self {
+        PackSource {
+            name: "Wynncraft Overhaul".to_string(),
+            github: Some(DEFAULT_REPO.to_string()),
+            branch: Some(DEFAULT_BRANCH.to_string()),
+            manifest_url: None,
+        }
+    }
+}
+
+impl PackSource {
+    /// Identifies the source, two entries with the same id are the same source.
+    pub fn id(&self) -> String {
+        match (&self.github, &self.manifest_url) {
+            (Some(repo), _) => format!("github:{}@{}", repo, self.branch()),
+            (None, Some(url)) => url.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.id() == PackSource::default().id()
+    }
+
+    pub fn branch(&self) -> String {
+        self.branch.clone().unwrap_or_else(|| DEFAULT_BRANCH.to_string())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match (&self.github, &self.manifest_url) {
+            (Some(_), Some(_)) => Err(format!("source '{}' sets both github and manifest_url", self.name)),
+            (None, None) => Err(format!("source '{}' needs github or manifest_url", self.name)),
+            (Some(repo), None) if repo.trim_matches('/').split('/').count() != 2 => {
+                Err(format!("source '{}': github should be owner/repo, got '{}'", self.name, repo))
+            }
+            (None, Some(url)) if !url.starts_with("https://") && !url.starts_with("http://") => {
+                Err(format!("source '{}': manifest_url isn't a url", self.name))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn repo(&self) -> Option<String> {
+        self.github.as_ref().map(|repo| repo.trim_matches('/').to_string())
+    }
+
+    pub fn universal_url(&self) -> String {
+        if self.is_default() {
+            return DEFAULT_UNIVERSAL_URL.to_string();
+        }
+        match &self.manifest_url {
+            Some(url) => url.clone(),
+            None => self.file_url("universal.json"),
+        }
+    }
+
+    // Folder holding universal.json, ends with a slash
+    fn base_url(&self) -> String {
+        match (self.repo(), &self.manifest_url) {
+            (Some(repo), _) => format!("{}{}/{}/", GH_RAW, repo, self.branch()),
+            (None, Some(url)) => match url.rfind('/') {
+                Some(slash) => url[..=slash].to_string(),
+                None => format!("{}/", url),
+            },
+            (None, None) => String::new(),
+        }
+    }
+
+    /// A file of the pack, relative to universal.json.
+    pub fn file_url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url(), path.trim_start_matches('/'))
+    }
+
+    pub fn presets_url(&self) -> String {
+        self.file_url("presets.json")
+    }
+
+    /// GitHub API listing of an include directory, None for sources outside of GitHub.
+    pub fn directory_api_url(&self, path: &str) -> Option<String> {
+        let repo = self.repo()?;
+        let path = path.trim_start_matches('/');
+        // The default repo never asked for a ref, keep its urls (and the cached listings) as they were
+        if self.is_default() {
+            return Some(format!("{}{}/contents/{}", GH_API, repo, path));
+        }
+        Some(format!("{}{}/contents/{}?ref={}", GH_API, repo, path, self.branch()))
+    }
+
+    /// The modpack_source and modpack_branch of an InstallerProfile for this source.
+    pub fn profile_location(&self) -> (String, String) {
+        match (self.repo(), &self.manifest_url) {
+            (Some(repo), _) => (format!("{}/", repo), self.branch()),
+            (None, Some(url)) => (url.clone(), String::new()),
+            (None, None) => (format!("{}/", DEFAULT_REPO), DEFAULT_BRANCH.to_string()),
+        }
+    }
+
+    /// Reverse of profile_location.
+    pub fn from_profile(modpack_source: &str, modpack_branch: &str) -> PackSource {
+        let source = if modpack_source.contains("://") {
+            PackSource {
+                name: modpack_source.to_string(),
+                github: None,
+                branch: None,
+                manifest_url: Some(modpack_source.to_string()),
+            }
+        } else {
+            PackSource {
+                name: modpack_source.trim_matches('/').to_string(),
+                github: Some(modpack_source.trim_matches('/').to_string()),
+                branch: Some(modpack_branch.to_string()).filter(|b| !b.is_empty()),
+                manifest_url: None,
+            }
+        };
+        // Prefer the configured entry, it has the name
+        find(&source.id()).unwrap_or(source)
+    }
+}
+
+// Mirrors config.sources, set at startup and whenever the config is replaced
+static SOURCES: RwLock<Vec<PackSource>> = RwLock::new(Vec::new());
+
+pub fn configure(sources: &[PackSource]) {
+    let default_id = PackSource::default().id();
+    let mut valid: Vec<PackSource> = Vec::new();
+    for source in sources {
+        if let Err(e) = source.validate() {
+            warn!("Ignoring modpack {}", e);
+            continue;
+        }
+        if source.id() == default_id || valid.iter().any(|s| s.id() == source.id()) {
+            continue;
+        }
+        valid.push(source.clone());
+    }
+    debug!("{} extra modpack source(s) configured", valid.len());
+    if let Ok(mut current) = SOURCES.write() {
+        *current = valid;
+    }
+}
+
+/// Every source, the default one first.
+pub fn all() -> Vec<PackSource> {
+    let mut sources = vec![PackSource::default()];
+    if let Ok(configured) = SOURCES.read() {
+        sources.extend(configured.iter().cloned());
+    }
+    sources
+}
+
+pub fn find(id: &str) -> Option<PackSource> {
+    all().into_iter().find(|s| s.id() == id)
+}
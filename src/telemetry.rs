@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+// Where usage statistics end up, from config.privacy. Sending posts them to the tracking worker,
+// local-only keeps them in a file next to the config (shown in Settings > Privacy) and nothing
+// leaves the computer, off drops them. Every event goes through crate::Analytics, which asks here.
+
+const STATISTICS_FILE: &str = "local_statistics.json";
+// Older events only stay in the counts
+const MAX_RECENT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Send,
+    LocalOnly,
+    Off,
+}
+
+// Mirrors config.privacy, set at startup and whenever preferences are saved
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+pub fn configure(mode: Mode) {
+    let value = match mode {
+        Mode::Send => 0,
+        Mode::LocalOnly => 1,
+        Mode::Off => 2,
+    };
+    if value != MODE.swap(value, Ordering::Relaxed) {
+        info!("Usage statistics: {:?}", mode);
+    }
+}
+
+pub fn mode() -> Mode {
+    match MODE.load(Ordering::Relaxed) {
+        0 => Mode::Send,
+        1 => Mode::LocalOnly,
+        _ => Mode::Off,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocalEvent {
+    pub action: String,
+    pub source: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LocalStatistics {
+    // Action -> number of times it happened
+    #[serde(default)]
+    pub counts: BTreeMap<String, u64>,
+    // Newest last
+    #[serde(default)]
+    pub recent: Vec<LocalEvent>,
+}
+
+fn statistics_path() -> PathBuf {
+    crate::get_installer_dir().join(STATISTICS_FILE)
+}
+
+pub fn load_local() -> LocalStatistics {
+    let path = statistics_path();
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable local statistics: {}", e);
+            LocalStatistics::default()
+        }),
+        Err(_) => LocalStatistics::default(),
+    }
+}
+
+pub fn record_local(action: &str, source: &str, data: serde_json::Value) {
+    let mut statistics = load_local();
+    *statistics.counts.entry(action.to_string()).or_insert(0) += 1;
+    statistics.recent.push(LocalEvent {
+        action: action.to_string(),
+        source: source.to_string(),
+        timestamp: Utc::now(),
+        data,
+    });
+    let overflow = statistics.recent.len().saturating_sub(MAX_RECENT);
+    statistics.recent.drain(..overflow);
+
+    let result = serde_json::to_string_pretty(&statistics)
+        .map_err(|e| e.to_string())
+        .and_then(|text| fs::write(statistics_path(), text).map_err(|e| e.to_string()));
+    match result {
+        Ok(_) => debug!("Recorded '{}' locally", action),
+        Err(e) => warn!("Failed to record local statistics: {}", e),
+    }
+}
+
+pub fn clear_local() -> Result<(), String> {
+    let path = statistics_path();
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to clear local statistics: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Look of the installer. The mode (dark, light or high contrast) comes from config.general.theme
+// and is a class on the app root. The installation page also takes the pack's own colors from
+// `tab_color` and `tab_background` in its universal manifest, the fields the old per-branch tabs
+// used, as CSS variables on the page. theme-styles.css uses both.
+
+// Value in config.general.theme and its name in the settings. "default" is the dark look the
+// installer always had, so configs from before the modes keep it
+pub const MODES: &[(&str, &str)] = &[
+    ("default", "Dark"),
+    ("light", "Light"),
+    ("high-contrast", "High contrast"),
+];
+
+/// Class for the app root, unknown values get the dark look.
+pub fn mode_class(theme: &str) -> &'static str {
+    match theme {
+        "light" => "theme-light",
+        "high-contrast" => "theme-high-contrast",
+        _ => "theme-dark",
+    }
+}
+
+// `#rgb` or `#rrggbb`
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().strip_prefix('#')?;
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        3 => {
+            let mut digits = hex.chars().map(|c| c.to_string().repeat(2));
+            Some((channel(&digits.next()?)?, channel(&digits.next()?)?, channel(&digits.next()?)?))
+        }
+        6 => Some((channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+        _ => None,
+    }
+}
+
+// The value ends up inside url("..."), anything that could break out of it is refused
+fn is_safe_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://"))
+        && !url.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | '\\' | ';' | '<' | '>'))
+}
+
+fn mix(channel: u8, target: u8, amount: f64) -> u8 {
+    (channel as f64 + (target as f64 - channel as f64) * amount).round() as u8
+}
+
+/// Inline style with the pack's colors, empty when the manifest has none or they're invalid.
+pub fn accent_variables(tab_color: Option<&str>, tab_background: Option<&str>) -> String {
+    let mut variables = Vec::new();
+    if let Some((r, g, b)) = tab_color.and_then(parse_hex_color) {
+        // Pack colors are often very dark backgrounds, text and borders need a lighter version
+        let (lr, lg, lb) = (mix(r, 255, 0.45), mix(g, 255, 0.45), mix(b, 255, 0.45));
+        variables.push(format!("--accent-color: #{:02x}{:02x}{:02x};", r, g, b));
+        variables.push(format!("--accent-rgb: {}, {}, {};", r, g, b));
+        variables.push(format!("--accent-light: #{:02x}{:02x}{:02x};", lr, lg, lb));
+        variables.push(format!("--accent-light-rgb: {}, {}, {};", lr, lg, lb));
+    }
+    if let Some(url) = tab_background.map(str::trim).filter(|url| is_safe_url(url)) {
+        variables.push(format!("--pack-background: url(\"{}\");", url));
+    }
+    variables.join(" ")
+}
@@ -0,0 +1,366 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::installation::{
+    get_installations_dir, load_installations_index, save_installations_index, Installation,
+    InstallationsIndex,
+};
+use crate::Config;
+
+// Moving to a new PC. Everything the installer keeps locally (config, the installation registry,
+// each installation's feature profiles and optionally backups) goes into one zip. Presets are always
+// fetched from the pack's source, installations only keep the preset id and their changes to it
+// in installation.json, so nothing else is needed for them. Game files are not exported, the next
+// install downloads them again.
+
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+const BUNDLE_MANIFEST: &str = "bundle.json";
+const BUNDLE_CONFIG: &str = "config.json";
+const BUNDLE_INSTALLATIONS: &str = "installations";
+// Per installation, next to installation.json. Older exports don't have it
+const BUNDLE_PROFILES: &str = "profiles.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub installer_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub source_os: String,
+    pub installations: Vec<String>,
+    pub includes_backups: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    pub imported: Vec<String>,
+    // Ids that already exist on this machine, we never overwrite those
+    pub skipped: Vec<String>,
+    pub warnings: Vec<String>,
+    pub config_imported: bool,
+}
+
+fn zip_options(path: &str) -> zip::write::FileOptions<'static, ()> {
+    // Backup chunks are already zstd compressed, deflating them again only burns time
+    let method = if path.ends_with(".zst") {
+        CompressionMethod::Stored
+    } else {
+        CompressionMethod::Deflated
+    };
+    zip::write::FileOptions::<()>::default()
+        .compression_method(method)
+        .large_file(true)
+}
+
+fn add_bytes<W: Write + io::Seek>(zip: &mut ZipWriter<W>, name: &str, bytes: &[u8]) -> Result<(), String> {
+    zip.start_file(name, zip_options(name))
+        .map_err(|e| format!("Failed to add {} to the export: {}", name, e))?;
+    zip.write_all(bytes)
+        .map_err(|e| format!("Failed to write {} to the export: {}", name, e))
+}
+
+fn add_tree<W: Write + io::Seek>(zip: &mut ZipWriter<W>, root: &Path, prefix: &str) -> Result<usize, String> {
+    let mut count = 0;
+    for path in crate::hashing::walk_files(root)? {
+        let relative = path.strip_prefix(root).map(Path::to_path_buf).unwrap_or_default();
+        let name = format!(
+            "{}/{}",
+            prefix,
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        );
+        zip.start_file(name.as_str(), zip_options(&name))
+            .map_err(|e| format!("Failed to add {} to the export: {}", name, e))?;
+        let mut file = fs::File::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        io::copy(&mut file, zip).map_err(|e| format!("Failed to write {} to the export: {}", name, e))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Writes config, the installation registry and optionally every installation's backups to `dest`.
+pub fn export_app_state(config: &Config, dest: &Path, include_backups: bool) -> Result<BundleManifest, String> {
+    let index = load_installations_index().map_err(|e| format!("Failed to load installations index: {}", e))?;
+    let installations_dir = get_installations_dir();
+
+    let file = fs::File::create(dest).map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+
+    let mut exported = Vec::new();
+    for id in &index.installations {
+        let installation_json = installations_dir.join(id).join("installation.json");
+        let bytes = match fs::read(&installation_json) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Not exporting installation {}, failed to read it: {}", id, e);
+                continue;
+            }
+        };
+        add_bytes(&mut zip, &format!("{}/{}/installation.json", BUNDLE_INSTALLATIONS, id), &bytes)?;
+
+        match fs::read(installations_dir.join(id).join(BUNDLE_PROFILES)) {
+            Ok(profiles) => add_bytes(&mut zip, &format!("{}/{}/{}", BUNDLE_INSTALLATIONS, id, BUNDLE_PROFILES), &profiles)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Not exporting the feature profiles of {}, failed to read them: {}", id, e),
+        }
+
+        let backups_dir = installations_dir.join(id).join("backups");
+        if include_backups && backups_dir.is_dir() {
+            let count = add_tree(&mut zip, &backups_dir, &format!("{}/{}/backups", BUNDLE_INSTALLATIONS, id))?;
+            debug!("Exported {} backup files for installation {}", count, id);
+        }
+        exported.push(id.clone());
+    }
+
+    // Only list what actually made it into the bundle
+    let exported_index = InstallationsIndex {
+        installations: exported.clone(),
+        active_installation: index.active_installation.filter(|id| exported.contains(id)),
+        last_active: index.last_active,
+    };
+    let index_json = serde_json::to_vec_pretty(&exported_index)
+        .map_err(|e| format!("Failed to serialize installations index: {}", e))?;
+    add_bytes(&mut zip, &format!("{}/index.json", BUNDLE_INSTALLATIONS), &index_json)?;
+
+    // The bundle is a plain zip that may be handed to someone, secrets stay on this computer and the
+    // import keeps the ones of the computer it lands on
+    let mut config = config.clone();
+    config.network.proxy_password = None;
+    config.network.curseforge_api_key = None;
+    config.network.github_token = None;
+    config.lock = crate::lock::LockSettings::default();
+    let config_json = serde_json::to_vec_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    add_bytes(&mut zip, BUNDLE_CONFIG, &config_json)?;
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        installer_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        source_os: std::env::consts::OS.to_string(),
+        installations: exported,
+        includes_backups: include_backups,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize export manifest: {}", e))?;
+    add_bytes(&mut zip, BUNDLE_MANIFEST, &manifest_json)?;
+
+    zip.finish().map_err(|e| format!("Failed to finish export: {}", e))?;
+    info!(
+        "Exported {} installations to {} (backups: {})",
+        manifest.installations.len(),
+        dest.display(),
+        include_backups
+    );
+    Ok(manifest)
+}
+
+fn read_entry<R: Read + io::Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Vec<u8>, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("Export is missing {}: {}", name, e))?;
+    let mut bytes = Vec::new();
+    entry
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {} from the export: {}", name, e))?;
+    Ok(bytes)
+}
+
+/// Reads the manifest without importing anything, so the UI can show what's in a bundle.
+pub fn read_manifest(archive_path: &Path) -> Result<BundleManifest, String> {
+    let file = fs::File::open(archive_path).map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a valid export: {}", e))?;
+    let manifest: BundleManifest = serde_json::from_slice(&read_entry(&mut archive, BUNDLE_MANIFEST)?)
+        .map_err(|e| format!("Invalid export manifest: {}", e))?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "This export was made by a newer installer (format {}), please update first",
+            manifest.format_version
+        ));
+    }
+    Ok(manifest)
+}
+
+// Launcher paths from the old machine usually don't exist here, fall back to what's configured now
+fn relink_launcher(launcher: &str, fallback: &str, what: &str, warnings: &mut Vec<String>) -> String {
+    if crate::get_launcher(launcher).is_ok() {
+        return launcher.to_string();
+    }
+    let message = format!(
+        "Launcher '{}' of {} is not available on this machine, using '{}' instead",
+        launcher, what, fallback
+    );
+    warn!("{}", message);
+    warnings.push(message);
+    fallback.to_string()
+}
+
+// Extracts everything under `prefix` into `dest`, see safe_extract.rs for what's rejected
+fn extract_prefix<R: Read + io::Seek>(archive: &mut ZipArchive<R>, prefix: &str, dest: &Path) -> Result<usize, String> {
+    let written = crate::safe_extract::extract_all(
+        archive,
+        dest,
+        |name| name.strip_prefix(prefix).ok().map(Path::to_path_buf),
+        |_, _| {},
+    )?;
+    Ok(written.len())
+}
+
+/// Imports a bundle made by `export_app_state`. Installation paths are rewritten for this machine and
+/// every imported installation is marked as not installed, so installing it recreates the game files
+/// and the launcher profile. When `import_config` is set the returned config should replace the
+/// current one, the caller is responsible for saving it.
+pub fn import_app_state(
+    archive_path: &Path,
+    current_config: &Config,
+    import_config: bool,
+) -> Result<(ImportSummary, Option<Config>), String> {
+    let manifest = read_manifest(archive_path)?;
+    debug!(
+        "Importing export from installer {} ({}), made {}",
+        manifest.installer_version, manifest.source_os, manifest.exported_at
+    );
+
+    let file = fs::File::open(archive_path).map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a valid export: {}", e))?;
+    let mut summary = ImportSummary::default();
+
+    let new_config = if import_config {
+        let mut config: Config = serde_json::from_slice(&read_entry(&mut archive, BUNDLE_CONFIG)?)
+            .map_err(|e| format!("Invalid config in export: {}", e))?;
+        config.launcher = relink_launcher(&config.launcher, &current_config.launcher, "the settings", &mut summary.warnings);
+        // The old cache folder is an absolute path on the old machine
+        if let Some(cache_dir) = &config.storage.cache_dir {
+            if !Path::new(cache_dir).is_dir() {
+                summary.warnings.push(format!("Cache folder '{}' doesn't exist here, using the default", cache_dir));
+                config.storage.cache_dir = None;
+            }
+        }
+        if let Some(shared_dir) = &config.storage.shared_cache_dir {
+            if !Path::new(shared_dir).is_dir() {
+                summary.warnings.push(format!("Shared cache folder '{}' doesn't exist here, it was turned off", shared_dir));
+                config.storage.shared_cache_dir = None;
+            }
+        }
+        // Not exported, see export_app_state
+        config.network.proxy_password = current_config.network.proxy_password.clone();
+        config.network.curseforge_api_key = current_config.network.curseforge_api_key.clone();
+        config.network.github_token = current_config.network.github_token.clone();
+        config.lock = current_config.lock.clone();
+        if config.watch.installation_id.as_ref().map_or(false, |id| !manifest.installations.contains(id)) {
+            config.watch.installation_id = None;
+            config.watch.enabled = false;
+        }
+        config.first_launch = Some(false);
+        summary.config_imported = true;
+        Some(config)
+    } else {
+        None
+    };
+
+    let installations_dir = get_installations_dir();
+    let mut index = load_installations_index().map_err(|e| format!("Failed to load installations index: {}", e))?;
+    let launcher_fallback = new_config
+        .as_ref()
+        .map(|c| c.launcher.clone())
+        .unwrap_or_else(|| current_config.launcher.clone());
+
+    for id in &manifest.installations {
+        // Ids end up in paths, a crafted bundle could point them outside the installations folder
+        if id.contains(['/', '\\']) || crate::validate_safe_path(&installations_dir, id).is_err() {
+            warn!("Not importing installation with invalid id {:?}", id);
+            summary.warnings.push(format!("Installation id {:?} is not valid, skipped", id));
+            continue;
+        }
+        if index.installations.contains(id) || installations_dir.join(id).exists() {
+            debug!("Installation {} already exists, not importing it", id);
+            summary.skipped.push(id.clone());
+            continue;
+        }
+
+        let entry_name = format!("{}/{}/installation.json", BUNDLE_INSTALLATIONS, id);
+        let mut installation: Installation = match read_entry(&mut archive, &entry_name)
+            .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| format!("Invalid installation {}: {}", id, e)))
+        {
+            Ok(installation) => installation,
+            Err(e) => {
+                warn!("{}", e);
+                summary.warnings.push(e);
+                continue;
+            }
+        };
+        if installation.id != *id {
+            summary.warnings.push(format!("Installation {} has a mismatched id, skipped", id));
+            continue;
+        }
+
+        installation.installation_path = installations_dir.join(id);
+        // A custom game dir is a path on the other machine, the files come back in the default one
+        if let Some(dir) = installation.custom_game_dir.take() {
+            summary.warnings.push(format!(
+                "'{}' kept its game files in {} on the other computer, it uses the default location here",
+                installation.name,
+                dir.display()
+            ));
+        }
+        installation.launcher_type = relink_launcher(
+            &installation.launcher_type,
+            &launcher_fallback,
+            &format!("'{}'", installation.name),
+            &mut summary.warnings,
+        );
+        // Nothing is on disk yet, the next install puts the selection back and creates the launcher profile
+        if installation.pending_features.is_empty() {
+            installation.pending_features = installation.enabled_features.clone();
+        }
+        installation.installed_features.clear();
+        installation.mark_as_fresh();
+
+        installation.save()?;
+        let profiles_entry = format!("{}/{}/{}", BUNDLE_INSTALLATIONS, id, BUNDLE_PROFILES);
+        if archive.index_for_name(&profiles_entry).is_some() {
+            let imported = read_entry(&mut archive, &profiles_entry).and_then(|bytes| {
+                let profiles: Vec<crate::feature_profiles::FeatureProfile> = serde_json::from_slice(&bytes)
+                    .map_err(|e| format!("Invalid feature profiles: {}", e))?;
+                crate::feature_profiles::write(id, &profiles)
+            });
+            if let Err(e) = imported {
+                warn!("{}", e);
+                summary.warnings.push(format!("The feature profiles of '{}' were not imported: {}", installation.name, e));
+            }
+        }
+        if manifest.includes_backups {
+            let prefix = format!("{}/{}/backups", BUNDLE_INSTALLATIONS, id);
+            let count = extract_prefix(&mut archive, &prefix, &installation.installation_path.join("backups"))?;
+            debug!("Imported {} backup files for {}", count, installation.name);
+        }
+
+        index.installations.push(id.clone());
+        summary.imported.push(installation.name.clone());
+    }
+
+    if index.active_installation.is_none() {
+        index.active_installation = manifest.installations.iter().find(|id| index.installations.contains(id)).cloned();
+    }
+    index.last_active = Some(Utc::now());
+    save_installations_index(&index).map_err(|e| format!("Failed to save installations index: {}", e))?;
+
+    info!(
+        "Imported {} installations ({} skipped, {} warnings)",
+        summary.imported.len(),
+        summary.skipped.len(),
+        summary.warnings.len()
+    );
+    Ok((summary, new_config))
+}
+
+pub fn default_export_name() -> PathBuf {
+    PathBuf::from(format!("wynncraft-overhaul-export-{}.zip", Utc::now().format("%Y-%m-%d")))
+}
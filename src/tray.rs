@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use tray_icon::menu::{IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{ClickType, Icon, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::installation::{load_all_installations, Installation};
+use crate::CachedHttpClient;
+
+// Tray mode: closing the window only hides it, the installer keeps running in the tray and checks
+// installations for updates every few hours. New updates pop a native notification and enable
+// "Update now" in the tray menu, which opens the window on the installation and starts the
+// update. Taken from the config at startup, switching it needs a restart.
+
+// How often the GUI looks at the tray's events
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrayAction {
+    Open,
+    CheckNow,
+    // Installation to update, the first one with an update
+    UpdateNow(Option<String>),
+    Quit,
+}
+
+// Actions from elsewhere than the menu, the notification's button on Linux
+static QUEUED: Mutex<Vec<TrayAction>> = Mutex::new(Vec::new());
+
+pub struct Tray {
+    icon: TrayIcon,
+    open: MenuItem,
+    check: MenuItem,
+    update: MenuItem,
+    quit: MenuItem,
+    // Installations with an update, in the order they were found
+    pending: Mutex<Vec<String>>,
+}
+
+impl Tray {
+    /// Adds the tray icon, has to run on the main thread (the GUI's).
+    pub fn create() -> Result<Tray, String> {
+        let image = image::load_from_memory(include_bytes!("assets/icon.png"))
+            .map_err(|e| format!("Failed to load the tray icon: {}", e))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        let icon = Icon::from_rgba(image.into_raw(), width, height).map_err(|e| format!("Invalid tray icon: {}", e))?;
+
+        let open = MenuItem::new("Open", true, None);
+        let check = MenuItem::new("Check for updates", true, None);
+        let update = MenuItem::new("Update now", false, None);
+        let quit = MenuItem::new("Quit", true, None);
+        let menu = Menu::new();
+        let separator = PredefinedMenuItem::separator();
+        for item in [&open as &dyn IsMenuItem, &check, &update, &separator, &quit] {
+            menu.append(item).map_err(|e| format!("Failed to build the tray menu: {}", e))?;
+        }
+
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("Majestic Overhaul Launcher")
+            .with_icon(icon)
+            .build()
+            .map_err(|e| format!("Failed to add the tray icon: {}", e))?;
+        info!("Running in the tray");
+        Ok(Tray { icon, open, check, update, quit, pending: Mutex::new(Vec::new()) })
+    }
+
+    /// The next thing the user asked for, None when nothing happened.
+    pub fn poll(&self) -> Option<TrayAction> {
+        if let Some(action) = QUEUED.lock().unwrap().pop() {
+            return Some(action);
+        }
+        if let Ok(event) = MenuEvent::receiver().try_recv() {
+            let action = if event.id == *self.open.id() {
+                TrayAction::Open
+            } else if event.id == *self.check.id() {
+                TrayAction::CheckNow
+            } else if event.id == *self.update.id() {
+                TrayAction::UpdateNow(self.pending.lock().unwrap().first().cloned())
+            } else if event.id == *self.quit.id() {
+                TrayAction::Quit
+            } else {
+                return None;
+            };
+            return Some(action);
+        }
+        match TrayIconEvent::receiver().try_recv() {
+            Ok(event) if event.click_type == ClickType::Double || (cfg!(windows) && event.click_type == ClickType::Left) => {
+                Some(TrayAction::Open)
+            }
+            _ => None,
+        }
+    }
+
+    /// Shows the installations that have an update, returns the ones that are new since last time.
+    pub fn set_pending(&self, with_update: &[Installation]) -> Vec<Installation> {
+        let mut pending = self.pending.lock().unwrap();
+        let known: HashSet<String> = pending.iter().cloned().collect();
+        let new: Vec<Installation> = with_update.iter().filter(|i| !known.contains(&i.id)).cloned().collect();
+        *pending = with_update.iter().map(|i| i.id.clone()).collect();
+
+        self.update.set_enabled(!pending.is_empty());
+        self.update.set_text(match with_update {
+            [] => "Update now".to_string(),
+            [only] => format!("Update {} now", only.name),
+            _ => format!("Update now ({} installations)", with_update.len()),
+        });
+        let tooltip = match with_update.len() {
+            0 => "Majestic Overhaul Launcher".to_string(),
+            1 => "Majestic Overhaul Launcher - 1 update available".to_string(),
+            count => format!("Majestic Overhaul Launcher - {} updates available", count),
+        };
+        if let Err(e) = self.icon.set_tooltip(Some(tooltip)) {
+            debug!("Failed to update the tray tooltip: {}", e);
+        }
+        new
+    }
+}
+
+/// Checks every installed installation for modpack and preset updates, returns the ones that have one.
+pub async fn check_for_updates(http_client: &CachedHttpClient) -> Vec<Installation> {
+    let installations = match load_all_installations() {
+        Ok(installations) => installations,
+        Err(e) => {
+            warn!("Background update check couldn't load the installations: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut with_update = Vec::new();
+    for mut installation in installations.into_iter().filter(|i| i.installed) {
+        let presets = match installation.load_presets(http_client).await {
+            Ok(presets) => presets,
+            Err(e) => {
+                warn!("Background update check of {} failed: {}", installation.name, e);
+                continue;
+            }
+        };
+        match installation.check_for_updates(http_client, &presets).await {
+            Ok(true) => with_update.push(installation),
+            Ok(false) => {}
+            Err(e) => warn!("Background update check of {} failed: {}", installation.name, e),
+        }
+    }
+    debug!("Background update check: {} installation(s) with an update", with_update.len());
+    with_update
+}
+
+/// Native notification about `new` updates. On Linux it has an "Update now" button.
+pub fn notify_updates(new: &[Installation]) {
+    let (summary, body) = match new {
+        [] => return,
+        [only] => (
+            format!("{} has an update", only.name),
+            "Pick \"Update now\" in the tray menu to install it.".to_string(),
+        ),
+        _ => (
+            format!("{} installations have an update", new.len()),
+            new.iter().map(|i| i.name.as_str()).collect::<Vec<_>>().join(", "),
+        ),
+    };
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let first = new[0].id.clone();
+        std::thread::spawn(move || {
+            let shown = notify_rust::Notification::new()
+                .summary(&summary)
+                .body(&body)
+                .appname("Majestic Overhaul Launcher")
+                .action("update", "Update now")
+                .show();
+            match shown {
+                Ok(handle) => handle.wait_for_action(|action| {
+                    if action == "update" {
+                        QUEUED.lock().unwrap().push(TrayAction::UpdateNow(Some(first)));
+                    }
+                }),
+                Err(e) => warn!("Failed to show desktop notification: {}", e),
+            }
+        });
+    }
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    crate::watch_mode::notify(&summary, &body);
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use log::{debug, error};
 
@@ -34,8 +35,74 @@ pub struct ModComponent {
     pub dependencies: Option<Vec<String>>,
     #[serde(default)]
     pub incompatibilities: Option<Vec<String>>,
+    // Components that can't be enabled together with this one, checked from both sides like
+    // incompatibilities. Picking this one turns the others off
+    #[serde(default)]
+    pub conflicts_with: Vec<String>,
+    // At least one of these has to be enabled too (one of several minimap mods, any shader loader)
+    #[serde(default)]
+    pub requires_any: Vec<String>,
     #[serde(default = "default_false")]
     pub ignore_update: bool,
+    // Note the user has to explicitly confirm before enabling this component
+    #[serde(default)]
+    pub acknowledgement: Option<String>,
+    // Longer markdown description shown in the details pane, `description` stays the card summary
+    #[serde(default)]
+    pub details: Option<String>,
+    // Project page, defaults to the Modrinth page for Modrinth components
+    #[serde(default)]
+    pub homepage: Option<String>,
+    // Markdown notes on what changed in `version`, shown in the component's details
+    #[serde(default)]
+    pub changelog: Option<String>,
+    // More places to read about the component (source code, wiki, issue tracker)
+    #[serde(default)]
+    pub links: Vec<ComponentLink>,
+    // Experimental, only listed for installations that opted in to experimental features
+    #[serde(default)]
+    pub hidden: bool,
+    // Big optional content (the music pack) that installed packs download and delete right when
+    // it's toggled instead of on the next update
+    #[serde(default)]
+    pub on_demand: bool,
+    // Minecraft version -> what to install for it, see UniversalManifest::minecraft_versions
+    #[serde(default)]
+    pub versions: HashMap<String, ComponentVersion>,
+    // Other places to get the same file from, tried in order when the download fails
+    #[serde(default)]
+    pub mirrors: Vec<Mirror>,
+    // Download size in bytes, optional since most sources can't be asked cheaply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentLink {
+    pub name: String,
+    pub url: String,
+}
+
+impl ModComponent {
+    /// Ids this component lists as conflicting, from conflicts_with and incompatibilities.
+    pub fn conflicts(&self) -> impl Iterator<Item = &String> {
+        self.conflicts_with.iter().chain(self.incompatibilities.iter().flatten())
+    }
+
+    /// False when requires_any lists options and none of them is in `enabled`.
+    pub fn requires_any_met(&self, enabled: &[String]) -> bool {
+        self.requires_any.is_empty() || self.requires_any.iter().any(|id| enabled.contains(id))
+    }
+
+    pub fn project_page_url(&self) -> Option<String> {
+        if let Some(homepage) = &self.homepage {
+            return Some(homepage.clone());
+        }
+        match self.source.as_str() {
+            "modrinth" => Some(format!("https://modrinth.com/project/{}", self.location)),
+            _ => None,
+        }
+    }
 }
 
 // NEW: RemoteIncludeComponent structure
@@ -66,6 +133,46 @@ pub struct RemoteIncludeComponent {
     pub dependencies: Option<Vec<String>>,
     #[serde(default = "default_false")]
     pub ignore_update: bool,
+    #[serde(default)]
+    pub acknowledgement: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub versions: HashMap<String, ComponentVersion>,
+    #[serde(default)]
+    pub mirrors: Vec<Mirror>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+// A component's build for another Minecraft version, the location only when it moved
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentVersion {
+    #[serde(default)]
+    pub location: Option<String>,
+    pub version: String,
+    // Mirrors of the moved location, the component's own point at the old file
+    #[serde(default)]
+    pub mirrors: Vec<Mirror>,
+}
+
+// Another place a component's file can be downloaded from. Usually a direct link (a GitHub release
+// asset, our own server) for when the primary source is down or rate limits us, but any source
+// the component itself could use works
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Mirror {
+    #[serde(default = "default_mirror_source")]
+    pub source: String,
+    pub location: String,
+}
+
+// A Minecraft version installations can pick besides the manifest's own
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct MinecraftVersionOption {
+    pub version: String,
+    // Loader version for it, the manifest's loader version when missing
+    #[serde(default)]
+    pub loader_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -111,6 +218,135 @@ pub struct UniversalManifest {
     pub min_mem: Option<i32>,
     #[serde(default)]
     pub java_args: Option<String>,
+
+    // Capabilities the installer must (or may) support to handle this manifest
+    #[serde(default)]
+    pub capabilities: Option<crate::capabilities::ManifestCapabilities>,
+
+    // Wynncraft release this pack version was tested against, e.g. "2.1"
+    #[serde(default)]
+    pub wynncraft_version: Option<String>,
+
+    // Icons users can pick for their installation's launcher profile
+    #[serde(default)]
+    pub icons: Vec<PackIcon>,
+
+    // Curated mod settings (e.g. Wynntils HUD layouts) users can pick when creating an installation
+    #[serde(default)]
+    pub config_presets: Vec<ConfigPreset>,
+
+    // "Shader quality" selector, shown instead of the shaderpacks the tiers pick between
+    #[serde(default)]
+    pub shader_tiers: Vec<ShaderTier>,
+
+    // Other Minecraft versions installations can switch to, components list their builds for
+    // them in `versions`
+    #[serde(default)]
+    pub minecraft_versions: Vec<MinecraftVersionOption>,
+
+    // Protected config paths (see config_protection.rs) this version replaces on purpose, e.g. a
+    // Wynntils config that changed format. Updates don't restore the user's copy of these
+    #[serde(default)]
+    pub config_overrides: Vec<String>,
+
+    // Multiplayer servers added to the installation's server list
+    #[serde(default)]
+    pub servers: Vec<ServerEntry>,
+
+    // Single values changed in mod configs after the includes, see config_patches.rs
+    #[serde(default)]
+    pub config_patches: Vec<crate::config_patches::ConfigPatch>,
+
+    // Default resource pack order, component ids with the highest priority first
+    #[serde(default)]
+    pub resourcepack_order: Vec<String>,
+
+    // Json or RSS feed shown as announcements on the home page, see news.rs
+    #[serde(default)]
+    pub news_feed: Option<String>,
+
+    // Accent color (#rrggbb) and background image of the installation page, see theme.rs
+    #[serde(default)]
+    pub tab_color: Option<String>,
+    #[serde(default)]
+    pub tab_background: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PackIcon {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ConfigPreset {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub files: Vec<ConfigPresetFile>,
+}
+
+// A json file whose values are merged over the config file at `path` (relative to the game directory)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ConfigPresetFile {
+    pub path: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ShaderTier {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    // Shaderpack ids this tier enables, the ones of every other tier are turned off. Empty for "Off"
+    #[serde(default)]
+    pub shaderpacks: Vec<String>,
+    #[serde(default)]
+    pub settings: Vec<ShaderTierSetting>,
+}
+
+// A key=value line set in a properties file (e.g. config/iris.properties), relative to the game directory
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ShaderTierSetting {
+    pub path: String,
+    pub key: String,
+    pub value: String,
+}
+
+// A servers.dat entry, see servers.rs. With an `id` it's only added when that feature is enabled
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ServerEntry {
+    pub name: String,
+    pub address: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    // Server resource packs: Some(true) always accepts, Some(false) never, None asks
+    #[serde(default)]
+    pub accept_textures: Option<bool>,
+}
+
+impl ServerEntry {
+    pub fn is_enabled(&self, enabled_features: &[String]) -> bool {
+        match &self.id {
+            None => true,
+            Some(id) => id == "default" || enabled_features.contains(id),
+        }
+    }
+}
+
+/// Every shaderpack some tier picks, these aren't listed as separate features.
+pub fn shader_tier_components(tiers: &[ShaderTier]) -> HashSet<String> {
+    tiers.iter().flat_map(|tier| tier.shaderpacks.iter().cloned()).collect()
+}
+
+/// The tier whose shaderpacks are exactly the enabled ones, None for a selection no tier matches.
+pub fn active_shader_tier<'a>(tiers: &'a [ShaderTier], enabled_features: &[String]) -> Option<&'a ShaderTier> {
+    let tier_components = shader_tier_components(tiers);
+    let enabled: HashSet<&String> = enabled_features.iter().filter(|id| tier_components.contains(*id)).collect();
+    tiers.iter().find(|tier| tier.shaderpacks.iter().collect::<HashSet<_>>() == enabled)
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -137,22 +373,34 @@ pub struct IncludeComponent {
     pub category: Option<String>,
     #[serde(default)]
     pub dependencies: Option<Vec<String>>,
+    #[serde(default)]
+    pub acknowledgement: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub force_keys: Vec<String>,
 }
 
 fn default_empty_string() -> String {
     String::new()
 }
 
+fn default_mirror_source() -> String {
+    "ddl".to_string()
+}
+
 fn default_false() -> bool {
     false
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ManifestError {
     pub message: String,
     pub error_type: ManifestErrorType,
     pub file_name: String,
     pub raw_content: Option<String>,
+    // Where in the file the problem is, see manifest_validation.rs
+    pub location: Option<crate::manifest_validation::ErrorLocation>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -166,7 +414,11 @@ pub enum ManifestErrorType {
 
 impl std::fmt::Display for ManifestError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} in {}: {}", self.error_type, self.file_name, self.message)
+        write!(f, "{} in {}: {}", self.error_type, self.file_name, self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location.describe())?;
+        }
+        Ok(())
     }
 }
 
@@ -182,7 +434,173 @@ impl std::fmt::Display for ManifestErrorType {
     }
 }
 
+// What's wrong with a set of enabled features, see UniversalManifest::validate_selection
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectionProblem {
+    // Both enabled, at least one lists the other as a conflict
+    Conflict(String, String),
+    // Component, the options of its requires_any
+    MissingAlternative(String, Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionError(pub Vec<SelectionProblem>);
+
+impl std::fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let problems: Vec<String> = self.0.iter()
+            .map(|problem| match problem {
+                SelectionProblem::Conflict(a, b) => format!("'{}' conflicts with '{}'", a, b),
+                SelectionProblem::MissingAlternative(id, options) => {
+                    format!("'{}' needs one of {}", id, options.join(", "))
+                }
+            })
+            .collect();
+        write!(f, "The selected features don't work together: {}", problems.join("; "))
+    }
+}
+
 impl UniversalManifest {
+    fn components(&self) -> impl Iterator<Item = &ModComponent> {
+        self.mods.iter().chain(self.shaderpacks.iter()).chain(self.resourcepacks.iter())
+    }
+
+    pub fn component(&self, id: &str) -> Option<&ModComponent> {
+        self.components().find(|c| c.id == id)
+    }
+
+    /// True when `a` and `b` can't be enabled together, whichever of them lists the other.
+    pub fn conflict_between(&self, a: &str, b: &str) -> bool {
+        a != b
+            && (self.component(a).map_or(false, |c| c.conflicts().any(|id| id == b))
+                || self.component(b).map_or(false, |c| c.conflicts().any(|id| id == a)))
+    }
+
+    /// Enabled components that conflict with `id`.
+    pub fn conflicts_in(&self, id: &str, enabled: &[String]) -> Vec<String> {
+        enabled.iter().filter(|other| self.conflict_between(id, other)).cloned().collect()
+    }
+
+    /// Declared download size of what `enabled` installs, and how many of those components
+    /// don't declare one.
+    pub fn selection_size(&self, enabled: &[String]) -> (u64, usize) {
+        let wanted = |id: &str, optional: bool| id == "default" || !optional || enabled.iter().any(|f| f == id);
+        let sizes: Vec<Option<u64>> = self.components()
+            .filter(|c| wanted(&c.id, c.optional))
+            .map(|c| c.size_bytes)
+            .chain(self.remote_include.iter().filter(|r| wanted(&r.id, r.optional)).map(|r| r.size_bytes))
+            .collect();
+        (sizes.iter().flatten().sum(), sizes.iter().filter(|s| s.is_none()).count())
+    }
+
+    /// Checks the final feature list before anything is downloaded.
+    pub fn validate_selection(&self, enabled: &[String]) -> Result<(), SelectionError> {
+        let mut problems = Vec::new();
+        for (i, a) in enabled.iter().enumerate() {
+            for b in &enabled[i + 1..] {
+                if self.conflict_between(a, b) {
+                    problems.push(SelectionProblem::Conflict(a.clone(), b.clone()));
+                }
+            }
+        }
+        for component in self.components().filter(|c| enabled.contains(&c.id)) {
+            if !component.requires_any_met(enabled) {
+                problems.push(SelectionProblem::MissingAlternative(component.id.clone(), component.requires_any.clone()));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SelectionError(problems))
+        }
+    }
+
+    /// The manifest's own Minecraft version first, then the ones installations can switch to.
+    pub fn selectable_minecraft_versions(&self) -> Vec<String> {
+        let mut versions = vec![self.minecraft_version.clone()];
+        for option in &self.minecraft_versions {
+            if !versions.contains(&option.version) {
+                versions.push(option.version.clone());
+            }
+        }
+        versions
+    }
+
+    /// Names of the components in `features` (and the required ones) without a build for `minecraft_version`.
+    pub fn incompatible_components(&self, minecraft_version: &str, features: &[String]) -> Vec<String> {
+        if minecraft_version == self.minecraft_version {
+            return Vec::new();
+        }
+        let wanted = |id: &str, optional: bool| !optional || features.iter().any(|f| f == id);
+        let mut names: Vec<String> = self
+            .mods
+            .iter()
+            .chain(&self.shaderpacks)
+            .chain(&self.resourcepacks)
+            .filter(|c| wanted(&c.id, c.optional) && !c.versions.contains_key(minecraft_version))
+            .map(|c| c.name.clone())
+            .collect();
+        names.extend(
+            self.remote_include
+                .iter()
+                .filter(|r| wanted(&r.id, r.optional) && !r.versions.contains_key(minecraft_version))
+                .map(|r| r.name.clone().unwrap_or_else(|| r.id.clone())),
+        );
+        names
+    }
+
+    /// The manifest as it installs for `minecraft_version`. Components pick their build for it,
+    /// the ones without one are left out, see incompatible_components.
+    pub fn for_minecraft_version(&self, minecraft_version: &str) -> Result<UniversalManifest, String> {
+        if minecraft_version == self.minecraft_version {
+            return Ok(self.clone());
+        }
+        let Some(option) = self.minecraft_versions.iter().find(|o| o.version == minecraft_version) else {
+            return Err(format!(
+                "{} doesn't support Minecraft {} anymore, switch the installation back to {}",
+                self.name, minecraft_version, self.minecraft_version
+            ));
+        };
+
+        let mut resolved = self.clone();
+        resolved.minecraft_version = minecraft_version.to_string();
+        resolved.loader.minecraft_version = minecraft_version.to_string();
+        if let Some(loader_version) = &option.loader_version {
+            resolved.loader.version = loader_version.clone();
+        }
+        let resolve = |components: &mut Vec<ModComponent>| {
+            components.retain_mut(|c| match c.versions.get(minecraft_version) {
+                Some(build) => {
+                    if let Some(location) = &build.location {
+                        c.location = location.clone();
+                        c.mirrors = build.mirrors.clone();
+                    }
+                    c.version = build.version.clone();
+                    true
+                }
+                None => {
+                    debug!("'{}' has no build for Minecraft {}, leaving it out", c.name, minecraft_version);
+                    false
+                }
+            });
+        };
+        resolve(&mut resolved.mods);
+        resolve(&mut resolved.shaderpacks);
+        resolve(&mut resolved.resourcepacks);
+        resolved.remote_include.retain_mut(|r| match r.versions.get(minecraft_version) {
+            Some(build) => {
+                if let Some(location) = &build.location {
+                    r.location = location.clone();
+                    r.mirrors = build.mirrors.clone();
+                }
+                r.version = build.version.clone();
+                true
+            }
+            None => false,
+        });
+        Ok(resolved)
+    }
+
     pub fn get_optional_includes(&self) -> Vec<&IncludeComponent> {
         self.include.iter()
             .filter(|include| include.optional && !include.id.is_empty())
@@ -196,6 +614,15 @@ impl UniversalManifest {
             .collect()
     }
     
+    /// Optional mods and packs that installed packs get right when they're toggled, see on_demand.rs
+    pub fn on_demand_components(&self) -> Vec<&ModComponent> {
+        self.mods.iter()
+            .chain(self.shaderpacks.iter())
+            .chain(self.resourcepacks.iter())
+            .filter(|c| c.optional && c.on_demand)
+            .collect()
+    }
+
     pub fn get_all_optional_components(&self) -> Vec<ModComponent> {
         let mut components = Vec::new();
         
@@ -237,7 +664,19 @@ impl UniversalManifest {
                 category: include.category.clone(), // Use the actual category
                 dependencies: include.dependencies.clone(),
                 incompatibilities: None,
+                conflicts_with: Vec::new(),
+                requires_any: Vec::new(),
                 ignore_update: include.ignore_update,
+                acknowledgement: include.acknowledgement.clone(),
+                details: None,
+                homepage: None,
+                changelog: None,
+                links: Vec::new(),
+                hidden: include.hidden,
+                on_demand: false,
+                versions: HashMap::new(),
+                mirrors: Vec::new(),
+                size_bytes: None,
             });
         }
         
@@ -257,7 +696,19 @@ impl UniversalManifest {
                 category: remote.category.clone(), // Use actual category
                 dependencies: remote.dependencies.clone(),
                 incompatibilities: None,
+                conflicts_with: Vec::new(),
+                requires_any: Vec::new(),
                 ignore_update: remote.ignore_update,
+                acknowledgement: remote.acknowledgement.clone(),
+                details: None,
+                homepage: None,
+                changelog: None,
+                links: Vec::new(),
+                hidden: remote.hidden,
+                on_demand: false,
+                versions: remote.versions.clone(),
+                mirrors: remote.mirrors.clone(),
+                size_bytes: remote.size_bytes,
             });
         }
         
@@ -285,7 +736,7 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
                 id: component.id.clone(),
                 name: component.name.clone(),
                 default: component.default_enabled,
-                hidden: false,
+                hidden: component.hidden,
                 description: component.description.clone(),
             });
         }
@@ -298,7 +749,7 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
                 id: component.id.clone(),
                 name: component.name.clone(),
                 default: component.default_enabled,
-                hidden: false,
+                hidden: component.hidden,
                 description: component.description.clone(),
             });
         }
@@ -310,7 +761,7 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
                 id: component.id.clone(),
                 name: component.name.clone(),
                 default: component.default_enabled,
-                hidden: false,
+                hidden: component.hidden,
                 description: component.description.clone(),
             });
         }
@@ -323,7 +774,7 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
                 id: include.id.clone(),
                 name: include.name.clone().unwrap_or_else(|| include.location.clone()),
                 default: include.default_enabled,
-                hidden: false,
+                hidden: include.hidden,
                 description: include.description.clone()
                     .or_else(|| Some(format!("Include: {}", include.location))),
             });
@@ -337,7 +788,7 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
                 id: remote.id.clone(),
                 name: remote.name.clone().unwrap_or_else(|| remote.id.clone()),
                 default: remote.default_enabled,
-                hidden: false,
+                hidden: remote.hidden,
                 description: remote.description.clone(),
             });
         }
@@ -354,6 +805,8 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
             id: component.id.clone(),
             authors: component.authors.clone(),
             ignore_update: component.ignore_update,
+            mirrors: component.mirrors.clone(),
+            size_bytes: component.size_bytes,
         }
     }).collect();
 
@@ -367,6 +820,8 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
             id: component.id.clone(),
             authors: component.authors.clone(),
             ignore_update: component.ignore_update,
+            mirrors: component.mirrors.clone(),
+            size_bytes: component.size_bytes,
         }
     }).collect();
 
@@ -380,6 +835,8 @@ pub fn universal_to_manifest(universal: &UniversalManifest, enabled_features: Ve
             id: component.id.clone(),
             authors: component.authors.clone(),
             ignore_update: component.ignore_update,
+            mirrors: component.mirrors.clone(),
+            size_bytes: component.size_bytes,
         }
     }).collect();
 
@@ -396,6 +853,7 @@ let includes: Vec<crate::Include> = universal.include.iter().map(|inc| {
         description: inc.description.clone(), // ADD this line
         category: inc.category.clone(), // ADD this line
         dependencies: inc.dependencies.clone(), // ADD this line
+        force_keys: inc.force_keys.clone(),
     }
 }).collect();
     
@@ -414,6 +872,8 @@ let includes: Vec<crate::Include> = universal.include.iter().map(|inc| {
                 // ADD THESE TWO LINES:
                 optional: remote.optional,
                 default_enabled: remote.default_enabled,
+                mirrors: remote.mirrors.clone(),
+                size_bytes: remote.size_bytes,
             }
         }).collect())
     };
@@ -426,8 +886,8 @@ let includes: Vec<crate::Include> = universal.include.iter().map(|inc| {
         subtitle: universal.subtitle.clone(),
         tab_group: None,
         tab_title: None,
-        tab_color: None,
-        tab_background: None,
+        tab_color: universal.tab_color.clone(),
+        tab_background: universal.tab_background.clone(),
         tab_primary_font: None,
         tab_secondary_font: None,
         settings_background: None,
@@ -454,12 +914,53 @@ let includes: Vec<crate::Include> = universal.include.iter().map(|inc| {
         category: universal.category.clone(),
         is_new: None,
         short_description: universal.short_description.clone(),
+        capabilities: universal.capabilities.clone(),
+        config_presets: universal.config_presets.clone(),
+        shader_tiers: universal.shader_tiers.clone(),
+        config_overrides: universal.config_overrides.clone(),
+        servers: universal.servers.clone(),
+        config_patches: universal.config_patches.clone(),
+        resourcepack_order: universal.resourcepack_order.clone(),
     }
 }
 
 // Rest of the existing functions remain the same...
+fn parse_universal_manifest(manifest_json: &str) -> Result<UniversalManifest, ManifestError> {
+    let manifest = crate::manifest_validation::parse::<UniversalManifest>(manifest_json, "universal.json").map_err(|e| {
+        error!("Failed to parse universal manifest JSON: {}", e);
+        e
+    })?;
+    if let Err(e) = crate::capabilities::check_manifest_capabilities(
+        manifest.manifest_version,
+        manifest.capabilities.as_ref(),
+    ) {
+        error!("Universal manifest is not supported: {}", e);
+        return Err(ManifestError {
+            message: e,
+            error_type: ManifestErrorType::ValidationError,
+            file_name: "universal.json".to_string(),
+            raw_content: Some(manifest_json.to_string()),
+            location: None,
+        });
+    }
+    debug!("Successfully loaded universal manifest for {}", manifest.name);
+    Ok(manifest)
+}
+
 pub async fn load_universal_manifest(http_client: &CachedHttpClient, url: Option<&str>) -> Result<UniversalManifest, ManifestError> {
-    let manifest_url = url.unwrap_or("https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/universal.json");
+    // Developer mode, see dev_manifest.rs
+    if let Some(local) = crate::dev_manifest::read_local("universal.json") {
+        let manifest_json = local.map_err(|e| ManifestError {
+            message: e,
+            error_type: ManifestErrorType::UnknownError,
+            file_name: "universal.json".to_string(),
+            raw_content: None,
+            location: None,
+        })?;
+        return parse_universal_manifest(&manifest_json);
+    }
+    let manifest_url = crate::dev_manifest::resolve_url(url.unwrap_or("https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/universal.json"));
+    let manifest_url = manifest_url.as_str();
     error!("MANIFEST URL BEING USED: {}", manifest_url);
     debug!("Loading universal manifest from: {}", manifest_url);
     
@@ -485,39 +986,12 @@ pub async fn load_universal_manifest(http_client: &CachedHttpClient, url: Option
                         error_type: ManifestErrorType::NetworkError,
                         file_name: "universal.json".to_string(),
                         raw_content: None,
+                        location: None,
                     });
                 }
                 
                 match response.text().await {
-                    Ok(manifest_json) => {
-                        let raw_content = Some(manifest_json.clone());
-                        
-                        if let Err(json_err) = serde_json::from_str::<serde_json::Value>(&manifest_json) {
-                            return Err(ManifestError {
-                                message: format!("Invalid JSON syntax: {}", json_err),
-                                error_type: ManifestErrorType::SyntaxError,
-                                file_name: "universal.json".to_string(),
-                                raw_content,
-                            });
-                        }
-                        
-                        match serde_json::from_str::<UniversalManifest>(&manifest_json) {
-                            Ok(manifest) => {
-                                debug!("Successfully loaded universal manifest for {}", manifest.name);
-                                return Ok(manifest);
-                            },
-                            Err(e) => {
-                                error!("Failed to parse universal manifest JSON: {}", e);
-                                
-                                return Err(ManifestError {
-                                    message: format!("Failed to parse universal manifest: {}", e),
-                                    error_type: ManifestErrorType::DeserializationError,
-                                    file_name: "universal.json".to_string(),
-                                    raw_content,
-                                });
-                            }
-                        }
-                    },
+                    Ok(manifest_json) => return parse_universal_manifest(&manifest_json),
                     Err(e) => {
                         error!("Failed to read universal manifest response: {}", e);
                         
@@ -533,6 +1007,7 @@ pub async fn load_universal_manifest(http_client: &CachedHttpClient, url: Option
                             error_type: ManifestErrorType::NetworkError,
                             file_name: "universal.json".to_string(),
                             raw_content: None,
+                            location: None,
                         });
                     }
                 }
@@ -552,6 +1027,7 @@ pub async fn load_universal_manifest(http_client: &CachedHttpClient, url: Option
                     error_type: ManifestErrorType::NetworkError,
                     file_name: "universal.json".to_string(),
                     raw_content: None,
+                    location: None,
                 });
             }
         }
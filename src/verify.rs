@@ -0,0 +1,387 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use isahc::http::StatusCode;
+use isahc::AsyncReadResponseExt;
+use log::{debug, info, warn};
+
+use crate::hashing::{self, HashAlgorithm};
+use crate::installation::{get_installations_dir, load_installation, Installation};
+use crate::{get_launcher, get_modpack_root, CachedHttpClient, Downloadable, Manifest};
+
+// "Verify Files" in the installation settings and the `verify` subcommand. Every file the local
+// manifest lists has to exist; mods, shaderpacks and resourcepacks also have to match the sha256
+// taken right after the install (installs from before that only get the existence check).
+// Repairing downloads just the broken files again. Include files are only brought back when
+// they're missing, an existing config is the player's and never overwritten.
+
+const HASHES_FILE: &str = "file-hashes.json";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Origin {
+    Mod,
+    Shaderpack,
+    Resourcepack,
+    Include,
+    // Remote include id
+    RemoteInclude(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    // e.g. "mods/sodium.jar"
+    pub relative: String,
+    // Mod or include it belongs to
+    pub component: String,
+    // Present but with the wrong hash, otherwise missing
+    pub corrupted: bool,
+    origin: Origin,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub checked: usize,
+    pub problems: Vec<Problem>,
+}
+
+struct Expected {
+    relative: String,
+    component: String,
+    origin: Origin,
+}
+
+fn relative_name(root: &Path, path: &Path) -> Option<String> {
+    Some(path.strip_prefix(root).ok()?.to_string_lossy().replace('\\', "/"))
+}
+
+fn hashes_path(installation_id: &str) -> PathBuf {
+    get_installations_dir().join(installation_id).join(HASHES_FILE)
+}
+
+fn load_hashes(installation_id: &str) -> HashMap<String, String> {
+    fs::read_to_string(hashes_path(installation_id))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn game_dir(installation: &Installation) -> Result<PathBuf, String> {
+    let launcher = get_launcher(&installation.launcher_type)?;
+    Ok(get_modpack_root(&launcher, &installation.id))
+}
+
+fn load_manifest(root: &Path) -> Result<Manifest, String> {
+    let path = root.join("manifest.json");
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn expected_files(root: &Path, manifest: &Manifest) -> Vec<Expected> {
+    let mut expected = Vec::new();
+    let components = manifest
+        .mods
+        .iter()
+        .map(|m| (m.path.as_deref(), &m.name, Origin::Mod))
+        .chain(manifest.shaderpacks.iter().map(|s| (s.path.as_deref(), &s.name, Origin::Shaderpack)))
+        .chain(manifest.resourcepacks.iter().map(|r| (r.path.as_deref(), &r.name, Origin::Resourcepack)));
+    for (path, name, origin) in components {
+        if let Some(relative) = path.and_then(|p| relative_name(root, p)) {
+            expected.push(Expected { relative, component: name.clone(), origin });
+        }
+    }
+    for (id, included) in manifest.included_files.iter().flatten() {
+        let remote = manifest.remote_include.iter().flatten().find(|r| &r.id == id);
+        let (component, origin) = match remote {
+            Some(remote) => (remote.name.clone().unwrap_or_else(|| id.clone()), Origin::RemoteInclude(id.clone())),
+            None => {
+                let include = manifest.include.iter().find(|i| &i.id == id);
+                (include.and_then(|i| i.name.clone()).unwrap_or_else(|| id.clone()), Origin::Include)
+            }
+        };
+        for file in &included.files {
+            if let Some(relative) = relative_name(root, Path::new(file)) {
+                expected.push(Expected { relative, component: component.clone(), origin: origin.clone() });
+            }
+        }
+    }
+    expected
+}
+
+/// Hashes the mods, shaderpacks and resourcepacks `manifest` lists, called after every install.
+pub fn record_hashes(installation_id: &str, root: &Path, manifest: &Manifest) -> Result<usize, String> {
+    let files: Vec<PathBuf> = expected_files(root, manifest)
+        .into_iter()
+        .filter(|e| matches!(e.origin, Origin::Mod | Origin::Shaderpack | Origin::Resourcepack))
+        .map(|e| root.join(e.relative))
+        .collect();
+    let hashes: HashMap<String, String> = hashing::hash_files(&files, HashAlgorithm::Sha256, |_, _| {})
+        .into_iter()
+        .filter_map(|(path, hash)| Some((relative_name(root, &path)?, hash.ok()?)))
+        .collect();
+    let path = hashes_path(installation_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&hashes).map_err(|e| format!("Failed to serialize file hashes: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    debug!("Recorded {} file hash(es) for {}", hashes.len(), installation_id);
+    Ok(hashes.len())
+}
+
+/// Checks the installed files, see the top of the file. Blocking, hashing takes a moment.
+pub fn check(installation: &Installation) -> Result<Report, String> {
+    let root = game_dir(installation)?;
+    let manifest = load_manifest(&root)?;
+    let hashes = load_hashes(&installation.id);
+    let expected = expected_files(&root, &manifest);
+
+    let hashed: HashMap<PathBuf, String> = expected
+        .iter()
+        .filter_map(|e| hashes.get(&e.relative).map(|hash| (PathBuf::from(&e.relative), hash.clone())))
+        .collect();
+    let result = hashing::verify_files(&root, &hashed, HashAlgorithm::Sha256, |_, _| {});
+    let normalize = |path: &Path| path.to_string_lossy().replace('\\', "/");
+    let corrupted: HashSet<String> = result
+        .mismatched
+        .iter()
+        .map(|p| normalize(p))
+        .chain(result.unreadable.iter().map(|(p, _)| normalize(p)))
+        .collect();
+
+    let mut problems: Vec<Problem> = expected
+        .iter()
+        .filter_map(|e| {
+            let is_corrupted = corrupted.contains(&e.relative);
+            let is_missing = !is_corrupted && !root.join(&e.relative).is_file();
+            (is_corrupted || is_missing).then(|| Problem {
+                relative: e.relative.clone(),
+                component: e.component.clone(),
+                corrupted: is_corrupted,
+                origin: e.origin.clone(),
+            })
+        })
+        .collect();
+    problems.sort_by(|a, b| a.relative.cmp(&b.relative));
+    problems.dedup_by(|a, b| a.relative == b.relative);
+    info!("Verified {}: {} file(s), {} problem(s)", installation.id, expected.len(), problems.len());
+    Ok(Report { checked: expected.len(), problems })
+}
+
+// Downloads the item of `items` installed at `relative` again, returns its index and new path
+async fn redownload<T: Downloadable + Sync>(
+    items: &[T],
+    relative: &str,
+    root: &Path,
+    loader_type: &str,
+    http_client: &CachedHttpClient,
+) -> Result<(usize, PathBuf), String> {
+    let (index, item) = items
+        .iter()
+        .enumerate()
+        .find(|(_, item)| item.get_path().as_deref().and_then(|p| relative_name(root, p)).as_deref() == Some(relative))
+        .ok_or_else(|| format!("{} isn't in the manifest anymore", relative))?;
+    let path = root.join(relative);
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    let (new_path, _) = crate::mirrors::download(item, root, loader_type, http_client).await.map_err(|e| e.to_string())?;
+    Ok((index, new_path))
+}
+
+async fn download_include_file(http_client: &CachedHttpClient, url: &str, target: &Path) -> Result<(), String> {
+    let mut response = http_client
+        .get_async(url)
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if response.status() != StatusCode::OK {
+        return Err(format!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+    let bytes = response.bytes().await.map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(target, bytes).map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+}
+
+/// Gets the files of `problems` back. Returns how many were fixed, fails with the ones that
+/// couldn't be.
+pub async fn repair(installation: &Installation, http_client: &CachedHttpClient, problems: &[Problem]) -> Result<usize, String> {
+    let root = game_dir(installation)?;
+    let mut manifest = load_manifest(&root)?;
+    let loader_type = manifest.loader.r#type.clone();
+    let pack_source = installation.pack_source();
+    let mut fixed = 0;
+    let mut failed = Vec::new();
+    let mut manifest_changed = false;
+    let mut remote_missing: HashMap<String, Vec<&Problem>> = HashMap::new();
+
+    for problem in problems {
+        let result = match &problem.origin {
+            Origin::Mod => redownload(&manifest.mods, &problem.relative, &root, &loader_type, http_client)
+                .await
+                .map(|(i, path)| manifest.mods[i].path.replace(path)),
+            Origin::Shaderpack => redownload(&manifest.shaderpacks, &problem.relative, &root, &loader_type, http_client)
+                .await
+                .map(|(i, path)| manifest.shaderpacks[i].path.replace(path)),
+            Origin::Resourcepack => redownload(&manifest.resourcepacks, &problem.relative, &root, &loader_type, http_client)
+                .await
+                .map(|(i, path)| manifest.resourcepacks[i].path.replace(path)),
+            // Missing means nothing of the player's is there to lose
+            Origin::Include if !problem.corrupted => {
+                download_include_file(http_client, &pack_source.file_url(&problem.relative), &root.join(&problem.relative))
+                    .await
+                    .map(|_| None)
+            }
+            Origin::RemoteInclude(id) if !problem.corrupted => {
+                remote_missing.entry(id.clone()).or_default().push(problem);
+                continue;
+            }
+            _ => {
+                debug!("Leaving {} alone, include files aren't hash checked", problem.relative);
+                continue;
+            }
+        };
+        match result {
+            Ok(previous) => {
+                // A new release of the file can come with a new name
+                if previous.is_some_and(|p| relative_name(&root, &p).as_deref() != Some(problem.relative.as_str())) {
+                    manifest_changed = true;
+                }
+                fixed += 1;
+            }
+            Err(e) => {
+                warn!("Failed to repair {}: {}", problem.relative, e);
+                failed.push(format!("{}: {}", problem.relative, e));
+            }
+        }
+    }
+
+    // One download per remote include, only the missing files are extracted from it
+    for (id, missing) in remote_missing {
+        let Some(remote) = manifest.remote_include.iter().flatten().find(|r| r.id == id) else {
+            failed.push(format!("{}: not in the manifest anymore", id));
+            continue;
+        };
+        let target = match &remote.path {
+            Some(path) => root.join(path),
+            None => root.clone(),
+        };
+        let only: HashSet<PathBuf> = missing
+            .iter()
+            .filter_map(|p| root.join(&p.relative).strip_prefix(&target).ok().map(Path::to_path_buf))
+            .collect();
+        let name = remote.name.clone().unwrap_or_else(|| remote.id.clone());
+        let cache_key = crate::remote_include_cache_key(&remote.location, &remote.version);
+        match crate::mirrors::download_zip(&name, http_client, &remote.location, &remote.mirrors, &target, &cache_key, Some(only)).await {
+            Ok((files, _)) => fixed += files.len(),
+            Err(e) => {
+                warn!("Failed to repair {}: {}", name, e);
+                failed.push(format!("{}: {}", name, e));
+            }
+        }
+    }
+
+    if manifest_changed {
+        let json = serde_json::to_string(&manifest).map_err(|e| format!("Failed to serialize manifest.json: {}", e))?;
+        fs::write(root.join("manifest.json"), json).map_err(|e| format!("Failed to save manifest.json: {}", e))?;
+    }
+    if let Err(e) = record_hashes(&installation.id, &root, &manifest) {
+        warn!("Failed to record file hashes after the repair: {}", e);
+    }
+    info!("Repaired {} file(s) of {}", fixed, installation.id);
+    crate::health::forget(&installation.id);
+    if failed.is_empty() {
+        Ok(fixed)
+    } else {
+        Err(format!("Repaired {} file(s), {} failed: {}", fixed, failed.len(), failed.join("; ")))
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: wynncraft-overhaul-installer verify --id <installation id> [--repair]");
+}
+
+/// `verify` subcommand. Exit code 0 when everything is fine (or got repaired), 1 when problems
+/// remain, 2 on errors.
+pub fn run(args: &[String]) -> i32 {
+    let mut id = None;
+    let mut repair_files = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--id" => id = iter.next().cloned(),
+            "--repair" => repair_files = true,
+            "-h" | "--help" => {
+                print_usage();
+                return 0;
+            }
+            other => {
+                eprintln!("Unknown argument '{}'", other);
+                print_usage();
+                return 2;
+            }
+        }
+    }
+    let Some(id) = id else {
+        print_usage();
+        return 2;
+    };
+
+    let (config, _) = crate::config_migration::load_config(&crate::get_installer_dir().join("config.json"));
+    crate::apply_config(&config);
+    let installation = match load_installation(&id) {
+        Ok(installation) => installation,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let report = match check(&installation) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    for problem in &report.problems {
+        let state = if problem.corrupted { "corrupted" } else { "missing" };
+        println!("{}\t{}\t{}", state, problem.relative, problem.component);
+    }
+    println!("{} file(s) checked, {} problem(s)", report.checked, report.problems.len());
+    if report.problems.is_empty() {
+        return 0;
+    }
+    if !repair_files {
+        return 1;
+    }
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 2;
+        }
+    };
+    let http_client = CachedHttpClient::new();
+    match runtime.block_on(repair(&installation, &http_client, &report.problems)) {
+        Ok(fixed) => {
+            println!("Repaired {} file(s)", fixed);
+            match check(&installation) {
+                Ok(report) if report.problems.is_empty() => 0,
+                Ok(report) => {
+                    println!("{} problem(s) left", report.problems.len());
+                    1
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    2
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
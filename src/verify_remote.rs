@@ -0,0 +1,459 @@
+use std::collections::{HashMap, HashSet};
+
+use futures::StreamExt;
+use isahc::AsyncReadResponseExt;
+use log::{debug, info};
+use serde::Serialize;
+
+use crate::universal::UniversalManifest;
+use crate::{CachedHttpClient, ModrinthObject, CONCURRENCY, DEFAULT_UNIVERSAL_URL};
+
+// Headless check of the published manifest, meant to be run by pack maintainers in CI
+// before tagging a release. Nothing is installed, every result ends up in the report.
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    // "manifest", "loader", "source", "include", "remote_include", "id", "dependency"
+    pub kind: String,
+    pub id: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub manifest_url: String,
+    pub modpack_version: Option<String>,
+    pub ok: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    pub checks: Vec<CheckResult>,
+}
+
+impl CheckResult {
+    pub(crate) fn new(kind: &str, id: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            id: id.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+// A single thing that needs a network round trip
+enum RemoteCheck {
+    Url { kind: &'static str, id: String, url: String },
+    Modrinth { id: String, project: String, version: String, r#type: &'static str },
+    CurseForge { id: String, project: String, file: String },
+}
+
+async fn check_url(http_client: &CachedHttpClient, kind: &str, id: &str, url: &str) -> CheckResult {
+    // HEAD first, some hosts (mediafire, github api) don't support it so fall back to GET
+    let head = isahc::Request::head(url).body(()).map_err(|e| e.to_string());
+    let status = match head {
+        Ok(request) => match http_client.http_client.send_async(request).await {
+            Ok(response) if response.status().is_success() => Some(response.status()),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+    let status = match status {
+        Some(status) => Ok(status),
+        None => http_client
+            .with_headers(url, &[])
+            .await
+            .map(|response| response.status())
+            .map_err(|e| e.to_string()),
+    };
+
+    match status {
+        Ok(status) if status.is_success() => CheckResult::new(kind, id, CheckStatus::Ok, url),
+        Ok(status) => CheckResult::new(kind, id, CheckStatus::Error, format!("{} returned {}", url, status)),
+        Err(e) => CheckResult::new(kind, id, CheckStatus::Error, format!("{} failed: {}", url, e)),
+    }
+}
+
+async fn check_modrinth(
+    http_client: &CachedHttpClient,
+    id: &str,
+    project: &str,
+    version: &str,
+    r#type: &str,
+    loader_type: &str,
+) -> CheckResult {
+    let url = format!("https://api.modrinth.com/v2/project/{}/version", project);
+    let mut response = match http_client.with_headers(url.as_str(), &[]).await {
+        Ok(response) => response,
+        Err(e) => return CheckResult::new("source", id, CheckStatus::Error, format!("{} failed: {}", url, e)),
+    };
+    if !response.status().is_success() {
+        return CheckResult::new(
+            "source",
+            id,
+            CheckStatus::Error,
+            format!("Modrinth project '{}' returned {}", project, response.status()),
+        );
+    }
+    let versions: Vec<ModrinthObject> = match response.text().await.map_err(|e| e.to_string()).and_then(|text| {
+        serde_json::from_str(&text).map_err(|e| e.to_string())
+    }) {
+        Ok(versions) => versions,
+        Err(e) => {
+            return CheckResult::new(
+                "source",
+                id,
+                CheckStatus::Error,
+                format!("Failed to parse Modrinth versions for '{}': {}", project, e),
+            )
+        }
+    };
+
+    // Same matching rules as download_from_modrinth
+    let found = versions.iter().any(|v| {
+        &v.version_number == version
+            && (v.loaders.iter().any(|l| l == "minecraft" || l == loader_type) || r#type == "shaderpack")
+            && !v.files.is_empty()
+    });
+    if found {
+        CheckResult::new("source", id, CheckStatus::Ok, format!("modrinth {}@{}", project, version))
+    } else {
+        CheckResult::new(
+            "source",
+            id,
+            CheckStatus::Error,
+            format!("Modrinth project '{}' has no version '{}' for {}", project, version, loader_type),
+        )
+    }
+}
+
+fn loader_url(manifest: &UniversalManifest) -> Option<String> {
+    let loader = &manifest.loader;
+    match loader.r#type.as_str() {
+        "fabric" => Some(format!(
+            "https://meta.fabricmc.net/v2/versions/loader/{}/{}/profile/json",
+            loader.minecraft_version, loader.version
+        )),
+        "quilt" => Some(format!(
+            "https://meta.quiltmc.org/v3/versions/loader/{}/{}/profile/json",
+            loader.minecraft_version, loader.version
+        )),
+        other => crate::forge::installer_url(other, &loader.minecraft_version, &loader.version),
+    }
+}
+
+async fn check_curseforge(http_client: &CachedHttpClient, id: &str, project: &str, file: &str) -> CheckResult {
+    let Some(api_key) = crate::curseforge::api_key() else {
+        return CheckResult::new(
+            "source",
+            id,
+            CheckStatus::Warning,
+            format!("Skipped CurseForge file {}/{}, {} is not set", project, file, crate::curseforge::API_KEY_ENV),
+        );
+    };
+    let url = crate::curseforge::file_url(project, file);
+    let mut response = match http_client
+        .with_headers(url.as_str(), &[("x-api-key", api_key.as_str()), ("Accept", "application/json")])
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return CheckResult::new("source", id, CheckStatus::Error, format!("{} failed: {}", url, e)),
+    };
+    if !response.status().is_success() {
+        return CheckResult::new(
+            "source",
+            id,
+            CheckStatus::Error,
+            format!("CurseForge file {}/{} returned {}", project, file, response.status()),
+        );
+    }
+    let parsed = response
+        .text()
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|text| crate::curseforge::parse_file(&text).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(info) if info.download_url.is_some() => {
+            CheckResult::new("source", id, CheckStatus::Ok, format!("curseforge {}/{}", project, file))
+        }
+        // Installs still work, users just have to download the file by hand
+        Ok(info) => CheckResult::new(
+            "source",
+            id,
+            CheckStatus::Warning,
+            format!("'{}' can't be downloaded by third party tools, users will have to download it manually", info.file_name),
+        ),
+        Err(e) => CheckResult::new(
+            "source",
+            id,
+            CheckStatus::Error,
+            format!("Failed to parse CurseForge file {}/{}: {}", project, file, e),
+        ),
+    }
+}
+
+// Checks that don't need the network: id collisions and dependency references
+fn check_structure(manifest: &UniversalManifest) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    let mut seen: HashMap<&str, &str> = HashMap::new();
+    let mut ids = Vec::new();
+
+    let components = manifest
+        .mods
+        .iter()
+        .map(|c| ("mod", c.id.as_str(), c.dependencies.as_ref(), c.incompatibilities.as_ref()))
+        .chain(manifest.shaderpacks.iter().map(|c| ("shaderpack", c.id.as_str(), c.dependencies.as_ref(), c.incompatibilities.as_ref())))
+        .chain(manifest.resourcepacks.iter().map(|c| ("resourcepack", c.id.as_str(), c.dependencies.as_ref(), c.incompatibilities.as_ref())))
+        .chain(manifest.include.iter().map(|c| ("include", c.id.as_str(), c.dependencies.as_ref(), None)))
+        .chain(manifest.remote_include.iter().map(|c| ("remote_include", c.id.as_str(), c.dependencies.as_ref(), None)))
+        .collect::<Vec<_>>();
+
+    for (kind, id, _, _) in &components {
+        // Empty and "default" ids are shared on purpose
+        if id.is_empty() || *id == "default" {
+            continue;
+        }
+        if let Some(previous) = seen.insert(*id, *kind) {
+            results.push(CheckResult::new(
+                "id",
+                id,
+                CheckStatus::Error,
+                format!("Id '{}' is used by both a {} and a {}", id, previous, kind),
+            ));
+        }
+        ids.push(*id);
+    }
+
+    let known: HashSet<&str> = ids.into_iter().chain(["default"]).collect();
+    for (_, id, dependencies, incompatibilities) in &components {
+        for dependency in dependencies.iter().flat_map(|d| d.iter()) {
+            if !known.contains(dependency.as_str()) {
+                results.push(CheckResult::new(
+                    "dependency",
+                    id,
+                    CheckStatus::Error,
+                    format!("'{}' depends on unknown component '{}'", id, dependency),
+                ));
+            }
+        }
+        for incompatible in incompatibilities.iter().flat_map(|i| i.iter()) {
+            if !known.contains(incompatible.as_str()) {
+                results.push(CheckResult::new(
+                    "dependency",
+                    id,
+                    CheckStatus::Warning,
+                    format!("'{}' lists unknown incompatibility '{}'", id, incompatible),
+                ));
+            }
+        }
+    }
+    for component in manifest.mods.iter().chain(manifest.shaderpacks.iter()).chain(manifest.resourcepacks.iter()) {
+        for other in component.conflicts_with.iter().chain(component.requires_any.iter()) {
+            if !known.contains(other.as_str()) {
+                results.push(CheckResult::new(
+                    "dependency",
+                    &component.id,
+                    CheckStatus::Warning,
+                    format!("'{}' refers to unknown component '{}'", component.id, other),
+                ));
+            }
+        }
+        if component.requires_any.iter().any(|option| component.conflicts().any(|c| c == option)) {
+            results.push(CheckResult::new(
+                "dependency",
+                &component.id,
+                CheckStatus::Error,
+                format!("'{}' conflicts with one of its requires_any options", component.id),
+            ));
+        }
+    }
+
+    results
+}
+
+fn remote_checks(manifest: &UniversalManifest) -> Vec<RemoteCheck> {
+    let mut checks = Vec::new();
+    let items = manifest
+        .mods
+        .iter()
+        .map(|c| (c, "mod"))
+        .chain(manifest.shaderpacks.iter().map(|c| (c, "shaderpack")))
+        .chain(manifest.resourcepacks.iter().map(|c| (c, "resourcepack")));
+
+    for (component, r#type) in items {
+        match component.source.as_str() {
+            "modrinth" => checks.push(RemoteCheck::Modrinth {
+                id: component.id.clone(),
+                project: component.location.clone(),
+                version: component.version.clone(),
+                r#type,
+            }),
+            "curseforge" => checks.push(RemoteCheck::CurseForge {
+                id: component.id.clone(),
+                project: component.location.clone(),
+                file: component.version.clone(),
+            }),
+            // ddl, mediafire and anything else we can at least reach
+            _ => checks.push(RemoteCheck::Url {
+                kind: "source",
+                id: component.id.clone(),
+                url: component.location.clone(),
+            }),
+        }
+    }
+
+    for include in &manifest.include {
+        let is_file = include.location.contains('.') && !include.location.starts_with('.');
+        let url = if is_file {
+            format!("https://raw.githubusercontent.com/Wynncraft-Overhaul/majestic-overhaul/master/{}", include.location)
+        } else {
+            format!("https://api.github.com/repos/Wynncraft-Overhaul/majestic-overhaul/contents/{}", include.location)
+        };
+        checks.push(RemoteCheck::Url {
+            kind: "include",
+            id: if include.id.is_empty() { include.location.clone() } else { include.id.clone() },
+            url,
+        });
+    }
+
+    for remote in &manifest.remote_include {
+        checks.push(RemoteCheck::Url {
+            kind: "remote_include",
+            id: remote.id.clone(),
+            url: remote.location.clone(),
+        });
+    }
+
+    checks
+}
+
+pub async fn verify_remote(manifest_url: Option<&str>) -> VerifyReport {
+    let url = manifest_url.unwrap_or(DEFAULT_UNIVERSAL_URL).to_string();
+    let http_client = CachedHttpClient::new();
+    let mut checks = Vec::new();
+
+    info!("Verifying manifest {}", url);
+    let manifest = match crate::universal::load_universal_manifest(&http_client, Some(&url)).await {
+        Ok(manifest) => {
+            checks.push(CheckResult::new("manifest", "universal.json", CheckStatus::Ok, "Manifest parsed"));
+            Some(manifest)
+        }
+        Err(e) => {
+            checks.push(CheckResult::new("manifest", &e.file_name, CheckStatus::Error, e.message));
+            None
+        }
+    };
+
+    if let Some(manifest) = &manifest {
+        checks.extend(check_structure(manifest));
+
+        match loader_url(manifest) {
+            Some(loader) => checks.push(check_url(&http_client, "loader", &manifest.loader.r#type, &loader).await),
+            None => checks.push(CheckResult::new(
+                "loader",
+                &manifest.loader.r#type,
+                CheckStatus::Error,
+                format!("Unsupported loader '{}'", manifest.loader.r#type),
+            )),
+        }
+
+        let loader_type = manifest.loader.r#type.clone();
+        let remote = remote_checks(manifest);
+        debug!("Running {} remote checks", remote.len());
+        let results: Vec<CheckResult> = futures::stream::iter(remote)
+            .map(|check| {
+                let http_client = &http_client;
+                let loader_type = &loader_type;
+                async move {
+                    match check {
+                        RemoteCheck::Url { kind, id, url } => check_url(http_client, kind, &id, &url).await,
+                        RemoteCheck::Modrinth { id, project, version, r#type } => {
+                            check_modrinth(http_client, &id, &project, &version, r#type, loader_type).await
+                        }
+                        RemoteCheck::CurseForge { id, project, file } => {
+                            check_curseforge(http_client, &id, &project, &file).await
+                        }
+                    }
+                }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .collect()
+            .await;
+        checks.extend(results);
+    }
+
+    // Stable output order so CI diffs are readable
+    checks.sort_by(|a, b| (&a.kind, &a.id).cmp(&(&b.kind, &b.id)));
+    let errors = checks.iter().filter(|c| c.status == CheckStatus::Error).count();
+    let warnings = checks.iter().filter(|c| c.status == CheckStatus::Warning).count();
+
+    VerifyReport {
+        manifest_url: url,
+        modpack_version: manifest.map(|m| m.modpack_version),
+        ok: errors == 0,
+        errors,
+        warnings,
+        checks,
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: wynncraft-overhaul-installer verify-remote [--branch <name> | --manifest <url>]");
+}
+
+/// Entry point for `verify-remote`. Prints the report as JSON on stdout and returns the exit code.
+pub fn run(args: &[String]) -> i32 {
+    let mut manifest_url = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--manifest" => manifest_url = iter.next().cloned(),
+            "--branch" => {
+                manifest_url = iter.next().map(|b| crate::watch_mode::branch_manifest_url(&crate::sources::PackSource::default(), b))
+            }
+            "-h" | "--help" => {
+                print_usage();
+                return 0;
+            }
+            other => {
+                eprintln!("Unknown argument '{}'", other);
+                print_usage();
+                return 2;
+            }
+        }
+    }
+
+    // Only the environment variable here, CI has no installer config
+    crate::curseforge::configure(None);
+    crate::github::configure(None);
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 2;
+        }
+    };
+    let report = runtime.block_on(verify_remote(manifest_url.as_deref()));
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize report: {}", e);
+            return 2;
+        }
+    }
+    if report.ok {
+        0
+    } else {
+        1
+    }
+}
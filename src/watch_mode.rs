@@ -0,0 +1,160 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use isahc::AsyncReadResponseExt;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::sources::PackSource;
+use crate::universal::UniversalManifest;
+use crate::CachedHttpClient;
+
+// Tester mode: poll a branch and reinstall a test installation whenever its manifest changes
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default)]
+pub struct WatchSettings {
+    pub enabled: bool,
+    pub branch: String,
+    // Installation that gets updated automatically, never touch anything else
+    pub installation_id: Option<String>,
+    pub interval_secs: u64,
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            branch: String::from("master"),
+            installation_id: None,
+            interval_secs: 120,
+        }
+    }
+}
+
+pub const MIN_INTERVAL_SECS: u64 = 30;
+
+/// universal.json of `source` on another branch. Sources outside of GitHub have no branches, their
+/// manifest is the one watched.
+pub fn branch_manifest_url(source: &PackSource, branch: &str) -> String {
+    PackSource { branch: Some(branch.to_string()), ..source.clone() }.universal_url()
+}
+
+// Fetches the manifest bypassing the response cache, otherwise we'd never see changes
+async fn fetch_branch_manifest(
+    http_client: &CachedHttpClient,
+    url: &str,
+    branch: &str,
+) -> Result<(u64, UniversalManifest), String> {
+    let mut response = http_client
+        .with_headers(url, &[("Cache-Control", "no-cache")])
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Fetching {} returned {}", url, response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    let manifest: UniversalManifest = serde_json::from_str(&text)
+        .map_err(|e| format!("Failed to parse manifest from branch '{}': {}", branch, e))?;
+    crate::capabilities::check_manifest_capabilities(
+        manifest.manifest_version,
+        manifest.capabilities.as_ref(),
+    )?;
+    Ok((hasher.finish(), manifest))
+}
+
+pub fn notify(summary: &str, body: &str) {
+    if let Err(e) = notify_rust::Notification::new()
+        .summary(summary)
+        .body(body)
+        .appname("Majestic Overhaul Launcher")
+        .show()
+    {
+        warn!("Failed to show desktop notification: {}", e);
+    }
+}
+
+async fn reinstall(
+    http_client: &CachedHttpClient,
+    installation_id: &str,
+    branch: &str,
+    manifest: &UniversalManifest,
+) -> Result<(), String> {
+    let mut installation = crate::installation::load_installation(installation_id)?;
+    info!(
+        "Watch mode: updating '{}' to {} from branch '{}'",
+        installation.name, manifest.modpack_version, branch
+    );
+    installation
+        .install_or_update_with_manifest(http_client, manifest, branch, || {})
+        .await?;
+    installation.universal_version = manifest.modpack_version.clone();
+    installation.mark_installed()
+}
+
+/// Runs forever, re-reading the settings through `get_settings` every iteration
+/// so changes in the preferences screen apply without a restart.
+pub async fn run_watch_loop<G: Fn() -> Option<WatchSettings>>(get_settings: G) {
+    let http_client = CachedHttpClient::new();
+    let mut last_hash: Option<(String, u64)> = None;
+
+    loop {
+        let settings = get_settings();
+        let interval = settings
+            .as_ref()
+            .map_or(WatchSettings::default().interval_secs, |s| s.interval_secs)
+            .max(MIN_INTERVAL_SECS);
+
+        if let Some(settings) = settings.filter(|s| s.enabled) {
+            match settings.installation_id.as_deref() {
+                None => debug!("Watch mode enabled but no test installation selected"),
+                Some(installation_id) => match crate::installation::load_installation(installation_id) {
+                    Err(e) => warn!("Watch mode can't load the test installation: {}", e),
+                    Ok(installation) => {
+                        // The installation's own source, only the branch is the watched one
+                        let url = branch_manifest_url(&installation.pack_source(), &settings.branch);
+                        match fetch_branch_manifest(&http_client, &url, &settings.branch).await {
+                            Ok((hash, manifest)) => {
+                                let changed = match &last_hash {
+                                    Some((previous_url, previous)) => previous_url != &url || *previous != hash,
+                                    // First poll only establishes a baseline
+                                    None => false,
+                                };
+                                // Behind the PIN lock the change waits, the baseline stays so the
+                                // first poll after unlocking still sees it as new
+                                if changed && crate::lock::blocks_background_changes() {
+                                    info!("Watch mode: '{}' changed, waiting until installations are unlocked", settings.branch);
+                                } else {
+                                    if changed {
+                                        match reinstall(&http_client, installation_id, &settings.branch, &manifest).await {
+                                            Ok(_) => notify(
+                                                "Test installation updated",
+                                                &format!("Installed {} from branch '{}'", manifest.modpack_version, settings.branch),
+                                            ),
+                                            Err(e) => {
+                                                error!("Watch mode reinstall failed: {}", e);
+                                                notify("Test installation update failed", &e);
+                                            }
+                                        }
+                                    }
+                                    last_hash = Some((url, hash));
+                                }
+                            }
+                            Err(e) => warn!("Watch mode poll failed: {}", e),
+                        }
+                    }
+                },
+            }
+        } else {
+            last_hash = None;
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
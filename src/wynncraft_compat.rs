@@ -0,0 +1,129 @@
+use isahc::AsyncReadResponseExt;
+use log::{debug, warn};
+use regex::Regex;
+
+use crate::universal::UniversalManifest;
+use crate::CachedHttpClient;
+
+// Wynncraft announces every content update in its news feed. The newest version mentioned there is
+// compared against the `wynncraft_version` the pack was tested with, so players get a heads up when
+// the server updated and the pack hasn't caught up yet.
+const WYNNCRAFT_NEWS_URL: &str = "https://api.wynncraft.com/v3/latest-news";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompatWarning {
+    pub pack_version: String,
+    pub wynncraft_version: String,
+}
+
+impl CompatWarning {
+    pub fn message(&self) -> String {
+        format!(
+            "Pack not yet updated for Wynncraft {} (tested with {}) — expect issues until the next pack update.",
+            self.wynncraft_version, self.pack_version
+        )
+    }
+}
+
+fn parse_version(version: &str) -> Option<Vec<u32>> {
+    let parts: Vec<u32> = version
+        .trim()
+        .trim_start_matches(|c| c == 'v' || c == 'V')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts)
+    }
+}
+
+// Hotfixes (2.1.1) don't usually break mods, only major.minor counts as a new game version
+fn feature_version(version: &[u32]) -> (u32, u32) {
+    (version.first().copied().unwrap_or(0), version.get(1).copied().unwrap_or(0))
+}
+
+/// Every version announced in the news titles, e.g. "Wynncraft 2.1 - Rekindled World" or "v2.0.4 Update".
+fn announced_versions(titles: &[String]) -> Vec<String> {
+    let re = Regex::new(r"(?i)(?:wynncraft\s+v?(\d+\.\d+(?:\.\d+)?))|(?:\bv?(\d+\.\d+(?:\.\d+)?)\s+update\b)")
+        .expect("valid wynncraft version regex");
+    titles
+        .iter()
+        .flat_map(|title| re.captures_iter(title).collect::<Vec<_>>())
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+fn collect_titles(value: &serde_json::Value, titles: &mut Vec<String>) {
+    // The news endpoint has changed shape before (list vs keyed object), just look for titles anywhere
+    match value {
+        serde_json::Value::Array(items) => items.iter().for_each(|item| collect_titles(item, titles)),
+        serde_json::Value::Object(map) => {
+            if let Some(serde_json::Value::String(title)) = map.get("title") {
+                titles.push(title.clone());
+            }
+            map.values()
+                .filter(|v| v.is_array() || v.is_object())
+                .for_each(|v| collect_titles(v, titles));
+        }
+        _ => {}
+    }
+}
+
+pub async fn fetch_latest_wynncraft_version(http_client: &CachedHttpClient) -> Result<Option<String>, String> {
+    // Single attempt, a missing banner is better than a slow startup
+    let mut response = http_client
+        .with_headers(WYNNCRAFT_NEWS_URL, &[])
+        .await
+        .map_err(|e| format!("Failed to fetch Wynncraft news: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Wynncraft news request returned {}", response.status()));
+    }
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read Wynncraft news: {}", e))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&text).map_err(|e| format!("Failed to parse Wynncraft news: {}", e))?;
+
+    let mut titles = Vec::new();
+    collect_titles(&json, &mut titles);
+    let latest = announced_versions(&titles)
+        .into_iter()
+        .filter_map(|v| parse_version(&v).map(|parsed| (parsed, v)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, v)| v);
+    debug!("Latest announced Wynncraft version: {:?}", latest);
+    Ok(latest)
+}
+
+/// Some when Wynncraft announced a newer game version than the one the pack was tested with.
+pub fn compare(manifest: &UniversalManifest, latest: &str) -> Option<CompatWarning> {
+    let pack_version = manifest.wynncraft_version.as_ref()?;
+    let (Some(supported), Some(current)) = (parse_version(pack_version), parse_version(latest)) else {
+        warn!("Can't compare Wynncraft versions '{}' and '{}'", pack_version, latest);
+        return None;
+    };
+    if feature_version(&current) > feature_version(&supported) {
+        Some(CompatWarning {
+            pack_version: pack_version.clone(),
+            wynncraft_version: latest.to_string(),
+        })
+    } else {
+        None
+    }
+}
+
+pub async fn check(http_client: &CachedHttpClient, manifest: &UniversalManifest) -> Option<CompatWarning> {
+    // Older manifests don't say which Wynncraft version they target, nothing to compare
+    manifest.wynncraft_version.as_ref()?;
+    match fetch_latest_wynncraft_version(http_client).await {
+        Ok(Some(latest)) => compare(manifest, &latest),
+        Ok(None) => None,
+        Err(e) => {
+            debug!("Couldn't check the Wynncraft version: {}", e);
+            None
+        }
+    }
+}